@@ -0,0 +1,18 @@
+//! Color parsing, on arbitrary text. Whatever parses must print as the
+//! canonical hex form and parse back to the same color.
+
+#![no_main]
+
+use cyberdeck::color::Color;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let Ok(color) = text.parse::<Color>() else {
+        return;
+    };
+    let printed = color.to_string();
+    assert_eq!(printed.parse::<Color>().ok(), Some(color), "{printed}");
+    let _ = color.to_oklch();
+    let _ = color.to_xterm256();
+    let _ = color.hue_name();
+});
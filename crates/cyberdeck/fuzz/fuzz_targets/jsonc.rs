@@ -0,0 +1,26 @@
+//! The JSONC reader, on arbitrary text: the comment stripper, the
+//! comment-preserving tree and the theme model must reject bad input with
+//! an error, and a tree that parses must print as JSONC that parses again.
+
+#![no_main]
+
+use cyberdeck::jsonc;
+use cyberdeck::schema;
+use cyberdeck::theme::Theme;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let stripped = jsonc::strip(text);
+    assert_eq!(stripped.len(), text.len(), "stripping moved byte offsets");
+
+    let _ = Theme::parse(text);
+    let _ = schema::validate(text, None);
+    let _ = jsonc::line_column(text, text.len());
+
+    if let Ok(tree) = jsonc::parse_tree(text) {
+        let printed = tree.to_pretty_string();
+        if let Err(e) = jsonc::parse_tree(&printed) {
+            panic!("printed tree doesn't parse: {e}\n{printed}");
+        }
+    }
+});
@@ -0,0 +1,27 @@
+//! Scope selector parsing and matching, on arbitrary selectors and scope
+//! stacks. A parsed selector must print as one that parses back the same.
+
+#![no_main]
+
+use cyberdeck::scope::{scope_prefix, Selector};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, Vec<&str>)| {
+    let (text, stack) = input;
+    let Some(selector) = Selector::parse(text) else {
+        return;
+    };
+    assert_eq!(
+        Selector::parse(&selector.to_string()).as_ref(),
+        Some(&selector),
+        "selector doesn't round-trip"
+    );
+    if let Some((_, parents)) = selector.matches(&stack) {
+        assert!(parents < stack.len(), "matched more parents than ancestors");
+    }
+    for part in selector.parts() {
+        for scope in &stack {
+            let _ = scope_prefix(part, scope);
+        }
+    }
+});
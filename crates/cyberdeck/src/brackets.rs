@@ -0,0 +1,195 @@
+//! Bracket pair colorization: the `editorBracketHighlight.*` keys, how
+//! VS Code applies them, and a ramp of depth colors generated from the
+//! palette's accents.
+//!
+//! VS Code colors `()`, `[]` and `{}` outside strings and comments by
+//! nesting depth, cycling through the depth colors that are set and not
+//! fully transparent. A theme that sets none gets VS Code's gold, orchid
+//! and blue. Closing brackets without an opening one take the unexpected
+//! bracket color.
+
+use anyhow::{Context, Result};
+
+use crate::color::{Color, Oklch, MID_LUMINANCE, NEUTRAL_CHROMA};
+use crate::palette::Palette;
+use crate::theme::Theme;
+
+/// How many depth keys VS Code has.
+pub const DEPTHS: usize = 6;
+
+/// The key for unmatched closing brackets.
+pub const UNEXPECTED_KEY: &str = "editorBracketHighlight.unexpectedBracket.foreground";
+
+/// The pairs VS Code colorizes in most languages.
+pub const PAIRS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+/// Contrast generated depth colors keep against the background, the same
+/// as body text.
+pub const MIN_CONTRAST: f64 = 4.5;
+
+/// VS Code's colors when a theme sets no depth key.
+const DEFAULT_DEPTHS: [Color; 3] = [
+    Color::rgb(0xff, 0xd7, 0x00),
+    Color::rgb(0xda, 0x70, 0xd6),
+    Color::rgb(0x17, 0x9f, 0xff),
+];
+const DEFAULT_UNEXPECTED: Color = Color::rgba(0xff, 0x12, 0x12, 0xcc);
+
+/// How far in hue a palette color may sit from a ramp step and still be
+/// used for it, in degrees.
+const SNAP_HUE: f64 = 20.0;
+/// How far lightness moves per step while raising contrast.
+const LIGHTNESS_STEP: f64 = 0.02;
+
+/// The key for a 1-based depth.
+pub fn key(depth: usize) -> String {
+    format!("editorBracketHighlight.foreground{depth}")
+}
+
+/// The depth keys a theme sets, in order, `None` where unset.
+pub fn depth_keys(theme: &Theme) -> Vec<Option<Color>> {
+    (1..=DEPTHS)
+        .map(|depth| theme.colors.get(&key(depth)).and_then(|v| v.parse().ok()))
+        .collect()
+}
+
+/// The colors VS Code cycles through for a theme: the set depth keys that
+/// aren't fully transparent, or its defaults.
+pub fn cycle(theme: &Theme) -> Vec<Color> {
+    let colors: Vec<Color> = depth_keys(theme)
+        .into_iter()
+        .flatten()
+        .filter(|color| color.a > 0)
+        .collect();
+    if colors.is_empty() {
+        DEFAULT_DEPTHS.to_vec()
+    } else {
+        colors
+    }
+}
+
+/// The color of unmatched closing brackets.
+pub fn unexpected(theme: &Theme) -> Color {
+    theme
+        .colors
+        .get(UNEXPECTED_KEY)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_UNEXPECTED)
+}
+
+/// Tracks nesting across a document and colors each bracket.
+#[derive(Debug, Clone)]
+pub struct Colorizer {
+    colors: Vec<Color>,
+    unexpected: Color,
+    open: Vec<char>,
+}
+
+impl Colorizer {
+    pub fn new(theme: &Theme) -> Colorizer {
+        Colorizer {
+            colors: cycle(theme),
+            unexpected: unexpected(theme),
+            open: Vec::new(),
+        }
+    }
+
+    /// The color of `c` if it's a bracket, which also opens or closes a
+    /// level.
+    pub fn color(&mut self, c: char) -> Option<Color> {
+        if PAIRS.iter().any(|&(open, _)| open == c) {
+            let color = self.colors[self.open.len() % self.colors.len()];
+            self.open.push(c);
+            return Some(color);
+        }
+        let &(open, _) = PAIRS.iter().find(|&&(_, close)| close == c)?;
+        if self.open.last() == Some(&open) {
+            self.open.pop();
+            Some(self.colors[self.open.len() % self.colors.len()])
+        } else {
+            Some(self.unexpected)
+        }
+    }
+}
+
+/// Whether a token's brackets are colorized: not in strings or comments.
+pub fn colorizes(scopes: &[String]) -> bool {
+    !scopes
+        .iter()
+        .any(|scope| scope.starts_with("string") || scope.starts_with("comment"))
+}
+
+/// A depth color for each of [`DEPTHS`], from the palette's accents.
+///
+/// The ramp steps around the hue circle in equal turns from
+/// `accent.primary`, heading toward `accent.secondary`, at the accents'
+/// average lightness and chroma. A step takes the palette color nearest in
+/// hue when one is close enough and readable; otherwise it's mixed, with
+/// lightness raised (or lowered, on a light background) until it reaches
+/// [`MIN_CONTRAST`].
+pub fn generate(palette: &Palette) -> Result<Vec<Color>> {
+    let background = palette
+        .role("background")
+        .context("the palette has no `background` role")?
+        .hex
+        .opaque();
+    let accents: Vec<Oklch> = ["accent.primary", "accent.secondary", "accent.tertiary"]
+        .into_iter()
+        .filter_map(|role| palette.role(role))
+        .map(|color| color.hex.to_oklch())
+        .collect();
+    let primary = palette
+        .role("accent.primary")
+        .context("the palette has no `accent.primary` role")?
+        .hex
+        .to_oklch();
+    let direction = match accents.get(1) {
+        Some(secondary) if hue_difference(primary.h, secondary.h) < 0.0 => -1.0,
+        _ => 1.0,
+    };
+    let turn = direction * 360.0 / DEPTHS as f64;
+    let lightness = accents.iter().map(|c| c.l).sum::<f64>() / accents.len() as f64;
+    let chroma = accents.iter().map(|c| c.c).sum::<f64>() / accents.len() as f64;
+    let dark = background.relative_luminance() < MID_LUMINANCE;
+
+    let mut used: Vec<Color> = Vec::new();
+    for step in 0..DEPTHS {
+        let hue = (primary.h + turn * step as f64).rem_euclid(360.0);
+        let snapped = palette
+            .colors
+            .iter()
+            .map(|entry| entry.hex.opaque())
+            .filter(|color| !used.contains(color))
+            .filter(|color| color.contrast_ratio(background) >= MIN_CONTRAST)
+            .map(|color| (color, color.to_oklch()))
+            .filter(|(_, lch)| lch.c >= NEUTRAL_CHROMA)
+            .map(|(color, lch)| (color, hue_difference(hue, lch.h).abs()))
+            .filter(|&(_, distance)| distance <= SNAP_HUE)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(color, _)| color);
+        let color = snapped.unwrap_or_else(|| {
+            let mut lch = Oklch {
+                l: lightness,
+                c: chroma,
+                h: hue,
+            };
+            let mut color = Color::from_oklch(lch);
+            while color.contrast_ratio(background) < MIN_CONTRAST && (0.0..=1.0).contains(&lch.l) {
+                lch.l += if dark {
+                    LIGHTNESS_STEP
+                } else {
+                    -LIGHTNESS_STEP
+                };
+                color = Color::from_oklch(lch);
+            }
+            color
+        });
+        used.push(color);
+    }
+    Ok(used)
+}
+
+/// The signed shortest turn from hue `from` to hue `to`, in degrees.
+fn hue_difference(from: f64, to: f64) -> f64 {
+    (to - from + 540.0).rem_euclid(360.0) - 180.0
+}
@@ -0,0 +1,132 @@
+//! The command-line interface: the top-level parser and subcommand dispatch.
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+use crate::commands::artwork::{self, ArtworkArgs};
+use crate::commands::brackets::{self, BracketsArgs};
+use crate::commands::bump::{self, BumpArgs};
+use crate::commands::compare::{self, CompareArgs};
+use crate::commands::completions::{self, CompletionsArgs};
+use crate::commands::coverage::{self, CoverageArgs};
+use crate::commands::demos::{self, DemosArgs};
+use crate::commands::export::{self, ExportArgs};
+use crate::commands::fmt::{self, FmtArgs};
+use crate::commands::grammar::{self, GrammarArgs};
+use crate::commands::import::{self, ImportArgs};
+use crate::commands::lint::{self, LintArgs};
+use crate::commands::man::{self, ManArgs};
+use crate::commands::markdown::{self, MarkdownArgs};
+use crate::commands::parity::{self, ParityArgs};
+use crate::commands::playground::{self, PlaygroundArgs};
+use crate::commands::preview::{self, PreviewArgs};
+use crate::commands::render::{self, RenderArgs};
+use crate::commands::stats::{self, StatsArgs};
+use crate::commands::suggest::{self, SuggestArgs};
+use crate::commands::tokens::{self, TokensArgs};
+use crate::commands::validate::{self, ValidateArgs};
+use crate::config;
+
+/// Development tooling for the Cyberdeck 2025 VS Code theme.
+#[derive(Debug, Parser)]
+#[command(name = "cyberdeck", version, about)]
+pub struct Cli {
+    /// Apply a `[profile.<name>]` from cyberdeck.toml (also `CYBERDECK_PROFILE`).
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+impl Cli {
+    pub fn run(self) -> Result<()> {
+        if let Some(profile) = self.profile {
+            config::select_profile(profile);
+        }
+        self.command.run()
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Report palette usage, unused entries and hue/lightness distribution.
+    Stats(StatsArgs),
+    /// Check the themes against the configurable lint rules.
+    Lint(LintArgs),
+    /// Check theme files against VS Code's color-theme schema.
+    Validate(ValidateArgs),
+    /// Report which workbench colors fall back to VS Code's defaults, by
+    /// UI component.
+    Coverage(CoverageArgs),
+    /// Check that the theme variants differ from the primary one only in
+    /// their colors.
+    Parity(ParityArgs),
+    /// Rewrite theme files in canonical form (or verify with --check).
+    Fmt(FmtArgs),
+    /// Reverse-engineer a VS Code theme into a palette file.
+    Import(ImportArgs),
+    /// Export the theme to other editors' and tools' formats.
+    Export(ExportArgs),
+    /// Browse the language demos in a terminal UI, colored by the theme.
+    Preview(PreviewArgs),
+    /// Render highlighted code to files: a static HTML gallery, SVG and
+    /// PNG screenshots, variant animations, ANSI-colored text or
+    /// before/after comparisons.
+    Render(RenderArgs),
+    /// Build the browser playground: the renderer compiled to WebAssembly.
+    Playground(PlaygroundArgs),
+    /// Suggest palette colors for scopes the theme leaves unstyled.
+    Suggest(SuggestArgs),
+    /// Generate bracket pair colorization colors from the palette's
+    /// accents and preview them.
+    Brackets(BracketsArgs),
+    /// Render Markdown as VS Code's preview would under the theme, or
+    /// print the preview's stylesheet.
+    Markdown(MarkdownArgs),
+    /// Regenerate the social preview and marketplace banner from the
+    /// palette.
+    Artwork(ArtworkArgs),
+    /// Compare how other themes style the same scopes.
+    Compare(CompareArgs),
+    /// Bump the version everywhere, update the changelog, commit and tag.
+    Bump(BumpArgs),
+    /// Maintain the language demo corpus.
+    Demos(DemosArgs),
+    /// Inspect tokenization and resolved styles.
+    Tokens(TokensArgs),
+    /// Fetch and cache TextMate grammars the bundled set lacks.
+    Grammar(GrammarArgs),
+    /// Print a shell completion script.
+    Completions(CompletionsArgs),
+    /// Write man pages for every command.
+    Man(ManArgs),
+}
+
+impl Command {
+    pub fn run(self) -> Result<()> {
+        match self {
+            Command::Stats(args) => stats::run(args),
+            Command::Lint(args) => lint::run(args),
+            Command::Validate(args) => validate::run(args),
+            Command::Coverage(args) => coverage::run(args),
+            Command::Parity(args) => parity::run(args),
+            Command::Fmt(args) => fmt::run(args),
+            Command::Import(args) => import::run(args),
+            Command::Export(args) => export::run(args),
+            Command::Preview(args) => preview::run(args),
+            Command::Render(args) => render::run(args),
+            Command::Playground(args) => playground::run(args),
+            Command::Suggest(args) => suggest::run(args),
+            Command::Brackets(args) => brackets::run(args),
+            Command::Markdown(args) => markdown::run(args),
+            Command::Artwork(args) => artwork::run(args),
+            Command::Compare(args) => compare::run(args),
+            Command::Bump(args) => bump::run(args),
+            Command::Demos(args) => demos::run(args),
+            Command::Tokens(args) => tokens::run(args),
+            Command::Grammar(args) => grammar::run(args),
+            Command::Completions(args) => completions::run(args),
+            Command::Man(args) => man::run(args),
+        }
+    }
+}
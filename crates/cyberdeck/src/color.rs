@@ -0,0 +1,284 @@
+//! Color values as they appear in theme files, plus the conversions the
+//! tooling needs to reason about them (OKLab/OKLCH, luminance, compositing).
+
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// An 8-bit sRGB color with alpha, as written in VS Code theme files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseColorError {
+    #[error("color `{0}` must start with `#`")]
+    MissingHash(String),
+    #[error("color `{0}` must have 3, 4, 6 or 8 hex digits")]
+    BadLength(String),
+    #[error("color `{0}` contains a non-hex digit")]
+    BadDigit(String),
+}
+
+/// A color in the OKLab perceptual space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+/// A color in OKLCH: lightness `0..=1`, chroma, hue in degrees `0..360`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+}
+
+/// Chroma below which a color is treated as neutral (grey) rather than hued.
+pub const NEUTRAL_CHROMA: f64 = 0.035;
+
+/// Relative luminance of a perceptual mid grey (CIE L* 50); darker editor
+/// backgrounds make a dark theme.
+pub const MID_LUMINANCE: f64 = 0.184;
+
+impl Color {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b, a: 0xff }
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Color { r, g, b, a }
+    }
+
+    /// The same color with the alpha channel dropped.
+    pub fn opaque(self) -> Self {
+        Color { a: 0xff, ..self }
+    }
+
+    pub fn with_alpha(self, a: u8) -> Self {
+        Color { a, ..self }
+    }
+
+    pub fn is_opaque(self) -> bool {
+        self.a == 0xff
+    }
+
+    /// `#rrggbb`, ignoring alpha.
+    pub fn to_hex_rgb(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// Composites this color over `background` (which is treated as opaque).
+    pub fn over(self, background: Color) -> Color {
+        let alpha = f64::from(self.a) / 255.0;
+        let mix = |fg: u8, bg: u8| -> u8 {
+            (f64::from(fg) * alpha + f64::from(bg) * (1.0 - alpha)).round() as u8
+        };
+        Color::rgb(
+            mix(self.r, background.r),
+            mix(self.g, background.g),
+            mix(self.b, background.b),
+        )
+    }
+
+    /// WCAG 2.x relative luminance of the opaque color.
+    pub fn relative_luminance(self) -> f64 {
+        let [r, g, b] = self.linear_rgb();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// WCAG 2.x contrast ratio between two opaque colors (`1.0..=21.0`).
+    pub fn contrast_ratio(self, other: Color) -> f64 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        let (hi, lo) = if a > b { (a, b) } else { (b, a) };
+        (hi + 0.05) / (lo + 0.05)
+    }
+
+    fn linear_rgb(self) -> [f64; 3] {
+        [self.r, self.g, self.b].map(|c| srgb_to_linear(f64::from(c) / 255.0))
+    }
+
+    pub fn to_oklab(self) -> Oklab {
+        let [r, g, b] = self.linear_rgb();
+        let l = (0.412_221_470_8 * r + 0.536_332_536_3 * g + 0.051_445_992_9 * b).cbrt();
+        let m = (0.211_903_498_2 * r + 0.680_699_545_1 * g + 0.107_396_956_6 * b).cbrt();
+        let s = (0.088_302_461_9 * r + 0.281_718_837_6 * g + 0.629_978_700_5 * b).cbrt();
+        Oklab {
+            l: 0.210_454_255_3 * l + 0.793_617_785_0 * m - 0.004_072_046_8 * s,
+            a: 1.977_998_495_1 * l - 2.428_592_205_0 * m + 0.450_593_709_9 * s,
+            b: 0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766_0 * s,
+        }
+    }
+
+    pub fn to_oklch(self) -> Oklch {
+        self.to_oklab().to_oklch()
+    }
+
+    /// Converts back from OKLab, clamping out-of-gamut channels. The alpha
+    /// channel is opaque.
+    pub fn from_oklab(lab: Oklab) -> Color {
+        let l = (lab.l + 0.396_337_777_4 * lab.a + 0.215_803_757_3 * lab.b).powi(3);
+        let m = (lab.l - 0.105_561_345_8 * lab.a - 0.063_854_172_8 * lab.b).powi(3);
+        let s = (lab.l - 0.089_484_177_5 * lab.a - 1.291_485_548_0 * lab.b).powi(3);
+        let r = 4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s;
+        let g = -1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s;
+        let b = -0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s;
+        let channel = |v: f64| (linear_to_srgb(v.clamp(0.0, 1.0)) * 255.0).round() as u8;
+        Color::rgb(channel(r), channel(g), channel(b))
+    }
+
+    pub fn from_oklch(lch: Oklch) -> Color {
+        Color::from_oklab(lch.to_oklab())
+    }
+
+    /// The closest entry of the xterm 256-color palette, for terminals
+    /// without true color. Only the 6×6×6 cube and the grey ramp are
+    /// considered: the first sixteen entries are whatever the terminal's
+    /// own palette says they are.
+    pub fn to_xterm256(self) -> u8 {
+        let target = self.opaque().to_oklab();
+        (16..=255u8)
+            .min_by(|&a, &b| {
+                let distance = |i: u8| Color::from_xterm256(i).to_oklab().distance(target);
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap_or(16)
+    }
+
+    /// The standard RGB value of an xterm 256-color palette entry. The first
+    /// sixteen get the common xterm defaults.
+    pub fn from_xterm256(index: u8) -> Color {
+        const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        const SYSTEM: [u32; 16] = [
+            0x000000, 0x800000, 0x008000, 0x808000, 0x000080, 0x800080, 0x008080, 0xc0c0c0,
+            0x808080, 0xff0000, 0x00ff00, 0xffff00, 0x0000ff, 0xff00ff, 0x00ffff, 0xffffff,
+        ];
+        match index {
+            0..=15 => {
+                let [_, r, g, b] = SYSTEM[usize::from(index)].to_be_bytes();
+                Color::rgb(r, g, b)
+            }
+            16..=231 => {
+                let i = usize::from(index - 16);
+                Color::rgb(CUBE[i / 36], CUBE[i / 6 % 6], CUBE[i % 6])
+            }
+            _ => {
+                let v = 8 + 10 * (index - 232);
+                Color::rgb(v, v, v)
+            }
+        }
+    }
+
+    /// Coarse hue family name used in reports, based on the OKLCH hue angle.
+    pub fn hue_name(self) -> &'static str {
+        let lch = self.to_oklch();
+        if lch.c < NEUTRAL_CHROMA {
+            return "neutral";
+        }
+        match lch.h {
+            h if h < 15.0 => "magenta",
+            h if h < 45.0 => "red",
+            h if h < 75.0 => "orange",
+            h if h < 110.0 => "yellow",
+            h if h < 160.0 => "green",
+            h if h < 215.0 => "cyan",
+            h if h < 275.0 => "blue",
+            h if h < 320.0 => "purple",
+            _ => "magenta",
+        }
+    }
+}
+
+impl Oklab {
+    pub fn to_oklch(self) -> Oklch {
+        let c = (self.a * self.a + self.b * self.b).sqrt();
+        let h = self.b.atan2(self.a).to_degrees().rem_euclid(360.0);
+        Oklch { l: self.l, c, h }
+    }
+
+    /// Euclidean distance, a decent approximation of perceptual difference.
+    pub fn distance(self, other: Oklab) -> f64 {
+        ((self.l - other.l).powi(2) + (self.a - other.a).powi(2) + (self.b - other.b).powi(2))
+            .sqrt()
+    }
+}
+
+impl Oklch {
+    pub fn to_oklab(self) -> Oklab {
+        let h = self.h.to_radians();
+        Oklab {
+            l: self.l,
+            a: self.c * h.cos(),
+            b: self.c * h.sin(),
+        }
+    }
+}
+
+fn srgb_to_linear(c: f64) -> f64 {
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f64) -> f64 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s
+            .strip_prefix('#')
+            .ok_or_else(|| ParseColorError::MissingHash(s.to_owned()))?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseColorError::BadDigit(s.to_owned()));
+        }
+        let nibble = |i: usize| u8::from_str_radix(&digits[i..=i], 16).unwrap() * 0x11;
+        let byte = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).unwrap();
+        match digits.len() {
+            3 => Ok(Color::rgb(nibble(0), nibble(1), nibble(2))),
+            4 => Ok(Color::rgba(nibble(0), nibble(1), nibble(2), nibble(3))),
+            6 => Ok(Color::rgb(byte(0), byte(2), byte(4))),
+            8 => Ok(Color::rgba(byte(0), byte(2), byte(4), byte(6))),
+            _ => Err(ParseColorError::BadLength(s.to_owned())),
+        }
+    }
+}
+
+/// Lowercase `#rrggbb`, or `#rrggbbaa` when not fully opaque.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex_rgb())?;
+        if !self.is_opaque() {
+            write!(f, "{:02x}", self.a)?;
+        }
+        Ok(())
+    }
+}
+
+impl serde::Serialize for Color {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
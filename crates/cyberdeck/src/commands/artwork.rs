@@ -0,0 +1,110 @@
+//! `cyberdeck artwork`: regenerate the social preview and marketplace
+//! banner from the palette.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+
+use crate::export::Artifact;
+use crate::highlight::Grammars;
+use crate::project::Project;
+use crate::render::artwork::{self, Artwork};
+use crate::render::font::Font;
+use crate::render::screenshot::DEFAULT_ADVANCE;
+use crate::render::svg::{svg, Text};
+use crate::render::{png, Highlighted};
+
+use super::{load_palette, load_theme};
+
+#[derive(Debug, Args)]
+pub struct ArtworkArgs {
+    /// Theme file to draw with (defaults to the primary contributed theme).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+    /// Palette file for the accents and swatches.
+    #[arg(long)]
+    pub palette: Option<PathBuf>,
+    /// Monospace font file to embed in the SVGs and rasterize PNGs with;
+    /// without one only SVGs are written, with live text.
+    #[arg(long, value_name = "FILE")]
+    pub font: Option<PathBuf>,
+    /// Text under the name (defaults to the first sentence of the
+    /// `package.json` description).
+    #[arg(long)]
+    pub tagline: Option<String>,
+    /// Fail if any artwork is out of date instead of writing it.
+    #[arg(long)]
+    pub check: bool,
+    /// Output directory.
+    #[arg(short, long, default_value = DEFAULT_DIR)]
+    pub output: PathBuf,
+}
+
+const DEFAULT_DIR: &str = "screenshots";
+
+pub fn run(args: ArtworkArgs) -> Result<()> {
+    let (_, theme) = load_theme(args.theme.as_deref())?;
+    let (_, palette) = load_palette(args.palette.as_deref())?;
+    let tagline = match &args.tagline {
+        Some(tagline) => tagline.clone(),
+        None => Project::current()
+            .ok()
+            .and_then(|project| project.manifest.description)
+            .map(|description| first_sentence(&description).to_owned())
+            .unwrap_or_default(),
+    };
+    let font = args.font.as_deref().map(Font::load).transpose()?;
+    let advance = font
+        .as_ref()
+        .map_or(DEFAULT_ADVANCE, |font| font.advance(1.0));
+    let text = font.as_ref().map_or(Text::Live, Text::Embedded);
+    let document = Grammars::bundled().parse(Path::new(artwork::SAMPLE_NAME), artwork::SAMPLE)?;
+    let code = Highlighted::new(&theme, &document);
+
+    let mut artifacts = Vec::new();
+    for artwork in Artwork::ALL {
+        let scene = artwork.scene(&theme, &palette, &code, &tagline, advance);
+        artifacts.push(Artifact::new(
+            format!("{}.svg", artwork.name()),
+            svg(&scene, text),
+        ));
+        if let Some(font) = &font {
+            artifacts.push(Artifact::new(
+                format!("{}.png", artwork.name()),
+                png::png(&scene, font, 1.0)?,
+            ));
+        }
+    }
+
+    let mut stale = Vec::new();
+    for artifact in &artifacts {
+        let path = args.output.join(&artifact.path);
+        if fs::read(&path).is_ok_and(|existing| existing == artifact.contents) {
+            continue;
+        }
+        if args.check {
+            stale.push(path.display().to_string());
+            continue;
+        }
+        fs::create_dir_all(&args.output)
+            .with_context(|| format!("failed to create {}", args.output.display()))?;
+        fs::write(&path, &artifact.contents)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        println!("wrote {}", path.display());
+    }
+    if !stale.is_empty() {
+        bail!(
+            "{} artwork file(s) out of date; run `cyberdeck artwork`:\n  {}",
+            stale.len(),
+            stale.join("\n  ")
+        );
+    }
+    Ok(())
+}
+
+/// Up to and including the first full stop.
+fn first_sentence(text: &str) -> &str {
+    text.find(". ").map_or(text, |end| &text[..=end])
+}
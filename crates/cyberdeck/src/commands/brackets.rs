@@ -0,0 +1,187 @@
+//! `cyberdeck brackets`: design the bracket pair colorization colors.
+//!
+//! Compares the theme's `editorBracketHighlight.foreground1..6` with a
+//! ramp generated from the palette's accents, prints the generated keys as
+//! `colors` entries, and optionally renders a nested sample under both.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use serde::Serialize;
+
+use crate::brackets::{self, DEPTHS, MIN_CONTRAST};
+use crate::color::Color;
+use crate::export::theme_name;
+use crate::highlight::Grammars;
+use crate::render::font::Font;
+use crate::render::screenshot::{Screenshot, DEFAULT_ADVANCE};
+use crate::render::svg::{svg, Text};
+use crate::render::{png, Highlighted};
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{load_palette, load_theme, print_json};
+
+#[derive(Debug, Args)]
+pub struct BracketsArgs {
+    /// Theme file to compare (defaults to the primary contributed theme).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+    /// Palette file to generate from.
+    #[arg(long)]
+    pub palette: Option<PathBuf>,
+    /// Only print the `colors` entries.
+    #[arg(long, conflicts_with = "json")]
+    pub entries: bool,
+    /// Print JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+    /// Render a deeply nested sample with the current colors beside the
+    /// generated ones, as SVG, or PNG by the extension (which needs
+    /// `--font`).
+    #[arg(long, value_name = "FILE")]
+    pub preview: Option<PathBuf>,
+    /// Monospace font file to embed in the SVG or rasterize the PNG with.
+    #[arg(long, value_name = "FILE")]
+    pub font: Option<PathBuf>,
+}
+
+/// Nests past every depth color, with brackets in a string and a comment
+/// that stay uncolored and an unmatched closing bracket at the end.
+const SAMPLE: &str = r#"function layout(items, scale) {
+  return items.map((item) => ({
+    id: item.id,
+    tags: [...(item.tags ?? [])].filter((tag) => [tag].length > 0),
+    size: { box: [item.width, (item.height * (scale ?? [1][0]))] },
+    label: `(${item.name}) [${item.kind}]`,
+  }));
+}
+// (comment) [brackets] {stay} uncolored
+}]"#;
+const SAMPLE_NAME: &str = "brackets.js";
+
+/// The `--json` schema.
+#[derive(Debug, Serialize)]
+struct BracketsReport {
+    theme: String,
+    palette: String,
+    depths: Vec<DepthJson>,
+}
+
+#[derive(Debug, Serialize)]
+struct DepthJson {
+    depth: usize,
+    key: String,
+    current: Option<Color>,
+    generated: Color,
+    /// Of the generated color against the editor background.
+    contrast: f64,
+}
+
+pub fn run(args: BracketsArgs) -> Result<()> {
+    let (theme_path, theme) = load_theme(args.theme.as_deref())?;
+    let (palette_path, palette) = load_palette(args.palette.as_deref())?;
+    let generated = brackets::generate(&palette)?;
+    let current = brackets::depth_keys(&theme);
+    let background = Resolver::new(&theme)
+        .defaults()
+        .background
+        .unwrap_or(Color::rgb(0, 0, 0))
+        .opaque();
+
+    if let Some(path) = &args.preview {
+        preview(&args, path, &theme, &generated)?;
+    }
+
+    if args.json {
+        let depths = generated
+            .iter()
+            .zip(&current)
+            .enumerate()
+            .map(|(index, (generated, current))| DepthJson {
+                depth: index + 1,
+                key: brackets::key(index + 1),
+                current: *current,
+                generated: *generated,
+                contrast: round(generated.contrast_ratio(background)),
+            })
+            .collect();
+        return print_json(&BracketsReport {
+            theme: theme_path.display().to_string(),
+            palette: palette_path.display().to_string(),
+            depths,
+        });
+    }
+
+    if !args.entries {
+        println!("Theme:   {}", theme_path.display());
+        println!("Palette: {}", palette_path.display());
+        println!();
+        println!("depth  current    generated  contrast");
+        for (index, (generated, current)) in generated.iter().zip(&current).enumerate() {
+            let contrast = generated.contrast_ratio(background);
+            println!(
+                "{:<6} {:<10} {:<10} {:>5.2}{}  {}",
+                index + 1,
+                current.map_or_else(|| "unset".to_owned(), |c| c.to_string()),
+                generated.to_string(),
+                contrast,
+                if contrast < MIN_CONTRAST { "!" } else { " " },
+                generated.hue_name(),
+            );
+        }
+        println!();
+        println!("colors entries:");
+        println!();
+    }
+    for (index, color) in generated.iter().enumerate() {
+        println!("    \"{}\": \"{color}\",", brackets::key(index + 1));
+    }
+    Ok(())
+}
+
+/// Writes the sample under the theme's colors beside the generated ones.
+fn preview(args: &BracketsArgs, path: &Path, theme: &Theme, generated: &[Color]) -> Result<()> {
+    let document = Grammars::bundled().parse(Path::new(SAMPLE_NAME), SAMPLE)?;
+    let mut proposed = theme.clone();
+    for (index, color) in generated.iter().enumerate().take(DEPTHS) {
+        proposed
+            .colors
+            .insert(brackets::key(index + 1), color.to_string());
+    }
+    let font = args.font.as_deref().map(Font::load).transpose()?;
+    let settings = Screenshot {
+        advance: font
+            .as_ref()
+            .map_or(DEFAULT_ADVANCE, |font| font.advance(1.0)),
+        line_numbers: true,
+        ..Screenshot::default()
+    };
+    let scene = |theme: &Theme, label: &str| {
+        let code = Highlighted::with_bracket_pairs(theme, &document);
+        Screenshot {
+            title: Some(format!("{SAMPLE_NAME} · {label}")),
+            ..settings.clone()
+        }
+        .scene(theme, &code)
+    };
+    let pair = scene(theme, theme_name(theme)).beside(scene(&proposed, "generated"));
+    let is_png = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("png"));
+    let contents = match &font {
+        Some(font) if is_png => png::png(&pair, font, 2.0)?,
+        None if is_png => bail!("a PNG preview needs --font FILE"),
+        Some(font) => svg(&pair, Text::Embedded(font)).into_bytes(),
+        None => svg(&pair, Text::Live).into_bytes(),
+    };
+    fs::write(path, contents).with_context(|| format!("failed to write {}", path.display()))?;
+    eprintln!("wrote {}", path.display());
+    Ok(())
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
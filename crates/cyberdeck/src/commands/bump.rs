@@ -0,0 +1,332 @@
+//! `cyberdeck bump`: set a new version everywhere it is recorded, add a
+//! changelog section derived from the theme changes since the last tag, then
+//! commit and tag the release.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use serde_json::Value;
+
+use crate::diff::ThemeDiff;
+use crate::git;
+use crate::project::Project;
+use crate::theme::Theme;
+
+#[derive(Debug, Args)]
+pub struct BumpArgs {
+    /// `patch`, `minor`, `major` or an explicit `X.Y.Z`.
+    pub version: String,
+    /// Show the changes without writing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Update the files but do not commit or tag.
+    #[arg(long)]
+    pub no_commit: bool,
+    /// Commit but do not create the tag.
+    #[arg(long, conflicts_with = "no_commit")]
+    pub no_tag: bool,
+    /// Proceed even if the working tree has uncommitted changes.
+    #[arg(long)]
+    pub allow_dirty: bool,
+}
+
+const CHANGELOG: &str = "CHANGELOG.md";
+/// Crate manifests whose `[package] version` follows the theme's.
+const CRATE_MANIFESTS: &[&str] = &[
+    "crates/cyberdeck/Cargo.toml",
+    "crates/playground/Cargo.toml",
+];
+
+/// JSON files carrying the version, with the JSON pointers to update.
+const JSON_VERSIONS: &[(&str, &[&str])] = &[
+    ("package.json", &["/version"]),
+    ("package-lock.json", &["/version", "/packages//version"]),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+}
+
+impl Version {
+    fn parse(text: &str) -> Option<Version> {
+        let mut parts = text.split('.').map(|p| p.parse().ok());
+        let version = Version {
+            major: parts.next()??,
+            minor: parts.next()??,
+            patch: parts.next()??,
+        };
+        parts.next().is_none().then_some(version)
+    }
+
+    fn bump(self, how: &str) -> Result<Version> {
+        Ok(match how {
+            "major" => Version {
+                major: self.major + 1,
+                minor: 0,
+                patch: 0,
+            },
+            "minor" => Version {
+                minor: self.minor + 1,
+                patch: 0,
+                ..self
+            },
+            "patch" => Version {
+                patch: self.patch + 1,
+                ..self
+            },
+            explicit => {
+                let version = Version::parse(explicit).with_context(|| {
+                    format!("`{explicit}` is not patch, minor, major or a X.Y.Z version")
+                })?;
+                if version <= self {
+                    bail!("{version} is not newer than the current version {self}");
+                }
+                version
+            }
+        })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+pub fn run(args: BumpArgs) -> Result<()> {
+    let project = Project::current()?;
+    let root = &project.root;
+    if !args.allow_dirty && !args.dry_run && !git::is_clean(root)? {
+        bail!("the working tree has uncommitted changes; commit them or pass --allow-dirty");
+    }
+
+    let current = Version::parse(&project.manifest.version).with_context(|| {
+        format!(
+            "package.json version `{}` is not X.Y.Z",
+            project.manifest.version
+        )
+    })?;
+    let next = current.bump(&args.version)?;
+    let tag = git::last_tag(root)?;
+    let section = changelog_section(&project, tag.as_deref(), next)?;
+
+    let mut edits: Vec<(&str, String)> = Vec::new();
+    for &(file, pointers) in JSON_VERSIONS {
+        if let Some(text) = read_optional(&root.join(file))? {
+            edits.push((file, set_json_version(&text, pointers, next)?));
+        }
+    }
+    for &manifest in CRATE_MANIFESTS {
+        let cargo = fs::read_to_string(root.join(manifest))
+            .with_context(|| format!("failed to read {manifest}"))?;
+        edits.push((manifest, set_cargo_version(&cargo, next).context(manifest)?));
+    }
+    let changelog = read_optional(&root.join(CHANGELOG))?.unwrap_or_default();
+    edits.push((CHANGELOG, insert_section(&changelog, &section)));
+
+    println!("{current} → {next}");
+    for (file, _) in &edits {
+        println!("  update {file}");
+    }
+    println!();
+    print!("{section}");
+    if args.dry_run {
+        return Ok(());
+    }
+
+    for (file, contents) in &edits {
+        let path = root.join(file);
+        fs::write(&path, contents)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    verify(&project, next)?;
+
+    if args.no_commit {
+        return Ok(());
+    }
+    let mut add = vec!["add", "--"];
+    add.extend(edits.iter().map(|(file, _)| *file));
+    git::git(root, &add)?;
+    git::git(
+        root,
+        &["commit", "-m", &format!("chore: bump version to {next}")],
+    )?;
+    if !args.no_tag {
+        git::git(root, &["tag", &next.to_string()])?;
+        println!();
+        println!("Tagged {next}; push with `git push origin HEAD {next}`.");
+    }
+    Ok(())
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    fs::read_to_string(path)
+        .map(Some)
+        .with_context(|| format!("failed to read {}", path.display()))
+}
+
+/// Rewrites the version at each pointer. npm writes two-space JSON, which
+/// serde_json reproduces byte for byte.
+fn set_json_version(text: &str, pointers: &[&str], version: Version) -> Result<String> {
+    let mut value: Value = serde_json::from_str(text)?;
+    for pointer in pointers {
+        if let Some(slot) = value.pointer_mut(pointer) {
+            *slot = Value::String(version.to_string());
+        }
+    }
+    Ok(serde_json::to_string_pretty(&value)? + "\n")
+}
+
+/// Rewrites the `version` line of the `[package]` table.
+fn set_cargo_version(text: &str, version: Version) -> Result<String> {
+    let mut in_package = false;
+    let mut found = false;
+    let mut out = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_package = trimmed == "[package]";
+        }
+        if in_package && !found && trimmed.starts_with("version") && trimmed.contains('=') {
+            out.push_str(&format!("version = \"{version}\"\n"));
+            found = true;
+        } else {
+            out.push_str(line);
+        }
+    }
+    if !found {
+        bail!("no [package] version");
+    }
+    Ok(out)
+}
+
+/// Re-reads every versioned file and fails unless they all agree.
+fn verify(project: &Project, version: Version) -> Result<()> {
+    let expected = version.to_string();
+    let mut mismatched = Vec::new();
+    for &(file, pointers) in JSON_VERSIONS {
+        let Some(text) = read_optional(&project.root.join(file))? else {
+            continue;
+        };
+        let value: Value = serde_json::from_str(&text)?;
+        for pointer in pointers {
+            if let Some(found) = value.pointer(pointer).and_then(Value::as_str) {
+                if found != expected {
+                    mismatched.push(format!("{file} {pointer} = {found}"));
+                }
+            }
+        }
+    }
+    for &manifest in CRATE_MANIFESTS {
+        let cargo: toml::Table = toml::from_str(&fs::read_to_string(project.root.join(manifest))?)?;
+        let found = cargo
+            .get("package")
+            .and_then(|p| p.get("version"))
+            .and_then(|v| v.as_str());
+        if found != Some(expected.as_str()) {
+            mismatched.push(format!("{manifest} version = {found:?}"));
+        }
+    }
+    if !mismatched.is_empty() {
+        bail!(
+            "versions disagree after the bump:\n  {}",
+            mismatched.join("\n  ")
+        );
+    }
+    Ok(())
+}
+
+/// A Keep a Changelog section listing the theme changes since `tag`.
+fn changelog_section(project: &Project, tag: Option<&str>, version: Version) -> Result<String> {
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    let mut removed = Vec::new();
+    let entries = project.themes();
+    for entry in entries {
+        let Some(tag) = tag else {
+            break;
+        };
+        let relative = entry.path.trim_start_matches("./");
+        let old = match git::show(&project.root, tag, relative)? {
+            Some(text) => Theme::parse(&text)
+                .with_context(|| format!("failed to parse {relative} at {tag}"))?,
+            None => Theme::default(),
+        };
+        let new = project.load_theme(entry)?;
+        let diff = ThemeDiff::between(&old, &new);
+        let prefix = if entries.len() > 1 {
+            format!("{}: ", entry.label)
+        } else {
+            String::new()
+        };
+        for (section, change) in diff.changes() {
+            let key = format!("{prefix}`{section}` `{}`", change.key);
+            match (&change.old, &change.new) {
+                (None, Some(new)) => added.push(format!("- {key}: `{new}`")),
+                (Some(old), None) => removed.push(format!("- {key} (was `{old}`)")),
+                (Some(old), Some(new)) => changed.push(format!("- {key}: `{old}` → `{new}`")),
+                (None, None) => {}
+            }
+        }
+    }
+
+    let mut section = format!("## [{version}] - {}\n\n", today());
+    if tag.is_none() {
+        section.push_str("- First tagged release\n\n");
+    } else if added.is_empty() && changed.is_empty() && removed.is_empty() {
+        section.push_str("- No theme color changes\n\n");
+    }
+    for (heading, lines) in [("Added", added), ("Changed", changed), ("Removed", removed)] {
+        if !lines.is_empty() {
+            section.push_str(&format!("### {heading}\n"));
+            for line in lines {
+                section.push_str(&line);
+                section.push('\n');
+            }
+            section.push('\n');
+        }
+    }
+    Ok(section)
+}
+
+/// Inserts `section` above the newest release heading.
+fn insert_section(changelog: &str, section: &str) -> String {
+    match changelog.find("\n## ") {
+        Some(index) => format!(
+            "{}{}{}",
+            &changelog[..=index],
+            section,
+            &changelog[index + 1..]
+        ),
+        None if changelog.is_empty() => format!("# Change Log\n\n{section}"),
+        None => format!("{}\n\n{section}", changelog.trim_end()),
+    }
+}
+
+/// Today's UTC date as `YYYY-MM-DD`.
+fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() / 86_400) as i64;
+    // Howard Hinnant's days-to-civil algorithm.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,345 @@
+//! `cyberdeck compare`: how other themes style the same scopes, and where
+//! they have dedicated rules the theme lacks.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+
+use crate::color::Color;
+use crate::import::{self, vsix};
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{load_theme, print_json};
+
+#[derive(Debug, Args)]
+pub struct CompareArgs {
+    /// Other theme files to compare against: VS Code themes
+    /// (`*-color-theme.json`), or anything `import` converts by extension
+    /// (`.tmTheme`, `.sublime-color-scheme`, `.icls`, Helix `.toml`, Neovim
+    /// `.lua`/`.vim`). A `.vsix` or an extension ID (`publisher.name`,
+    /// `marketplace:publisher.name@version`) adds every theme it
+    /// contributes.
+    #[arg(required = true)]
+    pub others: Vec<PathBuf>,
+    /// Theme file to compare (defaults to the primary contributed theme).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+    /// Only print the categories with gaps.
+    #[arg(long)]
+    pub gaps_only: bool,
+    /// Print JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+const COLUMN_WIDTH: usize = 30;
+
+/// The scope set every theme is resolved against, by category.
+const SCOPES: &[(&str, &[&str])] = &[
+    (
+        "Comments",
+        &[
+            "comment.line",
+            "comment.block",
+            "comment.block.documentation",
+            "punctuation.definition.comment",
+        ],
+    ),
+    (
+        "Strings",
+        &[
+            "string.quoted.double",
+            "string.quoted.single",
+            "string.template",
+            "string.regexp",
+            "constant.character.escape",
+            "punctuation.definition.string",
+        ],
+    ),
+    (
+        "Literals",
+        &[
+            "constant.numeric",
+            "constant.language",
+            "constant.language.boolean",
+            "constant.other",
+            "variable.other.constant",
+        ],
+    ),
+    (
+        "Keywords",
+        &[
+            "keyword.control",
+            "keyword.control.import",
+            "keyword.operator",
+            "keyword.operator.arithmetic",
+            "keyword.operator.logical",
+            "keyword.operator.assignment",
+            "keyword.other",
+            "storage.type",
+            "storage.modifier",
+        ],
+    ),
+    (
+        "Functions",
+        &[
+            "entity.name.function",
+            "meta.function-call",
+            "support.function",
+            "support.function.builtin",
+            "entity.name.function.macro",
+        ],
+    ),
+    (
+        "Types",
+        &[
+            "entity.name.type",
+            "entity.name.class",
+            "entity.other.inherited-class",
+            "entity.name.namespace",
+            "support.type",
+            "support.class",
+            "storage.type.primitive",
+        ],
+    ),
+    (
+        "Variables",
+        &[
+            "variable",
+            "variable.other",
+            "variable.parameter",
+            "variable.language",
+            "variable.other.property",
+            "variable.other.member",
+            "support.variable",
+        ],
+    ),
+    (
+        "Punctuation",
+        &[
+            "punctuation",
+            "punctuation.separator",
+            "punctuation.terminator",
+            "punctuation.section",
+            "punctuation.accessor",
+        ],
+    ),
+    (
+        "Markup",
+        &[
+            "markup.heading",
+            "markup.bold",
+            "markup.italic",
+            "markup.underline.link",
+            "markup.inline.raw",
+            "markup.quote",
+            "markup.list",
+            "markup.inserted",
+            "markup.deleted",
+            "markup.changed",
+        ],
+    ),
+    (
+        "Markup languages",
+        &[
+            "entity.name.tag",
+            "entity.other.attribute-name",
+            "support.type.property-name",
+            "meta.object-literal.key",
+        ],
+    ),
+    ("Other", &["entity.name.label", "meta.decorator", "invalid"]),
+];
+
+/// The `--json` schema.
+#[derive(Debug, Serialize)]
+struct CompareReport<'a> {
+    /// Theme labels; the first is the theme being compared.
+    themes: Vec<&'a str>,
+    categories: Vec<CategoryReport<'a>>,
+    gaps: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct CategoryReport<'a> {
+    name: &'a str,
+    scopes: Vec<ScopeReport<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScopeReport<'a> {
+    scope: &'a str,
+    /// One per theme, in `themes` order.
+    cells: Vec<Cell>,
+    /// Themes with a more specific rule than ours.
+    more_specific_in: Vec<&'a str>,
+}
+
+/// How one theme styles one scope.
+#[derive(Debug, Clone, Serialize)]
+struct Cell {
+    foreground: Option<Color>,
+    /// The deciding selector, `None` when unstyled.
+    selector: Option<String>,
+    /// How many segments of the scope the selector names.
+    depth: usize,
+}
+
+impl Cell {
+    fn text(&self) -> String {
+        match &self.selector {
+            Some(selector) => {
+                let color = self.foreground.map_or_else(String::new, |c| c.to_string());
+                format!("{color} {selector}")
+            }
+            None => "-".to_owned(),
+        }
+    }
+}
+
+/// A theme under comparison.
+struct Column {
+    label: String,
+    resolver: Resolver,
+}
+
+impl Column {
+    fn new(path: &Path, theme: &Theme) -> Column {
+        let label = theme.name.clone().unwrap_or_else(|| {
+            path.file_stem()
+                .map_or_else(String::new, |s| s.to_string_lossy().into_owned())
+        });
+        Column {
+            label,
+            resolver: Resolver::new(theme),
+        }
+    }
+
+    fn cell(&self, scope: &str) -> Cell {
+        match self.resolver.foreground_match(&["source", scope]) {
+            Some(m) => Cell {
+                foreground: self.resolver.resolve_matched(&["source", scope]).foreground,
+                selector: Some(m.selector.to_string()),
+                depth: m.specificity.depth,
+            },
+            None => Cell {
+                foreground: None,
+                selector: None,
+                depth: 0,
+            },
+        }
+    }
+}
+
+pub fn run(args: CompareArgs) -> Result<()> {
+    let (path, theme) = load_theme(args.theme.as_deref())?;
+    let mut columns = vec![Column::new(&path, &theme)];
+    for other in &args.others {
+        let is_vsix = other.extension().is_some_and(|e| e == "vsix");
+        if is_vsix || !other.exists() {
+            for contributed in vsix::load(&other.to_string_lossy())? {
+                columns.push(Column::new(other, &contributed.theme));
+            }
+        } else {
+            columns.push(Column::new(other, &import::load_theme(other)?));
+        }
+    }
+
+    let scope_width = SCOPES
+        .iter()
+        .flat_map(|(_, scopes)| scopes.iter())
+        .map(|s| s.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut gaps: Vec<(&str, &str, String)> = Vec::new();
+    let mut report = CompareReport {
+        themes: columns.iter().map(|c| c.label.as_str()).collect(),
+        categories: Vec::new(),
+        gaps: 0,
+    };
+    for &(category, scopes) in SCOPES {
+        let rows: Vec<(&str, Vec<Cell>)> = scopes
+            .iter()
+            .map(|&scope| (scope, columns.iter().map(|c| c.cell(scope)).collect()))
+            .collect();
+
+        let mut category_gaps = Vec::new();
+        let mut scope_reports = Vec::new();
+        for (scope, cells) in &rows {
+            let ours = cells[0].depth;
+            let better: Vec<&str> = columns[1..]
+                .iter()
+                .zip(&cells[1..])
+                .filter(|(_, cell)| cell.depth > ours)
+                .map(|(column, _)| column.label.as_str())
+                .collect();
+            if !better.is_empty() {
+                category_gaps.push((category, *scope, better.join(", ")));
+            }
+            scope_reports.push(ScopeReport {
+                scope,
+                cells: cells.clone(),
+                more_specific_in: better,
+            });
+        }
+        if args.gaps_only && category_gaps.is_empty() {
+            continue;
+        }
+        if args.json {
+            report.gaps += category_gaps.len();
+            report.categories.push(CategoryReport {
+                name: category,
+                scopes: scope_reports,
+            });
+            continue;
+        }
+
+        println!("{category}");
+        let mut header = format!("  {:<scope_width$}", "");
+        for column in &columns {
+            header += &format!("  {:<COLUMN_WIDTH$}", truncate(&column.label));
+        }
+        println!("{}", header.trim_end());
+        for (scope, cells) in &rows {
+            let gap = category_gaps.iter().any(|(_, s, _)| s == scope);
+            let mut line = format!("{} {scope:<scope_width$}", if gap { "!" } else { " " });
+            for cell in cells {
+                line += &format!("  {:<COLUMN_WIDTH$}", truncate(&cell.text()));
+            }
+            println!("{}", line.trim_end());
+        }
+        println!();
+        gaps.extend(category_gaps);
+    }
+
+    if args.json {
+        return print_json(&report);
+    }
+    if gaps.is_empty() {
+        println!("No gaps: every scope is styled at least as specifically as the others do.");
+        return Ok(());
+    }
+    println!(
+        "Gaps ({} scopes where {} lacks a rule as specific as another theme's):",
+        gaps.len(),
+        columns[0].label
+    );
+    for (category, scope, others) in &gaps {
+        println!("  {category:<18} {scope:<scope_width$}  {others}");
+    }
+    Ok(())
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= COLUMN_WIDTH {
+        return text.to_owned();
+    }
+    let mut short: String = text.chars().take(COLUMN_WIDTH - 1).collect();
+    short.push('…');
+    short
+}
@@ -0,0 +1,140 @@
+//! `cyberdeck coverage`: which workbench colors the themes set and which
+//! fall back to VS Code's defaults, by UI component.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::Args;
+use serde::Serialize;
+
+use crate::coverage::{self, pattern_matches, ComponentCoverage};
+use crate::project::Project;
+
+use super::{load_themes, print_json};
+
+#[derive(Debug, Args)]
+pub struct CoverageArgs {
+    /// Theme file to check (defaults to every enabled variant).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+    /// List the unset ids under each component.
+    #[arg(long)]
+    pub missing: bool,
+    /// Print JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// The `--json` schema.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThemeReport {
+    path: String,
+    set: usize,
+    known: usize,
+    components: Vec<ComponentReport>,
+    /// Must-cover ids and patterns the theme doesn't satisfy.
+    uncovered: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ComponentReport {
+    component: &'static str,
+    set: usize,
+    known: usize,
+    missing: Vec<&'static str>,
+}
+
+pub fn run(args: CoverageArgs) -> Result<()> {
+    let must_cover = Project::current()
+        .map(|project| project.config.coverage.must_cover)
+        .unwrap_or_default();
+
+    let mut reports = Vec::new();
+    for (path, theme) in load_themes(args.theme.as_deref())? {
+        let components = coverage::workbench(&theme);
+        let uncovered = uncovered(&must_cover, &components);
+        reports.push(ThemeReport {
+            path: path.display().to_string(),
+            set: components.iter().map(|c| c.set.len()).sum(),
+            known: components
+                .iter()
+                .map(|c| c.set.len() + c.missing.len())
+                .sum(),
+            components: components
+                .into_iter()
+                .map(|c| ComponentReport {
+                    component: c.component,
+                    set: c.set.len(),
+                    known: c.set.len() + c.missing.len(),
+                    missing: c.missing,
+                })
+                .collect(),
+            uncovered,
+        });
+    }
+
+    if args.json {
+        print_json(&reports)?;
+    } else {
+        for report in &reports {
+            println!("{}", report.path);
+            for component in &report.components {
+                println!(
+                    "  {:<32} {:>3}/{:<3} {:>4}",
+                    component.component,
+                    component.set,
+                    component.known,
+                    percent(component.set, component.known)
+                );
+                if args.missing {
+                    for id in &component.missing {
+                        println!("      {id}");
+                    }
+                }
+            }
+            println!(
+                "  {} of {} workbench colors set ({})",
+                report.set,
+                report.known,
+                percent(report.set, report.known)
+            );
+            for pattern in &report.uncovered {
+                println!("  must-cover `{pattern}` is not set");
+            }
+        }
+    }
+
+    let uncovered: usize = reports.iter().map(|r| r.uncovered.len()).sum();
+    if uncovered > 0 {
+        bail!("{uncovered} must-cover color(s) fall back to VS Code's defaults");
+    }
+    Ok(())
+}
+
+/// The must-cover entries a theme misses: exact ids it doesn't set, and
+/// patterns with any unset id. Ids outside the registry count as unset, so
+/// a typo can't pass silently.
+fn uncovered(must_cover: &[String], components: &[ComponentCoverage]) -> Vec<String> {
+    must_cover
+        .iter()
+        .filter(|pattern| {
+            let mut matched = components
+                .iter()
+                .flat_map(|c| {
+                    c.set
+                        .iter()
+                        .map(|id| (id, true))
+                        .chain(c.missing.iter().map(|id| (id, false)))
+                })
+                .filter(|(id, _)| pattern_matches(pattern, id))
+                .peekable();
+            matched.peek().is_none() || matched.any(|(_, set)| !set)
+        })
+        .cloned()
+        .collect()
+}
+
+fn percent(part: usize, whole: usize) -> String {
+    format!("{:.0}%", 100.0 * part as f64 / whole.max(1) as f64)
+}
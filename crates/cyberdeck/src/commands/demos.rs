@@ -0,0 +1,212 @@
+//! `cyberdeck demos`: maintain the language demo corpus.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use crate::demos::{self, Matrix};
+use crate::highlight::Grammars;
+use crate::project::{Project, DEMOS_DIR};
+use crate::scope::scope_prefix;
+
+use super::print_json;
+
+#[derive(Debug, Args)]
+pub struct DemosArgs {
+    #[command(subcommand)]
+    pub command: DemosCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DemosCommand {
+    /// Render demo files from demos/matrix.toml and the templates.
+    Generate(GenerateArgs),
+    /// Report scopes each demo's grammar can emit but the demo never triggers.
+    Verify(VerifyArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct GenerateArgs {
+    /// Languages to generate (defaults to every language in the matrix).
+    pub languages: Vec<String>,
+    /// Fail if any generated demo is out of date instead of writing it.
+    #[arg(long)]
+    pub check: bool,
+    /// Overwrite demo files that were written by hand.
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    /// Demo files to check (defaults to every language demo).
+    pub files: Vec<PathBuf>,
+    /// Include structural `meta.*` and error `invalid.*` scopes.
+    #[arg(long)]
+    pub all: bool,
+    /// Only print per-file totals.
+    #[arg(long)]
+    pub summary: bool,
+    /// Fail if any demo leaves a scope untriggered.
+    #[arg(long)]
+    pub strict: bool,
+    /// Print JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// One file in the `verify --json` output.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VerifiedDemo {
+    path: String,
+    /// `None` when no grammar applies and the file was skipped.
+    language: Option<String>,
+    emittable: usize,
+    triggered: usize,
+    missing: Vec<String>,
+}
+
+/// Scopes not expected from a well-formed demo, skipped unless `--all`.
+const SKIPPED_SCOPES: [&str; 2] = ["meta", "invalid"];
+
+pub fn run(args: DemosArgs) -> Result<()> {
+    match args.command {
+        DemosCommand::Generate(args) => generate(args),
+        DemosCommand::Verify(args) => verify(args),
+    }
+}
+
+fn generate(args: GenerateArgs) -> Result<()> {
+    let project = Project::current()?;
+    let matrix = Matrix::load(&project.root.join(demos::MATRIX))?;
+    let languages = if args.languages.is_empty() {
+        matrix.languages.iter().collect()
+    } else {
+        args.languages
+            .iter()
+            .map(|id| {
+                matrix
+                    .language(id)
+                    .with_context(|| format!("`{id}` is not a language in {}", demos::MATRIX))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let templates = project.root.join(demos::TEMPLATES_DIR);
+    let mut stale = Vec::new();
+    for language in languages {
+        let rendered = matrix.render(&templates, language)?;
+        let path = project.root.join(DEMOS_DIR).join(&language.file);
+        let existing = fs::read_to_string(&path).ok();
+        if existing.as_deref() == Some(rendered.as_str()) {
+            continue;
+        }
+        if args.check {
+            stale.push(path.display().to_string());
+            continue;
+        }
+        if let Some(existing) = &existing {
+            if !args.force && !demos::is_generated(existing) {
+                bail!(
+                    "{} was written by hand; pass --force to replace it",
+                    path.display()
+                );
+            }
+        }
+        fs::write(&path, rendered)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        println!("generated {}", path.display());
+    }
+
+    if !stale.is_empty() {
+        bail!(
+            "{} demo file(s) out of date; run `cyberdeck demos generate`:\n  {}",
+            stale.len(),
+            stale.join("\n  ")
+        );
+    }
+    Ok(())
+}
+
+fn verify(args: VerifyArgs) -> Result<()> {
+    let files = if args.files.is_empty() {
+        Project::current()?.demo_files()?
+    } else {
+        args.files
+    };
+    let grammars = Grammars::installed()?;
+    let inventory = grammars.emittable_scopes();
+
+    let mut incomplete = 0;
+    let mut report = Vec::new();
+    for path in &files {
+        let document = grammars.load(path)?;
+        let title = document.title();
+        let Some(language) = &document.language else {
+            if args.json {
+                report.push(VerifiedDemo {
+                    path: path.display().to_string(),
+                    language: None,
+                    emittable: 0,
+                    triggered: 0,
+                    missing: Vec::new(),
+                });
+            } else {
+                println!("{title}: no grammar, skipped");
+            }
+            continue;
+        };
+        let emittable: BTreeSet<&str> = inventory
+            .get(language)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|scope| args.all || !SKIPPED_SCOPES.iter().any(|s| scope_prefix(s, scope)))
+            .collect();
+        let triggered: BTreeSet<&str> = document
+            .lines
+            .iter()
+            .flatten()
+            .flat_map(|token| token.scopes.iter().map(String::as_str))
+            .collect();
+        let missing: Vec<&str> = emittable.difference(&triggered).copied().collect();
+
+        let covered = emittable.len() - missing.len();
+        if !missing.is_empty() {
+            incomplete += 1;
+        }
+        if args.json {
+            report.push(VerifiedDemo {
+                path: path.display().to_string(),
+                language: Some(language.clone()),
+                emittable: emittable.len(),
+                triggered: covered,
+                missing: missing.iter().map(|s| s.to_string()).collect(),
+            });
+            continue;
+        }
+        println!(
+            "{title} ({language}): {covered} of {} scopes triggered ({:.0}%)",
+            emittable.len(),
+            100.0 * covered as f64 / emittable.len().max(1) as f64
+        );
+        if !args.summary {
+            for scope in &missing {
+                println!("  {scope}");
+            }
+        }
+    }
+
+    if args.json {
+        print_json(&report)?;
+    }
+    if args.strict && incomplete > 0 {
+        bail!("{incomplete} demo file(s) leave grammar scopes untriggered");
+    }
+    Ok(())
+}
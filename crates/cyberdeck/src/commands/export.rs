@@ -0,0 +1,127 @@
+//! `cyberdeck export`: write the theme in other formats.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+
+use crate::config::ExportConfig;
+use crate::export::{Exporter, Registry};
+use crate::project::Project;
+
+use super::load_themes;
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Export target(s); see --list (defaults to `export.targets` in
+    /// cyberdeck.toml).
+    #[arg(short, long = "target", value_name = "NAME")]
+    pub targets: Vec<String>,
+    /// Export every registered target.
+    #[arg(long, conflicts_with = "targets")]
+    pub all: bool,
+    /// List the registered targets, then exit.
+    #[arg(long)]
+    pub list: bool,
+    /// Theme file to export (defaults to every enabled variant).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+    /// Directory the artifacts are written to (defaults to `export.out-dir`
+    /// in cyberdeck.toml, else `dist`).
+    #[arg(short, long)]
+    pub out_dir: Option<PathBuf>,
+}
+
+const DEFAULT_OUT_DIR: &str = "dist";
+
+pub fn run(args: ExportArgs) -> Result<()> {
+    run_with_registry(args, &Registry::with_builtins())
+}
+
+/// Runs the export command against a caller-supplied registry.
+pub fn run_with_registry(args: ExportArgs, registry: &Registry) -> Result<()> {
+    if args.list {
+        // Some formats (Ghostty themes) are named without an extension.
+        let extension = |exporter: &dyn Exporter| match exporter.extension() {
+            "" => String::new(),
+            extension => format!(".{extension}"),
+        };
+        let name_width = registry.iter().map(|e| e.name().len()).max().unwrap_or(0);
+        let extension_width = registry
+            .iter()
+            .map(|e| extension(e).len())
+            .max()
+            .unwrap_or(0);
+        for exporter in registry.iter() {
+            println!(
+                "{:<name_width$} {:<extension_width$} {}",
+                exporter.name(),
+                extension(exporter),
+                exporter.description()
+            );
+        }
+        return Ok(());
+    }
+
+    // Outside a project the flags must name the targets; the output
+    // directory still falls back to the default.
+    let config = match Project::current() {
+        Ok(project) => {
+            let mut config = project.config.export;
+            config.out_dir = config.out_dir.map(|dir| project.root.join(dir));
+            config
+        }
+        Err(err) if args.targets.is_empty() && !args.all => return Err(err),
+        Err(_) => ExportConfig::default(),
+    };
+    let targets = if args.targets.is_empty() {
+        &config.targets
+    } else {
+        &args.targets
+    };
+    let out_dir = args
+        .out_dir
+        .or(config.out_dir)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_OUT_DIR));
+
+    let exporters: Vec<&dyn Exporter> = if args.all {
+        registry.iter().collect()
+    } else {
+        if targets.is_empty() {
+            bail!(
+                "no export target given; use --target <NAME>, --all or --list, \
+                 or set export.targets in cyberdeck.toml"
+            );
+        }
+        targets
+            .iter()
+            .map(|name| {
+                registry.get(name).with_context(|| {
+                    format!("unknown export target `{name}`; see `cyberdeck export --list`")
+                })
+            })
+            .collect::<Result<_>>()?
+    };
+
+    let themes = load_themes(args.theme.as_deref())?;
+    for exporter in exporters {
+        let dir = out_dir.join(exporter.name());
+        for (path, theme) in &themes {
+            let artifacts = exporter.export(theme).with_context(|| {
+                format!("{} export of {} failed", exporter.name(), path.display())
+            })?;
+            for artifact in artifacts {
+                let target = dir.join(&artifact.path);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("failed to create {}", parent.display()))?;
+                }
+                fs::write(&target, &artifact.contents)
+                    .with_context(|| format!("failed to write {}", target.display()))?;
+                println!("wrote {}", target.display());
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! `cyberdeck fmt`: rewrite theme files in canonical form.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+
+use crate::format::format_theme;
+use crate::project::Project;
+use crate::shadow;
+
+#[derive(Debug, Args)]
+pub struct FmtArgs {
+    /// Theme files to format (defaults to every contributed theme).
+    pub files: Vec<PathBuf>,
+    /// Report files that are not formatted instead of rewriting them.
+    #[arg(long)]
+    pub check: bool,
+    /// Also drop token rule selectors that never take effect and merge
+    /// rules with identical settings.
+    #[arg(long)]
+    pub minimize: bool,
+}
+
+pub fn run(args: FmtArgs) -> Result<()> {
+    // The command that fixes what `--check` reports: the same one without it.
+    let mut fix = "cyberdeck fmt".to_owned();
+    if args.minimize {
+        fix.push_str(" --minimize");
+    }
+    for file in &args.files {
+        fix.push_str(&format!(" {}", file.display()));
+    }
+
+    let files = if args.files.is_empty() {
+        let project = Project::current()?;
+        project
+            .themes()
+            .iter()
+            .map(|entry| project.theme_path(entry))
+            .collect()
+    } else {
+        args.files
+    };
+
+    let mut unformatted = 0;
+    for path in &files {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let formatted = if args.minimize {
+            let minimized = shadow::minimize(&text)
+                .with_context(|| format!("failed to parse {}", path.display()))?;
+            if minimized.dropped + minimized.merged > 0 {
+                println!(
+                    "{}: {} dead selector(s), {} mergeable rule(s)",
+                    path.display(),
+                    minimized.dropped,
+                    minimized.merged
+                );
+            }
+            minimized.text
+        } else {
+            format_theme(&text).with_context(|| format!("failed to parse {}", path.display()))?
+        };
+        if formatted == text {
+            continue;
+        }
+        if args.check {
+            println!("{} is not formatted", path.display());
+            unformatted += 1;
+        } else {
+            fs::write(path, formatted)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            println!("formatted {}", path.display());
+        }
+    }
+
+    if unformatted > 0 {
+        bail!("{unformatted} file(s) need formatting; run `{fix}`");
+    }
+    Ok(())
+}
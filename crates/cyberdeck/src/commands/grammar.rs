@@ -0,0 +1,149 @@
+//! `cyberdeck grammar`: fetch and list third-party TextMate grammars.
+
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+
+use crate::grammar::convert::convert;
+use crate::grammar::{self, Lock, LockEntry, Source, LOCK_FILE};
+use crate::project::Project;
+
+#[derive(Debug, Args)]
+pub struct GrammarArgs {
+    #[command(subcommand)]
+    pub command: GrammarCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum GrammarCommand {
+    /// Download, verify and cache grammars from extensions or repositories.
+    Fetch(FetchArgs),
+    /// List the grammars recorded in the lock file and whether they are cached.
+    List,
+}
+
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+    /// Sources to fetch: `publisher.name` or `open-vsx:publisher.name`,
+    /// `marketplace:publisher.name`, `github:owner/repo[@ref]` or
+    /// `file:path.vsix`. Defaults to every source in the lock file.
+    pub sources: Vec<String>,
+    /// Extension version or git ref (defaults to the locked version, else the
+    /// latest).
+    #[arg(long)]
+    pub version: Option<String>,
+    /// Expected SHA-256 of the downloaded archive.
+    #[arg(long, value_name = "HEX")]
+    pub sha256: Option<String>,
+    /// Accept a checksum that differs from the lock file and re-lock it.
+    #[arg(long)]
+    pub update: bool,
+}
+
+pub fn run(args: GrammarArgs) -> Result<()> {
+    match args.command {
+        GrammarCommand::Fetch(args) => fetch(args),
+        GrammarCommand::List => list(),
+    }
+}
+
+fn fetch(args: FetchArgs) -> Result<()> {
+    let project = Project::current()?;
+    let mut lock = Lock::load(&project)?;
+    let sources: Vec<Source> = if args.sources.is_empty() {
+        if lock.entries.is_empty() {
+            bail!("nothing to fetch: pass a source or add one to {LOCK_FILE}");
+        }
+        lock.entries
+            .iter()
+            .map(|e| e.source.parse())
+            .collect::<Result<_>>()?
+    } else {
+        args.sources
+            .iter()
+            .map(|s| s.parse())
+            .collect::<Result<_>>()?
+    };
+    if sources.len() > 1 && (args.version.is_some() || args.sha256.is_some()) {
+        bail!("--version and --sha256 apply to a single source");
+    }
+
+    for source in &sources {
+        let key = source.to_string();
+        let locked = lock.entry(&key).cloned();
+        let version = args.version.as_deref().or_else(|| {
+            locked
+                .as_ref()
+                .filter(|_| !args.update)
+                .map(|e| e.version.as_str())
+        });
+        let archive = source.download(version)?;
+
+        if let Some(expected) = &args.sha256 {
+            if !expected.eq_ignore_ascii_case(&archive.sha256) {
+                bail!(
+                    "{key}: checksum mismatch: expected {expected}, got {}",
+                    archive.sha256
+                );
+            }
+        }
+        if let Some(locked) = &locked {
+            if locked.version == archive.version && locked.sha256 != archive.sha256 && !args.update
+            {
+                bail!(
+                    "{key}@{}: checksum mismatch: {LOCK_FILE} has {}, got {}; \
+                     pass --update if the change is expected",
+                    archive.version,
+                    locked.sha256,
+                    archive.sha256
+                );
+            }
+        }
+
+        let grammars = grammar::install(&archive)?;
+        if grammars.is_empty() {
+            bail!("{key} does not contribute any JSON grammars");
+        }
+        println!("{key}@{} ({})", archive.version, &archive.sha256[..12]);
+        for cached in &grammars {
+            let json = grammar::read_cached(&archive.sha256, cached)?;
+            match convert(&json, &cached.scope_name, &cached.extensions) {
+                Ok(converted) if converted.dropped > 0 => println!(
+                    "  {} ({} patterns unsupported and dropped)",
+                    cached.scope_name, converted.dropped
+                ),
+                Ok(_) => println!("  {}", cached.scope_name),
+                Err(err) => println!("  {}: unusable: {err:#}", cached.scope_name),
+            }
+        }
+        lock.upsert(LockEntry {
+            source: key,
+            version: archive.version,
+            sha256: archive.sha256,
+            scopes: grammars.into_iter().map(|g| g.scope_name).collect(),
+        });
+    }
+
+    lock.save(&project)?;
+    Ok(())
+}
+
+fn list() -> Result<()> {
+    let project = Project::current()?;
+    let lock = Lock::load(&project)?;
+    if lock.entries.is_empty() {
+        println!("no grammars in {LOCK_FILE}");
+        return Ok(());
+    }
+    for entry in &lock.entries {
+        let state = if grammar::cached(&entry.sha256)?.is_some() {
+            "cached"
+        } else {
+            "not cached"
+        };
+        println!("{}@{} ({state})", entry.source, entry.version);
+        for scope in &entry.scopes {
+            println!("  {scope}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,342 @@
+//! `cyberdeck import`: bootstrap a palette from an existing theme.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, ValueEnum};
+
+use crate::color::{Color, NEUTRAL_CHROMA};
+use crate::derive::{complete_roles, derive_variant};
+use crate::format::format_theme;
+use crate::import::{
+    self, base16, dtcg, figma, helix, image, jetbrains, neovim, sublime, terminal, tmtheme, vsix,
+    Mapping, Sample, DEFAULT_THRESHOLD,
+};
+use crate::roles::{resolve_roles, ROLES};
+use crate::theme::Theme;
+
+use super::load_theme;
+
+#[derive(Debug, Args)]
+pub struct ImportArgs {
+    /// Theme or scheme file to import; for `--format vsix`, a `.vsix` or an
+    /// extension ID (`publisher.name`, `marketplace:publisher.name@version`).
+    #[arg(required_unless_present = "from_image")]
+    pub file: Option<PathBuf>,
+    /// Extract the palette from an image's dominant colors instead, and
+    /// derive a matching variant of `--against` (see `--theme-out`).
+    #[arg(long, value_name = "IMAGE", conflicts_with_all = ["file", "format", "mapping"])]
+    pub from_image: Option<PathBuf>,
+    /// Format of the file.
+    #[arg(long, value_enum, default_value_t = ImportFormat::Vscode)]
+    pub format: ImportFormat,
+    /// TOML table of source keys (base16 slots, design token paths, Figma
+    /// variable names with `/` as `.`) to
+    /// palette roles, replacing the format's default mapping key by key
+    /// (e.g. `base08 = ["error", "deleted"]`).
+    #[arg(long, value_name = "FILE")]
+    pub mapping: Option<PathBuf>,
+    /// Figma variable mode to import as the variant (defaults to the
+    /// first).
+    #[arg(long, value_name = "NAME")]
+    pub mode: Option<String>,
+    /// Label of the VS Code extension theme to import (defaults to the
+    /// first the extension contributes).
+    #[arg(long, value_name = "NAME")]
+    pub label: Option<String>,
+    /// Palette name (defaults to the theme's `name`).
+    #[arg(long)]
+    pub name: Option<String>,
+    /// Where to write the palette TOML (defaults to stdout).
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    /// Also write the source converted to a VS Code color theme, with its
+    /// token rules, for formats that have them.
+    #[arg(long, value_name = "FILE")]
+    pub theme_out: Option<PathBuf>,
+    /// Also write a VS Code settings fragment that overrides the colors
+    /// and token styles the source sets differently from `--against`.
+    #[arg(long, value_name = "FILE")]
+    pub overrides_out: Option<PathBuf>,
+    /// Theme the override layer applies to, and that images and terminal
+    /// schemes derive their variant from (defaults to the project's primary
+    /// theme).
+    #[arg(long, value_name = "THEME")]
+    pub against: Option<PathBuf>,
+    /// OKLab distance under which colors merge into one palette entry.
+    #[arg(long, default_value_t = DEFAULT_THRESHOLD)]
+    pub threshold: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// A VS Code color theme JSON.
+    Vscode,
+    /// A base16 or base24 scheme YAML.
+    Base16,
+    /// A TextMate or Sublime Text `.tmTheme` property list.
+    Tmtheme,
+    /// A Sublime Text `.sublime-color-scheme`.
+    Sublime,
+    /// A JetBrains IDE editor scheme (`.icls`).
+    Jetbrains,
+    /// A VS Code extension (`.vsix` or extension ID), one contributed
+    /// theme at a time.
+    Vsix,
+    /// A Helix `theme.toml`, layered over the theme it `inherits` when
+    /// that sits alongside it.
+    Helix,
+    /// A Neovim colorscheme: Lua (`nvim_set_hl` calls and `hi` commands) or
+    /// Vimscript.
+    Neovim,
+    /// A W3C Design Tokens (DTCG) JSON file.
+    Dtcg,
+    /// A Figma variables export (REST API or variables2json), one mode at
+    /// a time. The converted theme is a variant of `--against` in the
+    /// mode's colors.
+    Figma,
+    /// A terminal color scheme: Gogh YAML/JSON, iTerm2-Color-Schemes'
+    /// Windows Terminal JSON or an `.itermcolors` file. The converted theme
+    /// is a variant of `--against` in the scheme's colors.
+    Terminal,
+}
+
+/// What a source file yields.
+struct Imported {
+    name: Option<String>,
+    samples: Vec<Sample>,
+    /// The source as a VS Code theme, for formats with editor colors and
+    /// token rules.
+    theme: Option<Theme>,
+}
+
+pub fn run(args: ImportArgs) -> Result<()> {
+    let overrides = match &args.mapping {
+        Some(path) => import::load_mapping(path)?,
+        None => Mapping::new(),
+    };
+    let imported = read(&args, overrides)?;
+    if let Some(path) = &args.theme_out {
+        let Some(theme) = &imported.theme else {
+            bail!("--theme-out needs a format with editor colors and token rules");
+        };
+        let text = format_theme(&serde_json::to_string_pretty(theme)?)
+            .context("failed to format the converted theme")?;
+        fs::write(path, text).with_context(|| format!("failed to write {}", path.display()))?;
+        eprintln!("wrote theme to {}", path.display());
+    }
+    if let Some(path) = &args.overrides_out {
+        let Some(theme) = &imported.theme else {
+            bail!("--overrides-out needs a format with editor colors and token rules");
+        };
+        let (_, base) = load_theme(args.against.as_deref())?;
+        let layer = import::override_layer(&base, theme);
+        let text = serde_json::to_string_pretty(&layer)? + "\n";
+        fs::write(path, text).with_context(|| format!("failed to write {}", path.display()))?;
+        eprintln!("wrote overrides to {}", path.display());
+    }
+    let name = args
+        .name
+        .clone()
+        .or(imported.name)
+        .unwrap_or_else(|| "Imported".to_owned());
+    let palette = import::build_palette(&name, imported.samples, args.threshold);
+
+    let text = format!(
+        "# Imported from {} by `cyberdeck import`.\n\n{}",
+        source(&args).display(),
+        toml::to_string(&palette)?
+    );
+    match &args.output {
+        Some(path) => {
+            fs::write(path, text).with_context(|| format!("failed to write {}", path.display()))?;
+            eprintln!(
+                "wrote {} colors to {}",
+                palette.colors.len(),
+                path.display()
+            );
+        }
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+/// The file being imported.
+fn source(args: &ImportArgs) -> &Path {
+    args.from_image
+        .as_deref()
+        .or(args.file.as_deref())
+        .expect("clap requires a file or --from-image")
+}
+
+/// Reads the source file in its format.
+fn read(args: &ImportArgs, overrides: Mapping) -> Result<Imported> {
+    let file = source(args);
+    if args.from_image.is_some() {
+        return read_image(args, file);
+    }
+    if args.mapping.is_some()
+        && !matches!(
+            args.format,
+            ImportFormat::Base16 | ImportFormat::Dtcg | ImportFormat::Figma
+        )
+    {
+        bail!("--mapping only applies to base16 schemes, design tokens and Figma variables; other formats resolve roles from their editor colors");
+    }
+    if args.mode.is_some() && args.format != ImportFormat::Figma {
+        bail!("--mode only applies to Figma variables");
+    }
+    if args.label.is_some() && args.format != ImportFormat::Vsix {
+        bail!("--label only applies to VS Code extensions");
+    }
+    let theme = match args.format {
+        ImportFormat::Vscode => Theme::load(file)?,
+        ImportFormat::Tmtheme => tmtheme::load(file)?,
+        ImportFormat::Sublime => sublime::load(file)?,
+        ImportFormat::Jetbrains => jetbrains::load(file)?,
+        ImportFormat::Vsix => {
+            let themes = vsix::load(&file.to_string_lossy())?;
+            let labels: Vec<&str> = themes.iter().map(|t| t.label.as_str()).collect();
+            let index = match &args.label {
+                Some(label) => labels.iter().position(|l| l == label).with_context(|| {
+                    format!(
+                        "{} has no theme `{label}`; it has {}",
+                        file.display(),
+                        labels.join(", ")
+                    )
+                })?,
+                None => 0,
+            };
+            if labels.len() > 1 {
+                eprintln!(
+                    "importing theme `{}` of {}",
+                    labels[index],
+                    labels.join(", ")
+                );
+            }
+            themes
+                .into_iter()
+                .nth(index)
+                .map(|t| t.theme)
+                .unwrap_or_default()
+        }
+        ImportFormat::Helix => helix::load(file)?,
+        ImportFormat::Neovim => neovim::load(file)?,
+        ImportFormat::Base16 => {
+            let scheme = base16::Scheme::load(file)?;
+            let mapping = base16::mapping(overrides)
+                .with_context(|| format!("invalid mapping for {}", file.display()))?;
+            return Ok(Imported {
+                samples: scheme.samples(&mapping),
+                name: scheme.name,
+                theme: None,
+            });
+        }
+        ImportFormat::Terminal => {
+            let scheme = terminal::Scheme::load(file)?;
+            let (_, base) = load_theme(args.against.as_deref())?;
+            let roles = scheme.roles(&resolve_roles(&base));
+            let name = args
+                .name
+                .clone()
+                .or_else(|| scheme.name.clone())
+                .unwrap_or_else(|| "Imported".to_owned());
+            return Ok(Imported {
+                samples: scheme.samples(&roles),
+                theme: Some(derive_variant(&base, &name, &roles)),
+                name: Some(name),
+            });
+        }
+        ImportFormat::Figma => {
+            let variables = figma::Variables::load(file)?;
+            let modes = variables.modes();
+            let mode = match &args.mode {
+                Some(mode) if modes.contains(&mode.as_str()) => mode.as_str(),
+                Some(mode) => bail!(
+                    "{} has no mode `{mode}`; it has {}",
+                    file.display(),
+                    modes.join(", ")
+                ),
+                None => modes
+                    .first()
+                    .copied()
+                    .with_context(|| format!("{} has no variable modes", file.display()))?,
+            };
+            if modes.len() > 1 {
+                eprintln!("importing mode `{mode}` of {}", modes.join(", "));
+            }
+            let tokens = variables.tokens(mode)?;
+            let mapping = tokens
+                .mapping(overrides)
+                .with_context(|| format!("invalid mapping for {}", file.display()))?;
+            let (_, base) = load_theme(args.against.as_deref())?;
+            let named = mapping
+                .iter()
+                .flat_map(|(path, roles)| roles.iter().map(move |role| (role, path)))
+                .filter_map(|(role, path)| {
+                    let (role, _) = ROLES.iter().find(|(name, _)| name == role)?;
+                    Some((*role, tokens.colors[path]))
+                })
+                .collect();
+            let accents: Vec<Color> = tokens
+                .colors
+                .values()
+                .copied()
+                .filter(|c| c.to_oklch().c >= NEUTRAL_CHROMA)
+                .collect();
+            let roles = complete_roles(&resolve_roles(&base), &named, &accents);
+            let name = args.name.clone().unwrap_or_else(|| match &base.name {
+                Some(base) => format!("{base} {mode}"),
+                None => mode.to_owned(),
+            });
+            return Ok(Imported {
+                samples: tokens.samples(&mapping),
+                theme: Some(derive_variant(&base, &name, &roles)),
+                name: Some(name),
+            });
+        }
+        ImportFormat::Dtcg => {
+            let tokens = dtcg::Tokens::load(file)?;
+            let mapping = tokens
+                .mapping(overrides)
+                .with_context(|| format!("invalid mapping for {}", file.display()))?;
+            return Ok(Imported {
+                samples: tokens.samples(&mapping),
+                name: None,
+                theme: None,
+            });
+        }
+    };
+    Ok(Imported {
+        name: theme.name.clone(),
+        samples: import::vscode::samples(&theme),
+        theme: Some(theme),
+    })
+}
+
+/// Clusters the image and derives a variant of the base theme from it.
+fn read_image(args: &ImportArgs, file: &Path) -> Result<Imported> {
+    let (_, base) = load_theme(args.against.as_deref())?;
+    let clusters = image::clusters(file)?;
+    let roles = image::assign_roles(&clusters, &resolve_roles(&base));
+    let name = args.name.clone().unwrap_or_else(|| {
+        let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+        match &base.name {
+            Some(base) => format!("{base} {stem}"),
+            None => stem.into_owned(),
+        }
+    });
+    Ok(Imported {
+        samples: roles
+            .iter()
+            .map(|(role, color)| Sample {
+                color: *color,
+                weight: 1,
+                roles: vec![(*role).to_owned()],
+            })
+            .collect(),
+        theme: Some(derive_variant(&base, &name, &roles)),
+        name: Some(name),
+    })
+}
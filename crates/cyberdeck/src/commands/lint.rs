@@ -0,0 +1,121 @@
+//! `cyberdeck lint`: run the configurable rule set over the themes.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::Args;
+use serde::Serialize;
+
+use crate::lint::{self, all_rules, Diagnostic, LintConfig, Severity};
+use crate::project::Project;
+
+use super::{load_palette, load_themes, print_json};
+
+#[derive(Debug, Args)]
+pub struct LintArgs {
+    /// Theme file to lint (defaults to every enabled variant).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+    /// Palette file used by palette-aware rules.
+    #[arg(long)]
+    pub palette: Option<PathBuf>,
+    /// Rule configuration (defaults to `[lint]` in cyberdeck.toml, else
+    /// cyberdeck-lint.toml in the project root).
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    /// List the available rules and their default severities, then exit.
+    #[arg(long)]
+    pub list_rules: bool,
+    /// Print JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// The `--json` schema.
+#[derive(Debug, Serialize)]
+struct LintReport {
+    files: Vec<FileReport>,
+    errors: usize,
+    warnings: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct FileReport {
+    path: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// One entry of `--list-rules --json`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RuleInfo {
+    id: &'static str,
+    default_severity: Severity,
+    description: &'static str,
+}
+
+pub fn run(args: LintArgs) -> Result<()> {
+    if args.list_rules {
+        if args.json {
+            let rules: Vec<RuleInfo> = all_rules()
+                .iter()
+                .map(|rule| RuleInfo {
+                    id: rule.id(),
+                    default_severity: rule.default_severity(),
+                    description: rule.description(),
+                })
+                .collect();
+            return print_json(&rules);
+        }
+        for rule in all_rules() {
+            println!(
+                "{:<24} {:<8} {}",
+                rule.id(),
+                rule.default_severity().to_string(),
+                rule.description()
+            );
+        }
+        return Ok(());
+    }
+
+    let config = match &args.config {
+        Some(path) => LintConfig::load(path)?,
+        None => Project::current()?.lint_config()?,
+    };
+    let (_, palette) = load_palette(args.palette.as_deref())?;
+
+    let mut errors = 0;
+    let mut warnings = 0;
+    let mut files = Vec::new();
+    for (path, theme) in load_themes(args.theme.as_deref())? {
+        let diagnostics = lint::lint(&theme, &palette, &config);
+        for diagnostic in &diagnostics {
+            if !args.json {
+                println!("{}: {diagnostic}", path.display());
+            }
+            match diagnostic.severity {
+                Severity::Error => errors += 1,
+                Severity::Warn => warnings += 1,
+                Severity::Info | Severity::Off => {}
+            }
+        }
+        files.push(FileReport {
+            path: path.display().to_string(),
+            diagnostics,
+        });
+    }
+
+    if args.json {
+        print_json(&LintReport {
+            files,
+            errors,
+            warnings,
+        })?;
+    } else {
+        println!("{errors} error(s), {warnings} warning(s)");
+    }
+    if errors > 0 {
+        bail!("lint failed with {errors} error(s)");
+    }
+    Ok(())
+}
@@ -0,0 +1,25 @@
+//! `cyberdeck man`: write roff man pages for the whole CLI.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Args, CommandFactory};
+
+use crate::cli::Cli;
+
+#[derive(Debug, Args)]
+pub struct ManArgs {
+    /// Directory to write `cyberdeck.1` and one page per subcommand into.
+    #[arg(long, default_value = "dist/man")]
+    pub out_dir: PathBuf,
+}
+
+pub fn run(args: ManArgs) -> Result<()> {
+    fs::create_dir_all(&args.out_dir)
+        .with_context(|| format!("failed to create {}", args.out_dir.display()))?;
+    clap_mangen::generate_to(Cli::command(), &args.out_dir)
+        .with_context(|| format!("failed to write man pages to {}", args.out_dir.display()))?;
+    println!("wrote man pages to {}", args.out_dir.display());
+    Ok(())
+}
@@ -0,0 +1,58 @@
+//! `cyberdeck markdown`: the Markdown preview under the theme, as an HTML
+//! page or just its stylesheet.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Args;
+
+use crate::render::markdown;
+
+use super::load_theme;
+
+#[derive(Debug, Args)]
+pub struct MarkdownArgs {
+    /// Markdown file to render (defaults to a sample using every styled
+    /// element).
+    pub file: Option<PathBuf>,
+    /// Theme file to style with (defaults to the primary contributed theme).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+    /// Only print the stylesheet.
+    #[arg(long, conflicts_with = "file")]
+    pub css: bool,
+    /// File to write (defaults to stdout).
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+pub fn run(args: MarkdownArgs) -> Result<()> {
+    let (_, theme) = load_theme(args.theme.as_deref())?;
+    let contents = if args.css {
+        markdown::stylesheet(&theme)
+    } else {
+        let (title, source) = match &args.file {
+            Some(path) => (
+                path.file_name().map_or_else(
+                    || path.display().to_string(),
+                    |name| name.to_string_lossy().into_owned(),
+                ),
+                fs::read_to_string(path)
+                    .with_context(|| format!("failed to read {}", path.display()))?,
+            ),
+            None => ("sample.md".to_owned(), markdown::SAMPLE.to_owned()),
+        };
+        markdown::html(&theme, &title, &source)
+    };
+    match &args.output {
+        Some(path) => {
+            fs::write(path, contents)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            eprintln!("wrote {}", path.display());
+        }
+        None => io::stdout().write_all(contents.as_bytes())?,
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+//! Subcommand implementations. Each module exposes a clap `Args` struct and
+//! a `run` function.
+
+pub mod artwork;
+pub mod brackets;
+pub mod bump;
+pub mod compare;
+pub mod completions;
+pub mod coverage;
+pub mod demos;
+pub mod export;
+pub mod fmt;
+pub mod grammar;
+pub mod import;
+pub mod lint;
+pub mod man;
+pub mod markdown;
+pub mod parity;
+pub mod playground;
+pub mod preview;
+pub mod render;
+pub mod stats;
+pub mod suggest;
+pub mod tokens;
+pub mod validate;
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::palette::Palette;
+use crate::project::Project;
+use crate::theme::Theme;
+
+/// Loads the theme at `path`, or the project's primary theme.
+pub(crate) fn load_theme(path: Option<&Path>) -> Result<(PathBuf, Theme)> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let project = Project::current()?;
+            project.theme_path(project.primary_theme()?)
+        }
+    };
+    let theme = Theme::load(&path)?;
+    Ok((path, theme))
+}
+
+/// Loads the theme at `path`, or every variant the project enables.
+pub(crate) fn load_themes(path: Option<&Path>) -> Result<Vec<(PathBuf, Theme)>> {
+    if let Some(path) = path {
+        return Ok(vec![(path.to_path_buf(), Theme::load(path)?)]);
+    }
+    let project = Project::current()?;
+    project
+        .variants()?
+        .into_iter()
+        .map(|entry| {
+            let path = project.theme_path(entry);
+            let theme = Theme::load(&path)?;
+            Ok((path, theme))
+        })
+        .collect()
+}
+
+/// Loads the palette at `path`, or the project's default palette.
+pub(crate) fn load_palette(path: Option<&Path>) -> Result<(PathBuf, Palette)> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => Project::current()?.palette_path(),
+    };
+    let palette = Palette::load(&path)?;
+    Ok((path, palette))
+}
+
+/// Prints `value` as pretty JSON on stdout. Every reporting command's
+/// `--json` output goes through here; field names are camelCase and new
+/// fields may be added, but existing ones keep their meaning.
+pub(crate) fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
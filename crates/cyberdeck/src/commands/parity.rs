@@ -0,0 +1,88 @@
+//! `cyberdeck parity`: whether the theme variants differ from the primary
+//! one only in their colors.
+
+use anyhow::{bail, Result};
+use clap::Args;
+use serde::Serialize;
+
+use crate::parity;
+use crate::project::Project;
+
+use super::print_json;
+
+#[derive(Debug, Args)]
+pub struct ParityArgs {
+    /// Print JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// The `--json` schema.
+#[derive(Debug, Serialize)]
+struct VariantReport {
+    variant: String,
+    path: String,
+    mismatches: Vec<MismatchReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct MismatchReport {
+    location: String,
+    message: String,
+}
+
+pub fn run(args: ParityArgs) -> Result<()> {
+    let project = Project::current()?;
+    let variants = project.variants()?;
+    let Some((primary, rest)) = variants.split_first() else {
+        bail!("package.json contributes no themes");
+    };
+    let primary_theme = project.load_theme(primary)?;
+
+    let mut reports = Vec::new();
+    for entry in rest {
+        let theme = project.load_theme(entry)?;
+        reports.push(VariantReport {
+            variant: entry.label.clone(),
+            path: project.theme_path(entry).display().to_string(),
+            mismatches: parity::compare(&primary_theme, &theme)
+                .into_iter()
+                .map(|m| MismatchReport {
+                    location: m.location,
+                    message: m.message,
+                })
+                .collect(),
+        });
+    }
+
+    if args.json {
+        print_json(&reports)?;
+    } else if reports.is_empty() {
+        println!("Only one variant ({}); nothing to compare.", primary.label);
+    } else {
+        for report in &reports {
+            for mismatch in &report.mismatches {
+                println!(
+                    "{}: {}: {}",
+                    report.path, mismatch.location, mismatch.message
+                );
+            }
+        }
+    }
+
+    let total: usize = reports.iter().map(|r| r.mismatches.len()).sum();
+    if total > 0 {
+        bail!(
+            "{total} difference(s) from the primary variant ({}) beyond colors",
+            primary.label
+        );
+    }
+    if !args.json && !reports.is_empty() {
+        println!(
+            "{} variant(s) match {} apart from their colors.",
+            reports.len(),
+            primary.label
+        );
+    }
+    Ok(())
+}
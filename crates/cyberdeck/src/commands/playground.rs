@@ -0,0 +1,153 @@
+//! `cyberdeck playground`: build the browser playground, a static site where
+//! the tokenizer, resolver and renderer run as WebAssembly.
+//!
+//! The bindings live in `crates/playground`; building them needs the
+//! `wasm32-unknown-unknown` target and the `wasm-bindgen` command line
+//! matching the `wasm-bindgen` version in `Cargo.lock`.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Subcommand};
+use serde_json::{Map, Value};
+
+use crate::jsonc;
+use crate::project::Project;
+
+use super::load_themes;
+
+#[derive(Debug, Args)]
+pub struct PlaygroundArgs {
+    #[command(subcommand)]
+    pub command: PlaygroundCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PlaygroundCommand {
+    /// Compile the renderer to WebAssembly and write the static site, with
+    /// the enabled variants and the language demos as samples.
+    Build(BuildArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct BuildArgs {
+    /// Directory the site is written to.
+    #[arg(short, long, default_value = "dist/playground")]
+    pub out_dir: PathBuf,
+    /// Build the WebAssembly without optimizations, for faster iteration.
+    #[arg(long)]
+    pub debug: bool,
+}
+
+const PACKAGE: &str = "cyberdeck-playground";
+const CRATE_DIR: &str = "crates/playground";
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+pub fn run(args: PlaygroundArgs) -> Result<()> {
+    match args.command {
+        PlaygroundCommand::Build(args) => build(args),
+    }
+}
+
+fn build(args: BuildArgs) -> Result<()> {
+    let project = Project::current()?;
+    let crate_dir = project.root.join(CRATE_DIR);
+    if !crate_dir.join("Cargo.toml").is_file() {
+        bail!(
+            "{} is missing; the playground builds from the repository",
+            crate_dir.display()
+        );
+    }
+    let profile = if args.debug { "debug" } else { "release" };
+
+    let mut cargo = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    cargo
+        .args(["build", "-p", PACKAGE, "--lib", "--target", WASM_TARGET])
+        .current_dir(&project.root);
+    if !args.debug {
+        cargo.arg("--release");
+    }
+    run_tool(
+        &mut cargo,
+        &format!("is the target installed? `rustup target add {WASM_TARGET}`"),
+    )?;
+
+    let target_dir =
+        env::var_os("CARGO_TARGET_DIR").map_or_else(|| project.root.join("target"), PathBuf::from);
+    let wasm = target_dir
+        .join(WASM_TARGET)
+        .join(profile)
+        .join(format!("{}.wasm", PACKAGE.replace('-', "_")));
+    let pkg = args.out_dir.join("pkg");
+    run_tool(
+        Command::new("wasm-bindgen")
+            .args(["--target", "web", "--no-typescript", "--out-dir"])
+            .arg(&pkg)
+            .arg(&wasm),
+        "is it installed? `cargo install wasm-bindgen-cli --version <the wasm-bindgen version in Cargo.lock>`",
+    )?;
+
+    copy_dir(&crate_dir.join("www"), &args.out_dir)?;
+    let themes = load_themes(None)?
+        .into_iter()
+        .map(|(path, _)| {
+            let text = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            jsonc::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))
+        })
+        .collect::<Result<Vec<Value>>>()?;
+    let mut samples = Map::new();
+    for path in project.demo_files()? {
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        let code = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        samples.insert(name, Value::String(code));
+    }
+    write_json(&args.out_dir.join("themes.json"), &Value::Array(themes))?;
+    write_json(&args.out_dir.join("samples.json"), &Value::Object(samples))?;
+    eprintln!(
+        "wrote the playground to {}; serve it over HTTP, e.g. `python3 -m http.server -d {}`",
+        args.out_dir.display(),
+        args.out_dir.display()
+    );
+    Ok(())
+}
+
+/// Runs an external build tool, with `hint` added when it can't be started
+/// or fails.
+fn run_tool(command: &mut Command, hint: &str) -> Result<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let status = command
+        .status()
+        .with_context(|| format!("failed to run {program}; {hint}"))?;
+    if !status.success() {
+        bail!("{program} failed ({status}); {hint}");
+    }
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to).with_context(|| format!("failed to create {}", to.display()))?;
+    for entry in fs::read_dir(from).with_context(|| format!("failed to read {}", from.display()))? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("failed to write {}", target.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn write_json(path: &Path, value: &Value) -> Result<()> {
+    fs::write(path, value.to_string())
+        .with_context(|| format!("failed to write {}", path.display()))
+}
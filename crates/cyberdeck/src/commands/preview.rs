@@ -0,0 +1,279 @@
+//! `cyberdeck preview`: browse the language demos in the terminal, colored
+//! with the theme's token colors.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color as TermColor, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::color::Color;
+use crate::highlight::{Document, Grammars, Styler};
+use crate::project::Project;
+use crate::scope::{ResolvedStyle, Resolver};
+use crate::theme::Theme;
+
+use super::load_themes;
+
+#[derive(Debug, Args)]
+pub struct PreviewArgs {
+    /// Files to preview (defaults to the language demos).
+    pub files: Vec<PathBuf>,
+    /// Theme file to preview (defaults to every contributed theme).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+}
+
+pub fn run(args: PreviewArgs) -> Result<()> {
+    let variants: Vec<Variant> = load_themes(args.theme.as_deref())?
+        .into_iter()
+        .map(|(_, theme)| Variant::new(theme))
+        .collect();
+    let files = if args.files.is_empty() {
+        Project::current()?.demo_files()?
+    } else {
+        args.files
+    };
+    let grammars = Grammars::installed()?;
+    let demos = files
+        .iter()
+        .map(|path| grammars.load(path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut app = App {
+        variants,
+        demos,
+        variant: 0,
+        demo: 0,
+        scroll: 0,
+        page: 0,
+    };
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+/// A theme with the colors the preview draws with.
+struct Variant {
+    theme: Theme,
+    resolver: Resolver,
+}
+
+impl Variant {
+    fn new(theme: Theme) -> Variant {
+        let resolver = Resolver::new(&theme);
+        Variant { theme, resolver }
+    }
+
+    fn background(&self) -> Color {
+        self.resolver
+            .defaults()
+            .background
+            .unwrap_or(Color::rgb(0, 0, 0))
+            .with_alpha(0xff)
+    }
+
+    /// A workbench color composited over the editor background.
+    fn workbench(&self, key: &str) -> Option<TermColor> {
+        let color: Color = self.theme.colors.get(key)?.parse().ok()?;
+        Some(term_color(color.over(self.background())))
+    }
+
+    fn style(&self, key_fg: &str, key_bg: &str) -> Style {
+        let mut style = Style::default();
+        if let Some(fg) = self.workbench(key_fg) {
+            style = style.fg(fg);
+        }
+        if let Some(bg) = self.workbench(key_bg) {
+            style = style.bg(bg);
+        }
+        style
+    }
+}
+
+struct App {
+    variants: Vec<Variant>,
+    demos: Vec<Document>,
+    variant: usize,
+    demo: usize,
+    scroll: usize,
+    /// Height of the code pane at the last draw, for paging.
+    page: usize,
+}
+
+impl App {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let lines = self.demos.get(self.demo).map_or(0, |d| d.lines.len());
+            let last = lines.saturating_sub(self.page.max(1));
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(())
+                }
+                KeyCode::Right | KeyCode::Char('l') => self.select_demo(1),
+                KeyCode::Left | KeyCode::Char('h') => {
+                    self.select_demo(self.demos.len().saturating_sub(1))
+                }
+                KeyCode::Tab | KeyCode::Char('v') => self.select_variant(1),
+                KeyCode::BackTab | KeyCode::Char('V') => {
+                    self.select_variant(self.variants.len() - 1)
+                }
+                KeyCode::Down | KeyCode::Char('j') => self.scroll = (self.scroll + 1).min(last),
+                KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+                KeyCode::PageDown | KeyCode::Char(' ') => {
+                    self.scroll = (self.scroll + self.page).min(last)
+                }
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(self.page),
+                KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+                KeyCode::End | KeyCode::Char('G') => self.scroll = last,
+                _ => {}
+            }
+        }
+    }
+
+    fn select_demo(&mut self, step: usize) {
+        if !self.demos.is_empty() {
+            self.demo = (self.demo + step) % self.demos.len();
+            self.scroll = 0;
+        }
+    }
+
+    fn select_variant(&mut self, step: usize) {
+        self.variant = (self.variant + step) % self.variants.len();
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tabs, code, status] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        self.page = code.height as usize;
+
+        let variant = &self.variants[self.variant];
+        self.draw_tabs(frame, tabs, variant);
+        self.draw_code(frame, code, variant);
+        self.draw_status(frame, status, variant);
+    }
+
+    fn draw_tabs(&self, frame: &mut Frame, area: Rect, variant: &Variant) {
+        let active = variant.style("tab.activeForeground", "tab.activeBackground");
+        let inactive = variant.style("tab.inactiveForeground", "tab.inactiveBackground");
+        let spans: Vec<Span> = self
+            .demos
+            .iter()
+            .enumerate()
+            .map(|(i, demo)| {
+                let style = if i == self.demo { active } else { inactive };
+                Span::styled(format!(" {} ", demo.title()), style)
+            })
+            .collect();
+        let bar = variant.style("foreground", "editorGroupHeader.tabsBackground");
+        frame.render_widget(Paragraph::new(Line::from(spans)).style(bar), area);
+    }
+
+    fn draw_code(&self, frame: &mut Frame, area: Rect, variant: &Variant) {
+        let background = term_color(variant.background());
+        let gutter = variant
+            .workbench("editorLineNumber.foreground")
+            .unwrap_or(TermColor::DarkGray);
+        let Some(demo) = self.demos.get(self.demo) else {
+            frame.render_widget(
+                Paragraph::new("").style(Style::default().bg(background)),
+                area,
+            );
+            return;
+        };
+
+        let width = demo.lines.len().to_string().len();
+        let mut styler = Styler::new(&variant.resolver);
+        let lines: Vec<Line> = demo
+            .lines
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(area.height as usize)
+            .map(|(number, tokens)| {
+                let mut spans = vec![Span::styled(
+                    format!("{:>width$}  ", number + 1),
+                    Style::default().fg(gutter),
+                )];
+                spans.extend(tokens.iter().map(|token| {
+                    let style = term_style(styler.style(token), variant.background());
+                    Span::styled(token.text.replace('\t', "    "), style)
+                }));
+                Line::from(spans)
+            })
+            .collect();
+        let fg = variant
+            .resolver
+            .defaults()
+            .foreground
+            .map_or(TermColor::Reset, term_color);
+        let paragraph = Paragraph::new(lines).style(Style::default().fg(fg).bg(background));
+        frame.render_widget(paragraph, area);
+    }
+
+    fn draw_status(&self, frame: &mut Frame, area: Rect, variant: &Variant) {
+        let (language, lines) = self.demos.get(self.demo).map_or(("", 0), |d| {
+            (d.language.as_deref().unwrap_or("plain text"), d.lines.len())
+        });
+        let text = format!(
+            " {} ({}/{}) │ {} │ {}/{}   ←/→ file  v variant  ↑/↓ PgUp/PgDn scroll  q quit",
+            variant.theme.name.as_deref().unwrap_or("untitled"),
+            self.variant + 1,
+            self.variants.len(),
+            language,
+            (self.scroll + 1).min(lines),
+            lines,
+        );
+        let style = variant.style("statusBar.foreground", "statusBar.background");
+        frame.render_widget(Paragraph::new(text).style(style), area);
+    }
+}
+
+fn term_color(color: Color) -> TermColor {
+    TermColor::Rgb(color.r, color.g, color.b)
+}
+
+/// Converts a resolved token style, compositing translucent colors over the
+/// editor background since terminals have no alpha.
+fn term_style(resolved: &ResolvedStyle, background: Color) -> Style {
+    let mut style = Style::default();
+    if let Some(fg) = resolved.foreground {
+        style = style.fg(term_color(fg.over(background)));
+    }
+    if let Some(bg) = resolved.background {
+        style = style.bg(term_color(bg.over(background)));
+    }
+    for word in resolved
+        .font_style
+        .as_deref()
+        .unwrap_or("")
+        .split_whitespace()
+    {
+        style = style.add_modifier(match word {
+            "bold" => Modifier::BOLD,
+            "italic" => Modifier::ITALIC,
+            "underline" => Modifier::UNDERLINED,
+            "strikethrough" => Modifier::CROSSED_OUT,
+            _ => continue,
+        });
+    }
+    style
+}
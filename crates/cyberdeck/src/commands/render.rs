@@ -0,0 +1,406 @@
+//! `cyberdeck render`: highlighted code rendered outside the editor.
+
+use std::fs;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{ArgGroup, Args};
+
+use crate::export::{theme_name, Artifact};
+use crate::git;
+use crate::highlight::{Document, Grammars};
+use crate::project::Project;
+use crate::render::animation::{animate, Format};
+use crate::render::ansi::ansi;
+use crate::render::diff::{Version, VisualDiff};
+use crate::render::font::Font;
+use crate::render::gallery::gallery;
+use crate::render::png;
+use crate::render::screenshot::{Chrome, Screenshot, DEFAULT_ADVANCE};
+use crate::render::svg::{self, Text};
+use crate::render::terminal;
+use crate::render::{workbench, Highlighted};
+
+use crate::theme::Theme;
+
+use super::{load_palette, load_theme, load_themes};
+
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("format").required(true)))]
+pub struct RenderArgs {
+    /// Files to render (defaults to the language demos).
+    pub files: Vec<PathBuf>,
+    /// Theme file to render with (defaults to every enabled variant).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+    /// Build a static HTML gallery: a page per file per variant, with a
+    /// variant switcher and a palette swatch page.
+    #[arg(long, group = "format")]
+    pub gallery: bool,
+    /// Render one file as an SVG screenshot in a window frame (with the
+    /// primary theme unless `--theme` is given).
+    #[arg(long, group = "format")]
+    pub svg: bool,
+    /// Render one file as a PNG screenshot, rasterized with `--font`.
+    #[arg(long, group = "format", requires = "font")]
+    pub png: bool,
+    /// Print one file with 24-bit ANSI colors, for `less -R` or a quick
+    /// look in the terminal.
+    #[arg(long, group = "format")]
+    pub ansi: bool,
+    /// Compare with an earlier version of the theme: a theme file, or a git
+    /// revision as `REV` (the primary theme there) or `REV:PATH`. Writes
+    /// side-by-side images of every file that changes and a page with a
+    /// before/after slider for each.
+    #[arg(long, group = "format", value_name = "BEFORE")]
+    pub diff: Option<String>,
+    /// Render one file as an animation cycling through every enabled
+    /// variant, rasterized with `--font`: a GIF or APNG, by the extension
+    /// of `--output`.
+    #[arg(long, group = "format", requires_all = ["font", "output"])]
+    pub animate: bool,
+    /// Palette for the swatch page (defaults to the project's).
+    #[arg(long)]
+    pub palette: Option<PathBuf>,
+    /// Only this 1-based, inclusive line range, as `START-END`.
+    #[arg(long, value_name = "START-END")]
+    pub lines: Option<String>,
+    /// Number the lines.
+    #[arg(long)]
+    pub line_numbers: bool,
+    /// Frame screenshots in a mock-up of the whole VS Code workbench:
+    /// activity bar, explorer, tabs, terminal panel and status bar.
+    #[arg(long, conflicts_with_all = ["gallery", "ansi"])]
+    pub workbench: bool,
+    /// Lay sample editor decorations over screenshots: error, warning and
+    /// info squiggles with Error Lens messages, git changes in the gutter
+    /// and inline, find matches and word highlights.
+    #[arg(long, conflicts_with_all = ["gallery", "ansi"])]
+    pub decorations: bool,
+    /// Color brackets by nesting depth with the theme's
+    /// `editorBracketHighlight.*` colors, as VS Code does.
+    #[arg(long, conflicts_with = "gallery")]
+    pub bracket_pairs: bool,
+    /// Show a simulated minimap beside screenshots, with the slider over
+    /// the lines in view.
+    #[arg(long, conflicts_with_all = ["gallery", "ansi"])]
+    pub minimap: bool,
+    /// Render a sample integrated-terminal session in the theme's ANSI
+    /// colors instead of a file: a prompt, `ls`, a `git diff`, compiler
+    /// errors and a color test of all sixteen slots.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "files", "gallery", "diff", "animate", "lines", "line_numbers",
+            "workbench", "decorations", "minimap", "bracket_pairs",
+        ]
+    )]
+    pub terminal: bool,
+    /// Monospace font file to measure with and embed or rasterize (without
+    /// it, an SVG names common coding fonts and relies on the viewer having
+    /// one).
+    #[arg(long, value_name = "FILE")]
+    pub font: Option<PathBuf>,
+    /// Draw glyph outlines from `--font` instead of embedding it, so the
+    /// image looks the same everywhere.
+    #[arg(long, requires = "font")]
+    pub outline: bool,
+    /// Font size in pixels.
+    #[arg(long, default_value_t = 14.0)]
+    pub font_size: f64,
+    /// Transparent space around the window, in pixels.
+    #[arg(long, default_value_t = 32.0)]
+    pub padding: f64,
+    /// Device pixels per pixel in a PNG, for high-density displays.
+    #[arg(long, default_value_t = 2.0)]
+    pub scale: f64,
+    /// How long each variant shows in an animation, in milliseconds.
+    #[arg(long, value_name = "MS", default_value_t = 2000)]
+    pub delay: u16,
+    /// Where output goes: a directory for the gallery and diff (defaults to
+    /// `dist/gallery` and `dist/diff`), a file otherwise (defaults to
+    /// stdout).
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+const DEFAULT_GALLERY_DIR: &str = "dist/gallery";
+const DEFAULT_DIFF_DIR: &str = "dist/diff";
+
+pub fn run(args: RenderArgs) -> Result<()> {
+    if !(args.scale > 0.0 && args.scale <= 8.0) {
+        bail!("--scale must be above 0 and at most 8");
+    }
+    if args.gallery {
+        run_gallery(&args)
+    } else if let Some(before) = &args.diff {
+        run_diff(&args, before)
+    } else if args.animate {
+        run_animation(&args)
+    } else {
+        run_screenshot(&args)
+    }
+}
+
+fn run_gallery(args: &RenderArgs) -> Result<()> {
+    let themes: Vec<_> = load_themes(args.theme.as_deref())?
+        .into_iter()
+        .map(|(_, theme)| theme)
+        .collect();
+    let project = Project::current().ok();
+    let documents = documents(args)?;
+
+    // Outside a project only an explicit palette gets a swatch page.
+    let palette = match (&args.palette, &project) {
+        (None, None) => None,
+        (palette, _) => Some(load_palette(palette.as_deref())?.1),
+    };
+    let title = project
+        .as_ref()
+        .and_then(|p| p.manifest.display_name.clone())
+        .unwrap_or_else(|| themes.first().map_or("Theme", theme_name).to_owned());
+    let dir = args
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_GALLERY_DIR));
+    write(
+        &dir,
+        &gallery(&title, &themes, &documents, palette.as_ref()),
+    )
+}
+
+fn run_screenshot(args: &RenderArgs) -> Result<()> {
+    let format = if args.png {
+        "--png"
+    } else if args.ansi {
+        "--ansi"
+    } else {
+        "--svg"
+    };
+    if args.png && args.output.is_none() {
+        bail!("--png needs --output FILE");
+    }
+    let (_, theme) = load_theme(args.theme.as_deref())?;
+    let code = if args.terminal {
+        terminal::session(&theme)
+    } else {
+        let [path] = args.files.as_slice() else {
+            bail!("{format} renders exactly one file");
+        };
+        let document = Grammars::installed()?.load(path)?;
+        let mut code = highlight(args, &theme, &document);
+        if let Some(lines) = &args.lines {
+            let range = parse_lines(lines)?;
+            if *range.start() > code.lines.len() {
+                bail!("{} has only {} lines", path.display(), code.lines.len());
+            }
+            code = code.region(range);
+        }
+        code
+    };
+    if args.ansi {
+        let numbers = args.line_numbers.then(|| {
+            workbench(&theme, "editorLineNumber.foreground", code.background)
+                .unwrap_or(code.foreground)
+        });
+        return output(args, ansi(&code, numbers).into_bytes());
+    }
+    let font = args.font.as_deref().map(Font::load).transpose()?;
+    let scene = Screenshot {
+        title: Some(code.title.clone()),
+        ..screenshot(args, font.as_ref())
+    }
+    .scene(&theme, &code);
+    let image = match &font {
+        Some(font) if args.png => png::png(&scene, font, args.scale)?,
+        font => svg::svg(&scene, text(args, font.as_ref())).into_bytes(),
+    };
+    output(args, image)
+}
+
+fn run_animation(args: &RenderArgs) -> Result<()> {
+    let [path] = args.files.as_slice() else {
+        bail!("--animate renders exactly one file");
+    };
+    let Some(output) = &args.output else {
+        bail!("--animate needs --output FILE");
+    };
+    let format = Format::from_path(output)?;
+    let font = args.font.as_deref().map(Font::load).transpose()?;
+    let Some(font) = &font else {
+        bail!("--animate needs --font FILE to rasterize with");
+    };
+    let lines = args.lines.as_deref().map(parse_lines).transpose()?;
+    let document = Grammars::installed()?.load(path)?;
+    let frames = load_themes(args.theme.as_deref())?
+        .iter()
+        .map(|(_, theme)| {
+            let mut code = highlight(args, theme, &document);
+            if let Some(lines) = &lines {
+                code = code.region(lines.clone());
+            }
+            let scene = Screenshot {
+                title: Some(format!("{} · {}", code.title, theme_name(theme))),
+                ..screenshot(args, Some(font))
+            }
+            .scene(theme, &code);
+            png::rasterize(&scene, font, args.scale)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    eprintln!("encoding {} frames", frames.len());
+    self::output(args, animate(&frames, args.delay, format)?)
+}
+
+fn run_diff(args: &RenderArgs, before: &str) -> Result<()> {
+    let old = load_version(before)?;
+    let (path, new) = load_theme(args.theme.as_deref())?;
+    let after = match &args.theme {
+        Some(_) => path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        ),
+        None => "working tree".to_owned(),
+    };
+    let documents = documents(args)?;
+    let font = args.font.as_deref().map(Font::load).transpose()?;
+    let diff = VisualDiff {
+        screenshot: screenshot(args, font.as_ref()),
+        lines: args.lines.as_deref().map(parse_lines).transpose()?,
+        text: text(args, font.as_ref()),
+        png: font.as_ref().map(|font| (font, args.scale)),
+        bracket_pairs: args.bracket_pairs,
+    };
+    let (artifacts, changed) = diff.render(
+        Version {
+            label: before,
+            theme: &old,
+        },
+        Version {
+            label: &after,
+            theme: &new,
+        },
+        &documents,
+    )?;
+    eprintln!("{changed} of {} files render differently", documents.len());
+    let dir = args
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_DIFF_DIR));
+    write(&dir, &artifacts)
+}
+
+/// The theme `spec` names: a theme file, or `REV` or `REV:PATH` in the
+/// project's git history, `REV` meaning the primary theme there.
+fn load_version(spec: &str) -> Result<Theme> {
+    if Path::new(spec).is_file() {
+        return Theme::load(Path::new(spec));
+    }
+    let project = Project::current()?;
+    let (rev, path) = match spec.split_once(':') {
+        Some((rev, path)) => (rev, path.to_owned()),
+        None => (
+            spec,
+            project
+                .primary_theme()?
+                .path
+                .trim_start_matches("./")
+                .to_owned(),
+        ),
+    };
+    let Some(text) = git::show(&project.root, rev, &path)? else {
+        bail!("{spec:?} is neither a theme file nor a revision with {path}");
+    };
+    Theme::parse(&text).with_context(|| format!("failed to parse {path} at {rev}"))
+}
+
+/// The files given, or the language demos, highlighted.
+fn documents(args: &RenderArgs) -> Result<Vec<Document>> {
+    let files = if args.files.is_empty() {
+        Project::current()?.demo_files()?
+    } else {
+        args.files.clone()
+    };
+    let grammars = Grammars::installed()?;
+    files.iter().map(|path| grammars.load(path)).collect()
+}
+
+/// Screenshot settings from the arguments, untitled.
+fn screenshot(args: &RenderArgs, font: Option<&Font>) -> Screenshot {
+    Screenshot {
+        font_size: args.font_size,
+        advance: font.map_or(DEFAULT_ADVANCE, |font| font.advance(1.0)),
+        padding: args.padding.max(0.0),
+        line_numbers: args.line_numbers,
+        title: None,
+        chrome: if args.workbench {
+            Chrome::Workbench
+        } else {
+            Chrome::Window
+        },
+        decorations: args.decorations,
+        minimap: args.minimap,
+    }
+}
+
+fn highlight(args: &RenderArgs, theme: &Theme, document: &Document) -> Highlighted {
+    if args.bracket_pairs {
+        Highlighted::with_bracket_pairs(theme, document)
+    } else {
+        Highlighted::new(theme, document)
+    }
+}
+
+/// How SVG text is drawn given `--font` and `--outline`.
+fn text<'a>(args: &RenderArgs, font: Option<&'a Font>) -> Text<'a> {
+    match font {
+        None => Text::Live,
+        Some(font) if args.outline => Text::Outlined(font),
+        Some(font) => Text::Embedded(font),
+    }
+}
+
+/// Writes a single-file rendering to `--output`, or stdout.
+fn output(args: &RenderArgs, contents: Vec<u8>) -> Result<()> {
+    match &args.output {
+        Some(path) => {
+            fs::write(path, contents)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            eprintln!("wrote {}", path.display());
+        }
+        None => io::stdout().write_all(&contents)?,
+    }
+    Ok(())
+}
+
+/// Parses `START-END`, or a single line number.
+fn parse_lines(text: &str) -> Result<RangeInclusive<usize>> {
+    let (start, end) = text.split_once('-').unwrap_or((text, text));
+    let parse = |n: &str| {
+        n.trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|n| *n > 0)
+            .with_context(|| format!("invalid line range {text:?}, expected START-END"))
+    };
+    let (start, end) = (parse(start)?, parse(end)?);
+    if start > end {
+        bail!("invalid line range {text:?}: {start} is after {end}");
+    }
+    Ok(start..=end)
+}
+
+fn write(dir: &Path, artifacts: &[Artifact]) -> Result<()> {
+    for artifact in artifacts {
+        let target = dir.join(&artifact.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        fs::write(&target, &artifact.contents)
+            .with_context(|| format!("failed to write {}", target.display()))?;
+    }
+    eprintln!("wrote {} files to {}", artifacts.len(), dir.display());
+    Ok(())
+}
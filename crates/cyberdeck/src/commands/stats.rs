@@ -0,0 +1,357 @@
+//! `cyberdeck stats`: how the theme spends its palette.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::Args;
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::color::Color;
+use crate::palette::{Palette, PaletteColor};
+use crate::theme::{ColorLocation, Theme};
+
+use super::{load_palette, load_theme, print_json};
+
+#[derive(Debug, Args)]
+pub struct StatsArgs {
+    /// Theme file to analyze (defaults to the primary contributed theme).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+    /// Palette file to compare against.
+    #[arg(long)]
+    pub palette: Option<PathBuf>,
+    /// Print JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+const BAR_WIDTH: usize = 40;
+const LIGHTNESS_BUCKETS: usize = 10;
+
+/// Reference counts for one color, split by the part of the theme using it.
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+struct Usage {
+    workbench: usize,
+    tokens: usize,
+    semantic: usize,
+}
+
+impl Usage {
+    fn total(self) -> usize {
+        self.workbench + self.tokens + self.semantic
+    }
+
+    fn record(&mut self, location: &ColorLocation) {
+        match location {
+            ColorLocation::Workbench(_) => self.workbench += 1,
+            ColorLocation::Token { .. } => self.tokens += 1,
+            ColorLocation::Semantic(_) => self.semantic += 1,
+        }
+    }
+}
+
+pub fn run(args: StatsArgs) -> Result<()> {
+    let (theme_path, theme) = load_theme(args.theme.as_deref())?;
+    let (palette_path, palette) = load_palette(args.palette.as_deref())?;
+    let report = Report::build(&theme);
+
+    if args.json {
+        return print_json(&report.to_json(&theme_path, &palette_path, &palette));
+    }
+    println!("Theme:   {}", theme_path.display());
+    println!("Palette: {}", palette_path.display());
+    println!();
+
+    report.print_palette_usage(&palette);
+    report.print_off_palette(&palette);
+    report.print_invalid();
+    report.print_areas();
+    report.print_hues();
+    report.print_lightness();
+    Ok(())
+}
+
+/// The `--json` schema.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StatsJson {
+    theme: String,
+    palette: String,
+    /// Palette entries the theme references, most used first.
+    palette_usage: Vec<PaletteUsageJson>,
+    unused: Vec<UnusedJson>,
+    off_palette: Vec<OffPaletteJson>,
+    invalid: Vec<InvalidJson>,
+    /// Workbench area → hue family → reference count.
+    areas: BTreeMap<String, BTreeMap<&'static str, usize>>,
+    /// Hue family → reference count, most used first.
+    hues: IndexMap<&'static str, usize>,
+    /// OKLCH lightness range → reference count.
+    lightness: IndexMap<String, usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct PaletteUsageJson {
+    name: String,
+    color: Color,
+    total: usize,
+    #[serde(flatten)]
+    usage: Usage,
+}
+
+#[derive(Debug, Serialize)]
+struct UnusedJson {
+    name: String,
+    color: Color,
+}
+
+#[derive(Debug, Serialize)]
+struct OffPaletteJson {
+    color: Color,
+    hue: &'static str,
+    refs: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct InvalidJson {
+    location: String,
+    value: String,
+}
+
+struct Report {
+    /// Usage keyed by opaque color, so alpha variants count toward their base.
+    usage: BTreeMap<Color, Usage>,
+    /// Workbench area (key prefix) → hue family → reference count.
+    areas: BTreeMap<String, BTreeMap<&'static str, usize>>,
+    /// Every parsed reference, alpha included.
+    refs: Vec<Color>,
+    invalid: Vec<(ColorLocation, String)>,
+}
+
+impl Report {
+    fn build(theme: &Theme) -> Report {
+        let mut report = Report {
+            usage: BTreeMap::new(),
+            areas: BTreeMap::new(),
+            refs: Vec::new(),
+            invalid: Vec::new(),
+        };
+        for color_ref in theme.color_refs() {
+            let color: Color = match color_ref.value.parse() {
+                Ok(color) => color,
+                Err(_) => {
+                    report
+                        .invalid
+                        .push((color_ref.location.clone(), color_ref.value.to_owned()));
+                    continue;
+                }
+            };
+            report
+                .usage
+                .entry(color.opaque())
+                .or_default()
+                .record(&color_ref.location);
+            if let ColorLocation::Workbench(key) = &color_ref.location {
+                let area = key.split('.').next().unwrap_or(key).to_owned();
+                *report
+                    .areas
+                    .entry(area)
+                    .or_default()
+                    .entry(color.hue_name())
+                    .or_default() += 1;
+            }
+            report.refs.push(color);
+        }
+        report
+    }
+
+    /// Every palette entry with its usage, most used first.
+    fn palette_rows<'p>(&self, palette: &'p Palette) -> Vec<(&'p PaletteColor, Usage)> {
+        let mut rows: Vec<_> = palette
+            .colors
+            .iter()
+            .map(|entry| {
+                let usage = self
+                    .usage
+                    .get(&entry.hex.opaque())
+                    .copied()
+                    .unwrap_or_default();
+                (entry, usage)
+            })
+            .collect();
+        rows.sort_by_key(|(_, usage)| Reverse(usage.total()));
+        rows
+    }
+
+    /// Colors the palette does not name, most used first.
+    fn off_palette(&self, palette: &Palette) -> Vec<(Color, Usage)> {
+        let mut off: Vec<_> = self
+            .usage
+            .iter()
+            .filter(|(color, _)| palette.find(**color).is_none())
+            .map(|(color, usage)| (*color, *usage))
+            .collect();
+        off.sort_by_key(|(_, usage)| Reverse(usage.total()));
+        off
+    }
+
+    fn hues(&self) -> IndexMap<&'static str, usize> {
+        let mut hues: IndexMap<&'static str, usize> = IndexMap::new();
+        for color in &self.refs {
+            *hues.entry(color.hue_name()).or_default() += 1;
+        }
+        hues.sort_by(|_, a, _, b| b.cmp(a));
+        hues
+    }
+
+    /// Reference counts by OKLCH lightness, labelled with their range.
+    fn lightness(&self) -> Vec<(String, usize)> {
+        let mut buckets = [0usize; LIGHTNESS_BUCKETS];
+        for color in &self.refs {
+            let l = color.to_oklch().l.clamp(0.0, 1.0);
+            let bucket = ((l * LIGHTNESS_BUCKETS as f64) as usize).min(LIGHTNESS_BUCKETS - 1);
+            buckets[bucket] += 1;
+        }
+        buckets
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                let lo = i as f64 / LIGHTNESS_BUCKETS as f64;
+                let hi = (i + 1) as f64 / LIGHTNESS_BUCKETS as f64;
+                (format!("{lo:.1}-{hi:.1}"), *n)
+            })
+            .collect()
+    }
+
+    fn to_json(&self, theme: &Path, palette_path: &Path, palette: &Palette) -> StatsJson {
+        let (used, unused): (Vec<_>, Vec<_>) = self
+            .palette_rows(palette)
+            .into_iter()
+            .partition(|(_, usage)| usage.total() > 0);
+        StatsJson {
+            theme: theme.display().to_string(),
+            palette: palette_path.display().to_string(),
+            palette_usage: used
+                .into_iter()
+                .map(|(entry, usage)| PaletteUsageJson {
+                    name: entry.name.clone(),
+                    color: entry.hex.opaque(),
+                    total: usage.total(),
+                    usage,
+                })
+                .collect(),
+            unused: unused
+                .into_iter()
+                .map(|(entry, _)| UnusedJson {
+                    name: entry.name.clone(),
+                    color: entry.hex.opaque(),
+                })
+                .collect(),
+            off_palette: self
+                .off_palette(palette)
+                .into_iter()
+                .map(|(color, usage)| OffPaletteJson {
+                    color,
+                    hue: color.hue_name(),
+                    refs: usage.total(),
+                })
+                .collect(),
+            invalid: self
+                .invalid
+                .iter()
+                .map(|(location, value)| InvalidJson {
+                    location: location.to_string(),
+                    value: value.clone(),
+                })
+                .collect(),
+            areas: self.areas.clone(),
+            hues: self.hues(),
+            lightness: self.lightness().into_iter().collect(),
+        }
+    }
+
+    fn print_palette_usage(&self, palette: &Palette) {
+        println!("Palette usage");
+        let rows = self.palette_rows(palette);
+        for (entry, usage) in rows.iter().filter(|(_, u)| u.total() > 0) {
+            println!(
+                "  {}  {:<22} {:>4} refs  (workbench {}, tokens {}, semantic {})",
+                entry.hex.to_hex_rgb(),
+                entry.name,
+                usage.total(),
+                usage.workbench,
+                usage.tokens,
+                usage.semantic,
+            );
+        }
+        println!();
+
+        let unused: Vec<_> = rows.iter().filter(|(_, u)| u.total() == 0).collect();
+        println!("Unused palette entries ({})", unused.len());
+        for (entry, _) in unused {
+            println!("  {}  {}", entry.hex.to_hex_rgb(), entry.name);
+        }
+        println!();
+    }
+
+    fn print_off_palette(&self, palette: &Palette) {
+        let off = self.off_palette(palette);
+        println!("Off-palette colors ({})", off.len());
+        for (color, usage) in off {
+            println!(
+                "  {}  {:<8} {:>4} refs",
+                color.to_hex_rgb(),
+                color.hue_name(),
+                usage.total()
+            );
+        }
+        println!();
+    }
+
+    fn print_invalid(&self) {
+        if self.invalid.is_empty() {
+            return;
+        }
+        println!("Unparseable color values ({})", self.invalid.len());
+        for (location, value) in &self.invalid {
+            println!("  {location}: {value:?}");
+        }
+        println!();
+    }
+
+    fn print_areas(&self) {
+        println!("Workbench areas by hue");
+        let width = self.areas.keys().map(String::len).max().unwrap_or(0);
+        for (area, hues) in &self.areas {
+            let mut hues: Vec<_> = hues.iter().collect();
+            hues.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+            let hues: Vec<String> = hues.iter().map(|(h, n)| format!("{h} {n}")).collect();
+            println!("  {area:<width$}  {}", hues.join(", "));
+        }
+        println!();
+    }
+
+    fn print_hues(&self) {
+        println!("Hue distribution (by reference)");
+        print_histogram(self.hues().iter().map(|(h, n)| (h.to_string(), *n)));
+        println!();
+    }
+
+    fn print_lightness(&self) {
+        println!("Lightness distribution (OKLCH L, by reference)");
+        print_histogram(self.lightness().into_iter());
+    }
+}
+
+fn print_histogram(rows: impl Iterator<Item = (String, usize)>) {
+    let rows: Vec<_> = rows.collect();
+    let max = rows.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+    let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    for (label, count) in rows {
+        let bar = "█".repeat(count * BAR_WIDTH / max);
+        println!("  {label:<width$}  {bar} {count}");
+    }
+}
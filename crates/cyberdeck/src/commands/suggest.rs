@@ -0,0 +1,175 @@
+//! `cyberdeck suggest`: propose colors for scopes the demos produce but the
+//! theme leaves unstyled.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::color::Color;
+use crate::coverage::{self, ScopeCoverage};
+use crate::highlight::Grammars;
+use crate::project::Project;
+use crate::suggest;
+use crate::theme::TokenRule;
+
+use super::{load_palette, load_theme, print_json};
+
+#[derive(Debug, Args)]
+pub struct SuggestArgs {
+    /// Files to scan (defaults to the language demos).
+    pub files: Vec<PathBuf>,
+    /// Theme file to check (defaults to the primary contributed theme).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+    /// Palette file to draw candidates from.
+    #[arg(long)]
+    pub palette: Option<PathBuf>,
+    /// Only print the tokenColors entries.
+    #[arg(long)]
+    pub entries: bool,
+    /// Print JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// The `--json` schema.
+#[derive(Debug, Serialize)]
+struct SuggestReport {
+    theme: String,
+    palette: String,
+    files: usize,
+    scopes: Vec<UnstyledScope>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UnstyledScope {
+    scope: String,
+    unstyled_tokens: usize,
+    tokens: usize,
+    /// Best first.
+    candidates: Vec<CandidateJson>,
+    /// The `tokenColors` entry for the best candidate.
+    rule: Option<TokenRule>,
+}
+
+#[derive(Debug, Serialize)]
+struct CandidateJson {
+    name: String,
+    color: Color,
+    reason: String,
+}
+
+pub fn run(args: SuggestArgs) -> Result<()> {
+    let (theme_path, theme) = load_theme(args.theme.as_deref())?;
+    let (palette_path, palette) = load_palette(args.palette.as_deref())?;
+    let files = if args.files.is_empty() {
+        Project::current()?.demo_files()?
+    } else {
+        args.files
+    };
+    let grammars = Grammars::installed()?;
+    let documents = files
+        .iter()
+        .map(|path| grammars.load(path))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Language-specific scopes are merged into their generic form, which is
+    // what a theme rule would target.
+    let mut unstyled: IndexMap<String, (usize, usize)> = IndexMap::new();
+    for ScopeCoverage {
+        generic,
+        tokens,
+        unstyled: missing,
+        ..
+    } in coverage::scan(&theme, &documents)
+    {
+        if missing > 0 {
+            let entry = unstyled.entry(generic).or_default();
+            entry.0 += missing;
+            entry.1 += tokens;
+        }
+    }
+    unstyled.sort_by(|a, _, b, _| a.cmp(b));
+
+    if args.json {
+        let scopes = unstyled
+            .iter()
+            .map(|(scope, (missing, tokens))| {
+                let candidates = suggest::candidates(&theme, &palette, scope);
+                UnstyledScope {
+                    scope: scope.clone(),
+                    unstyled_tokens: *missing,
+                    tokens: *tokens,
+                    rule: candidates
+                        .first()
+                        .map(|best| suggest::token_rule(scope, best.color)),
+                    candidates: candidates
+                        .iter()
+                        .map(|c| CandidateJson {
+                            name: c.color.name.clone(),
+                            color: c.color.hex,
+                            reason: c.reason.to_string(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        return print_json(&SuggestReport {
+            theme: theme_path.display().to_string(),
+            palette: palette_path.display().to_string(),
+            files: documents.len(),
+            scopes,
+        });
+    }
+
+    if !args.entries {
+        println!("Theme:   {}", theme_path.display());
+        println!("Palette: {}", palette_path.display());
+        println!();
+        println!(
+            "{} unstyled scopes in {} files",
+            unstyled.len(),
+            documents.len()
+        );
+    }
+
+    let mut rules = Vec::new();
+    for (scope, (missing, tokens)) in &unstyled {
+        let candidates = suggest::candidates(&theme, &palette, scope);
+        if !args.entries {
+            println!();
+            println!("{scope}  ({missing} of {tokens} tokens unstyled)");
+            if candidates.is_empty() {
+                println!("  no candidates");
+            }
+            for candidate in &candidates {
+                println!(
+                    "  {}  {:<20} {}",
+                    candidate.color.hex, candidate.color.name, candidate.reason
+                );
+            }
+        }
+        if let Some(best) = candidates.first() {
+            rules.push(suggest::token_rule(scope, best.color));
+        }
+    }
+
+    if rules.is_empty() {
+        return Ok(());
+    }
+    if !args.entries {
+        println!();
+        println!("tokenColors entries:");
+        println!();
+    }
+    for rule in &rules {
+        let json = serde_json::to_string_pretty(rule)?;
+        let indented: Vec<String> = json.lines().map(|line| format!("    {line}")).collect();
+        println!("{},", indented.join("\n"));
+    }
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! `cyberdeck tokens`: inspect how a file tokenizes and how the theme
+//! styles each token.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use crate::color::Color;
+use crate::highlight::Grammars;
+use crate::scope::Resolver;
+
+use super::{load_theme, print_json};
+
+#[derive(Debug, Args)]
+pub struct TokensArgs {
+    #[command(subcommand)]
+    pub command: TokensCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TokensCommand {
+    /// Print every token's scope stack and resolved style.
+    Dump(DumpArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct DumpArgs {
+    /// Source file to tokenize.
+    pub file: PathBuf,
+    /// Theme file to resolve styles with (defaults to the primary theme).
+    #[arg(long)]
+    pub theme: Option<PathBuf>,
+    /// Only show tokens with a scope containing this text.
+    #[arg(long, value_name = "TEXT")]
+    pub filter: Option<String>,
+    /// Print JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// One token in the dump; also the JSON schema.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DumpedToken<'a> {
+    line: usize,
+    /// 1-based, in characters.
+    column: usize,
+    text: &'a str,
+    scopes: &'a [String],
+    foreground: Option<Color>,
+    /// Only set when a rule gives the token its own background.
+    background: Option<Color>,
+    font_style: Option<String>,
+    /// The selector that decided the foreground, if any rule matched.
+    rule: Option<String>,
+}
+
+pub fn run(args: TokensArgs) -> Result<()> {
+    match args.command {
+        TokensCommand::Dump(args) => dump(args),
+    }
+}
+
+fn dump(args: DumpArgs) -> Result<()> {
+    let (_, theme) = load_theme(args.theme.as_deref())?;
+    let resolver = Resolver::new(&theme);
+    let grammars = Grammars::installed()?;
+    let document = grammars.load(&args.file)?;
+    if document.language.is_none() {
+        bail!(
+            "no grammar for {}; see `cyberdeck grammar fetch`",
+            args.file.display()
+        );
+    }
+
+    let mut tokens = Vec::new();
+    for (index, line) in document.lines.iter().enumerate() {
+        let mut column = 1;
+        for token in line {
+            let start = column;
+            column += token.text.chars().count();
+            if let Some(filter) = &args.filter {
+                if !token.scopes.iter().any(|s| s.contains(filter.as_str())) {
+                    continue;
+                }
+            }
+            let style = resolver.resolve(&token.scopes);
+            tokens.push(DumpedToken {
+                line: index + 1,
+                column: start,
+                text: &token.text,
+                scopes: &token.scopes,
+                foreground: style.foreground,
+                background: resolver.resolve_matched(&token.scopes).background,
+                font_style: style.font_style,
+                rule: resolver
+                    .foreground_match(&token.scopes)
+                    .map(|m| m.selector.to_string()),
+            });
+        }
+    }
+
+    if args.json {
+        return print_json(&tokens);
+    }
+    for token in &tokens {
+        let mut style = token
+            .foreground
+            .map_or_else(|| "-".to_owned(), |c| c.to_string());
+        if let Some(background) = token.background {
+            style.push_str(&format!(" on {background}"));
+        }
+        if let Some(font_style) = token.font_style.as_deref().filter(|s| !s.is_empty()) {
+            style.push_str(&format!(" {font_style}"));
+        }
+        println!(
+            "{}:{}  {:?}\n    {}\n    {style}  ({})",
+            token.line,
+            token.column,
+            token.text,
+            token.scopes.join(" "),
+            token.rule.as_deref().unwrap_or("default"),
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! `cyberdeck validate`: check theme files against VS Code's color-theme
+//! schema.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use serde::Serialize;
+
+use crate::lint::Severity;
+use crate::project::Project;
+use crate::schema::{self, Diagnostic};
+
+use super::print_json;
+
+#[derive(Debug, Args)]
+pub struct ValidateArgs {
+    /// Theme files to validate (defaults to every contributed theme).
+    pub files: Vec<PathBuf>,
+    /// VS Code version to check deprecations against, e.g. `1.104`
+    /// (defaults to the oldest `engines.vscode` in package.json admits).
+    #[arg(long, value_name = "VERSION")]
+    pub vscode: Option<String>,
+    /// Print JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// The `--json` schema.
+#[derive(Debug, Serialize)]
+struct ValidateReport {
+    files: Vec<FileReport>,
+    errors: usize,
+    warnings: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct FileReport {
+    path: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+pub fn run(args: ValidateArgs) -> Result<()> {
+    let project = Project::current();
+    let target = match &args.vscode {
+        Some(version) => Some(
+            schema::engine_version(version)
+                .with_context(|| format!("invalid VS Code version `{version}`"))?,
+        ),
+        None => project
+            .as_ref()
+            .ok()
+            .and_then(|project| project.manifest.engines.vscode.as_deref())
+            .and_then(schema::engine_version),
+    };
+    let files = if args.files.is_empty() {
+        let project = project?;
+        project
+            .themes()
+            .iter()
+            .map(|entry| project.theme_path(entry))
+            .collect()
+    } else {
+        args.files
+    };
+
+    let mut errors = 0;
+    let mut warnings = 0;
+    let mut reports = Vec::new();
+    for path in &files {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let diagnostics = schema::validate(&text, target);
+        for diagnostic in &diagnostics {
+            if !args.json {
+                println!("{}:{diagnostic}", path.display());
+            }
+            match diagnostic.severity {
+                Severity::Error => errors += 1,
+                Severity::Warn => warnings += 1,
+                Severity::Info | Severity::Off => {}
+            }
+        }
+        reports.push(FileReport {
+            path: path.display().to_string(),
+            diagnostics,
+        });
+    }
+
+    if args.json {
+        print_json(&ValidateReport {
+            files: reports,
+            errors,
+            warnings,
+        })?;
+    } else {
+        println!("{errors} error(s), {warnings} warning(s)");
+    }
+    if errors > 0 {
+        bail!("validation failed with {errors} error(s)");
+    }
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! `cyberdeck.toml`: project-wide settings for the tooling.
+//!
+//! ```toml
+//! palette = "palette/cyberdeck-2025.toml"
+//! variants = ["Cyberdeck 2025"]
+//!
+//! [export]
+//! targets = ["roles"]
+//! out-dir = "dist"
+//!
+//! [coverage]
+//! must-cover = ["editor.background", "terminal.ansi*"]
+//!
+//! [lint.rules.palette-only]
+//! severity = "warn"
+//!
+//! [profile.release.lint.rules.palette-only]
+//! severity = "error"
+//! ```
+//!
+//! A `[profile.<name>]` table has the same shape as the top level and is
+//! merged over it when the profile is selected with `--profile` or
+//! `CYBERDECK_PROFILE`: tables merge key by key, anything else is replaced.
+//! Command-line flags override both.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::lint::LintConfig;
+
+/// The configuration file name, looked up in the project root.
+pub const CONFIG_FILE: &str = "cyberdeck.toml";
+
+/// The profile chosen on the command line, which takes precedence over the
+/// environment.
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Selects the profile for the rest of the process. Called once, before any
+/// command runs.
+pub fn select_profile(name: String) {
+    // Only the CLI entry point sets this, so a second call cannot happen.
+    let _ = PROFILE.set(name);
+}
+
+/// The selected profile name, if any.
+pub fn active_profile() -> Option<String> {
+    PROFILE
+        .get()
+        .cloned()
+        .or_else(|| std::env::var("CYBERDECK_PROFILE").ok())
+        .filter(|name| !name.is_empty())
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    /// Palette file, relative to the project root.
+    #[serde(default)]
+    pub palette: Option<PathBuf>,
+    /// Theme variants the commands work on, by label or path; defaults to
+    /// every contributed theme.
+    #[serde(default)]
+    pub variants: Option<Vec<String>>,
+    #[serde(default)]
+    pub export: ExportConfig,
+    #[serde(default)]
+    pub coverage: CoverageConfig,
+    /// Lint rule settings, in the shape of `cyberdeck-lint.toml`.
+    #[serde(default)]
+    pub lint: Option<LintConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ExportConfig {
+    /// Targets `cyberdeck export` writes when none are given.
+    #[serde(default)]
+    pub targets: Vec<String>,
+    /// Output directory, relative to the project root.
+    #[serde(default)]
+    pub out_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct CoverageConfig {
+    /// Workbench color ids every variant must set, exactly or as a prefix
+    /// ending in `*`; `cyberdeck coverage` fails if one falls back to
+    /// VS Code's default.
+    #[serde(default)]
+    pub must_cover: Vec<String>,
+}
+
+impl Config {
+    /// Loads `cyberdeck.toml` from `root` with `profile` applied; a missing
+    /// file is the empty configuration, unless a profile was asked for.
+    pub fn load(root: &Path, profile: Option<&str>) -> Result<Config> {
+        let path = root.join(CONFIG_FILE);
+        if !path.is_file() {
+            if let Some(profile) = profile {
+                bail!("profile `{profile}` selected but there is no {CONFIG_FILE}");
+            }
+            return Ok(Config::default());
+        }
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let mut table: toml::Table =
+            toml::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))?;
+
+        let profiles = match table.remove("profile") {
+            Some(toml::Value::Table(profiles)) => profiles,
+            Some(_) => bail!("{}: `profile` must be a table", path.display()),
+            None => toml::Table::new(),
+        };
+        if let Some(name) = profile {
+            match profiles.get(name) {
+                Some(toml::Value::Table(overrides)) => merge(&mut table, overrides),
+                Some(_) => bail!("{}: `profile.{name}` must be a table", path.display()),
+                None => {
+                    let known: Vec<&str> = profiles.keys().map(String::as_str).collect();
+                    bail!(
+                        "{} has no profile `{name}` (available: {})",
+                        path.display(),
+                        if known.is_empty() {
+                            "none".to_owned()
+                        } else {
+                            known.join(", ")
+                        }
+                    );
+                }
+            }
+        }
+
+        Config::deserialize(toml::Value::Table(table))
+            .with_context(|| format!("failed to parse {}", path.display()))
+    }
+}
+
+/// Merges `overrides` into `base`: nested tables key by key, other values
+/// replaced.
+fn merge(base: &mut toml::Table, overrides: &toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overrides)) => {
+                merge(base, overrides)
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Which scopes a corpus of tokenized files produces, and whether the
+//! theme's `tokenColors` style them; and which workbench colors the theme
+//! leaves to VS Code's defaults.
+
+use indexmap::IndexMap;
+
+use crate::highlight::Document;
+use crate::schema::COLOR_IDS;
+use crate::scope::{scope_prefix, Resolver};
+use crate::theme::Theme;
+
+/// Scopes that mark structure rather than a kind of token; text directly
+/// inside them is meant to take the default foreground.
+const STRUCTURAL: [&str; 3] = ["source", "text", "meta"];
+
+/// How one innermost scope fares across the corpus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeCoverage {
+    /// The scope as the grammar emits it, e.g. `keyword.operator.go`.
+    pub scope: String,
+    /// The scope with its language suffix removed, e.g. `keyword.operator`.
+    pub generic: String,
+    /// Tokens whose innermost scope this is.
+    pub tokens: usize,
+    /// Of those, the tokens no rule gives a foreground.
+    pub unstyled: usize,
+}
+
+impl ScopeCoverage {
+    pub fn is_covered(&self) -> bool {
+        self.unstyled == 0
+    }
+}
+
+/// Scans the corpus, returning every innermost scope in order of first
+/// appearance. Unscoped text (files without a grammar), whitespace and
+/// structural scopes are skipped.
+pub fn scan(theme: &Theme, documents: &[Document]) -> Vec<ScopeCoverage> {
+    let resolver = Resolver::new(theme);
+    let mut scopes: IndexMap<String, ScopeCoverage> = IndexMap::new();
+    for token in documents.iter().flat_map(|d| d.lines.iter().flatten()) {
+        let Some(innermost) = token.scopes.last() else {
+            continue;
+        };
+        if token.text.trim().is_empty() || STRUCTURAL.iter().any(|s| scope_prefix(s, innermost)) {
+            continue;
+        }
+        let entry = scopes
+            .entry(innermost.clone())
+            .or_insert_with(|| ScopeCoverage {
+                scope: innermost.clone(),
+                generic: generic_scope(innermost, &token.scopes[0]),
+                tokens: 0,
+                unstyled: 0,
+            });
+        entry.tokens += 1;
+        if resolver.resolve_matched(&token.scopes).foreground.is_none() {
+            entry.unstyled += 1;
+        }
+    }
+    scopes.into_values().collect()
+}
+
+/// Drops the language suffix a grammar appends to its scopes, so
+/// `string.quoted.double.go` under `source.go` becomes
+/// `string.quoted.double`.
+pub fn generic_scope(scope: &str, root: &str) -> String {
+    let language = root.rsplit_once('.').map_or(root, |(_, last)| last);
+    scope
+        .strip_suffix(language)
+        .and_then(|s| s.strip_suffix('.'))
+        .filter(|s| !s.is_empty())
+        .unwrap_or(scope)
+        .to_owned()
+}
+
+/// The workbench color ids of one UI component, e.g. `statusBarItem`, split
+/// by whether the theme sets them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentCoverage {
+    /// The ids' first segment, or `base` for the undotted global colors
+    /// such as `foreground` and `focusBorder`.
+    pub component: &'static str,
+    pub set: Vec<&'static str>,
+    /// Ids that fall back to VS Code's defaults.
+    pub missing: Vec<&'static str>,
+}
+
+/// Every component in the color registry, in name order, with the ids the
+/// theme sets and the ones it doesn't. Keys the registry doesn't know are
+/// ignored here; `cyberdeck validate` reports them.
+pub fn workbench(theme: &Theme) -> Vec<ComponentCoverage> {
+    let mut components: IndexMap<&'static str, ComponentCoverage> = IndexMap::new();
+    for &id in COLOR_IDS {
+        let component = component(id);
+        let entry = components
+            .entry(component)
+            .or_insert_with(|| ComponentCoverage {
+                component,
+                set: Vec::new(),
+                missing: Vec::new(),
+            });
+        if theme.colors.contains_key(id) {
+            entry.set.push(id);
+        } else {
+            entry.missing.push(id);
+        }
+    }
+    let mut components: Vec<ComponentCoverage> = components.into_values().collect();
+    components.sort_by_key(|c| c.component);
+    components
+}
+
+/// The UI component a workbench color id belongs to.
+pub fn component(id: &str) -> &str {
+    id.split_once('.')
+        .map_or("base", |(component, _)| component)
+}
+
+/// Whether `id` is named by a must-cover `pattern`: an exact id, or a
+/// prefix followed by `*` such as `terminal.ansi*`.
+pub fn pattern_matches(pattern: &str, id: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => id.starts_with(prefix),
+        None => id == pattern,
+    }
+}
@@ -0,0 +1,137 @@
+//! The declarative language demo corpus.
+//!
+//! `demos/matrix.toml` lists the constructs a demo can show and, per
+//! language, which of them its demo covers. Each cell of the matrix is a
+//! template at `demos/templates/<language>/<construct>.<extension>`; a
+//! language's demo is its templates concatenated in construct order, each
+//! under a section banner.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+/// The matrix, relative to the project root.
+pub const MATRIX: &str = "demos/matrix.toml";
+/// The template directory, relative to the project root.
+pub const TEMPLATES_DIR: &str = "demos/templates";
+/// Marks a demo file as generated, and so safe to overwrite.
+pub const GENERATED_MARKER: &str = "Generated by `cyberdeck demos generate`";
+
+const BANNER_WIDTH: usize = 76;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Matrix {
+    #[serde(rename = "construct", default)]
+    pub constructs: Vec<Construct>,
+    #[serde(rename = "language", default)]
+    pub languages: Vec<Language>,
+}
+
+/// A language feature demos can show.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Construct {
+    pub id: String,
+    /// The section banner; constructs without one (imports, entry points)
+    /// are emitted bare.
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Language {
+    /// Template directory name, e.g. `python`.
+    pub id: String,
+    /// Display name, e.g. `Python`.
+    pub name: String,
+    /// Output file name inside `language_demos/`.
+    pub file: String,
+    /// Line comment prefix.
+    pub comment: String,
+    /// Constructs to include; defaults to every construct with a template.
+    #[serde(default)]
+    pub constructs: Option<Vec<String>>,
+}
+
+impl Matrix {
+    pub fn load(path: &Path) -> Result<Matrix> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let matrix: Matrix =
+            toml::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))?;
+        for language in &matrix.languages {
+            for id in language.constructs.iter().flatten() {
+                if matrix.construct(id).is_none() {
+                    bail!(
+                        "{}: language `{}` lists unknown construct `{id}`",
+                        path.display(),
+                        language.id
+                    );
+                }
+            }
+        }
+        Ok(matrix)
+    }
+
+    pub fn construct(&self, id: &str) -> Option<&Construct> {
+        self.constructs.iter().find(|c| c.id == id)
+    }
+
+    pub fn language(&self, id: &str) -> Option<&Language> {
+        self.languages.iter().find(|l| l.id == id)
+    }
+
+    /// Renders `language`'s demo from the templates under `templates`.
+    pub fn render(&self, templates: &Path, language: &Language) -> Result<String> {
+        let extension = Path::new(&language.file)
+            .extension()
+            .and_then(|e| e.to_str())
+            .with_context(|| format!("demo file `{}` has no extension", language.file))?;
+        let dir = templates.join(&language.id);
+        let template = |construct: &Construct| -> PathBuf {
+            dir.join(format!("{}.{extension}", construct.id))
+        };
+
+        let c = &language.comment;
+        let mut out = format!(
+            "{c} {name} Demo\n\
+             {c} This file demonstrates various {name} language features and idioms\n\
+             {c} {GENERATED_MARKER} from {MATRIX};\n\
+             {c} edit {TEMPLATES_DIR}/{id}/ instead.\n",
+            name = language.name,
+            id = language.id,
+        );
+        let banner = format!("{c} {}\n", "=".repeat(BANNER_WIDTH));
+
+        for construct in &self.constructs {
+            let path = template(construct);
+            let listed = language.constructs.as_ref();
+            if listed.is_some_and(|ids| !ids.contains(&construct.id)) {
+                continue;
+            }
+            if listed.is_none() && !path.is_file() {
+                continue;
+            }
+            let body = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            out.push('\n');
+            if let Some(title) = &construct.title {
+                out.push_str(&banner);
+                out.push_str(&format!("{c} {}\n", title.to_uppercase()));
+                out.push_str(&banner);
+                out.push('\n');
+            }
+            out.push_str(body.trim_matches('\n'));
+            out.push('\n');
+        }
+        Ok(out)
+    }
+}
+
+/// Whether a demo file was produced by the generator.
+pub fn is_generated(text: &str) -> bool {
+    text.lines()
+        .take(5)
+        .any(|line| line.contains(GENERATED_MARKER))
+}
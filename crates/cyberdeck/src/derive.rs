@@ -0,0 +1,155 @@
+//! Deriving theme variants: recoloring an existing theme so its palette
+//! roles take new colors.
+//!
+//! Every color of the base theme follows the base role color nearest to it
+//! in OKLab, moving by the same offset that role moved, so borders, hovers
+//! and other shades keep their relation to the role they were made from.
+//! Colors that are exactly a role's color become the new role color. Alpha
+//! is kept throughout.
+
+use indexmap::IndexMap;
+
+use crate::color::{Color, Oklab, Oklch, MID_LUMINANCE, NEUTRAL_CHROMA};
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+/// OKLab distance under which a color counts as exactly its anchor.
+const SAME_COLOR: f64 = 0.002;
+
+/// A base role color and what it becomes.
+struct Anchor {
+    from: Oklab,
+    to: Color,
+}
+
+/// `base` with its role colors replaced by `roles`, named `name`. Roles
+/// `roles` doesn't give keep their color, and so do the shades near them.
+pub fn derive_variant(base: &Theme, name: &str, roles: &IndexMap<&str, Color>) -> Theme {
+    let anchors: Vec<Anchor> = resolve_roles(base)
+        .into_iter()
+        .map(|(role, color)| Anchor {
+            from: color.opaque().to_oklab(),
+            to: roles.get(role).copied().unwrap_or(color).opaque(),
+        })
+        .collect();
+
+    let mut theme = base.clone();
+    theme.name = Some(name.to_owned());
+    theme.map_colors(|color| recolor(&anchors, color));
+    if let Some(background) = roles.get("background") {
+        let dark = background.relative_luminance() < MID_LUMINANCE;
+        theme.kind = Some(if dark { "dark" } else { "light" }.to_owned());
+    }
+    theme
+}
+
+fn recolor(anchors: &[Anchor], color: Color) -> Color {
+    let lab = color.opaque().to_oklab();
+    let Some((anchor, distance)) = anchors
+        .iter()
+        .map(|anchor| (anchor, anchor.from.distance(lab)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+    else {
+        return color;
+    };
+    let moved = if distance < SAME_COLOR {
+        anchor.to
+    } else {
+        let to = anchor.to.to_oklab();
+        Color::from_oklab(Oklab {
+            l: lab.l + to.l - anchor.from.l,
+            a: lab.a + to.a - anchor.from.a,
+            b: lab.b + to.b - anchor.from.b,
+        })
+    };
+    moved.with_alpha(color.a)
+}
+
+/// A color for each of `base`'s roles (as `resolve_roles` gives them), from
+/// a new background, foreground and set of accents. Neutral roles keep
+/// where their lightness sat between the base background and foreground;
+/// hued roles take the accent closest in hue, so strings stay the greenest
+/// color on offer and keywords the pinkest. Role alphas are kept.
+pub fn fit_roles(
+    base: &IndexMap<&'static str, Color>,
+    background: Color,
+    foreground: Color,
+    accents: &[Color],
+) -> IndexMap<&'static str, Color> {
+    let base_background = base.get("background").map(|c| c.to_oklch());
+    let base_foreground = base.get("foreground").map(|c| c.to_oklch());
+    let base_dark = base_background.is_none_or(|b| base_foreground.is_none_or(|f| b.l < f.l));
+    let background = background.opaque().to_oklab();
+    let foreground = foreground.opaque().to_oklab();
+    let dark = background.l < foreground.l;
+    let accents: Vec<Oklch> = accents.iter().map(|c| c.to_oklch()).collect();
+    base.iter()
+        .map(|(role, color)| {
+            let lch = color.to_oklch();
+            let assigned = if lch.c < NEUTRAL_CHROMA || matches!(*role, "background" | "foreground")
+            {
+                let t = match (base_background, base_foreground) {
+                    (Some(b), Some(f)) if (f.l - b.l).abs() > f64::EPSILON => {
+                        ((lch.l - b.l) / (f.l - b.l)).clamp(0.0, 1.0)
+                    }
+                    _ => 0.0,
+                };
+                let t = match *role {
+                    "background" => 0.0,
+                    "foreground" => 1.0,
+                    _ => t,
+                };
+                Color::from_oklab(Oklab {
+                    l: background.l + (foreground.l - background.l) * t,
+                    a: background.a + (foreground.a - background.a) * t,
+                    b: background.b + (foreground.b - background.b) * t,
+                })
+            } else {
+                let accent = accents
+                    .iter()
+                    .min_by(|a, b| hue_distance(a.h, lch.h).total_cmp(&hue_distance(b.h, lch.h)))
+                    .copied()
+                    .unwrap_or(lch);
+                // Lightness tuned against the base background still holds
+                // up against a background of the same polarity.
+                let l = if base_dark == dark { lch.l } else { accent.l };
+                Color::from_oklch(Oklch { l, ..accent })
+            };
+            (*role, assigned.with_alpha(color.a))
+        })
+        .collect()
+}
+
+/// A color for each of `base`'s roles: `named` ones as given, the rest
+/// fitted around them with [`fit_roles`], using the named background and
+/// foreground (or the base's) and `accents`. Opaque named colors take the
+/// base role's alpha, so translucent selections stay translucent.
+pub fn complete_roles(
+    base: &IndexMap<&'static str, Color>,
+    named: &IndexMap<&'static str, Color>,
+    accents: &[Color],
+) -> IndexMap<&'static str, Color> {
+    let role = |name: &str| {
+        named
+            .get(name)
+            .or_else(|| base.get(name))
+            .copied()
+            .unwrap_or(Color::rgb(0, 0, 0))
+    };
+    let mut roles = fit_roles(base, role("background"), role("foreground"), accents);
+    for (name, color) in named {
+        if let Some(slot) = roles.get_mut(name) {
+            *slot = if color.is_opaque() {
+                color.with_alpha(slot.a)
+            } else {
+                *color
+            };
+        }
+    }
+    roles
+}
+
+fn hue_distance(a: f64, b: f64) -> f64 {
+    let d = (a - b).rem_euclid(360.0);
+    d.min(360.0 - d)
+}
@@ -0,0 +1,157 @@
+//! Structured differences between two versions of a theme.
+//!
+//! Workbench and semantic colors are compared by key, token rules by each of
+//! their scope selectors, so reordering or regrouping rules is not a change.
+
+use indexmap::IndexMap;
+
+use crate::theme::{SemanticStyle, Theme, TokenSettings};
+
+/// One key whose value was added, removed or changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub key: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl Change {
+    pub fn is_added(&self) -> bool {
+        self.old.is_none()
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.new.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThemeDiff {
+    pub colors: Vec<Change>,
+    pub token_colors: Vec<Change>,
+    pub semantic_token_colors: Vec<Change>,
+}
+
+impl ThemeDiff {
+    pub fn between(old: &Theme, new: &Theme) -> ThemeDiff {
+        ThemeDiff {
+            colors: diff_maps(&old.colors, &new.colors),
+            token_colors: diff_maps(&token_styles(old), &token_styles(new)),
+            semantic_token_colors: diff_maps(&semantic_styles(old), &semantic_styles(new)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+            && self.token_colors.is_empty()
+            && self.semantic_token_colors.is_empty()
+    }
+
+    /// Every change, with the section it belongs to.
+    pub fn changes(&self) -> impl Iterator<Item = (&'static str, &Change)> {
+        self.colors
+            .iter()
+            .map(|c| ("colors", c))
+            .chain(self.token_colors.iter().map(|c| ("tokenColors", c)))
+            .chain(
+                self.semantic_token_colors
+                    .iter()
+                    .map(|c| ("semanticTokenColors", c)),
+            )
+    }
+}
+
+/// The effective settings per selector; a later rule's settings win, as
+/// they would in VS Code.
+fn token_styles(theme: &Theme) -> IndexMap<String, String> {
+    let mut styles = IndexMap::new();
+    for rule in &theme.token_colors {
+        let selectors = rule.scope.selectors();
+        let selectors = if selectors.is_empty() {
+            vec!["(default)"]
+        } else {
+            selectors
+        };
+        for selector in selectors {
+            styles.insert(selector.to_owned(), describe_settings(&rule.settings));
+        }
+    }
+    styles
+}
+
+fn semantic_styles(theme: &Theme) -> IndexMap<String, String> {
+    theme
+        .semantic_token_colors
+        .iter()
+        .map(|(selector, style)| {
+            let description = match style {
+                SemanticStyle::Color(color) => color.to_lowercase(),
+                SemanticStyle::Style(style) => {
+                    let mut parts = Vec::new();
+                    if let Some(fg) = &style.foreground {
+                        parts.push(fg.to_lowercase());
+                    }
+                    if let Some(font_style) = &style.font_style {
+                        parts.push(font_style.clone());
+                    }
+                    let flags = [
+                        ("bold", style.bold),
+                        ("italic", style.italic),
+                        ("underline", style.underline),
+                        ("strikethrough", style.strikethrough),
+                    ];
+                    for (flag, value) in flags {
+                        match value {
+                            Some(true) => parts.push(flag.to_owned()),
+                            Some(false) => parts.push(format!("no {flag}")),
+                            None => {}
+                        }
+                    }
+                    parts.join(" ")
+                }
+            };
+            (selector.clone(), description)
+        })
+        .collect()
+}
+
+fn describe_settings(settings: &TokenSettings) -> String {
+    let mut parts = Vec::new();
+    if let Some(fg) = &settings.foreground {
+        parts.push(fg.to_lowercase());
+    }
+    if let Some(bg) = &settings.background {
+        parts.push(format!("on {}", bg.to_lowercase()));
+    }
+    match settings.font_style.as_deref() {
+        Some("") => parts.push("normal".to_owned()),
+        Some(style) => parts.push(style.to_owned()),
+        None => {}
+    }
+    parts.join(" ")
+}
+
+fn diff_maps(old: &IndexMap<String, String>, new: &IndexMap<String, String>) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for (key, value) in new {
+        let previous = old.get(key);
+        if previous.map(|p| p.to_lowercase()) != Some(value.to_lowercase()) {
+            changes.push(Change {
+                key: key.clone(),
+                old: previous.cloned(),
+                new: Some(value.clone()),
+            });
+        }
+    }
+    for (key, value) in old {
+        if !new.contains_key(key) {
+            changes.push(Change {
+                key: key.clone(),
+                old: Some(value.clone()),
+                new: None,
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.key.cmp(&b.key));
+    changes
+}
@@ -0,0 +1,227 @@
+//! `ace`: an Ace editor theme.
+//!
+//! - `theme-<slug>.js`, the `ace/theme/<slug>` module Ace loads for
+//!   `editor.setTheme("ace/theme/<slug>")`, with the stylesheet inlined as
+//!   Ace's own themes have it.
+//! - `<slug>.css`, the same stylesheet for pages that bundle styles
+//!   separately.
+//!
+//! Ace's token classes follow TextMate scope names (`ace_constant
+//! ace_numeric`), so each is styled after the matching scope path.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::highlightjs::rule;
+use super::{is_dark, theme_name, theme_slug, workbench_color, Artifact, Exporter, TokenStyle};
+
+/// A declaration template, with `{}` standing for the color, and the
+/// workbench keys that color reads.
+type Declaration = (&'static str, &'static [&'static str]);
+
+/// Editor chrome selectors, below the theme class, and their declarations.
+const CHROME: &[(&str, &[Declaration])] = &[
+    (
+        "",
+        &[
+            ("background-color: {};", &["editor.background"]),
+            ("color: {};", &["editor.foreground", "foreground"]),
+        ],
+    ),
+    (
+        " .ace_gutter",
+        &[
+            (
+                "background: {};",
+                &["editorGutter.background", "editor.background"],
+            ),
+            ("color: {};", &["editorLineNumber.foreground"]),
+        ],
+    ),
+    (
+        " .ace_gutter-active-line",
+        &[("background-color: {};", &["editor.lineHighlightBackground"])],
+    ),
+    (
+        " .ace_print-margin",
+        &[(
+            "background: {};",
+            &["editorRuler.foreground", "editorGroup.border"],
+        )],
+    ),
+    (
+        " .ace_cursor",
+        &[("color: {};", &["editorCursor.foreground"])],
+    ),
+    (
+        " .ace_marker-layer .ace_selection",
+        &[("background: {};", &["editor.selectionBackground"])],
+    ),
+    (
+        ".ace_multiselect .ace_selection.ace_start",
+        &[("box-shadow: 0 0 3px 0 {};", &["editor.background"])],
+    ),
+    (
+        " .ace_marker-layer .ace_step",
+        &[("background: {};", &["editor.findMatchBackground"])],
+    ),
+    (
+        " .ace_marker-layer .ace_bracket",
+        &[(
+            "margin: -1px 0 0 -1px;\n  border: 1px solid {};",
+            &["editorBracketMatch.border"],
+        )],
+    ),
+    (
+        " .ace_marker-layer .ace_active-line",
+        &[("background: {};", &["editor.lineHighlightBackground"])],
+    ),
+    (
+        " .ace_marker-layer .ace_selected-word",
+        &[(
+            "border: 1px solid {};",
+            &[
+                "editor.selectionHighlightBorder",
+                "editor.selectionHighlightBackground",
+            ],
+        )],
+    ),
+    (
+        " .ace_invisible",
+        &[("color: {};", &["editorWhitespace.foreground"])],
+    ),
+    (
+        " .ace_indent-guide",
+        &[(
+            "box-shadow: inset -1px 0 0 {};",
+            &[
+                "editorIndentGuide.background1",
+                "editorIndentGuide.background",
+            ],
+        )],
+    ),
+    (
+        " .ace_fold",
+        &[
+            ("background-color: {};", &["editor.foldBackground"]),
+            ("border-color: {};", &["editor.foreground", "foreground"]),
+        ],
+    ),
+];
+
+/// Ace token classes and the scope path each is resolved from.
+const TOKENS: &[(&str, &str)] = &[
+    ("comment", "comment.line"),
+    ("comment.doc", "comment.block.documentation"),
+    ("keyword", "keyword.control"),
+    ("keyword.operator", "keyword.operator"),
+    ("storage", "storage.modifier"),
+    ("storage.type", "storage.type"),
+    ("constant", "constant"),
+    ("constant.language", "constant.language"),
+    ("constant.numeric", "constant.numeric"),
+    ("constant.character", "constant.character"),
+    ("constant.character.escape", "constant.character.escape"),
+    ("string", "string.quoted.double"),
+    ("string.regexp", "string.regexp"),
+    ("variable", "variable.other.readwrite"),
+    ("variable.parameter", "variable.parameter"),
+    ("variable.language", "variable.language"),
+    ("support.function", "support.function"),
+    ("support.type", "support.type.primitive"),
+    ("support.class", "support.class"),
+    ("support.constant", "support.constant"),
+    ("entity.name.function", "entity.name.function"),
+    ("entity.name.tag", "entity.name.tag"),
+    ("entity.other.attribute-name", "entity.other.attribute-name"),
+    ("meta.tag", "meta.tag punctuation.definition.tag"),
+    ("punctuation", "punctuation"),
+    ("paren", "punctuation.section.parens"),
+    ("heading", "markup.heading"),
+    ("markup.heading", "markup.heading"),
+    ("markup.list", "markup.list"),
+    ("markup.underline", "markup.underline"),
+    ("invalid", "invalid.illegal"),
+    ("invalid.deprecated", "invalid.deprecated"),
+];
+
+pub struct AceExporter;
+
+impl Exporter for AceExporter {
+    fn name(&self) -> &'static str {
+        "ace"
+    }
+
+    fn extension(&self) -> &'static str {
+        "js"
+    }
+
+    fn description(&self) -> &'static str {
+        "Ace editor theme module and stylesheet"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let slug = theme_slug(theme);
+        let class = format!("ace-{slug}");
+        let css = stylesheet(theme, &class);
+
+        let module = format!(
+            "// {} for Ace.\n\
+             // Generated by `cyberdeck export --target ace`; do not edit.\n\
+             ace.define(\"ace/theme/{slug}\", [\"require\", \"exports\", \"module\", \"ace/lib/dom\"], \
+             function (require, exports, module) {{\n  \
+             exports.isDark = {};\n  \
+             exports.cssClass = {};\n  \
+             exports.cssText = {};\n  \
+             var dom = require(\"ace/lib/dom\");\n  \
+             dom.importCssString(exports.cssText, exports.cssClass, false);\n\
+             }});\n",
+            theme_name(theme),
+            is_dark(theme),
+            serde_json::Value::from(class.as_str()),
+            serde_json::Value::from(css.as_str()),
+        );
+        Ok(vec![
+            Artifact::new(format!("theme-{slug}.js"), module),
+            Artifact::new(format!("{slug}.css"), css),
+        ])
+    }
+}
+
+fn stylesheet(theme: &Theme, class: &str) -> String {
+    let resolver = Resolver::new(theme);
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "/* {}; generated by `cyberdeck export --target ace`; do not edit. */\n",
+        theme_name(theme)
+    );
+    for (selector, properties) in CHROME {
+        let declarations: Vec<String> = properties
+            .iter()
+            .filter_map(|(template, keys)| {
+                let color = workbench_color(theme, keys)?;
+                Some(template.replace("{}", &color.to_string()))
+            })
+            .collect();
+        rule(&mut out, &format!(".{class}{selector}"), &declarations);
+    }
+    for (token, scope) in TOKENS {
+        if let Some(style) = TokenStyle::resolve(&resolver, scope) {
+            let classes: String = token
+                .split('.')
+                .map(|part| format!(".ace_{part}"))
+                .collect();
+            rule(
+                &mut out,
+                &format!(".{class} {classes}"),
+                &style.css_declarations(),
+            );
+        }
+    }
+    format!("{}\n", out.trim_end())
+}
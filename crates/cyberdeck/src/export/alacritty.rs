@@ -0,0 +1,67 @@
+//! `alacritty`: Alacritty's color section (`<slug>.toml`, to `import` from
+//! `alacritty.toml`).
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter, TerminalColors, ANSI_NAMES};
+
+pub struct AlacrittyExporter;
+
+impl Exporter for AlacrittyExporter {
+    fn name(&self) -> &'static str {
+        "alacritty"
+    }
+
+    fn extension(&self) -> &'static str {
+        "toml"
+    }
+
+    fn description(&self) -> &'static str {
+        "Alacritty terminal colors"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let colors = TerminalColors::new(theme);
+        let hex = |color: Color| color.to_hex_rgb();
+
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}", theme_name(theme));
+        out.push_str("# Generated by `cyberdeck export --target alacritty`; do not edit.\n");
+        let _ = write!(
+            out,
+            "\n[colors.primary]\nbackground = \"{}\"\nforeground = \"{}\"\n",
+            hex(colors.background),
+            hex(colors.foreground)
+        );
+        let _ = write!(
+            out,
+            "\n[colors.cursor]\ntext = \"{}\"\ncursor = \"{}\"\n",
+            hex(colors.cursor_text),
+            hex(colors.cursor)
+        );
+        // `CellForeground` keeps the selected text's own color.
+        let selection_text = colors
+            .selection_foreground
+            .map_or_else(|| "CellForeground".to_owned(), hex);
+        let _ = write!(
+            out,
+            "\n[colors.selection]\ntext = \"{selection_text}\"\nbackground = \"{}\"\n",
+            hex(colors.selection_background)
+        );
+        for (table, ansi) in [("normal", &colors.ansi[..8]), ("bright", &colors.ansi[8..])] {
+            let _ = writeln!(out, "\n[colors.{table}]");
+            for (name, color) in ANSI_NAMES.iter().zip(ansi) {
+                let _ = writeln!(out, "{name} = \"{}\"", hex(*color));
+            }
+        }
+        Ok(vec![Artifact::new(
+            format!("{}.toml", theme_slug(theme)),
+            out,
+        )])
+    }
+}
@@ -0,0 +1,160 @@
+//! `base16`: the theme as a base16 scheme (`<slug>.base16.yaml`) and a
+//! base24 scheme (`<slug>.base24.yaml`), in the tinted-theming layout, for
+//! the base16 template ecosystem.
+//!
+//! The neutral slots come from the palette roles, with the extra-light
+//! foregrounds (`base06`, `base07`) and base24's extra-dark backgrounds
+//! (`base10`, `base11`) stepped from the foreground and background in
+//! OKLCH lightness. The accents follow the terminal's ANSI colors when the
+//! theme defines them, which is what base24 specifies and what terminal
+//! templates assume, and the syntax roles otherwise.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{ansi_colors, is_dark, theme_name, theme_slug, Artifact, Exporter, AUTHOR};
+
+/// Neutral slots and the roles they read, first present wins.
+const NEUTRALS: &[(&str, &[&str])] = &[
+    ("base00", &["background"]),
+    ("base01", &["background.secondary", "background"]),
+    ("base02", &["highlight"]),
+    ("base03", &["comment", "foreground.muted"]),
+    ("base04", &["foreground.muted", "foreground.secondary"]),
+    ("base05", &["foreground"]),
+];
+
+/// Accent slots, the ANSI color each takes (`None` for base16's orange and
+/// brown, which the terminal has no slot for) and the roles read without
+/// ANSI colors or for those two.
+const ACCENTS: &[(&str, Option<usize>, &[&str])] = &[
+    ("base08", Some(1), &["error", "variable"]),
+    ("base09", None, &["storage", "number"]),
+    ("base0A", Some(3), &["warning", "class"]),
+    ("base0B", Some(2), &["success", "string"]),
+    ("base0C", Some(6), &["type", "cursor"]),
+    ("base0D", Some(4), &["function", "accent.primary"]),
+    ("base0E", Some(5), &["keyword", "accent.secondary"]),
+    ("base0F", None, &["deleted", "constant"]),
+];
+
+/// base24's bright accents and the ANSI color each takes; without ANSI
+/// colors they repeat the base16 accent.
+const BRIGHTS: &[(&str, usize, &str)] = &[
+    ("base12", 9, "base08"),
+    ("base13", 11, "base0A"),
+    ("base14", 10, "base0B"),
+    ("base15", 14, "base0C"),
+    ("base16", 12, "base0D"),
+    ("base17", 13, "base0E"),
+];
+
+pub struct Base16Exporter;
+
+impl Exporter for Base16Exporter {
+    fn name(&self) -> &'static str {
+        "base16"
+    }
+
+    fn extension(&self) -> &'static str {
+        "yaml"
+    }
+
+    fn description(&self) -> &'static str {
+        "base16 and base24 schemes"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let roles = resolve_roles(theme);
+        let background = roles
+            .get("background")
+            .map_or(Color::rgb(0, 0, 0), |c| c.opaque());
+        let role = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| roles.get(name))
+                .map(|c| c.over(background))
+        };
+        let ansi = ansi_colors(theme).map(|colors| colors.map(|c| c.over(background)));
+        let dark = is_dark(theme);
+        let foreground = role(&["foreground"]).unwrap_or(if dark {
+            Color::rgb(0xff, 0xff, 0xff)
+        } else {
+            Color::rgb(0, 0, 0)
+        });
+        // Lightness the foreground steps toward, and the background away from.
+        let (light, deep) = if dark { (1.0, 0.0) } else { (0.0, 1.0) };
+
+        let mut base16: IndexMap<&str, Color> = IndexMap::new();
+        for (slot, names) in NEUTRALS {
+            base16.insert(slot, role(names).unwrap_or(background));
+        }
+        base16.insert("base05", foreground);
+        base16.insert("base06", toward(foreground, light, 1.0 / 3.0));
+        base16.insert("base07", toward(foreground, light, 2.0 / 3.0));
+        for (slot, index, names) in ACCENTS {
+            let color = index
+                .zip(ansi)
+                .map(|(index, ansi)| ansi[index])
+                .or_else(|| role(names))
+                .unwrap_or(foreground);
+            base16.insert(slot, color);
+        }
+
+        let mut base24 = base16.clone();
+        base24.insert("base10", toward(background, deep, 1.0 / 3.0));
+        base24.insert("base11", toward(background, deep, 2.0 / 3.0));
+        for (slot, index, fallback) in BRIGHTS {
+            let color = match ansi {
+                Some(ansi) => ansi[*index],
+                None => base16[fallback],
+            };
+            base24.insert(slot, color);
+        }
+
+        let slug = theme_slug(theme);
+        Ok(vec![
+            Artifact::new(
+                format!("{slug}.base16.yaml"),
+                scheme(theme, "base16", &base16),
+            ),
+            Artifact::new(
+                format!("{slug}.base24.yaml"),
+                scheme(theme, "base24", &base24),
+            ),
+        ])
+    }
+}
+
+/// A scheme file in the tinted-theming layout.
+fn scheme(theme: &Theme, system: &str, palette: &IndexMap<&str, Color>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# Generated by `cyberdeck export --target base16`; do not edit.\n\
+         system: \"{system}\"\n\
+         name: {}\n\
+         author: \"{AUTHOR}\"\n\
+         variant: \"{}\"\n\
+         palette:",
+        serde_json::Value::from(theme_name(theme)),
+        if is_dark(theme) { "dark" } else { "light" }
+    );
+    for (slot, color) in palette {
+        let _ = writeln!(out, "  {slot}: \"{}\"", color.to_hex_rgb());
+    }
+    out
+}
+
+/// `color` with its OKLCH lightness moved `amount` of the way to `target`.
+fn toward(color: Color, target: f64, amount: f64) -> Color {
+    let mut lch = color.to_oklch();
+    lch.l += (target - lch.l) * amount;
+    Color::from_oklch(lch)
+}
@@ -0,0 +1,220 @@
+//! `bat`: a bundle for terminal file viewing and diffs.
+//!
+//! - `<name>.tmTheme`, the theme as a TextMate plist for bat's (and
+//!   delta's) syntax highlighter. Like the `sublime` target it keeps every
+//!   `tokenColors` rule and adds Sublime's scope names, since bat ships
+//!   Sublime syntaxes.
+//! - `config`, a bat config selecting it.
+//! - `delta.gitconfig`, a `[delta]` section to `[include]` from
+//!   `~/.gitconfig`, with diff colors from the workbench's diff editor.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::sublime::{with_translations, FONT_STYLES};
+use super::{theme_name, workbench_color, xml_escape, Artifact, Exporter};
+
+/// tmTheme global settings and the workbench keys they read.
+const GLOBALS: &[(&str, &[&str])] = &[
+    ("background", &["editor.background"]),
+    ("foreground", &["editor.foreground", "foreground"]),
+    ("caret", &["editorCursor.foreground"]),
+    ("lineHighlight", &["editor.lineHighlightBackground"]),
+    ("selection", &["editor.selectionBackground"]),
+    ("inactiveSelection", &["editor.inactiveSelectionBackground"]),
+    ("gutter", &["editorGutter.background", "editor.background"]),
+    ("gutterForeground", &["editorLineNumber.foreground"]),
+    ("findHighlight", &["editor.findMatchBackground"]),
+    ("invisibles", &["editorWhitespace.foreground"]),
+    (
+        "guide",
+        &[
+            "editorIndentGuide.background1",
+            "editorIndentGuide.background",
+        ],
+    ),
+    ("accent", &["activityBar.activeBorder", "focusBorder"]),
+];
+
+/// One side of a diff: delta's style prefix and the workbench keys of its
+/// line background, changed-text background and gutter color.
+const DIFF_SIDES: &[(&str, &str, &str, &str)] = &[
+    (
+        "minus",
+        "diffEditor.removedLineBackground",
+        "diffEditor.removedTextBackground",
+        "editorGutter.deletedBackground",
+    ),
+    (
+        "plus",
+        "diffEditor.insertedLineBackground",
+        "diffEditor.insertedTextBackground",
+        "editorGutter.addedBackground",
+    ),
+];
+
+pub struct BatExporter;
+
+impl Exporter for BatExporter {
+    fn name(&self) -> &'static str {
+        "bat"
+    }
+
+    fn extension(&self) -> &'static str {
+        "tmTheme"
+    }
+
+    fn description(&self) -> &'static str {
+        "bat syntax theme with bat config and delta settings"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let name = theme_name(theme);
+        let config = format!(
+            "# {name} for bat; copy to `$(bat --config-dir)/config`, then put the\n\
+             # .tmTheme in `$(bat --config-dir)/themes` and run `bat cache --build`.\n\
+             # Generated by `cyberdeck export --target bat`; do not edit.\n\
+             --theme=\"{name}\"\n\
+             --italic-text=always\n"
+        );
+        Ok(vec![
+            Artifact::new(format!("{name}.tmTheme"), tm_theme(theme)),
+            Artifact::new("config", config),
+            Artifact::new("delta.gitconfig", delta(theme)),
+        ])
+    }
+}
+
+fn tm_theme(theme: &Theme) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+         \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n<dict>\n\
+         \t<key>name</key>\n\t<string>{}</string>\n\
+         \t<key>settings</key>\n\t<array>\n\
+         \t\t<dict>\n\t\t\t<key>settings</key>\n\t\t\t<dict>\n",
+        xml_escape(theme_name(theme))
+    );
+    for (key, sources) in GLOBALS {
+        if let Some(color) = workbench_color(theme, sources) {
+            entry(&mut out, 4, key, &color.to_string());
+        }
+    }
+    out.push_str("\t\t\t</dict>\n\t\t</dict>\n");
+
+    for rule in &theme.token_colors {
+        let selectors = rule.scope.selectors();
+        if selectors.is_empty() {
+            continue;
+        }
+        let color = |value: &Option<String>| value.as_deref()?.parse::<Color>().ok();
+        let mut settings = Vec::new();
+        if let Some(foreground) = color(&rule.settings.foreground) {
+            settings.push(("foreground", foreground.to_string()));
+        }
+        if let Some(background) = color(&rule.settings.background) {
+            settings.push(("background", background.to_string()));
+        }
+        if let Some(font_style) = &rule.settings.font_style {
+            let styles: Vec<&str> = font_style
+                .split_whitespace()
+                .filter(|s| FONT_STYLES.contains(s))
+                .collect();
+            settings.push(("fontStyle", styles.join(" ")));
+        }
+        if settings.is_empty() {
+            continue;
+        }
+
+        out.push_str("\t\t<dict>\n");
+        if let Some(name) = &rule.name {
+            entry(&mut out, 3, "name", name);
+        }
+        entry(
+            &mut out,
+            3,
+            "scope",
+            &with_translations(&selectors).join(", "),
+        );
+        out.push_str("\t\t\t<key>settings</key>\n\t\t\t<dict>\n");
+        for (key, value) in &settings {
+            entry(&mut out, 4, key, value);
+        }
+        out.push_str("\t\t\t</dict>\n\t\t</dict>\n");
+    }
+    out.push_str("\t</array>\n</dict>\n</plist>\n");
+    out
+}
+
+/// A `<key>`/`<string>` pair, indented `depth` tabs.
+fn entry(out: &mut String, depth: usize, key: &str, value: &str) {
+    let indent = "\t".repeat(depth);
+    let _ = writeln!(
+        out,
+        "{indent}<key>{}</key>\n{indent}<string>{}</string>",
+        xml_escape(key),
+        xml_escape(value)
+    );
+}
+
+fn delta(theme: &Theme) -> String {
+    let background = workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+    let color = |keys: &[&str]| workbench_color(theme, keys).map(|c| c.over(background));
+    let hex = |color: Color| format!("\"{}\"", color.to_hex_rgb());
+    let name = theme_name(theme);
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "# {name} for delta; `[include]` this from ~/.gitconfig.\n\
+         # Generated by `cyberdeck export --target bat`; do not edit.\n\
+         [delta]\n    \
+         syntax-theme = {name}\n    \
+         line-numbers = true\n"
+    );
+    for (side, line, text, gutter) in DIFF_SIDES {
+        // Themes often only color the changed text; the line gets a fainter
+        // version of it, and failing both, of the gutter marker.
+        let emph = workbench_color(theme, &[text])
+            .or_else(|| workbench_color(theme, &[gutter]).map(|c| c.with_alpha(0x50)));
+        let line = workbench_color(theme, &[line]).or(emph.map(|c| c.with_alpha(c.a / 2)));
+        if let (Some(line), Some(emph)) = (line, emph) {
+            let _ = writeln!(
+                out,
+                "    {side}-style = syntax {}\n    {side}-emph-style = syntax {}",
+                hex(line.over(background)),
+                hex(emph.over(background))
+            );
+        }
+        if let Some(marker) = color(&[gutter]) {
+            let _ = writeln!(out, "    line-numbers-{side}-style = {}", hex(marker));
+        }
+    }
+    if let Some(muted) = color(&["editorLineNumber.foreground"]) {
+        let _ = writeln!(out, "    line-numbers-zero-style = {}", hex(muted));
+    }
+    if let Some(accent) = color(&["activityBar.activeBorder", "focusBorder"]) {
+        let _ = writeln!(
+            out,
+            "    file-style = {} bold\n    file-decoration-style = {} ul",
+            hex(accent),
+            hex(accent)
+        );
+    }
+    if let Some(border) = color(&["editorGroup.border", "panel.border"]) {
+        let _ = writeln!(
+            out,
+            "    hunk-header-style = file line-number syntax\n    \
+             hunk-header-decoration-style = {} box",
+            hex(border)
+        );
+    }
+    out
+}
@@ -0,0 +1,117 @@
+//! `btop`: system monitor colors.
+//!
+//! - `<slug>.theme`, a btop theme for `~/.config/btop/themes`, from the
+//!   palette roles; meters and graphs are three-stop gradients between
+//!   roles.
+//! - `<slug>.htoprc`, the htop setting to merge into `~/.config/htop/htoprc`.
+//!   htop has no custom colors: its schemes draw with the terminal's 16 ANSI
+//!   colors, so the mapping picks the scheme that suits the theme's
+//!   background and leaves the palette to a terminal target.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{is_dark, theme_name, theme_slug, Artifact, Exporter};
+
+/// Single-color `theme[...]` keys and the roles they read.
+const COLORS: &[(&str, &[&str])] = &[
+    ("main_bg", &["background"]),
+    ("main_fg", &["foreground"]),
+    ("title", &["foreground"]),
+    ("hi_fg", &["accent.primary"]),
+    ("selected_bg", &["highlight"]),
+    ("selected_fg", &["accent.secondary", "foreground"]),
+    ("inactive_fg", &["foreground.muted", "comment"]),
+    ("graph_text", &["foreground.secondary", "foreground"]),
+    ("meter_bg", &["border"]),
+    ("proc_misc", &["function", "accent.primary"]),
+    ("cpu_box", &["accent.primary"]),
+    ("mem_box", &["accent.secondary"]),
+    ("net_box", &["type", "accent.primary"]),
+    ("proc_box", &["border"]),
+    ("div_line", &["border"]),
+];
+
+/// Gradient keys (`<key>_start`, `_mid`, `_end`) and the role each stop
+/// reads.
+const GRADIENTS: &[(&str, [&str; 3])] = &[
+    ("temp", ["success", "warning", "error"]),
+    ("cpu", ["success", "warning", "error"]),
+    ("free", ["border", "comment", "success"]),
+    ("cached", ["border", "comment", "type"]),
+    ("available", ["border", "comment", "function"]),
+    ("used", ["border", "warning", "error"]),
+    ("download", ["border", "function", "string"]),
+    ("upload", ["border", "accent.secondary", "keyword"]),
+    ("process", ["accent.primary", "accent.secondary", "error"]),
+];
+
+pub struct BtopExporter;
+
+impl Exporter for BtopExporter {
+    fn name(&self) -> &'static str {
+        "btop"
+    }
+
+    fn extension(&self) -> &'static str {
+        "theme"
+    }
+
+    fn description(&self) -> &'static str {
+        "btop theme and htoprc color scheme"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let roles = resolve_roles(theme);
+        let background = roles
+            .get("background")
+            .map_or(Color::rgb(0, 0, 0), |c| c.opaque());
+        let role = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| roles.get(name))
+                .map(|c| c.over(background).to_hex_rgb())
+        };
+        let name = theme_name(theme);
+        let slug = theme_slug(theme);
+
+        let mut btop = String::new();
+        let _ = writeln!(
+            btop,
+            "# {name} for btop; save in ~/.config/btop/themes.\n\
+             # Generated by `cyberdeck export --target btop`; do not edit.\n"
+        );
+        for (key, names) in COLORS {
+            if let Some(color) = role(names) {
+                let _ = writeln!(btop, "theme[{key}]=\"{color}\"");
+            }
+        }
+        for (key, stops) in GRADIENTS {
+            for (stop, name) in ["start", "mid", "end"].iter().zip(stops) {
+                if let Some(color) = role(&[name]) {
+                    let _ = writeln!(btop, "theme[{key}_{stop}]=\"{color}\"");
+                }
+            }
+        }
+
+        // htop's schemes: 0 is Default (for dark terminals), 3 is Light
+        // Terminal.
+        let scheme = if is_dark(theme) { 0 } else { 3 };
+        let htoprc = format!(
+            "# {name} for htop; merge into ~/.config/htop/htoprc.\n\
+             # htop draws with the terminal's ANSI colors; pair this with a\n\
+             # cyberdeck terminal target.\n\
+             # Generated by `cyberdeck export --target btop`; do not edit.\n\
+             color_scheme={scheme}\n"
+        );
+        Ok(vec![
+            Artifact::new(format!("{slug}.theme"), btop),
+            Artifact::new(format!("{slug}.htoprc"), htoprc),
+        ])
+    }
+}
@@ -0,0 +1,98 @@
+//! `chroma`: a Chroma XML style (`<slug>.xml`) for Hugo sites, gitea and
+//! anything else highlighting server-side through Chroma.
+//!
+//! Chroma's token types are Pygments' with the dots dropped and it reads
+//! the same style strings, so the token styles come from the `pygments`
+//! target.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::pygments::{style_string, token_styles};
+use super::{theme_slug, workbench_color, xml_escape, Artifact, Exporter};
+
+/// Chrome entries and the workbench keys their foreground and background
+/// read.
+const CHROME: &[(&str, &[&str], &[&str])] = &[
+    ("LineHighlight", &[], &["editor.lineHighlightBackground"]),
+    (
+        "LineNumbers",
+        &["editorLineNumber.foreground"],
+        &["editorGutter.background"],
+    ),
+    (
+        "LineNumbersTable",
+        &["editorLineNumber.foreground"],
+        &["editorGutter.background"],
+    ),
+    ("LineTableTD", &[], &["editorGutter.background"]),
+];
+
+pub struct ChromaExporter;
+
+impl Exporter for ChromaExporter {
+    fn name(&self) -> &'static str {
+        "chroma"
+    }
+
+    fn extension(&self) -> &'static str {
+        "xml"
+    }
+
+    fn description(&self) -> &'static str {
+        "Chroma XML style for Hugo and gitea"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let slug = theme_slug(theme);
+        let background =
+            workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+        let hex = |color: Color| color.over(background).to_hex_rgb();
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<!-- Generated by `cyberdeck export` (chroma target); do not edit. -->\n\
+             <style name=\"{}\">",
+            xml_escape(&slug)
+        );
+        for (token, style, inherited) in token_styles(theme) {
+            // Pygments' root token is Chroma's Background, which also sets
+            // the page background.
+            let (token, style) = if token == "Token" {
+                let mut style = style;
+                style.background = Some(background);
+                ("Background".to_owned(), style)
+            } else {
+                (token.replace('.', ""), style)
+            };
+            entry(&mut out, &token, &style_string(&style, &inherited, hex));
+        }
+        for (token, foreground, bg) in CHROME {
+            let mut style = Vec::new();
+            if let Some(color) = workbench_color(theme, foreground) {
+                style.push(hex(color));
+            }
+            if let Some(color) = workbench_color(theme, bg) {
+                style.push(format!("bg:{}", hex(color)));
+            }
+            if !style.is_empty() {
+                entry(&mut out, token, &style.join(" "));
+            }
+        }
+        out.push_str("</style>\n");
+        Ok(vec![Artifact::new(format!("{slug}.xml"), out)])
+    }
+}
+
+fn entry(out: &mut String, token: &str, style: &str) {
+    let _ = writeln!(
+        out,
+        "  <entry type=\"{token}\" style=\"{}\"/>",
+        xml_escape(style)
+    );
+}
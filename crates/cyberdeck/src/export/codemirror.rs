@@ -0,0 +1,333 @@
+//! `codemirror`: a CodeMirror 6 module (`<slug>.ts`) exporting the editor
+//! chrome as an `EditorView.theme`, the syntax colors as a `HighlightStyle`,
+//! and both together as one extension.
+//!
+//! The module has no type annotations, so it also works renamed to `.js`.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{is_dark, theme_name, theme_slug, workbench_color, Artifact, Exporter, TokenStyle};
+
+/// A style property and the workbench keys its color reads.
+type Property = (&'static str, &'static [&'static str]);
+
+/// Editor theme selectors and their properties.
+const CHROME: &[(&str, &[Property])] = &[
+    (
+        "&",
+        &[
+            ("color", &["editor.foreground", "foreground"]),
+            ("backgroundColor", &["editor.background"]),
+        ],
+    ),
+    (
+        ".cm-content",
+        &[("caretColor", &["editorCursor.foreground"])],
+    ),
+    (
+        ".cm-cursor, .cm-dropCursor",
+        &[("borderLeftColor", &["editorCursor.foreground"])],
+    ),
+    (
+        "&.cm-focused > .cm-scroller > .cm-selectionLayer .cm-selectionBackground, \
+         .cm-selectionBackground, .cm-content ::selection",
+        &[("backgroundColor", &["editor.selectionBackground"])],
+    ),
+    (
+        ".cm-activeLine",
+        &[("backgroundColor", &["editor.lineHighlightBackground"])],
+    ),
+    (
+        ".cm-selectionMatch",
+        &[("backgroundColor", &["editor.selectionHighlightBackground"])],
+    ),
+    (
+        ".cm-searchMatch",
+        &[
+            ("backgroundColor", &["editor.findMatchHighlightBackground"]),
+            ("outlineColor", &["editor.findMatchHighlightBorder"]),
+        ],
+    ),
+    (
+        ".cm-searchMatch.cm-searchMatch-selected",
+        &[("backgroundColor", &["editor.findMatchBackground"])],
+    ),
+    (
+        "&.cm-focused .cm-matchingBracket, &.cm-focused .cm-nonmatchingBracket",
+        &[
+            ("backgroundColor", &["editorBracketMatch.background"]),
+            ("outlineColor", &["editorBracketMatch.border"]),
+        ],
+    ),
+    (
+        ".cm-gutters",
+        &[
+            (
+                "backgroundColor",
+                &["editorGutter.background", "editor.background"],
+            ),
+            ("color", &["editorLineNumber.foreground"]),
+            ("borderRightColor", &["editorGroup.border", "panel.border"]),
+        ],
+    ),
+    (
+        ".cm-activeLineGutter",
+        &[
+            ("backgroundColor", &["editor.lineHighlightBackground"]),
+            ("color", &["editorLineNumber.activeForeground"]),
+        ],
+    ),
+    (
+        ".cm-foldPlaceholder",
+        &[
+            ("backgroundColor", &["editor.foldBackground"]),
+            ("color", &["editorLineNumber.foreground"]),
+        ],
+    ),
+    (
+        ".cm-panels",
+        &[
+            ("backgroundColor", &["editorWidget.background"]),
+            ("color", &["editorWidget.foreground", "foreground"]),
+        ],
+    ),
+    (
+        ".cm-tooltip",
+        &[
+            (
+                "backgroundColor",
+                &["editorHoverWidget.background", "editorWidget.background"],
+            ),
+            (
+                "borderColor",
+                &["editorHoverWidget.border", "editorWidget.border"],
+            ),
+        ],
+    ),
+    (
+        ".cm-tooltip-autocomplete > ul > li[aria-selected]",
+        &[
+            (
+                "backgroundColor",
+                &[
+                    "editorSuggestWidget.selectedBackground",
+                    "list.activeSelectionBackground",
+                ],
+            ),
+            (
+                "color",
+                &[
+                    "editorSuggestWidget.selectedForeground",
+                    "list.activeSelectionForeground",
+                ],
+            ),
+        ],
+    ),
+];
+
+/// `@lezer/highlight` tags and the scope path each group is resolved from.
+const TAGS: &[(&str, &str)] = &[
+    ("t.comment, t.lineComment, t.blockComment", "comment.line"),
+    ("t.docComment", "comment.block.documentation"),
+    (
+        "t.keyword, t.controlKeyword, t.moduleKeyword",
+        "keyword.control",
+    ),
+    ("t.definitionKeyword", "storage.type"),
+    ("t.modifier", "storage.modifier"),
+    ("t.operator, t.operatorKeyword", "keyword.operator"),
+    ("t.punctuation, t.separator, t.bracket", "punctuation"),
+    ("t.bool, t.null, t.atom", "constant.language"),
+    ("t.number, t.integer, t.float", "constant.numeric"),
+    ("t.string, t.docString", "string.quoted.double"),
+    ("t.character", "string.quoted.single"),
+    ("t.escape", "constant.character.escape"),
+    ("t.regexp", "string.regexp"),
+    (
+        "t.special(t.string)",
+        "punctuation.definition.template-expression",
+    ),
+    ("t.variableName", "variable.other.readwrite"),
+    ("t.definition(t.variableName)", "variable.other.readwrite"),
+    ("t.special(t.variableName), t.self", "variable.language"),
+    ("t.constant(t.variableName)", "variable.other.constant"),
+    ("t.standard(t.variableName)", "support.variable"),
+    (
+        "t.function(t.variableName), t.function(t.definition(t.variableName))",
+        "entity.name.function",
+    ),
+    (
+        "t.function(t.propertyName)",
+        "meta.function-call entity.name.function",
+    ),
+    ("t.standard(t.function(t.variableName))", "support.function"),
+    ("t.propertyName", "variable.other.property"),
+    ("t.attributeName", "entity.other.attribute-name"),
+    ("t.attributeValue", "string.quoted.double"),
+    (
+        "t.typeName, t.standard(t.typeName)",
+        "support.type.primitive",
+    ),
+    (
+        "t.className, t.definition(t.className)",
+        "entity.name.type.class",
+    ),
+    ("t.namespace", "entity.name.namespace"),
+    ("t.macroName", "entity.name.function.preprocessor"),
+    ("t.labelName", "entity.name.label"),
+    ("t.tagName", "entity.name.tag"),
+    ("t.angleBracket", "punctuation.definition.tag"),
+    ("t.meta, t.processingInstruction", "meta.preprocessor"),
+    ("t.annotation", "meta.decorator"),
+    ("t.heading", "markup.heading"),
+    ("t.emphasis", "markup.italic"),
+    ("t.strong", "markup.bold"),
+    ("t.strikethrough", "markup.strikethrough"),
+    ("t.link, t.url", "markup.underline.link"),
+    ("t.quote", "markup.quote"),
+    ("t.monospace", "markup.inline.raw"),
+    ("t.inserted", "markup.inserted"),
+    ("t.deleted", "markup.deleted"),
+    ("t.changed", "markup.changed"),
+    ("t.invalid", "invalid.illegal"),
+];
+
+pub struct CodeMirrorExporter;
+
+impl Exporter for CodeMirrorExporter {
+    fn name(&self) -> &'static str {
+        "codemirror"
+    }
+
+    fn extension(&self) -> &'static str {
+        "ts"
+    }
+
+    fn description(&self) -> &'static str {
+        "CodeMirror 6 theme and highlight style module"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let slug = theme_slug(theme);
+        let ident = identifier(&slug);
+        let resolver = Resolver::new(theme);
+
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "// {} for CodeMirror 6.\n\
+             // Generated by `cyberdeck export --target codemirror`; do not edit.\n\n\
+             import {{ HighlightStyle, syntaxHighlighting }} from \"@codemirror/language\";\n\
+             import {{ EditorView }} from \"@codemirror/view\";\n\
+             import {{ tags as t }} from \"@lezer/highlight\";\n\n\
+             export const {ident}Theme = EditorView.theme(\n  {{\n",
+            theme_name(theme)
+        );
+        for (selector, properties) in CHROME {
+            let properties: Vec<String> = properties
+                .iter()
+                .filter_map(|(property, keys)| {
+                    let color = workbench_color(theme, keys)?;
+                    Some(format!("{property}: {}", js_string(&color.to_string())))
+                })
+                .collect();
+            if !properties.is_empty() {
+                let _ = writeln!(
+                    out,
+                    "    {}: {{ {} }},",
+                    js_string(selector),
+                    properties.join(", ")
+                );
+            }
+        }
+        let _ = write!(
+            out,
+            "  }},\n  {{ dark: {} }},\n);\n\n\
+             export const {ident}HighlightStyle = HighlightStyle.define([\n",
+            is_dark(theme)
+        );
+        for (tags, scope) in TAGS {
+            let Some(style) = TokenStyle::resolve(&resolver, scope) else {
+                continue;
+            };
+            let tag = if tags.contains(", ") {
+                format!("[{tags}]")
+            } else {
+                (*tags).to_owned()
+            };
+            let mut properties = vec![format!("tag: {tag}")];
+            properties.extend(js_properties(&style));
+            let _ = writeln!(out, "  {{ {} }},", properties.join(", "));
+        }
+        let _ = writeln!(
+            out,
+            "]);\n\n\
+             export const {ident} = [{ident}Theme, syntaxHighlighting({ident}HighlightStyle)];"
+        );
+        Ok(vec![Artifact::new(format!("{slug}.ts"), out)])
+    }
+}
+
+/// A style's `HighlightStyle` properties.
+fn js_properties(style: &TokenStyle) -> Vec<String> {
+    let mut properties = Vec::new();
+    if let Some(color) = style.foreground {
+        properties.push(format!("color: {}", js_string(&color.to_string())));
+    }
+    if let Some(color) = style.background {
+        properties.push(format!(
+            "backgroundColor: {}",
+            js_string(&color.to_string())
+        ));
+    }
+    if style.bold {
+        properties.push("fontWeight: \"bold\"".to_owned());
+    }
+    if style.italic {
+        properties.push("fontStyle: \"italic\"".to_owned());
+    }
+    let decorations: Vec<&str> = [
+        (style.underline, "underline"),
+        (style.strikethrough, "line-through"),
+    ]
+    .into_iter()
+    .filter_map(|(set, line)| set.then_some(line))
+    .collect();
+    if !decorations.is_empty() {
+        properties.push(format!(
+            "textDecoration: {}",
+            js_string(&decorations.join(" "))
+        ));
+    }
+    properties
+}
+
+/// A double-quoted JavaScript string literal.
+fn js_string(text: &str) -> String {
+    serde_json::Value::from(text).to_string()
+}
+
+/// `cyberdeck-2025` → `cyberdeck2025`, the stem of the exported names.
+fn identifier(slug: &str) -> String {
+    let mut ident = String::new();
+    for (index, word) in slug.split('-').enumerate() {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if index > 0 {
+                ident.push(first.to_ascii_uppercase());
+            } else {
+                ident.push(first);
+            }
+            ident.push_str(chars.as_str());
+        }
+    }
+    if !ident.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        ident.insert_str(0, "theme");
+    }
+    ident
+}
@@ -0,0 +1,48 @@
+//! `css-variables`: the palette roles as CSS custom properties on `:root`
+//! (`<slug>.css`), named `--cyberdeck-<role>` with `-` for `.`
+//! (`accent.primary` → `--cyberdeck-accent-primary`).
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::highlightjs::rule;
+use super::{theme_name, theme_slug, Artifact, Exporter};
+
+pub struct CssVariablesExporter;
+
+impl Exporter for CssVariablesExporter {
+    fn name(&self) -> &'static str {
+        "css-variables"
+    }
+
+    fn extension(&self) -> &'static str {
+        "css"
+    }
+
+    fn description(&self) -> &'static str {
+        "palette roles as CSS custom properties"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let declarations: Vec<String> = resolve_roles(theme)
+            .iter()
+            .map(|(role, color)| format!("--cyberdeck-{}: {color};", role.replace('.', "-")))
+            .collect();
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "/* {} palette.\n   Generated by `cyberdeck export --target css-variables`; do not edit. */\n",
+            theme_name(theme)
+        );
+        rule(&mut out, ":root", &declarations);
+        Ok(vec![Artifact::new(
+            format!("{}.css", theme_slug(theme)),
+            format!("{}\n", out.trim_end()),
+        )])
+    }
+}
@@ -0,0 +1,89 @@
+//! `dtcg`: the palette roles, and the terminal colors when the theme has
+//! them, as a W3C Design Tokens file (`<slug>.tokens.json`) for Figma
+//! plugins, Style Dictionary and other token pipelines.
+//!
+//! Tokens are named after their role with `-` for `.` (`accent.primary` →
+//! `color.accent-primary`), since the format reserves dots for paths. Values
+//! use the structured sRGB form with a hex fallback.
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{ansi_colors, theme_name, theme_slug, Artifact, Exporter, ANSI_NAMES};
+
+pub struct DtcgExporter;
+
+impl Exporter for DtcgExporter {
+    fn name(&self) -> &'static str {
+        "dtcg"
+    }
+
+    fn extension(&self) -> &'static str {
+        "tokens.json"
+    }
+
+    fn description(&self) -> &'static str {
+        "W3C design tokens"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let mut root = Map::new();
+        root.insert(
+            "$description".to_owned(),
+            format!(
+                "{} palette. Generated by `cyberdeck export --target dtcg`; do not edit.",
+                theme_name(theme)
+            )
+            .into(),
+        );
+        root.insert(
+            "color".to_owned(),
+            group(
+                resolve_roles(theme)
+                    .into_iter()
+                    .map(|(role, color)| (role.replace('.', "-"), color)),
+            ),
+        );
+        if let Some(ansi) = ansi_colors(theme) {
+            let names = ANSI_NAMES
+                .iter()
+                .map(|name| (*name).to_owned())
+                .chain(ANSI_NAMES.iter().map(|name| format!("bright-{name}")));
+            root.insert("terminal".to_owned(), group(names.zip(ansi)));
+        }
+
+        let mut json = serde_json::to_string_pretty(&root)?;
+        json.push('\n');
+        Ok(vec![Artifact::new(
+            format!("{}.tokens.json", theme_slug(theme)),
+            json,
+        )])
+    }
+}
+
+/// A group of color tokens.
+fn group(tokens: impl Iterator<Item = (String, Color)>) -> Value {
+    let mut group = Map::new();
+    group.insert("$type".to_owned(), "color".into());
+    for (name, color) in tokens {
+        group.insert(name, json!({ "$value": value(color) }));
+    }
+    group.into()
+}
+
+fn value(color: Color) -> Value {
+    let component = |channel: u8| (f64::from(channel) / 255.0 * 10_000.0).round() / 10_000.0;
+    let mut value = json!({
+        "colorSpace": "srgb",
+        "components": [component(color.r), component(color.g), component(color.b)],
+    });
+    if !color.is_opaque() {
+        value["alpha"] = component(color.a).into();
+    }
+    value["hex"] = color.to_hex_rgb().into();
+    value
+}
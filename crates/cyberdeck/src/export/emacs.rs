@@ -0,0 +1,318 @@
+//! `emacs`: an Emacs custom theme (`<slug>-theme.el`).
+//!
+//! Every face has two specs: the exact colors for graphical frames and
+//! true-color terminals, and the nearest xterm 256-color entries for
+//! terminals that cannot show them. Emacs has no translucency, so colors are
+//! composited over the editor background first.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{ansi_colors, theme_name, theme_slug, workbench_color, Artifact, Exporter, TokenStyle};
+
+/// Font-lock (and similar) faces and the scope path each is resolved from.
+const TOKEN_FACES: &[(&str, &str)] = &[
+    ("font-lock-comment-face", "comment.line"),
+    (
+        "font-lock-comment-delimiter-face",
+        "comment.line punctuation.definition.comment",
+    ),
+    ("font-lock-doc-face", "comment.block.documentation"),
+    ("font-lock-string-face", "string.quoted.double"),
+    ("font-lock-keyword-face", "keyword.control"),
+    ("font-lock-builtin-face", "support.function"),
+    ("font-lock-function-name-face", "entity.name.function"),
+    (
+        "font-lock-function-call-face",
+        "meta.function-call entity.name.function",
+    ),
+    ("font-lock-variable-name-face", "variable.other.readwrite"),
+    ("font-lock-variable-use-face", "variable.other"),
+    ("font-lock-type-face", "entity.name.type"),
+    ("font-lock-constant-face", "constant.language"),
+    ("font-lock-number-face", "constant.numeric"),
+    ("font-lock-escape-face", "constant.character.escape"),
+    ("font-lock-preprocessor-face", "meta.preprocessor"),
+    ("font-lock-negation-char-face", "keyword.operator.logical"),
+    ("font-lock-operator-face", "keyword.operator"),
+    ("font-lock-property-name-face", "variable.other.property"),
+    ("font-lock-punctuation-face", "punctuation"),
+    ("font-lock-bracket-face", "punctuation.section.brackets"),
+    ("font-lock-delimiter-face", "punctuation.separator"),
+    ("font-lock-regexp-grouping-construct", "string.regexp"),
+    ("font-lock-warning-face", "invalid.illegal"),
+    ("org-code", "markup.inline.raw"),
+    ("org-verbatim", "markup.inline.raw"),
+    ("org-quote", "markup.quote"),
+];
+
+/// UI faces and the workbench keys their foreground and background read.
+const UI_FACES: &[(&str, &[&str], &[&str])] = &[
+    ("cursor", &[], &["editorCursor.foreground"]),
+    ("region", &[], &["editor.selectionBackground"]),
+    ("highlight", &[], &["editor.lineHighlightBackground"]),
+    ("hl-line", &[], &["editor.lineHighlightBackground"]),
+    ("fringe", &[], &["editorGutter.background"]),
+    ("line-number", &["editorLineNumber.foreground"], &[]),
+    (
+        "line-number-current-line",
+        &["editorLineNumber.activeForeground"],
+        &[],
+    ),
+    (
+        "mode-line",
+        &["statusBar.foreground"],
+        &["statusBar.background"],
+    ),
+    (
+        "mode-line-inactive",
+        &["statusBar.foreground"],
+        &["statusBar.noFolderBackground", "statusBar.background"],
+    ),
+    ("vertical-border", &["editorGroup.border"], &[]),
+    ("isearch", &[], &["editor.findMatchBackground"]),
+    (
+        "lazy-highlight",
+        &[],
+        &["editor.findMatchHighlightBackground"],
+    ),
+    (
+        "show-paren-match",
+        &["editorBracketMatch.border"],
+        &["editorBracketMatch.background"],
+    ),
+    ("link", &["textLink.foreground"], &[]),
+    ("error", &["editorError.foreground", "errorForeground"], &[]),
+    ("warning", &["editorWarning.foreground"], &[]),
+    ("whitespace-space", &["editorWhitespace.foreground"], &[]),
+    (
+        "company-tooltip",
+        &["editorSuggestWidget.foreground"],
+        &["editorSuggestWidget.background"],
+    ),
+    (
+        "company-tooltip-selection",
+        &["editorSuggestWidget.selectedForeground"],
+        &["editorSuggestWidget.selectedBackground"],
+    ),
+    (
+        "company-tooltip-scrollbar-track",
+        &[],
+        &["scrollbarSlider.background"],
+    ),
+    (
+        "company-tooltip-scrollbar-thumb",
+        &[],
+        &["scrollbarSlider.activeBackground"],
+    ),
+    (
+        "magit-diff-added",
+        &[],
+        &["diffEditor.insertedLineBackground"],
+    ),
+    (
+        "magit-diff-added-highlight",
+        &[],
+        &["diffEditor.insertedTextBackground"],
+    ),
+    (
+        "magit-diff-removed",
+        &[],
+        &["diffEditor.removedLineBackground"],
+    ),
+    (
+        "magit-diff-removed-highlight",
+        &[],
+        &["diffEditor.removedTextBackground"],
+    ),
+];
+
+/// Faces colored by palette role: foreground role, background role (empty
+/// for none) and attributes.
+const ROLE_FACES: &[(&str, &str, &str, &[&str])] = &[
+    ("minibuffer-prompt", "accent.primary", "", &["bold"]),
+    ("success", "success", "", &[]),
+    ("company-tooltip-common", "accent.primary", "", &["bold"]),
+    ("company-preview", "foreground.muted", "", &[]),
+    ("company-preview-common", "accent.primary", "", &[]),
+    ("magit-section-heading", "keyword", "", &["bold"]),
+    ("magit-branch-local", "function", "", &[]),
+    ("magit-branch-remote", "string", "", &[]),
+    ("magit-hash", "comment", "", &[]),
+    (
+        "magit-diff-hunk-heading",
+        "foreground.muted",
+        "background.tertiary",
+        &[],
+    ),
+    (
+        "magit-diff-hunk-heading-highlight",
+        "foreground",
+        "highlight",
+        &[],
+    ),
+    ("org-document-title", "accent.primary", "", &["bold"]),
+    ("org-level-1", "keyword", "", &["bold"]),
+    ("org-level-2", "function", "", &["bold"]),
+    ("org-level-3", "class", "", &[]),
+    ("org-level-4", "storage", "", &[]),
+    ("org-todo", "error", "", &["bold"]),
+    ("org-done", "success", "", &["bold"]),
+    ("org-date", "number", "", &["underline"]),
+    ("org-tag", "comment", "", &[]),
+    ("org-block", "", "background.secondary", &[]),
+    (
+        "org-block-begin-line",
+        "comment",
+        "background.secondary",
+        &[],
+    ),
+    ("org-block-end-line", "comment", "background.secondary", &[]),
+];
+
+pub struct EmacsExporter;
+
+impl Exporter for EmacsExporter {
+    fn name(&self) -> &'static str {
+        "emacs"
+    }
+
+    fn extension(&self) -> &'static str {
+        "el"
+    }
+
+    fn description(&self) -> &'static str {
+        "Emacs custom theme with 256-color terminal fallbacks"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let slug = theme_slug(theme);
+        Ok(vec![Artifact::new(
+            format!("{slug}-theme.el"),
+            deftheme(theme, &slug),
+        )])
+    }
+}
+
+fn deftheme(theme: &Theme, slug: &str) -> String {
+    let background = workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+    let resolver = Resolver::new(theme);
+    let roles = resolve_roles(theme);
+
+    let mut faces = Vec::new();
+    let default = TokenStyle {
+        foreground: resolver.defaults().foreground,
+        background: Some(background),
+        ..TokenStyle::default()
+    };
+    faces.push(("default", default));
+    for (face, scope) in TOKEN_FACES {
+        if let Some(style) = TokenStyle::resolve(&resolver, scope) {
+            faces.push((face, style));
+        }
+    }
+    for (face, foreground, bg) in UI_FACES {
+        let style = TokenStyle {
+            foreground: workbench_color(theme, foreground),
+            background: workbench_color(theme, bg),
+            ..TokenStyle::default()
+        };
+        if style.foreground.is_some() || style.background.is_some() {
+            faces.push((face, style));
+        }
+    }
+    for (face, foreground, bg, attrs) in ROLE_FACES {
+        let style = TokenStyle {
+            foreground: roles.get(foreground).copied(),
+            background: roles.get(bg).copied(),
+            bold: attrs.contains(&"bold"),
+            italic: attrs.contains(&"italic"),
+            underline: attrs.contains(&"underline"),
+            strikethrough: attrs.contains(&"strikethrough"),
+        };
+        if style.foreground.is_some() || style.background.is_some() {
+            faces.push((face, style));
+        }
+    }
+
+    let name = theme_name(theme);
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        ";;; {slug}-theme.el --- {name} -*- lexical-binding: t -*-\n\n\
+         ;; Generated by `cyberdeck export --target emacs'; do not edit.\n\n\
+         ;;; Code:\n\n\
+         (deftheme {slug} \"{name}\")\n\n\
+         (let ((gui '((class color) (min-colors 257)))\n      \
+         (term '((class color) (min-colors 256))))\n  \
+         (custom-theme-set-faces\n   '{slug}"
+    );
+    for (face, style) in &faces {
+        let gui = attributes(style, |c| c.over(background));
+        let term = attributes(style, |c| {
+            Color::from_xterm256(c.over(background).to_xterm256())
+        });
+        let _ = write!(out, "\n   `({face} ((,gui ({gui})) (,term ({term}))))");
+    }
+    out.push(')');
+
+    if let Some(ansi) = ansi_colors(theme) {
+        let colors: Vec<String> = ansi
+            .iter()
+            .map(|c| format!("\"{}\"", c.over(background).to_hex_rgb()))
+            .collect();
+        let _ = write!(
+            out,
+            "\n\n  (custom-theme-set-variables\n   '{slug}\n   \
+             '(ansi-color-names-vector [{}])))",
+            colors[..8].join(" ")
+        );
+    } else {
+        out.push(')');
+    }
+
+    let _ = writeln!(
+        out,
+        "\n\n;;;###autoload\n\
+         (when load-file-name\n  \
+         (add-to-list 'custom-theme-load-path\n               \
+         (file-name-as-directory (file-name-directory load-file-name))))\n\n\
+         (provide-theme '{slug})\n\n\
+         ;;; {slug}-theme.el ends here"
+    );
+    out
+}
+
+/// A face attribute plist, with colors passed through `color`.
+fn attributes(style: &TokenStyle, color: impl Fn(Color) -> Color) -> String {
+    let mut plist = Vec::new();
+    if let Some(foreground) = style.foreground {
+        plist.push(format!(
+            ":foreground \"{}\"",
+            color(foreground).to_hex_rgb()
+        ));
+    }
+    if let Some(background) = style.background {
+        plist.push(format!(
+            ":background \"{}\"",
+            color(background).to_hex_rgb()
+        ));
+    }
+    for (set, attribute) in [
+        (style.bold, ":weight bold"),
+        (style.italic, ":slant italic"),
+        (style.underline, ":underline t"),
+        (style.strikethrough, ":strike-through t"),
+    ] {
+        if set {
+            plist.push(attribute.to_owned());
+        }
+    }
+    plist.join(" ")
+}
@@ -0,0 +1,240 @@
+//! `fish`: fish shell colors, for the command line, the pager and the
+//! prompt variables fish's own prompts read.
+//!
+//! - `<name>.theme`, a theme file for `~/.config/fish/themes`, picked with
+//!   `fish_config theme choose`.
+//! - `<slug>.fish`, the same values as `set -U` commands for fish versions
+//!   before theme files.
+//!
+//! Syntax colors are resolved from the scope path the shell grammar gives
+//! each construct; colors are composited over the terminal background.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{
+    theme_name, theme_slug, workbench_color, Artifact, Exporter, TerminalColors, TokenStyle,
+};
+
+/// Command-line variables and the scope path each is resolved from.
+const SYNTAX: &[(&str, &str)] = &[
+    (
+        "fish_color_command",
+        "source.shell entity.name.command.shell",
+    ),
+    ("fish_color_keyword", "source.shell keyword.control.shell"),
+    (
+        "fish_color_quote",
+        "source.shell string.quoted.double.shell",
+    ),
+    (
+        "fish_color_redirection",
+        "source.shell keyword.operator.redirect.shell",
+    ),
+    ("fish_color_end", "source.shell keyword.operator.list.shell"),
+    ("fish_color_error", "source.shell invalid.illegal"),
+    (
+        "fish_color_param",
+        "source.shell string.unquoted.argument.shell",
+    ),
+    (
+        "fish_color_option",
+        "source.shell constant.other.option.shell",
+    ),
+    (
+        "fish_color_comment",
+        "source.shell comment.line.number-sign.shell",
+    ),
+    (
+        "fish_color_operator",
+        "source.shell keyword.operator.glob.shell",
+    ),
+    (
+        "fish_color_escape",
+        "source.shell constant.character.escape.shell",
+    ),
+];
+
+/// Interface variables, the workbench keys their foreground and background
+/// read, and extra flags.
+const UI: &[(&str, &[&str], &[&str], &str)] = &[
+    ("fish_color_valid_path", &[], &[], "--underline"),
+    (
+        "fish_color_autosuggestion",
+        &["editorGhostText.foreground", "editorLineNumber.foreground"],
+        &[],
+        "",
+    ),
+    (
+        "fish_color_selection",
+        &["editor.selectionForeground", "editor.foreground"],
+        &["terminal.selectionBackground", "editor.selectionBackground"],
+        "--bold",
+    ),
+    (
+        "fish_color_search_match",
+        &[],
+        &["editor.findMatchBackground"],
+        "--bold",
+    ),
+    (
+        "fish_color_history_current",
+        &["activityBar.activeBorder", "focusBorder"],
+        &[],
+        "--bold",
+    ),
+    ("fish_color_cancel", &["terminal.ansiRed"], &[], "--reverse"),
+    ("fish_color_cwd", &["terminal.ansiGreen"], &[], ""),
+    ("fish_color_cwd_root", &["terminal.ansiRed"], &[], ""),
+    ("fish_color_user", &["terminal.ansiBrightGreen"], &[], ""),
+    ("fish_color_host", &["terminal.ansiCyan"], &[], ""),
+    ("fish_color_host_remote", &["terminal.ansiYellow"], &[], ""),
+    ("fish_color_status", &["terminal.ansiRed"], &[], ""),
+    (
+        "fish_pager_color_progress",
+        &["descriptionForeground"],
+        &[],
+        "--italics",
+    ),
+    (
+        "fish_pager_color_prefix",
+        &["list.highlightForeground"],
+        &[],
+        "--bold --underline",
+    ),
+    (
+        "fish_pager_color_completion",
+        &["editorSuggestWidget.foreground", "editor.foreground"],
+        &[],
+        "",
+    ),
+    (
+        "fish_pager_color_description",
+        &["descriptionForeground"],
+        &[],
+        "",
+    ),
+    (
+        "fish_pager_color_selected_background",
+        &[],
+        &[
+            "editorSuggestWidget.selectedBackground",
+            "list.activeSelectionBackground",
+        ],
+        "",
+    ),
+    (
+        "fish_pager_color_selected_completion",
+        &[
+            "editorSuggestWidget.selectedForeground",
+            "list.activeSelectionForeground",
+        ],
+        &[],
+        "",
+    ),
+];
+
+pub struct FishExporter;
+
+impl Exporter for FishExporter {
+    fn name(&self) -> &'static str {
+        "fish"
+    }
+
+    fn extension(&self) -> &'static str {
+        "theme"
+    }
+
+    fn description(&self) -> &'static str {
+        "fish theme file and set -U script"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let variables = variables(theme);
+        let name = theme_name(theme);
+        let background = TerminalColors::new(theme).background;
+
+        let mut theme_file = String::new();
+        let _ = writeln!(
+            theme_file,
+            "# name: '{name}'\n\
+             # preferred_background: {}\n\
+             # Generated by `cyberdeck export --target fish`; do not edit.\n",
+            bare(background)
+        );
+        let mut script = String::new();
+        let _ = writeln!(
+            script,
+            "# {name} for fish; run once with `source`, the colors are universal.\n\
+             # Generated by `cyberdeck export --target fish`; do not edit."
+        );
+        for (variable, value) in &variables {
+            let _ = writeln!(theme_file, "{variable} {value}");
+            let _ = writeln!(script, "set -U {variable} {value}");
+        }
+        Ok(vec![
+            Artifact::new(format!("{name}.theme"), theme_file),
+            Artifact::new(format!("{}.fish", theme_slug(theme)), script),
+        ])
+    }
+}
+
+/// Every variable fish should set, with its value.
+fn variables(theme: &Theme) -> Vec<(&'static str, String)> {
+    let terminal = TerminalColors::new(theme);
+    let color = |keys: &[&str]| workbench_color(theme, keys).map(|c| c.over(terminal.background));
+    let resolver = Resolver::new(theme);
+
+    let mut variables = vec![("fish_color_normal", bare(terminal.foreground))];
+    for (variable, scope) in SYNTAX {
+        let Some(style) = TokenStyle::resolve(&resolver, scope) else {
+            continue;
+        };
+        let mut value: Vec<String> = style
+            .foreground
+            .map(|c| bare(c.over(terminal.background)))
+            .into_iter()
+            .collect();
+        if let Some(background) = style.background {
+            value.push(format!(
+                "--background={}",
+                bare(background.over(terminal.background))
+            ));
+        }
+        for (set, flag) in [
+            (style.bold, "--bold"),
+            (style.italic, "--italics"),
+            (style.underline, "--underline"),
+        ] {
+            if set {
+                value.push(flag.to_owned());
+            }
+        }
+        if !value.is_empty() {
+            variables.push((variable, value.join(" ")));
+        }
+    }
+    for (variable, foreground, background, flags) in UI {
+        let mut value: Vec<String> = color(foreground).map(bare).into_iter().collect();
+        if let Some(background) = color(background) {
+            value.push(format!("--background={}", bare(background)));
+        }
+        if !flags.is_empty() {
+            value.push((*flags).to_owned());
+        }
+        if !value.is_empty() {
+            variables.push((variable, value.join(" ")));
+        }
+    }
+    variables
+}
+
+/// A color as fish writes it: hex without the `#`.
+fn bare(color: Color) -> String {
+    color.to_hex_rgb()[1..].to_owned()
+}
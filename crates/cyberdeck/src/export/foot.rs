@@ -0,0 +1,71 @@
+//! `foot`: colors for the foot Wayland terminal (`<slug>.ini`, to
+//! `include=` from `foot.ini`). foot wants bare `rrggbb` values.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter, TerminalColors};
+
+pub struct FootExporter;
+
+impl Exporter for FootExporter {
+    fn name(&self) -> &'static str {
+        "foot"
+    }
+
+    fn extension(&self) -> &'static str {
+        "ini"
+    }
+
+    fn description(&self) -> &'static str {
+        "foot terminal colors"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let colors = TerminalColors::new(theme);
+        let hex = |color: Color| color.over(colors.background).to_hex_rgb()[1..].to_owned();
+
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}", theme_name(theme));
+        out.push_str("# Generated by `cyberdeck export --target foot`; do not edit.\n\n");
+        let _ = writeln!(
+            out,
+            "[cursor]\ncolor={} {}\n",
+            hex(colors.cursor_text),
+            hex(colors.cursor)
+        );
+        out.push_str("[colors]\n");
+        let _ = writeln!(out, "foreground={}", hex(colors.foreground));
+        let _ = writeln!(out, "background={}", hex(colors.background));
+        for (index, color) in colors.ansi.iter().enumerate() {
+            let (kind, slot) = if index < 8 {
+                ("regular", index)
+            } else {
+                ("bright", index - 8)
+            };
+            let _ = writeln!(out, "{kind}{slot}={}", hex(*color));
+        }
+        // foot only honours the selection colors as a pair.
+        let _ = writeln!(
+            out,
+            "selection-foreground={}",
+            hex(colors.selection_foreground.unwrap_or(colors.foreground))
+        );
+        let _ = writeln!(
+            out,
+            "selection-background={}",
+            hex(colors.selection_background)
+        );
+        if let Some(color) = workbench_color(theme, &["textLink.foreground", "terminal.ansiBlue"]) {
+            let _ = writeln!(out, "urls={}", hex(color));
+        }
+        Ok(vec![Artifact::new(
+            format!("{}.ini", theme_slug(theme)),
+            out,
+        )])
+    }
+}
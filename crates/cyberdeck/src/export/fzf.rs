@@ -0,0 +1,107 @@
+//! `fzf`: fzf colors after the workbench's quick pick.
+//!
+//! - `<slug>.sh`, a line adding the `--color` option to
+//!   `FZF_DEFAULT_OPTS`, to source from the shell's rc file.
+//! - `<slug>.fzfrc`, the bare option, for `FZF_DEFAULT_OPTS_FILE`.
+//!
+//! Colors are composited over the terminal background, which fzf draws on.
+
+use anyhow::Result;
+
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter, TerminalColors};
+
+/// fzf color names and the workbench keys they read.
+const COLORS: &[(&str, &[&str])] = &[
+    ("hl", &["list.highlightForeground"]),
+    (
+        "fg+",
+        &["list.activeSelectionForeground", "editor.foreground"],
+    ),
+    (
+        "bg+",
+        &[
+            "quickInputList.focusBackground",
+            "list.activeSelectionBackground",
+        ],
+    ),
+    (
+        "hl+",
+        &["list.focusHighlightForeground", "list.highlightForeground"],
+    ),
+    ("info", &["descriptionForeground"]),
+    ("prompt", &["activityBar.activeBorder", "focusBorder"]),
+    ("pointer", &["editorCursor.foreground"]),
+    (
+        "marker",
+        &[
+            "gitDecoration.addedResourceForeground",
+            "editorGutter.addedBackground",
+        ],
+    ),
+    ("spinner", &["activityBar.activeBorder", "focusBorder"]),
+    (
+        "header",
+        &["pickerGroup.foreground", "descriptionForeground"],
+    ),
+    ("border", &["editorGroup.border", "panel.border"]),
+    ("label", &["pickerGroup.foreground", "foreground"]),
+    ("query", &["input.foreground", "foreground"]),
+    ("disabled", &["disabledForeground", "descriptionForeground"]),
+    (
+        "scrollbar",
+        &[
+            "scrollbarSlider.activeBackground",
+            "scrollbarSlider.background",
+        ],
+    ),
+    ("separator", &["editorGroup.border", "panel.border"]),
+    ("preview-bg", &["editor.background"]),
+];
+
+pub struct FzfExporter;
+
+impl Exporter for FzfExporter {
+    fn name(&self) -> &'static str {
+        "fzf"
+    }
+
+    fn extension(&self) -> &'static str {
+        "sh"
+    }
+
+    fn description(&self) -> &'static str {
+        "fzf --color option and FZF_DEFAULT_OPTS line"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let terminal = TerminalColors::new(theme);
+        let mut colors = vec![
+            format!("fg:{}", terminal.foreground.to_hex_rgb()),
+            format!("bg:{}", terminal.background.to_hex_rgb()),
+            format!("gutter:{}", terminal.background.to_hex_rgb()),
+        ];
+        for (name, keys) in COLORS {
+            if let Some(color) = workbench_color(theme, keys) {
+                colors.push(format!(
+                    "{name}:{}",
+                    color.over(terminal.background).to_hex_rgb()
+                ));
+            }
+        }
+        let option = format!("--color={}", colors.join(","));
+
+        let name = theme_name(theme);
+        let slug = theme_slug(theme);
+        let script = format!(
+            "# {name} for fzf; source this from your shell's rc file.\n\
+             # Generated by `cyberdeck export --target fzf`; do not edit.\n\
+             export FZF_DEFAULT_OPTS=\"$FZF_DEFAULT_OPTS {option}\"\n"
+        );
+        Ok(vec![
+            Artifact::new(format!("{slug}.sh"), script),
+            Artifact::new(format!("{slug}.fzfrc"), format!("{option}\n")),
+        ])
+    }
+}
@@ -0,0 +1,53 @@
+//! `ghostty`: a Ghostty theme. Ghostty finds themes by file name, so the
+//! artifact is named after the theme with no extension; copy it to
+//! `~/.config/ghostty/themes/` and set `theme = <name>`.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::theme::Theme;
+
+use super::{theme_name, Artifact, Exporter, TerminalColors};
+
+pub struct GhosttyExporter;
+
+impl Exporter for GhosttyExporter {
+    fn name(&self) -> &'static str {
+        "ghostty"
+    }
+
+    fn extension(&self) -> &'static str {
+        ""
+    }
+
+    fn description(&self) -> &'static str {
+        "Ghostty theme"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let colors = TerminalColors::new(theme);
+
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}", theme_name(theme));
+        out.push_str("# Generated by `cyberdeck export --target ghostty`; do not edit.\n\n");
+        for (index, color) in colors.ansi.iter().enumerate() {
+            let _ = writeln!(out, "palette = {index}={}", color.to_hex_rgb());
+        }
+        out.push('\n');
+        let mut entries = vec![
+            ("background", colors.background),
+            ("foreground", colors.foreground),
+            ("cursor-color", colors.cursor),
+            ("cursor-text", colors.cursor_text),
+            ("selection-background", colors.selection_background),
+        ];
+        if let Some(color) = colors.selection_foreground {
+            entries.push(("selection-foreground", color));
+        }
+        for (key, color) in entries {
+            let _ = writeln!(out, "{key} = {}", color.to_hex_rgb());
+        }
+        Ok(vec![Artifact::new(theme_name(theme).to_owned(), out)])
+    }
+}
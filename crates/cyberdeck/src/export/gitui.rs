@@ -0,0 +1,127 @@
+//! `gitui`: a gitui theme (`<slug>.ron`, saved as `theme.ron` in gitui's
+//! config directory) from the workbench's selection, git decoration and
+//! diff editor colors.
+//!
+//! Colors are composited over the editor background.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter};
+
+/// Theme fields and the workbench keys they read.
+const FIELDS: &[(&str, &[&str])] = &[
+    ("selected_tab", &["activityBar.activeBorder", "focusBorder"]),
+    ("command_fg", &["statusBar.foreground", "foreground"]),
+    (
+        "selection_bg",
+        &[
+            "list.activeSelectionBackground",
+            "editor.selectionBackground",
+        ],
+    ),
+    (
+        "selection_fg",
+        &["list.activeSelectionForeground", "editor.foreground"],
+    ),
+    ("cmdbar_bg", &["statusBar.background"]),
+    (
+        "cmdbar_extra_lines_bg",
+        &["statusBar.noFolderBackground", "statusBar.background"],
+    ),
+    (
+        "disabled_fg",
+        &["disabledForeground", "editorLineNumber.foreground"],
+    ),
+    (
+        "diff_line_add",
+        &["editorGutter.addedBackground", "terminal.ansiGreen"],
+    ),
+    (
+        "diff_line_delete",
+        &["editorGutter.deletedBackground", "terminal.ansiRed"],
+    ),
+    (
+        "diff_file_added",
+        &["gitDecoration.addedResourceForeground"],
+    ),
+    (
+        "diff_file_removed",
+        &["gitDecoration.deletedResourceForeground"],
+    ),
+    (
+        "diff_file_moved",
+        &[
+            "gitDecoration.renamedResourceForeground",
+            "gitDecoration.untrackedResourceForeground",
+        ],
+    ),
+    (
+        "diff_file_modified",
+        &["gitDecoration.modifiedResourceForeground"],
+    ),
+    ("commit_hash", &["textLink.foreground"]),
+    ("commit_time", &["descriptionForeground"]),
+    (
+        "commit_author",
+        &["gitDecoration.untrackedResourceForeground", "foreground"],
+    ),
+    ("danger_fg", &["editorError.foreground", "errorForeground"]),
+    ("push_gauge_bg", &["progressBar.background"]),
+    ("push_gauge_fg", &["editor.background"]),
+    ("tag_fg", &["gitDecoration.conflictingResourceForeground"]),
+    (
+        "branch_fg",
+        &[
+            "gitDecoration.submoduleResourceForeground",
+            "terminal.ansiCyan",
+        ],
+    ),
+];
+
+pub struct GituiExporter;
+
+impl Exporter for GituiExporter {
+    fn name(&self) -> &'static str {
+        "gitui"
+    }
+
+    fn extension(&self) -> &'static str {
+        "ron"
+    }
+
+    fn description(&self) -> &'static str {
+        "gitui theme.ron"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let background =
+            workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "// {} for gitui; save as theme.ron in gitui's config directory.\n\
+             // Generated by `cyberdeck export --target gitui`; do not edit.\n(",
+            theme_name(theme)
+        );
+        for (field, keys) in FIELDS {
+            if let Some(color) = workbench_color(theme, keys) {
+                let _ = writeln!(
+                    out,
+                    "    {field}: Some(\"{}\"),",
+                    color.over(background).to_hex_rgb()
+                );
+            }
+        }
+        out.push_str(")\n");
+        Ok(vec![Artifact::new(
+            format!("{}.ron", theme_slug(theme)),
+            out,
+        )])
+    }
+}
@@ -0,0 +1,115 @@
+//! `gnome-terminal`: a shell script (`<slug>.sh`) that creates a GNOME
+//! Terminal profile with `dconf load` and adds it to the profile list.
+//!
+//! The profile ID is derived from the theme name, so running the script
+//! again updates the same profile instead of adding another.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter, TerminalColors};
+
+const PROFILES: &str = "/org/gnome/terminal/legacy/profiles:";
+
+pub struct GnomeTerminalExporter;
+
+impl Exporter for GnomeTerminalExporter {
+    fn name(&self) -> &'static str {
+        "gnome-terminal"
+    }
+
+    fn extension(&self) -> &'static str {
+        "sh"
+    }
+
+    fn description(&self) -> &'static str {
+        "dconf script installing a GNOME Terminal profile"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let colors = TerminalColors::new(theme);
+        let name = theme_name(theme);
+        let id = profile_id(name);
+        let hex = |color: Color| format!("'{}'", color.to_hex_rgb());
+        let palette: Vec<String> = colors.ansi.iter().map(|c| hex(*c)).collect();
+        let selection_foreground = colors.selection_foreground.unwrap_or(colors.foreground);
+
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "#!/bin/sh\n\
+             # Installs the {name} GNOME Terminal profile.\n\
+             # Generated by `cyberdeck export --target gnome-terminal`; do not edit.\n\
+             set -eu\n\n\
+             profile={id}\n\n\
+             dconf load \"{PROFILES}/:$profile/\" <<'EOF'\n\
+             [/]\n\
+             visible-name={}\n\
+             use-theme-colors=false\n\
+             background-color={}\n\
+             foreground-color={}\n\
+             bold-color={}\n\
+             bold-color-same-as-fg=false\n\
+             bold-is-bright=true\n\
+             cursor-colors-set=true\n\
+             cursor-background-color={}\n\
+             cursor-foreground-color={}\n\
+             highlight-colors-set=true\n\
+             highlight-background-color={}\n\
+             highlight-foreground-color={}\n\
+             palette=[{}]\n\
+             EOF\n\n",
+            gvariant_string(name),
+            hex(colors.background),
+            hex(colors.foreground),
+            hex(colors.ansi[15]),
+            hex(colors.cursor),
+            hex(colors.cursor_text),
+            hex(colors.selection_background),
+            hex(selection_foreground),
+            palette.join(", ")
+        );
+        let _ = writeln!(
+            out,
+            "list=$(dconf read {PROFILES}/list)\n\
+             case \"$list\" in\n    \
+             *\"$profile\"*) ;;\n    \
+             \"\" | \"@as []\") dconf write {PROFILES}/list \"['$profile']\" ;;\n    \
+             *) dconf write {PROFILES}/list \"${{list%]}}, '$profile']\" ;;\n\
+             esac\n\
+             echo \"Installed the {name} profile; select it in Preferences.\""
+        );
+        Ok(vec![Artifact::new(
+            format!("{}.sh", theme_slug(theme)),
+            out,
+        )])
+    }
+}
+
+/// A stable UUID-shaped ID (version 5 layout) hashed from the theme name.
+fn profile_id(name: &str) -> String {
+    let digest = Sha256::digest(format!("cyberdeck gnome-terminal profile {name}"));
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x50;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// A single-quoted GVariant string.
+fn gvariant_string(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
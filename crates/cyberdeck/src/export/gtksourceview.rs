@@ -0,0 +1,163 @@
+//! `gtksourceview`: a GtkSourceView style scheme (`<slug>.xml`) for gedit,
+//! GNOME Builder, Meld and other GtkSourceView editors.
+//!
+//! The scheme declares the palette roles as named colors and builds every
+//! style from them. GtkSourceView has no translucency, so colors are
+//! composited over the editor background first.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, xml_escape, Artifact, Exporter, AUTHOR};
+
+/// Styles: foreground role, background role (empty for none) and
+/// attributes.
+const STYLES: &[(&str, &str, &str, &[&str])] = &[
+    ("text", "foreground", "background", &[]),
+    ("selection", "", "highlight", &[]),
+    ("selection-unfocused", "", "background.tertiary", &[]),
+    ("cursor", "cursor", "", &[]),
+    ("secondary-cursor", "foreground.muted", "", &[]),
+    ("current-line", "", "background.secondary", &[]),
+    ("line-numbers", "foreground.muted", "background", &[]),
+    (
+        "current-line-number",
+        "foreground",
+        "background.secondary",
+        &[],
+    ),
+    (
+        "bracket-match",
+        "accent.primary",
+        "background.tertiary",
+        &["bold"],
+    ),
+    ("bracket-mismatch", "error", "", &["underline"]),
+    ("right-margin", "border", "", &[]),
+    ("draw-spaces", "border", "", &[]),
+    ("search-match", "", "highlight", &[]),
+    ("def:comment", "comment", "", &["italic"]),
+    ("def:shebang", "comment", "", &["bold"]),
+    ("def:doc-comment-element", "comment", "", &["bold"]),
+    ("def:constant", "constant", "", &[]),
+    ("def:special-constant", "constant", "", &[]),
+    ("def:boolean", "constant", "", &[]),
+    ("def:string", "string", "", &[]),
+    ("def:character", "string", "", &[]),
+    ("def:special-char", "constant", "", &[]),
+    ("def:number", "number", "", &[]),
+    ("def:identifier", "variable", "", &[]),
+    ("def:function", "function", "", &[]),
+    ("def:builtin", "function", "", &[]),
+    ("def:statement", "keyword", "", &[]),
+    ("def:keyword", "keyword", "", &[]),
+    ("def:operator", "keyword", "", &[]),
+    ("def:preprocessor", "storage", "", &[]),
+    ("def:type", "type", "", &[]),
+    ("def:note", "warning", "", &["bold"]),
+    ("def:error", "error", "", &["underline"]),
+    ("def:warning", "warning", "", &[]),
+    ("def:net-address", "accent.primary", "", &["underline"]),
+    ("def:emphasis", "", "", &["italic"]),
+    ("def:strong-emphasis", "", "", &["bold"]),
+    ("def:heading", "keyword", "", &["bold"]),
+    ("def:link-text", "string", "", &[]),
+    ("def:link-destination", "accent.primary", "", &["underline"]),
+    ("def:insertion", "inserted", "", &[]),
+    ("def:deletion", "deleted", "", &["strikethrough"]),
+    ("diff:added-line", "inserted", "", &[]),
+    ("diff:removed-line", "deleted", "", &[]),
+    ("diff:changed-line", "modified", "", &[]),
+    ("diff:location", "accent.secondary", "", &["bold"]),
+];
+
+pub struct GtkSourceViewExporter;
+
+impl Exporter for GtkSourceViewExporter {
+    fn name(&self) -> &'static str {
+        "gtksourceview"
+    }
+
+    fn extension(&self) -> &'static str {
+        "xml"
+    }
+
+    fn description(&self) -> &'static str {
+        "GtkSourceView style scheme for gedit, GNOME Builder and Meld"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let slug = theme_slug(theme);
+        Ok(vec![Artifact::new(
+            format!("{slug}.xml"),
+            style_scheme(theme, &slug),
+        )])
+    }
+}
+
+fn style_scheme(theme: &Theme, slug: &str) -> String {
+    let roles = resolve_roles(theme);
+    let background = roles
+        .get("background")
+        .copied()
+        .unwrap_or(Color::rgb(0, 0, 0));
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<!-- Generated by `cyberdeck export` (gtksourceview target); do not edit. -->\n");
+    let _ = writeln!(
+        out,
+        "<style-scheme id=\"{slug}\" name=\"{}\" version=\"1.0\">",
+        xml_escape(theme_name(theme))
+    );
+    let _ = writeln!(out, "  <author>{}</author>", xml_escape(AUTHOR));
+    let _ = writeln!(
+        out,
+        "  <description>{}</description>\n",
+        xml_escape(theme_name(theme))
+    );
+    for (role, color) in &roles {
+        let _ = writeln!(
+            out,
+            "  <color name=\"{}\" value=\"{}\"/>",
+            color_name(role),
+            color.over(background).to_hex_rgb()
+        );
+    }
+    out.push('\n');
+
+    for (style, foreground, bg, attrs) in STYLES {
+        let mut attributes = String::new();
+        if roles.contains_key(foreground) {
+            let _ = write!(attributes, " foreground=\"{}\"", color_name(foreground));
+        }
+        if roles.contains_key(bg) {
+            let _ = write!(attributes, " background=\"{}\"", color_name(bg));
+        }
+        for (attr, markup) in [
+            ("bold", " bold=\"true\""),
+            ("italic", " italic=\"true\""),
+            ("underline", " underline=\"single\""),
+            ("strikethrough", " strikethrough=\"true\""),
+        ] {
+            if attrs.contains(&attr) {
+                attributes.push_str(markup);
+            }
+        }
+        if !attributes.is_empty() {
+            let _ = writeln!(out, "  <style name=\"{style}\"{attributes}/>");
+        }
+    }
+    out.push_str("</style-scheme>\n");
+    out
+}
+
+/// `background.secondary` → `background-secondary`.
+fn color_name(role: &str) -> String {
+    role.replace('.', "-")
+}
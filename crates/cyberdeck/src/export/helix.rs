@@ -0,0 +1,338 @@
+//! `helix`: a Helix theme (`<slug>.toml`).
+//!
+//! Helix styles refer to colors by name through the theme's `[palette]`
+//! table, so the colors are clustered into a palette the same way
+//! `cyberdeck import` does and named after the roles they play. Helix has
+//! no translucency; colors are composited over the editor background first.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::import::{build_palette, vscode, Sample};
+use crate::palette::Palette;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter, TokenStyle};
+
+/// Syntax scopes and the scope path each is resolved from.
+const SYNTAX_SCOPES: &[(&str, &str)] = &[
+    ("attribute", "meta.decorator"),
+    ("type", "entity.name.type"),
+    ("type.builtin", "support.type.primitive"),
+    ("constructor", "entity.name.function.constructor"),
+    ("constant", "variable.other.constant"),
+    ("constant.builtin", "constant.language"),
+    ("constant.builtin.boolean", "constant.language.boolean"),
+    ("constant.character", "constant.character"),
+    ("constant.character.escape", "constant.character.escape"),
+    ("constant.numeric", "constant.numeric"),
+    ("string", "string.quoted.double"),
+    ("string.regexp", "string.regexp"),
+    ("comment", "comment.line"),
+    ("comment.block.documentation", "comment.block.documentation"),
+    ("variable", "variable.other.readwrite"),
+    ("variable.builtin", "variable.language"),
+    ("variable.parameter", "variable.parameter"),
+    ("variable.other.member", "variable.other.property"),
+    ("label", "entity.name.label"),
+    ("punctuation", "punctuation"),
+    ("punctuation.delimiter", "punctuation.separator"),
+    ("punctuation.bracket", "punctuation.section.brackets"),
+    ("keyword", "keyword"),
+    ("keyword.control", "keyword.control"),
+    ("keyword.control.conditional", "keyword.control.conditional"),
+    ("keyword.control.repeat", "keyword.control.loop"),
+    ("keyword.control.import", "keyword.control.import"),
+    ("keyword.control.return", "keyword.control.return"),
+    ("keyword.control.exception", "keyword.control.exception"),
+    ("keyword.operator", "keyword.operator.word"),
+    ("keyword.directive", "meta.preprocessor"),
+    ("keyword.function", "storage.type.function"),
+    ("keyword.storage.type", "storage.type"),
+    ("keyword.storage.modifier", "storage.modifier"),
+    ("operator", "keyword.operator"),
+    ("function", "entity.name.function"),
+    ("function.builtin", "support.function"),
+    ("function.method", "entity.name.function.member"),
+    ("function.macro", "entity.name.function.macro"),
+    ("tag", "entity.name.tag"),
+    ("namespace", "entity.name.namespace"),
+    ("markup.heading", "markup.heading"),
+    ("markup.bold", "markup.bold"),
+    ("markup.italic", "markup.italic"),
+    ("markup.strikethrough", "markup.strikethrough"),
+    ("markup.link.url", "markup.underline.link"),
+    ("markup.link.text", "string.other.link"),
+    ("markup.quote", "markup.quote"),
+    ("markup.raw", "markup.inline.raw"),
+    ("diff.plus", "markup.inserted"),
+    ("diff.minus", "markup.deleted"),
+    ("diff.delta", "markup.changed"),
+];
+
+/// UI scopes and the workbench keys their foreground and background read.
+const UI_SCOPES: &[(&str, &[&str], &[&str])] = &[
+    ("ui.background", &[], &["editor.background"]),
+    ("ui.text", &["editor.foreground", "foreground"], &[]),
+    ("ui.text.focus", &["list.activeSelectionForeground"], &[]),
+    (
+        "ui.cursor",
+        &["editorCursor.background", "editor.background"],
+        &["editorCursor.foreground"],
+    ),
+    (
+        "ui.cursor.primary",
+        &["editorCursor.background", "editor.background"],
+        &["editorCursor.foreground"],
+    ),
+    (
+        "ui.cursor.match",
+        &["editorBracketMatch.border"],
+        &["editorBracketMatch.background"],
+    ),
+    (
+        "ui.cursorline.primary",
+        &[],
+        &["editor.lineHighlightBackground"],
+    ),
+    ("ui.linenr", &["editorLineNumber.foreground"], &[]),
+    (
+        "ui.linenr.selected",
+        &["editorLineNumber.activeForeground"],
+        &[],
+    ),
+    ("ui.gutter", &[], &["editorGutter.background"]),
+    ("ui.selection", &[], &["editor.inactiveSelectionBackground"]),
+    ("ui.selection.primary", &[], &["editor.selectionBackground"]),
+    (
+        "ui.statusline",
+        &["statusBar.foreground"],
+        &["statusBar.background"],
+    ),
+    (
+        "ui.statusline.inactive",
+        &["statusBar.foreground"],
+        &["statusBar.noFolderBackground", "statusBar.background"],
+    ),
+    (
+        "ui.popup",
+        &["editorWidget.foreground"],
+        &["editorWidget.background"],
+    ),
+    ("ui.window", &["editorGroup.border"], &[]),
+    (
+        "ui.help",
+        &["editorHoverWidget.foreground"],
+        &["editorHoverWidget.background"],
+    ),
+    (
+        "ui.menu",
+        &["editorSuggestWidget.foreground"],
+        &["editorSuggestWidget.background"],
+    ),
+    (
+        "ui.menu.selected",
+        &["editorSuggestWidget.selectedForeground"],
+        &["editorSuggestWidget.selectedBackground"],
+    ),
+    (
+        "ui.bufferline",
+        &["tab.inactiveForeground"],
+        &["tab.inactiveBackground"],
+    ),
+    (
+        "ui.bufferline.active",
+        &["tab.activeForeground"],
+        &["tab.activeBackground"],
+    ),
+    (
+        "ui.virtual.whitespace",
+        &["editorWhitespace.foreground"],
+        &[],
+    ),
+    ("ui.virtual.ruler", &[], &["editorRuler.foreground"]),
+    (
+        "ui.virtual.indent-guide",
+        &[
+            "editorIndentGuide.background1",
+            "editorIndentGuide.background",
+        ],
+        &[],
+    ),
+    (
+        "ui.virtual.inlay-hint",
+        &["editorInlayHint.foreground"],
+        &["editorInlayHint.background"],
+    ),
+    ("ui.highlight", &[], &["editor.rangeHighlightBackground"]),
+];
+
+/// Diagnostic severities and the workbench keys their color reads.
+const DIAGNOSTICS: &[(&str, &[&str])] = &[
+    ("error", &["editorError.foreground", "errorForeground"]),
+    ("warning", &["editorWarning.foreground"]),
+    ("info", &["editorInfo.foreground"]),
+    ("hint", &["editorHint.foreground", "editorInfo.foreground"]),
+];
+
+pub struct HelixExporter;
+
+impl Exporter for HelixExporter {
+    fn name(&self) -> &'static str {
+        "helix"
+    }
+
+    fn extension(&self) -> &'static str {
+        "toml"
+    }
+
+    fn description(&self) -> &'static str {
+        "Helix theme with a named palette"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        Ok(vec![Artifact::new(
+            format!("{}.toml", theme_slug(theme)),
+            helix_theme(theme),
+        )])
+    }
+}
+
+/// One scope's style, with colors already composited.
+struct Style {
+    scope: String,
+    token: TokenStyle,
+    /// Diagnostics underline in this color instead of recoloring the text.
+    underline: Option<Color>,
+}
+
+fn helix_theme(theme: &Theme) -> String {
+    let background = workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+    let composite = |c: Option<Color>| c.map(|c| c.over(background));
+    let resolver = Resolver::new(theme);
+
+    let mut styles = Vec::new();
+    let mut push = |scope: &str, token: TokenStyle, underline: Option<Color>| {
+        styles.push(Style {
+            scope: scope.to_owned(),
+            token: TokenStyle {
+                foreground: composite(token.foreground),
+                background: composite(token.background),
+                ..token
+            },
+            underline: composite(underline),
+        });
+    };
+    for (scope, path) in SYNTAX_SCOPES {
+        if let Some(token) = TokenStyle::resolve(&resolver, path) {
+            push(scope, token, None);
+        }
+    }
+    for (scope, foreground, bg) in UI_SCOPES {
+        let token = TokenStyle {
+            foreground: workbench_color(theme, foreground),
+            background: workbench_color(theme, bg),
+            ..TokenStyle::default()
+        };
+        if token.foreground.is_some() || token.background.is_some() {
+            push(scope, token, None);
+        }
+    }
+    for (severity, keys) in DIAGNOSTICS {
+        if let Some(color) = workbench_color(theme, keys) {
+            let token = TokenStyle {
+                foreground: Some(color),
+                ..TokenStyle::default()
+            };
+            push(severity, token, None);
+            push(
+                &format!("diagnostic.{severity}"),
+                TokenStyle::default(),
+                Some(color),
+            );
+        }
+    }
+
+    let palette = palette(theme, &styles);
+    let name = |color: Color| {
+        palette
+            .find(color)
+            .map_or_else(|| color.to_hex_rgb(), |entry| palette_key(&entry.name))
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}", theme_name(theme));
+    out.push_str("# Generated by `cyberdeck export --target helix`; do not edit.\n\n");
+    for style in &styles {
+        let mut fields = Vec::new();
+        if let Some(color) = style.token.foreground {
+            fields.push(format!("fg = \"{}\"", name(color)));
+        }
+        if let Some(color) = style.token.background {
+            fields.push(format!("bg = \"{}\"", name(color)));
+        }
+        if let Some(color) = style.underline {
+            fields.push(format!(
+                "underline = {{ color = \"{}\", style = \"curl\" }}",
+                name(color)
+            ));
+        }
+        let modifiers: Vec<&str> = [
+            (style.token.bold, "\"bold\""),
+            (style.token.italic, "\"italic\""),
+            (style.token.underline, "\"underlined\""),
+            (style.token.strikethrough, "\"crossed_out\""),
+        ]
+        .into_iter()
+        .filter_map(|(set, modifier)| set.then_some(modifier))
+        .collect();
+        if !modifiers.is_empty() {
+            fields.push(format!("modifiers = [{}]", modifiers.join(", ")));
+        }
+        let _ = writeln!(out, "\"{}\" = {{ {} }}", style.scope, fields.join(", "));
+    }
+
+    out.push_str("\n[palette]\n");
+    for entry in &palette.colors {
+        let _ = writeln!(
+            out,
+            "{} = \"{}\"",
+            palette_key(&entry.name),
+            entry.hex.to_hex_rgb()
+        );
+    }
+    out
+}
+
+/// The palette the styles refer to: every color they use, named after its
+/// role where the theme gives it one.
+fn palette(theme: &Theme, styles: &[Style]) -> Palette {
+    let used: Vec<Color> = styles
+        .iter()
+        .flat_map(|s| [s.token.foreground, s.token.background, s.underline])
+        .flatten()
+        .collect();
+    let mut samples: Vec<Sample> = vscode::samples(theme)
+        .into_iter()
+        .filter(|s| used.contains(&s.color))
+        .collect();
+    for color in used {
+        if !samples.iter().any(|s| s.color == color) {
+            samples.push(Sample {
+                color,
+                weight: 1,
+                roles: Vec::new(),
+            });
+        }
+    }
+    // A zero threshold keeps every used color exactly.
+    build_palette(theme_name(theme), samples, 0.0)
+}
+
+/// `Background Secondary` → `background-secondary`.
+fn palette_key(name: &str) -> String {
+    name.to_lowercase().replace(' ', "-")
+}
@@ -0,0 +1,142 @@
+//! `highlightjs`: a highlight.js stylesheet (`<slug>.css`).
+//!
+//! Each `.hljs-*` class is styled after the scope path of the construct
+//! highlight.js gives it; classes no rule styles inherit the `.hljs` text
+//! color.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter, TokenStyle, AUTHOR};
+
+/// highlight.js selectors and the scope path each is resolved from.
+const CLASSES: &[(&str, &str)] = &[
+    (".hljs-comment,\n.hljs-quote", "comment.line"),
+    (".hljs-doctag", "comment.block.documentation storage.type"),
+    (".hljs-keyword", "keyword.control"),
+    (".hljs-operator", "keyword.operator"),
+    (".hljs-punctuation", "punctuation"),
+    (".hljs-built_in", "support.function"),
+    (".hljs-type", "support.type.primitive"),
+    (".hljs-literal", "constant.language"),
+    (".hljs-number", "constant.numeric"),
+    (".hljs-string", "string.quoted.double"),
+    (".hljs-char.escape_", "constant.character.escape"),
+    (".hljs-regexp", "string.regexp"),
+    (".hljs-subst", "meta.embedded"),
+    (".hljs-symbol", "constant.other.symbol"),
+    (".hljs-variable", "variable.other.readwrite"),
+    (".hljs-variable.language_", "variable.language"),
+    (".hljs-variable.constant_", "variable.other.constant"),
+    (".hljs-template-variable", "variable.other.readwrite"),
+    (".hljs-params", "variable.parameter"),
+    (".hljs-property", "variable.other.property"),
+    (".hljs-title", "entity.name.function"),
+    (".hljs-title.function_", "entity.name.function"),
+    (
+        ".hljs-title.class_,\n.hljs-title.class_.inherited__",
+        "entity.name.type.class",
+    ),
+    (".hljs-meta", "meta.preprocessor"),
+    (
+        ".hljs-meta .hljs-keyword",
+        "meta.preprocessor keyword.control",
+    ),
+    (".hljs-meta .hljs-string", "meta.preprocessor string"),
+    (".hljs-tag", "meta.tag punctuation.definition.tag"),
+    (".hljs-name", "entity.name.tag"),
+    (".hljs-attr", "variable.other.property"),
+    (".hljs-attribute", "entity.other.attribute-name"),
+    (".hljs-selector-tag", "entity.name.tag.css"),
+    (".hljs-selector-id", "entity.other.attribute-name.id"),
+    (".hljs-selector-class", "entity.other.attribute-name.class"),
+    (
+        ".hljs-selector-attr,\n.hljs-selector-pseudo",
+        "entity.other.attribute-name.pseudo-class",
+    ),
+    (".hljs-section", "markup.heading"),
+    (".hljs-bullet", "punctuation.definition.list.begin.markdown"),
+    (".hljs-code", "markup.inline.raw"),
+    (".hljs-link", "markup.underline.link"),
+    (".hljs-emphasis", "markup.italic"),
+    (".hljs-strong", "markup.bold"),
+    (".hljs-addition", "markup.inserted"),
+    (".hljs-deletion", "markup.deleted"),
+];
+
+pub struct HighlightJsExporter;
+
+impl Exporter for HighlightJsExporter {
+    fn name(&self) -> &'static str {
+        "highlightjs"
+    }
+
+    fn extension(&self) -> &'static str {
+        "css"
+    }
+
+    fn description(&self) -> &'static str {
+        "highlight.js stylesheet"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let resolver = Resolver::new(theme);
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "/*!\n  Theme: {}\n  Author: {}\n  \
+             Generated by `cyberdeck export --target highlightjs`; do not edit.\n*/\n",
+            theme_name(theme),
+            AUTHOR
+        );
+
+        let base = TokenStyle {
+            foreground: resolver.defaults().foreground,
+            background: workbench_color(theme, &["editor.background"]),
+            ..TokenStyle::default()
+        };
+        rule(
+            &mut out,
+            "pre code.hljs",
+            &["display: block;", "overflow-x: auto;", "padding: 1em;"].map(str::to_owned),
+        );
+        rule(&mut out, "code.hljs", &["padding: 3px 5px;".to_owned()]);
+        rule(&mut out, ".hljs", &base.css_declarations());
+        if let Some(selection) = workbench_color(theme, &["editor.selectionBackground"]) {
+            let mut declarations = vec![format!("background-color: {selection};")];
+            if let Some(color) = workbench_color(theme, &["editor.selectionForeground"]) {
+                declarations.push(format!("color: {color};"));
+            }
+            rule(
+                &mut out,
+                ".hljs::selection,\n.hljs ::selection",
+                &declarations,
+            );
+        }
+        for (selector, scope) in CLASSES {
+            if let Some(style) = TokenStyle::resolve(&resolver, scope) {
+                rule(&mut out, selector, &style.css_declarations());
+            }
+        }
+        Ok(vec![Artifact::new(
+            format!("{}.css", theme_slug(theme)),
+            format!("{}\n", out.trim_end()),
+        )])
+    }
+}
+
+/// Appends a CSS rule, unless it has no declarations.
+pub(super) fn rule(out: &mut String, selector: &str, declarations: &[String]) {
+    if declarations.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "{selector} {{");
+    for declaration in declarations {
+        let _ = writeln!(out, "  {declaration}");
+    }
+    out.push_str("}\n\n");
+}
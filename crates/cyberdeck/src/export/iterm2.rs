@@ -0,0 +1,93 @@
+//! `iterm2`: an iTerm2 color preset (`<name>.itermcolors`).
+//!
+//! Each color is a dictionary of sRGB components as plist reals. Components
+//! are written with the shortest decimal that reads back to the same double,
+//! so iTerm2 lands on exactly the theme's 8-bit values.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, workbench_color, xml_escape, Artifact, Exporter, TerminalColors};
+
+pub struct Iterm2Exporter;
+
+impl Exporter for Iterm2Exporter {
+    fn name(&self) -> &'static str {
+        "iterm2"
+    }
+
+    fn extension(&self) -> &'static str {
+        "itermcolors"
+    }
+
+    fn description(&self) -> &'static str {
+        "iTerm2 color preset"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let colors = TerminalColors::new(theme);
+        let mut entries: Vec<(String, Color)> = colors
+            .ansi
+            .iter()
+            .enumerate()
+            .map(|(index, color)| (format!("Ansi {index} Color"), *color))
+            .collect();
+        let link = workbench_color(theme, &["terminal.ansiBlue", "textLink.foreground"])
+            .map_or(colors.ansi[4], |c| c.over(colors.background));
+        entries.extend([
+            ("Background Color".to_owned(), colors.background),
+            ("Foreground Color".to_owned(), colors.foreground),
+            ("Bold Color".to_owned(), colors.foreground),
+            ("Cursor Color".to_owned(), colors.cursor),
+            ("Cursor Text Color".to_owned(), colors.cursor_text),
+            ("Selection Color".to_owned(), colors.selection_background),
+            (
+                "Selected Text Color".to_owned(),
+                colors.selection_foreground.unwrap_or(colors.foreground),
+            ),
+            ("Link Color".to_owned(), link),
+            (
+                "Cursor Guide Color".to_owned(),
+                colors.cursor.with_alpha(0x40),
+            ),
+        ]);
+        // Property lists conventionally keep dictionary keys sorted.
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+             \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+             <!-- {} (generated by `cyberdeck export`; do not edit) -->\n\
+             <plist version=\"1.0\">\n<dict>\n",
+            xml_escape(theme_name(theme))
+        );
+        for (key, color) in &entries {
+            let _ = writeln!(out, "\t<key>{key}</key>\n\t<dict>");
+            for (component, value) in [
+                ("Alpha", color.a),
+                ("Blue", color.b),
+                ("Green", color.g),
+                ("Red", color.r),
+            ] {
+                let _ = writeln!(
+                    out,
+                    "\t\t<key>{component} Component</key>\n\t\t<real>{}</real>",
+                    f64::from(value) / 255.0
+                );
+            }
+            out.push_str("\t\t<key>Color Space</key>\n\t\t<string>sRGB</string>\n\t</dict>\n");
+        }
+        out.push_str("</dict>\n</plist>\n");
+        Ok(vec![Artifact::new(
+            format!("{}.itermcolors", theme_name(theme)),
+            out,
+        )])
+    }
+}
@@ -0,0 +1,344 @@
+//! `jetbrains`: an IntelliJ-platform editor scheme (`.icls`) and the UI
+//! theme (`.theme.json`) that installs it.
+//!
+//! Editor attributes are resolved from the token rules; anything the theme
+//! does not style is left to the Darcula parent scheme. Editor scheme colors
+//! cannot be translucent, so they are composited over the editor background.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+use crate::color::Color;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{
+    is_dark, theme_name, theme_slug, workbench_color, xml_escape, Artifact, Exporter, TokenStyle,
+};
+
+/// Editor scheme `<colors>` options and the workbench keys they read.
+const EDITOR_COLORS: &[(&str, &[&str])] = &[
+    ("CARET_COLOR", &["editorCursor.foreground"]),
+    ("CARET_ROW_COLOR", &["editor.lineHighlightBackground"]),
+    ("SELECTION_BACKGROUND", &["editor.selectionBackground"]),
+    (
+        "GUTTER_BACKGROUND",
+        &["editorGutter.background", "editor.background"],
+    ),
+    ("LINE_NUMBERS_COLOR", &["editorLineNumber.foreground"]),
+    (
+        "LINE_NUMBER_ON_CARET_ROW_COLOR",
+        &["editorLineNumber.activeForeground"],
+    ),
+    (
+        "INDENT_GUIDE",
+        &[
+            "editorIndentGuide.background1",
+            "editorIndentGuide.background",
+        ],
+    ),
+    (
+        "SELECTED_INDENT_GUIDE",
+        &[
+            "editorIndentGuide.activeBackground1",
+            "editorIndentGuide.activeBackground",
+        ],
+    ),
+    ("WHITESPACES", &["editorWhitespace.foreground"]),
+    ("RIGHT_MARGIN_COLOR", &["editorRuler.foreground"]),
+    ("ADDED_LINES_COLOR", &["editorGutter.addedBackground"]),
+    ("MODIFIED_LINES_COLOR", &["editorGutter.modifiedBackground"]),
+    ("DELETED_LINES_COLOR", &["editorGutter.deletedBackground"]),
+    (
+        "CONSOLE_BACKGROUND_KEY",
+        &["terminal.background", "panel.background"],
+    ),
+    ("DOCUMENTATION_COLOR", &["editorHoverWidget.background"]),
+    ("LOOKUP_COLOR", &["editorSuggestWidget.background"]),
+    ("TEARLINE_COLOR", &["editorGroup.border"]),
+];
+
+/// Editor scheme `<attributes>` and the scope path each is resolved from.
+const ATTRIBUTES: &[(&str, &str)] = &[
+    ("DEFAULT_KEYWORD", "keyword.control"),
+    ("DEFAULT_LINE_COMMENT", "comment.line"),
+    ("DEFAULT_BLOCK_COMMENT", "comment.block"),
+    ("DEFAULT_DOC_COMMENT", "comment.block.documentation"),
+    ("DEFAULT_STRING", "string.quoted.double"),
+    ("DEFAULT_VALID_STRING_ESCAPE", "constant.character.escape"),
+    ("DEFAULT_INVALID_STRING_ESCAPE", "invalid.illegal"),
+    ("DEFAULT_NUMBER", "constant.numeric"),
+    ("DEFAULT_CONSTANT", "variable.other.constant"),
+    ("DEFAULT_PREDEFINED_SYMBOL", "support.function.builtin"),
+    ("DEFAULT_IDENTIFIER", "variable.other"),
+    ("DEFAULT_LOCAL_VARIABLE", "variable.other.readwrite"),
+    ("DEFAULT_GLOBAL_VARIABLE", "variable.other.global"),
+    ("DEFAULT_PARAMETER", "variable.parameter"),
+    ("DEFAULT_INSTANCE_FIELD", "variable.other.property"),
+    ("DEFAULT_STATIC_FIELD", "variable.other.constant.property"),
+    ("DEFAULT_FUNCTION_DECLARATION", "entity.name.function"),
+    (
+        "DEFAULT_FUNCTION_CALL",
+        "meta.function-call entity.name.function",
+    ),
+    ("DEFAULT_INSTANCE_METHOD", "entity.name.function.member"),
+    ("DEFAULT_CLASS_NAME", "entity.name.class"),
+    ("DEFAULT_CLASS_REFERENCE", "entity.name.type"),
+    ("DEFAULT_INTERFACE_NAME", "entity.name.type.interface"),
+    ("DEFAULT_METADATA", "meta.decorator"),
+    ("DEFAULT_LABEL", "entity.name.label"),
+    ("DEFAULT_OPERATION_SIGN", "keyword.operator"),
+    ("DEFAULT_BRACES", "punctuation.section.braces"),
+    ("DEFAULT_BRACKETS", "punctuation.section.brackets"),
+    ("DEFAULT_PARENTHS", "punctuation.section.parens"),
+    ("DEFAULT_COMMA", "punctuation.separator.comma"),
+    ("DEFAULT_DOT", "punctuation.accessor"),
+    ("DEFAULT_SEMICOLON", "punctuation.terminator"),
+    ("DEFAULT_TAG", "entity.name.tag"),
+    ("DEFAULT_ATTRIBUTE", "entity.other.attribute-name"),
+    ("DEFAULT_ENTITY", "constant.character.entity"),
+];
+
+/// UI theme keys and the workbench keys they read.
+const UI_KEYS: &[(&str, &[&str])] = &[
+    ("*.background", &["editor.background"]),
+    ("*.foreground", &["foreground", "editor.foreground"]),
+    ("*.selectionBackground", &["list.activeSelectionBackground"]),
+    ("*.selectionForeground", &["list.activeSelectionForeground"]),
+    (
+        "*.selectionInactiveBackground",
+        &["list.inactiveSelectionBackground"],
+    ),
+    ("*.hoverBackground", &["list.hoverBackground"]),
+    ("*.borderColor", &["panel.border", "editorGroup.border"]),
+    ("*.separatorColor", &["panel.border", "editorGroup.border"]),
+    (
+        "*.disabledForeground",
+        &["disabledForeground", "descriptionForeground"],
+    ),
+    ("*.infoForeground", &["descriptionForeground"]),
+    ("Component.focusColor", &["focusBorder"]),
+    ("Component.focusedBorderColor", &["focusBorder"]),
+    (
+        "Component.errorFocusColor",
+        &["inputValidation.errorBorder", "errorForeground"],
+    ),
+    ("Link.activeForeground", &["textLink.foreground"]),
+    ("Link.hoverForeground", &["textLink.activeForeground"]),
+    ("Button.default.startBackground", &["button.background"]),
+    ("Button.default.endBackground", &["button.background"]),
+    ("Button.default.foreground", &["button.foreground"]),
+    ("TextField.background", &["input.background"]),
+    ("TextField.foreground", &["input.foreground"]),
+    ("ComboBox.background", &["dropdown.background"]),
+    ("Popup.background", &["editorWidget.background"]),
+    ("ToolTip.background", &["editorHoverWidget.background"]),
+    (
+        "ToolWindow.Header.background",
+        &["sideBarSectionHeader.background"],
+    ),
+    ("ToolWindow.background", &["sideBar.background"]),
+    ("SidePanel.background", &["sideBar.background"]),
+    ("MainToolbar.background", &["titleBar.activeBackground"]),
+    (
+        "MainWindow.Tab.selectedBackground",
+        &["tab.activeBackground"],
+    ),
+    (
+        "EditorTabs.background",
+        &["editorGroupHeader.tabsBackground"],
+    ),
+    ("EditorTabs.selectedBackground", &["tab.activeBackground"]),
+    (
+        "EditorTabs.underlineColor",
+        &["tab.activeBorder", "tab.activeBorderTop"],
+    ),
+    (
+        "EditorTabs.inactiveColoredFileBackground",
+        &["tab.inactiveBackground"],
+    ),
+    ("TabbedPane.underlineColor", &["panelTitle.activeBorder"]),
+    ("StatusBar.background", &["statusBar.background"]),
+    ("StatusBar.foreground", &["statusBar.foreground"]),
+    ("ProgressBar.progressColor", &["progressBar.background"]),
+    ("ScrollBar.thumbColor", &["scrollbarSlider.background"]),
+    (
+        "ScrollBar.hoverThumbColor",
+        &["scrollbarSlider.hoverBackground"],
+    ),
+    (
+        "SearchMatch.startBackground",
+        &["editor.findMatchBackground"],
+    ),
+    ("SearchMatch.endBackground", &["editor.findMatchBackground"]),
+    ("Notification.background", &["notifications.background"]),
+    ("Counter.background", &["badge.background"]),
+    ("Counter.foreground", &["badge.foreground"]),
+];
+
+pub struct JetBrainsExporter;
+
+impl Exporter for JetBrainsExporter {
+    fn name(&self) -> &'static str {
+        "jetbrains"
+    }
+
+    fn extension(&self) -> &'static str {
+        "icls"
+    }
+
+    fn description(&self) -> &'static str {
+        "JetBrains IDE editor scheme and UI theme"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let slug = theme_slug(theme);
+        let scheme_file = format!("{slug}.icls");
+        Ok(vec![
+            Artifact::new(&scheme_file, editor_scheme(theme)),
+            Artifact::new(format!("{slug}.theme.json"), ui_theme(theme, &scheme_file)?),
+        ])
+    }
+}
+
+fn editor_scheme(theme: &Theme) -> String {
+    let background = workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+    let hex = |color: Color| color.over(background).to_hex_rgb()[1..].to_owned();
+    let resolver = Resolver::new(theme);
+    let defaults = resolver.defaults();
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<scheme name=\"{}\" version=\"142\" parent_scheme=\"{}\">",
+        xml_escape(theme_name(theme)),
+        if is_dark(theme) { "Darcula" } else { "Default" }
+    );
+    out.push_str("  <colors>\n");
+    for (option, keys) in EDITOR_COLORS {
+        if let Some(color) = workbench_color(theme, keys) {
+            let _ = writeln!(
+                out,
+                "    <option name=\"{option}\" value=\"{}\" />",
+                hex(color)
+            );
+        }
+    }
+    out.push_str("  </colors>\n  <attributes>\n");
+
+    let text = TokenStyle {
+        foreground: defaults.foreground,
+        background: Some(background),
+        ..TokenStyle::default()
+    };
+    write_attribute(&mut out, "TEXT", &text, hex);
+    for (attribute, scope) in ATTRIBUTES {
+        if let Some(style) = TokenStyle::resolve(&resolver, scope) {
+            write_attribute(&mut out, attribute, &style, hex);
+        }
+    }
+    for (attribute, keys) in [
+        (
+            "ERRORS_ATTRIBUTES",
+            ["editorError.foreground", "errorForeground"],
+        ),
+        (
+            "WARNING_ATTRIBUTES",
+            ["editorWarning.foreground", "warningForeground"],
+        ),
+    ] {
+        if let Some(color) = workbench_color(theme, &keys) {
+            let _ = writeln!(
+                out,
+                "    <option name=\"{attribute}\">\n      <value>\n        \
+                 <option name=\"EFFECT_COLOR\" value=\"{}\" />\n        \
+                 <option name=\"EFFECT_TYPE\" value=\"2\" />\n      </value>\n    </option>",
+                hex(color)
+            );
+        }
+    }
+    out.push_str("  </attributes>\n</scheme>\n");
+    out
+}
+
+fn write_attribute(
+    out: &mut String,
+    name: &str,
+    style: &TokenStyle,
+    hex: impl Fn(Color) -> String,
+) {
+    let _ = writeln!(out, "    <option name=\"{name}\">\n      <value>");
+    if let Some(foreground) = style.foreground {
+        let _ = writeln!(
+            out,
+            "        <option name=\"FOREGROUND\" value=\"{}\" />",
+            hex(foreground)
+        );
+    }
+    if let Some(background) = style.background {
+        let _ = writeln!(
+            out,
+            "        <option name=\"BACKGROUND\" value=\"{}\" />",
+            hex(background)
+        );
+    }
+    let font_type = u8::from(style.bold) | (u8::from(style.italic) << 1);
+    if font_type != 0 {
+        let _ = writeln!(
+            out,
+            "        <option name=\"FONT_TYPE\" value=\"{font_type}\" />"
+        );
+    }
+    // Effect type 1 is an underline, 3 a strikeout; only one can be shown.
+    let effect = if style.underline {
+        Some(1)
+    } else if style.strikethrough {
+        Some(3)
+    } else {
+        None
+    };
+    if let (Some(effect), Some(foreground)) = (effect, style.foreground) {
+        let _ = writeln!(
+            out,
+            "        <option name=\"EFFECT_COLOR\" value=\"{}\" />\n        \
+             <option name=\"EFFECT_TYPE\" value=\"{effect}\" />",
+            hex(foreground)
+        );
+    }
+    out.push_str("      </value>\n    </option>\n");
+}
+
+fn ui_theme(theme: &Theme, scheme_file: &str) -> Result<String> {
+    let mut ui: Map<String, Value> = Map::new();
+    for (key, sources) in UI_KEYS {
+        let Some(color) = workbench_color(theme, sources) else {
+            continue;
+        };
+        // `Button.default.foreground` nests as `Button` → `default` → key.
+        let mut parts: Vec<&str> = key.split('.').collect();
+        let leaf = parts.pop().unwrap_or(key);
+        let mut group = &mut ui;
+        for part in parts {
+            let entry = group
+                .entry(part.to_owned())
+                .or_insert_with(|| Value::Object(Map::new()));
+            group = match entry {
+                Value::Object(map) => map,
+                _ => unreachable!("only leaves hold colors"),
+            };
+        }
+        group.insert(leaf.to_owned(), color.to_string().into());
+    }
+
+    let document = json!({
+        "name": theme_name(theme),
+        "dark": is_dark(theme),
+        "editorScheme": format!("/{scheme_file}"),
+        "ui": ui,
+    });
+    let mut text = serde_json::to_string_pretty(&document)?;
+    text.push('\n');
+    Ok(text)
+}
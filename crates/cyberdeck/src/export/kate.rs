@@ -0,0 +1,189 @@
+//! `kate`: a KSyntaxHighlighting theme (`<slug>.theme`) for Kate, KWrite,
+//! KDevelop and anything else built on KTextEditor.
+//!
+//! Text styles are resolved from the token rules and editor colors read
+//! from the workbench keys, composited over the editor background.
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+use crate::color::Color;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter, TokenStyle, AUTHOR};
+
+/// Default text styles and the scope path each is resolved from.
+const TEXT_STYLES: &[(&str, &str)] = &[
+    ("Keyword", "keyword"),
+    ("Function", "entity.name.function"),
+    ("Variable", "variable.other.readwrite"),
+    ("ControlFlow", "keyword.control"),
+    ("Operator", "keyword.operator"),
+    ("BuiltIn", "support.function"),
+    ("Extension", "support.class"),
+    ("Preprocessor", "meta.preprocessor"),
+    ("Attribute", "entity.other.attribute-name"),
+    ("Char", "constant.character"),
+    ("SpecialChar", "constant.character.escape"),
+    ("String", "string.quoted.double"),
+    ("VerbatimString", "string.quoted.other"),
+    ("SpecialString", "string.regexp"),
+    ("Import", "keyword.control.import"),
+    ("DataType", "entity.name.type"),
+    ("DecVal", "constant.numeric.integer"),
+    ("BaseN", "constant.numeric.hex"),
+    ("Float", "constant.numeric.float"),
+    ("Constant", "constant.language"),
+    ("Comment", "comment.line"),
+    ("Documentation", "comment.block.documentation"),
+    ("Annotation", "meta.decorator"),
+    (
+        "CommentVar",
+        "comment.block.documentation variable.parameter",
+    ),
+    (
+        "RegionMarker",
+        "comment.line punctuation.definition.comment",
+    ),
+    ("Error", "invalid.illegal"),
+    ("Others", "variable.other"),
+];
+
+/// Text styles that read a workbench color instead of a token rule.
+const STATUS_STYLES: &[(&str, &[&str])] = &[
+    ("Information", &["editorInfo.foreground"]),
+    ("Warning", &["editorWarning.foreground"]),
+    ("Alert", &["editorError.foreground", "errorForeground"]),
+];
+
+/// Editor colors and the workbench keys they read.
+const EDITOR_COLORS: &[(&str, &[&str])] = &[
+    ("BackgroundColor", &["editor.background"]),
+    ("BracketMatching", &["editorBracketMatch.background"]),
+    ("CodeFolding", &["editor.foldBackground"]),
+    ("CurrentLine", &["editor.lineHighlightBackground"]),
+    ("CurrentLineNumber", &["editorLineNumber.activeForeground"]),
+    (
+        "IconBorder",
+        &["editorGutter.background", "editor.background"],
+    ),
+    (
+        "IndentationLine",
+        &[
+            "editorIndentGuide.background1",
+            "editorIndentGuide.background",
+        ],
+    ),
+    ("LineNumbers", &["editorLineNumber.foreground"]),
+    (
+        "MarkBookmark",
+        &["editorBookmark.foreground", "textLink.foreground"],
+    ),
+    ("MarkError", &["editorError.foreground", "errorForeground"]),
+    ("MarkWarning", &["editorWarning.foreground"]),
+    ("ModifiedLines", &["editorGutter.modifiedBackground"]),
+    ("SavedLines", &["editorGutter.addedBackground"]),
+    ("SearchHighlight", &["editor.findMatchBackground"]),
+    ("ReplaceHighlight", &["editor.findMatchHighlightBackground"]),
+    ("Separator", &["editorGroup.border"]),
+    (
+        "SpellChecking",
+        &["editorError.foreground", "errorForeground"],
+    ),
+    ("TabMarker", &["editorWhitespace.foreground"]),
+    ("TextSelection", &["editor.selectionBackground"]),
+    ("WordWrapMarker", &["editorRuler.foreground"]),
+];
+
+pub struct KateExporter;
+
+impl Exporter for KateExporter {
+    fn name(&self) -> &'static str {
+        "kate"
+    }
+
+    fn extension(&self) -> &'static str {
+        "theme"
+    }
+
+    fn description(&self) -> &'static str {
+        "KSyntaxHighlighting theme for Kate, KWrite and KDevelop"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let background =
+            workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+        let hex = |color: Color| color.over(background).to_hex_rgb();
+        let resolver = Resolver::new(theme);
+        let selected = workbench_color(theme, &["editor.selectionForeground"]);
+
+        let mut text_styles = Map::new();
+        let normal = TokenStyle {
+            foreground: resolver.defaults().foreground,
+            ..TokenStyle::default()
+        };
+        text_styles.insert("Normal".to_owned(), text_style(&normal, selected, hex));
+        for (name, scope) in TEXT_STYLES {
+            if let Some(style) = TokenStyle::resolve(&resolver, scope) {
+                text_styles.insert((*name).to_owned(), text_style(&style, selected, hex));
+            }
+        }
+        for (name, keys) in STATUS_STYLES {
+            if let Some(color) = workbench_color(theme, keys) {
+                let style = TokenStyle {
+                    foreground: Some(color),
+                    ..TokenStyle::default()
+                };
+                text_styles.insert((*name).to_owned(), text_style(&style, selected, hex));
+            }
+        }
+
+        let mut editor_colors = Map::new();
+        for (name, keys) in EDITOR_COLORS {
+            if let Some(color) = workbench_color(theme, keys) {
+                editor_colors.insert((*name).to_owned(), hex(color).into());
+            }
+        }
+
+        let document = json!({
+            "metadata": {
+                "name": theme_name(theme),
+                "revision": 1,
+                "copyright": [format!("SPDX-FileCopyrightText: {AUTHOR}")],
+                "license": "SPDX-License-Identifier: MIT",
+            },
+            "text-styles": text_styles,
+            "editor-colors": editor_colors,
+        });
+        let mut text = serde_json::to_string_pretty(&document)?;
+        text.push('\n');
+        Ok(vec![Artifact::new(
+            format!("{}.theme", theme_slug(theme)),
+            text,
+        )])
+    }
+}
+
+fn text_style(style: &TokenStyle, selected: Option<Color>, hex: impl Fn(Color) -> String) -> Value {
+    let mut fields = Map::new();
+    if let Some(foreground) = style.foreground {
+        fields.insert("text-color".to_owned(), hex(foreground).into());
+        let selected = selected.unwrap_or(foreground);
+        fields.insert("selected-text-color".to_owned(), hex(selected).into());
+    }
+    if let Some(background) = style.background {
+        fields.insert("background-color".to_owned(), hex(background).into());
+    }
+    for (set, key) in [
+        (style.bold, "bold"),
+        (style.italic, "italic"),
+        (style.underline, "underline"),
+        (style.strikethrough, "strike-through"),
+    ] {
+        if set {
+            fields.insert(key.to_owned(), true.into());
+        }
+    }
+    fields.into()
+}
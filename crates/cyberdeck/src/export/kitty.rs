@@ -0,0 +1,97 @@
+//! `kitty`: a kitty theme (`<slug>.conf`), with the `##` metadata header
+//! the `kitten themes` browser reads.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter, TerminalColors, AUTHOR};
+
+/// Window chrome settings and the workbench keys they read.
+const CHROME: &[(&str, &[&str])] = &[
+    ("url_color", &["textLink.foreground", "terminal.ansiBlue"]),
+    ("active_border_color", &["focusBorder"]),
+    (
+        "inactive_border_color",
+        &["editorGroup.border", "panel.border"],
+    ),
+    ("bell_border_color", &["editorWarning.foreground"]),
+    (
+        "tab_bar_background",
+        &[
+            "editorGroupHeader.tabsBackground",
+            "titleBar.activeBackground",
+        ],
+    ),
+    ("active_tab_foreground", &["tab.activeForeground"]),
+    ("active_tab_background", &["tab.activeBackground"]),
+    ("inactive_tab_foreground", &["tab.inactiveForeground"]),
+    ("inactive_tab_background", &["tab.inactiveBackground"]),
+    ("mark1_background", &["editor.findMatchBackground"]),
+    ("mark2_background", &["editor.findMatchHighlightBackground"]),
+];
+
+pub struct KittyExporter;
+
+impl Exporter for KittyExporter {
+    fn name(&self) -> &'static str {
+        "kitty"
+    }
+
+    fn extension(&self) -> &'static str {
+        "conf"
+    }
+
+    fn description(&self) -> &'static str {
+        "kitty theme with tab bar, cursor and ANSI colors"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let colors = TerminalColors::new(theme);
+        let hex = |color: Color| color.over(colors.background).to_hex_rgb();
+
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "# vim:ft=kitty\n\n\
+             ## name: {}\n\
+             ## author: {AUTHOR}\n\
+             ## license: MIT\n\
+             ## blurb: Generated by `cyberdeck export --target kitty`; do not edit.\n\n",
+            theme_name(theme)
+        );
+        let _ = writeln!(out, "background {}", hex(colors.background));
+        let _ = writeln!(out, "foreground {}", hex(colors.foreground));
+        let _ = writeln!(out, "cursor {}", hex(colors.cursor));
+        let _ = writeln!(out, "cursor_text_color {}", hex(colors.cursor_text));
+        let _ = writeln!(
+            out,
+            "selection_background {}",
+            hex(colors.selection_background)
+        );
+        let _ = writeln!(
+            out,
+            "selection_foreground {}",
+            colors
+                .selection_foreground
+                .map_or_else(|| "none".to_owned(), hex)
+        );
+        out.push('\n');
+        for (setting, keys) in CHROME {
+            if let Some(color) = workbench_color(theme, keys) {
+                let _ = writeln!(out, "{setting} {}", hex(color));
+            }
+        }
+        out.push('\n');
+        for (index, color) in colors.ansi.iter().enumerate() {
+            let _ = writeln!(out, "color{index} {}", hex(*color));
+        }
+        Ok(vec![Artifact::new(
+            format!("{}.conf", theme_slug(theme)),
+            out,
+        )])
+    }
+}
@@ -0,0 +1,103 @@
+//! `konsole`: a Konsole/Yakuake color scheme (`<slug>.colorscheme`).
+//!
+//! Konsole wants a normal, intense and faint shade of every color. The
+//! theme's bright ANSI colors serve as the intense shades of the first
+//! eight; everything else is computed: intense by raising OKLCH lightness,
+//! faint by fading toward the background.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::{Color, Oklch};
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter, TerminalColors};
+
+/// How much lighter (in OKLCH lightness) an intense shade is.
+const INTENSE_LIGHTNESS: f64 = 0.08;
+
+/// How opaque a faint shade is over the background.
+const FAINT_ALPHA: u8 = 0x99;
+
+pub struct KonsoleExporter;
+
+impl Exporter for KonsoleExporter {
+    fn name(&self) -> &'static str {
+        "konsole"
+    }
+
+    fn extension(&self) -> &'static str {
+        "colorscheme"
+    }
+
+    fn description(&self) -> &'static str {
+        "Konsole and Yakuake color scheme"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let colors = TerminalColors::new(theme);
+        let faint = |color: Color| color.with_alpha(FAINT_ALPHA).over(colors.background);
+
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}", theme_name(theme));
+        out.push_str("# Generated by `cyberdeck export --target konsole`; do not edit.\n");
+        let mut section = |name: &str, normal: Color, faint: Color, intense: Color| {
+            for (suffix, color) in [("", normal), ("Faint", faint), ("Intense", intense)] {
+                let _ = writeln!(
+                    out,
+                    "\n[{name}{suffix}]\nColor={},{},{}",
+                    color.r, color.g, color.b
+                );
+            }
+        };
+        section(
+            "Background",
+            colors.background,
+            colors.background,
+            colors.background,
+        );
+        for index in 0..8 {
+            let normal = colors.ansi[index];
+            section(
+                &format!("Color{index}"),
+                normal,
+                faint(normal),
+                colors.ansi[index + 8],
+            );
+        }
+        section(
+            "Foreground",
+            colors.foreground,
+            faint(colors.foreground),
+            intense(colors.foreground),
+        );
+
+        let _ = write!(
+            out,
+            "\n[General]\n\
+             Anchor=0.5,0.5\n\
+             Blur=false\n\
+             ColorRandomization=false\n\
+             Description={}\n\
+             FillStyle=Tile\n\
+             Opacity=1\n\
+             Wallpaper=\n\
+             WallpaperFlipType=NoFlip\n\
+             WallpaperOpacity=1\n",
+            theme_name(theme)
+        );
+        Ok(vec![Artifact::new(
+            format!("{}.colorscheme", theme_slug(theme)),
+            out,
+        )])
+    }
+}
+
+fn intense(color: Color) -> Color {
+    let lch = color.to_oklch();
+    Color::from_oklch(Oklch {
+        l: (lch.l + INTENSE_LIGHTNESS).min(1.0),
+        ..lch
+    })
+}
@@ -0,0 +1,130 @@
+//! `lazygit`: the `gui` section of a lazygit `config.yml` (`<slug>.yml`, to
+//! merge into the user's config) with borders, selection and commit colors
+//! from the workbench's list, panel and git decoration colors.
+//!
+//! lazygit colors diffs through the git pager, so diff colors are left to
+//! the `bat` target's delta settings.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter};
+
+/// `gui.theme` keys, the workbench keys they read, and attributes.
+const THEME: &[(&str, &[&str], &str)] = &[
+    (
+        "activeBorderColor",
+        &["activityBar.activeBorder", "focusBorder"],
+        "bold",
+    ),
+    (
+        "inactiveBorderColor",
+        &["editorGroup.border", "panel.border"],
+        "",
+    ),
+    (
+        "searchingActiveBorderColor",
+        &["editor.findMatchBorder", "editorWarning.foreground"],
+        "bold",
+    ),
+    ("optionsTextColor", &["textLink.foreground"], ""),
+    (
+        "selectedLineBgColor",
+        &[
+            "list.activeSelectionBackground",
+            "editor.selectionBackground",
+        ],
+        "",
+    ),
+    (
+        "inactiveViewSelectedLineBgColor",
+        &["list.inactiveSelectionBackground"],
+        "bold",
+    ),
+    (
+        "cherryPickedCommitFgColor",
+        &["gitDecoration.addedResourceForeground"],
+        "",
+    ),
+    (
+        "cherryPickedCommitBgColor",
+        &["list.activeSelectionBackground"],
+        "",
+    ),
+    (
+        "markedBaseCommitFgColor",
+        &["gitDecoration.modifiedResourceForeground"],
+        "",
+    ),
+    (
+        "markedBaseCommitBgColor",
+        &["list.inactiveSelectionBackground"],
+        "",
+    ),
+    (
+        "unstagedChangesColor",
+        &[
+            "gitDecoration.deletedResourceForeground",
+            "editorError.foreground",
+        ],
+        "",
+    ),
+    ("defaultFgColor", &["editor.foreground", "foreground"], ""),
+];
+
+pub struct LazygitExporter;
+
+impl Exporter for LazygitExporter {
+    fn name(&self) -> &'static str {
+        "lazygit"
+    }
+
+    fn extension(&self) -> &'static str {
+        "yml"
+    }
+
+    fn description(&self) -> &'static str {
+        "lazygit gui theme section"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let background =
+            workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+        let color = |keys: &[&str]| {
+            workbench_color(theme, keys).map(|c| format!("\"{}\"", c.over(background).to_hex_rgb()))
+        };
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# {} for lazygit; merge into ~/.config/lazygit/config.yml.\n\
+             # Generated by `cyberdeck export --target lazygit`; do not edit.\n\
+             gui:\n  theme:",
+            theme_name(theme)
+        );
+        for (key, sources, attrs) in THEME {
+            if let Some(color) = color(sources) {
+                let value = if attrs.is_empty() {
+                    color
+                } else {
+                    format!("{color}, {attrs}")
+                };
+                let _ = writeln!(out, "    {key}: [{value}]");
+            }
+        }
+        if let Some(author) = color(&[
+            "gitDecoration.untrackedResourceForeground",
+            "textLink.foreground",
+        ]) {
+            let _ = writeln!(out, "  authorColors:\n    \"*\": {author}");
+        }
+        Ok(vec![Artifact::new(
+            format!("{}.yml", theme_slug(theme)),
+            out,
+        )])
+    }
+}
@@ -0,0 +1,326 @@
+//! `ls-colors`: directory listing colors from the palette roles.
+//!
+//! - `<slug>-ls-colors.sh`, an `export LS_COLORS=…` line for GNU `ls`,
+//!   `tree`, `fd` and the completion menus that read it, in 24-bit color.
+//! - `<slug>-eza.yml`, the same file kinds and groups as an eza theme
+//!   (`~/.config/eza/theme.yml`), plus eza's permission, size, user, date
+//!   and git columns.
+//!
+//! Translucent roles are composited over the background role.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter};
+
+/// File kinds: `LS_COLORS` code, eza `filekinds` key (empty for none), role
+/// and attributes. A role with a `bg:` prefix colors the background and
+/// leaves the text in the background role.
+const KINDS: &[(&str, &str, &str, &[&str])] = &[
+    ("di", "directory", "type", &["bold"]),
+    ("ln", "symlink", "class", &["italic"]),
+    ("ex", "executable", "function", &["bold"]),
+    ("pi", "pipe", "storage", &[]),
+    ("so", "socket", "storage", &["bold"]),
+    ("bd", "block_device", "warning", &["bold"]),
+    ("cd", "char_device", "warning", &[]),
+    ("do", "special", "storage", &["bold"]),
+    ("or", "", "error", &["bold"]),
+    ("mi", "", "error", &[]),
+    ("su", "", "bg:error", &[]),
+    ("sg", "", "bg:warning", &[]),
+    ("tw", "", "bg:accent.secondary", &[]),
+    ("ow", "", "type", &["underline"]),
+    ("st", "", "bg:accent.primary", &[]),
+];
+
+/// File groups: eza `file_type` key, role and the extensions `LS_COLORS`
+/// matches for it.
+const FILE_TYPES: &[(&str, &str, &[&str])] = &[
+    (
+        "compressed",
+        "keyword",
+        &[
+            "7z", "bz2", "deb", "gz", "jar", "lz", "lz4", "lzma", "rar", "rpm", "tar", "tbz",
+            "tgz", "txz", "xz", "zip", "zst",
+        ],
+    ),
+    (
+        "image",
+        "accent.secondary",
+        &[
+            "avif", "bmp", "gif", "heic", "ico", "jpeg", "jpg", "png", "svg", "tif", "tiff", "webp",
+        ],
+    ),
+    (
+        "video",
+        "number",
+        &[
+            "avi", "m4v", "mkv", "mov", "mp4", "mpeg", "mpg", "webm", "wmv",
+        ],
+    ),
+    (
+        "music",
+        "constant",
+        &["aac", "m4a", "mid", "mp3", "ogg", "opus", "wma"],
+    ),
+    (
+        "lossless",
+        "constant",
+        &["aiff", "alac", "ape", "flac", "wav"],
+    ),
+    (
+        "document",
+        "string",
+        &[
+            "doc", "docx", "epub", "md", "odp", "ods", "odt", "pdf", "ppt", "pptx", "rst", "rtf",
+            "txt", "xls", "xlsx",
+        ],
+    ),
+    (
+        "crypto",
+        "warning",
+        &[
+            "asc", "crt", "gpg", "key", "p12", "pem", "pgp", "pub", "sig",
+        ],
+    ),
+    (
+        "source",
+        "property",
+        &[
+            "c", "cpp", "cs", "css", "go", "h", "hpp", "html", "java", "js", "json", "kt", "lua",
+            "py", "rb", "rs", "sh", "swift", "toml", "ts", "yaml", "yml", "zig",
+        ],
+    ),
+    (
+        "compiled",
+        "foreground.muted",
+        &["a", "class", "dll", "elc", "o", "obj", "pyc", "so", "wasm"],
+    ),
+    (
+        "temp",
+        "comment",
+        &["bak", "log", "old", "orig", "part", "swp", "tmp"],
+    ),
+];
+
+/// Other eza theme entries: section, key, role and attributes.
+const EZA: &[(&str, &str, &str, &[&str])] = &[
+    ("filekinds", "normal", "foreground", &[]),
+    ("filekinds", "mount_point", "type", &["bold", "underline"]),
+    ("perms", "user_read", "string", &[]),
+    ("perms", "user_write", "keyword", &[]),
+    ("perms", "user_execute_file", "function", &["bold"]),
+    ("perms", "user_execute_other", "function", &[]),
+    ("perms", "group_read", "string", &[]),
+    ("perms", "group_write", "keyword", &[]),
+    ("perms", "group_execute", "function", &[]),
+    ("perms", "other_read", "string", &[]),
+    ("perms", "other_write", "keyword", &[]),
+    ("perms", "other_execute", "function", &[]),
+    ("perms", "special_user_file", "accent.secondary", &[]),
+    ("perms", "special_other", "accent.secondary", &[]),
+    ("perms", "attribute", "foreground.muted", &[]),
+    ("size", "number_byte", "foreground.secondary", &[]),
+    ("size", "number_kilo", "number", &[]),
+    ("size", "number_mega", "constant", &[]),
+    ("size", "number_giga", "warning", &[]),
+    ("size", "number_huge", "error", &["bold"]),
+    ("size", "unit_byte", "foreground.muted", &[]),
+    ("size", "unit_kilo", "foreground.muted", &[]),
+    ("size", "unit_mega", "foreground.muted", &[]),
+    ("size", "unit_giga", "foreground.muted", &[]),
+    ("size", "unit_huge", "foreground.muted", &[]),
+    ("size", "major", "warning", &[]),
+    ("size", "minor", "warning", &[]),
+    ("users", "user_you", "string", &["bold"]),
+    ("users", "user_root", "error", &[]),
+    ("users", "user_other", "foreground.secondary", &[]),
+    ("users", "group_yours", "string", &[]),
+    ("users", "group_root", "error", &[]),
+    ("users", "group_other", "foreground.secondary", &[]),
+    ("links", "normal", "class", &[]),
+    ("links", "multi_is_file", "warning", &["bold"]),
+    ("git", "new", "inserted", &[]),
+    ("git", "modified", "modified", &[]),
+    ("git", "deleted", "deleted", &[]),
+    ("git", "renamed", "accent.secondary", &[]),
+    ("git", "typechange", "storage", &[]),
+    ("git", "ignored", "comment", &[]),
+    ("git", "conflicted", "error", &["bold"]),
+    ("git_repo", "branch_main", "accent.primary", &[]),
+    ("git_repo", "branch_other", "accent.secondary", &[]),
+    ("git_repo", "git_clean", "success", &[]),
+    ("git_repo", "git_dirty", "warning", &[]),
+    ("", "punctuation", "foreground.muted", &[]),
+    ("", "date", "comment", &[]),
+    ("", "inode", "foreground.muted", &[]),
+    ("", "blocks", "foreground.muted", &[]),
+    ("", "header", "foreground", &["underline"]),
+    ("", "octal", "number", &[]),
+    ("", "flags", "storage", &[]),
+    ("", "symlink_path", "class", &[]),
+    ("", "control_char", "error", &[]),
+    ("", "broken_symlink", "error", &["bold"]),
+    ("", "broken_path_overlay", "error", &["underline"]),
+];
+
+pub struct LsColorsExporter;
+
+impl Exporter for LsColorsExporter {
+    fn name(&self) -> &'static str {
+        "ls-colors"
+    }
+
+    fn extension(&self) -> &'static str {
+        "sh"
+    }
+
+    fn description(&self) -> &'static str {
+        "LS_COLORS and eza theme from the palette roles"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let roles = Roles::new(theme);
+        let name = theme_name(theme);
+        let slug = theme_slug(theme);
+        Ok(vec![
+            Artifact::new(format!("{slug}-ls-colors.sh"), ls_colors(&roles, name)),
+            Artifact::new(format!("{slug}-eza.yml"), eza_theme(&roles, name)),
+        ])
+    }
+}
+
+/// The palette roles, opaque over the background role.
+struct Roles {
+    background: Color,
+    colors: IndexMap<&'static str, Color>,
+}
+
+impl Roles {
+    fn new(theme: &Theme) -> Roles {
+        let colors = resolve_roles(theme);
+        let background = colors
+            .get("background")
+            .map_or(Color::rgb(0, 0, 0), |c| c.opaque());
+        Roles { background, colors }
+    }
+
+    /// A role as `(foreground, background)`, following the `bg:` convention
+    /// of [`KINDS`].
+    fn get(&self, role: &str) -> Option<(Color, Option<Color>)> {
+        let color = |role: &str| self.colors.get(role).map(|c| c.over(self.background));
+        match role.strip_prefix("bg:") {
+            Some(role) => Some((self.background, Some(color(role)?))),
+            None => Some((color(role)?, None)),
+        }
+    }
+}
+
+fn ls_colors(roles: &Roles, name: &str) -> String {
+    let mut entries = vec!["rs=0".to_owned()];
+    for (code, _, role, attrs) in KINDS {
+        if let Some((foreground, background)) = roles.get(role) {
+            entries.push(format!("{code}={}", sgr(foreground, background, attrs)));
+        }
+    }
+    for (_, role, extensions) in FILE_TYPES {
+        if let Some((foreground, background)) = roles.get(role) {
+            let sgr = sgr(foreground, background, &[]);
+            entries.extend(extensions.iter().map(|ext| format!("*.{ext}={sgr}")));
+        }
+    }
+    format!(
+        "# {name} directory colors; source this from your shell's rc file.\n\
+         # Generated by `cyberdeck export --target ls-colors`; do not edit.\n\
+         export LS_COLORS='{}:'\n",
+        entries.join(":")
+    )
+}
+
+/// An SGR sequence body (`01;38;2;r;g;b`) for `LS_COLORS`.
+fn sgr(foreground: Color, background: Option<Color>, attrs: &[&str]) -> String {
+    let mut codes: Vec<String> = attrs
+        .iter()
+        .filter_map(|attr| match *attr {
+            "bold" => Some("01"),
+            "italic" => Some("03"),
+            "underline" => Some("04"),
+            _ => None,
+        })
+        .map(str::to_owned)
+        .collect();
+    codes.push(format!(
+        "38;2;{};{};{}",
+        foreground.r, foreground.g, foreground.b
+    ));
+    if let Some(background) = background {
+        codes.push(format!(
+            "48;2;{};{};{}",
+            background.r, background.g, background.b
+        ));
+    }
+    codes.join(";")
+}
+
+fn eza_theme(roles: &Roles, name: &str) -> String {
+    let mut sections: IndexMap<&str, Vec<(&str, String)>> = IndexMap::new();
+    let mut add = |section: &'static str, key: &'static str, role: &str, attrs: &[&str]| {
+        if let Some((foreground, background)) = roles.get(role) {
+            sections
+                .entry(section)
+                .or_default()
+                .push((key, eza_style(foreground, background, attrs)));
+        }
+    };
+    for (_, key, role, attrs) in KINDS {
+        if !key.is_empty() {
+            add("filekinds", key, role, attrs);
+        }
+    }
+    for (key, role, _) in FILE_TYPES {
+        add("file_type", key, role, &[]);
+    }
+    for (section, key, role, attrs) in EZA {
+        add(section, key, role, attrs);
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# {name} for eza; save as ~/.config/eza/theme.yml.\n\
+         # Generated by `cyberdeck export --target ls-colors`; do not edit."
+    );
+    for (section, entries) in &sections {
+        if section.is_empty() {
+            out.push('\n');
+            for (key, style) in entries {
+                let _ = writeln!(out, "{key}: {style}");
+            }
+        } else {
+            let _ = writeln!(out, "\n{section}:");
+            for (key, style) in entries {
+                let _ = writeln!(out, "  {key}: {style}");
+            }
+        }
+    }
+    out
+}
+
+/// An eza style as a YAML flow mapping.
+fn eza_style(foreground: Color, background: Option<Color>, attrs: &[&str]) -> String {
+    let mut fields = vec![format!("foreground: \"{}\"", foreground.to_hex_rgb())];
+    if let Some(background) = background {
+        fields.push(format!("background: \"{}\"", background.to_hex_rgb()));
+    }
+    for attr in attrs {
+        fields.push(format!("is_{attr}: true"));
+    }
+    format!("{{ {} }}", fields.join(", "))
+}
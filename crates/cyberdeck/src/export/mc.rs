@@ -0,0 +1,563 @@
+//! `mc`: a Midnight Commander skin (`<slug>.ini`, for
+//! `~/.local/share/mc/skins`) after the workbench's side bar, menus,
+//! dialogs, editor and diff colors.
+//!
+//! Colors are written as xterm 256-color indices (`colorN`), which every mc
+//! with `256colors` support reads; translucent colors are composited over
+//! the editor background first.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter};
+
+/// A skin entry: section, key, the workbench keys its foreground and
+/// background read (empty inherits the section's `_default_`), and
+/// attributes.
+type Entry = (
+    &'static str,
+    &'static str,
+    &'static [&'static str],
+    &'static [&'static str],
+    &'static str,
+);
+
+const ENTRIES: &[Entry] = &[
+    (
+        "core",
+        "_default_",
+        &["sideBar.foreground", "foreground"],
+        &["sideBar.background", "editor.background"],
+        "",
+    ),
+    (
+        "core",
+        "selected",
+        &["list.activeSelectionForeground"],
+        &["list.activeSelectionBackground"],
+        "",
+    ),
+    ("core", "marked", &["list.highlightForeground"], &[], "bold"),
+    (
+        "core",
+        "markselect",
+        &["list.highlightForeground"],
+        &["list.activeSelectionBackground"],
+        "bold",
+    ),
+    (
+        "core",
+        "gauge",
+        &["progressBar.background"],
+        &["editorWidget.background"],
+        "",
+    ),
+    (
+        "core",
+        "input",
+        &["input.foreground", "foreground"],
+        &["input.background"],
+        "",
+    ),
+    (
+        "core",
+        "inputunchanged",
+        &["input.placeholderForeground"],
+        &["input.background"],
+        "",
+    ),
+    (
+        "core",
+        "inputmark",
+        &["editor.selectionForeground", "input.foreground"],
+        &["editor.selectionBackground"],
+        "",
+    ),
+    (
+        "core",
+        "commandlinemark",
+        &["editor.selectionForeground", "terminal.foreground"],
+        &["terminal.selectionBackground", "editor.selectionBackground"],
+        "",
+    ),
+    (
+        "core",
+        "reverse",
+        &["list.activeSelectionForeground"],
+        &["list.activeSelectionBackground"],
+        "",
+    ),
+    (
+        "core",
+        "header",
+        &[
+            "sideBarSectionHeader.foreground",
+            "activityBar.activeBorder",
+        ],
+        &[],
+        "bold",
+    ),
+    ("core", "disabled", &["disabledForeground"], &[], ""),
+    ("core", "shadow", &[], &["widget.shadow"], ""),
+    (
+        "core",
+        "frame",
+        &["sideBar.border", "panel.border"],
+        &[],
+        "",
+    ),
+    (
+        "dialog",
+        "_default_",
+        &["editorWidget.foreground", "foreground"],
+        &["editorWidget.background"],
+        "",
+    ),
+    (
+        "dialog",
+        "dfocus",
+        &["list.activeSelectionForeground"],
+        &["list.activeSelectionBackground"],
+        "",
+    ),
+    (
+        "dialog",
+        "dhotnormal",
+        &["list.highlightForeground"],
+        &[],
+        "bold",
+    ),
+    (
+        "dialog",
+        "dhotfocus",
+        &["list.highlightForeground"],
+        &["list.activeSelectionBackground"],
+        "bold",
+    ),
+    (
+        "dialog",
+        "dtitle",
+        &["activityBar.activeBorder", "focusBorder"],
+        &[],
+        "bold",
+    ),
+    (
+        "error",
+        "_default_",
+        &["inputValidation.errorForeground", "editor.foreground"],
+        &["inputValidation.errorBackground", "editorError.foreground"],
+        "",
+    ),
+    (
+        "error",
+        "errdfocus",
+        &["editor.background"],
+        &["editorError.foreground", "errorForeground"],
+        "bold",
+    ),
+    (
+        "error",
+        "errdtitle",
+        &["editorWarning.foreground"],
+        &[],
+        "bold",
+    ),
+    (
+        "filehighlight",
+        "directory",
+        &[
+            "gitDecoration.submoduleResourceForeground",
+            "terminal.ansiBlue",
+        ],
+        &[],
+        "bold",
+    ),
+    (
+        "filehighlight",
+        "executable",
+        &["terminal.ansiGreen"],
+        &[],
+        "bold",
+    ),
+    ("filehighlight", "symlink", &["terminal.ansiCyan"], &[], ""),
+    ("filehighlight", "hardlink", &["terminal.ansiCyan"], &[], ""),
+    (
+        "filehighlight",
+        "stalelink",
+        &["editorError.foreground", "errorForeground"],
+        &[],
+        "",
+    ),
+    ("filehighlight", "device", &["terminal.ansiYellow"], &[], ""),
+    (
+        "filehighlight",
+        "special",
+        &["terminal.ansiMagenta"],
+        &[],
+        "",
+    ),
+    (
+        "filehighlight",
+        "core",
+        &["editorError.foreground", "errorForeground"],
+        &[],
+        "",
+    ),
+    (
+        "filehighlight",
+        "temp",
+        &["gitDecoration.ignoredResourceForeground"],
+        &[],
+        "",
+    ),
+    (
+        "filehighlight",
+        "archive",
+        &["terminal.ansiMagenta"],
+        &[],
+        "",
+    ),
+    (
+        "filehighlight",
+        "doc",
+        &["terminal.ansiBrightWhite"],
+        &[],
+        "",
+    ),
+    (
+        "filehighlight",
+        "source",
+        &["terminal.ansiBrightCyan"],
+        &[],
+        "",
+    ),
+    (
+        "filehighlight",
+        "media",
+        &["terminal.ansiBrightMagenta"],
+        &[],
+        "",
+    ),
+    (
+        "filehighlight",
+        "graph",
+        &["terminal.ansiBrightYellow"],
+        &[],
+        "",
+    ),
+    (
+        "filehighlight",
+        "database",
+        &["terminal.ansiBrightBlue"],
+        &[],
+        "",
+    ),
+    (
+        "menu",
+        "_default_",
+        &["menu.foreground", "foreground"],
+        &["menu.background", "editorWidget.background"],
+        "",
+    ),
+    (
+        "menu",
+        "menusel",
+        &["menu.selectionForeground", "list.activeSelectionForeground"],
+        &["menu.selectionBackground", "list.activeSelectionBackground"],
+        "",
+    ),
+    (
+        "menu",
+        "menuhot",
+        &["list.highlightForeground"],
+        &[],
+        "bold",
+    ),
+    (
+        "menu",
+        "menuhotsel",
+        &["list.highlightForeground"],
+        &["menu.selectionBackground", "list.activeSelectionBackground"],
+        "bold",
+    ),
+    (
+        "menu",
+        "menuinactive",
+        &["titleBar.inactiveForeground", "disabledForeground"],
+        &[],
+        "",
+    ),
+    (
+        "popupmenu",
+        "_default_",
+        &["menu.foreground", "foreground"],
+        &["menu.background", "editorWidget.background"],
+        "",
+    ),
+    (
+        "popupmenu",
+        "menusel",
+        &["menu.selectionForeground", "list.activeSelectionForeground"],
+        &["menu.selectionBackground", "list.activeSelectionBackground"],
+        "",
+    ),
+    (
+        "popupmenu",
+        "menutitle",
+        &["activityBar.activeBorder", "focusBorder"],
+        &[],
+        "bold",
+    ),
+    (
+        "buttonbar",
+        "hotkey",
+        &["statusBar.foreground"],
+        &["statusBar.background"],
+        "bold",
+    ),
+    (
+        "buttonbar",
+        "button",
+        &["statusBarItem.prominentForeground", "statusBar.foreground"],
+        &["statusBarItem.prominentBackground", "statusBar.background"],
+        "",
+    ),
+    (
+        "statusbar",
+        "_default_",
+        &["statusBar.foreground"],
+        &["statusBar.background"],
+        "",
+    ),
+    (
+        "help",
+        "_default_",
+        &["editorHoverWidget.foreground", "editor.foreground"],
+        &["editorHoverWidget.background", "editor.background"],
+        "",
+    ),
+    ("help", "helpbold", &[], &[], "bold"),
+    ("help", "helpitalic", &[], &[], "italic"),
+    (
+        "help",
+        "helplink",
+        &["textLink.foreground"],
+        &[],
+        "underline",
+    ),
+    (
+        "help",
+        "helpslink",
+        &["textLink.activeForeground", "textLink.foreground"],
+        &["editor.selectionBackground"],
+        "",
+    ),
+    (
+        "help",
+        "helptitle",
+        &["activityBar.activeBorder", "focusBorder"],
+        &[],
+        "bold",
+    ),
+    (
+        "editor",
+        "_default_",
+        &["editor.foreground"],
+        &["editor.background"],
+        "",
+    ),
+    ("editor", "editbold", &[], &[], "bold"),
+    (
+        "editor",
+        "editmarked",
+        &["editor.selectionForeground", "editor.foreground"],
+        &["editor.selectionBackground"],
+        "",
+    ),
+    (
+        "editor",
+        "editwhitespace",
+        &["editorWhitespace.foreground"],
+        &[],
+        "",
+    ),
+    (
+        "editor",
+        "editlinestate",
+        &["editorLineNumber.foreground"],
+        &["editorGutter.background", "editor.background"],
+        "",
+    ),
+    (
+        "editor",
+        "bookmark",
+        &[],
+        &["editor.lineHighlightBackground"],
+        "",
+    ),
+    (
+        "editor",
+        "bookmarkfound",
+        &[],
+        &["editor.findMatchBackground"],
+        "",
+    ),
+    (
+        "editor",
+        "editrightmargin",
+        &["editorRuler.foreground"],
+        &[],
+        "",
+    ),
+    (
+        "editor",
+        "editframe",
+        &["editorGroup.border", "panel.border"],
+        &[],
+        "",
+    ),
+    (
+        "editor",
+        "editframeactive",
+        &["activityBar.activeBorder", "focusBorder"],
+        &[],
+        "",
+    ),
+    (
+        "viewer",
+        "_default_",
+        &["editor.foreground"],
+        &["editor.background"],
+        "",
+    ),
+    ("viewer", "viewbold", &[], &[], "bold"),
+    ("viewer", "viewunderline", &[], &[], "underline"),
+    (
+        "viewer",
+        "viewselected",
+        &["editor.selectionForeground", "editor.foreground"],
+        &["editor.findMatchBackground"],
+        "",
+    ),
+    (
+        "diffviewer",
+        "added",
+        &[],
+        &[
+            "diffEditor.insertedLineBackground",
+            "diffEditor.insertedTextBackground",
+        ],
+        "",
+    ),
+    (
+        "diffviewer",
+        "changedline",
+        &["editorGutter.modifiedBackground"],
+        &[],
+        "",
+    ),
+    (
+        "diffviewer",
+        "changednew",
+        &[],
+        &["diffEditor.insertedTextBackground"],
+        "",
+    ),
+    (
+        "diffviewer",
+        "changed",
+        &[],
+        &["editor.lineHighlightBackground"],
+        "",
+    ),
+    (
+        "diffviewer",
+        "removed",
+        &[],
+        &[
+            "diffEditor.removedLineBackground",
+            "diffEditor.removedTextBackground",
+        ],
+        "",
+    ),
+    (
+        "diffviewer",
+        "error",
+        &["editorError.foreground", "errorForeground"],
+        &[],
+        "",
+    ),
+];
+
+/// mc's default box-drawing characters, which a skin has to repeat.
+const LINES: &str = "[Lines]\n    \
+    horiz = ─\n    vert = │\n    lefttop = ┌\n    righttop = ┐\n    \
+    leftbottom = └\n    rightbottom = ┘\n    topmiddle = ┬\n    bottommiddle = ┴\n    \
+    leftmiddle = ├\n    rightmiddle = ┤\n    cross = ┼\n    \
+    dhoriz = ─\n    dvert = │\n    dlefttop = ┌\n    drighttop = ┐\n    \
+    dleftbottom = └\n    drightbottom = ┘\n    dtopmiddle = ┬\n    dbottommiddle = ┴\n    \
+    dleftmiddle = ├\n    drightmiddle = ┤\n";
+
+pub struct McExporter;
+
+impl Exporter for McExporter {
+    fn name(&self) -> &'static str {
+        "mc"
+    }
+
+    fn extension(&self) -> &'static str {
+        "ini"
+    }
+
+    fn description(&self) -> &'static str {
+        "Midnight Commander skin"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let background =
+            workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+        let color = |keys: &[&str]| {
+            workbench_color(theme, keys)
+                .map(|c| format!("color{}", c.over(background).to_xterm256()))
+                .unwrap_or_default()
+        };
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# Generated by `cyberdeck export --target mc`; do not edit.\n\
+             [skin]\n    \
+             description = {}\n    \
+             256colors = true\n",
+            theme_name(theme)
+        );
+        out.push_str(LINES);
+
+        let mut section = "";
+        for (name, key, foreground, bg, attrs) in ENTRIES {
+            let (foreground, bg) = (color(foreground), color(bg));
+            if foreground.is_empty() && bg.is_empty() && attrs.is_empty() {
+                continue;
+            }
+            if section != *name {
+                section = name;
+                let _ = writeln!(out, "\n[{name}]");
+            }
+            let value = if attrs.is_empty() {
+                format!("{foreground};{bg}")
+            } else {
+                format!("{foreground};{bg};{attrs}")
+            };
+            let _ = writeln!(out, "    {key} = {value}");
+        }
+        out.push_str("\n[widget-common]\n    sort-sign-up = ↑\n    sort-sign-down = ↓\n");
+        Ok(vec![Artifact::new(
+            format!("{}.ini", theme_slug(theme)),
+            out,
+        )])
+    }
+}
@@ -0,0 +1,405 @@
+//! Exporting the theme to other editors' and tools' formats.
+//!
+//! Every format is an [`Exporter`] registered in a [`Registry`]. The CLI
+//! dispatches `cyberdeck export --target <name>` through the registry, so a
+//! downstream binary can add targets by registering its own exporters and
+//! calling [`crate::commands::export::run_with_registry`].
+
+mod ace;
+mod alacritty;
+mod base16;
+mod bat;
+mod btop;
+mod chroma;
+mod codemirror;
+mod css_variables;
+mod dtcg;
+mod emacs;
+mod fish;
+mod foot;
+mod fzf;
+mod ghostty;
+mod gitui;
+mod gnome_terminal;
+mod gtksourceview;
+mod helix;
+mod highlightjs;
+mod iterm2;
+mod jetbrains;
+mod kate;
+mod kitty;
+mod konsole;
+mod lazygit;
+mod ls_colors;
+mod mc;
+mod monaco;
+mod neovim;
+mod notepadpp;
+mod obsidian;
+mod polybar;
+mod prism;
+mod putty;
+mod pygments;
+mod qutebrowser;
+mod rofi;
+mod roles;
+mod scss;
+mod shiki;
+mod slack;
+mod starship;
+mod sublime;
+mod sway;
+mod tailwind;
+mod tmux;
+mod vim;
+mod visualstudio;
+mod waybar;
+mod wezterm;
+mod windows_terminal;
+mod xcode;
+mod zathura;
+mod zed;
+mod zsh_highlight;
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::lint::TERMINAL_ANSI_KEYS;
+use crate::scope::Resolver;
+use crate::theme::{SemanticStyle, Theme};
+
+/// One generated file, with a path relative to the output directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub path: PathBuf,
+    pub contents: Vec<u8>,
+}
+
+impl Artifact {
+    pub fn new(path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>) -> Artifact {
+        Artifact {
+            path: path.into(),
+            contents: contents.into(),
+        }
+    }
+}
+
+pub trait Exporter {
+    /// The `--target` name, e.g. `sublime`.
+    fn name(&self) -> &'static str;
+
+    /// Extension of the main artifact, without the dot; empty if it has
+    /// none.
+    fn extension(&self) -> &'static str;
+
+    /// One-line summary for `cyberdeck export --list`.
+    fn description(&self) -> &'static str;
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>>;
+}
+
+/// The set of exporters available to `cyberdeck export`.
+#[derive(Default)]
+pub struct Registry {
+    exporters: Vec<Box<dyn Exporter>>,
+}
+
+impl Registry {
+    /// An empty registry.
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    /// A registry holding every exporter that ships with the tooling.
+    pub fn with_builtins() -> Registry {
+        let mut registry = Registry::new();
+        registry.register(Box::new(ace::AceExporter));
+        registry.register(Box::new(alacritty::AlacrittyExporter));
+        registry.register(Box::new(base16::Base16Exporter));
+        registry.register(Box::new(bat::BatExporter));
+        registry.register(Box::new(btop::BtopExporter));
+        registry.register(Box::new(chroma::ChromaExporter));
+        registry.register(Box::new(codemirror::CodeMirrorExporter));
+        registry.register(Box::new(css_variables::CssVariablesExporter));
+        registry.register(Box::new(dtcg::DtcgExporter));
+        registry.register(Box::new(emacs::EmacsExporter));
+        registry.register(Box::new(fish::FishExporter));
+        registry.register(Box::new(foot::FootExporter));
+        registry.register(Box::new(fzf::FzfExporter));
+        registry.register(Box::new(ghostty::GhosttyExporter));
+        registry.register(Box::new(gitui::GituiExporter));
+        registry.register(Box::new(gnome_terminal::GnomeTerminalExporter));
+        registry.register(Box::new(gtksourceview::GtkSourceViewExporter));
+        registry.register(Box::new(helix::HelixExporter));
+        registry.register(Box::new(highlightjs::HighlightJsExporter));
+        registry.register(Box::new(iterm2::Iterm2Exporter));
+        registry.register(Box::new(jetbrains::JetBrainsExporter));
+        registry.register(Box::new(kate::KateExporter));
+        registry.register(Box::new(kitty::KittyExporter));
+        registry.register(Box::new(konsole::KonsoleExporter));
+        registry.register(Box::new(lazygit::LazygitExporter));
+        registry.register(Box::new(ls_colors::LsColorsExporter));
+        registry.register(Box::new(mc::McExporter));
+        registry.register(Box::new(monaco::MonacoExporter));
+        registry.register(Box::new(neovim::NeovimExporter));
+        registry.register(Box::new(notepadpp::NotepadPlusPlusExporter));
+        registry.register(Box::new(obsidian::ObsidianExporter));
+        registry.register(Box::new(polybar::PolybarExporter));
+        registry.register(Box::new(prism::PrismExporter));
+        registry.register(Box::new(putty::PuttyExporter));
+        registry.register(Box::new(pygments::PygmentsExporter));
+        registry.register(Box::new(qutebrowser::QutebrowserExporter));
+        registry.register(Box::new(rofi::RofiExporter));
+        registry.register(Box::new(roles::RolesExporter));
+        registry.register(Box::new(scss::ScssExporter));
+        registry.register(Box::new(shiki::ShikiExporter));
+        registry.register(Box::new(slack::SlackExporter));
+        registry.register(Box::new(starship::StarshipExporter));
+        registry.register(Box::new(sublime::SublimeExporter));
+        registry.register(Box::new(sway::SwayExporter));
+        registry.register(Box::new(tailwind::TailwindExporter));
+        registry.register(Box::new(tmux::TmuxExporter));
+        registry.register(Box::new(vim::VimExporter));
+        registry.register(Box::new(visualstudio::VisualStudioExporter));
+        registry.register(Box::new(waybar::WaybarExporter));
+        registry.register(Box::new(wezterm::WeztermExporter));
+        registry.register(Box::new(windows_terminal::WindowsTerminalExporter));
+        registry.register(Box::new(xcode::XcodeExporter));
+        registry.register(Box::new(zathura::ZathuraExporter));
+        registry.register(Box::new(zed::ZedExporter));
+        registry.register(Box::new(zsh_highlight::ZshHighlightExporter));
+        registry
+    }
+
+    /// Adds an exporter, replacing any existing one with the same name.
+    pub fn register(&mut self, exporter: Box<dyn Exporter>) {
+        self.exporters.retain(|e| e.name() != exporter.name());
+        self.exporters.push(exporter);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Exporter> {
+        self.exporters
+            .iter()
+            .find(|e| e.name() == name)
+            .map(Box::as_ref)
+    }
+
+    /// Exporters sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Exporter> {
+        let mut exporters: Vec<&dyn Exporter> = self.exporters.iter().map(Box::as_ref).collect();
+        exporters.sort_by_key(|e| e.name());
+        exporters.into_iter()
+    }
+}
+
+/// Credited in formats that carry an author field.
+pub const AUTHOR: &str = "ex1tium";
+
+/// File-name stem for a theme: `Cyberdeck 2025` → `cyberdeck-2025`.
+pub fn theme_slug(theme: &Theme) -> String {
+    let name = theme.name.as_deref().unwrap_or("theme");
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_owned()
+}
+
+/// The theme's display name.
+pub fn theme_name(theme: &Theme) -> &str {
+    theme.name.as_deref().unwrap_or("Theme")
+}
+
+/// Whether the theme is a dark one, going by its `type`.
+pub fn is_dark(theme: &Theme) -> bool {
+    !matches!(theme.kind.as_deref(), Some("light" | "hcLight"))
+}
+
+/// The first of `keys` the theme defines with a valid color.
+pub fn workbench_color(theme: &Theme, keys: &[&str]) -> Option<Color> {
+    keys.iter()
+        .find_map(|key| theme.colors.get(*key).and_then(|v| v.parse().ok()))
+}
+
+/// The integrated terminal's sixteen ANSI colors, black to bright white;
+/// `None` unless the theme defines all of them.
+pub fn ansi_colors(theme: &Theme) -> Option<[Color; 16]> {
+    let mut colors = [Color::rgb(0, 0, 0); 16];
+    for (slot, key) in colors.iter_mut().zip(TERMINAL_ANSI_KEYS) {
+        *slot = workbench_color(theme, &[key])?;
+    }
+    Some(colors)
+}
+
+/// The eight ANSI color names, in `TERMINAL_ANSI_KEYS` order; the bright
+/// variants repeat them.
+pub const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// The colors a terminal emulator theme is made of, read from the integrated
+/// terminal's keys (then the editor's) and composited over the terminal
+/// background. ANSI slots the theme leaves out keep xterm's defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalColors {
+    pub background: Color,
+    pub foreground: Color,
+    pub cursor: Color,
+    /// The text under a block cursor.
+    pub cursor_text: Color,
+    pub selection_background: Color,
+    /// `None` keeps the selected text's own colors.
+    pub selection_foreground: Option<Color>,
+    /// Black to bright white.
+    pub ansi: [Color; 16],
+}
+
+impl TerminalColors {
+    pub fn new(theme: &Theme) -> TerminalColors {
+        let background = workbench_color(theme, &["terminal.background", "editor.background"])
+            .map_or(Color::rgb(0, 0, 0), Color::opaque);
+        let color = |keys: &[&str]| workbench_color(theme, keys).map(|c| c.over(background));
+        let foreground = color(&["terminal.foreground", "editor.foreground", "foreground"])
+            .unwrap_or(Color::rgb(0xff, 0xff, 0xff));
+        let mut ansi = [Color::rgb(0, 0, 0); 16];
+        for (index, (slot, key)) in ansi.iter_mut().zip(TERMINAL_ANSI_KEYS).enumerate() {
+            *slot = color(&[key]).unwrap_or(Color::from_xterm256(index as u8));
+        }
+        TerminalColors {
+            background,
+            foreground,
+            cursor: color(&["terminalCursor.foreground", "editorCursor.foreground"])
+                .unwrap_or(foreground),
+            cursor_text: color(&["terminalCursor.background", "editorCursor.background"])
+                .unwrap_or(background),
+            selection_background: color(&[
+                "terminal.selectionBackground",
+                "editor.selectionBackground",
+            ])
+            .unwrap_or(foreground.with_alpha(0x40).over(background)),
+            selection_foreground: color(&[
+                "terminal.selectionForeground",
+                "editor.selectionForeground",
+            ]),
+            ansi,
+        }
+    }
+}
+
+/// A resolved token style, for targets that style fixed groups rather than
+/// matching scope selectors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenStyle {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+
+impl TokenStyle {
+    /// How `tokenColors` style `scope`, a space-separated scope path
+    /// (outermost first) inside a source file. `None` when no rule matches,
+    /// so targets can leave the group to their own defaults.
+    pub fn resolve(resolver: &Resolver, scope: &str) -> Option<TokenStyle> {
+        let stack: Vec<&str> = std::iter::once("source")
+            .chain(scope.split_whitespace())
+            .collect();
+        let matched = resolver.resolve_matched(&stack);
+        if matched.foreground.is_none()
+            && matched.background.is_none()
+            && matched.font_style.is_none()
+        {
+            return None;
+        }
+        let font_style = matched.font_style.unwrap_or_default();
+        let has = |style: &str| font_style.split_whitespace().any(|s| s == style);
+        Some(TokenStyle {
+            foreground: matched.foreground,
+            background: matched.background,
+            bold: has("bold"),
+            italic: has("italic"),
+            underline: has("underline"),
+            strikethrough: has("strikethrough"),
+        })
+    }
+
+    /// A `semanticTokenColors` value as a style; `None` when it sets nothing.
+    /// The explicit `bold`/`italic`/… flags win over `fontStyle`.
+    pub fn from_semantic(style: &SemanticStyle) -> Option<TokenStyle> {
+        let style = match style {
+            SemanticStyle::Color(color) => {
+                return Some(TokenStyle {
+                    foreground: Some(color.parse().ok()?),
+                    ..TokenStyle::default()
+                })
+            }
+            SemanticStyle::Style(style) => style,
+        };
+        let font_style = style.font_style.as_deref().unwrap_or_default();
+        let has = |flag: Option<bool>, name: &str| {
+            flag.unwrap_or_else(|| font_style.split_whitespace().any(|s| s == name))
+        };
+        let resolved = TokenStyle {
+            foreground: style.foreground.as_deref().and_then(|c| c.parse().ok()),
+            background: None,
+            bold: has(style.bold, "bold"),
+            italic: has(style.italic, "italic"),
+            underline: has(style.underline, "underline"),
+            strikethrough: has(style.strikethrough, "strikethrough"),
+        };
+        (resolved != TokenStyle::default()).then_some(resolved)
+    }
+
+    /// The style as CSS declarations, e.g. `color: #ff019a;`. Colors keep
+    /// their alpha, since browsers blend it themselves.
+    pub fn css_declarations(&self) -> Vec<String> {
+        let mut declarations = Vec::new();
+        if let Some(color) = self.foreground {
+            declarations.push(format!("color: {color};"));
+        }
+        if let Some(color) = self.background {
+            declarations.push(format!("background-color: {color};"));
+        }
+        if self.bold {
+            declarations.push("font-weight: bold;".to_owned());
+        }
+        if self.italic {
+            declarations.push("font-style: italic;".to_owned());
+        }
+        let decorations: Vec<&str> = [
+            (self.underline, "underline"),
+            (self.strikethrough, "line-through"),
+        ]
+        .into_iter()
+        .filter_map(|(set, line)| set.then_some(line))
+        .collect();
+        if !decorations.is_empty() {
+            declarations.push(format!("text-decoration: {};", decorations.join(" ")));
+        }
+        declarations
+    }
+}
+
+/// Escapes text for use in XML content and attribute values.
+pub fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,177 @@
+//! `monaco`: the argument to Monaco's `monaco.editor.defineTheme`
+//! (`<slug>.json`).
+//!
+//! Workbench colors carry over under the same keys. Monaco's rules match
+//! token types by prefix with no notion of ancestry, so they are every
+//! single-scope `tokenColors` selector, for setups that tokenize with
+//! TextMate grammars, followed by the Monarch token types Monaco's built-in
+//! languages emit; a later rule for the same token wins.
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+use crate::color::Color;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{theme_slug, workbench_color, Artifact, Exporter, TokenStyle};
+
+/// Monarch token types and the scope path each is resolved from.
+const TOKENS: &[(&str, &str)] = &[
+    ("comment", "comment.line"),
+    ("comment.doc", "comment.block.documentation"),
+    ("keyword", "keyword.control"),
+    ("keyword.flow", "keyword.control.flow"),
+    ("operator", "keyword.operator"),
+    ("operators", "keyword.operator"),
+    ("delimiter", "punctuation"),
+    ("identifier", "variable.other.readwrite"),
+    ("variable", "variable.other.readwrite"),
+    ("variable.predefined", "variable.language"),
+    ("constant", "constant.language"),
+    ("number", "constant.numeric"),
+    ("string", "string.quoted.double"),
+    ("string.escape", "constant.character.escape"),
+    ("regexp", "string.regexp"),
+    ("type", "support.type.primitive"),
+    ("type.identifier", "entity.name.type.class"),
+    ("predefined", "support.function"),
+    ("namespace", "entity.name.namespace"),
+    ("annotation", "meta.decorator"),
+    ("metatag", "meta.preprocessor"),
+    ("tag", "entity.name.tag"),
+    ("attribute.name", "entity.other.attribute-name"),
+    ("attribute.value", "string.quoted.double"),
+    ("key", "support.type.property-name"),
+    ("emphasis", "markup.italic"),
+    ("strong", "markup.bold"),
+    ("invalid", "invalid.illegal"),
+];
+
+/// Font styles Monaco understands.
+const FONT_STYLES: [&str; 4] = ["italic", "bold", "underline", "strikethrough"];
+
+pub struct MonacoExporter;
+
+impl Exporter for MonacoExporter {
+    fn name(&self) -> &'static str {
+        "monaco"
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn description(&self) -> &'static str {
+        "Monaco editor defineTheme data"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let background =
+            workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+        // Rule colors are six-digit hex without the `#`, and take no alpha.
+        let hex = |color: Color| color.over(background).to_hex_rgb()[1..].to_owned();
+        let resolver = Resolver::new(theme);
+
+        let mut rules = Vec::new();
+        let mut default = Map::new();
+        default.insert("token".to_owned(), "".into());
+        if let Some(foreground) = resolver.defaults().foreground {
+            default.insert("foreground".to_owned(), hex(foreground).into());
+        }
+        default.insert("background".to_owned(), hex(background).into());
+        rules.push(Value::Object(default));
+        for source in &theme.token_colors {
+            let color = |value: &Option<String>| value.as_deref()?.parse::<Color>().ok().map(hex);
+            // An absent `fontStyle` inherits; an empty one resets.
+            let font_style = source.settings.font_style.as_deref().map(|font_style| {
+                font_style
+                    .split_whitespace()
+                    .filter(|s| FONT_STYLES.contains(s))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            });
+            let foreground = color(&source.settings.foreground);
+            let background = color(&source.settings.background);
+            if foreground.is_none() && background.is_none() && font_style.is_none() {
+                continue;
+            }
+            for selector in source.scope.selectors() {
+                if !selector.contains(char::is_whitespace) {
+                    rules.push(rule(
+                        selector,
+                        foreground.clone(),
+                        background.clone(),
+                        font_style.clone(),
+                    ));
+                }
+            }
+        }
+        for (token, scope) in TOKENS {
+            if let Some(style) = TokenStyle::resolve(&resolver, scope) {
+                let font_style: Vec<&str> = [
+                    (style.italic, "italic"),
+                    (style.bold, "bold"),
+                    (style.underline, "underline"),
+                    (style.strikethrough, "strikethrough"),
+                ]
+                .into_iter()
+                .filter_map(|(set, name)| set.then_some(name))
+                .collect();
+                rules.push(rule(
+                    token,
+                    style.foreground.map(hex),
+                    style.background.map(hex),
+                    Some(font_style.join(" ")),
+                ));
+            }
+        }
+
+        let colors: Map<String, Value> = theme
+            .colors
+            .iter()
+            .filter_map(|(key, value)| {
+                let color: Color = value.parse().ok()?;
+                Some((key.clone(), color.to_string().into()))
+            })
+            .collect();
+        let base = match theme.kind.as_deref() {
+            Some("light") => "vs",
+            Some("hcLight") => "hc-light",
+            Some("hcDark") => "hc-black",
+            _ => "vs-dark",
+        };
+        let data = json!({
+            "base": base,
+            "inherit": true,
+            "rules": rules,
+            "colors": colors,
+        });
+        let mut text = serde_json::to_string_pretty(&data)?;
+        text.push('\n');
+        Ok(vec![Artifact::new(
+            format!("{}.json", theme_slug(theme)),
+            text,
+        )])
+    }
+}
+
+fn rule(
+    token: &str,
+    foreground: Option<String>,
+    background: Option<String>,
+    font_style: Option<String>,
+) -> Value {
+    let mut rule = Map::new();
+    rule.insert("token".to_owned(), token.into());
+    if let Some(foreground) = foreground {
+        rule.insert("foreground".to_owned(), foreground.into());
+    }
+    if let Some(background) = background {
+        rule.insert("background".to_owned(), background.into());
+    }
+    if let Some(font_style) = font_style {
+        rule.insert("fontStyle".to_owned(), font_style.into());
+    }
+    Value::Object(rule)
+}
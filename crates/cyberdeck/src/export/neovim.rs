@@ -0,0 +1,351 @@
+//! `neovim`: a Lua colorscheme (`colors/<name>.lua`) for Neovim.
+//!
+//! On top of the classic groups shared with the `vim` target it covers the
+//! `@` tree-sitter captures, the `@lsp.*` semantic token groups and a few
+//! popular plugins. Tree-sitter captures are resolved from the token rules,
+//! LSP groups from `semanticTokenColors` (falling back to a link to the
+//! matching capture), and plugin groups from the palette roles.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::vim::{colors_name, SYNTAX_GROUPS, UI_GROUPS};
+use super::{ansi_colors, is_dark, theme_name, workbench_color, Artifact, Exporter, TokenStyle};
+
+/// Tree-sitter captures and the scope path each is resolved from.
+const CAPTURES: &[(&str, &str)] = &[
+    ("@comment", "comment.line"),
+    ("@comment.documentation", "comment.block.documentation"),
+    ("@string", "string.quoted.double"),
+    ("@string.escape", "constant.character.escape"),
+    ("@string.regexp", "string.regexp"),
+    ("@string.special.url", "markup.underline.link"),
+    ("@character", "constant.character"),
+    ("@number", "constant.numeric"),
+    ("@number.float", "constant.numeric.float"),
+    ("@boolean", "constant.language.boolean"),
+    ("@constant", "variable.other.constant"),
+    ("@constant.builtin", "constant.language"),
+    ("@variable", "variable.other.readwrite"),
+    ("@variable.builtin", "variable.language"),
+    ("@variable.parameter", "variable.parameter"),
+    ("@variable.member", "variable.other.property"),
+    ("@property", "variable.other.property"),
+    ("@function", "entity.name.function"),
+    ("@function.call", "meta.function-call entity.name.function"),
+    ("@function.builtin", "support.function"),
+    ("@function.method", "entity.name.function.member"),
+    ("@function.macro", "entity.name.function.macro"),
+    ("@constructor", "entity.name.function.constructor"),
+    ("@keyword", "keyword"),
+    ("@keyword.function", "storage.type.function"),
+    ("@keyword.operator", "keyword.operator.word"),
+    ("@keyword.return", "keyword.control.return"),
+    ("@keyword.conditional", "keyword.control.conditional"),
+    ("@keyword.repeat", "keyword.control.loop"),
+    ("@keyword.import", "keyword.control.import"),
+    ("@keyword.exception", "keyword.control.exception"),
+    ("@keyword.modifier", "storage.modifier"),
+    ("@keyword.type", "storage.type"),
+    ("@operator", "keyword.operator"),
+    ("@type", "entity.name.type"),
+    ("@type.builtin", "support.type.primitive"),
+    ("@attribute", "meta.decorator"),
+    ("@module", "entity.name.namespace"),
+    ("@label", "entity.name.label"),
+    ("@punctuation.delimiter", "punctuation.separator"),
+    ("@punctuation.bracket", "punctuation.section.brackets"),
+    (
+        "@punctuation.special",
+        "punctuation.definition.template-expression",
+    ),
+    ("@tag", "entity.name.tag"),
+    ("@tag.attribute", "entity.other.attribute-name"),
+    ("@tag.delimiter", "punctuation.definition.tag"),
+    ("@markup.heading", "markup.heading"),
+    ("@markup.strong", "markup.bold"),
+    ("@markup.italic", "markup.italic"),
+    ("@markup.strikethrough", "markup.strikethrough"),
+    ("@markup.link.url", "markup.underline.link"),
+    ("@markup.raw", "markup.inline.raw"),
+    ("@markup.quote", "markup.quote"),
+    ("@diff.plus", "markup.inserted"),
+    ("@diff.minus", "markup.deleted"),
+    ("@diff.delta", "markup.changed"),
+];
+
+/// Standard LSP token types and the capture they link to when the theme
+/// has no `semanticTokenColors` entry for them.
+const LSP_LINKS: &[(&str, &str)] = &[
+    ("class", "@type"),
+    ("comment", "@comment"),
+    ("decorator", "@attribute"),
+    ("enum", "@type"),
+    ("enumMember", "@constant"),
+    ("function", "@function"),
+    ("interface", "@type"),
+    ("keyword", "@keyword"),
+    ("macro", "@function.macro"),
+    ("method", "@function.method"),
+    ("namespace", "@module"),
+    ("number", "@number"),
+    ("operator", "@operator"),
+    ("parameter", "@variable.parameter"),
+    ("property", "@property"),
+    ("regexp", "@string.regexp"),
+    ("string", "@string"),
+    ("struct", "@type"),
+    ("type", "@type"),
+    ("typeParameter", "@type"),
+    ("variable", "@variable"),
+];
+
+/// Diagnostic severities and the workbench keys their color reads.
+const DIAGNOSTICS: &[(&str, &[&str])] = &[
+    ("Error", &["editorError.foreground", "errorForeground"]),
+    ("Warn", &["editorWarning.foreground"]),
+    ("Info", &["editorInfo.foreground"]),
+    ("Hint", &["editorHint.foreground", "editorInfo.foreground"]),
+];
+
+/// Plugin groups: foreground role, background role (empty for none) and
+/// attributes.
+const PLUGIN_GROUPS: &[(&str, &str, &str, &[&str])] = &[
+    // Telescope
+    ("TelescopeNormal", "foreground", "background.secondary", &[]),
+    ("TelescopeBorder", "border", "background.secondary", &[]),
+    ("TelescopeTitle", "accent.primary", "", &["bold"]),
+    ("TelescopePromptPrefix", "accent.secondary", "", &[]),
+    ("TelescopeSelection", "", "highlight", &[]),
+    ("TelescopeSelectionCaret", "accent.secondary", "", &[]),
+    ("TelescopeMatching", "accent.primary", "", &["bold"]),
+    // nvim-cmp
+    ("CmpItemAbbr", "foreground", "", &[]),
+    (
+        "CmpItemAbbrDeprecated",
+        "foreground.muted",
+        "",
+        &["strikethrough"],
+    ),
+    ("CmpItemAbbrMatch", "accent.primary", "", &["bold"]),
+    ("CmpItemAbbrMatchFuzzy", "accent.primary", "", &["bold"]),
+    ("CmpItemMenu", "foreground.muted", "", &[]),
+    ("CmpItemKind", "foreground.secondary", "", &[]),
+    ("CmpItemKindFunction", "function", "", &[]),
+    ("CmpItemKindMethod", "function", "", &[]),
+    ("CmpItemKindVariable", "variable", "", &[]),
+    ("CmpItemKindField", "property", "", &[]),
+    ("CmpItemKindProperty", "property", "", &[]),
+    ("CmpItemKindKeyword", "keyword", "", &[]),
+    ("CmpItemKindClass", "class", "", &[]),
+    ("CmpItemKindInterface", "type", "", &[]),
+    ("CmpItemKindConstant", "constant", "", &[]),
+    ("CmpItemKindText", "string", "", &[]),
+    // gitsigns
+    ("GitSignsAdd", "inserted", "", &[]),
+    ("GitSignsChange", "modified", "", &[]),
+    ("GitSignsDelete", "deleted", "", &[]),
+    (
+        "GitSignsCurrentLineBlame",
+        "foreground.muted",
+        "",
+        &["italic"],
+    ),
+];
+
+pub struct NeovimExporter;
+
+impl Exporter for NeovimExporter {
+    fn name(&self) -> &'static str {
+        "neovim"
+    }
+
+    fn extension(&self) -> &'static str {
+        "lua"
+    }
+
+    fn description(&self) -> &'static str {
+        "Neovim Lua colorscheme with tree-sitter, LSP and plugin groups"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let name = colors_name(theme);
+        Ok(vec![Artifact::new(
+            format!("colors/{name}.lua"),
+            colorscheme(theme, &name),
+        )])
+    }
+}
+
+/// One `nvim_set_hl` call's attributes.
+#[derive(Default)]
+struct Highlight {
+    style: TokenStyle,
+    special: Option<Color>,
+    undercurl: bool,
+}
+
+fn colorscheme(theme: &Theme, name: &str) -> String {
+    let background = workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+    let resolver = Resolver::new(theme);
+    let roles = resolve_roles(theme);
+
+    let mut out = String::new();
+    let _ = writeln!(out, "-- {}", theme_name(theme));
+    out.push_str("-- Generated by `cyberdeck export --target neovim`; do not edit.\n\n");
+    out.push_str(
+        "vim.cmd('highlight clear')\n\
+         if vim.fn.exists('syntax_on') == 1 then\n  vim.cmd('syntax reset')\nend\n",
+    );
+    let _ = writeln!(
+        out,
+        "vim.o.background = '{}'\nvim.g.colors_name = '{name}'\n",
+        if is_dark(theme) { "dark" } else { "light" }
+    );
+    out.push_str("local function hl(group, spec)\n  vim.api.nvim_set_hl(0, group, spec)\nend\n\n");
+
+    let mut set = |group: &str, highlight: Highlight| {
+        let _ = writeln!(out, "hl('{group}', {})", spec(&highlight, background));
+    };
+    let normal = TokenStyle {
+        foreground: resolver.defaults().foreground,
+        background: Some(background),
+        ..TokenStyle::default()
+    };
+    set("Normal", style(normal));
+    for (group, scope) in SYNTAX_GROUPS.iter().chain(CAPTURES) {
+        if let Some(token) = TokenStyle::resolve(&resolver, scope) {
+            set(group, style(token));
+        }
+    }
+    for (group, foreground, bg) in UI_GROUPS {
+        let token = TokenStyle {
+            foreground: workbench_color(theme, foreground),
+            background: workbench_color(theme, bg),
+            ..TokenStyle::default()
+        };
+        if token.foreground.is_some() || token.background.is_some() {
+            set(group, style(token));
+        }
+    }
+    for (severity, keys) in DIAGNOSTICS {
+        let Some(color) = workbench_color(theme, keys) else {
+            continue;
+        };
+        let token = TokenStyle {
+            foreground: Some(color),
+            ..TokenStyle::default()
+        };
+        set(&format!("Diagnostic{severity}"), style(token));
+        set(
+            &format!("DiagnosticUnderline{severity}"),
+            Highlight {
+                special: Some(color),
+                undercurl: true,
+                ..Highlight::default()
+            },
+        );
+    }
+    for (group, foreground, bg, attrs) in PLUGIN_GROUPS {
+        let role = |role: &str| roles.get(role).copied();
+        let token = TokenStyle {
+            foreground: role(foreground),
+            background: role(bg),
+            bold: attrs.contains(&"bold"),
+            italic: attrs.contains(&"italic"),
+            underline: attrs.contains(&"underline"),
+            strikethrough: attrs.contains(&"strikethrough"),
+        };
+        if token.foreground.is_some() || token.background.is_some() {
+            set(group, style(token));
+        }
+    }
+
+    out.push('\n');
+    let semantic = semantic_groups(theme);
+    for (token_type, capture) in LSP_LINKS {
+        let group = format!("@lsp.type.{token_type}");
+        if !semantic.contains_key(&group) {
+            let _ = writeln!(out, "hl('{group}', {{ link = '{capture}' }})");
+        }
+    }
+    for (group, token) in semantic {
+        let _ = writeln!(out, "hl('{group}', {})", spec(&style(token), background));
+    }
+
+    if let Some(ansi) = ansi_colors(theme) {
+        out.push('\n');
+        for (index, color) in ansi.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "vim.g.terminal_color_{index} = '{}'",
+                color.over(background).to_hex_rgb()
+            );
+        }
+    }
+    out
+}
+
+fn style(style: TokenStyle) -> Highlight {
+    Highlight {
+        style,
+        ..Highlight::default()
+    }
+}
+
+/// `semanticTokenColors` as `@lsp.*` groups: `type` → `@lsp.type.type`,
+/// `*.modifier` → `@lsp.mod.modifier` and `type.modifier` →
+/// `@lsp.typemod.type.modifier`. Language-qualified selectors and ones with
+/// several modifiers have no Neovim equivalent and are skipped.
+fn semantic_groups(theme: &Theme) -> IndexMap<String, TokenStyle> {
+    let mut groups = IndexMap::new();
+    for (selector, value) in &theme.semantic_token_colors {
+        if selector.contains(':') {
+            continue;
+        }
+        let group = match selector.split('.').collect::<Vec<_>>()[..] {
+            ["*", modifier] => format!("@lsp.mod.{modifier}"),
+            [token_type] => format!("@lsp.type.{token_type}"),
+            [token_type, modifier] => format!("@lsp.typemod.{token_type}.{modifier}"),
+            _ => continue,
+        };
+        if let Some(token) = TokenStyle::from_semantic(value) {
+            groups.insert(group, token);
+        }
+    }
+    groups
+}
+
+/// The Lua table for one group, with colors composited over `background`.
+fn spec(highlight: &Highlight, background: Color) -> String {
+    let mut fields = Vec::new();
+    let style = &highlight.style;
+    for (key, color) in [
+        ("fg", style.foreground),
+        ("bg", style.background),
+        ("sp", highlight.special),
+    ] {
+        if let Some(color) = color {
+            fields.push(format!("{key} = '{}'", color.over(background).to_hex_rgb()));
+        }
+    }
+    for (set, attr) in [
+        (style.bold, "bold"),
+        (style.italic, "italic"),
+        (style.underline, "underline"),
+        (highlight.undercurl, "undercurl"),
+        (style.strikethrough, "strikethrough"),
+    ] {
+        if set {
+            fields.push(format!("{attr} = true"));
+        }
+    }
+    format!("{{ {} }}", fields.join(", "))
+}
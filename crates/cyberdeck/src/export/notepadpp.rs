@@ -0,0 +1,353 @@
+//! `notepadpp`: a Notepad++ theme (`<slug>.xml`, for the `themes` folder).
+//!
+//! Notepad++ styles Scintilla lexer states rather than scopes, so each
+//! lexer's states are listed with the scope path that stands for them.
+//! States the token rules leave unstyled get the default foreground, as
+//! Notepad++ does not inherit between them. Colors are composited over the
+//! editor background.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, xml_escape, Artifact, Exporter, TokenStyle};
+
+/// A lexer: its name, description and `(state, style ID, scope path)`
+/// triples.
+type Lexer = (
+    &'static str,
+    &'static str,
+    &'static [(&'static str, u32, &'static str)],
+);
+
+const C_LIKE: &[(&str, u32, &str)] = &[
+    ("PREPROCESSOR", 9, "meta.preprocessor"),
+    ("DEFAULT", 11, "variable.other"),
+    ("INSTRUCTION WORD", 5, "keyword.control"),
+    ("TYPE WORD", 16, "storage.type"),
+    ("NUMBER", 4, "constant.numeric"),
+    ("STRING", 6, "string.quoted.double"),
+    ("CHARACTER", 7, "string.quoted.single"),
+    ("OPERATOR", 10, "keyword.operator"),
+    ("VERBATIM", 13, "string.quoted.other"),
+    ("REGEX", 14, "string.regexp"),
+    ("COMMENT", 1, "comment.block"),
+    ("COMMENT LINE", 2, "comment.line"),
+    ("COMMENT DOC", 3, "comment.block.documentation"),
+    ("COMMENT LINE DOC", 15, "comment.block.documentation"),
+    (
+        "COMMENT DOC KEYWORD",
+        17,
+        "comment.block.documentation storage.type.class",
+    ),
+];
+
+const JAVASCRIPT: &[(&str, u32, &str)] = &[
+    ("DEFAULT", 11, "variable.other"),
+    ("INSTRUCTION WORD", 5, "keyword.control"),
+    ("TYPE WORD", 16, "storage.type"),
+    ("WINDOW INSTRUCTION", 19, "support.variable"),
+    ("NUMBER", 4, "constant.numeric"),
+    ("STRING", 6, "string.quoted.double"),
+    ("STRINGRAW", 20, "string.template"),
+    ("CHARACTER", 7, "string.quoted.single"),
+    ("OPERATOR", 10, "keyword.operator"),
+    ("REGEX", 14, "string.regexp"),
+    ("COMMENT", 1, "comment.block"),
+    ("COMMENT LINE", 2, "comment.line"),
+    ("COMMENT DOC", 3, "comment.block.documentation"),
+];
+
+const PYTHON: &[(&str, u32, &str)] = &[
+    ("DEFAULT", 0, "variable.other"),
+    ("COMMENTLINE", 1, "comment.line"),
+    ("NUMBER", 2, "constant.numeric"),
+    ("STRING", 3, "string.quoted.double"),
+    ("CHARACTER", 4, "string.quoted.single"),
+    ("KEYWORDS", 5, "keyword.control"),
+    ("TRIPLE", 6, "string.quoted.docstring"),
+    ("TRIPLEDOUBLE", 7, "string.quoted.docstring"),
+    ("CLASSNAME", 8, "entity.name.type.class"),
+    ("DEFNAME", 9, "entity.name.function"),
+    ("OPERATOR", 10, "keyword.operator"),
+    ("IDENTIFIER", 11, "variable.other"),
+    ("COMMENTBLOCK", 12, "comment.block"),
+    ("DECORATOR", 15, "meta.decorator"),
+    ("F STRING", 16, "string.interpolated"),
+];
+
+const RUST: &[(&str, u32, &str)] = &[
+    ("DEFAULT", 0, "variable.other"),
+    ("BLOCK COMMENT", 1, "comment.block"),
+    ("LINE COMMENT", 2, "comment.line"),
+    ("BLOCK DOC COMMENT", 3, "comment.block.documentation"),
+    ("LINE DOC COMMENT", 4, "comment.block.documentation"),
+    ("NUMBER", 5, "constant.numeric"),
+    ("KEYWORDS1", 6, "keyword.control"),
+    ("KEYWORDS2", 7, "storage.type"),
+    ("STRING", 13, "string.quoted.double"),
+    ("RAW STRING", 14, "string.quoted.double.raw"),
+    ("CHARACTER", 15, "string.quoted.single"),
+    ("OPERATOR", 16, "keyword.operator"),
+    ("IDENTIFIER", 17, "variable.other"),
+    ("LIFETIME", 18, "storage.modifier.lifetime"),
+    ("MACRO", 19, "entity.name.function.macro"),
+    ("LEXICAL ERROR", 20, "invalid.illegal"),
+];
+
+const JSON: &[(&str, u32, &str)] = &[
+    ("DEFAULT", 0, "meta.structure"),
+    ("NUMBER", 1, "constant.numeric"),
+    ("STRING", 2, "string.quoted.double"),
+    ("PROPERTY NAME", 4, "support.type.property-name"),
+    ("ESCAPE SEQUENCE", 5, "constant.character.escape"),
+    ("LINE COMMENT", 6, "comment.line"),
+    ("BLOCK COMMENT", 7, "comment.block"),
+    ("OPERATOR", 8, "punctuation.separator"),
+    ("KEYWORD", 11, "constant.language"),
+    ("PARSING ERROR", 13, "invalid.illegal"),
+];
+
+const CSS: &[(&str, u32, &str)] = &[
+    ("DEFAULT", 0, "source.css"),
+    ("TAG", 1, "entity.name.tag.css"),
+    ("CLASS", 2, "entity.other.attribute-name.class.css"),
+    (
+        "PSEUDOCLASS",
+        3,
+        "entity.other.attribute-name.pseudo-class.css",
+    ),
+    ("OPERATOR", 5, "keyword.operator"),
+    ("IDENTIFIER", 6, "support.type.property-name.css"),
+    ("VALUE", 8, "support.constant.property-value.css"),
+    ("COMMENT", 9, "comment.block"),
+    ("ID", 10, "entity.other.attribute-name.id.css"),
+    ("IMPORTANT", 11, "keyword.other.important.css"),
+    ("DIRECTIVE", 12, "keyword.control.at-rule.css"),
+];
+
+const HTML: &[(&str, u32, &str)] = &[
+    ("DEFAULT", 0, "text.html"),
+    ("TAG", 1, "entity.name.tag"),
+    ("TAGUNKNOWN", 2, "entity.name.tag"),
+    ("ATTRIBUTE", 3, "entity.other.attribute-name"),
+    ("ATTRIBUTEUNKNOWN", 4, "entity.other.attribute-name"),
+    ("NUMBER", 5, "constant.numeric"),
+    ("DOUBLESTRING", 6, "string.quoted.double"),
+    ("SINGLESTRING", 7, "string.quoted.single"),
+    ("COMMENT", 9, "comment.block"),
+    ("ENTITY", 10, "constant.character.entity"),
+    ("TAGEND", 11, "punctuation.definition.tag"),
+];
+
+const BASH: &[(&str, u32, &str)] = &[
+    ("DEFAULT", 0, "variable.other"),
+    ("ERROR", 1, "invalid.illegal"),
+    ("COMMENTLINE", 2, "comment.line"),
+    ("NUMBER", 3, "constant.numeric"),
+    ("WORD", 4, "keyword.control"),
+    ("STRING", 5, "string.quoted.double"),
+    ("CHARACTER", 6, "string.quoted.single"),
+    ("OPERATOR", 7, "keyword.operator"),
+    ("IDENTIFIER", 8, "variable.other"),
+    ("SCALAR", 9, "variable.other.normal"),
+    ("PARAM", 10, "variable.parameter"),
+    ("BACKTICKS", 11, "string.interpolated.backtick"),
+];
+
+const LEXERS: &[Lexer] = &[
+    ("bash", "bash", BASH),
+    ("c", "C", C_LIKE),
+    ("cpp", "C++", C_LIKE),
+    ("cs", "C#", C_LIKE),
+    ("css", "CSS", CSS),
+    ("html", "HTML", HTML),
+    ("java", "Java", C_LIKE),
+    ("javascript.js", "JavaScript", JAVASCRIPT),
+    ("json", "JSON", JSON),
+    ("python", "Python", PYTHON),
+    ("rust", "Rust", RUST),
+    ("typescript", "TypeScript", JAVASCRIPT),
+];
+
+/// Global widget styles: name, style ID, and the workbench keys of their
+/// foreground and background.
+const WIDGET_STYLES: &[(&str, u32, &[&str], &[&str])] = &[
+    (
+        "Indent guideline style",
+        37,
+        &[
+            "editorIndentGuide.background1",
+            "editorIndentGuide.background",
+        ],
+        &[],
+    ),
+    (
+        "Brace highlight style",
+        34,
+        &["editorBracketMatch.border"],
+        &["editorBracketMatch.background"],
+    ),
+    (
+        "Bad brace colour",
+        35,
+        &["editorError.foreground", "errorForeground"],
+        &[],
+    ),
+    (
+        "Current line background colour",
+        0,
+        &[],
+        &["editor.lineHighlightBackground"],
+    ),
+    (
+        "Selected text colour",
+        0,
+        &[],
+        &["editor.selectionBackground"],
+    ),
+    ("Caret colour", 2069, &["editorCursor.foreground"], &[]),
+    ("Edge colour", 0, &["editorRuler.foreground"], &[]),
+    (
+        "Line number margin",
+        33,
+        &["editorLineNumber.foreground"],
+        &["editorGutter.background", "editor.background"],
+    ),
+    (
+        "Fold margin",
+        0,
+        &["editorLineNumber.foreground"],
+        &["editorGutter.background", "editor.background"],
+    ),
+    (
+        "White space symbol",
+        0,
+        &["editorWhitespace.foreground"],
+        &[],
+    ),
+    (
+        "Smart HighLighting",
+        29,
+        &[],
+        &["editor.wordHighlightBackground"],
+    ),
+    ("Find Mark Style", 31, &[], &["editor.findMatchBackground"]),
+    (
+        "Incremental highlight all",
+        28,
+        &[],
+        &["editor.findMatchHighlightBackground"],
+    ),
+];
+
+pub struct NotepadPlusPlusExporter;
+
+impl Exporter for NotepadPlusPlusExporter {
+    fn name(&self) -> &'static str {
+        "notepadpp"
+    }
+
+    fn extension(&self) -> &'static str {
+        "xml"
+    }
+
+    fn description(&self) -> &'static str {
+        "Notepad++ theme with global and per-lexer styles"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        Ok(vec![Artifact::new(
+            format!("{}.xml", theme_slug(theme)),
+            stylers(theme),
+        )])
+    }
+}
+
+fn stylers(theme: &Theme) -> String {
+    let background = workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+    let resolver = Resolver::new(theme);
+    let default = TokenStyle {
+        foreground: resolver.defaults().foreground,
+        background: Some(background),
+        ..TokenStyle::default()
+    };
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n");
+    let _ = writeln!(
+        out,
+        "<!-- {} (generated by `cyberdeck export`; do not edit) -->",
+        xml_escape(theme_name(theme))
+    );
+    out.push_str("<NotepadPlus>\n    <LexerStyles>\n");
+    for (lexer, description, states) in LEXERS {
+        let _ = writeln!(
+            out,
+            "        <LexerType name=\"{lexer}\" desc=\"{description}\" ext=\"\">"
+        );
+        for (state, id, scope) in *states {
+            let style = TokenStyle::resolve(&resolver, scope).unwrap_or(default);
+            let style = TokenStyle {
+                foreground: style.foreground.or(default.foreground),
+                background: style.background.or(default.background),
+                ..style
+            };
+            let _ = writeln!(
+                out,
+                "            <WordsStyle name=\"{state}\" styleID=\"{id}\"{} />",
+                attributes(&style, background)
+            );
+        }
+        out.push_str("        </LexerType>\n");
+    }
+    out.push_str("    </LexerStyles>\n    <GlobalStyles>\n");
+
+    for (name, id) in [("Global override", 0), ("Default Style", 32)] {
+        let _ = writeln!(
+            out,
+            "        <WidgetStyle name=\"{name}\" styleID=\"{id}\"{} />",
+            attributes(&default, background)
+        );
+    }
+    for (name, id, foreground, bg) in WIDGET_STYLES {
+        let style = TokenStyle {
+            foreground: workbench_color(theme, foreground),
+            background: workbench_color(theme, bg),
+            ..TokenStyle::default()
+        };
+        if style.foreground.is_some() || style.background.is_some() {
+            let _ = writeln!(
+                out,
+                "        <WidgetStyle name=\"{name}\" styleID=\"{id}\"{} />",
+                attributes(&style, background)
+            );
+        }
+    }
+    out.push_str("    </GlobalStyles>\n</NotepadPlus>\n");
+    out
+}
+
+/// The `fgColor`, `bgColor` and `fontStyle` attributes. Notepad++ wants
+/// bare upper-case hex; font style bits are 1 bold, 2 italic, 4 underline.
+fn attributes(style: &TokenStyle, background: Color) -> String {
+    let hex = |color: Color| color.over(background).to_hex_rgb()[1..].to_uppercase();
+    let mut attributes = String::new();
+    if let Some(foreground) = style.foreground {
+        let _ = write!(attributes, " fgColor=\"{}\"", hex(foreground));
+    }
+    if let Some(bg) = style.background {
+        let _ = write!(attributes, " bgColor=\"{}\"", hex(bg));
+    }
+    let font_style =
+        u8::from(style.bold) | (u8::from(style.italic) << 1) | (u8::from(style.underline) << 2);
+    let _ = write!(
+        attributes,
+        " fontName=\"\" fontStyle=\"{font_style}\" fontSize=\"\""
+    );
+    attributes
+}
@@ -0,0 +1,124 @@
+//! `obsidian`: a CSS snippet (`<slug>.css`, for the vault's
+//! `.obsidian/snippets`) overriding Obsidian's color variables for the
+//! theme's base (`.theme-dark` or `.theme-light`) from the palette roles,
+//! so notes, code blocks and accents match the editor.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::highlightjs::rule;
+use super::{is_dark, theme_name, theme_slug, Artifact, Exporter};
+
+/// Obsidian variables and the roles they read, first present wins.
+const VARIABLES: &[(&str, &[&str])] = &[
+    ("--background-primary", &["background"]),
+    (
+        "--background-primary-alt",
+        &["background.tertiary", "background"],
+    ),
+    (
+        "--background-secondary",
+        &["background.secondary", "background"],
+    ),
+    (
+        "--background-secondary-alt",
+        &["background.secondary", "background"],
+    ),
+    ("--background-modifier-border", &["border"]),
+    ("--background-modifier-hover", &["highlight"]),
+    ("--background-modifier-error", &["error"]),
+    ("--background-modifier-success", &["success"]),
+    ("--text-normal", &["foreground"]),
+    ("--text-muted", &["foreground.secondary", "foreground"]),
+    ("--text-faint", &["foreground.muted", "comment"]),
+    ("--text-accent", &["accent.primary"]),
+    (
+        "--text-accent-hover",
+        &["accent.secondary", "accent.primary"],
+    ),
+    ("--text-error", &["error"]),
+    ("--text-success", &["success"]),
+    ("--text-warning", &["warning"]),
+    ("--text-selection", &["highlight"]),
+    ("--text-highlight-bg", &["highlight"]),
+    ("--interactive-accent", &["accent.primary"]),
+    (
+        "--interactive-accent-hover",
+        &["accent.secondary", "accent.primary"],
+    ),
+    ("--caret-color", &["cursor", "foreground"]),
+    ("--link-color", &["function", "accent.primary"]),
+    ("--link-external-color", &["type", "accent.primary"]),
+    ("--tag-color", &["accent.secondary"]),
+    ("--blockquote-border-color", &["accent.primary"]),
+    ("--h1-color", &["keyword"]),
+    ("--h2-color", &["function"]),
+    ("--h3-color", &["type"]),
+    ("--h4-color", &["string"]),
+    ("--h5-color", &["number"]),
+    ("--h6-color", &["comment"]),
+    ("--code-background", &["background.secondary", "background"]),
+    ("--code-normal", &["foreground"]),
+    ("--code-comment", &["comment"]),
+    ("--code-keyword", &["keyword"]),
+    ("--code-string", &["string"]),
+    ("--code-function", &["function"]),
+    ("--code-value", &["number", "constant"]),
+    ("--code-property", &["property"]),
+    ("--code-tag", &["class", "type"]),
+    ("--code-important", &["storage", "keyword"]),
+    ("--code-operator", &["keyword"]),
+    (
+        "--code-punctuation",
+        &["foreground.secondary", "foreground"],
+    ),
+];
+
+pub struct ObsidianExporter;
+
+impl Exporter for ObsidianExporter {
+    fn name(&self) -> &'static str {
+        "obsidian"
+    }
+
+    fn extension(&self) -> &'static str {
+        "css"
+    }
+
+    fn description(&self) -> &'static str {
+        "Obsidian CSS snippet"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let roles = resolve_roles(theme);
+        let declarations: Vec<String> = VARIABLES
+            .iter()
+            .filter_map(|(variable, names)| {
+                let color = names.iter().find_map(|name| roles.get(name))?;
+                Some(format!("{variable}: {color};"))
+            })
+            .collect();
+        let selector = if is_dark(theme) {
+            ".theme-dark"
+        } else {
+            ".theme-light"
+        };
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "/* {} for Obsidian; save in .obsidian/snippets and enable it under Appearance.\n   \
+             Generated by `cyberdeck export --target obsidian`; do not edit. */\n",
+            theme_name(theme)
+        );
+        rule(&mut out, selector, &declarations);
+        Ok(vec![Artifact::new(
+            format!("{}.css", theme_slug(theme)),
+            format!("{}\n", out.trim_end()),
+        )])
+    }
+}
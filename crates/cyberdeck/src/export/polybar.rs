@@ -0,0 +1,125 @@
+//! `polybar`: a `[colors]` section (`<slug>.ini`) with every palette role,
+//! to include from the Polybar config and reference as
+//! `${colors.accent_primary}` and so on. Example bar and module settings
+//! using the colors follow as comments, since Polybar rejects sections
+//! defined twice.
+//!
+//! Role names become keys with `_` for `.`. Polybar reads `#aarrggbb`, so
+//! translucent roles keep their alpha.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter};
+
+/// Commented example settings: section, key, and the role it references.
+const EXAMPLES: &[(&str, &str, &str)] = &[
+    ("bar/cyberdeck", "background", "background"),
+    ("bar/cyberdeck", "foreground", "foreground"),
+    ("bar/cyberdeck", "border-color", "border"),
+    ("bar/cyberdeck", "line-color", "accent.primary"),
+    ("module/xworkspaces", "label-active-background", "highlight"),
+    (
+        "module/xworkspaces",
+        "label-active-underline",
+        "accent.primary",
+    ),
+    (
+        "module/xworkspaces",
+        "label-occupied-foreground",
+        "foreground",
+    ),
+    ("module/xworkspaces", "label-urgent-background", "error"),
+    (
+        "module/xworkspaces",
+        "label-empty-foreground",
+        "foreground.muted",
+    ),
+    ("module/xwindow", "label-foreground", "foreground.secondary"),
+    ("module/cpu", "format-prefix-foreground", "accent.primary"),
+    (
+        "module/memory",
+        "format-prefix-foreground",
+        "accent.secondary",
+    ),
+    ("module/date", "label-foreground", "type"),
+    ("module/battery", "format-charging-foreground", "success"),
+    ("module/battery", "format-discharging-foreground", "warning"),
+    ("module/battery", "label-full-foreground", "success"),
+    (
+        "module/pulseaudio",
+        "format-volume-prefix-foreground",
+        "function",
+    ),
+    (
+        "module/pulseaudio",
+        "label-muted-foreground",
+        "foreground.muted",
+    ),
+];
+
+pub struct PolybarExporter;
+
+impl Exporter for PolybarExporter {
+    fn name(&self) -> &'static str {
+        "polybar"
+    }
+
+    fn extension(&self) -> &'static str {
+        "ini"
+    }
+
+    fn description(&self) -> &'static str {
+        "Polybar colors section and example modules"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let roles = resolve_roles(theme);
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "; {} for Polybar; include-file this from the Polybar config.\n\
+             ; Generated by `cyberdeck export --target polybar`; do not edit.\n\n\
+             [colors]",
+            theme_name(theme)
+        );
+        for (role, color) in &roles {
+            let _ = writeln!(out, "{} = {}", key(role), polybar_color(*color));
+        }
+
+        let mut section = "";
+        for (name, option, role) in EXAMPLES {
+            if !roles.contains_key(role) {
+                continue;
+            }
+            if section != *name {
+                section = name;
+                let _ = writeln!(out, "\n; [{name}]");
+            }
+            let _ = writeln!(out, "; {option} = ${{colors.{}}}", key(role));
+        }
+        Ok(vec![Artifact::new(
+            format!("{}.ini", theme_slug(theme)),
+            out,
+        )])
+    }
+}
+
+fn key(role: &str) -> String {
+    role.replace('.', "_")
+}
+
+/// A color as Polybar writes it, with the alpha first when there is one.
+fn polybar_color(color: Color) -> String {
+    if color.is_opaque() {
+        color.to_hex_rgb()
+    } else {
+        format!("#{:02x}{}", color.a, &color.to_hex_rgb()[1..])
+    }
+}
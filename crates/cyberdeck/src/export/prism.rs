@@ -0,0 +1,159 @@
+//! `prism`: a Prism.js theme stylesheet (`prism-<slug>.css`), including
+//! the line-highlight and line-numbers plugins.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::highlightjs::rule;
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter, TokenStyle, AUTHOR};
+
+/// Prism token classes and the scope path each group is resolved from.
+const TOKENS: &[(&[&str], &str)] = &[
+    (&["comment", "prolog", "doctype", "cdata"], "comment.line"),
+    (&["punctuation"], "punctuation"),
+    (&["namespace"], "entity.name.namespace"),
+    (&["keyword", "atrule"], "keyword.control"),
+    (&["operator"], "keyword.operator"),
+    (&["boolean", "constant"], "constant.language"),
+    (&["number"], "constant.numeric"),
+    (&["string", "char", "attr-value"], "string.quoted.double"),
+    (&["regex"], "string.regexp"),
+    (&["symbol"], "constant.other.symbol"),
+    (&["entity"], "constant.character.entity"),
+    (&["url"], "markup.underline.link"),
+    (&["builtin"], "support.function"),
+    (&["function"], "entity.name.function"),
+    (&["class-name"], "entity.name.type.class"),
+    (&["variable"], "variable.other.readwrite"),
+    (&["property"], "variable.other.property"),
+    (&["tag"], "entity.name.tag"),
+    (&["attr-name"], "entity.other.attribute-name"),
+    (&["selector"], "entity.other.attribute-name.class"),
+    (&["important"], "keyword.other.important"),
+    (&["inserted"], "markup.inserted"),
+    (&["deleted"], "markup.deleted"),
+    (&["bold"], "markup.bold"),
+    (&["italic"], "markup.italic"),
+];
+
+const CODE: &str = "code[class*=\"language-\"],\npre[class*=\"language-\"]";
+
+pub struct PrismExporter;
+
+impl Exporter for PrismExporter {
+    fn name(&self) -> &'static str {
+        "prism"
+    }
+
+    fn extension(&self) -> &'static str {
+        "css"
+    }
+
+    fn description(&self) -> &'static str {
+        "Prism.js stylesheet with line-highlight and line-numbers support"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let resolver = Resolver::new(theme);
+        let color = |keys: &[&str]| workbench_color(theme, keys);
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "/**\n * {} for Prism.js, by {AUTHOR}.\n * \
+             Generated by `cyberdeck export --target prism`; do not edit.\n */\n",
+            theme_name(theme)
+        );
+
+        let mut base = vec![
+            "text-align: left;".to_owned(),
+            "white-space: pre;".to_owned(),
+            "word-spacing: normal;".to_owned(),
+            "word-break: normal;".to_owned(),
+            "word-wrap: normal;".to_owned(),
+            "tab-size: 4;".to_owned(),
+            "hyphens: none;".to_owned(),
+        ];
+        if let Some(foreground) = resolver.defaults().foreground {
+            base.insert(0, format!("color: {foreground};"));
+        }
+        rule(&mut out, CODE, &base);
+
+        if let Some(selection) = color(&["editor.selectionBackground"]) {
+            let mut declarations = vec![format!("background: {selection};")];
+            if let Some(foreground) = color(&["editor.selectionForeground"]) {
+                declarations.push(format!("color: {foreground};"));
+            }
+            let selectors: Vec<String> = CODE
+                .split('\n')
+                .flat_map(|code| {
+                    let code = code.trim_end_matches(',');
+                    [format!("{code}::selection"), format!("{code} ::selection")]
+                })
+                .collect();
+            rule(&mut out, &selectors.join(",\n"), &declarations);
+        }
+
+        if let Some(background) = color(&["editor.background"]) {
+            rule(
+                &mut out,
+                "pre[class*=\"language-\"]",
+                &[
+                    format!("background: {background};"),
+                    "padding: 1em;".to_owned(),
+                    "margin: 0.5em 0;".to_owned(),
+                    "overflow: auto;".to_owned(),
+                ],
+            );
+            rule(
+                &mut out,
+                ":not(pre) > code[class*=\"language-\"]",
+                &[
+                    format!("background: {background};"),
+                    "padding: 0.1em 0.3em;".to_owned(),
+                    "border-radius: 0.3em;".to_owned(),
+                ],
+            );
+        }
+
+        for (classes, scope) in TOKENS {
+            if let Some(style) = TokenStyle::resolve(&resolver, scope) {
+                let selector: Vec<String> = classes.iter().map(|c| format!(".token.{c}")).collect();
+                rule(&mut out, &selector.join(",\n"), &style.css_declarations());
+            }
+        }
+
+        // Plugins.
+        if let Some(highlight) = color(&["editor.lineHighlightBackground"]) {
+            let mut declarations = vec![format!("background: {highlight};")];
+            if let Some(border) =
+                color(&["editor.lineHighlightBorder"]).filter(|border| *border != highlight)
+            {
+                declarations.push(format!("box-shadow: inset 2px 0 0 {border};"));
+            }
+            rule(&mut out, ".line-highlight", &declarations);
+        }
+        if let Some(border) = color(&["editorGroup.border", "panel.border"]) {
+            rule(
+                &mut out,
+                ".line-numbers .line-numbers-rows",
+                &[format!("border-right: 1px solid {border};")],
+            );
+        }
+        if let Some(number) = color(&["editorLineNumber.foreground"]) {
+            rule(
+                &mut out,
+                ".line-numbers-rows > span::before",
+                &[format!("color: {number};")],
+            );
+        }
+
+        Ok(vec![Artifact::new(
+            format!("prism-{}.css", theme_slug(theme)),
+            format!("{}\n", out.trim_end()),
+        )])
+    }
+}
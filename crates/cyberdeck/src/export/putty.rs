@@ -0,0 +1,107 @@
+//! `putty`: PuTTY session colors.
+//!
+//! `<slug>.reg` writes `Colour0`–`Colour21` into a saved session named after
+//! the theme; `<slug>.txt` lists the same values for pasting into the
+//! Colours panel by hand (or into a session PuTTY already has).
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter, TerminalColors, ANSI_NAMES};
+
+pub struct PuttyExporter;
+
+impl Exporter for PuttyExporter {
+    fn name(&self) -> &'static str {
+        "putty"
+    }
+
+    fn extension(&self) -> &'static str {
+        "reg"
+    }
+
+    fn description(&self) -> &'static str {
+        "PuTTY session colors as a registry file and a plain listing"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let colours = colours(&TerminalColors::new(theme));
+        let name = theme_name(theme);
+        let slug = theme_slug(theme);
+
+        // regedit expects CRLF line endings.
+        let mut reg = String::new();
+        let _ = write!(
+            reg,
+            "Windows Registry Editor Version 5.00\r\n\r\n\
+             ; {name}\r\n\
+             ; Generated by `cyberdeck export --target putty`; do not edit.\r\n\r\n\
+             [HKEY_CURRENT_USER\\Software\\SimonTatham\\PuTTY\\Sessions\\{}]\r\n",
+            session_key(name)
+        );
+        for (index, (_, color)) in colours.iter().enumerate() {
+            let _ = write!(reg, "\"Colour{index}\"=\"{}\"\r\n", rgb(*color));
+        }
+
+        let mut listing = String::new();
+        let _ = writeln!(listing, "PuTTY colours for {name}\n");
+        for (index, (label, color)) in colours.iter().enumerate() {
+            let _ = writeln!(
+                listing,
+                "{:<9} {label:<26} {:<12} {}",
+                format!("Colour{index}"),
+                rgb(*color),
+                color.to_hex_rgb()
+            );
+        }
+
+        Ok(vec![
+            Artifact::new(format!("{slug}.reg"), reg),
+            Artifact::new(format!("{slug}.txt"), listing),
+        ])
+    }
+}
+
+/// PuTTY's twenty-two colours in `ColourN` order, with the Colours panel's
+/// labels.
+fn colours(colors: &TerminalColors) -> Vec<(String, Color)> {
+    let mut colours = vec![
+        ("Default Foreground".to_owned(), colors.foreground),
+        ("Default Bold Foreground".to_owned(), colors.foreground),
+        ("Default Background".to_owned(), colors.background),
+        ("Default Bold Background".to_owned(), colors.background),
+        ("Cursor Text".to_owned(), colors.cursor_text),
+        ("Cursor Colour".to_owned(), colors.cursor),
+    ];
+    for (index, name) in ANSI_NAMES.iter().enumerate() {
+        let label = format!("ANSI {}{}", name[..1].to_uppercase(), &name[1..]);
+        colours.push((label.clone(), colors.ansi[index]));
+        colours.push((format!("{label} Bold"), colors.ansi[index + 8]));
+    }
+    colours
+}
+
+fn rgb(color: Color) -> String {
+    format!("{},{},{}", color.r, color.g, color.b)
+}
+
+/// Session names are stored percent-escaped, with PuTTY's own rule: spaces,
+/// `\*?%`, anything outside printable ASCII and a leading dot.
+fn session_key(name: &str) -> String {
+    let mut key = String::new();
+    for (index, byte) in name.bytes().enumerate() {
+        let escape = matches!(byte, b' ' | b'\\' | b'*' | b'?' | b'%')
+            || !(b' '..=b'~').contains(&byte)
+            || (byte == b'.' && index == 0);
+        if escape {
+            let _ = write!(key, "%{byte:02X}");
+        } else {
+            key.push(char::from(byte));
+        }
+    }
+    key
+}
@@ -0,0 +1,205 @@
+//! `pygments`: a Pygments style module (`<slug>.py`, with underscores) for
+//! Sphinx, MkDocs and anything else that highlights through Pygments.
+//!
+//! Pygments tokens inherit from their parents, so only tokens whose style
+//! differs from their parent's are listed. Colors are composited over the
+//! editor background.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter, TokenStyle};
+
+/// Pygments tokens, parents first, and the scope path each is resolved from.
+const TOKENS: &[(&str, &str)] = &[
+    ("Comment", "comment.line"),
+    ("Comment.Hashbang", "comment.line.shebang"),
+    ("Comment.Preproc", "meta.preprocessor"),
+    ("Comment.Special", "comment.block.documentation"),
+    ("Keyword", "keyword.control"),
+    ("Keyword.Constant", "constant.language"),
+    ("Keyword.Declaration", "storage.type"),
+    ("Keyword.Namespace", "keyword.control.import"),
+    ("Keyword.Type", "support.type.primitive"),
+    ("Operator", "keyword.operator"),
+    ("Operator.Word", "keyword.operator.word"),
+    ("Punctuation", "punctuation"),
+    ("Name", "variable.other"),
+    ("Name.Attribute", "entity.other.attribute-name"),
+    ("Name.Builtin", "support.function"),
+    ("Name.Builtin.Pseudo", "variable.language"),
+    ("Name.Class", "entity.name.type.class"),
+    ("Name.Constant", "variable.other.constant"),
+    ("Name.Decorator", "meta.decorator"),
+    ("Name.Entity", "constant.character.entity"),
+    ("Name.Exception", "entity.name.type.class"),
+    ("Name.Function", "entity.name.function"),
+    ("Name.Label", "entity.name.label"),
+    ("Name.Namespace", "entity.name.namespace"),
+    ("Name.Property", "variable.other.property"),
+    ("Name.Tag", "entity.name.tag"),
+    ("Name.Variable", "variable.other.readwrite"),
+    ("Literal", "constant"),
+    ("Literal.Number", "constant.numeric"),
+    ("Literal.String", "string.quoted.double"),
+    ("Literal.String.Char", "string.quoted.single"),
+    ("Literal.String.Doc", "string.quoted.docstring"),
+    ("Literal.String.Escape", "constant.character.escape"),
+    (
+        "Literal.String.Interpol",
+        "punctuation.definition.template-expression",
+    ),
+    ("Literal.String.Regex", "string.regexp"),
+    ("Literal.String.Symbol", "constant.other.symbol"),
+    ("Generic.Deleted", "markup.deleted"),
+    ("Generic.Emph", "markup.italic"),
+    ("Generic.Heading", "markup.heading"),
+    ("Generic.Inserted", "markup.inserted"),
+    ("Generic.Strong", "markup.bold"),
+    ("Generic.Subheading", "markup.heading.2"),
+    ("Error", "invalid.illegal"),
+];
+
+pub struct PygmentsExporter;
+
+impl Exporter for PygmentsExporter {
+    fn name(&self) -> &'static str {
+        "pygments"
+    }
+
+    fn extension(&self) -> &'static str {
+        "py"
+    }
+
+    fn description(&self) -> &'static str {
+        "Pygments style class"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let slug = theme_slug(theme);
+        Ok(vec![Artifact::new(
+            format!("{}.py", slug.replace('-', "_")),
+            style_module(theme, &slug),
+        )])
+    }
+}
+
+fn style_module(theme: &Theme, slug: &str) -> String {
+    let background = workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+    let hex = |color: Color| color.over(background).to_hex_rgb();
+    let styles = token_styles(theme);
+
+    let class = slug
+        .split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect::<String>();
+    let name = theme_name(theme);
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "\"\"\"{name} style for Pygments.\n\n\
+         Generated by `cyberdeck export --target pygments`; do not edit.\n\
+         \"\"\"\n\n\
+         from pygments.style import Style\n\
+         from pygments.token import (\n    \
+         Comment,\n    Error,\n    Generic,\n    Keyword,\n    Literal,\n    \
+         Name,\n    Operator,\n    Punctuation,\n    Token,\n\
+         )\n\n\n\
+         class {class}Style(Style):\n    \
+         name = \"{slug}\"\n\n    \
+         background_color = \"{}\"\n",
+        hex(background)
+    );
+    for (attribute, keys) in [
+        ("highlight_color", &["editor.lineHighlightBackground"][..]),
+        ("line_number_color", &["editorLineNumber.foreground"][..]),
+        (
+            "line_number_background_color",
+            &["editorGutter.background", "editor.background"][..],
+        ),
+        (
+            "line_number_special_color",
+            &["editorLineNumber.activeForeground"][..],
+        ),
+    ] {
+        if let Some(color) = workbench_color(theme, keys) {
+            let _ = writeln!(out, "    {attribute} = \"{}\"", hex(color));
+        }
+    }
+    out.push_str("\n    styles = {\n");
+    for (token, style, inherited) in &styles {
+        let _ = writeln!(
+            out,
+            "        {token}: \"{}\",",
+            style_string(style, inherited, hex)
+        );
+    }
+    out.push_str("    }\n");
+    out
+}
+
+/// `Token` followed by every token in [`TOKENS`] whose style differs from
+/// the one it inherits, as `(token, style, inherited)`.
+pub(super) fn token_styles(theme: &Theme) -> Vec<(&'static str, TokenStyle, TokenStyle)> {
+    let resolver = Resolver::new(theme);
+    let text = TokenStyle {
+        foreground: resolver.defaults().foreground,
+        ..TokenStyle::default()
+    };
+
+    // Every token's effective style, listed or inherited, so children are
+    // compared with what Pygments would otherwise give them.
+    let mut effective: Vec<(&str, TokenStyle)> = vec![("Token", text)];
+    let mut styles = vec![("Token", text, text)];
+    for (token, scope) in TOKENS {
+        let parent = token.rsplit_once('.').map_or("Token", |(parent, _)| parent);
+        let inherited = effective
+            .iter()
+            .find(|(name, _)| *name == parent)
+            .map_or(text, |(_, style)| *style);
+        let style = TokenStyle::resolve(&resolver, scope).unwrap_or(inherited);
+        effective.push((token, style));
+        if style != inherited {
+            styles.push((*token, style, inherited));
+        }
+    }
+    styles
+}
+
+/// A Pygments style string such as `bold #ff0000 bg:#000000`, turning off
+/// attributes the token would otherwise inherit.
+pub(super) fn style_string(
+    style: &TokenStyle,
+    inherited: &TokenStyle,
+    hex: impl Fn(Color) -> String,
+) -> String {
+    let mut parts = Vec::new();
+    for (set, was_set, word) in [
+        (style.bold, inherited.bold, "bold"),
+        (style.italic, inherited.italic, "italic"),
+        (style.underline, inherited.underline, "underline"),
+    ] {
+        if set {
+            parts.push(word.to_owned());
+        } else if was_set {
+            parts.push(format!("no{word}"));
+        }
+    }
+    if let Some(color) = style.foreground {
+        parts.push(hex(color));
+    }
+    if let Some(color) = style.background {
+        parts.push(format!("bg:{}", hex(color)));
+    }
+    parts.join(" ")
+}
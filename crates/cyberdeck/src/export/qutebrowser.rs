@@ -0,0 +1,201 @@
+//! `qutebrowser`: a `config.py` fragment (`<slug>.py`) setting
+//! `c.colors.*` for the completion menu, tabs, status bar, hints and
+//! messages from the workbench colors. Load it with
+//! `config.source('<slug>.py')`.
+//!
+//! Colors are composited over the editor background.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter};
+
+/// `c.colors` options and the workbench keys they read, first present wins.
+const OPTIONS: &[(&str, &[&str])] = &[
+    (
+        "completion.fg",
+        &["editorSuggestWidget.foreground", "editor.foreground"],
+    ),
+    (
+        "completion.odd.bg",
+        &["editorSuggestWidget.background", "editorWidget.background"],
+    ),
+    (
+        "completion.even.bg",
+        &["editorSuggestWidget.background", "editorWidget.background"],
+    ),
+    (
+        "completion.category.fg",
+        &["sideBarSectionHeader.foreground", "sideBarTitle.foreground"],
+    ),
+    (
+        "completion.category.bg",
+        &["sideBarSectionHeader.background", "sideBar.background"],
+    ),
+    (
+        "completion.category.border.top",
+        &["sideBarSectionHeader.border", "panel.border"],
+    ),
+    (
+        "completion.category.border.bottom",
+        &["sideBarSectionHeader.border", "panel.border"],
+    ),
+    (
+        "completion.item.selected.fg",
+        &[
+            "editorSuggestWidget.selectedForeground",
+            "list.activeSelectionForeground",
+        ],
+    ),
+    (
+        "completion.item.selected.bg",
+        &[
+            "editorSuggestWidget.selectedBackground",
+            "list.activeSelectionBackground",
+        ],
+    ),
+    (
+        "completion.item.selected.border.top",
+        &[
+            "editorSuggestWidget.selectedBackground",
+            "list.activeSelectionBackground",
+        ],
+    ),
+    (
+        "completion.item.selected.border.bottom",
+        &[
+            "editorSuggestWidget.selectedBackground",
+            "list.activeSelectionBackground",
+        ],
+    ),
+    (
+        "completion.match.fg",
+        &[
+            "editorSuggestWidget.highlightForeground",
+            "list.highlightForeground",
+        ],
+    ),
+    ("completion.scrollbar.fg", &["scrollbarSlider.background"]),
+    (
+        "completion.scrollbar.bg",
+        &["editorSuggestWidget.background", "editorWidget.background"],
+    ),
+    ("tabs.bar.bg", &["editorGroupHeader.tabsBackground"]),
+    ("tabs.odd.fg", &["tab.inactiveForeground"]),
+    ("tabs.odd.bg", &["tab.inactiveBackground"]),
+    ("tabs.even.fg", &["tab.inactiveForeground"]),
+    ("tabs.even.bg", &["tab.inactiveBackground"]),
+    ("tabs.selected.odd.fg", &["tab.activeForeground"]),
+    ("tabs.selected.odd.bg", &["tab.activeBackground"]),
+    ("tabs.selected.even.fg", &["tab.activeForeground"]),
+    ("tabs.selected.even.bg", &["tab.activeBackground"]),
+    ("tabs.indicator.start", &["progressBar.background"]),
+    (
+        "tabs.indicator.stop",
+        &[
+            "gitDecoration.addedResourceForeground",
+            "terminal.ansiGreen",
+        ],
+    ),
+    (
+        "tabs.indicator.error",
+        &["editorError.foreground", "errorForeground"],
+    ),
+    ("statusbar.normal.fg", &["statusBar.foreground"]),
+    ("statusbar.normal.bg", &["statusBar.background"]),
+    ("statusbar.insert.fg", &["statusBar.foreground"]),
+    (
+        "statusbar.insert.bg",
+        &["statusBar.debuggingBackground", "statusBar.background"],
+    ),
+    (
+        "statusbar.command.fg",
+        &["input.foreground", "editor.foreground"],
+    ),
+    (
+        "statusbar.command.bg",
+        &["input.background", "editorWidget.background"],
+    ),
+    ("statusbar.url.fg", &["textLink.foreground"]),
+    (
+        "statusbar.url.hover.fg",
+        &["textLink.activeForeground", "textLink.foreground"],
+    ),
+    ("statusbar.url.success.https.fg", &["terminal.ansiGreen"]),
+    ("statusbar.url.warn.fg", &["editorWarning.foreground"]),
+    (
+        "statusbar.url.error.fg",
+        &["editorError.foreground", "errorForeground"],
+    ),
+    ("statusbar.progress.bg", &["progressBar.background"]),
+    (
+        "hints.fg",
+        &["badge.foreground", "activityBarBadge.foreground"],
+    ),
+    (
+        "hints.bg",
+        &["badge.background", "activityBarBadge.background"],
+    ),
+    ("hints.match.fg", &["editorWarning.foreground"]),
+    (
+        "messages.info.fg",
+        &["notifications.foreground", "editor.foreground"],
+    ),
+    (
+        "messages.info.bg",
+        &["notifications.background", "editorWidget.background"],
+    ),
+    ("messages.warning.fg", &["editor.background"]),
+    ("messages.warning.bg", &["editorWarning.foreground"]),
+    ("messages.error.fg", &["editor.background"]),
+    (
+        "messages.error.bg",
+        &["editorError.foreground", "errorForeground"],
+    ),
+];
+
+pub struct QutebrowserExporter;
+
+impl Exporter for QutebrowserExporter {
+    fn name(&self) -> &'static str {
+        "qutebrowser"
+    }
+
+    fn extension(&self) -> &'static str {
+        "py"
+    }
+
+    fn description(&self) -> &'static str {
+        "qutebrowser config.py colors"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let background =
+            workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# {} for qutebrowser; load with config.source() from config.py.\n\
+             # Generated by `cyberdeck export --target qutebrowser`; do not edit.\n",
+            theme_name(theme)
+        );
+        for (option, keys) in OPTIONS {
+            if let Some(color) = workbench_color(theme, keys) {
+                let _ = writeln!(
+                    out,
+                    "c.colors.{option} = '{}'",
+                    color.over(background).to_hex_rgb()
+                );
+            }
+        }
+        Ok(vec![Artifact::new(
+            format!("{}.py", theme_slug(theme)),
+            out,
+        )])
+    }
+}
@@ -0,0 +1,101 @@
+//! `rofi`: a Rofi color file (`<slug>.rasi`) setting the variables Rofi's
+//! default theme reads, from the palette roles. Import it from a theme with
+//! `@import "<slug>.rasi"`, or use it on its own.
+//!
+//! Rofi understands `#rrggbbaa`, so translucent roles keep their alpha.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter};
+
+/// Variables and the roles they read, first present wins.
+const VARIABLES: &[(&str, &[&str])] = &[
+    ("background", &["background"]),
+    ("foreground", &["foreground"]),
+    ("lightbg", &["background.secondary", "background"]),
+    ("lightfg", &["foreground.secondary", "foreground"]),
+    ("red", &["error"]),
+    ("blue", &["accent.primary"]),
+    ("border-color", &["accent.primary", "border"]),
+    ("separatorcolor", &["border"]),
+    ("normal-background", &["background"]),
+    ("normal-foreground", &["foreground"]),
+    (
+        "alternate-normal-background",
+        &["background.secondary", "background"],
+    ),
+    ("alternate-normal-foreground", &["foreground"]),
+    ("selected-normal-background", &["highlight"]),
+    (
+        "selected-normal-foreground",
+        &["accent.secondary", "foreground"],
+    ),
+    ("active-background", &["background"]),
+    ("active-foreground", &["accent.primary"]),
+    (
+        "alternate-active-background",
+        &["background.secondary", "background"],
+    ),
+    ("alternate-active-foreground", &["accent.primary"]),
+    ("selected-active-background", &["highlight"]),
+    ("selected-active-foreground", &["accent.primary"]),
+    ("urgent-background", &["background"]),
+    ("urgent-foreground", &["error"]),
+    (
+        "alternate-urgent-background",
+        &["background.secondary", "background"],
+    ),
+    ("alternate-urgent-foreground", &["error"]),
+    ("selected-urgent-background", &["error"]),
+    ("selected-urgent-foreground", &["background"]),
+    ("background-color", &[]),
+];
+
+pub struct RofiExporter;
+
+impl Exporter for RofiExporter {
+    fn name(&self) -> &'static str {
+        "rofi"
+    }
+
+    fn extension(&self) -> &'static str {
+        "rasi"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rofi color variables"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let roles = resolve_roles(theme);
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "/* {} for Rofi.\n   Generated by `cyberdeck export --target rofi`; do not edit. */\n\n* {{",
+            theme_name(theme)
+        );
+        for (variable, names) in VARIABLES {
+            // An empty role list is the transparent widget background Rofi's
+            // default theme expects.
+            let value = if names.is_empty() {
+                "transparent".to_owned()
+            } else if let Some(color) = names.iter().find_map(|name| roles.get(name)) {
+                color.to_string()
+            } else {
+                continue;
+            };
+            let _ = writeln!(out, "    {variable}: {value};");
+        }
+        out.push_str("}\n");
+        Ok(vec![Artifact::new(
+            format!("{}.rasi", theme_slug(theme)),
+            out,
+        )])
+    }
+}
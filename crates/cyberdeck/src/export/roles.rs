@@ -0,0 +1,39 @@
+//! `roles`: the resolved role → color map as JSON, mainly for checking what
+//! the other exporters will see.
+
+use anyhow::Result;
+use indexmap::IndexMap;
+
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{theme_slug, Artifact, Exporter};
+
+pub struct RolesExporter;
+
+impl Exporter for RolesExporter {
+    fn name(&self) -> &'static str {
+        "roles"
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn description(&self) -> &'static str {
+        "resolved palette roles as a JSON object"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let roles: IndexMap<&str, String> = resolve_roles(theme)
+            .into_iter()
+            .map(|(role, color)| (role, color.to_string()))
+            .collect();
+        let mut json = serde_json::to_string_pretty(&roles)?;
+        json.push('\n');
+        Ok(vec![Artifact::new(
+            format!("{}-roles.json", theme_slug(theme)),
+            json,
+        )])
+    }
+}
@@ -0,0 +1,52 @@
+//! `scss`: the palette roles as SCSS variables (`_<slug>.scss`, a partial
+//! to `@use`), named `$cyberdeck-<role>` with `-` for `.`, plus a
+//! `$cyberdeck-palette` map of the same colors keyed by role for loops.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter};
+
+pub struct ScssExporter;
+
+impl Exporter for ScssExporter {
+    fn name(&self) -> &'static str {
+        "scss"
+    }
+
+    fn extension(&self) -> &'static str {
+        "scss"
+    }
+
+    fn description(&self) -> &'static str {
+        "palette roles as SCSS variables and a map"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let roles = resolve_roles(theme);
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "// {} palette.\n// Generated by `cyberdeck export --target scss`; do not edit.\n",
+            theme_name(theme)
+        );
+        for (role, color) in &roles {
+            let _ = writeln!(out, "$cyberdeck-{}: {color};", role.replace('.', "-"));
+        }
+        out.push_str("\n$cyberdeck-palette: (\n");
+        for role in roles.keys() {
+            let name = role.replace('.', "-");
+            let _ = writeln!(out, "  \"{name}\": $cyberdeck-{name},");
+        }
+        out.push_str(");\n");
+        Ok(vec![Artifact::new(
+            format!("_{}.scss", theme_slug(theme)),
+            out,
+        )])
+    }
+}
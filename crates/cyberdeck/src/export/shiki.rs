@@ -0,0 +1,174 @@
+//! `shiki`: the theme as a flat JSON document (`<slug>.json`) that Shiki
+//! loads as-is, for static site generators that embed it.
+//!
+//! The source theme may be JSONC; the output is plain JSON with every color
+//! normalized to `#rrggbb[aa]`, every scope list split into single
+//! selectors, and the unscoped default rule Shiki's loader looks for first.
+//! The result is checked against the rules of vscode-textmate's theme
+//! parser, which Shiki uses, before it is written.
+
+use anyhow::{bail, Result};
+use serde_json::{json, Map, Value};
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{is_dark, theme_name, theme_slug, workbench_color, Artifact, Exporter};
+
+/// Font styles vscode-textmate understands.
+const FONT_STYLES: [&str; 4] = ["italic", "bold", "underline", "strikethrough"];
+
+pub struct ShikiExporter;
+
+impl Exporter for ShikiExporter {
+    fn name(&self) -> &'static str {
+        "shiki"
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn description(&self) -> &'static str {
+        "flat theme JSON for Shiki"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let document = flatten(theme);
+        validate(&document)?;
+        let mut text = serde_json::to_string_pretty(&document)?;
+        text.push('\n');
+        Ok(vec![Artifact::new(
+            format!("{}.json", theme_slug(theme)),
+            text,
+        )])
+    }
+}
+
+fn flatten(theme: &Theme) -> Value {
+    let color = |value: Option<&str>| value?.parse::<Color>().ok().map(|c| c.to_string());
+
+    let colors: Map<String, Value> = theme
+        .colors
+        .iter()
+        .filter_map(|(key, value)| Some((key.clone(), color(Some(value))?.into())))
+        .collect();
+    let foreground =
+        workbench_color(theme, &["editor.foreground", "foreground"]).map(|c| c.to_string());
+    let background = workbench_color(theme, &["editor.background"]).map(|c| c.to_string());
+
+    let mut defaults = Map::new();
+    if let Some(foreground) = &foreground {
+        defaults.insert("foreground".to_owned(), foreground.as_str().into());
+    }
+    if let Some(background) = &background {
+        defaults.insert("background".to_owned(), background.as_str().into());
+    }
+    let mut rules = vec![json!({ "settings": defaults })];
+    for rule in &theme.token_colors {
+        let selectors = rule.scope.selectors();
+        if selectors.is_empty() {
+            continue;
+        }
+        let mut settings = Map::new();
+        if let Some(foreground) = color(rule.settings.foreground.as_deref()) {
+            settings.insert("foreground".to_owned(), foreground.into());
+        }
+        if let Some(background) = color(rule.settings.background.as_deref()) {
+            settings.insert("background".to_owned(), background.into());
+        }
+        if let Some(font_style) = &rule.settings.font_style {
+            let styles: Vec<&str> = font_style
+                .split_whitespace()
+                .filter(|s| FONT_STYLES.contains(s))
+                .collect();
+            settings.insert("fontStyle".to_owned(), styles.join(" ").into());
+        }
+        if settings.is_empty() {
+            continue;
+        }
+        let mut entry = Map::new();
+        if let Some(name) = &rule.name {
+            entry.insert("name".to_owned(), name.as_str().into());
+        }
+        entry.insert("scope".to_owned(), selectors.into());
+        entry.insert("settings".to_owned(), settings.into());
+        rules.push(Value::Object(entry));
+    }
+
+    let mut document = Map::new();
+    document.insert("name".to_owned(), theme_slug(theme).into());
+    document.insert("displayName".to_owned(), theme_name(theme).into());
+    document.insert(
+        "type".to_owned(),
+        if is_dark(theme) { "dark" } else { "light" }.into(),
+    );
+    if let Some(foreground) = foreground {
+        document.insert("fg".to_owned(), foreground.into());
+    }
+    if let Some(background) = background {
+        document.insert("bg".to_owned(), background.into());
+    }
+    if let Some(enabled) = theme.semantic_highlighting {
+        document.insert("semanticHighlighting".to_owned(), enabled.into());
+    }
+    document.insert("colors".to_owned(), colors.into());
+    document.insert("tokenColors".to_owned(), rules.into());
+    if !theme.semantic_token_colors.is_empty() {
+        document.insert(
+            "semanticTokenColors".to_owned(),
+            serde_json::to_value(&theme.semantic_token_colors).unwrap_or_default(),
+        );
+    }
+    Value::Object(document)
+}
+
+/// Fails on anything Shiki would reject or silently ignore.
+fn validate(document: &Value) -> Result<()> {
+    let hex = |value: &Value| {
+        value.as_str().is_some_and(|text| {
+            let digits = text.strip_prefix('#').unwrap_or_default();
+            matches!(digits.len(), 3 | 4 | 6 | 8) && digits.chars().all(|c| c.is_ascii_hexdigit())
+        })
+    };
+    if document["name"].as_str().is_none_or(str::is_empty) {
+        bail!("shiki: the theme needs a name");
+    }
+    if !matches!(document["type"].as_str(), Some("dark" | "light")) {
+        bail!("shiki: `type` must be `dark` or `light`");
+    }
+    let rules = document["tokenColors"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    if rules.first().is_none_or(|rule| rule.get("scope").is_some()) {
+        bail!("shiki: `tokenColors` must start with the unscoped default rule");
+    }
+    for (index, rule) in rules.iter().enumerate() {
+        let Some(settings) = rule["settings"].as_object() else {
+            bail!("shiki: tokenColors[{index}] has no `settings` object");
+        };
+        if let Some(scope) = rule.get("scope") {
+            let selectors = scope.as_array().map(Vec::as_slice).unwrap_or_default();
+            if selectors.is_empty()
+                || !selectors
+                    .iter()
+                    .all(|s| s.as_str().is_some_and(|s| !s.contains(',')))
+            {
+                bail!("shiki: tokenColors[{index}].scope is not a list of single selectors");
+            }
+        }
+        for field in ["foreground", "background"] {
+            if settings.get(field).is_some_and(|value| !hex(value)) {
+                bail!("shiki: tokenColors[{index}].settings.{field} is not a hex color");
+            }
+        }
+    }
+    if let Some((key, _)) = document["colors"]
+        .as_object()
+        .and_then(|colors| colors.iter().find(|(_, value)| !hex(value)))
+    {
+        bail!("shiki: colors[\"{key}\"] is not a hex color");
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! `slack`: the custom sidebar theme string (`<slug>.txt`) to paste into
+//! Slack's Preferences → Themes → Custom theme.
+//!
+//! The string is Slack's eight comma-separated colors, in order: column
+//! background, menu hover background, active item, active item text, hover
+//! item, text, active presence and mention badge. It's left bare so it can
+//! be copied straight from the file; colors are composited over the side
+//! bar background.
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_slug, workbench_color, Artifact, Exporter};
+
+/// The workbench keys each position in the string reads, first present
+/// wins.
+const COLORS: &[&[&str]] = &[
+    &["sideBar.background", "editor.background"],
+    &["list.hoverBackground", "sideBar.background"],
+    &[
+        "list.activeSelectionBackground",
+        "editor.selectionBackground",
+    ],
+    &["list.activeSelectionForeground", "editor.foreground"],
+    &["list.hoverBackground", "list.inactiveSelectionBackground"],
+    &["sideBar.foreground", "editor.foreground"],
+    &[
+        "gitDecoration.addedResourceForeground",
+        "terminal.ansiGreen",
+    ],
+    &["activityBarBadge.background", "badge.background"],
+];
+
+pub struct SlackExporter;
+
+impl Exporter for SlackExporter {
+    fn name(&self) -> &'static str {
+        "slack"
+    }
+
+    fn extension(&self) -> &'static str {
+        "txt"
+    }
+
+    fn description(&self) -> &'static str {
+        "Slack sidebar theme string"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let background = workbench_color(theme, &["sideBar.background", "editor.background"])
+            .map_or(Color::rgb(0, 0, 0), Color::opaque);
+        let colors: Vec<String> = COLORS
+            .iter()
+            .map(|keys| {
+                workbench_color(theme, keys)
+                    .unwrap_or(background)
+                    .over(background)
+                    .to_hex_rgb()
+                    .to_uppercase()
+            })
+            .collect();
+        Ok(vec![Artifact::new(
+            format!("{}.txt", theme_slug(theme)),
+            format!("{}\n", colors.join(",")),
+        )])
+    }
+}
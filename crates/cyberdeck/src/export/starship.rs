@@ -0,0 +1,119 @@
+//! `starship`: a starship.toml snippet (`<slug>.toml`) defining
+//! `[palettes.cyberdeck]` from the palette roles and pointing common
+//! modules' styles at it, to merge into `~/.config/starship.toml`.
+//!
+//! Role names become palette colors with `_` for `.`
+//! (`accent.primary` → `accent_primary`); translucent roles are composited
+//! over the background role.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter};
+
+const PALETTE: &str = "cyberdeck";
+
+/// Module options: module, option, role, style attributes, and the symbol
+/// for options that are a `[symbol](style)` format string (empty for plain
+/// style options).
+const MODULES: &[(&str, &str, &str, &str, &str)] = &[
+    ("character", "success_symbol", "success", "bold", "❯"),
+    ("character", "error_symbol", "error", "bold", "❯"),
+    ("character", "vimcmd_symbol", "accent.primary", "bold", "❮"),
+    ("directory", "style", "type", "bold", ""),
+    ("directory", "read_only_style", "error", "", ""),
+    ("git_branch", "style", "accent.primary", "bold", ""),
+    ("git_commit", "style", "accent.secondary", "", ""),
+    ("git_state", "style", "warning", "bold", ""),
+    ("git_status", "style", "modified", "bold", ""),
+    ("git_metrics", "added_style", "inserted", "bold", ""),
+    ("git_metrics", "deleted_style", "deleted", "bold", ""),
+    ("cmd_duration", "style", "warning", "", ""),
+    ("username", "style_user", "function", "bold", ""),
+    ("username", "style_root", "error", "bold", ""),
+    ("hostname", "style", "accent.secondary", "bold", ""),
+    ("jobs", "style", "storage", "bold", ""),
+    ("status", "style", "error", "bold", ""),
+    ("time", "style", "comment", "", ""),
+    ("package", "style", "number", "bold", ""),
+    ("rust", "style", "storage", "bold", ""),
+    ("nodejs", "style", "function", "bold", ""),
+    ("python", "style", "string", "bold", ""),
+    ("golang", "style", "type", "bold", ""),
+];
+
+pub struct StarshipExporter;
+
+impl Exporter for StarshipExporter {
+    fn name(&self) -> &'static str {
+        "starship"
+    }
+
+    fn extension(&self) -> &'static str {
+        "toml"
+    }
+
+    fn description(&self) -> &'static str {
+        "starship palette and module styles"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let roles = resolve_roles(theme);
+        let background = roles
+            .get("background")
+            .map_or(Color::rgb(0, 0, 0), |c| c.opaque());
+
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "# {} for starship; merge into ~/.config/starship.toml.\n\
+             # Generated by `cyberdeck export --target starship`; do not edit.\n\
+             palette = \"{PALETTE}\"\n\n\
+             [palettes.{PALETTE}]\n",
+            theme_name(theme)
+        );
+        for (role, color) in &roles {
+            let _ = writeln!(
+                out,
+                "{} = \"{}\"",
+                palette_name(role),
+                color.over(background).to_hex_rgb()
+            );
+        }
+
+        let mut module = "";
+        for (name, option, role, attrs, symbol) in MODULES {
+            if !roles.contains_key(role) {
+                continue;
+            }
+            if module != *name {
+                module = name;
+                let _ = writeln!(out, "\n[{name}]");
+            }
+            let style = if attrs.is_empty() {
+                palette_name(role)
+            } else {
+                format!("{attrs} {}", palette_name(role))
+            };
+            let value = if symbol.is_empty() {
+                style
+            } else {
+                format!("[{symbol}]({style})")
+            };
+            let _ = writeln!(out, "{option} = {}", toml::Value::from(value));
+        }
+        Ok(vec![Artifact::new(
+            format!("{}.toml", theme_slug(theme)),
+            out,
+        )])
+    }
+}
+
+fn palette_name(role: &str) -> String {
+    role.replace('.', "_")
+}
@@ -0,0 +1,195 @@
+//! `sublime`: a Sublime Text `.sublime-color-scheme`.
+//!
+//! Sublime uses the same TextMate scope selectors, so every `tokenColors`
+//! rule carries over. Where Sublime's default syntaxes name a construct
+//! differently from VS Code's grammars, the translated selector is added
+//! next to the original.
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+use crate::color::Color;
+use crate::scope::scope_prefix;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter};
+
+/// Sublime `globals` and the workbench keys they are read from.
+const GLOBALS: &[(&str, &[&str])] = &[
+    ("background", &["editor.background"]),
+    ("foreground", &["editor.foreground", "foreground"]),
+    ("caret", &["editorCursor.foreground"]),
+    ("line_highlight", &["editor.lineHighlightBackground"]),
+    ("selection", &["editor.selectionBackground"]),
+    ("selection_border", &["editor.selectionHighlightBorder"]),
+    (
+        "inactive_selection",
+        &["editor.inactiveSelectionBackground"],
+    ),
+    ("gutter", &["editorGutter.background", "editor.background"]),
+    ("gutter_foreground", &["editorLineNumber.foreground"]),
+    (
+        "gutter_foreground_highlight",
+        &["editorLineNumber.activeForeground"],
+    ),
+    ("find_highlight", &["editor.findMatchBackground"]),
+    ("highlight", &["editor.findMatchBorder", "focusBorder"]),
+    ("invisibles", &["editorWhitespace.foreground"]),
+    (
+        "guide",
+        &[
+            "editorIndentGuide.background1",
+            "editorIndentGuide.background",
+        ],
+    ),
+    (
+        "active_guide",
+        &[
+            "editorIndentGuide.activeBackground1",
+            "editorIndentGuide.activeBackground",
+        ],
+    ),
+    ("brackets_foreground", &["editorBracketMatch.border"]),
+    ("accent", &["focusBorder"]),
+    ("line_diff_added", &["editorGutter.addedBackground"]),
+    ("line_diff_modified", &["editorGutter.modifiedBackground"]),
+    ("line_diff_deleted", &["editorGutter.deletedBackground"]),
+];
+
+/// VS Code scope prefixes and the prefix Sublime's syntaxes use instead.
+const SCOPE_TRANSLATIONS: &[(&str, &str)] = &[
+    ("meta.function-call", "variable.function"),
+    ("entity.name.function.macro", "support.macro"),
+    ("entity.name.function.decorator", "variable.annotation"),
+    ("entity.name.type.class", "entity.name.class"),
+    ("storage.type.function", "keyword.declaration.function"),
+    ("storage.type.class", "keyword.declaration.class"),
+    ("storage.type.struct", "keyword.declaration.struct"),
+    ("storage.type.enum", "keyword.declaration.enum"),
+    ("storage.type.interface", "keyword.declaration.interface"),
+    ("storage.type.trait", "keyword.declaration.trait"),
+    ("storage.type.type", "keyword.declaration.type"),
+    ("variable.other.property", "variable.other.member"),
+    ("variable.other.object.property", "variable.other.member"),
+    ("support.type.property-name", "meta.mapping.key string"),
+    ("meta.object-literal.key", "meta.mapping.key"),
+    ("meta.decorator", "meta.annotation"),
+    ("punctuation.decorator", "punctuation.definition.annotation"),
+    (
+        "punctuation.definition.template-expression",
+        "punctuation.section.interpolation",
+    ),
+    ("markup.inline.raw", "markup.raw.inline"),
+    ("markup.fenced_code.block", "markup.raw.block"),
+];
+
+/// Font styles Sublime understands; others (strikethrough) are dropped.
+pub(super) const FONT_STYLES: [&str; 3] = ["bold", "italic", "underline"];
+
+pub struct SublimeExporter;
+
+impl Exporter for SublimeExporter {
+    fn name(&self) -> &'static str {
+        "sublime"
+    }
+
+    fn extension(&self) -> &'static str {
+        "sublime-color-scheme"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sublime Text color scheme"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let mut globals = Map::new();
+        for (key, sources) in GLOBALS {
+            if let Some(color) = workbench_color(theme, sources) {
+                globals.insert((*key).to_owned(), color.to_string().into());
+            }
+        }
+
+        let mut rules = Vec::new();
+        for rule in &theme.token_colors {
+            let selectors = rule.scope.selectors();
+            if selectors.is_empty() {
+                continue;
+            }
+            let scopes = with_translations(&selectors);
+
+            let mut entry = Map::new();
+            if let Some(name) = &rule.name {
+                entry.insert("name".to_owned(), name.as_str().into());
+            }
+            entry.insert("scope".to_owned(), scopes.join(", ").into());
+            let color = |value: &Option<String>| value.as_deref()?.parse::<Color>().ok();
+            if let Some(foreground) = color(&rule.settings.foreground) {
+                entry.insert("foreground".to_owned(), foreground.to_string().into());
+            }
+            if let Some(background) = color(&rule.settings.background) {
+                entry.insert("background".to_owned(), background.to_string().into());
+            }
+            if let Some(font_style) = &rule.settings.font_style {
+                let styles: Vec<&str> = font_style
+                    .split_whitespace()
+                    .filter(|s| FONT_STYLES.contains(s))
+                    .collect();
+                entry.insert("font_style".to_owned(), styles.join(" ").into());
+            }
+            if ["foreground", "background", "font_style"]
+                .iter()
+                .any(|key| entry.contains_key(*key))
+            {
+                rules.push(Value::Object(entry));
+            }
+        }
+
+        let scheme = json!({
+            "name": theme_name(theme),
+            "globals": globals,
+            "rules": rules,
+        });
+        let mut text = serde_json::to_string_pretty(&scheme)?;
+        text.push('\n');
+        Ok(vec![Artifact::new(
+            format!("{}.sublime-color-scheme", theme_slug(theme)),
+            text,
+        )])
+    }
+}
+
+/// `selectors`, each followed by its Sublime translation where it has one.
+pub(super) fn with_translations(selectors: &[&str]) -> Vec<String> {
+    let mut scopes: Vec<String> = Vec::new();
+    for selector in selectors {
+        scopes.push((*selector).to_owned());
+        if let Some(translated) = translate(selector) {
+            if !scopes.contains(&translated) {
+                scopes.push(translated);
+            }
+        }
+    }
+    scopes
+}
+
+/// `selector` with every VS Code-specific scope replaced by Sublime's, or
+/// `None` if nothing needed translating.
+fn translate(selector: &str) -> Option<String> {
+    let mut changed = false;
+    let parts: Vec<String> = selector
+        .split_whitespace()
+        .map(|part| {
+            let found = SCOPE_TRANSLATIONS
+                .iter()
+                .find(|(vscode, _)| scope_prefix(vscode, part));
+            match found {
+                Some((vscode, sublime)) => {
+                    changed = true;
+                    format!("{sublime}{}", &part[vscode.len()..])
+                }
+                None => part.to_owned(),
+            }
+        })
+        .collect();
+    changed.then(|| parts.join(" "))
+}
@@ -0,0 +1,112 @@
+//! `sway`: window border colors (`<slug>.conf`) for sway or i3, which share
+//! the `client.*` syntax. Include it from the window manager's config.
+//!
+//! Colors come from the palette roles, composited over the background role
+//! since i3 has no alpha.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter};
+
+/// `client.*` classes and the roles for their border, background, text,
+/// indicator and child border columns.
+const CLIENTS: &[(&str, [&str; 5])] = &[
+    (
+        "focused",
+        [
+            "accent.primary",
+            "background.secondary",
+            "foreground",
+            "accent.secondary",
+            "accent.primary",
+        ],
+    ),
+    (
+        "focused_inactive",
+        [
+            "border",
+            "background.secondary",
+            "foreground.secondary",
+            "border",
+            "border",
+        ],
+    ),
+    (
+        "unfocused",
+        [
+            "border",
+            "background",
+            "foreground.muted",
+            "border",
+            "border",
+        ],
+    ),
+    ("urgent", ["error", "error", "background", "error", "error"]),
+    (
+        "placeholder",
+        [
+            "background",
+            "background",
+            "foreground",
+            "background",
+            "background",
+        ],
+    ),
+];
+
+pub struct SwayExporter;
+
+impl Exporter for SwayExporter {
+    fn name(&self) -> &'static str {
+        "sway"
+    }
+
+    fn extension(&self) -> &'static str {
+        "conf"
+    }
+
+    fn description(&self) -> &'static str {
+        "sway/i3 window border colors"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let roles = resolve_roles(theme);
+        let background = roles
+            .get("background")
+            .map_or(Color::rgb(0, 0, 0), |c| c.opaque());
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# {} for sway and i3; include from the window manager's config.\n\
+             # Generated by `cyberdeck export --target sway`; do not edit.\n\n\
+             # class                 border  backgr. text    indic.  child_border",
+            theme_name(theme)
+        );
+        for (class, columns) in CLIENTS {
+            let colors: Option<Vec<String>> = columns
+                .iter()
+                .map(|role| roles.get(role).map(|c| c.over(background).to_hex_rgb()))
+                .collect();
+            if let Some(colors) = colors {
+                let _ = writeln!(
+                    out,
+                    "{:<23} {}",
+                    format!("client.{class}"),
+                    colors.join(" ")
+                );
+            }
+        }
+        let _ = writeln!(out, "client.background       {}", background.to_hex_rgb());
+        Ok(vec![Artifact::new(
+            format!("{}.conf", theme_slug(theme)),
+            out,
+        )])
+    }
+}
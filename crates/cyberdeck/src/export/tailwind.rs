@@ -0,0 +1,69 @@
+//! `tailwind`: a Tailwind CSS preset (`<slug>.preset.js`) adding the
+//! palette roles under `colors.cyberdeck`, for a config's `presets` list.
+//!
+//! Dotted roles nest, with the bare role as `DEFAULT`, so `background` and
+//! `background.secondary` become `bg-cyberdeck-background` and
+//! `bg-cyberdeck-background-secondary`.
+
+use anyhow::Result;
+use serde_json::{Map, Value};
+
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter};
+
+pub struct TailwindExporter;
+
+impl Exporter for TailwindExporter {
+    fn name(&self) -> &'static str {
+        "tailwind"
+    }
+
+    fn extension(&self) -> &'static str {
+        "js"
+    }
+
+    fn description(&self) -> &'static str {
+        "Tailwind CSS color preset"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let mut colors = Map::new();
+        for (role, color) in resolve_roles(theme) {
+            let (group, shade) = role.split_once('.').unwrap_or((role, "DEFAULT"));
+            let entry = colors
+                .entry(group)
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(shades) = entry {
+                shades.insert(shade.to_owned(), Value::from(color.to_string()));
+            }
+        }
+        // A role without variants is a plain color rather than a one-entry
+        // object.
+        for value in colors.values_mut() {
+            if let Value::Object(shades) = value {
+                if shades.len() == 1 {
+                    if let Some(color) = shades.remove("DEFAULT") {
+                        *value = color;
+                    }
+                }
+            }
+        }
+        let preset = serde_json::json!({
+            "theme": { "extend": { "colors": { "cyberdeck": colors } } }
+        });
+
+        let out = format!(
+            "// {} palette as a Tailwind CSS preset; add it to `presets` in tailwind.config.js.\n\
+             // Generated by `cyberdeck export --target tailwind`; do not edit.\n\
+             module.exports = {};\n",
+            theme_name(theme),
+            serde_json::to_string_pretty(&preset)?
+        );
+        Ok(vec![Artifact::new(
+            format!("{}.preset.js", theme_slug(theme)),
+            out,
+        )])
+    }
+}
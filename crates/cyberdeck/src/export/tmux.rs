@@ -0,0 +1,159 @@
+//! `tmux`: a tmux snippet (`<slug>.tmux.conf`, to `source-file` from
+//! `.tmux.conf`) styling the status bar, pane borders, messages and copy
+//! mode after the workbench's status bar and selection colors.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter};
+
+/// Style options, the workbench keys their foreground and background read,
+/// and extra attributes (empty for none).
+const STYLES: &[(&str, &[&str], &[&str], &str)] = &[
+    (
+        "status-style",
+        &["statusBar.foreground"],
+        &["statusBar.background"],
+        "",
+    ),
+    (
+        "window-status-style",
+        &["statusBar.foreground"],
+        &["statusBar.background"],
+        "",
+    ),
+    (
+        "window-status-current-style",
+        &["statusBarItem.prominentForeground", "statusBar.foreground"],
+        &[
+            "statusBarItem.prominentBackground",
+            "statusBarItem.remoteBackground",
+        ],
+        "bold",
+    ),
+    (
+        "window-status-activity-style",
+        &["editorWarning.foreground"],
+        &["statusBar.background"],
+        "",
+    ),
+    (
+        "window-status-bell-style",
+        &["editorError.foreground", "errorForeground"],
+        &["statusBar.background"],
+        "bold",
+    ),
+    (
+        "pane-border-style",
+        &["editorGroup.border", "panel.border"],
+        &[],
+        "",
+    ),
+    (
+        "pane-active-border-style",
+        &["activityBar.activeBorder", "focusBorder"],
+        &[],
+        "",
+    ),
+    (
+        "message-style",
+        &["editorWidget.foreground", "foreground"],
+        &["editorWidget.background"],
+        "",
+    ),
+    (
+        "message-command-style",
+        &["input.foreground", "foreground"],
+        &["input.background"],
+        "",
+    ),
+    (
+        "mode-style",
+        &["editor.selectionForeground"],
+        &["editor.selectionBackground"],
+        "",
+    ),
+    (
+        "copy-mode-match-style",
+        &[],
+        &["editor.findMatchHighlightBackground"],
+        "",
+    ),
+    (
+        "copy-mode-current-match-style",
+        &[],
+        &["editor.findMatchBackground"],
+        "",
+    ),
+];
+
+/// Single-color options and the workbench keys they read.
+const COLOURS: &[(&str, &[&str])] = &[
+    (
+        "display-panes-colour",
+        &["editorGroup.border", "panel.border"],
+    ),
+    (
+        "display-panes-active-colour",
+        &["activityBar.activeBorder", "focusBorder"],
+    ),
+    (
+        "clock-mode-colour",
+        &["activityBar.activeBorder", "statusBar.foreground"],
+    ),
+];
+
+pub struct TmuxExporter;
+
+impl Exporter for TmuxExporter {
+    fn name(&self) -> &'static str {
+        "tmux"
+    }
+
+    fn extension(&self) -> &'static str {
+        "conf"
+    }
+
+    fn description(&self) -> &'static str {
+        "tmux status bar, pane border and copy-mode styles"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let background =
+            workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+        let color = |keys: &[&str]| workbench_color(theme, keys).map(|c| c.over(background));
+
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}", theme_name(theme));
+        out.push_str("# Generated by `cyberdeck export --target tmux`; do not edit.\n\n");
+        for (option, foreground, bg, attrs) in STYLES {
+            let mut style = Vec::new();
+            if let Some(color) = color(foreground) {
+                style.push(format!("fg={}", color.to_hex_rgb()));
+            }
+            if let Some(color) = color(bg) {
+                style.push(format!("bg={}", color.to_hex_rgb()));
+            }
+            if style.is_empty() {
+                continue;
+            }
+            if !attrs.is_empty() {
+                style.push((*attrs).to_owned());
+            }
+            let _ = writeln!(out, "set -g {option} \"{}\"", style.join(","));
+        }
+        for (option, keys) in COLOURS {
+            if let Some(color) = color(keys) {
+                let _ = writeln!(out, "set -g {option} \"{}\"", color.to_hex_rgb());
+            }
+        }
+        Ok(vec![Artifact::new(
+            format!("{}.tmux.conf", theme_slug(theme)),
+            out,
+        )])
+    }
+}
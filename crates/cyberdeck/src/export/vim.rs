@@ -0,0 +1,278 @@
+//! `vim`: a classic Vim colorscheme (`colors/<name>.vim`).
+//!
+//! Every group gets both `gui*` colors, used with `termguicolors` and in
+//! GVim, and `cterm*` approximations from the xterm 256-color cube. Vim has
+//! no translucency, so colors are composited over the editor background.
+//! Groups the theme does not style are left out and keep Vim's defaults.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{
+    ansi_colors, is_dark, theme_name, theme_slug, workbench_color, Artifact, Exporter, TokenStyle,
+};
+
+/// Standard syntax groups and the scope path each is resolved from.
+pub(super) const SYNTAX_GROUPS: &[(&str, &str)] = &[
+    ("Comment", "comment.line"),
+    ("Constant", "constant.language"),
+    ("String", "string.quoted.double"),
+    ("Character", "constant.character"),
+    ("Number", "constant.numeric"),
+    ("Boolean", "constant.language.boolean"),
+    ("Float", "constant.numeric.float"),
+    ("Identifier", "variable.other"),
+    ("Function", "entity.name.function"),
+    ("Statement", "keyword.control"),
+    ("Conditional", "keyword.control.conditional"),
+    ("Repeat", "keyword.control.loop"),
+    ("Label", "entity.name.label"),
+    ("Operator", "keyword.operator"),
+    ("Keyword", "keyword"),
+    ("Exception", "keyword.control.exception"),
+    ("PreProc", "meta.preprocessor"),
+    ("Include", "keyword.control.import"),
+    ("Define", "keyword.control.directive.define"),
+    ("Macro", "entity.name.function.macro"),
+    ("PreCondit", "keyword.control.directive.conditional"),
+    ("Type", "entity.name.type"),
+    ("StorageClass", "storage.modifier"),
+    ("Structure", "storage.type.struct"),
+    ("Typedef", "storage.type"),
+    ("Special", "support.function"),
+    ("SpecialChar", "constant.character.escape"),
+    ("Tag", "entity.name.tag"),
+    ("Delimiter", "punctuation.separator"),
+    ("SpecialComment", "comment.block.documentation"),
+    ("Underlined", "markup.underline.link"),
+    ("Error", "invalid.illegal"),
+    ("Todo", "comment.line keyword.todo"),
+    ("Title", "markup.heading"),
+];
+
+/// Editor UI groups and the workbench keys their foreground and background
+/// read.
+pub(super) const UI_GROUPS: &[(&str, &[&str], &[&str])] = &[
+    ("CursorLine", &[], &["editor.lineHighlightBackground"]),
+    ("CursorColumn", &[], &["editor.lineHighlightBackground"]),
+    ("ColorColumn", &[], &["editorRuler.foreground"]),
+    (
+        "LineNr",
+        &["editorLineNumber.foreground"],
+        &["editorGutter.background"],
+    ),
+    (
+        "CursorLineNr",
+        &["editorLineNumber.activeForeground"],
+        &["editor.lineHighlightBackground"],
+    ),
+    ("SignColumn", &[], &["editorGutter.background"]),
+    ("FoldColumn", &["editorLineNumber.foreground"], &[]),
+    ("Folded", &[], &["editor.foldBackground"]),
+    ("Visual", &[], &["editor.selectionBackground"]),
+    ("Search", &[], &["editor.findMatchHighlightBackground"]),
+    ("IncSearch", &[], &["editor.findMatchBackground"]),
+    (
+        "MatchParen",
+        &["editorBracketMatch.border"],
+        &["editorBracketMatch.background"],
+    ),
+    (
+        "NonText",
+        &["editorWhitespace.foreground", "editorLineNumber.foreground"],
+        &[],
+    ),
+    ("SpecialKey", &["editorWhitespace.foreground"], &[]),
+    (
+        "Pmenu",
+        &["editorSuggestWidget.foreground"],
+        &["editorSuggestWidget.background"],
+    ),
+    (
+        "PmenuSel",
+        &["editorSuggestWidget.selectedForeground"],
+        &["editorSuggestWidget.selectedBackground"],
+    ),
+    ("PmenuSbar", &[], &["scrollbarSlider.background"]),
+    ("PmenuThumb", &[], &["scrollbarSlider.activeBackground"]),
+    (
+        "StatusLine",
+        &["statusBar.foreground"],
+        &["statusBar.background"],
+    ),
+    (
+        "StatusLineNC",
+        &["statusBar.foreground"],
+        &["statusBar.noFolderBackground", "statusBar.background"],
+    ),
+    ("VertSplit", &["editorGroup.border"], &["editor.background"]),
+    (
+        "TabLine",
+        &["tab.inactiveForeground"],
+        &["tab.inactiveBackground"],
+    ),
+    (
+        "TabLineSel",
+        &["tab.activeForeground"],
+        &["tab.activeBackground"],
+    ),
+    ("TabLineFill", &[], &["editorGroupHeader.tabsBackground"]),
+    (
+        "WildMenu",
+        &["list.activeSelectionForeground"],
+        &["list.activeSelectionBackground"],
+    ),
+    ("Directory", &["textLink.foreground"], &[]),
+    (
+        "ErrorMsg",
+        &["editorError.foreground", "errorForeground"],
+        &[],
+    ),
+    (
+        "WarningMsg",
+        &["editorWarning.foreground", "warningForeground"],
+        &[],
+    ),
+    ("ModeMsg", &["statusBar.foreground"], &[]),
+    ("MoreMsg", &["textLink.foreground"], &[]),
+    ("Question", &["textLink.foreground"], &[]),
+    (
+        "DiffAdd",
+        &[],
+        &[
+            "diffEditor.insertedLineBackground",
+            "diffEditor.insertedTextBackground",
+        ],
+    ),
+    (
+        "DiffDelete",
+        &[],
+        &[
+            "diffEditor.removedLineBackground",
+            "diffEditor.removedTextBackground",
+        ],
+    ),
+    ("DiffText", &[], &["diffEditor.insertedTextBackground"]),
+];
+
+pub struct VimExporter;
+
+impl Exporter for VimExporter {
+    fn name(&self) -> &'static str {
+        "vim"
+    }
+
+    fn extension(&self) -> &'static str {
+        "vim"
+    }
+
+    fn description(&self) -> &'static str {
+        "Vim colorscheme with 256-color fallbacks"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let name = colors_name(theme);
+        Ok(vec![Artifact::new(
+            format!("colors/{name}.vim"),
+            colorscheme(theme, &name),
+        )])
+    }
+}
+
+/// The `g:colors_name`, which is also what `:colorscheme` takes: the slug
+/// without dashes, e.g. `cyberdeck2025`.
+pub(super) fn colors_name(theme: &Theme) -> String {
+    theme_slug(theme).replace('-', "")
+}
+
+fn colorscheme(theme: &Theme, name: &str) -> String {
+    let background = workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+    let resolver = Resolver::new(theme);
+    let defaults = resolver.defaults();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "\" {}", theme_name(theme));
+    out.push_str("\" Generated by `cyberdeck export --target vim`; do not edit.\n\n");
+    out.push_str("hi clear\nif exists('syntax_on')\n  syntax reset\nendif\n");
+    let _ = writeln!(
+        out,
+        "set background={}\nlet g:colors_name = '{name}'\n",
+        if is_dark(theme) { "dark" } else { "light" }
+    );
+
+    let normal = TokenStyle {
+        foreground: defaults.foreground,
+        background: Some(background),
+        ..TokenStyle::default()
+    };
+    write_group(&mut out, "Normal", &normal, background);
+    for (group, scope) in SYNTAX_GROUPS {
+        if let Some(style) = TokenStyle::resolve(&resolver, scope) {
+            write_group(&mut out, group, &style, background);
+        }
+    }
+    for (group, foreground, bg) in UI_GROUPS {
+        let style = TokenStyle {
+            foreground: workbench_color(theme, foreground),
+            background: workbench_color(theme, bg),
+            ..TokenStyle::default()
+        };
+        if style.foreground.is_some() || style.background.is_some() {
+            write_group(&mut out, group, &style, background);
+        }
+    }
+
+    if let Some(ansi) = ansi_colors(theme) {
+        let colors: Vec<String> = ansi
+            .iter()
+            .map(|c| format!("'{}'", c.over(background).to_hex_rgb()))
+            .collect();
+        let _ = writeln!(
+            out,
+            "\nlet g:terminal_ansi_colors = [{}]",
+            colors.join(", ")
+        );
+    }
+    out
+}
+
+/// Writes one `:highlight` command. Unset attributes are `NONE` so that
+/// switching from another colorscheme leaves nothing behind.
+fn write_group(out: &mut String, group: &str, style: &TokenStyle, background: Color) {
+    let color = |color: Option<Color>| color.map(|c| c.over(background));
+    let (foreground, bg) = (color(style.foreground), color(style.background));
+    let gui = |c: Option<Color>| c.map_or_else(|| "NONE".to_owned(), Color::to_hex_rgb);
+    let cterm =
+        |c: Option<Color>| c.map_or_else(|| "NONE".to_owned(), |c| c.to_xterm256().to_string());
+
+    let mut attrs = Vec::new();
+    for (set, attr) in [
+        (style.bold, "bold"),
+        (style.italic, "italic"),
+        (style.underline, "underline"),
+        (style.strikethrough, "strikethrough"),
+    ] {
+        if set {
+            attrs.push(attr);
+        }
+    }
+    let attrs = if attrs.is_empty() {
+        "NONE".to_owned()
+    } else {
+        attrs.join(",")
+    };
+
+    let _ = writeln!(
+        out,
+        "hi {group} guifg={} guibg={} gui={attrs} ctermfg={} ctermbg={} cterm={attrs}",
+        gui(foreground),
+        gui(bg),
+        cterm(foreground),
+        cterm(bg),
+    );
+}
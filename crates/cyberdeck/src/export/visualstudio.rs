@@ -0,0 +1,234 @@
+//! `visualstudio`: Visual Studio editor colors as a settings file
+//! (`<slug>.vssettings`, for Tools > Import and Export Settings).
+//!
+//! Only the Text Editor category of Fonts and Colors is written, so
+//! importing the file leaves the rest of the environment alone. Visual
+//! Studio stores colors as `0x00BBGGRR` with no translucency; colors are
+//! composited over the editor background, and items the theme does not
+//! color are left at Visual Studio's automatic value.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, xml_escape, Artifact, Exporter, TokenStyle};
+
+/// The Text Editor category of Fonts and Colors.
+const TEXT_EDITOR_GUID: &str = "{A27B4E24-A735-4D1D-B8E7-9716E1E3D8E0}";
+
+/// Visual Studio's "Automatic" color.
+const AUTOMATIC: &str = "0x02000000";
+
+/// Display items and the scope path each is resolved from.
+const TOKEN_ITEMS: &[(&str, &str)] = &[
+    ("Comment", "comment.line"),
+    ("XML Doc Comment", "comment.block.documentation"),
+    (
+        "XML Doc Tag",
+        "comment.block.documentation storage.type.class",
+    ),
+    ("Keyword", "keyword.control"),
+    ("Preprocessor Keyword", "meta.preprocessor"),
+    ("Operator", "keyword.operator"),
+    ("String", "string.quoted.double"),
+    ("String - Verbatim", "string.quoted.other"),
+    ("string - escape character", "constant.character.escape"),
+    ("Number", "constant.numeric"),
+    ("Identifier", "variable.other"),
+    ("class name", "entity.name.type.class"),
+    ("interface name", "entity.name.type.interface"),
+    ("enum name", "entity.name.type.enum"),
+    ("struct name", "entity.name.type.struct"),
+    ("type parameter name", "entity.name.type.parameter"),
+    ("namespace name", "entity.name.namespace"),
+    ("method name", "entity.name.function"),
+    ("extension method name", "entity.name.function"),
+    ("field name", "variable.other.property"),
+    ("property name", "variable.other.property"),
+    ("local name", "variable.other.readwrite"),
+    ("parameter name", "variable.parameter"),
+    ("constant name", "variable.other.constant"),
+    ("enum member name", "variable.other.enummember"),
+    ("label name", "entity.name.label"),
+    ("HTML Element Name", "entity.name.tag"),
+    ("HTML Attribute Name", "entity.other.attribute-name"),
+    ("XML Name", "entity.name.tag"),
+    ("XML Attribute", "entity.other.attribute-name"),
+    ("CSS Property Name", "support.type.property-name.css"),
+    ("CSS Selector", "entity.other.attribute-name.class.css"),
+];
+
+/// Display items and the workbench keys their foreground and background
+/// read.
+const UI_ITEMS: &[(&str, &[&str], &[&str])] = &[
+    (
+        "Selected Text",
+        &["editor.selectionForeground"],
+        &["editor.selectionBackground"],
+    ),
+    (
+        "Inactive Selected Text",
+        &[],
+        &[
+            "editor.inactiveSelectionBackground",
+            "editor.selectionBackground",
+        ],
+    ),
+    (
+        "Line Number",
+        &["editorLineNumber.foreground"],
+        &["editorGutter.background", "editor.background"],
+    ),
+    (
+        "Indicator Margin",
+        &[],
+        &["editorGutter.background", "editor.background"],
+    ),
+    ("Visible White Space", &["editorWhitespace.foreground"], &[]),
+    (
+        "Brace Matching (Rectangle)",
+        &["editorBracketMatch.border"],
+        &["editorBracketMatch.background"],
+    ),
+    (
+        "Highlighted Reference",
+        &[],
+        &["editor.wordHighlightBackground"],
+    ),
+    (
+        "Highlighted Written Reference",
+        &[],
+        &["editor.wordHighlightStrongBackground"],
+    ),
+    ("Find Match Highlight", &[], &["editor.findMatchBackground"]),
+    (
+        "Syntax Error",
+        &["editorError.foreground", "errorForeground"],
+        &[],
+    ),
+    (
+        "Compiler Error",
+        &["editorError.foreground", "errorForeground"],
+        &[],
+    ),
+    ("Warning", &["editorWarning.foreground"], &[]),
+    ("Suggestion", &["editorInfo.foreground"], &[]),
+    ("urlformat", &["textLink.foreground"], &[]),
+    ("Excluded Code", &["descriptionForeground"], &[]),
+    (
+        "Track Changes after save",
+        &["editorGutter.addedBackground"],
+        &[],
+    ),
+    (
+        "Track Changes before save",
+        &["editorGutter.modifiedBackground"],
+        &[],
+    ),
+];
+
+pub struct VisualStudioExporter;
+
+impl Exporter for VisualStudioExporter {
+    fn name(&self) -> &'static str {
+        "visualstudio"
+    }
+
+    fn extension(&self) -> &'static str {
+        "vssettings"
+    }
+
+    fn description(&self) -> &'static str {
+        "Visual Studio editor colors as an importable settings file"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        Ok(vec![Artifact::new(
+            format!("{}.vssettings", theme_slug(theme)),
+            settings(theme),
+        )])
+    }
+}
+
+fn settings(theme: &Theme) -> String {
+    let background = workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+    let resolver = Resolver::new(theme);
+
+    let mut items = Vec::new();
+    items.push((
+        "Plain Text",
+        TokenStyle {
+            foreground: resolver.defaults().foreground,
+            background: Some(background),
+            ..TokenStyle::default()
+        },
+    ));
+    for (item, scope) in TOKEN_ITEMS {
+        if let Some(style) = TokenStyle::resolve(&resolver, scope) {
+            items.push((item, style));
+        }
+    }
+    for (item, foreground, bg) in UI_ITEMS {
+        let style = TokenStyle {
+            foreground: workbench_color(theme, foreground),
+            background: workbench_color(theme, bg),
+            ..TokenStyle::default()
+        };
+        if style.foreground.is_some() || style.background.is_some() {
+            items.push((item, style));
+        }
+    }
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <!-- {} (generated by `cyberdeck export`; do not edit) -->\n\
+         <UserSettings>\n  \
+         <ApplicationIdentity version=\"17.0\"/>\n  \
+         <ToolsOptions/>\n  \
+         <Category name=\"Environment_Group\" RegisteredName=\"Environment_Group\">\n    \
+         <Category name=\"Environment_FontsAndColors\" \
+         Category=\"{{1EDA5DD4-927A-43a7-810E-7FD247D0DA1D}}\" \
+         Package=\"{{DA9FB551-C724-11d0-AE1F-00A0C90FFFC3}}\" \
+         RegisteredName=\"Environment_FontsAndColors\" \
+         PackageName=\"Visual Studio Environment Package\">\n      \
+         <PropertyValue name=\"Version\">2</PropertyValue>\n      \
+         <FontsAndColors Version=\"2.0\">\n        \
+         <Categories>\n          \
+         <Category GUID=\"{TEXT_EDITOR_GUID}\" FontIsDefault=\"Yes\">\n            \
+         <Items>\n",
+        xml_escape(theme_name(theme))
+    );
+    let color =
+        |c: Option<Color>| c.map_or_else(|| AUTOMATIC.to_owned(), |c| bgr(c.over(background)));
+    for (item, style) in &items {
+        let _ = writeln!(
+            out,
+            "              <Item Name=\"{}\" Foreground=\"{}\" Background=\"{}\" BoldFont=\"{}\"/>",
+            xml_escape(item),
+            color(style.foreground),
+            color(style.background),
+            if style.bold { "Yes" } else { "No" }
+        );
+    }
+    out.push_str(
+        "            </Items>\n          \
+         </Category>\n        \
+         </Categories>\n      \
+         </FontsAndColors>\n    \
+         </Category>\n  \
+         </Category>\n\
+         </UserSettings>\n",
+    );
+    out
+}
+
+/// Visual Studio's `0x00BBGGRR` notation.
+fn bgr(color: Color) -> String {
+    format!("0x00{:02X}{:02X}{:02X}", color.b, color.g, color.r)
+}
@@ -0,0 +1,67 @@
+//! `waybar`: the palette as GTK CSS named colors (`<slug>.css`), for a
+//! Waybar `style.css` to `@import` and reference as `@accent_primary` and
+//! so on.
+//!
+//! Role names become color names with `_` for `.`; translucent roles keep
+//! their alpha through `alpha()`, which GTK CSS understands.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter};
+
+pub struct WaybarExporter;
+
+impl Exporter for WaybarExporter {
+    fn name(&self) -> &'static str {
+        "waybar"
+    }
+
+    fn extension(&self) -> &'static str {
+        "css"
+    }
+
+    fn description(&self) -> &'static str {
+        "Waybar GTK CSS palette"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "/* {} for Waybar; @import this from style.css.\n   \
+             Generated by `cyberdeck export --target waybar`; do not edit. */\n",
+            theme_name(theme)
+        );
+        for (role, color) in resolve_roles(theme) {
+            let _ = writeln!(
+                out,
+                "@define-color {} {};",
+                role.replace('.', "_"),
+                gtk_color(color)
+            );
+        }
+        Ok(vec![Artifact::new(
+            format!("{}.css", theme_slug(theme)),
+            out,
+        )])
+    }
+}
+
+/// A color as GTK CSS writes it; GTK 3 doesn't read `#rrggbbaa`.
+fn gtk_color(color: Color) -> String {
+    if color.is_opaque() {
+        color.to_hex_rgb()
+    } else {
+        format!(
+            "alpha({}, {:.3})",
+            color.to_hex_rgb(),
+            f64::from(color.a) / 255.0
+        )
+    }
+}
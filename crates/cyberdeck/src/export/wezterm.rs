@@ -0,0 +1,127 @@
+//! `wezterm`: a WezTerm color scheme (`<slug>.toml`, for a directory in
+//! `color_scheme_dirs`).
+//!
+//! Besides the terminal palette, the retro tab bar takes its colors from the
+//! workbench title bar and tabs.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, workbench_color, Artifact, Exporter, TerminalColors, AUTHOR};
+
+/// Tab bar sections and the workbench keys their background and foreground
+/// read.
+const TAB_BAR: &[(&str, &[&str], &[&str])] = &[
+    (
+        "active_tab",
+        &["tab.activeBackground"],
+        &["tab.activeForeground"],
+    ),
+    (
+        "inactive_tab",
+        &["tab.inactiveBackground"],
+        &["tab.inactiveForeground"],
+    ),
+    (
+        "inactive_tab_hover",
+        &["tab.hoverBackground", "list.hoverBackground"],
+        &["tab.hoverForeground", "tab.activeForeground"],
+    ),
+    (
+        "new_tab",
+        &["tab.inactiveBackground"],
+        &["tab.inactiveForeground"],
+    ),
+    (
+        "new_tab_hover",
+        &["tab.hoverBackground", "list.hoverBackground"],
+        &["tab.hoverForeground", "tab.activeForeground"],
+    ),
+];
+
+pub struct WeztermExporter;
+
+impl Exporter for WeztermExporter {
+    fn name(&self) -> &'static str {
+        "wezterm"
+    }
+
+    fn extension(&self) -> &'static str {
+        "toml"
+    }
+
+    fn description(&self) -> &'static str {
+        "WezTerm color scheme with tab bar colors"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let colors = TerminalColors::new(theme);
+        let hex = |color: Color| format!("\"{}\"", color.over(colors.background).to_hex_rgb());
+        let list = |ansi: &[Color]| ansi.iter().map(|c| hex(*c)).collect::<Vec<_>>().join(", ");
+
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}", theme_name(theme));
+        out.push_str("# Generated by `cyberdeck export --target wezterm`; do not edit.\n\n");
+        out.push_str("[colors]\n");
+        let mut entries = vec![
+            ("foreground", colors.foreground),
+            ("background", colors.background),
+            ("cursor_bg", colors.cursor),
+            ("cursor_fg", colors.cursor_text),
+            ("cursor_border", colors.cursor),
+            ("selection_bg", colors.selection_background),
+        ];
+        if let Some(color) = colors.selection_foreground {
+            entries.push(("selection_fg", color));
+        }
+        for (key, sources) in [
+            ("scrollbar_thumb", &["scrollbarSlider.background"][..]),
+            ("split", &["editorGroup.border", "panel.border"][..]),
+        ] {
+            if let Some(color) = workbench_color(theme, sources) {
+                entries.push((key, color));
+            }
+        }
+        for (key, color) in entries {
+            let _ = writeln!(out, "{key} = {}", hex(color));
+        }
+        let _ = writeln!(out, "ansi = [{}]", list(&colors.ansi[..8]));
+        let _ = writeln!(out, "brights = [{}]", list(&colors.ansi[8..]));
+
+        if let Some(color) = workbench_color(
+            theme,
+            &[
+                "editorGroupHeader.tabsBackground",
+                "titleBar.activeBackground",
+            ],
+        ) {
+            let _ = writeln!(out, "\n[colors.tab_bar]\nbackground = {}", hex(color));
+        }
+        for (section, bg, fg) in TAB_BAR {
+            let (Some(bg), Some(fg)) = (workbench_color(theme, bg), workbench_color(theme, fg))
+            else {
+                continue;
+            };
+            let _ = writeln!(
+                out,
+                "\n[colors.tab_bar.{section}]\nbg_color = {}\nfg_color = {}",
+                hex(bg),
+                hex(fg)
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "\n[metadata]\nname = {}\nauthor = \"{AUTHOR}\"",
+            toml::Value::from(theme_name(theme))
+        );
+        Ok(vec![Artifact::new(
+            format!("{}.toml", theme_slug(theme)),
+            out,
+        )])
+    }
+}
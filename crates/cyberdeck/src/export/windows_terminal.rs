@@ -0,0 +1,88 @@
+//! `windows-terminal`: a Windows Terminal color scheme.
+//!
+//! `<slug>.json` holds the `schemes` entry to merge into `settings.json`;
+//! `<slug>-profile.json` is the matching `profiles.defaults` patch that
+//! switches every profile over to it.
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter, TerminalColors};
+
+/// Windows Terminal's names for the sixteen ANSI colors, black to bright
+/// white.
+const ANSI_KEYS: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "purple",
+    "cyan",
+    "white",
+    "brightBlack",
+    "brightRed",
+    "brightGreen",
+    "brightYellow",
+    "brightBlue",
+    "brightPurple",
+    "brightCyan",
+    "brightWhite",
+];
+
+pub struct WindowsTerminalExporter;
+
+impl Exporter for WindowsTerminalExporter {
+    fn name(&self) -> &'static str {
+        "windows-terminal"
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn description(&self) -> &'static str {
+        "Windows Terminal color scheme and profile defaults"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let colors = TerminalColors::new(theme);
+        let name = theme_name(theme);
+        let slug = theme_slug(theme);
+
+        let mut scheme = Map::new();
+        scheme.insert("name".to_owned(), name.into());
+        for (key, color) in [
+            ("background", colors.background),
+            ("foreground", colors.foreground),
+            ("cursorColor", colors.cursor),
+            ("selectionBackground", colors.selection_background),
+        ] {
+            scheme.insert(key.to_owned(), color.to_hex_rgb().into());
+        }
+        for (key, color) in ANSI_KEYS.iter().zip(colors.ansi) {
+            scheme.insert((*key).to_owned(), color.to_hex_rgb().into());
+        }
+
+        let schemes = json!({ "schemes": [Value::Object(scheme)] });
+        let profile = json!({
+            "profiles": {
+                "defaults": {
+                    "colorScheme": name,
+                },
+            },
+        });
+        let mut artifacts = Vec::new();
+        for (path, document) in [
+            (format!("{slug}.json"), schemes),
+            (format!("{slug}-profile.json"), profile),
+        ] {
+            let mut text = serde_json::to_string_pretty(&document)?;
+            text.push('\n');
+            artifacts.push(Artifact::new(path, text));
+        }
+        Ok(artifacts)
+    }
+}
@@ -0,0 +1,245 @@
+//! `xcode`: an Xcode color theme (`<name>.xccolortheme`, for
+//! `~/Library/Developer/Xcode/UserData/FontAndColorThemes`).
+//!
+//! The theme is a property list of source editor and console colors, each
+//! written as space-separated RGBA fractions. Xcode refuses themes whose
+//! syntax keys lack a font, so every syntax color is paired with an SF Mono
+//! face matching its weight and slant. Colors are composited over the editor
+//! background.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{theme_name, workbench_color, xml_escape, Artifact, Exporter, TokenStyle};
+
+/// Xcode syntax keys (without the `xcode.syntax.` prefix) and the scope path
+/// each is resolved from.
+const SYNTAX: &[(&str, &str)] = &[
+    ("attribute", "meta.decorator"),
+    ("character", "string.quoted.single"),
+    ("comment", "comment.line"),
+    ("comment.doc", "comment.block.documentation"),
+    (
+        "comment.doc.keyword",
+        "comment.block.documentation storage.type.class",
+    ),
+    ("declaration.other", "entity.name.function"),
+    ("declaration.type", "entity.name.type.class"),
+    ("identifier.class", "entity.name.type"),
+    ("identifier.class.system", "support.class"),
+    ("identifier.constant", "variable.other.constant"),
+    ("identifier.constant.system", "support.constant"),
+    (
+        "identifier.function",
+        "meta.function-call entity.name.function",
+    ),
+    ("identifier.function.system", "support.function"),
+    ("identifier.macro", "entity.name.function.macro"),
+    ("identifier.macro.system", "support.function.macro"),
+    ("identifier.type", "entity.name.type"),
+    ("identifier.type.system", "support.type"),
+    ("identifier.variable", "variable.other.readwrite"),
+    ("identifier.variable.system", "variable.language"),
+    ("keyword", "keyword.control"),
+    ("mark", "comment.line punctuation.definition.comment"),
+    ("markup.code", "markup.inline.raw"),
+    ("number", "constant.numeric"),
+    ("preprocessor", "meta.preprocessor"),
+    ("regex", "string.regexp"),
+    ("regex.capturename", "string.regexp variable.other"),
+    ("string", "string.quoted.double"),
+    ("url", "markup.underline.link"),
+];
+
+/// Source editor and console colors and the workbench keys they read.
+/// (`Exectuable` is Xcode's own spelling.)
+const UI_COLORS: &[(&str, &[&str])] = &[
+    ("DVTSourceTextBackground", &["editor.background"]),
+    (
+        "DVTSourceTextCurrentLineHighlightColor",
+        &["editor.lineHighlightBackground"],
+    ),
+    (
+        "DVTSourceTextInsertionPointColor",
+        &["editorCursor.foreground"],
+    ),
+    (
+        "DVTSourceTextSelectionColor",
+        &["editor.selectionBackground"],
+    ),
+    (
+        "DVTSourceTextInvisiblesColor",
+        &["editorWhitespace.foreground"],
+    ),
+    (
+        "DVTSourceTextBlockDimBackgroundColor",
+        &["editorGutter.background", "editor.background"],
+    ),
+    (
+        "DVTMarkupTextBackgroundColor",
+        &["editorWidget.background", "editor.background"],
+    ),
+    (
+        "DVTConsoleTextBackgroundColor",
+        &[
+            "terminal.background",
+            "panel.background",
+            "editor.background",
+        ],
+    ),
+    (
+        "DVTConsoleTextInsertionPointColor",
+        &["terminalCursor.foreground", "editorCursor.foreground"],
+    ),
+    (
+        "DVTConsoleTextSelectionColor",
+        &["terminal.selectionBackground", "editor.selectionBackground"],
+    ),
+    (
+        "DVTConsoleDebuggerInputTextColor",
+        &["terminal.foreground", "editor.foreground"],
+    ),
+    (
+        "DVTConsoleDebuggerOutputTextColor",
+        &["terminal.foreground", "editor.foreground"],
+    ),
+    (
+        "DVTConsoleDebuggerPromptTextColor",
+        &["terminal.ansiBlue", "textLink.foreground"],
+    ),
+    (
+        "DVTConsoleExectuableInputTextColor",
+        &["terminal.ansiGreen", "terminal.foreground"],
+    ),
+    (
+        "DVTConsoleExectuableOutputTextColor",
+        &["terminal.foreground", "editor.foreground"],
+    ),
+];
+
+/// Console fonts; the debugger prompt is bold.
+const CONSOLE_FONTS: &[(&str, &str)] = &[
+    ("DVTConsoleDebuggerInputTextFont", "SFMono-Regular"),
+    ("DVTConsoleDebuggerOutputTextFont", "SFMono-Regular"),
+    ("DVTConsoleDebuggerPromptTextFont", "SFMono-Bold"),
+    ("DVTConsoleExectuableInputTextFont", "SFMono-Regular"),
+    ("DVTConsoleExectuableOutputTextFont", "SFMono-Regular"),
+];
+
+const FONT_SIZE: &str = "12.0";
+
+pub struct XcodeExporter;
+
+impl Exporter for XcodeExporter {
+    fn name(&self) -> &'static str {
+        "xcode"
+    }
+
+    fn extension(&self) -> &'static str {
+        "xccolortheme"
+    }
+
+    fn description(&self) -> &'static str {
+        "Xcode source editor and console color theme"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        Ok(vec![Artifact::new(
+            format!("{}.xccolortheme", theme_name(theme)),
+            color_theme(theme),
+        )])
+    }
+}
+
+fn color_theme(theme: &Theme) -> String {
+    let background = workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+    let resolver = Resolver::new(theme);
+    let plain = TokenStyle {
+        foreground: resolver.defaults().foreground,
+        ..TokenStyle::default()
+    };
+    let mut syntax = vec![("plain", plain)];
+    for (key, scope) in SYNTAX {
+        let style = TokenStyle::resolve(&resolver, scope).unwrap_or(plain);
+        if style.foreground.is_some() {
+            syntax.push((key, style));
+        }
+    }
+
+    let mut out = String::new();
+    out.push_str(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+         \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n<dict>\n",
+    );
+    for (key, font) in CONSOLE_FONTS {
+        entry(&mut out, 1, key, &format!("{font} - {FONT_SIZE}"));
+    }
+    for (key, sources) in UI_COLORS {
+        if let Some(color) = workbench_color(theme, sources) {
+            entry(&mut out, 1, key, &components(color.over(background)));
+        }
+    }
+    out.push_str("\t<key>DVTFontAndColorVersion</key>\n\t<integer>1</integer>\n");
+
+    out.push_str("\t<key>DVTSourceTextSyntaxColors</key>\n\t<dict>\n");
+    for (key, style) in &syntax {
+        if let Some(color) = style.foreground {
+            entry(
+                &mut out,
+                2,
+                &format!("xcode.syntax.{key}"),
+                &components(color.over(background)),
+            );
+        }
+    }
+    out.push_str("\t</dict>\n\t<key>DVTSourceTextSyntaxFonts</key>\n\t<dict>\n");
+    for (key, style) in &syntax {
+        let face = match (style.bold, style.italic) {
+            (true, true) => "SFMono-BoldItalic",
+            (true, false) => "SFMono-Bold",
+            (false, true) => "SFMono-RegularItalic",
+            (false, false) => "SFMono-Regular",
+        };
+        entry(
+            &mut out,
+            2,
+            &format!("xcode.syntax.{key}"),
+            &format!("{face} - {FONT_SIZE}"),
+        );
+    }
+    out.push_str("\t</dict>\n</dict>\n</plist>\n");
+    out
+}
+
+/// A `<key>`/`<string>` pair, indented `depth` tabs.
+fn entry(out: &mut String, depth: usize, key: &str, value: &str) {
+    let indent = "\t".repeat(depth);
+    let _ = writeln!(
+        out,
+        "{indent}<key>{}</key>\n{indent}<string>{}</string>",
+        xml_escape(key),
+        xml_escape(value)
+    );
+}
+
+/// Xcode's color notation: `r g b a` as fractions of one.
+fn components(color: Color) -> String {
+    let fraction = |channel: u8| {
+        let text = format!("{:.6}", f64::from(channel) / 255.0);
+        text.trim_end_matches('0').trim_end_matches('.').to_owned()
+    };
+    format!(
+        "{} {} {} {}",
+        fraction(color.r),
+        fraction(color.g),
+        fraction(color.b),
+        fraction(color.a)
+    )
+}
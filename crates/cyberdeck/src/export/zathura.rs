@@ -0,0 +1,157 @@
+//! `zathura`: color settings (`<slug>.zathurarc`) to `include` from
+//! `~/.config/zathura/zathurarc`, from the workbench colors.
+//!
+//! Recoloring maps the page's white to the editor background and its black
+//! to the editor foreground, so documents read like the editor; it's on by
+//! default for dark themes. Highlights keep their alpha as `rgba()`, the
+//! rest is composited over the editor background.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::{is_dark, theme_name, theme_slug, workbench_color, Artifact, Exporter};
+
+/// Options and the workbench keys they read, first present wins.
+const OPTIONS: &[(&str, &[&str])] = &[
+    ("default-bg", &["editor.background"]),
+    ("default-fg", &["editor.foreground", "foreground"]),
+    ("statusbar-bg", &["statusBar.background"]),
+    ("statusbar-fg", &["statusBar.foreground"]),
+    (
+        "inputbar-bg",
+        &["input.background", "editorWidget.background"],
+    ),
+    ("inputbar-fg", &["input.foreground", "editor.foreground"]),
+    (
+        "notification-bg",
+        &["notifications.background", "editorWidget.background"],
+    ),
+    (
+        "notification-fg",
+        &["notifications.foreground", "editor.foreground"],
+    ),
+    ("notification-error-bg", &["editor.background"]),
+    (
+        "notification-error-fg",
+        &["editorError.foreground", "errorForeground"],
+    ),
+    ("notification-warning-bg", &["editor.background"]),
+    ("notification-warning-fg", &["editorWarning.foreground"]),
+    (
+        "completion-bg",
+        &["editorSuggestWidget.background", "editorWidget.background"],
+    ),
+    (
+        "completion-fg",
+        &["editorSuggestWidget.foreground", "editor.foreground"],
+    ),
+    (
+        "completion-group-bg",
+        &["sideBarSectionHeader.background", "sideBar.background"],
+    ),
+    (
+        "completion-group-fg",
+        &["sideBarSectionHeader.foreground", "sideBarTitle.foreground"],
+    ),
+    (
+        "completion-highlight-bg",
+        &[
+            "editorSuggestWidget.selectedBackground",
+            "list.activeSelectionBackground",
+        ],
+    ),
+    (
+        "completion-highlight-fg",
+        &[
+            "editorSuggestWidget.highlightForeground",
+            "list.highlightForeground",
+        ],
+    ),
+    ("index-bg", &["sideBar.background", "editor.background"]),
+    ("index-fg", &["sideBar.foreground", "editor.foreground"]),
+    ("index-active-bg", &["list.activeSelectionBackground"]),
+    (
+        "index-active-fg",
+        &["list.activeSelectionForeground", "editor.foreground"],
+    ),
+    ("recolor-lightcolor", &["editor.background"]),
+    ("recolor-darkcolor", &["editor.foreground", "foreground"]),
+];
+
+/// Highlight options, written with their alpha.
+const HIGHLIGHTS: &[(&str, &[&str])] = &[
+    (
+        "highlight-color",
+        &[
+            "editor.findMatchHighlightBackground",
+            "editor.selectionBackground",
+        ],
+    ),
+    (
+        "highlight-active-color",
+        &["editor.findMatchBackground", "editor.selectionBackground"],
+    ),
+];
+
+pub struct ZathuraExporter;
+
+impl Exporter for ZathuraExporter {
+    fn name(&self) -> &'static str {
+        "zathura"
+    }
+
+    fn extension(&self) -> &'static str {
+        "zathurarc"
+    }
+
+    fn description(&self) -> &'static str {
+        "zathura colors with recoloring"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let background =
+            workbench_color(theme, &["editor.background"]).unwrap_or(Color::rgb(0, 0, 0));
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# {} for zathura; include this from ~/.config/zathura/zathurarc.\n\
+             # Generated by `cyberdeck export --target zathura`; do not edit.\n",
+            theme_name(theme)
+        );
+        for (option, keys) in OPTIONS {
+            if let Some(color) = workbench_color(theme, keys) {
+                let _ = writeln!(
+                    out,
+                    "set {option} \"{}\"",
+                    color.over(background).to_hex_rgb()
+                );
+            }
+        }
+        for (option, keys) in HIGHLIGHTS {
+            if let Some(color) = workbench_color(theme, keys) {
+                let _ = writeln!(
+                    out,
+                    "set {option} \"rgba({},{},{},{:.2})\"",
+                    color.r,
+                    color.g,
+                    color.b,
+                    f64::from(color.a) / 255.0
+                );
+            }
+        }
+        let _ = writeln!(
+            out,
+            "\nset recolor {}\nset recolor-keephue true",
+            is_dark(theme)
+        );
+        Ok(vec![Artifact::new(
+            format!("{}.zathurarc", theme_slug(theme)),
+            out,
+        )])
+    }
+}
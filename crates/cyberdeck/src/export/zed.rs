@@ -0,0 +1,332 @@
+//! `zed`: a Zed theme family (`<slug>.json`).
+//!
+//! Zed understands translucent colors, so workbench colors are carried over
+//! as they are. Player colors (collaborator cursors and selections) start
+//! with the theme's own cursor, then cycle through the accent and token
+//! roles.
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+use crate::color::Color;
+use crate::lint::TERMINAL_ANSI_KEYS;
+use crate::roles::resolve_roles;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{
+    is_dark, theme_name, theme_slug, workbench_color, Artifact, Exporter, TokenStyle, AUTHOR,
+};
+
+/// Zed style keys and the workbench keys they read.
+const STYLE_KEYS: &[(&str, &[&str])] = &[
+    ("background", &["editor.background"]),
+    ("border", &["panel.border", "editorGroup.border"]),
+    ("border.variant", &["editorGroup.border", "panel.border"]),
+    ("border.focused", &["focusBorder"]),
+    ("border.selected", &["focusBorder"]),
+    ("elevated_surface.background", &["editorWidget.background"]),
+    ("surface.background", &["sideBar.background"]),
+    (
+        "element.background",
+        &["button.secondaryBackground", "input.background"],
+    ),
+    ("element.hover", &["list.hoverBackground"]),
+    ("element.active", &["list.activeSelectionBackground"]),
+    ("element.selected", &["list.activeSelectionBackground"]),
+    (
+        "ghost_element.hover",
+        &["toolbar.hoverBackground", "list.hoverBackground"],
+    ),
+    (
+        "ghost_element.selected",
+        &["list.inactiveSelectionBackground"],
+    ),
+    ("drop_target.background", &["list.dropBackground"]),
+    ("text", &["foreground", "editor.foreground"]),
+    ("text.muted", &["descriptionForeground"]),
+    ("text.placeholder", &["input.placeholderForeground"]),
+    ("text.disabled", &["disabledForeground"]),
+    ("text.accent", &["textLink.foreground"]),
+    ("icon", &["icon.foreground", "foreground"]),
+    ("icon.muted", &["descriptionForeground"]),
+    ("icon.accent", &["textLink.foreground"]),
+    ("status_bar.background", &["statusBar.background"]),
+    ("title_bar.background", &["titleBar.activeBackground"]),
+    (
+        "title_bar.inactive_background",
+        &["titleBar.inactiveBackground"],
+    ),
+    ("toolbar.background", &["editor.background"]),
+    ("tab_bar.background", &["editorGroupHeader.tabsBackground"]),
+    ("tab.inactive_background", &["tab.inactiveBackground"]),
+    ("tab.active_background", &["tab.activeBackground"]),
+    (
+        "search.match_background",
+        &["editor.findMatchHighlightBackground"],
+    ),
+    (
+        "panel.background",
+        &["panel.background", "sideBar.background"],
+    ),
+    ("panel.focused_border", &["focusBorder"]),
+    ("pane.focused_border", &["focusBorder"]),
+    (
+        "scrollbar.thumb.background",
+        &["scrollbarSlider.background"],
+    ),
+    (
+        "scrollbar.thumb.hover_background",
+        &["scrollbarSlider.hoverBackground"],
+    ),
+    ("scrollbar.track.border", &["editorOverviewRuler.border"]),
+    ("editor.foreground", &["editor.foreground"]),
+    ("editor.background", &["editor.background"]),
+    (
+        "editor.gutter.background",
+        &["editorGutter.background", "editor.background"],
+    ),
+    (
+        "editor.active_line.background",
+        &["editor.lineHighlightBackground"],
+    ),
+    (
+        "editor.highlighted_line.background",
+        &["editor.rangeHighlightBackground"],
+    ),
+    ("editor.line_number", &["editorLineNumber.foreground"]),
+    (
+        "editor.active_line_number",
+        &["editorLineNumber.activeForeground"],
+    ),
+    ("editor.invisible", &["editorWhitespace.foreground"]),
+    ("editor.wrap_guide", &["editorRuler.foreground"]),
+    (
+        "editor.indent_guide",
+        &[
+            "editorIndentGuide.background1",
+            "editorIndentGuide.background",
+        ],
+    ),
+    (
+        "editor.indent_guide_active",
+        &[
+            "editorIndentGuide.activeBackground1",
+            "editorIndentGuide.activeBackground",
+        ],
+    ),
+    (
+        "editor.document_highlight.read_background",
+        &["editor.wordHighlightBackground"],
+    ),
+    (
+        "editor.document_highlight.write_background",
+        &["editor.wordHighlightStrongBackground"],
+    ),
+    ("terminal.background", &["terminal.background"]),
+    ("terminal.foreground", &["terminal.foreground"]),
+    ("link_text.hover", &["textLink.activeForeground"]),
+    ("error", &["editorError.foreground", "errorForeground"]),
+    ("warning", &["editorWarning.foreground"]),
+    ("info", &["editorInfo.foreground"]),
+    ("hint", &["editorHint.foreground", "editorInfo.foreground"]),
+    (
+        "created",
+        &[
+            "gitDecoration.addedResourceForeground",
+            "editorGutter.addedBackground",
+        ],
+    ),
+    (
+        "modified",
+        &[
+            "gitDecoration.modifiedResourceForeground",
+            "editorGutter.modifiedBackground",
+        ],
+    ),
+    (
+        "deleted",
+        &[
+            "gitDecoration.deletedResourceForeground",
+            "editorGutter.deletedBackground",
+        ],
+    ),
+    ("conflict", &["gitDecoration.conflictingResourceForeground"]),
+    ("ignored", &["gitDecoration.ignoredResourceForeground"]),
+    ("renamed", &["gitDecoration.renamedResourceForeground"]),
+];
+
+/// Zed syntax highlight names and the scope path each is resolved from.
+const SYNTAX: &[(&str, &str)] = &[
+    ("attribute", "entity.other.attribute-name"),
+    ("boolean", "constant.language.boolean"),
+    ("comment", "comment.line"),
+    ("comment.doc", "comment.block.documentation"),
+    ("constant", "variable.other.constant"),
+    ("constructor", "entity.name.function.constructor"),
+    ("embedded", "meta.embedded"),
+    ("emphasis", "markup.italic"),
+    ("emphasis.strong", "markup.bold"),
+    ("enum", "entity.name.type.enum"),
+    ("function", "entity.name.function"),
+    ("keyword", "keyword"),
+    ("label", "entity.name.label"),
+    ("link_text", "string.other.link"),
+    ("link_uri", "markup.underline.link"),
+    ("number", "constant.numeric"),
+    ("operator", "keyword.operator"),
+    ("preproc", "meta.preprocessor"),
+    ("property", "variable.other.property"),
+    ("punctuation", "punctuation"),
+    ("punctuation.bracket", "punctuation.section.brackets"),
+    ("punctuation.delimiter", "punctuation.separator"),
+    (
+        "punctuation.list_marker",
+        "punctuation.definition.list.begin",
+    ),
+    (
+        "punctuation.special",
+        "punctuation.definition.template-expression",
+    ),
+    ("string", "string.quoted.double"),
+    ("string.escape", "constant.character.escape"),
+    ("string.regex", "string.regexp"),
+    ("string.special", "string.other"),
+    ("string.special.symbol", "constant.other.symbol"),
+    ("tag", "entity.name.tag"),
+    ("text.literal", "markup.inline.raw"),
+    ("title", "markup.heading"),
+    ("type", "entity.name.type"),
+    ("variable", "variable.other.readwrite"),
+    ("variable.special", "variable.language"),
+    ("variant", "variable.other.enummember"),
+];
+
+/// Roles that color the remaining players, in order.
+const PLAYER_ROLES: &[&str] = &[
+    "accent.primary",
+    "accent.secondary",
+    "function",
+    "string",
+    "storage",
+    "class",
+    "keyword",
+];
+
+/// Zed's names for the sixteen ANSI colors, in `TERMINAL_ANSI_KEYS` order.
+const ANSI_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright_black",
+    "bright_red",
+    "bright_green",
+    "bright_yellow",
+    "bright_blue",
+    "bright_magenta",
+    "bright_cyan",
+    "bright_white",
+];
+
+pub struct ZedExporter;
+
+impl Exporter for ZedExporter {
+    fn name(&self) -> &'static str {
+        "zed"
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn description(&self) -> &'static str {
+        "Zed theme family with syntax, UI and player colors"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let document = json!({
+            "$schema": "https://zed.dev/schema/themes/v0.2.0.json",
+            "name": theme_name(theme),
+            "author": AUTHOR,
+            "themes": [{
+                "name": theme_name(theme),
+                "appearance": if is_dark(theme) { "dark" } else { "light" },
+                "style": style(theme),
+            }],
+        });
+        let mut text = serde_json::to_string_pretty(&document)?;
+        text.push('\n');
+        Ok(vec![Artifact::new(
+            format!("{}.json", theme_slug(theme)),
+            text,
+        )])
+    }
+}
+
+fn style(theme: &Theme) -> Map<String, Value> {
+    let mut style = Map::new();
+    for (key, sources) in STYLE_KEYS {
+        if let Some(color) = workbench_color(theme, sources) {
+            style.insert((*key).to_owned(), color.to_string().into());
+        }
+    }
+    for (name, key) in ANSI_NAMES.iter().zip(TERMINAL_ANSI_KEYS) {
+        if let Some(color) = workbench_color(theme, &[key]) {
+            style.insert(format!("terminal.ansi.{name}"), color.to_string().into());
+        }
+    }
+    style.insert("players".to_owned(), players(theme).into());
+    style.insert("syntax".to_owned(), syntax(theme).into());
+    style
+}
+
+/// The local player uses the editor's cursor and selection colors; the
+/// others get accent colors with a translucent selection.
+fn players(theme: &Theme) -> Vec<Value> {
+    let roles = resolve_roles(theme);
+    let mut players = Vec::new();
+    if let Some(cursor) = workbench_color(theme, &["editorCursor.foreground"]) {
+        let selection = workbench_color(theme, &["editor.selectionBackground"])
+            .unwrap_or(cursor.with_alpha(0x3d));
+        players.push(player(cursor, selection));
+    }
+    for role in PLAYER_ROLES {
+        if let Some(&color) = roles.get(role) {
+            let color = color.opaque();
+            players.push(player(color, color.with_alpha(0x3d)));
+        }
+    }
+    players
+}
+
+fn player(cursor: Color, selection: Color) -> Value {
+    json!({
+        "cursor": cursor.to_string(),
+        "background": cursor.to_string(),
+        "selection": selection.to_string(),
+    })
+}
+
+fn syntax(theme: &Theme) -> Map<String, Value> {
+    let resolver = Resolver::new(theme);
+    let mut syntax = Map::new();
+    for (name, scope) in SYNTAX {
+        let Some(style) = TokenStyle::resolve(&resolver, scope) else {
+            continue;
+        };
+        syntax.insert(
+            (*name).to_owned(),
+            json!({
+                "color": style.foreground.map(|c| c.to_string()),
+                "font_style": style.italic.then_some("italic"),
+                "font_weight": style.bold.then_some(700),
+            }),
+        );
+    }
+    syntax
+}
@@ -0,0 +1,159 @@
+//! `zsh-syntax-highlighting`: a snippet (`<slug>.zsh`) setting
+//! `ZSH_HIGHLIGHT_STYLES`, so the command line is colored like shell
+//! scripts in the editor. Source it after the plugin.
+//!
+//! Each style is resolved from the scope path the shell grammar gives the
+//! construct, with colors composited over the terminal background.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+use super::{theme_name, theme_slug, Artifact, Exporter, TerminalColors, TokenStyle};
+
+/// `ZSH_HIGHLIGHT_STYLES` keys and the scope path each is resolved from.
+const STYLES: &[(&str, &str)] = &[
+    ("unknown-token", "source.shell invalid.illegal"),
+    ("reserved-word", "source.shell keyword.control.shell"),
+    ("alias", "source.shell entity.name.function.call.shell"),
+    (
+        "suffix-alias",
+        "source.shell entity.name.function.call.shell",
+    ),
+    (
+        "global-alias",
+        "source.shell entity.name.function.call.shell",
+    ),
+    ("builtin", "source.shell support.function.builtin.shell"),
+    ("function", "source.shell entity.name.function.call.shell"),
+    ("command", "source.shell entity.name.command.shell"),
+    ("precommand", "source.shell support.function.builtin.shell"),
+    ("hashed-command", "source.shell entity.name.command.shell"),
+    (
+        "commandseparator",
+        "source.shell keyword.operator.list.shell",
+    ),
+    (
+        "redirection",
+        "source.shell keyword.operator.redirect.shell",
+    ),
+    (
+        "autodirectory",
+        "source.shell string.unquoted.argument.shell",
+    ),
+    ("path", "source.shell string.unquoted.argument.shell"),
+    ("globbing", "source.shell keyword.operator.glob.shell"),
+    (
+        "history-expansion",
+        "source.shell variable.other.special.shell",
+    ),
+    (
+        "command-substitution-delimiter",
+        "source.shell punctuation.definition.evaluation.backticks.shell",
+    ),
+    (
+        "process-substitution-delimiter",
+        "source.shell punctuation.definition.evaluation.backticks.shell",
+    ),
+    (
+        "single-hyphen-option",
+        "source.shell constant.other.option.dash.shell",
+    ),
+    (
+        "double-hyphen-option",
+        "source.shell constant.other.option.shell",
+    ),
+    (
+        "back-quoted-argument-delimiter",
+        "source.shell punctuation.definition.evaluation.backticks.shell",
+    ),
+    (
+        "single-quoted-argument",
+        "source.shell string.quoted.single.shell",
+    ),
+    (
+        "double-quoted-argument",
+        "source.shell string.quoted.double.shell",
+    ),
+    (
+        "dollar-quoted-argument",
+        "source.shell string.quoted.single.dollar.shell",
+    ),
+    (
+        "dollar-double-quoted-argument",
+        "source.shell string.quoted.double.shell variable.other.normal.shell",
+    ),
+    (
+        "back-double-quoted-argument",
+        "source.shell string.quoted.double.shell constant.character.escape.shell",
+    ),
+    (
+        "back-dollar-quoted-argument",
+        "source.shell constant.character.escape.shell",
+    ),
+    ("assign", "source.shell variable.other.assignment.shell"),
+    ("named-fd", "source.shell variable.other.normal.shell"),
+    ("numeric-fd", "source.shell constant.numeric.integer.shell"),
+    ("comment", "source.shell comment.line.number-sign.shell"),
+];
+
+pub struct ZshHighlightExporter;
+
+impl Exporter for ZshHighlightExporter {
+    fn name(&self) -> &'static str {
+        "zsh-syntax-highlighting"
+    }
+
+    fn extension(&self) -> &'static str {
+        "zsh"
+    }
+
+    fn description(&self) -> &'static str {
+        "zsh-syntax-highlighting styles"
+    }
+
+    fn export(&self, theme: &Theme) -> Result<Vec<Artifact>> {
+        let background = TerminalColors::new(theme).background;
+        let resolver = Resolver::new(theme);
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# {} for zsh-syntax-highlighting; source this after the plugin.\n\
+             # Generated by `cyberdeck export --target zsh-syntax-highlighting`; do not edit.\n\
+             typeset -gA ZSH_HIGHLIGHT_STYLES",
+            theme_name(theme)
+        );
+        for (key, scope) in STYLES {
+            let Some(style) = TokenStyle::resolve(&resolver, scope) else {
+                continue;
+            };
+            let mut parts = Vec::new();
+            if let Some(color) = style.foreground {
+                parts.push(format!("fg={}", color.over(background).to_hex_rgb()));
+            }
+            if let Some(color) = style.background {
+                parts.push(format!("bg={}", color.over(background).to_hex_rgb()));
+            }
+            for (set, attr) in [
+                (style.bold, "bold"),
+                (style.italic, "italic"),
+                (style.underline, "underline"),
+            ] {
+                if set {
+                    parts.push(attr.to_owned());
+                }
+            }
+            if !parts.is_empty() {
+                let _ = writeln!(out, "ZSH_HIGHLIGHT_STYLES[{key}]='{}'", parts.join(","));
+            }
+        }
+        Ok(vec![Artifact::new(
+            format!("{}.zsh", theme_slug(theme)),
+            out,
+        )])
+    }
+}
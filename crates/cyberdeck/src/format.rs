@@ -0,0 +1,291 @@
+//! Canonical formatting of theme files.
+//!
+//! Formatting is comment-preserving and never changes what VS Code renders:
+//!
+//! - top-level keys follow a fixed order;
+//! - workbench and semantic token colors are sorted alphabetically within
+//!   each comment-delimited section, keeping the last of any duplicate key;
+//! - hex colors are lowercased;
+//! - token rule scopes are split, de-duplicated and sorted, a selector is
+//!   dropped from a rule when a later rule restyles it with at least the same
+//!   settings, and rules are ordered by their first scope as far as that
+//!   can't change which of two rules wins a tie: a rule never moves past
+//!   one that sets the same property for a selector it could tie with.
+
+use std::collections::HashSet;
+
+use crate::jsonc::{self, Comment, Element, Member, Node, ParseError};
+use crate::scope::Selector;
+use crate::shadow::may_tie;
+
+/// Canonical order of top-level theme keys; unknown keys follow in their
+/// original order.
+const TOP_LEVEL_ORDER: [&str; 8] = [
+    "name",
+    "$schema",
+    "type",
+    "include",
+    "semanticHighlighting",
+    "colors",
+    "semanticTokenColors",
+    "tokenColors",
+];
+
+const RULE_ORDER: [&str; 3] = ["name", "scope", "settings"];
+const SETTINGS_ORDER: [&str; 3] = ["foreground", "background", "fontStyle"];
+
+/// Formats theme source text.
+pub fn format_theme(text: &str) -> Result<String, ParseError> {
+    let mut root = jsonc::parse_tree(text)?;
+    if let Node::Object { members, .. } = &mut root {
+        order_members(members, &TOP_LEVEL_ORDER);
+        for member in members.iter_mut() {
+            match member.key.as_str() {
+                "colors" | "semanticTokenColors" => sort_sections(&mut member.value),
+                "tokenColors" => format_token_colors(&mut member.value),
+                _ => {}
+            }
+        }
+    }
+    lowercase_hex(&mut root);
+    Ok(root.to_pretty_string())
+}
+
+/// Stable-sorts `members` so keys listed in `order` come first, in order.
+fn order_members(members: &mut [Member], order: &[&str]) {
+    members.sort_by_key(|m| {
+        order
+            .iter()
+            .position(|k| *k == m.key)
+            .unwrap_or(order.len())
+    });
+}
+
+/// Sorts an object's members by key within runs separated by comments,
+/// dropping all but the last occurrence of each key.
+fn sort_sections(node: &mut Node) {
+    let Node::Object { members, dangling } = node else {
+        return;
+    };
+
+    let mut seen = HashSet::new();
+    let mut keep = vec![false; members.len()];
+    for (i, member) in members.iter().enumerate().rev() {
+        keep[i] = seen.insert(member.key.clone());
+    }
+
+    let mut sections: Vec<(Vec<Comment>, Vec<Member>)> = Vec::new();
+    for (member, keep) in std::mem::take(members).into_iter().zip(keep) {
+        let mut member = member;
+        let header = std::mem::take(&mut member.leading);
+        if !header.is_empty() || sections.is_empty() {
+            sections.push((header, Vec::new()));
+        }
+        if keep {
+            sections.last_mut().unwrap().1.push(member);
+        }
+    }
+
+    // A section whose keys all reappear later keeps its header for the next
+    // section (or the end of the object).
+    let mut carry: Vec<Comment> = Vec::new();
+    for (header, mut section) in sections {
+        carry.extend(header);
+        section.sort_by(|a, b| a.key.cmp(&b.key));
+        let mut section = section.into_iter();
+        if let Some(mut first) = section.next() {
+            first.leading = std::mem::take(&mut carry);
+            members.push(first);
+            members.extend(section);
+        }
+    }
+    dangling.splice(0..0, carry);
+}
+
+/// A token rule's scope selectors and the settings keys it defines.
+struct RuleShape {
+    selectors: Vec<String>,
+    was_array: bool,
+    settings: Vec<String>,
+}
+
+fn rule_shape(rule: &Node) -> Option<RuleShape> {
+    let (selectors, was_array) = match rule.get("scope")? {
+        Node::String(s) => (split_selectors(s), false),
+        Node::Array { elements, .. } => (
+            elements
+                .iter()
+                .filter_map(|e| e.value.as_str())
+                .flat_map(split_selectors)
+                .collect(),
+            true,
+        ),
+        _ => return None,
+    };
+    let settings = match rule.get("settings") {
+        Some(Node::Object { members, .. }) => members.iter().map(|m| m.key.clone()).collect(),
+        _ => Vec::new(),
+    };
+    Some(RuleShape {
+        selectors,
+        was_array,
+        settings,
+    })
+}
+
+fn split_selectors(s: &str) -> Vec<String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+fn format_token_colors(node: &mut Node) {
+    let Node::Array { elements, .. } = node else {
+        return;
+    };
+
+    let shapes: Vec<Option<RuleShape>> = elements.iter().map(|e| rule_shape(&e.value)).collect();
+
+    // Drop selectors a later rule fully restyles.
+    let mut kept: Vec<Option<Vec<String>>> = Vec::with_capacity(shapes.len());
+    for (i, shape) in shapes.iter().enumerate() {
+        let Some(shape) = shape else {
+            kept.push(None);
+            continue;
+        };
+        let mut selectors: Vec<String> = Vec::new();
+        for selector in &shape.selectors {
+            if selectors.contains(selector) {
+                continue;
+            }
+            let overridden = shapes[i + 1..].iter().flatten().any(|later| {
+                later.selectors.contains(selector)
+                    && shape.settings.iter().all(|k| later.settings.contains(k))
+            });
+            if !overridden {
+                selectors.push(selector.clone());
+            }
+        }
+        selectors.sort();
+        kept.push(Some(selectors));
+    }
+
+    let mut rules: Vec<Element> = Vec::new();
+    for ((mut element, shape), selectors) in
+        std::mem::take(elements).into_iter().zip(&shapes).zip(kept)
+    {
+        if let (Some(shape), Some(selectors)) = (shape, selectors) {
+            if selectors.is_empty() {
+                continue;
+            }
+            set_scope(&mut element.value, &selectors, shape.was_array);
+        }
+        if let Node::Object { members, .. } = &mut element.value {
+            order_members(members, &RULE_ORDER);
+            for member in members.iter_mut() {
+                if let (true, Node::Object { members, .. }) =
+                    (member.key == "settings", &mut member.value)
+                {
+                    order_members(members, &SETTINGS_ORDER);
+                }
+            }
+        }
+        rules.push(element);
+    }
+
+    // Rules without a scope (global defaults) sort first. This is an
+    // insertion sort that stops at any rule the moving one could tie with,
+    // so their order, and with it which one VS Code applies, is kept.
+    let mut sorted: Vec<(String, Option<RuleShape>, Element)> = Vec::with_capacity(rules.len());
+    for element in rules {
+        let shape = rule_shape(&element.value);
+        let key = shape
+            .as_ref()
+            .and_then(|s| s.selectors.first().cloned())
+            .unwrap_or_default();
+        let mut at = sorted.len();
+        while at > 0 {
+            let (earlier_key, earlier_shape, _) = &sorted[at - 1];
+            if *earlier_key <= key || conflicts(shape.as_ref(), earlier_shape.as_ref()) {
+                break;
+            }
+            at -= 1;
+        }
+        sorted.insert(at, (key, shape, element));
+    }
+    *elements = sorted.into_iter().map(|(_, _, element)| element).collect();
+}
+
+/// Whether two rules set a common property for selectors that could match
+/// the same token equally specifically, so swapping them changes which
+/// one applies. Unscoped rules (`None`) set the global defaults and only
+/// conflict with each other.
+fn conflicts(a: Option<&RuleShape>, b: Option<&RuleShape>) -> bool {
+    let shares_setting = |a: &[String], b: &[String]| a.iter().any(|k| b.contains(k));
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            if !shares_setting(&a.settings, &b.settings) {
+                return false;
+            }
+            let parse = |shape: &RuleShape| -> Vec<Selector> {
+                shape
+                    .selectors
+                    .iter()
+                    .filter_map(|s| Selector::parse(s))
+                    .collect()
+            };
+            let (a, b) = (parse(a), parse(b));
+            a.iter().any(|s| b.iter().any(|t| may_tie(s, t)))
+        }
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Replaces a rule's `scope` with `selectors`: a string if there is one
+/// and it was a string, else an array.
+pub(crate) fn set_scope(rule: &mut Node, selectors: &[String], was_array: bool) {
+    let Node::Object { members, .. } = rule else {
+        return;
+    };
+    let Some(member) = members.iter_mut().rev().find(|m| m.key == "scope") else {
+        return;
+    };
+    member.value = if selectors.len() == 1 && !was_array {
+        Node::String(selectors[0].clone())
+    } else {
+        Node::Array {
+            elements: selectors
+                .iter()
+                .map(|s| Element {
+                    leading: Vec::new(),
+                    value: Node::String(s.clone()),
+                    span: 0..0,
+                    trailing: None,
+                })
+                .collect(),
+            dangling: Vec::new(),
+        }
+    };
+}
+
+fn lowercase_hex(node: &mut Node) {
+    match node {
+        Node::String(s) => {
+            let is_hex =
+                s.len() > 1 && s.starts_with('#') && s[1..].bytes().all(|b| b.is_ascii_hexdigit());
+            if is_hex {
+                s.make_ascii_lowercase();
+            }
+        }
+        Node::Array { elements, .. } => elements
+            .iter_mut()
+            .for_each(|e| lowercase_hex(&mut e.value)),
+        Node::Object { members, .. } => {
+            members.iter_mut().for_each(|m| lowercase_hex(&mut m.value))
+        }
+        Node::Scalar(_) => {}
+    }
+}
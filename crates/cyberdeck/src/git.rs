@@ -0,0 +1,49 @@
+//! Thin wrappers over the `git` command line, run in the project root.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+/// Runs `git <args>` in `root`, returning its trimmed stdout.
+pub fn git(root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_owned())
+}
+
+/// Whether the working tree has no uncommitted changes.
+pub fn is_clean(root: &Path) -> Result<bool> {
+    Ok(git(root, &["status", "--porcelain"])?.is_empty())
+}
+
+/// The most recent tag reachable from `HEAD`, if any.
+pub fn last_tag(root: &Path) -> Result<Option<String>> {
+    match git(root, &["describe", "--tags", "--abbrev=0"]) {
+        Ok(tag) => Ok(Some(tag)),
+        Err(_) if git(root, &["tag", "--list"])?.is_empty() => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// The contents of `path` (relative to the root) at `rev`, or `None` if
+/// the file did not exist there.
+pub fn show(root: &Path, rev: &str, path: &str) -> Result<Option<String>> {
+    let spec = format!("{rev}:{path}");
+    if git(root, &["cat-file", "-e", &spec]).is_err() {
+        return Ok(None);
+    }
+    git(root, &["show", &spec]).map(Some)
+}
@@ -0,0 +1,298 @@
+//! Converting VS Code TextMate grammars (`*.tmLanguage.json`) into syntect
+//! syntax definitions.
+//!
+//! The grammar is rewritten as an equivalent `.sublime-syntax` document,
+//! which syntect then loads:
+//!
+//! - top-level `patterns` become the `main` context and each `repository`
+//!   entry a context of its own;
+//! - `begin`/`end` rules push an anonymous context whose `meta_scope` is the
+//!   rule's `name`, whose `meta_content_scope` is its `contentName`, and
+//!   which pops on `end`; `while` rules pop on the first line not matching;
+//! - includes of other grammars become `scope:` includes.
+//!
+//! Oniguruma features the regex engine lacks (such as `\G`) make a pattern
+//! unusable; such patterns are dropped and counted rather than failing the
+//! whole grammar.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use syntect::parsing::{Regex, SyntaxDefinition};
+
+/// A converted grammar and how many of its patterns had to be dropped.
+pub struct Converted {
+    pub definition: SyntaxDefinition,
+    pub dropped: usize,
+}
+
+/// Converts a parsed `tmLanguage.json` document. `extensions` are the file
+/// extensions (without dots) the grammar applies to.
+pub fn convert(grammar: &Value, name: &str, extensions: &[String]) -> Result<Converted> {
+    let scope = grammar
+        .get("scopeName")
+        .and_then(Value::as_str)
+        .context("grammar has no scopeName")?;
+    let mut converter = Converter {
+        contexts: Map::new(),
+        dropped: 0,
+    };
+
+    let main = converter.patterns(grammar.get("patterns"));
+    converter.repository(grammar.get("repository"));
+    converter
+        .contexts
+        .insert("main".to_owned(), Value::Array(main));
+    converter.prune_includes();
+
+    let mut file_extensions: Vec<String> = extensions.to_vec();
+    for extension in grammar
+        .get("fileTypes")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+    {
+        if !file_extensions.iter().any(|e| e == extension) {
+            file_extensions.push(extension.to_owned());
+        }
+    }
+
+    let document = json!({
+        "name": grammar.get("name").and_then(Value::as_str).unwrap_or(name),
+        "scope": scope,
+        "file_extensions": file_extensions,
+        "contexts": converter.contexts,
+    });
+    let text = to_yaml(&document);
+    let definition = SyntaxDefinition::load_from_str(&text, true, Some(name))
+        .with_context(|| format!("failed to load converted grammar {scope}"))?;
+    Ok(Converted {
+        definition,
+        dropped: converter.dropped,
+    })
+}
+
+struct Converter {
+    contexts: Map<String, Value>,
+    dropped: usize,
+}
+
+impl Converter {
+    /// Removes includes of repository entries the grammar never defines,
+    /// which syntect cannot link.
+    fn prune_includes(&mut self) {
+        fn prune(patterns: &mut Vec<Value>, defined: &[String]) {
+            patterns.retain(
+                |pattern| match pattern.get("include").and_then(Value::as_str) {
+                    Some(target) => {
+                        target.starts_with("scope:") || defined.iter().any(|d| d == target)
+                    }
+                    None => true,
+                },
+            );
+            for pattern in patterns {
+                if let Some(Value::Array(pushed)) = pattern.get_mut("push") {
+                    prune(pushed, defined);
+                }
+            }
+        }
+        let defined: Vec<String> = self.contexts.keys().cloned().collect();
+        for patterns in self.contexts.values_mut() {
+            if let Value::Array(patterns) = patterns {
+                prune(patterns, &defined);
+            }
+        }
+    }
+
+    /// Adds every repository entry, including nested ones, as a context.
+    fn repository(&mut self, repository: Option<&Value>) {
+        let Some(repository) = repository.and_then(Value::as_object) else {
+            return;
+        };
+        for (key, rule) in repository {
+            let patterns = if is_container(rule) {
+                self.repository(rule.get("repository"));
+                self.patterns(rule.get("patterns"))
+            } else {
+                self.rule(rule)
+            };
+            self.contexts
+                .insert(context_name(key), Value::Array(patterns));
+        }
+    }
+
+    fn patterns(&mut self, patterns: Option<&Value>) -> Vec<Value> {
+        patterns
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .flat_map(|rule| self.rule(rule))
+            .collect()
+    }
+
+    /// Converts one rule; containers expand to their patterns.
+    fn rule(&mut self, rule: &Value) -> Vec<Value> {
+        let str_field = |key: &str| rule.get(key).and_then(Value::as_str);
+        self.repository(rule.get("repository"));
+
+        if let Some(include) = str_field("include") {
+            return vec![json!({ "include": include_target(include) })];
+        }
+        if let Some(pattern) = str_field("match") {
+            if !compiles(pattern) {
+                self.dropped += 1;
+                return Vec::new();
+            }
+            let mut out = Map::new();
+            out.insert("match".to_owned(), pattern.into());
+            if let Some(name) = str_field("name") {
+                out.insert("scope".to_owned(), name.into());
+            }
+            if let Some(captures) = captures(rule.get("captures")) {
+                out.insert("captures".to_owned(), captures);
+            }
+            return vec![Value::Object(out)];
+        }
+        if let Some(begin) = str_field("begin") {
+            return self.begin_end(rule, begin);
+        }
+        self.patterns(rule.get("patterns"))
+    }
+
+    fn begin_end(&mut self, rule: &Value, begin: &str) -> Vec<Value> {
+        let str_field = |key: &str| rule.get(key).and_then(Value::as_str);
+        let end = match (str_field("end"), str_field("while")) {
+            (Some(end), _) => end.to_owned(),
+            (None, Some(while_)) => format!("^(?!{while_})"),
+            (None, None) => String::new(),
+        };
+        if !compiles(begin) || !compiles(&end) {
+            self.dropped += 1;
+            return Vec::new();
+        }
+
+        let mut pop = Map::new();
+        pop.insert("match".to_owned(), end.into());
+        if let Some(captures) =
+            captures(rule.get("endCaptures")).or_else(|| captures(rule.get("captures")))
+        {
+            pop.insert("captures".to_owned(), captures);
+        }
+        pop.insert("pop".to_owned(), true.into());
+
+        let mut pushed = Vec::new();
+        if let Some(name) = str_field("name") {
+            pushed.push(json!({ "meta_scope": name }));
+        }
+        if let Some(content) = str_field("contentName") {
+            pushed.push(json!({ "meta_content_scope": content }));
+        }
+        let inner = self.patterns(rule.get("patterns"));
+        let end_last = rule
+            .get("applyEndPatternLast")
+            .is_some_and(|v| v.as_bool() == Some(true) || v.as_i64() == Some(1));
+        if end_last {
+            pushed.extend(inner);
+            pushed.push(Value::Object(pop));
+        } else {
+            pushed.push(Value::Object(pop));
+            pushed.extend(inner);
+        }
+
+        let mut push = Map::new();
+        // `name` covers the begin and end matches through `meta_scope`.
+        push.insert("match".to_owned(), begin.into());
+        if let Some(captures) =
+            captures(rule.get("beginCaptures")).or_else(|| captures(rule.get("captures")))
+        {
+            push.insert("captures".to_owned(), captures);
+        }
+        push.insert("push".to_owned(), Value::Array(pushed));
+        vec![Value::Object(push)]
+    }
+}
+
+/// Whether a rule only groups other patterns.
+fn is_container(rule: &Value) -> bool {
+    ["match", "begin", "include"]
+        .iter()
+        .all(|key| rule.get(key).is_none())
+}
+
+fn context_name(key: &str) -> String {
+    format!("repository.{key}")
+}
+
+fn include_target(include: &str) -> String {
+    match include {
+        "$self" | "$base" => "main".to_owned(),
+        local if local.starts_with('#') => context_name(&local[1..]),
+        // `source.x#rule` can only be approximated by the whole grammar.
+        external => format!("scope:{}", external.split('#').next().unwrap_or(external)),
+    }
+}
+
+/// `{"1": {"name": "x"}}` becomes `{"1": "x"}`; nested capture patterns
+/// are not supported and ignored.
+fn captures(captures: Option<&Value>) -> Option<Value> {
+    let captures = captures?.as_object()?;
+    let mapped: Map<String, Value> = captures
+        .iter()
+        .filter(|(index, _)| index.parse::<usize>().is_ok())
+        .filter_map(|(index, capture)| {
+            let name = capture.get("name")?.as_str()?;
+            Some((index.clone(), name.into()))
+        })
+        .collect();
+    (!mapped.is_empty()).then_some(Value::Object(mapped))
+}
+
+fn compiles(pattern: &str) -> bool {
+    // Back-references to `begin` captures are only resolved at match time.
+    let mut probe = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\'
+            && chars
+                .peek()
+                .is_some_and(|d| d.is_ascii_digit() && *d != '0')
+        {
+            chars.next();
+            probe.push('x');
+        } else {
+            probe.push(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    probe.push(escaped);
+                }
+            }
+        }
+    }
+    Regex::try_compile(&probe).is_none()
+}
+
+/// Renders `value` as YAML flow syntax. JSON is almost that already, but
+/// capture groups must be integer keys, which JSON cannot express.
+fn to_yaml(value: &Value) -> String {
+    match value {
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(to_yaml).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Object(entries) => {
+            let entries: Vec<String> = entries
+                .iter()
+                .map(|(key, value)| {
+                    let key = if key.parse::<usize>().is_ok() {
+                        key.clone()
+                    } else {
+                        Value::from(key.as_str()).to_string()
+                    };
+                    format!("{key}: {}", to_yaml(value))
+                })
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        scalar => scalar.to_string(),
+    }
+}
@@ -0,0 +1,419 @@
+//! Third-party TextMate grammars for languages syntect does not bundle.
+//!
+//! `cyberdeck grammar fetch` downloads a published VS Code extension (from
+//! Open VSX or the Visual Studio Marketplace) or a GitHub repository,
+//! extracts the JSON grammars its `package.json` contributes and caches them
+//! under the archive's SHA-256. The project records what it fetched in
+//! `cyberdeck-grammars.toml`, so later fetches can verify the archive has not
+//! changed and the tokenizer can load the cached grammars.
+
+pub mod convert;
+
+use std::fmt;
+use std::fs;
+use std::io::{Cursor, Read, Seek};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use zip::ZipArchive;
+
+use crate::project::Project;
+
+/// The lock file, relative to the project root.
+pub const LOCK_FILE: &str = "cyberdeck-grammars.toml";
+
+/// Upper bound on a downloaded archive.
+#[cfg(feature = "net")]
+const MAX_ARCHIVE_BYTES: u64 = 256 * 1024 * 1024;
+/// The per-archive index written next to the cached grammars.
+const INDEX_FILE: &str = "grammars.json";
+
+/// Where an extension archive comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `open-vsx:<namespace>.<name>`, or a bare extension ID.
+    OpenVsx { namespace: String, name: String },
+    /// `marketplace:<publisher>.<name>`; needs an explicit version.
+    Marketplace { publisher: String, name: String },
+    /// `github:<owner>/<repo>[@<ref>]`.
+    GitHub {
+        owner: String,
+        repo: String,
+        reference: Option<String>,
+    },
+    /// `file:<path>` to a local `.vsix` or zip archive.
+    File(PathBuf),
+}
+
+impl FromStr for Source {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> Result<Source> {
+        let (kind, rest) = text.split_once(':').unwrap_or(("open-vsx", text));
+        let extension_id = |rest: &str| -> Result<(String, String)> {
+            match rest.split_once('.') {
+                Some((publisher, name)) if !publisher.is_empty() && !name.is_empty() => {
+                    check_url_part(rest, publisher)?;
+                    check_url_part(rest, name)?;
+                    Ok((publisher.to_owned(), name.to_owned()))
+                }
+                _ => bail!("`{rest}` is not an extension ID like `publisher.name`"),
+            }
+        };
+        Ok(match kind {
+            "open-vsx" => {
+                let (namespace, name) = extension_id(rest)?;
+                Source::OpenVsx { namespace, name }
+            }
+            "marketplace" => {
+                let (publisher, name) = extension_id(rest)?;
+                Source::Marketplace { publisher, name }
+            }
+            "github" => {
+                let (repo, reference) = match rest.split_once('@') {
+                    Some((repo, reference)) => (repo, Some(reference.to_owned())),
+                    None => (rest, None),
+                };
+                let Some((owner, repo)) = repo.split_once('/') else {
+                    bail!("`{rest}` is not a repository like `owner/repo`");
+                };
+                check_url_part(rest, owner)?;
+                check_url_part(rest, repo)?;
+                Source::GitHub {
+                    owner: owner.to_owned(),
+                    repo: repo.to_owned(),
+                    reference,
+                }
+            }
+            "file" => Source::File(PathBuf::from(rest)),
+            _ => bail!(
+                "unknown grammar source `{kind}` (expected open-vsx, marketplace, github or file)"
+            ),
+        })
+    }
+}
+
+/// Checks that `part` of the source `spec` can go into a download URL as
+/// is: letters, digits, `.`, `_` and `-`, and not a `.` or `..` segment.
+fn check_url_part(spec: &str, part: &str) -> Result<()> {
+    let allowed = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-');
+    if part.is_empty() || matches!(part, "." | "..") || !part.chars().all(allowed) {
+        bail!("`{part}` in `{spec}` may only contain letters, digits, `.`, `_` and `-`");
+    }
+    Ok(())
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::OpenVsx { namespace, name } => write!(f, "open-vsx:{namespace}.{name}"),
+            Source::Marketplace { publisher, name } => write!(f, "marketplace:{publisher}.{name}"),
+            Source::GitHub {
+                owner,
+                repo,
+                reference,
+            } => {
+                write!(f, "github:{owner}/{repo}")?;
+                match reference {
+                    Some(reference) => write!(f, "@{reference}"),
+                    None => Ok(()),
+                }
+            }
+            Source::File(path) => write!(f, "file:{}", path.display()),
+        }
+    }
+}
+
+/// A downloaded archive.
+pub struct Archive {
+    /// The resolved version: the extension version, git ref or `local`.
+    pub version: String,
+    pub sha256: String,
+    pub bytes: Vec<u8>,
+}
+
+impl Source {
+    /// Downloads the archive, at `version` if given, else the latest.
+    pub fn download(&self, version: Option<&str>) -> Result<Archive> {
+        let (version, bytes) = match self {
+            Source::OpenVsx { namespace, name } => {
+                let url = format!(
+                    "https://open-vsx.org/api/{namespace}/{name}/{}",
+                    version.unwrap_or("latest")
+                );
+                let metadata: Value = serde_json::from_slice(&get(&url)?)
+                    .with_context(|| format!("failed to parse the response from {url}"))?;
+                let version = metadata
+                    .get("version")
+                    .and_then(Value::as_str)
+                    .with_context(|| format!("{url} did not report a version"))?;
+                let download = metadata
+                    .pointer("/files/download")
+                    .and_then(Value::as_str)
+                    .with_context(|| format!("{url} did not report a download link"))?;
+                (version.to_owned(), get(download)?)
+            }
+            Source::Marketplace { publisher, name } => {
+                let version = version.with_context(|| {
+                    format!("{self} needs --version; the marketplace has no stable `latest` link")
+                })?;
+                let url = format!(
+                    "https://marketplace.visualstudio.com/_apis/public/gallery/publishers/\
+                     {publisher}/vsextensions/{name}/{version}/vspackage"
+                );
+                (version.to_owned(), get(&url)?)
+            }
+            Source::GitHub {
+                owner,
+                repo,
+                reference,
+            } => {
+                let reference = version.or(reference.as_deref()).unwrap_or("HEAD");
+                let url = format!("https://codeload.github.com/{owner}/{repo}/zip/{reference}");
+                (reference.to_owned(), get(&url)?)
+            }
+            Source::File(path) => {
+                let bytes =
+                    fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+                (version.unwrap_or("local").to_owned(), bytes)
+            }
+        };
+        Ok(Archive {
+            version,
+            sha256: sha256_hex(&bytes),
+            bytes,
+        })
+    }
+}
+
+#[cfg(feature = "net")]
+fn get(url: &str) -> Result<Vec<u8>> {
+    let mut response = ureq::get(url)
+        .header(
+            "User-Agent",
+            concat!("cyberdeck/", env!("CARGO_PKG_VERSION")),
+        )
+        .call()
+        .with_context(|| format!("failed to download {url}"))?;
+    response
+        .body_mut()
+        .with_config()
+        .limit(MAX_ARCHIVE_BYTES)
+        .read_to_vec()
+        .with_context(|| format!("failed to download {url}"))
+}
+
+#[cfg(not(feature = "net"))]
+fn get(url: &str) -> Result<Vec<u8>> {
+    bail!("cannot download {url}: built without the `net` feature")
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// One grammar extracted into the cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedGrammar {
+    pub scope_name: String,
+    /// The VS Code language ID, if the grammar is a language's main grammar.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// File extensions, without the dot.
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// File name inside the archive's cache directory: the SHA-256 of the
+    /// scope name, never the scope name itself.
+    pub file: String,
+}
+
+/// The user-wide cache directory: `$CYBERDECK_CACHE_DIR`, else the XDG
+/// cache directory.
+pub fn cache_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os("CYBERDECK_CACHE_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_CACHE_HOME") {
+        return Ok(PathBuf::from(dir).join("cyberdeck"));
+    }
+    let home = std::env::var_os("HOME").context("neither CYBERDECK_CACHE_DIR nor HOME is set")?;
+    Ok(PathBuf::from(home).join(".cache").join("cyberdeck"))
+}
+
+/// The cache directory of the archive with the given checksum.
+pub fn archive_dir(sha256: &str) -> Result<PathBuf> {
+    Ok(cache_dir()?.join("grammars").join(sha256))
+}
+
+/// Extracts the JSON grammars `archive` contributes into its cache
+/// directory. Grammars in other formats are reported and skipped.
+pub fn install(archive: &Archive) -> Result<Vec<CachedGrammar>> {
+    let mut zip = ZipArchive::new(Cursor::new(&archive.bytes)).context("not a zip archive")?;
+    let (base, manifest) = read_manifest(&mut zip)?;
+
+    let dir = archive_dir(&archive.sha256)?;
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let mut grammars = Vec::new();
+    let contributed = manifest
+        .pointer("/contributes/grammars")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten();
+    for entry in contributed {
+        let field = |key: &str| entry.get(key).and_then(Value::as_str);
+        let (Some(scope_name), Some(path)) = (field("scopeName"), field("path")) else {
+            continue;
+        };
+        if !path.ends_with(".json") {
+            eprintln!("warning: skipping {scope_name}: only JSON grammars are supported ({path})");
+            continue;
+        }
+        let entry_name = format!("{base}{}", path.trim_start_matches("./"));
+        let text = read_entry(&mut zip, &entry_name)?;
+        // The scope name comes from the archive, so it only goes in the
+        // index; the file is named by its hash to stay inside `dir`.
+        let file = format!("{}.tmLanguage.json", sha256_hex(scope_name.as_bytes()));
+        fs::write(dir.join(&file), text)
+            .with_context(|| format!("failed to write {}", dir.join(&file).display()))?;
+        let language = field("language").map(str::to_owned);
+        grammars.push(CachedGrammar {
+            extensions: language
+                .as_deref()
+                .map(|id| language_extensions(&manifest, id))
+                .unwrap_or_default(),
+            scope_name: scope_name.to_owned(),
+            language,
+            file,
+        });
+    }
+
+    let index = dir.join(INDEX_FILE);
+    fs::write(&index, serde_json::to_string_pretty(&grammars)?)
+        .with_context(|| format!("failed to write {}", index.display()))?;
+    Ok(grammars)
+}
+
+/// The grammars cached for an archive, or `None` if it was never installed.
+pub fn cached(sha256: &str) -> Result<Option<Vec<CachedGrammar>>> {
+    let index = archive_dir(sha256)?.join(INDEX_FILE);
+    if !index.is_file() {
+        return Ok(None);
+    }
+    let text = fs::read_to_string(&index)
+        .with_context(|| format!("failed to read {}", index.display()))?;
+    serde_json::from_str(&text)
+        .map(Some)
+        .with_context(|| format!("failed to parse {}", index.display()))
+}
+
+/// The extension's `package.json` (the outermost, for repository archives
+/// that nest it) and the directory prefix it sits under.
+pub(crate) fn read_manifest<R: Read + Seek>(zip: &mut ZipArchive<R>) -> Result<(String, Value)> {
+    let manifest_path = zip
+        .file_names()
+        .filter_map(|name| name.ok().map(|name| name.into_owned()))
+        .filter(|name| name == "package.json" || name.ends_with("/package.json"))
+        .min_by_key(|name| name.matches('/').count())
+        .context("the archive has no package.json")?;
+    let manifest: Value = serde_json::from_str(&read_entry(zip, &manifest_path)?)
+        .with_context(|| format!("failed to parse {manifest_path}"))?;
+    let base = manifest_path.trim_end_matches("package.json").to_owned();
+    Ok((base, manifest))
+}
+
+pub(crate) fn read_entry<R: Read + Seek>(zip: &mut ZipArchive<R>, name: &str) -> Result<String> {
+    let mut file = zip
+        .by_name(name)
+        .with_context(|| format!("the archive has no {name}"))?;
+    let mut text = String::new();
+    file.read_to_string(&mut text)
+        .with_context(|| format!("failed to read {name} from the archive"))?;
+    Ok(text)
+}
+
+fn language_extensions(manifest: &Value, id: &str) -> Vec<String> {
+    manifest
+        .pointer("/contributes/languages")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|language| language.get("id").and_then(Value::as_str) == Some(id))
+        .filter_map(|language| language.get("extensions")?.as_array())
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(|e| e.trim_start_matches('.').to_owned())
+        .collect()
+}
+
+/// `cyberdeck-grammars.toml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Lock {
+    #[serde(rename = "grammar", default)]
+    pub entries: Vec<LockEntry>,
+}
+
+/// One fetched archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockEntry {
+    pub source: String,
+    pub version: String,
+    pub sha256: String,
+    /// The grammar scopes the archive provided.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+impl Lock {
+    pub fn path(project: &Project) -> PathBuf {
+        project.root.join(LOCK_FILE)
+    }
+
+    /// Reads the project's lock file; a missing file is an empty lock.
+    pub fn load(project: &Project) -> Result<Lock> {
+        let path = Lock::path(project);
+        if !path.is_file() {
+            return Ok(Lock::default());
+        }
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, project: &Project) -> Result<()> {
+        let path = Lock::path(project);
+        let text = format!(
+            "# Grammars fetched by `cyberdeck grammar fetch`; do not edit by hand.\n\n{}",
+            toml::to_string(self)?
+        );
+        fs::write(&path, text).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn entry(&self, source: &str) -> Option<&LockEntry> {
+        self.entries.iter().find(|e| e.source == source)
+    }
+
+    /// Adds or replaces the entry for `entry.source`.
+    pub fn upsert(&mut self, entry: LockEntry) {
+        match self.entries.iter_mut().find(|e| e.source == entry.source) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+        self.entries.sort_by(|a, b| a.source.cmp(&b.source));
+    }
+}
+
+/// Reads a cached grammar's JSON.
+pub fn read_cached(sha256: &str, grammar: &CachedGrammar) -> Result<Value> {
+    let path = archive_dir(sha256)?.join(&grammar.file);
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))
+}
@@ -0,0 +1,226 @@
+//! Tokenizing source files with the available grammars and styling the tokens
+//! with a theme.
+//!
+//! Grammars come from syntect's bundled Sublime Text syntax set, whose scope
+//! names follow the same TextMate conventions VS Code grammars use, plus any
+//! grammars the project fetched with `cyberdeck grammar fetch`.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use syntect::parsing::syntax_definition::Pattern;
+use syntect::parsing::{ParseState, Scope, ScopeStack, SyntaxReference, SyntaxSet};
+
+use crate::grammar::convert::convert;
+use crate::grammar::{self, Lock};
+use crate::project::Project;
+use crate::scope::{ResolvedStyle, Resolver};
+
+/// Extensions without a bundled grammar, mapped to the closest one that has.
+const EXTENSION_FALLBACKS: &[(&str, &str)] = &[("ts", "js"), ("tsx", "js"), ("mts", "js")];
+
+/// A run of text and its scope stack, outermost scope first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub text: String,
+    pub scopes: Vec<String>,
+}
+
+/// The tokens of one source line, without the line terminator.
+pub type Line = Vec<Token>;
+
+/// A tokenized source file.
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub path: PathBuf,
+    /// The grammar's name, or `None` when no grammar applies and the
+    /// file was read as unscoped plain text.
+    pub language: Option<String>,
+    pub lines: Vec<Line>,
+}
+
+impl Document {
+    /// The file name, for display.
+    pub fn title(&self) -> String {
+        self.path.file_name().map_or_else(
+            || self.path.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        )
+    }
+}
+
+pub struct Grammars {
+    set: SyntaxSet,
+}
+
+impl Grammars {
+    /// The grammars bundled into the binary.
+    pub fn bundled() -> Grammars {
+        Grammars {
+            set: SyntaxSet::load_defaults_newlines(),
+        }
+    }
+
+    /// The bundled grammars plus those the project's grammar lock file
+    /// lists. Cached grammars that are missing or fail to convert are
+    /// reported and skipped; outside a project this is just the bundled set.
+    pub fn installed() -> Result<Grammars> {
+        let Ok(project) = Project::current() else {
+            return Ok(Grammars::bundled());
+        };
+        let lock = Lock::load(&project)?;
+        if lock.entries.is_empty() {
+            return Ok(Grammars::bundled());
+        }
+
+        let mut builder = SyntaxSet::load_defaults_newlines().into_builder();
+        for entry in &lock.entries {
+            let Some(cached) = grammar::cached(&entry.sha256)? else {
+                eprintln!(
+                    "warning: {} is not cached; run `cyberdeck grammar fetch`",
+                    entry.source
+                );
+                continue;
+            };
+            for cached in cached {
+                let converted = grammar::read_cached(&entry.sha256, &cached)
+                    .and_then(|json| convert(&json, &cached.scope_name, &cached.extensions));
+                match converted {
+                    Ok(converted) => builder.add(converted.definition),
+                    Err(err) => eprintln!("warning: skipping {}: {err:#}", cached.scope_name),
+                }
+            }
+        }
+        Ok(Grammars {
+            set: builder.build(),
+        })
+    }
+
+    /// The grammar for a file, chosen by extension.
+    pub fn for_path(&self, path: &Path) -> Option<&SyntaxReference> {
+        let extension = path.extension()?.to_str()?;
+        self.set.find_syntax_by_extension(extension).or_else(|| {
+            let (_, fallback) = EXTENSION_FALLBACKS.iter().find(|(e, _)| *e == extension)?;
+            self.set.find_syntax_by_extension(fallback)
+        })
+    }
+
+    /// Reads and tokenizes a file.
+    pub fn load(&self, path: &Path) -> Result<Document> {
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        self.parse(path, &source)
+    }
+
+    /// Tokenizes `source` as the contents of a file at `path`, which only
+    /// picks the grammar and names the document.
+    pub fn parse(&self, path: &Path, source: &str) -> Result<Document> {
+        let (language, lines) = match self.for_path(path) {
+            Some(syntax) => (
+                Some(syntax.name.clone()),
+                self.tokenize(syntax, source)
+                    .with_context(|| format!("failed to tokenize {}", path.display()))?,
+            ),
+            None => (
+                None,
+                source
+                    .lines()
+                    .map(|line| {
+                        vec![Token {
+                            text: line.to_owned(),
+                            scopes: Vec::new(),
+                        }]
+                    })
+                    .collect(),
+            ),
+        };
+        Ok(Document {
+            path: path.to_path_buf(),
+            language,
+            lines,
+        })
+    }
+
+    /// Every scope each grammar can assign, keyed by grammar name. Scopes
+    /// only reachable through another grammar (embedded languages) are
+    /// attributed to that grammar.
+    pub fn emittable_scopes(&self) -> BTreeMap<String, BTreeSet<String>> {
+        let builder = self.set.clone().into_builder();
+        let mut inventory = BTreeMap::new();
+        for definition in builder.syntaxes() {
+            let mut scopes = BTreeSet::new();
+            let mut add = |list: &[Scope]| scopes.extend(list.iter().map(|s| s.build_string()));
+            add(&[definition.scope]);
+            for context in definition.contexts.values() {
+                add(&context.meta_scope);
+                add(&context.meta_content_scope);
+                for pattern in &context.patterns {
+                    if let Pattern::Match(pattern) = pattern {
+                        add(&pattern.scope);
+                        for (_, captured) in pattern.captures.iter().flatten() {
+                            add(captured);
+                        }
+                    }
+                }
+            }
+            inventory.insert(definition.name.clone(), scopes);
+        }
+        inventory
+    }
+
+    /// Splits `source` into lines of scoped tokens.
+    pub fn tokenize(&self, syntax: &SyntaxReference, source: &str) -> Result<Vec<Line>> {
+        let mut state = ParseState::new(syntax);
+        let mut stack = ScopeStack::new();
+        let mut lines = Vec::new();
+        for raw in source.split_inclusive('\n') {
+            let ops = state.parse_line(raw, &self.set)?;
+            let text = raw.trim_end_matches(['\n', '\r']);
+            let mut line = Vec::new();
+            let mut start = 0;
+            for (offset, op) in ops {
+                push_token(&mut line, text, start, offset, &stack);
+                start = start.max(offset);
+                stack.apply(&op)?;
+            }
+            push_token(&mut line, text, start, raw.len(), &stack);
+            lines.push(line);
+        }
+        Ok(lines)
+    }
+}
+
+fn push_token(line: &mut Line, text: &str, start: usize, end: usize, stack: &ScopeStack) {
+    let end = end.min(text.len());
+    if start >= end {
+        return;
+    }
+    line.push(Token {
+        text: text[start..end].to_owned(),
+        scopes: stack.as_slice().iter().map(|s| s.build_string()).collect(),
+    });
+}
+
+/// Resolves token styles, caching by scope stack.
+pub struct Styler<'a> {
+    resolver: &'a Resolver,
+    cache: HashMap<Vec<String>, ResolvedStyle>,
+}
+
+impl<'a> Styler<'a> {
+    pub fn new(resolver: &'a Resolver) -> Styler<'a> {
+        Styler {
+            resolver,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn style(&mut self, token: &Token) -> &ResolvedStyle {
+        let resolver = self.resolver;
+        self.cache
+            .entry(token.scopes.clone())
+            .or_insert_with(|| resolver.resolve(&token.scopes))
+    }
+}
@@ -0,0 +1,145 @@
+//! Sampling base16 and base24 schemes.
+//!
+//! Both the classic layout (`scheme:` and `base00`… at the top level, hex
+//! without `#`) and the tinted-theming layout (`name:` and a `palette:`
+//! map) are read. Each slot becomes one sample carrying the roles a
+//! [`Mapping`] gives it.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use yaml_rust::{Yaml, YamlLoader};
+
+use crate::color::Color;
+
+use super::{Mapping, Sample};
+
+/// Which roles each slot plays, after the base16 styling guidelines. base24
+/// adds darker backgrounds (`base10`, `base11`) and bright accents
+/// (`base12`…`base17`), which are kept as unnamed colors.
+pub const DEFAULT_MAPPING: &[(&str, &[&str])] = &[
+    ("base00", &["background"]),
+    ("base01", &["background.secondary", "border"]),
+    ("base02", &["highlight"]),
+    ("base03", &["comment", "foreground.muted"]),
+    ("base04", &["foreground.secondary"]),
+    ("base05", &["foreground", "cursor"]),
+    (
+        "base08",
+        &["variable", "parameter", "property", "error", "deleted"],
+    ),
+    ("base09", &["number", "constant"]),
+    ("base0A", &["class", "warning"]),
+    ("base0B", &["string", "success", "inserted"]),
+    ("base0C", &["type", "accent.tertiary"]),
+    ("base0D", &["function", "accent.primary"]),
+    (
+        "base0E",
+        &["keyword", "storage", "modified", "accent.secondary"],
+    ),
+    ("base10", &["background.tertiary"]),
+];
+
+/// A parsed scheme: its name and slot colors in file order.
+#[derive(Debug, Clone)]
+pub struct Scheme {
+    pub name: Option<String>,
+    pub colors: IndexMap<String, Color>,
+}
+
+impl Scheme {
+    pub fn load(path: &Path) -> Result<Scheme> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Scheme::parse(&text).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Scheme> {
+        let docs = YamlLoader::load_from_str(text)?;
+        let Some(Yaml::Hash(root)) = docs.into_iter().next() else {
+            bail!("expected a mapping at the top level");
+        };
+        let field = |key: &str| root.get(&Yaml::String(key.to_owned()));
+        let name = field("name")
+            .or_else(|| field("scheme"))
+            .and_then(Yaml::as_str)
+            .map(str::to_owned);
+        let slots = match field("palette") {
+            Some(Yaml::Hash(palette)) => palette,
+            Some(_) => bail!("`palette` must be a mapping"),
+            None => &root,
+        };
+
+        let mut colors = IndexMap::new();
+        for (key, value) in slots {
+            let Some(slot) = key.as_str().filter(|k| is_slot(k)) else {
+                continue;
+            };
+            let Some(value) = value.as_str() else {
+                bail!("`{slot}` must be a string");
+            };
+            let hex = value.trim();
+            let color = if hex.starts_with('#') {
+                hex.parse()
+            } else {
+                format!("#{hex}").parse()
+            }
+            .with_context(|| format!("`{slot}` is not a hex color"))?;
+            colors.insert(canonical_slot(slot), color);
+        }
+        if !colors.contains_key("base00") || !colors.contains_key("base05") {
+            bail!("not a base16 scheme: base00 and base05 are required");
+        }
+        Ok(Scheme { name, colors })
+    }
+
+    /// One sample per slot, with the roles `mapping` assigns it.
+    pub fn samples(&self, mapping: &Mapping) -> Vec<Sample> {
+        self.colors
+            .iter()
+            .map(|(slot, color)| Sample {
+                color: *color,
+                weight: 1,
+                roles: mapping.get(slot).cloned().unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// [`DEFAULT_MAPPING`] with `overrides` replacing whole slots. A role an
+/// override claims is taken from the slot that had it by default, so each
+/// role still names one color.
+pub fn mapping(overrides: Mapping) -> Result<Mapping> {
+    let mut mapping: Mapping = DEFAULT_MAPPING
+        .iter()
+        .map(|(slot, roles)| {
+            let roles = roles.iter().map(|r| (*r).to_owned()).collect();
+            ((*slot).to_owned(), roles)
+        })
+        .collect();
+    for (slot, roles) in overrides {
+        if !is_slot(&slot) {
+            bail!("`{slot}` is not a base16 or base24 slot");
+        }
+        for taken in mapping.values_mut() {
+            taken.retain(|role| !roles.contains(role));
+        }
+        mapping.insert(canonical_slot(&slot), roles);
+    }
+    Ok(mapping)
+}
+
+/// `base00`…`base0F` and base24's `base10`…`base17`, in any case.
+fn is_slot(key: &str) -> bool {
+    key.len() == 6
+        && key.is_ascii()
+        && key[..4].eq_ignore_ascii_case("base")
+        && key[4..].bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Slots as the guidelines spell them: `base0a` → `base0A`.
+fn canonical_slot(slot: &str) -> String {
+    format!("base{}", slot[4..].to_ascii_uppercase())
+}
@@ -0,0 +1,197 @@
+//! Sampling W3C Design Tokens (DTCG) files.
+//!
+//! Every color token becomes one sample, keyed by its dot-separated path
+//! (`color.brand.primary`). `$type` is inherited from enclosing groups and
+//! `{path}` aliases are followed. Values may be hex strings or the
+//! structured `{ "colorSpace", "components", "alpha" }` form in the `srgb`
+//! and `oklch` spaces, or any space with a `hex` fallback.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::color::{Color, Oklch};
+use crate::roles::ROLES;
+
+use super::{Mapping, Sample};
+
+/// How many aliases to follow before giving up on a cycle.
+const MAX_DEPTH: usize = 16;
+
+/// The color tokens of a file, by path in file order.
+#[derive(Debug, Clone)]
+pub struct Tokens {
+    pub colors: IndexMap<String, Color>,
+}
+
+impl Tokens {
+    pub fn load(path: &Path) -> Result<Tokens> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Tokens::parse(&text).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Tokens> {
+        let root: Value = serde_json::from_str(text)?;
+        if !root.is_object() {
+            bail!("expected a token group at the top level");
+        }
+        let mut tokens = IndexMap::new();
+        collect(&root, "", None, &mut tokens);
+
+        let mut colors = IndexMap::new();
+        for (path, (kind, _)) in &tokens {
+            if kind.as_deref().is_some_and(|k| k != "color") {
+                continue;
+            }
+            match resolve(&tokens, path, 0) {
+                Ok(color) => {
+                    colors.insert(path.clone(), color);
+                }
+                // Untyped tokens that aren't colors are some other kind.
+                Err(_) if kind.is_none() => {}
+                Err(err) => return Err(err.context(format!("invalid color token `{path}`"))),
+            }
+        }
+        Ok(Tokens { colors })
+    }
+
+    pub fn samples(&self, mapping: &Mapping) -> Vec<Sample> {
+        self.colors
+            .iter()
+            .map(|(path, color)| Sample {
+                color: *color,
+                weight: 1,
+                roles: mapping.get(path).cloned().unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Which roles each token plays. By default a token plays the roles its
+    /// path ends in (`color.foreground.muted` → `foreground.muted`), with
+    /// `-` also standing for `.` in the last segment (`color.accent-primary`
+    /// → `accent.primary`), the first such token winning; `overrides` replace whole tokens and take
+    /// the roles they claim from the tokens that had them.
+    pub fn mapping(&self, overrides: Mapping) -> Result<Mapping> {
+        let mut mapping = Mapping::new();
+        for (role, _) in ROLES {
+            let suffix = format!(".{role}");
+            let token = self.colors.keys().find(|path| {
+                let leaf = path.rsplit('.').next().unwrap_or_default();
+                path.as_str() == *role || path.ends_with(&suffix) || leaf.replace('-', ".") == *role
+            });
+            if let Some(token) = token {
+                mapping
+                    .entry(token.clone())
+                    .or_default()
+                    .push((*role).to_owned());
+            }
+        }
+        for (path, roles) in overrides {
+            if !self.colors.contains_key(&path) {
+                bail!("`{path}` is not a color token");
+            }
+            for taken in mapping.values_mut() {
+                taken.retain(|role| !roles.contains(role));
+            }
+            mapping.insert(path, roles);
+        }
+        Ok(mapping)
+    }
+}
+
+/// Every token under `group` as its `$type` (own or inherited) and `$value`.
+fn collect<'a>(
+    group: &'a Value,
+    prefix: &str,
+    inherited: Option<&str>,
+    tokens: &mut IndexMap<String, (Option<String>, &'a Value)>,
+) {
+    let Some(members) = group.as_object() else {
+        return;
+    };
+    let kind = members.get("$type").and_then(Value::as_str).or(inherited);
+    for (key, member) in members.iter().filter(|(k, _)| !k.starts_with('$')) {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match member.get("$value") {
+            Some(value) => {
+                let kind = member.get("$type").and_then(Value::as_str).or(kind);
+                tokens.insert(path, (kind.map(str::to_owned), value));
+            }
+            None => collect(member, &path, kind, tokens),
+        }
+    }
+}
+
+/// The color the token at `path` resolves to, following aliases.
+fn resolve(
+    tokens: &IndexMap<String, (Option<String>, &Value)>,
+    path: &str,
+    depth: usize,
+) -> Result<Color> {
+    let Some((_, value)) = tokens.get(path) else {
+        bail!("alias to missing token `{path}`");
+    };
+    if let Some(target) = value
+        .as_str()
+        .and_then(|s| s.strip_prefix('{'))
+        .and_then(|s| s.strip_suffix('}'))
+    {
+        if depth >= MAX_DEPTH {
+            bail!("aliases nest deeper than {MAX_DEPTH} levels");
+        }
+        return resolve(tokens, target, depth + 1);
+    }
+    parse_value(value)
+}
+
+fn parse_value(value: &Value) -> Result<Color> {
+    if let Some(hex) = value.as_str() {
+        return hex
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("`{hex}` is not a hex color"));
+    }
+    let Some(object) = value.as_object() else {
+        bail!("expected a hex string or a color object");
+    };
+    let components: Vec<f64> = object
+        .get("components")
+        .and_then(Value::as_array)
+        .map(|c| c.iter().map(|v| v.as_f64().unwrap_or(0.0)).collect())
+        .unwrap_or_default();
+    let color = match (
+        object.get("colorSpace").and_then(Value::as_str),
+        &components[..],
+    ) {
+        (Some("srgb"), [r, g, b]) => {
+            let channel = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+            Color::rgb(channel(*r), channel(*g), channel(*b))
+        }
+        (Some("oklch"), [l, c, h]) => Color::from_oklch(Oklch {
+            l: *l,
+            c: *c,
+            h: *h,
+        }),
+        (space, _) => match object.get("hex").and_then(Value::as_str) {
+            Some(hex) => hex
+                .parse()
+                .map_err(|_| anyhow!("`{hex}` is not a hex color"))?,
+            None => bail!(
+                "unsupported color space `{}` without a `hex` fallback",
+                space.unwrap_or_default()
+            ),
+        },
+    };
+    Ok(match object.get("alpha").and_then(Value::as_f64) {
+        Some(alpha) => color.with_alpha((alpha.clamp(0.0, 1.0) * 255.0).round() as u8),
+        None => color,
+    })
+}
@@ -0,0 +1,280 @@
+//! Reading Figma variables exports: the REST API's local variables response
+//! (`meta.variableCollections` and `meta.variables`, values by mode ID) and
+//! the collections → modes → variables layout of the variables2json
+//! plugin.
+//!
+//! A variant is read per mode name. Collections with a mode of that name
+//! contribute it, the others (typically single-mode primitives) their
+//! default mode, and aliases resolve the same way in the collection they
+//! point into. Variable names become token paths with `/` read as `.`
+//! (`bg/primary` → `bg.primary`), so mapping works as for design tokens.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::color::Color;
+
+use super::dtcg::Tokens;
+
+/// How many aliases to follow before giving up on a cycle.
+const MAX_DEPTH: usize = 16;
+
+/// Every collection of an export.
+#[derive(Debug, Clone)]
+pub struct Variables {
+    collections: Vec<Collection>,
+}
+
+#[derive(Debug, Clone)]
+struct Collection {
+    name: String,
+    /// Color variables by name, per mode name; the first mode is the
+    /// default.
+    modes: IndexMap<String, IndexMap<String, Raw>>,
+}
+
+#[derive(Debug, Clone)]
+enum Raw {
+    Color(Color),
+    Alias { collection: String, name: String },
+}
+
+impl Variables {
+    pub fn load(path: &Path) -> Result<Variables> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Variables::parse(&text).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Variables> {
+        let root: Value = serde_json::from_str(text)?;
+        let api = root.get("meta").unwrap_or(&root);
+        if api.get("variableCollections").is_some() {
+            from_api(api)
+        } else if root.get("collections").is_some() {
+            from_plugin(&root)
+        } else {
+            bail!("expected Figma's `variableCollections` or a `collections` list")
+        }
+    }
+
+    /// The mode names a variant can be read for: those of the collections
+    /// with several modes, or when none has, of the last collection (the
+    /// semantic layer, usually, with primitives before it).
+    pub fn modes(&self) -> Vec<&str> {
+        let mut modes: Vec<&str> = Vec::new();
+        let themed: Vec<&Collection> = self
+            .collections
+            .iter()
+            .filter(|c| c.modes.len() > 1)
+            .collect();
+        let collections = if themed.is_empty() {
+            self.collections.last().into_iter().collect()
+        } else {
+            themed
+        };
+        for collection in collections {
+            for mode in collection.modes.keys() {
+                if !modes.contains(&mode.as_str()) {
+                    modes.push(mode);
+                }
+            }
+        }
+        modes
+    }
+
+    /// The color variables as they resolve in `mode`.
+    pub fn tokens(&self, mode: &str) -> Result<Tokens> {
+        let mut colors = IndexMap::new();
+        for collection in &self.collections {
+            for name in mode_of(collection, mode).into_iter().flat_map(|m| m.keys()) {
+                let color = self
+                    .resolve(&collection.name, name, mode, 0)
+                    .with_context(|| format!("invalid variable `{name}`"))?;
+                colors.insert(name.replace('/', "."), color);
+            }
+        }
+        Ok(Tokens { colors })
+    }
+
+    fn resolve(&self, collection: &str, name: &str, mode: &str, depth: usize) -> Result<Color> {
+        let raw = self
+            .collections
+            .iter()
+            .find(|c| c.name == collection)
+            .and_then(|c| mode_of(c, mode)?.get(name))
+            .ok_or_else(|| anyhow!("alias to missing variable `{collection}/{name}`"))?;
+        match raw {
+            Raw::Color(color) => Ok(*color),
+            Raw::Alias { .. } if depth >= MAX_DEPTH => {
+                bail!("aliases nest deeper than {MAX_DEPTH} levels")
+            }
+            Raw::Alias { collection, name } => self.resolve(collection, name, mode, depth + 1),
+        }
+    }
+}
+
+/// A collection's variables in `mode`, or its default mode's.
+fn mode_of<'a>(collection: &'a Collection, mode: &str) -> Option<&'a IndexMap<String, Raw>> {
+    collection
+        .modes
+        .get(mode)
+        .or_else(|| collection.modes.values().next())
+}
+
+/// The REST API layout: collections list their modes by ID and variables
+/// hold `valuesByMode`, aliases naming variables by ID.
+fn from_api(api: &Value) -> Result<Variables> {
+    let object = |value: Option<&Value>| value.and_then(Value::as_object).cloned();
+    let collections = object(api.get("variableCollections")).unwrap_or_default();
+    let variables = object(api.get("variables")).unwrap_or_default();
+
+    // Variable ID → (collection name, variable name), for aliases.
+    let mut names = IndexMap::new();
+    for (id, variable) in &variables {
+        let collection = field(variable, "variableCollectionId")
+            .and_then(|c| collections.get(c))
+            .and_then(|c| field(c, "name"));
+        if let (Some(collection), Some(name)) = (collection, field(variable, "name")) {
+            names.insert(id.as_str(), (collection.to_owned(), name.to_owned()));
+        }
+    }
+
+    let mut result = Vec::new();
+    for (id, collection) in &collections {
+        let name = field(collection, "name").unwrap_or(id).to_owned();
+        let mut modes: Vec<(String, String)> = collection
+            .get("modes")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|m| Some((field(m, "modeId")?.to_owned(), field(m, "name")?.to_owned())))
+            .collect();
+        // The default mode first.
+        if let Some(default) = field(collection, "defaultModeId") {
+            modes.sort_by_key(|(id, _)| id != default);
+        }
+        let mut by_mode: IndexMap<String, IndexMap<String, Raw>> = modes
+            .iter()
+            .map(|(_, name)| (name.clone(), IndexMap::new()))
+            .collect();
+        for variable in variables.values() {
+            if field(variable, "variableCollectionId") != Some(id)
+                || field(variable, "resolvedType") != Some("COLOR")
+            {
+                continue;
+            }
+            let Some(variable_name) = field(variable, "name") else {
+                continue;
+            };
+            for (mode_id, mode_name) in &modes {
+                let Some(value) = variable.get("valuesByMode").and_then(|v| v.get(mode_id)) else {
+                    continue;
+                };
+                let raw = if field(value, "type") == Some("VARIABLE_ALIAS") {
+                    let target = field(value, "id").unwrap_or_default();
+                    let Some((collection, name)) = names.get(target) else {
+                        bail!("`{variable_name}` aliases unknown variable `{target}`");
+                    };
+                    Raw::Alias {
+                        collection: collection.clone(),
+                        name: name.clone(),
+                    }
+                } else {
+                    Raw::Color(parse_color(value).with_context(|| {
+                        format!("invalid color for `{variable_name}` in {mode_name}")
+                    })?)
+                };
+                by_mode[mode_name].insert(variable_name.to_owned(), raw);
+            }
+        }
+        result.push(Collection {
+            name,
+            modes: by_mode,
+        });
+    }
+    Ok(Variables {
+        collections: result,
+    })
+}
+
+/// The variables2json layout: `collections[].modes[].variables[]`, aliases
+/// naming their collection and variable.
+fn from_plugin(root: &Value) -> Result<Variables> {
+    let list = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let str_field = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned()
+    };
+    let mut collections = Vec::new();
+    for collection in list(root, "collections") {
+        let mut modes = IndexMap::new();
+        for mode in list(&collection, "modes") {
+            let mut variables = IndexMap::new();
+            for variable in list(&mode, "variables") {
+                if !str_field(&variable, "type").eq_ignore_ascii_case("color") {
+                    continue;
+                }
+                let name = str_field(&variable, "name");
+                let value = variable.get("value").unwrap_or(&Value::Null);
+                let raw = if variable.get("isAlias").and_then(Value::as_bool) == Some(true) {
+                    Raw::Alias {
+                        collection: str_field(value, "collection"),
+                        name: str_field(value, "name"),
+                    }
+                } else {
+                    Raw::Color(
+                        parse_color(value)
+                            .with_context(|| format!("invalid color for `{name}`"))?,
+                    )
+                };
+                variables.insert(name, raw);
+            }
+            modes.insert(str_field(&mode, "name"), variables);
+        }
+        collections.push(Collection {
+            name: str_field(&collection, "name"),
+            modes,
+        });
+    }
+    Ok(Variables { collections })
+}
+
+fn field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key)?.as_str()
+}
+
+/// A hex string or Figma's `{ r, g, b, a }` with 0–1 channels.
+fn parse_color(value: &Value) -> Result<Color> {
+    if let Some(hex) = value.as_str() {
+        return hex
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("`{hex}` is not a hex color"));
+    }
+    let channel = |key: &str, default: Option<f64>| {
+        let value = value.get(key).and_then(Value::as_f64).or(default);
+        value
+            .map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+            .ok_or_else(|| anyhow!("missing `{key}`"))
+    };
+    Ok(Color::rgba(
+        channel("r", None)?,
+        channel("g", None)?,
+        channel("b", None)?,
+        channel("a", Some(1.0))?,
+    ))
+}
@@ -0,0 +1,315 @@
+//! Converting Helix `theme.toml` files into the theme model.
+//!
+//! `ui.*` keys map onto their workbench equivalents and the rest are
+//! tree-sitter captures, which become token rules for the TextMate scope
+//! each corresponds to. Colors are `[palette]` names, hex or the terminal's
+//! sixteen color names (taken at xterm's defaults). A theme that `inherits`
+//! another is layered over it when the parent sits in the same directory,
+//! as in Helix's runtime; built-in parents elsewhere aren't available.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use toml::{Table, Value};
+
+use crate::color::{Color, MID_LUMINANCE};
+use crate::theme::{ScopeSpec, Theme, TokenRule, TokenSettings};
+
+/// `ui.*` keys, which of their colors is read and the workbench key it
+/// becomes; the first key listed for a workbench key wins.
+const UI: &[(&str, Field, &str)] = &[
+    ("ui.background", Field::Bg, "editor.background"),
+    ("ui.text", Field::Fg, "editor.foreground"),
+    ("ui.cursor.primary", Field::Bg, "editorCursor.foreground"),
+    ("ui.cursor", Field::Bg, "editorCursor.foreground"),
+    (
+        "ui.selection.primary",
+        Field::Bg,
+        "editor.selectionBackground",
+    ),
+    ("ui.selection", Field::Bg, "editor.selectionBackground"),
+    (
+        "ui.cursorline.primary",
+        Field::Bg,
+        "editor.lineHighlightBackground",
+    ),
+    ("ui.cursorline", Field::Bg, "editor.lineHighlightBackground"),
+    ("ui.linenr", Field::Fg, "editorLineNumber.foreground"),
+    (
+        "ui.linenr.selected",
+        Field::Fg,
+        "editorLineNumber.activeForeground",
+    ),
+    ("ui.gutter", Field::Bg, "editorGutter.background"),
+    ("ui.statusline", Field::Bg, "statusBar.background"),
+    ("ui.statusline", Field::Fg, "statusBar.foreground"),
+    ("ui.popup", Field::Bg, "editorWidget.background"),
+    ("ui.help", Field::Bg, "editorHoverWidget.background"),
+    ("ui.menu", Field::Bg, "editorSuggestWidget.background"),
+    (
+        "ui.menu.selected",
+        Field::Bg,
+        "editorSuggestWidget.selectedBackground",
+    ),
+    ("ui.window", Field::Fg, "editorGroup.border"),
+    (
+        "ui.virtual.whitespace",
+        Field::Fg,
+        "editorWhitespace.foreground",
+    ),
+    (
+        "ui.virtual.indent-guide",
+        Field::Fg,
+        "editorIndentGuide.background1",
+    ),
+    ("ui.virtual.ruler", Field::Bg, "editorRuler.foreground"),
+    ("error", Field::Fg, "editorError.foreground"),
+    ("warning", Field::Fg, "editorWarning.foreground"),
+    ("info", Field::Fg, "editorInfo.foreground"),
+    ("hint", Field::Fg, "editorHint.foreground"),
+    ("diff.plus", Field::Fg, "editorGutter.addedBackground"),
+    ("diff.delta", Field::Fg, "editorGutter.modifiedBackground"),
+    ("diff.minus", Field::Fg, "editorGutter.deletedBackground"),
+];
+
+/// Tree-sitter captures and the TextMate scope they correspond to. A
+/// capture takes the entry for its longest listed prefix, with the rest of
+/// its name appended (`keyword.control.conditional` →
+/// `keyword.control.conditional`, `function.builtin.static` →
+/// `support.function.static`). Shared with the Neovim importer, whose
+/// `@captures` are the same names.
+const CAPTURES: &[(&str, &str)] = &[
+    ("attribute", "entity.other.attribute-name"),
+    ("boolean", "constant.language.boolean"),
+    ("character", "constant.character"),
+    ("comment", "comment"),
+    ("comment.documentation", "comment.block.documentation"),
+    ("constant", "constant"),
+    ("constant.builtin", "constant.language"),
+    ("constant.character", "constant.character"),
+    ("constant.numeric", "constant.numeric"),
+    ("constructor", "entity.name.function.constructor"),
+    ("diff.delta", "markup.changed"),
+    ("diff.minus", "markup.deleted"),
+    ("diff.plus", "markup.inserted"),
+    ("field", "variable.other.property"),
+    ("float", "constant.numeric.float"),
+    ("function", "entity.name.function"),
+    ("function.builtin", "support.function"),
+    ("function.call", "meta.function-call entity.name.function"),
+    ("function.macro", "entity.name.function.macro"),
+    ("function.method", "entity.name.function.member"),
+    ("include", "keyword.control.import"),
+    ("keyword", "keyword"),
+    ("keyword.directive", "meta.preprocessor"),
+    ("keyword.function", "storage.type.function"),
+    ("keyword.import", "keyword.control.import"),
+    ("keyword.storage", "storage"),
+    ("label", "entity.name.label"),
+    ("markup", "markup"),
+    ("markup.link.url", "markup.underline.link"),
+    ("markup.raw", "markup.inline.raw"),
+    ("module", "entity.name.namespace"),
+    ("namespace", "entity.name.namespace"),
+    ("number", "constant.numeric"),
+    ("operator", "keyword.operator"),
+    ("parameter", "variable.parameter"),
+    ("property", "variable.other.property"),
+    ("punctuation", "punctuation"),
+    ("punctuation.bracket", "punctuation.section"),
+    ("punctuation.delimiter", "punctuation.separator"),
+    ("special", "constant.character"),
+    ("string", "string"),
+    ("string.escape", "constant.character.escape"),
+    ("string.regex", "string.regexp"),
+    ("string.regexp", "string.regexp"),
+    ("string.special", "string.other"),
+    ("tag", "entity.name.tag"),
+    ("tag.attribute", "entity.other.attribute-name"),
+    ("type", "entity.name.type"),
+    ("type.builtin", "support.type"),
+    ("type.enum.variant", "variable.other.enummember"),
+    ("variable", "variable"),
+    ("variable.builtin", "variable.language"),
+    ("variable.member", "variable.other.property"),
+    ("variable.other.member", "variable.other.property"),
+    ("variable.parameter", "variable.parameter"),
+];
+
+/// The terminal color names Helix accepts, in ANSI order (Helix's `gray`
+/// is bright black).
+const TERMINAL_COLORS: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "light-gray",
+    "gray",
+    "light-red",
+    "light-green",
+    "light-yellow",
+    "light-blue",
+    "light-magenta",
+    "light-cyan",
+    "white",
+];
+
+/// How many `inherits` links to follow before giving up on a cycle.
+const MAX_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Fg,
+    Bg,
+}
+
+pub fn load(path: &Path) -> Result<Theme> {
+    let table = load_table(path, 0)?;
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned());
+    from_table(name, &table).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+/// The theme's table layered over the ones it inherits from.
+fn load_table(path: &Path, depth: usize) -> Result<Table> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let mut table: Table =
+        toml::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))?;
+    let Some(parent) = table.get("inherits").and_then(Value::as_str) else {
+        return Ok(table);
+    };
+    let parent_path = path.with_file_name(format!("{parent}.toml"));
+    if !parent_path.is_file() {
+        return Ok(table);
+    }
+    if depth >= MAX_DEPTH {
+        bail!("`inherits` nests deeper than {MAX_DEPTH} levels");
+    }
+    let mut merged = load_table(&parent_path, depth + 1)?;
+    let palette = match (merged.remove("palette"), table.remove("palette")) {
+        (Some(Value::Table(mut base)), Some(Value::Table(own))) => {
+            base.extend(own);
+            Some(Value::Table(base))
+        }
+        (base, own) => own.or(base),
+    };
+    merged.extend(table);
+    if let Some(palette) = palette {
+        merged.insert("palette".to_owned(), palette);
+    }
+    Ok(merged)
+}
+
+/// The theme a parsed `theme.toml` table describes.
+pub fn from_table(name: Option<String>, table: &Table) -> Result<Theme> {
+    let palette = table
+        .get("palette")
+        .and_then(Value::as_table)
+        .cloned()
+        .unwrap_or_default();
+    let color = |value: Option<&Value>| -> Option<Color> {
+        let value = value?.as_str()?;
+        let value = palette.get(value).and_then(Value::as_str).unwrap_or(value);
+        if let Some(index) = TERMINAL_COLORS.iter().position(|c| *c == value) {
+            return Some(Color::from_xterm256(index as u8));
+        }
+        value.parse().ok()
+    };
+    // A bare string is the foreground.
+    let field = |key: &str, field: Field| -> Option<Color> {
+        match (table.get(key)?, field) {
+            (value @ Value::String(_), Field::Fg) => color(Some(value)),
+            (Value::Table(style), Field::Fg) => color(style.get("fg")),
+            (Value::Table(style), Field::Bg) => color(style.get("bg")),
+            _ => None,
+        }
+    };
+
+    let mut colors = IndexMap::new();
+    for (key, which, workbench) in UI {
+        if colors.contains_key(*workbench) {
+            continue;
+        }
+        if let Some(color) = field(key, *which) {
+            colors.insert((*workbench).to_owned(), color.to_string());
+        }
+    }
+
+    let mut token_colors = Vec::new();
+    for (key, value) in table {
+        if key.starts_with("ui.") || matches!(key.as_str(), "palette" | "inherits") {
+            continue;
+        }
+        let Some(scope) = capture_selector(key) else {
+            continue;
+        };
+        let modifiers: Vec<&str> = match value {
+            Value::Table(style) => {
+                let mut modifiers: Vec<&str> = style
+                    .get("modifiers")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|m| match m.as_str()? {
+                        "bold" => Some("bold"),
+                        "italic" => Some("italic"),
+                        "underlined" => Some("underline"),
+                        "crossed_out" => Some("strikethrough"),
+                        _ => None,
+                    })
+                    .collect();
+                if style.contains_key("underline") && !modifiers.contains(&"underline") {
+                    modifiers.push("underline");
+                }
+                modifiers
+            }
+            _ => Vec::new(),
+        };
+        let settings = TokenSettings {
+            foreground: field(key, Field::Fg).map(|c| c.to_string()),
+            background: field(key, Field::Bg).map(|c| c.to_string()),
+            font_style: (!modifiers.is_empty()).then(|| modifiers.join(" ")),
+        };
+        if settings != TokenSettings::default() {
+            token_colors.push(TokenRule {
+                name: Some(key.clone()),
+                scope: ScopeSpec::One(scope),
+                settings,
+            });
+        }
+    }
+
+    let dark = colors
+        .get("editor.background")
+        .and_then(|c| c.parse::<Color>().ok())
+        .is_none_or(|c| c.relative_luminance() < MID_LUMINANCE);
+    Ok(Theme {
+        name,
+        kind: Some(if dark { "dark" } else { "light" }.to_owned()),
+        colors,
+        token_colors,
+        ..Theme::default()
+    })
+}
+
+/// The TextMate scope a tree-sitter capture corresponds to, if it's one
+/// [`CAPTURES`] covers.
+pub(super) fn capture_selector(capture: &str) -> Option<String> {
+    let (prefix, scope) = CAPTURES
+        .iter()
+        .filter(|(prefix, _)| {
+            capture == *prefix
+                || capture
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+        .max_by_key(|(prefix, _)| prefix.len())?;
+    Some(format!("{scope}{}", &capture[prefix.len()..]))
+}
@@ -0,0 +1,203 @@
+//! Extracting a palette from an image, for wallpaper-matched variants.
+//!
+//! The image is downsampled and its pixels clustered with k-means in OKLab.
+//! The darkest and lightest clusters (by the image's overall lightness,
+//! which also picks the variant's polarity) become the background and
+//! foreground, and the most common chromatic clusters become accents, which
+//! [`fit_roles`] then spreads over a base theme's roles.
+
+use std::cmp::Reverse;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+
+use crate::color::{Color, Oklab, Oklch, NEUTRAL_CHROMA};
+use crate::derive::fit_roles;
+
+/// Number of clusters the image is reduced to.
+const CLUSTERS: usize = 8;
+
+/// The longest side pixels are sampled at.
+const SAMPLE_SIZE: u32 = 96;
+
+const ITERATIONS: usize = 24;
+
+/// Up to how many chromatic clusters become accents.
+const ACCENTS: usize = 6;
+
+/// A group of similar pixels.
+#[derive(Debug, Clone, Copy)]
+pub struct Cluster {
+    pub center: Oklab,
+    /// How many sampled pixels the cluster holds.
+    pub weight: usize,
+}
+
+/// The image's dominant colors, most common first.
+pub fn clusters(path: &Path) -> Result<Vec<Cluster>> {
+    let image = ::image::open(path)
+        .with_context(|| format!("failed to read {}", path.display()))?
+        .thumbnail(SAMPLE_SIZE, SAMPLE_SIZE)
+        .to_rgba8();
+    let pixels: Vec<Oklab> = image
+        .pixels()
+        .filter(|p| p[3] >= 0x80)
+        .map(|p| Color::rgb(p[0], p[1], p[2]).to_oklab())
+        .collect();
+    if pixels.is_empty() {
+        bail!("{} has no opaque pixels", path.display());
+    }
+    Ok(kmeans(&pixels, CLUSTERS))
+}
+
+/// k-means seeded deterministically: the pixel nearest the mean, then
+/// repeatedly the pixel farthest from every seed so far.
+fn kmeans(pixels: &[Oklab], k: usize) -> Vec<Cluster> {
+    let mean = average(pixels.iter().map(|p| (*p, 1.0)));
+    let nearest = |centers: &[Oklab], p: Oklab| {
+        centers
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i, c.distance(p)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0, 0.0))
+    };
+    let mut centers = vec![pixels[nearest(pixels, mean).0]];
+    while centers.len() < k {
+        let (farthest, distance) = pixels
+            .iter()
+            .map(|p| nearest(&centers, *p).1)
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0, 0.0));
+        // Fewer distinct colors than clusters.
+        if distance < 1e-6 {
+            break;
+        }
+        centers.push(pixels[farthest]);
+    }
+
+    let mut members = vec![0; pixels.len()];
+    for _ in 0..ITERATIONS {
+        for (member, pixel) in members.iter_mut().zip(pixels) {
+            *member = nearest(&centers, *pixel).0;
+        }
+        let mut moved = false;
+        for (index, center) in centers.iter_mut().enumerate() {
+            let assigned = members
+                .iter()
+                .zip(pixels)
+                .filter(|(m, _)| **m == index)
+                .map(|(_, p)| (*p, 1.0));
+            let next = average(assigned);
+            moved |= next.distance(*center) > 1e-4;
+            *center = next;
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    let mut clusters: Vec<Cluster> = centers
+        .iter()
+        .enumerate()
+        .map(|(index, center)| Cluster {
+            center: *center,
+            weight: members.iter().filter(|m| **m == index).count(),
+        })
+        .filter(|c| c.weight > 0)
+        .collect();
+    clusters.sort_by_key(|c| Reverse(c.weight));
+    clusters
+}
+
+fn average(points: impl Iterator<Item = (Oklab, f64)>) -> Oklab {
+    let (mut sum, mut total) = (
+        Oklab {
+            l: 0.0,
+            a: 0.0,
+            b: 0.0,
+        },
+        0.0,
+    );
+    for (p, weight) in points {
+        sum.l += p.l * weight;
+        sum.a += p.a * weight;
+        sum.b += p.b * weight;
+        total += weight;
+    }
+    if total == 0.0 {
+        return sum;
+    }
+    Oklab {
+        l: sum.l / total,
+        a: sum.a / total,
+        b: sum.b / total,
+    }
+}
+
+/// A color for each of `base`'s roles (as `resolve_roles` gives them),
+/// drawn from `clusters`.
+pub fn assign_roles(
+    clusters: &[Cluster],
+    base: &IndexMap<&'static str, Color>,
+) -> IndexMap<&'static str, Color> {
+    let lightness = average(clusters.iter().map(|c| (c.center, c.weight as f64))).l;
+    let dark = lightness < 0.6;
+    let by_lightness = |a: &&Cluster, b: &&Cluster| a.center.l.total_cmp(&b.center.l);
+    let (Some(darkest), Some(lightest)) = (
+        clusters.iter().min_by(by_lightness),
+        clusters.iter().max_by(by_lightness),
+    ) else {
+        return IndexMap::new();
+    };
+    let (back, front) = if dark {
+        (darkest, lightest)
+    } else {
+        (lightest, darkest)
+    };
+    let back = back.center.to_oklch();
+    let front = front.center.to_oklch();
+    let background = Oklch {
+        l: if dark {
+            back.l.clamp(0.12, 0.22)
+        } else {
+            back.l.max(0.96)
+        },
+        c: back.c.min(0.04),
+        h: back.h,
+    };
+    let foreground = Oklch {
+        l: if dark { 0.9 } else { 0.25 },
+        c: front.c.min(0.03),
+        h: front.h,
+    };
+
+    let mut accents: Vec<Oklch> = clusters
+        .iter()
+        .map(|c| c.center.to_oklch())
+        .filter(|c| c.c >= NEUTRAL_CHROMA)
+        .take(ACCENTS)
+        .map(|c| Oklch {
+            l: if dark { 0.75 } else { 0.5 },
+            c: c.c.clamp(0.12, 0.25),
+            h: c.h,
+        })
+        .collect();
+    // A monochrome image still needs something for hued roles.
+    if accents.is_empty() {
+        accents.push(Oklch {
+            l: if dark { 0.75 } else { 0.5 },
+            ..foreground
+        });
+    }
+
+    let accents: Vec<Color> = accents.into_iter().map(Color::from_oklch).collect();
+    fit_roles(
+        base,
+        Color::from_oklch(background),
+        Color::from_oklch(foreground),
+        &accents,
+    )
+}
@@ -0,0 +1,258 @@
+//! Converting IntelliJ-platform editor schemes (`.icls`) into the theme
+//! model.
+//!
+//! `<colors>` options map onto their workbench equivalents and the language
+//! defaults in `<attributes>` become token rules for the scopes they cover,
+//! following `baseAttributes` inheritance within the file. Attributes a
+//! scheme leaves to its parent scheme aren't in the file and so aren't
+//! imported.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use roxmltree::{Document, Node};
+
+use crate::color::{Color, MID_LUMINANCE};
+use crate::theme::{ScopeSpec, Theme, TokenRule, TokenSettings};
+
+/// `<colors>` options and the workbench keys they become.
+const COLORS: &[(&str, &str)] = &[
+    ("CARET_COLOR", "editorCursor.foreground"),
+    ("CARET_ROW_COLOR", "editor.lineHighlightBackground"),
+    ("SELECTION_BACKGROUND", "editor.selectionBackground"),
+    ("SELECTION_FOREGROUND", "editor.selectionForeground"),
+    ("GUTTER_BACKGROUND", "editorGutter.background"),
+    ("LINE_NUMBERS_COLOR", "editorLineNumber.foreground"),
+    (
+        "LINE_NUMBER_ON_CARET_ROW_COLOR",
+        "editorLineNumber.activeForeground",
+    ),
+    ("INDENT_GUIDE", "editorIndentGuide.background1"),
+    (
+        "SELECTED_INDENT_GUIDE",
+        "editorIndentGuide.activeBackground1",
+    ),
+    ("WHITESPACES", "editorWhitespace.foreground"),
+    ("RIGHT_MARGIN_COLOR", "editorRuler.foreground"),
+    ("ADDED_LINES_COLOR", "editorGutter.addedBackground"),
+    ("MODIFIED_LINES_COLOR", "editorGutter.modifiedBackground"),
+    ("DELETED_LINES_COLOR", "editorGutter.deletedBackground"),
+    ("CONSOLE_BACKGROUND_KEY", "terminal.background"),
+    ("DOCUMENTATION_COLOR", "editorHoverWidget.background"),
+    ("LOOKUP_COLOR", "editorSuggestWidget.background"),
+    ("TEARLINE_COLOR", "editorGroup.border"),
+];
+
+/// `<attributes>` and the scope selector their token rule gets.
+const ATTRIBUTES: &[(&str, &str)] = &[
+    ("DEFAULT_IDENTIFIER", "variable"),
+    ("DEFAULT_KEYWORD", "keyword"),
+    ("DEFAULT_LINE_COMMENT", "comment.line"),
+    ("DEFAULT_BLOCK_COMMENT", "comment.block"),
+    ("DEFAULT_DOC_COMMENT", "comment.block.documentation"),
+    ("DEFAULT_STRING", "string"),
+    ("DEFAULT_VALID_STRING_ESCAPE", "constant.character.escape"),
+    ("DEFAULT_INVALID_STRING_ESCAPE", "invalid.illegal"),
+    ("DEFAULT_NUMBER", "constant.numeric"),
+    ("DEFAULT_CONSTANT", "variable.other.constant"),
+    ("DEFAULT_PREDEFINED_SYMBOL", "support.function"),
+    ("DEFAULT_LOCAL_VARIABLE", "variable.other.readwrite"),
+    ("DEFAULT_GLOBAL_VARIABLE", "variable.other.global"),
+    ("DEFAULT_PARAMETER", "variable.parameter"),
+    ("DEFAULT_INSTANCE_FIELD", "variable.other.property"),
+    ("DEFAULT_STATIC_FIELD", "variable.other.constant.property"),
+    ("DEFAULT_FUNCTION_DECLARATION", "entity.name.function"),
+    (
+        "DEFAULT_FUNCTION_CALL",
+        "meta.function-call entity.name.function",
+    ),
+    ("DEFAULT_INSTANCE_METHOD", "entity.name.function.member"),
+    ("DEFAULT_CLASS_NAME", "entity.name.class"),
+    ("DEFAULT_CLASS_REFERENCE", "entity.name.type"),
+    ("DEFAULT_INTERFACE_NAME", "entity.name.type.interface"),
+    ("DEFAULT_METADATA", "meta.decorator"),
+    ("DEFAULT_LABEL", "entity.name.label"),
+    ("DEFAULT_OPERATION_SIGN", "keyword.operator"),
+    ("DEFAULT_BRACES", "punctuation.section.braces"),
+    ("DEFAULT_BRACKETS", "punctuation.section.brackets"),
+    ("DEFAULT_PARENTHS", "punctuation.section.parens"),
+    ("DEFAULT_COMMA", "punctuation.separator.comma"),
+    ("DEFAULT_DOT", "punctuation.accessor"),
+    ("DEFAULT_SEMICOLON", "punctuation.terminator"),
+    ("DEFAULT_TAG", "entity.name.tag"),
+    ("DEFAULT_ATTRIBUTE", "entity.other.attribute-name"),
+    ("DEFAULT_ENTITY", "constant.character.entity"),
+];
+
+/// Attributes whose effect color is a workbench color.
+const EFFECTS: &[(&str, &str)] = &[
+    ("ERRORS_ATTRIBUTES", "editorError.foreground"),
+    ("WARNING_ATTRIBUTES", "editorWarning.foreground"),
+];
+
+/// How many `baseAttributes` links to follow before giving up on a cycle.
+const MAX_DEPTH: usize = 16;
+
+pub fn load(path: &Path) -> Result<Theme> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    parse(&text).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+pub fn parse(text: &str) -> Result<Theme> {
+    let document = Document::parse(text)?;
+    let scheme = document.root_element();
+    if !scheme.has_tag_name("scheme") {
+        bail!("expected a <scheme> root element");
+    }
+    let section = |name: &str| {
+        scheme
+            .children()
+            .find(|n| n.has_tag_name(name))
+            .map(options)
+            .unwrap_or_default()
+    };
+    let color_options = section("colors");
+    let attributes = section("attributes");
+
+    let mut colors = IndexMap::new();
+    for (option, key) in COLORS {
+        if let Some(color) = color_options
+            .get(option)
+            .and_then(|n| n.attribute("value"))
+            .and_then(parse_color)
+        {
+            colors.insert((*key).to_owned(), color.to_string());
+        }
+    }
+    let text_style = attribute(&attributes, "TEXT", 0);
+    if let Some(background) = text_style.get("BACKGROUND") {
+        colors.insert("editor.background".to_owned(), background.to_string());
+    }
+    if let Some(foreground) = text_style.get("FOREGROUND") {
+        colors.insert("editor.foreground".to_owned(), foreground.to_string());
+    }
+    for (attribute_name, key) in EFFECTS {
+        if let Some(color) = attribute(&attributes, attribute_name, 0).get("EFFECT_COLOR") {
+            colors.insert((*key).to_owned(), color.to_string());
+        }
+    }
+
+    let mut token_colors = Vec::new();
+    for (attribute_name, scope) in ATTRIBUTES {
+        let values = attribute(&attributes, attribute_name, 0);
+        let flags = |name: &str| {
+            attribute_value(&attributes, attribute_name, name, 0)
+                .and_then(|v| v.parse::<u8>().ok())
+                .unwrap_or(0)
+        };
+        let font_type = flags("FONT_TYPE");
+        let mut font_style = Vec::new();
+        if font_type & 1 != 0 {
+            font_style.push("bold");
+        }
+        if font_type & 2 != 0 {
+            font_style.push("italic");
+        }
+        match flags("EFFECT_TYPE") {
+            1 | 2 if values.contains_key("EFFECT_COLOR") => font_style.push("underline"),
+            3 if values.contains_key("EFFECT_COLOR") => font_style.push("strikethrough"),
+            _ => {}
+        }
+        let settings = TokenSettings {
+            foreground: values.get("FOREGROUND").map(|c| c.to_string()),
+            background: values.get("BACKGROUND").map(|c| c.to_string()),
+            font_style: (!font_style.is_empty()).then(|| font_style.join(" ")),
+        };
+        if settings != TokenSettings::default() {
+            token_colors.push(TokenRule {
+                name: Some((*attribute_name).to_owned()),
+                scope: ScopeSpec::One((*scope).to_owned()),
+                settings,
+            });
+        }
+    }
+
+    let dark = match scheme.attribute("parent_scheme") {
+        Some("Darcula") => true,
+        Some("Default") => false,
+        _ => colors
+            .get("editor.background")
+            .and_then(|c| c.parse::<Color>().ok())
+            .is_none_or(|c| c.relative_luminance() < MID_LUMINANCE),
+    };
+    Ok(Theme {
+        name: scheme.attribute("name").map(str::to_owned),
+        kind: Some(if dark { "dark" } else { "light" }.to_owned()),
+        colors,
+        token_colors,
+        ..Theme::default()
+    })
+}
+
+/// The `<option name=…>` children of a section, by name.
+fn options<'a, 'input>(section: Node<'a, 'input>) -> HashMap<&'a str, Node<'a, 'input>> {
+    section
+        .children()
+        .filter(|n| n.has_tag_name("option"))
+        .filter_map(|n| Some((n.attribute("name")?, n)))
+        .collect()
+}
+
+/// An attribute's color values (`FOREGROUND`, `BACKGROUND`, `EFFECT_COLOR`),
+/// inherited through `baseAttributes` when the attribute has no value of
+/// its own.
+fn attribute(
+    attributes: &HashMap<&str, Node<'_, '_>>,
+    name: &str,
+    depth: usize,
+) -> HashMap<String, Color> {
+    let Some(node) = attributes.get(name) else {
+        return HashMap::new();
+    };
+    match node.children().find(|n| n.has_tag_name("value")) {
+        Some(value) => options(value)
+            .into_iter()
+            .filter_map(|(option, node)| {
+                let color = parse_color(node.attribute("value")?)?;
+                Some((option.to_owned(), color))
+            })
+            .collect(),
+        None => match node.attribute("baseAttributes") {
+            Some(base) if depth < MAX_DEPTH => attribute(attributes, base, depth + 1),
+            _ => HashMap::new(),
+        },
+    }
+}
+
+/// One raw option of an attribute's value, inherited like [`attribute`].
+fn attribute_value<'a>(
+    attributes: &HashMap<&str, Node<'a, '_>>,
+    name: &str,
+    option: &str,
+    depth: usize,
+) -> Option<&'a str> {
+    let node = attributes.get(name)?;
+    match node.children().find(|n| n.has_tag_name("value")) {
+        Some(value) => options(value).get(option)?.attribute("value"),
+        None if depth < MAX_DEPTH => attribute_value(
+            attributes,
+            node.attribute("baseAttributes")?,
+            option,
+            depth + 1,
+        ),
+        None => None,
+    }
+}
+
+/// IntelliJ writes colors as bare hex with leading zeros dropped.
+fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    if value.is_empty() || value.len() > 6 {
+        return None;
+    }
+    format!("#{value:0>6}").parse().ok()
+}
@@ -0,0 +1,218 @@
+//! Reverse-engineering existing themes into the palette model.
+//!
+//! Importers turn a foreign theme into weighted color [`Sample`]s, some of
+//! which are known to play a role. [`build_palette`] then clusters
+//! near-identical colors in OKLab and names the clusters after their roles.
+
+pub mod base16;
+pub mod dtcg;
+pub mod figma;
+pub mod helix;
+pub mod image;
+pub mod jetbrains;
+pub mod neovim;
+pub mod sublime;
+pub mod terminal;
+pub mod tmtheme;
+pub mod vscode;
+pub mod vsix;
+
+use std::cmp::Reverse;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+
+use crate::color::{Color, Oklab};
+use crate::diff::ThemeDiff;
+use crate::palette::{Palette, PaletteColor};
+use crate::roles::ROLES;
+use crate::theme::{Theme, TokenSettings};
+
+/// OKLab distance under which two colors are considered the same entry.
+pub const DEFAULT_THRESHOLD: f64 = 0.03;
+
+/// A color observed in a source theme.
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub color: Color,
+    /// How many times the source references the color.
+    pub weight: usize,
+    /// Roles the source assigns to exactly this color.
+    pub roles: Vec<String>,
+}
+
+/// Which roles each source key (a base16 slot, a token path…) plays, as
+/// given to `cyberdeck import --mapping`.
+pub type Mapping = IndexMap<String, Vec<String>>;
+
+/// Loads a mapping file: a TOML table from source keys to role lists.
+///
+/// ```toml
+/// base08 = ["error", "deleted"]
+/// base0C = ["type", "cursor"]
+/// ```
+pub fn load_mapping(path: &Path) -> Result<Mapping> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let mapping: Mapping =
+        toml::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))?;
+    for (key, roles) in &mapping {
+        if let Some(role) = roles.iter().find(|r| !ROLES.iter().any(|(n, _)| n == r)) {
+            bail!("{}: `{key}` maps to unknown role `{role}`", path.display());
+        }
+    }
+    Ok(mapping)
+}
+
+/// Loads any editor theme the importers convert, telling the format from
+/// the extension: `.tmTheme`, `.sublime-color-scheme`, `.icls`, a Helix
+/// `.toml`, a Neovim `.lua` or `.vim` colorscheme, else a VS Code theme.
+pub fn load_theme(path: &Path) -> Result<Theme> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "tmtheme" => tmtheme::load(path),
+        "sublime-color-scheme" => sublime::load(path),
+        "icls" => jetbrains::load(path),
+        "toml" => helix::load(path),
+        "lua" | "vim" => neovim::load(path),
+        _ => Theme::load(path),
+    }
+}
+
+struct Cluster {
+    seed: Color,
+    lab: Oklab,
+    weight: usize,
+    roles: Vec<String>,
+}
+
+/// Clusters samples into palette entries. Role-bearing samples seed clusters
+/// first so role colors are kept exactly; other colors within `threshold`
+/// of a seed fold into it.
+pub fn build_palette(name: &str, mut samples: Vec<Sample>, threshold: f64) -> Palette {
+    samples.sort_by_key(|s| (s.roles.is_empty(), Reverse(s.weight)));
+
+    let mut clusters: Vec<Cluster> = Vec::new();
+    for sample in samples {
+        let color = sample.color.opaque();
+        let lab = color.to_oklab();
+        let nearest = clusters
+            .iter_mut()
+            .map(|c| (c.lab.distance(lab), c))
+            .filter(|(d, _)| *d <= threshold)
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+        match nearest {
+            Some((_, cluster)) => {
+                cluster.weight += sample.weight;
+                for role in sample.roles {
+                    if !cluster.roles.contains(&role) {
+                        cluster.roles.push(role);
+                    }
+                }
+            }
+            None => clusters.push(Cluster {
+                seed: color,
+                lab,
+                weight: sample.weight,
+                roles: sample.roles,
+            }),
+        }
+    }
+
+    let role_rank = |role: &str| {
+        ROLES
+            .iter()
+            .position(|(r, _)| *r == role)
+            .unwrap_or(ROLES.len())
+    };
+    for cluster in &mut clusters {
+        cluster.roles.sort_by_key(|r| role_rank(r));
+    }
+    clusters.sort_by_key(|c| {
+        let rank = c.roles.first().map_or(usize::MAX, |r| role_rank(r));
+        (rank, Reverse(c.weight))
+    });
+
+    let mut unnamed: std::collections::HashMap<&'static str, usize> = Default::default();
+    let colors = clusters
+        .into_iter()
+        .map(|cluster| {
+            let family = cluster.seed.hue_name();
+            let name = match cluster.roles.first() {
+                Some(role) => title_case(role),
+                None => {
+                    let n = unnamed.entry(family).or_default();
+                    *n += 1;
+                    format!("{} {n}", title_case(family))
+                }
+            };
+            PaletteColor {
+                name,
+                hex: cluster.seed,
+                family: family.to_owned(),
+                roles: cluster.roles,
+            }
+        })
+        .collect();
+
+    Palette {
+        name: name.to_owned(),
+        colors,
+    }
+}
+
+/// `background.secondary` → `Background Secondary`.
+fn title_case(role: &str) -> String {
+    role.split(['.', '-', '_'])
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A VS Code settings fragment that layers the colors `imported` sets
+/// differently from `base` on top of it: `workbench.colorCustomizations`
+/// and `editor.tokenColorCustomizations` scoped to `base`'s name. Settings
+/// `base` has that `imported` lacks are left alone.
+pub fn override_layer(base: &Theme, imported: &Theme) -> serde_json::Value {
+    let diff = ThemeDiff::between(base, imported);
+    let colors: serde_json::Map<_, _> = diff
+        .colors
+        .iter()
+        .filter_map(|change| Some((change.key.clone(), change.new.clone()?.into())))
+        .collect();
+
+    let mut styles: IndexMap<&str, &TokenSettings> = IndexMap::new();
+    for rule in &imported.token_colors {
+        for selector in rule.scope.selectors() {
+            styles.insert(selector, &rule.settings);
+        }
+    }
+    let rules: Vec<_> = diff
+        .token_colors
+        .iter()
+        .filter(|change| !change.is_removed())
+        .filter_map(|change| {
+            let settings = styles.get(change.key.as_str())?;
+            Some(serde_json::json!({ "scope": change.key, "settings": settings }))
+        })
+        .collect();
+
+    let scope = format!("[{}]", base.name.as_deref().unwrap_or_default());
+    serde_json::json!({
+        "workbench.colorCustomizations": { &scope: colors },
+        "editor.tokenColorCustomizations": { &scope: { "textMateRules": rules } },
+    })
+}
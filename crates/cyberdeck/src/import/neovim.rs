@@ -0,0 +1,955 @@
+//! Converting Neovim colorschemes into the theme model.
+//!
+//! Lua colorschemes are run through a small interpreter for the subset
+//! they're usually written in: locals, tables (with `..`, `or` and `and`
+//! between values), functions without control flow, `for … in pairs(…)`
+//! loops and `vim.tbl_extend`. Highlights come from `nvim_set_hl` calls,
+//! through any alias or wrapper function, and from `hi` commands passed to
+//! `vim.cmd`; `if` and `while` blocks are skipped. Vimscript colorschemes
+//! are read for their `hi` lines.
+//!
+//! Editor UI groups map onto their workbench equivalents; syntax groups and
+//! tree-sitter `@captures` become token rules for the TextMate scope they
+//! correspond to.
+
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+
+use crate::color::{Color, MID_LUMINANCE};
+use crate::theme::{ScopeSpec, Theme, TokenRule, TokenSettings};
+
+use super::helix::capture_selector;
+
+/// UI groups, which of their colors is read and the workbench key it
+/// becomes; the first group listed for a workbench key wins.
+const UI: &[(&str, Field, &str)] = &[
+    ("Normal", Field::Bg, "editor.background"),
+    ("Normal", Field::Fg, "editor.foreground"),
+    ("Cursor", Field::Bg, "editorCursor.foreground"),
+    ("Visual", Field::Bg, "editor.selectionBackground"),
+    ("CursorLine", Field::Bg, "editor.lineHighlightBackground"),
+    ("LineNr", Field::Fg, "editorLineNumber.foreground"),
+    (
+        "CursorLineNr",
+        Field::Fg,
+        "editorLineNumber.activeForeground",
+    ),
+    ("SignColumn", Field::Bg, "editorGutter.background"),
+    ("StatusLine", Field::Bg, "statusBar.background"),
+    ("StatusLine", Field::Fg, "statusBar.foreground"),
+    ("NormalFloat", Field::Bg, "editorWidget.background"),
+    ("Pmenu", Field::Bg, "editorSuggestWidget.background"),
+    (
+        "PmenuSel",
+        Field::Bg,
+        "editorSuggestWidget.selectedBackground",
+    ),
+    ("WinSeparator", Field::Fg, "editorGroup.border"),
+    ("VertSplit", Field::Fg, "editorGroup.border"),
+    ("Search", Field::Bg, "editor.findMatchHighlightBackground"),
+    ("IncSearch", Field::Bg, "editor.findMatchBackground"),
+    ("Whitespace", Field::Fg, "editorWhitespace.foreground"),
+    ("NonText", Field::Fg, "editorWhitespace.foreground"),
+    ("ColorColumn", Field::Bg, "editorRuler.foreground"),
+    ("DiagnosticError", Field::Fg, "editorError.foreground"),
+    ("DiagnosticWarn", Field::Fg, "editorWarning.foreground"),
+    ("DiagnosticInfo", Field::Fg, "editorInfo.foreground"),
+    ("DiagnosticHint", Field::Fg, "editorHint.foreground"),
+    ("GitSignsAdd", Field::Fg, "editorGutter.addedBackground"),
+    (
+        "GitSignsChange",
+        Field::Fg,
+        "editorGutter.modifiedBackground",
+    ),
+    (
+        "GitSignsDelete",
+        Field::Fg,
+        "editorGutter.deletedBackground",
+    ),
+];
+
+/// Vim's standard syntax groups and the TextMate scope they correspond to.
+const SYNTAX: &[(&str, &str)] = &[
+    ("Comment", "comment"),
+    ("Constant", "constant"),
+    ("String", "string"),
+    ("Character", "constant.character"),
+    ("Number", "constant.numeric"),
+    ("Boolean", "constant.language.boolean"),
+    ("Float", "constant.numeric.float"),
+    ("Identifier", "variable"),
+    ("Function", "entity.name.function"),
+    ("Statement", "keyword"),
+    ("Conditional", "keyword.control.conditional"),
+    ("Repeat", "keyword.control.loop"),
+    ("Label", "entity.name.label"),
+    ("Operator", "keyword.operator"),
+    ("Keyword", "keyword"),
+    ("Exception", "keyword.control.exception"),
+    ("PreProc", "meta.preprocessor"),
+    ("Include", "keyword.control.import"),
+    ("Define", "meta.preprocessor.macro"),
+    ("Macro", "entity.name.function.macro"),
+    ("Type", "entity.name.type"),
+    ("StorageClass", "storage.modifier"),
+    ("Structure", "storage.type.struct"),
+    ("Typedef", "storage.type"),
+    ("Special", "constant.character"),
+    ("SpecialChar", "constant.character.escape"),
+    ("Tag", "entity.name.tag"),
+    ("Delimiter", "punctuation"),
+    ("SpecialComment", "comment.block.documentation"),
+    ("Underlined", "markup.underline"),
+    ("Error", "invalid"),
+    ("DiffAdd", "markup.inserted"),
+    ("DiffChange", "markup.changed"),
+    ("DiffDelete", "markup.deleted"),
+];
+
+/// How deeply links and function calls may nest.
+const MAX_DEPTH: usize = 16;
+
+/// Statements run before giving up on a script.
+const MAX_STEPS: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Fg,
+    Bg,
+}
+
+/// One highlight group's definition.
+#[derive(Debug, Clone, Default)]
+struct Highlight {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+    link: Option<String>,
+}
+
+pub fn load(path: &Path) -> Result<Theme> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let is_vim = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("vim"));
+    let (name, highlights) = if is_vim {
+        vimscript(&text)
+    } else {
+        lua(&text).with_context(|| format!("failed to parse {}", path.display()))?
+    };
+    let name = name.or_else(|| {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+    });
+    Ok(theme(name, &highlights))
+}
+
+/// The colorscheme name and highlights a Vimscript file sets.
+fn vimscript(text: &str) -> (Option<String>, IndexMap<String, Highlight>) {
+    let mut highlights = IndexMap::new();
+    let mut name = None;
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(value) = line
+            .strip_prefix("let g:colors_name")
+            .and_then(|rest| rest.trim().strip_prefix('='))
+        {
+            name = Some(value.trim().trim_matches(['"', '\'']).to_owned());
+        } else {
+            hi_command(line, &mut highlights);
+        }
+    }
+    (name, highlights)
+}
+
+/// Applies one `:highlight` command; anything else is ignored.
+fn hi_command(line: &str, highlights: &mut IndexMap<String, Highlight>) {
+    let mut words = line.split_whitespace();
+    let Some(command) = words.next() else {
+        return;
+    };
+    let command = command.trim_end_matches('!');
+    if !matches!(command, "hi" | "highlight") {
+        return;
+    }
+    let mut words = words.filter(|w| *w != "default").peekable();
+    if words.peek() == Some(&"link") {
+        words.next();
+        if let (Some(from), Some(to)) = (words.next(), words.next()) {
+            highlights.insert(
+                from.to_owned(),
+                Highlight {
+                    link: Some(to.to_owned()),
+                    ..Highlight::default()
+                },
+            );
+        }
+        return;
+    }
+    let Some(group) = words.next().filter(|g| *g != "clear") else {
+        return;
+    };
+    let highlight = highlights.entry(group.to_owned()).or_default();
+    for word in words {
+        let Some((key, value)) = word.split_once('=') else {
+            continue;
+        };
+        match key {
+            "guifg" => highlight.fg = value.parse().ok(),
+            "guibg" => highlight.bg = value.parse().ok(),
+            "gui" => {
+                for attr in value.split(',') {
+                    match attr {
+                        "bold" => highlight.bold = true,
+                        "italic" => highlight.italic = true,
+                        "underline" | "undercurl" => highlight.underline = true,
+                        "strikethrough" => highlight.strikethrough = true,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The colorscheme name and highlights a Lua file sets.
+fn lua(text: &str) -> Result<(Option<String>, IndexMap<String, Highlight>)> {
+    let tokens = lex(text)?;
+    let mut interpreter = Interpreter {
+        tokens: &tokens,
+        frames: vec![IndexMap::new()],
+        highlights: IndexMap::new(),
+        steps: 0,
+        depth: 0,
+    };
+    interpreter.execute(0..tokens.len());
+    let name = match interpreter.lookup("vim").index("g").index("colors_name") {
+        Value::Str(name) => Some(name),
+        _ => None,
+    };
+    Ok((name, interpreter.highlights))
+}
+
+/// The theme the highlights describe, with links followed.
+fn theme(name: Option<String>, highlights: &IndexMap<String, Highlight>) -> Theme {
+    let resolve = |group: &str| {
+        let mut group = group;
+        for _ in 0..MAX_DEPTH {
+            let highlight = highlights.get(group)?;
+            match &highlight.link {
+                Some(target) => group = target,
+                None => return Some(highlight),
+            }
+        }
+        None
+    };
+
+    let mut colors = IndexMap::new();
+    for (group, field, key) in UI {
+        if colors.contains_key(*key) {
+            continue;
+        }
+        let color = resolve(group).and_then(|h| match field {
+            Field::Fg => h.fg,
+            Field::Bg => h.bg,
+        });
+        if let Some(color) = color {
+            colors.insert((*key).to_owned(), color.to_string());
+        }
+    }
+
+    let mut token_colors = Vec::new();
+    for group in highlights.keys() {
+        let scope = match group.strip_prefix('@') {
+            Some(capture) => capture_selector(capture),
+            None => SYNTAX
+                .iter()
+                .find(|(name, _)| name == group)
+                .map(|(_, scope)| (*scope).to_owned()),
+        };
+        let (Some(scope), Some(highlight)) = (scope, resolve(group)) else {
+            continue;
+        };
+        let font_style: Vec<&str> = [
+            (highlight.bold, "bold"),
+            (highlight.italic, "italic"),
+            (highlight.underline, "underline"),
+            (highlight.strikethrough, "strikethrough"),
+        ]
+        .into_iter()
+        .filter_map(|(set, style)| set.then_some(style))
+        .collect();
+        let settings = TokenSettings {
+            foreground: highlight.fg.map(|c| c.to_string()),
+            background: highlight.bg.map(|c| c.to_string()),
+            font_style: (!font_style.is_empty()).then(|| font_style.join(" ")),
+        };
+        if settings != TokenSettings::default() {
+            token_colors.push(TokenRule {
+                name: Some(group.clone()),
+                scope: ScopeSpec::One(scope),
+                settings,
+            });
+        }
+    }
+
+    let dark = colors
+        .get("editor.background")
+        .and_then(|c| c.parse::<Color>().ok())
+        .is_none_or(|c| c.relative_luminance() < MID_LUMINANCE);
+    Theme {
+        name,
+        kind: Some(if dark { "dark" } else { "light" }.to_owned()),
+        colors,
+        token_colors,
+        ..Theme::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Name(String),
+    Str(String),
+    Num(f64),
+    Sym(&'static str),
+}
+
+const SYMBOLS: &[&str] = &[
+    "...", "..", "==", "~=", "<=", ">=", "::", "//", "<<", ">>", "=", ".", ",", ";", ":", "(", ")",
+    "{", "}", "[", "]", "+", "-", "*", "/", "%", "^", "#", "<", ">", "&", "|", "~",
+];
+
+fn lex(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if let Some(comment) = rest.strip_prefix("--") {
+            rest = match long_bracket(comment) {
+                Some((_, after)) => after,
+                None => comment.find('\n').map_or("", |end| &comment[end..]),
+            };
+        } else if c == '[' && long_bracket(rest).is_some() {
+            let (content, after) = long_bracket(rest).unwrap_or_default();
+            tokens.push(Token::Str(content.to_owned()));
+            rest = after;
+        } else if c == '"' || c == '\'' {
+            let (content, after) = quoted(rest)?;
+            tokens.push(Token::Str(content));
+            rest = after;
+        } else if c.is_ascii_digit() {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+                .unwrap_or(rest.len());
+            let literal = &rest[..end];
+            let number = match literal
+                .strip_prefix("0x")
+                .or_else(|| literal.strip_prefix("0X"))
+            {
+                Some(hex) => u64::from_str_radix(hex, 16).map(|n| n as f64).ok(),
+                None => literal.parse().ok(),
+            };
+            tokens.push(Token::Num(number.unwrap_or(0.0)));
+            rest = &rest[end..];
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push(Token::Name(rest[..end].to_owned()));
+            rest = &rest[end..];
+        } else {
+            let Some(symbol) = SYMBOLS.iter().find(|s| rest.starts_with(**s)) else {
+                bail!("unexpected character `{c}`");
+            };
+            tokens.push(Token::Sym(symbol));
+            rest = &rest[symbol.len()..];
+        }
+    }
+    Ok(tokens)
+}
+
+/// A `[[…]]` or `[==[…]==]` string at the start of `text`: its content and
+/// what follows it.
+fn long_bracket(text: &str) -> Option<(&str, &str)> {
+    let level = text.strip_prefix('[')?.find(|c| c != '=')?;
+    let open = format!("[{}[", "=".repeat(level));
+    let close = format!("]{}]", "=".repeat(level));
+    let body = text.strip_prefix(open.as_str())?;
+    let end = body.find(&close)?;
+    Some((&body[..end], &body[end + close.len()..]))
+}
+
+/// A quoted string at the start of `text`, unescaped, and what follows it.
+fn quoted(text: &str) -> Result<(String, &str)> {
+    let mut chars = text.char_indices();
+    let Some((_, quote)) = chars.next() else {
+        bail!("expected a string");
+    };
+    let mut content = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, 'n')) => content.push('\n'),
+                Some((_, 't')) => content.push('\t'),
+                Some((_, other)) => content.push(other),
+                None => break,
+            },
+            c if c == quote => return Ok((content, &text[i + c.len_utf8()..])),
+            '\n' => break,
+            c => content.push(c),
+        }
+    }
+    bail!("unterminated string")
+}
+
+#[derive(Debug, Clone, Default)]
+enum Value {
+    #[default]
+    Nil,
+    Bool(bool),
+    Num(f64),
+    Str(String),
+    Table(IndexMap<String, Value>),
+    /// Something outside the script, such as `vim.api.nvim_set_hl`, by
+    /// path.
+    Ref(String),
+    Function {
+        params: Vec<String>,
+        body: Range<usize>,
+    },
+}
+
+impl Value {
+    fn index(&self, key: &str) -> Value {
+        match self {
+            Value::Table(fields) => fields.get(key).cloned().unwrap_or_default(),
+            Value::Ref(path) => Value::Ref(format!("{path}.{key}")),
+            _ => Value::Nil,
+        }
+    }
+
+    fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Nil | Value::Bool(false))
+    }
+
+    fn key(&self) -> Option<String> {
+        match self {
+            Value::Str(s) => Some(s.clone()),
+            Value::Num(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+
+    fn color(&self) -> Option<Color> {
+        match self {
+            Value::Str(s) => s.parse().ok(),
+            Value::Num(n) if (0.0..=f64::from(0xff_ffff)).contains(n) => {
+                let [_, r, g, b] = (*n as u32).to_be_bytes();
+                Some(Color::rgb(r, g, b))
+            }
+            _ => None,
+        }
+    }
+}
+
+struct Interpreter<'a> {
+    tokens: &'a [Token],
+    /// Local scopes, innermost last; the first holds globals.
+    frames: Vec<IndexMap<String, Value>>,
+    highlights: IndexMap<String, Highlight>,
+    steps: usize,
+    depth: usize,
+}
+
+impl Interpreter<'_> {
+    fn name_at(&self, pos: usize) -> Option<&str> {
+        match self.tokens.get(pos) {
+            Some(Token::Name(name)) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn sym_at(&self, pos: usize, symbol: &str) -> bool {
+        matches!(self.tokens.get(pos), Some(Token::Sym(s)) if *s == symbol)
+    }
+
+    fn lookup(&self, name: &str) -> Value {
+        self.frames
+            .iter()
+            .rev()
+            .find_map(|frame| frame.get(name))
+            .cloned()
+            .unwrap_or_else(|| Value::Ref(name.to_owned()))
+    }
+
+    /// Runs the statements in `range`, returning what a `return` gives.
+    fn execute(&mut self, range: Range<usize>) -> Option<Value> {
+        let mut pos = range.start;
+        while pos < range.end {
+            self.steps += 1;
+            if self.steps > MAX_STEPS {
+                return None;
+            }
+            match self.name_at(pos) {
+                Some("local") if self.name_at(pos + 1) == Some("function") => {
+                    let name = self.name_at(pos + 2).unwrap_or_default().to_owned();
+                    let (function, next) = self.function(pos + 3);
+                    self.frames.last_mut()?.insert(name, function);
+                    pos = next;
+                }
+                Some("local") => {
+                    let mut names = Vec::new();
+                    pos += 1;
+                    while let Some(name) = self.name_at(pos) {
+                        names.push(name.to_owned());
+                        pos += 1;
+                        if !self.sym_at(pos, ",") {
+                            break;
+                        }
+                        pos += 1;
+                    }
+                    let values = if self.sym_at(pos, "=") {
+                        pos += 1;
+                        self.expressions(&mut pos)
+                    } else {
+                        Vec::new()
+                    };
+                    let frame = self.frames.last_mut()?;
+                    for (index, name) in names.into_iter().enumerate() {
+                        frame.insert(name, values.get(index).cloned().unwrap_or_default());
+                    }
+                }
+                Some("function") => {
+                    let mut path = vec![self.name_at(pos + 1).unwrap_or_default().to_owned()];
+                    pos += 2;
+                    while self.sym_at(pos, ".") || self.sym_at(pos, ":") {
+                        path.push(self.name_at(pos + 1).unwrap_or_default().to_owned());
+                        pos += 2;
+                    }
+                    let (function, next) = self.function(pos);
+                    self.assign(&path, function);
+                    pos = next;
+                }
+                Some("for") => pos = self.for_loop(pos),
+                Some("if" | "while" | "repeat" | "do") => pos = self.block_end(pos) + 1,
+                Some("return") => {
+                    pos += 1;
+                    return Some(self.expression(&mut pos));
+                }
+                Some(_) => {
+                    let start = pos;
+                    match self.target(&mut pos) {
+                        Some(path) if self.sym_at(pos, "=") => {
+                            pos += 1;
+                            let value = self.expression(&mut pos);
+                            self.assign(&path, value);
+                        }
+                        _ => {
+                            pos = start;
+                            self.expression(&mut pos);
+                            pos = pos.max(start + 1);
+                        }
+                    }
+                }
+                None => pos += 1,
+            }
+        }
+        None
+    }
+
+    /// A function's parameters and body, from the `(` after its name; and
+    /// where the function ends.
+    fn function(&self, pos: usize) -> (Value, usize) {
+        let mut params = Vec::new();
+        let mut body = pos + 1;
+        while let Some(token) = self.tokens.get(body) {
+            body += 1;
+            match token {
+                Token::Name(name) => params.push(name.clone()),
+                Token::Sym(")") => break,
+                _ => {}
+            }
+        }
+        let end = self.block_end_from(body, 1).unwrap_or(self.tokens.len());
+        (
+            Value::Function {
+                params,
+                body: body..end,
+            },
+            end + 1,
+        )
+    }
+
+    /// Where the block opened at `pos` (`if`, `do`, `function`…) ends.
+    fn block_end(&self, pos: usize) -> usize {
+        self.block_end_from(pos, 0).unwrap_or(self.tokens.len())
+    }
+
+    fn block_end_from(&self, pos: usize, mut depth: usize) -> Option<usize> {
+        for index in pos..self.tokens.len() {
+            match self.name_at(index) {
+                Some("function" | "if" | "do" | "repeat") => depth += 1,
+                Some("end" | "until") => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return Some(index);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Runs a `for k, v in pairs(t) do … end` loop's body per entry; other
+    /// loops are skipped.
+    fn for_loop(&mut self, pos: usize) -> usize {
+        let end = self.block_end(pos);
+        let mut names = Vec::new();
+        let mut cursor = pos + 1;
+        while let Some(name) = self.name_at(cursor) {
+            if name == "in" {
+                break;
+            }
+            names.push(name.to_owned());
+            cursor += 1;
+            if self.sym_at(cursor, ",") {
+                cursor += 1;
+            }
+        }
+        if self.name_at(cursor) != Some("in") {
+            return end + 1;
+        }
+        cursor += 1;
+        let Value::Table(entries) = self.expression(&mut cursor) else {
+            return end + 1;
+        };
+        if self.name_at(cursor) != Some("do") {
+            return end + 1;
+        }
+        for (key, value) in entries {
+            let key = match key.parse::<f64>() {
+                Ok(n) => Value::Num(n),
+                Err(_) => Value::Str(key),
+            };
+            let frame = names.iter().cloned().zip([key, value]).collect();
+            self.frames.push(frame);
+            self.execute(cursor + 1..end);
+            self.frames.pop();
+        }
+        end + 1
+    }
+
+    /// An assignment target (`a`, `a.b`, `a["b"]`) at `pos`.
+    fn target(&self, pos: &mut usize) -> Option<Vec<String>> {
+        let mut path = vec![self.name_at(*pos)?.to_owned()];
+        *pos += 1;
+        loop {
+            if self.sym_at(*pos, ".") {
+                path.push(self.name_at(*pos + 1)?.to_owned());
+                *pos += 2;
+            } else if self.sym_at(*pos, "[") && self.sym_at(*pos + 2, "]") {
+                match self.tokens.get(*pos + 1)? {
+                    Token::Str(key) => path.push(key.clone()),
+                    _ => return None,
+                }
+                *pos += 3;
+            } else {
+                return Some(path);
+            }
+        }
+    }
+
+    fn assign(&mut self, path: &[String], value: Value) {
+        let Some((root, fields)) = path.split_first() else {
+            return;
+        };
+        let frame = self
+            .frames
+            .iter()
+            .rposition(|frame| frame.contains_key(root))
+            .unwrap_or(0);
+        let mut slot = self.frames[frame].entry(root.clone()).or_default();
+        for field in fields {
+            if !matches!(slot, Value::Table(_)) {
+                *slot = Value::Table(IndexMap::new());
+            }
+            let Value::Table(table) = slot else {
+                return;
+            };
+            slot = table.entry(field.clone()).or_default();
+        }
+        *slot = value;
+    }
+
+    fn expressions(&mut self, pos: &mut usize) -> Vec<Value> {
+        let mut values = vec![self.expression(pos)];
+        while self.sym_at(*pos, ",") {
+            *pos += 1;
+            values.push(self.expression(pos));
+        }
+        values
+    }
+
+    /// A value and the binary operators after it, left to right.
+    fn expression(&mut self, pos: &mut usize) -> Value {
+        let mut value = self.unary(pos);
+        loop {
+            let op = match self.tokens.get(*pos) {
+                Some(Token::Name(op)) if op == "or" || op == "and" => op.clone(),
+                Some(Token::Sym(op))
+                    if [
+                        "..", "==", "~=", "<", ">", "<=", ">=", "+", "-", "*", "/", "%", "^",
+                    ]
+                    .contains(op) =>
+                {
+                    (*op).to_owned()
+                }
+                _ => return value,
+            };
+            *pos += 1;
+            let rhs = self.unary(pos);
+            value = match op.as_str() {
+                "or" if value.is_truthy() => value,
+                "or" => rhs,
+                "and" if value.is_truthy() => rhs,
+                "and" => value,
+                ".." => match (value.key(), rhs.key()) {
+                    (Some(a), Some(b)) => Value::Str(a + &b),
+                    _ => Value::Nil,
+                },
+                _ => Value::Nil,
+            };
+        }
+    }
+
+    fn unary(&mut self, pos: &mut usize) -> Value {
+        if self.name_at(*pos) == Some("not") || self.sym_at(*pos, "#") {
+            *pos += 1;
+            self.unary(pos);
+            return Value::Nil;
+        }
+        if self.sym_at(*pos, "-") {
+            *pos += 1;
+            return match self.unary(pos) {
+                Value::Num(n) => Value::Num(-n),
+                _ => Value::Nil,
+            };
+        }
+        self.suffixed(pos)
+    }
+
+    /// A primary value followed by field accesses and calls.
+    fn suffixed(&mut self, pos: &mut usize) -> Value {
+        let Some(token) = self.tokens.get(*pos).cloned() else {
+            return Value::Nil;
+        };
+        *pos += 1;
+        // Where the value sits under the globals, for fields the script
+        // doesn't set itself (`vim.api` once it has set `vim.g.…`).
+        let mut path = match &token {
+            Token::Name(name) => Some(name.clone()),
+            _ => None,
+        };
+        let mut value = match token {
+            Token::Str(s) => return Value::Str(s),
+            Token::Num(n) => return Value::Num(n),
+            Token::Sym("{") => return self.table(pos),
+            Token::Sym("(") => {
+                let value = self.expression(pos);
+                if self.sym_at(*pos, ")") {
+                    *pos += 1;
+                }
+                value
+            }
+            Token::Name(name) => match name.as_str() {
+                "nil" => return Value::Nil,
+                "true" => return Value::Bool(true),
+                "false" => return Value::Bool(false),
+                "function" => {
+                    let (function, next) = self.function(*pos);
+                    *pos = next;
+                    return function;
+                }
+                _ => self.lookup(&name),
+            },
+            Token::Sym(_) => return Value::Nil,
+        };
+        loop {
+            match self.tokens.get(*pos) {
+                Some(Token::Sym(".")) => {
+                    let field = self.name_at(*pos + 1).unwrap_or_default().to_owned();
+                    value = value.index(&field);
+                    path = path.map(|path| format!("{path}.{field}"));
+                    if let (Value::Nil, Some(path)) = (&value, &path) {
+                        value = Value::Ref(path.clone());
+                    }
+                    *pos += 2;
+                }
+                Some(Token::Sym("[")) => {
+                    *pos += 1;
+                    let key = self.expression(pos);
+                    if self.sym_at(*pos, "]") {
+                        *pos += 1;
+                    }
+                    value = key.key().map_or(Value::Nil, |key| value.index(&key));
+                    path = None;
+                }
+                Some(Token::Sym(":")) => {
+                    let method = self.name_at(*pos + 1).unwrap_or_default().to_owned();
+                    *pos += 2;
+                    let args = self.arguments(pos);
+                    value = self.call(&value.index(&method), args);
+                    path = None;
+                }
+                Some(Token::Sym("(") | Token::Sym("{") | Token::Str(_)) => {
+                    let args = self.arguments(pos);
+                    value = self.call(&value, args);
+                    path = None;
+                }
+                _ => return value,
+            }
+        }
+    }
+
+    /// Call arguments: a parenthesized list, a table or a string.
+    fn arguments(&mut self, pos: &mut usize) -> Vec<Value> {
+        match self.tokens.get(*pos).cloned() {
+            Some(Token::Str(s)) => {
+                *pos += 1;
+                vec![Value::Str(s)]
+            }
+            Some(Token::Sym("{")) => {
+                *pos += 1;
+                vec![self.table(pos)]
+            }
+            Some(Token::Sym("(")) => {
+                *pos += 1;
+                if self.sym_at(*pos, ")") {
+                    *pos += 1;
+                    return Vec::new();
+                }
+                let args = self.expressions(pos);
+                if self.sym_at(*pos, ")") {
+                    *pos += 1;
+                }
+                args
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// A table constructor's fields, from after its `{`.
+    fn table(&mut self, pos: &mut usize) -> Value {
+        let mut fields = IndexMap::new();
+        let mut index = 1;
+        while *pos < self.tokens.len() && !self.sym_at(*pos, "}") {
+            let start = *pos;
+            if self.sym_at(*pos, "[") {
+                *pos += 1;
+                let key = self.expression(pos);
+                if self.sym_at(*pos, "]") && self.sym_at(*pos + 1, "=") {
+                    *pos += 2;
+                }
+                let value = self.expression(pos);
+                if let Some(key) = key.key() {
+                    fields.insert(key, value);
+                }
+            } else if self.name_at(*pos).is_some() && self.sym_at(*pos + 1, "=") {
+                let key = self.name_at(*pos).unwrap_or_default().to_owned();
+                *pos += 2;
+                let value = self.expression(pos);
+                fields.insert(key, value);
+            } else {
+                let value = self.expression(pos);
+                fields.insert(index.to_string(), value);
+                index += 1;
+            }
+            if self.sym_at(*pos, ",") || self.sym_at(*pos, ";") {
+                *pos += 1;
+            } else if *pos == start {
+                // Something the subset doesn't cover; skip it.
+                *pos += 1;
+            }
+        }
+        *pos += 1;
+        Value::Table(fields)
+    }
+
+    fn call(&mut self, callee: &Value, args: Vec<Value>) -> Value {
+        match callee {
+            Value::Function { params, body } if self.depth < MAX_DEPTH => {
+                let frame = params.iter().cloned().zip(args).collect();
+                self.frames.push(frame);
+                self.depth += 1;
+                let result = self.execute(body.clone());
+                self.depth -= 1;
+                self.frames.pop();
+                result.unwrap_or_default()
+            }
+            Value::Ref(path) => self.builtin(path, args),
+            _ => Value::Nil,
+        }
+    }
+
+    /// The parts of Neovim's API a colorscheme needs.
+    fn builtin(&mut self, path: &str, args: Vec<Value>) -> Value {
+        match path {
+            "pairs" | "ipairs" => args.into_iter().next().unwrap_or_default(),
+            "vim.tbl_extend" | "vim.tbl_deep_extend" => {
+                let mut merged = IndexMap::new();
+                for arg in args.into_iter().skip(1) {
+                    if let Value::Table(fields) = arg {
+                        merged.extend(fields);
+                    }
+                }
+                Value::Table(merged)
+            }
+            "vim.cmd" | "vim.api.nvim_command" | "vim.api.nvim_exec" | "vim.api.nvim_exec2" => {
+                for arg in &args {
+                    if let Value::Str(script) = arg {
+                        for line in script.lines() {
+                            hi_command(line.trim(), &mut self.highlights);
+                        }
+                    }
+                }
+                Value::Nil
+            }
+            "vim.cmd.highlight" | "vim.cmd.hi" => {
+                let words: Vec<String> = args.iter().filter_map(Value::key).collect();
+                hi_command(&format!("hi {}", words.join(" ")), &mut self.highlights);
+                Value::Nil
+            }
+            _ if path.ends_with("nvim_set_hl") => {
+                if let (Some(Value::Str(group)), Some(Value::Table(spec))) =
+                    (args.get(1), args.get(2))
+                {
+                    let flag = |key: &str| spec.get(key).is_some_and(Value::is_truthy);
+                    let color = |keys: &[&str]| keys.iter().find_map(|k| spec.get(*k)?.color());
+                    let highlight = Highlight {
+                        fg: color(&["fg", "foreground"]),
+                        bg: color(&["bg", "background"]),
+                        bold: flag("bold"),
+                        italic: flag("italic"),
+                        underline: flag("underline") || flag("undercurl"),
+                        strikethrough: flag("strikethrough"),
+                        link: spec.get("link").and_then(Value::key),
+                    };
+                    self.highlights.insert(group.clone(), highlight);
+                }
+                Value::Nil
+            }
+            _ => Value::Nil,
+        }
+    }
+}
@@ -0,0 +1,416 @@
+//! Converting Sublime Text `.sublime-color-scheme` files into the theme
+//! model.
+//!
+//! `globals` map onto their workbench equivalents and each rule becomes a
+//! token rule. Color values are evaluated the way Sublime does: hex, CSS
+//! `rgb()`/`hsl()` functions, a few CSS names, `var()` references into
+//! `variables`, and `color()` with the `alpha()`, `lightness()`,
+//! `saturation()`, `blend()`, `blenda()` and `min-contrast()` adjusters.
+//! Values that can't be evaluated are dropped.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::color::{Color, MID_LUMINANCE};
+use crate::jsonc;
+use crate::theme::{ScopeSpec, Theme, TokenRule, TokenSettings};
+
+/// `globals` and the workbench keys they become.
+const GLOBALS: &[(&str, &[&str])] = &[
+    ("background", &["editor.background"]),
+    ("foreground", &["editor.foreground"]),
+    ("caret", &["editorCursor.foreground"]),
+    ("line_highlight", &["editor.lineHighlightBackground"]),
+    ("selection", &["editor.selectionBackground"]),
+    ("selection_foreground", &["editor.selectionForeground"]),
+    ("selection_border", &["editor.selectionHighlightBorder"]),
+    (
+        "inactive_selection",
+        &["editor.inactiveSelectionBackground"],
+    ),
+    ("gutter", &["editorGutter.background"]),
+    ("gutter_foreground", &["editorLineNumber.foreground"]),
+    (
+        "gutter_foreground_highlight",
+        &["editorLineNumber.activeForeground"],
+    ),
+    ("find_highlight", &["editor.findMatchBackground"]),
+    ("highlight", &["editor.findMatchBorder"]),
+    ("invisibles", &["editorWhitespace.foreground"]),
+    ("guide", &["editorIndentGuide.background1"]),
+    ("active_guide", &["editorIndentGuide.activeBackground1"]),
+    ("brackets_foreground", &["editorBracketMatch.border"]),
+    ("accent", &["focusBorder"]),
+    ("line_diff_added", &["editorGutter.addedBackground"]),
+    ("line_diff_modified", &["editorGutter.modifiedBackground"]),
+    ("line_diff_deleted", &["editorGutter.deletedBackground"]),
+];
+
+/// CSS color names Sublime schemes commonly use.
+const NAMED: &[(&str, Color)] = &[
+    ("black", Color::rgb(0x00, 0x00, 0x00)),
+    ("white", Color::rgb(0xff, 0xff, 0xff)),
+    ("red", Color::rgb(0xff, 0x00, 0x00)),
+    ("green", Color::rgb(0x00, 0x80, 0x00)),
+    ("lime", Color::rgb(0x00, 0xff, 0x00)),
+    ("blue", Color::rgb(0x00, 0x00, 0xff)),
+    ("yellow", Color::rgb(0xff, 0xff, 0x00)),
+    ("cyan", Color::rgb(0x00, 0xff, 0xff)),
+    ("aqua", Color::rgb(0x00, 0xff, 0xff)),
+    ("magenta", Color::rgb(0xff, 0x00, 0xff)),
+    ("fuchsia", Color::rgb(0xff, 0x00, 0xff)),
+    ("orange", Color::rgb(0xff, 0xa5, 0x00)),
+    ("purple", Color::rgb(0x80, 0x00, 0x80)),
+    ("pink", Color::rgb(0xff, 0xc0, 0xcb)),
+    ("brown", Color::rgb(0xa5, 0x2a, 0x2a)),
+    ("gray", Color::rgb(0x80, 0x80, 0x80)),
+    ("grey", Color::rgb(0x80, 0x80, 0x80)),
+    ("silver", Color::rgb(0xc0, 0xc0, 0xc0)),
+    ("maroon", Color::rgb(0x80, 0x00, 0x00)),
+    ("navy", Color::rgb(0x00, 0x00, 0x80)),
+    ("olive", Color::rgb(0x80, 0x80, 0x00)),
+    ("teal", Color::rgb(0x00, 0x80, 0x80)),
+    ("transparent", Color::rgba(0x00, 0x00, 0x00, 0x00)),
+];
+
+/// How deep `var()` references may nest before they count as a cycle.
+const MAX_DEPTH: usize = 16;
+
+pub fn load(path: &Path) -> Result<Theme> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    parse(&text).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+pub fn parse(text: &str) -> Result<Theme> {
+    let root: Value = jsonc::from_str(text)?;
+    let Some(root) = root.as_object() else {
+        bail!("expected an object at the top level");
+    };
+    let variables: IndexMap<String, String> = root
+        .get("variables")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.to_owned())))
+        .collect();
+    let eval = |value: Option<&Value>| {
+        // A gradient foreground is a list; its first stop stands in.
+        let value = match value? {
+            Value::Array(stops) => stops.first()?,
+            value => value,
+        };
+        evaluate(value.as_str()?, &variables, 0).ok()
+    };
+
+    let mut colors = IndexMap::new();
+    if let Some(globals) = root.get("globals").and_then(Value::as_object) {
+        for (global, keys) in GLOBALS {
+            if let Some(color) = eval(globals.get(*global)) {
+                for key in *keys {
+                    colors.insert((*key).to_owned(), color.to_string());
+                }
+            }
+        }
+    }
+
+    let mut token_colors = Vec::new();
+    for rule in root
+        .get("rules")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object)
+    {
+        let Some(scope) = rule.get("scope").and_then(Value::as_str).map(str::trim) else {
+            continue;
+        };
+        if scope.is_empty() {
+            continue;
+        }
+        let font_style = rule
+            .get("font_style")
+            .and_then(Value::as_str)
+            .map(|style| style.split_whitespace().collect::<Vec<_>>().join(" "));
+        let settings = TokenSettings {
+            foreground: eval(rule.get("foreground")).map(|c| c.to_string()),
+            background: eval(rule.get("background")).map(|c| c.to_string()),
+            font_style,
+        };
+        if settings == TokenSettings::default() {
+            continue;
+        }
+        token_colors.push(TokenRule {
+            name: rule.get("name").and_then(Value::as_str).map(str::to_owned),
+            scope: ScopeSpec::One(scope.to_owned()),
+            settings,
+        });
+    }
+
+    let dark = colors
+        .get("editor.background")
+        .and_then(|c| c.parse::<Color>().ok())
+        .is_none_or(|c| c.relative_luminance() < MID_LUMINANCE);
+    Ok(Theme {
+        name: root.get("name").and_then(Value::as_str).map(str::to_owned),
+        kind: Some(if dark { "dark" } else { "light" }.to_owned()),
+        colors,
+        token_colors,
+        ..Theme::default()
+    })
+}
+
+/// Evaluates a Sublime color value.
+fn evaluate(value: &str, variables: &IndexMap<String, String>, depth: usize) -> Result<Color> {
+    if depth > MAX_DEPTH {
+        bail!("variables nest too deeply (is there a cycle?)");
+    }
+    let value = value.trim();
+    if value.starts_with('#') {
+        return Ok(value.parse()?);
+    }
+    if let Some((function, args)) = call(value) {
+        return match function {
+            "var" => {
+                let Some(referenced) = variables.get(args.trim()) else {
+                    bail!("undefined variable `{}`", args.trim());
+                };
+                evaluate(referenced, variables, depth + 1)
+            }
+            "rgb" | "rgba" => rgb_function(args),
+            "hsl" | "hsla" => hsl_function(args),
+            "color" => color_function(args, variables, depth),
+            _ => bail!("unsupported color function `{function}()`"),
+        };
+    }
+    NAMED
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(value))
+        .map(|(_, color)| *color)
+        .with_context(|| format!("unsupported color `{value}`"))
+}
+
+/// `name(args)` → `(name, args)`.
+fn call(text: &str) -> Option<(&str, &str)> {
+    let open = text.find('(')?;
+    let args = text[open + 1..].strip_suffix(')')?;
+    Some((text[..open].trim(), args))
+}
+
+/// Splits on whitespace (and commas and slashes, for CSS functions)
+/// outside parentheses.
+fn tokens(text: &str, separators: &[char]) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if depth == 0 && (c.is_whitespace() || separators.contains(&c)) {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// A number, with `%` meaning hundredths.
+fn amount(text: &str) -> Result<f64> {
+    let text = text.trim();
+    let (number, scale) = match text.strip_suffix('%') {
+        Some(number) => (number, 100.0),
+        None => (text, 1.0),
+    };
+    let number: f64 = number
+        .trim()
+        .parse()
+        .with_context(|| format!("`{text}` is not a number"))?;
+    Ok(number / scale)
+}
+
+fn rgb_function(args: &str) -> Result<Color> {
+    let args = tokens(args, &[',', '/']);
+    if !(3..=4).contains(&args.len()) {
+        bail!("rgb() takes three or four values");
+    }
+    let channel = |text: &str| -> Result<f64> {
+        Ok(if text.ends_with('%') {
+            amount(text)? * 255.0
+        } else {
+            amount(text)?
+        })
+    };
+    let alpha = args.get(3).map_or(Ok(1.0), |a| amount(a))?;
+    Ok(from_unit([
+        channel(&args[0])? / 255.0,
+        channel(&args[1])? / 255.0,
+        channel(&args[2])? / 255.0,
+        alpha,
+    ]))
+}
+
+fn hsl_function(args: &str) -> Result<Color> {
+    let args = tokens(args, &[',', '/']);
+    if !(3..=4).contains(&args.len()) {
+        bail!("hsl() takes three or four values");
+    }
+    let hue = amount(args[0].trim_end_matches("deg"))?;
+    let alpha = args.get(3).map_or(Ok(1.0), |a| amount(a))?;
+    let [r, g, b] = hsl_to_rgb([hue, amount(&args[1])?, amount(&args[2])?]);
+    Ok(from_unit([r, g, b, alpha]))
+}
+
+fn color_function(args: &str, variables: &IndexMap<String, String>, depth: usize) -> Result<Color> {
+    let mut parts = tokens(args, &[]).into_iter();
+    let Some(base) = parts.next() else {
+        bail!("color() needs a base color");
+    };
+    let mut color = evaluate(&base, variables, depth + 1)?;
+    for adjuster in parts {
+        let Some((name, args)) = call(&adjuster) else {
+            bail!("unsupported color() adjuster `{adjuster}`");
+        };
+        color = match name {
+            "alpha" | "a" => {
+                let alpha = adjust(f64::from(color.a) / 255.0, args)?;
+                Color::rgba(color.r, color.g, color.b, unit_byte(alpha))
+            }
+            "lightness" | "l" | "saturation" | "s" => {
+                let [r, g, b, a] = to_unit(color);
+                let mut hsl = rgb_to_hsl([r, g, b]);
+                let index = if name.starts_with('l') { 2 } else { 1 };
+                hsl[index] = adjust(hsl[index], args)?.clamp(0.0, 1.0);
+                let [r, g, b] = hsl_to_rgb(hsl);
+                from_unit([r, g, b, a])
+            }
+            "blend" | "blenda" => {
+                let args = tokens(args, &[]);
+                let (Some(other), Some(weight)) = (args.first(), args.get(1)) else {
+                    bail!("{name}() takes a color and a percentage");
+                };
+                let other = evaluate(other, variables, depth + 1)?;
+                let hsl = args.get(2).is_some_and(|mode| mode == "hsl");
+                blend(color, other, amount(weight)?, hsl, name == "blenda")
+            }
+            "min-contrast" => {
+                let args = tokens(args, &[]);
+                let (Some(other), Some(ratio)) = (args.first(), args.get(1)) else {
+                    bail!("min-contrast() takes a color and a ratio");
+                };
+                let other = evaluate(other, variables, depth + 1)?;
+                min_contrast(color, other, amount(ratio)?)
+            }
+            _ => bail!("unsupported color() adjuster `{name}()`"),
+        };
+    }
+    Ok(color)
+}
+
+/// Applies an adjuster's argument: `+ x` and `- x` shift, `* x` scales, a
+/// bare value replaces.
+fn adjust(current: f64, args: &str) -> Result<f64> {
+    let args = args.trim();
+    Ok(match args.chars().next() {
+        Some('+') => current + amount(&args[1..])?,
+        Some('-') => current - amount(&args[1..])?,
+        Some('*') => current * amount(&args[1..])?,
+        _ => amount(args)?,
+    })
+}
+
+/// Mixes `weight` of `base` with the rest of `other`; `blenda()` mixes the
+/// alpha too, `blend()` keeps the base's.
+fn blend(base: Color, other: Color, weight: f64, hsl: bool, with_alpha: bool) -> Color {
+    let [r1, g1, b1, a1] = to_unit(base);
+    let [r2, g2, b2, a2] = to_unit(other);
+    let mix = |x: f64, y: f64| x * weight + y * (1.0 - weight);
+    let [r, g, b] = if hsl {
+        let (h1, h2) = (rgb_to_hsl([r1, g1, b1]), rgb_to_hsl([r2, g2, b2]));
+        hsl_to_rgb([mix(h1[0], h2[0]), mix(h1[1], h2[1]), mix(h1[2], h2[2])])
+    } else {
+        [mix(r1, r2), mix(g1, g2), mix(b1, b2)]
+    };
+    from_unit([r, g, b, if with_alpha { mix(a1, a2) } else { a1 }])
+}
+
+/// `color` lightened or darkened, whichever direction gets there, until it
+/// contrasts with `other` by `ratio`.
+fn min_contrast(color: Color, other: Color, ratio: f64) -> Color {
+    if color.contrast_ratio(other) >= ratio {
+        return color;
+    }
+    let [r, g, b, a] = to_unit(color);
+    let [h, s, l] = rgb_to_hsl([r, g, b]);
+    let toward_white = other.relative_luminance() < 0.5;
+    for step in 1..=100 {
+        let shift = f64::from(step) / 100.0;
+        let l = if toward_white { l + shift } else { l - shift }.clamp(0.0, 1.0);
+        let [r, g, b] = hsl_to_rgb([h, s, l]);
+        let candidate = from_unit([r, g, b, a]);
+        if candidate.contrast_ratio(other) >= ratio || l <= 0.0 || l >= 1.0 {
+            return candidate;
+        }
+    }
+    color
+}
+
+fn to_unit(color: Color) -> [f64; 4] {
+    [color.r, color.g, color.b, color.a].map(|c| f64::from(c) / 255.0)
+}
+
+fn from_unit([r, g, b, a]: [f64; 4]) -> Color {
+    Color::rgba(unit_byte(r), unit_byte(g), unit_byte(b), unit_byte(a))
+}
+
+fn unit_byte(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Hue in degrees, saturation and lightness in `0..=1`.
+fn rgb_to_hsl([r, g, b]: [f64; 3]) -> [f64; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return [0.0, 0.0, l];
+    }
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    [h * 60.0, s, l]
+}
+
+fn hsl_to_rgb([h, s, l]: [f64; 3]) -> [f64; 3] {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    [r + m, g + m, b + m]
+}
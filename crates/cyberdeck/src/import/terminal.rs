@@ -0,0 +1,217 @@
+//! Reading terminal color schemes from the public collections: Gogh's
+//! YAML and JSON (`color_01`…`color_16`), the Windows Terminal JSON that
+//! iTerm2-Color-Schemes publishes (`black`…`brightWhite`) and iTerm2's own
+//! `.itermcolors` property lists.
+//!
+//! A scheme only has terminal colors, so the editor roles they don't name
+//! are fitted from its ANSI accents by [`complete_roles`].
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use plist::Value as Plist;
+use yaml_rust::{Yaml, YamlLoader};
+
+use crate::color::Color;
+use crate::derive::complete_roles;
+
+use super::Sample;
+
+/// Key spellings of the sixteen ANSI colors, per collection format.
+const ANSI_KEYS: [[&str; 3]; 16] = [
+    ["color_01", "black", "Ansi 0 Color"],
+    ["color_02", "red", "Ansi 1 Color"],
+    ["color_03", "green", "Ansi 2 Color"],
+    ["color_04", "yellow", "Ansi 3 Color"],
+    ["color_05", "blue", "Ansi 4 Color"],
+    ["color_06", "purple", "Ansi 5 Color"],
+    ["color_07", "cyan", "Ansi 6 Color"],
+    ["color_08", "white", "Ansi 7 Color"],
+    ["color_09", "brightBlack", "Ansi 8 Color"],
+    ["color_10", "brightRed", "Ansi 9 Color"],
+    ["color_11", "brightGreen", "Ansi 10 Color"],
+    ["color_12", "brightYellow", "Ansi 11 Color"],
+    ["color_13", "brightBlue", "Ansi 12 Color"],
+    ["color_14", "brightPurple", "Ansi 13 Color"],
+    ["color_15", "brightCyan", "Ansi 14 Color"],
+    ["color_16", "brightWhite", "Ansi 15 Color"],
+];
+
+const BACKGROUND_KEYS: &[&str] = &["background", "Background Color"];
+const FOREGROUND_KEYS: &[&str] = &["foreground", "Foreground Color"];
+const CURSOR_KEYS: &[&str] = &["cursor", "cursorColor", "Cursor Color"];
+const SELECTION_KEYS: &[&str] = &["selectionBackground", "Selection Color"];
+
+/// Roles an ANSI color plays outright, by index.
+const ANSI_ROLES: &[(usize, &[&str])] = &[
+    (1, &["error", "deleted"]),
+    (2, &["string", "success", "inserted"]),
+    (3, &["warning", "class"]),
+    (4, &["function", "accent.primary"]),
+    (5, &["keyword", "accent.secondary"]),
+    (6, &["type"]),
+    (8, &["comment"]),
+];
+
+/// The hued ANSI colors, which the remaining roles are fitted to.
+const ACCENTS: [usize; 12] = [1, 2, 3, 4, 5, 6, 9, 10, 11, 12, 13, 14];
+
+/// A parsed terminal scheme.
+#[derive(Debug, Clone)]
+pub struct Scheme {
+    pub name: Option<String>,
+    pub background: Color,
+    pub foreground: Color,
+    pub cursor: Option<Color>,
+    pub selection: Option<Color>,
+    pub ansi: [Color; 16],
+}
+
+impl Scheme {
+    /// Reads a scheme, telling the format from the extension: `.itermcolors`,
+    /// `.json`, else YAML.
+    pub fn load(path: &Path) -> Result<Scheme> {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let colors = if extension.eq_ignore_ascii_case("itermcolors") {
+            let value = Plist::from_file(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            itermcolors(&value)
+        } else {
+            let text = fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            if extension.eq_ignore_ascii_case("json") {
+                json(&text)
+            } else {
+                yaml(&text)
+            }
+        };
+        let mut scheme = colors
+            .and_then(|colors| Scheme::from_keys(&colors))
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        if scheme.name.is_none() {
+            scheme.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned());
+        }
+        Ok(scheme)
+    }
+
+    /// A scheme from its values by key, in any of the collections' key
+    /// spellings. `name` is the only non-color value read.
+    fn from_keys(values: &IndexMap<String, String>) -> Result<Scheme> {
+        let color = |keys: &[&str]| -> Result<Option<Color>> {
+            let Some((key, value)) = keys.iter().find_map(|k| Some((*k, values.get(*k)?))) else {
+                return Ok(None);
+            };
+            let value = value.trim();
+            let value = if value.starts_with('#') {
+                value.to_owned()
+            } else {
+                format!("#{value}")
+            };
+            match value.parse() {
+                Ok(color) => Ok(Some(color)),
+                Err(_) => bail!("`{key}` is not a color: {value}"),
+            }
+        };
+        let mut ansi = [Color::rgb(0, 0, 0); 16];
+        for (slot, keys) in ansi.iter_mut().zip(ANSI_KEYS) {
+            let Some(color) = color(&keys)? else {
+                bail!("missing ANSI color `{}`", keys[0]);
+            };
+            *slot = color;
+        }
+        let (Some(background), Some(foreground)) =
+            (color(BACKGROUND_KEYS)?, color(FOREGROUND_KEYS)?)
+        else {
+            bail!("missing `background` or `foreground`");
+        };
+        Ok(Scheme {
+            name: values.get("name").cloned(),
+            background,
+            foreground,
+            cursor: color(CURSOR_KEYS)?,
+            selection: color(SELECTION_KEYS)?,
+            ansi,
+        })
+    }
+
+    /// The roles the scheme names outright.
+    fn named_roles(&self) -> IndexMap<&'static str, Color> {
+        let mut roles = IndexMap::from([
+            ("background", self.background),
+            ("foreground", self.foreground),
+        ]);
+        roles.extend(self.cursor.map(|c| ("cursor", c)));
+        roles.extend(self.selection.map(|c| ("highlight", c)));
+        for (index, names) in ANSI_ROLES {
+            roles.extend(names.iter().map(|name| (*name, self.ansi[*index])));
+        }
+        roles
+    }
+
+    /// A color for each of `base`'s roles: the ones the scheme names, and
+    /// the rest fitted to its ANSI accents.
+    pub fn roles(&self, base: &IndexMap<&'static str, Color>) -> IndexMap<&'static str, Color> {
+        let accents: Vec<Color> = ACCENTS.iter().map(|i| self.ansi[*i]).collect();
+        complete_roles(base, &self.named_roles(), &accents)
+    }
+
+    pub fn samples(&self, roles: &IndexMap<&'static str, Color>) -> Vec<Sample> {
+        let named = roles.iter().map(|(role, color)| Sample {
+            color: *color,
+            weight: 1,
+            roles: vec![(*role).to_owned()],
+        });
+        let ansi = self.ansi.iter().map(|color| Sample {
+            color: *color,
+            weight: 1,
+            roles: Vec::new(),
+        });
+        named.chain(ansi).collect()
+    }
+}
+
+fn json(text: &str) -> Result<IndexMap<String, String>> {
+    let values: IndexMap<String, serde_json::Value> = serde_json::from_str(text)?;
+    Ok(values
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value.as_str()?.to_owned())))
+        .collect())
+}
+
+fn yaml(text: &str) -> Result<IndexMap<String, String>> {
+    let docs = YamlLoader::load_from_str(text)?;
+    let Some(Yaml::Hash(hash)) = docs.into_iter().next() else {
+        bail!("expected a mapping at the top level");
+    };
+    Ok(hash
+        .into_iter()
+        .filter_map(|(key, value)| Some((key.into_string()?, value.into_string()?)))
+        .collect())
+}
+
+/// `.itermcolors` entries as hex, from their 0–1 color components.
+fn itermcolors(value: &Plist) -> Result<IndexMap<String, String>> {
+    let Some(root) = value.as_dictionary() else {
+        bail!("expected a dictionary at the top level");
+    };
+    Ok(root
+        .iter()
+        .filter_map(|(key, value)| {
+            let components = value.as_dictionary()?;
+            let channel = |name: &str| {
+                let value = components.get(name)?.as_real()?;
+                Some((value.clamp(0.0, 1.0) * 255.0).round() as u8)
+            };
+            let color = Color::rgb(
+                channel("Red Component")?,
+                channel("Green Component")?,
+                channel("Blue Component")?,
+            );
+            Some((key.clone(), color.to_string()))
+        })
+        .collect())
+}
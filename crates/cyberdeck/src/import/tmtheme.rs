@@ -0,0 +1,114 @@
+//! Converting TextMate/Sublime `.tmTheme` property lists into the theme
+//! model.
+//!
+//! The first settings entry without a scope holds the editor colors, which
+//! map onto their workbench equivalents; every scoped entry becomes a token
+//! rule. Colors that don't parse are dropped, valid ones are normalized to
+//! lowercase hex.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use plist::{Dictionary, Value};
+
+use crate::color::{Color, MID_LUMINANCE};
+use crate::theme::{ScopeSpec, Theme, TokenRule, TokenSettings};
+
+/// Global settings and the workbench keys they become.
+const GLOBALS: &[(&str, &[&str])] = &[
+    ("background", &["editor.background"]),
+    ("foreground", &["editor.foreground"]),
+    ("caret", &["editorCursor.foreground"]),
+    ("selection", &["editor.selectionBackground"]),
+    ("selectionForeground", &["editor.selectionForeground"]),
+    ("selectionBorder", &["editor.selectionHighlightBorder"]),
+    ("inactiveSelection", &["editor.inactiveSelectionBackground"]),
+    ("lineHighlight", &["editor.lineHighlightBackground"]),
+    ("invisibles", &["editorWhitespace.foreground"]),
+    ("findHighlight", &["editor.findMatchHighlightBackground"]),
+    ("gutter", &["editorGutter.background"]),
+    ("gutterForeground", &["editorLineNumber.foreground"]),
+    ("guide", &["editorIndentGuide.background1"]),
+    ("activeGuide", &["editorIndentGuide.activeBackground1"]),
+    ("bracketsForeground", &["editorBracketMatch.border"]),
+    (
+        "bracketContentsForeground",
+        &["editorBracketMatch.background"],
+    ),
+];
+
+pub fn load(path: &Path) -> Result<Theme> {
+    let value =
+        Value::from_file(path).with_context(|| format!("failed to read {}", path.display()))?;
+    from_plist(&value).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+/// The theme a `.tmTheme` property list describes.
+pub fn from_plist(value: &Value) -> Result<Theme> {
+    let Some(root) = value.as_dictionary() else {
+        bail!("expected a dictionary at the top level");
+    };
+    let Some(entries) = root.get("settings").and_then(Value::as_array) else {
+        bail!("missing `settings` array");
+    };
+
+    let mut colors = IndexMap::new();
+    let mut token_colors = Vec::new();
+    for entry in entries.iter().filter_map(Value::as_dictionary) {
+        let Some(settings) = entry.get("settings").and_then(Value::as_dictionary) else {
+            continue;
+        };
+        match entry.get("scope").and_then(Value::as_string).map(str::trim) {
+            Some(scope) if !scope.is_empty() => token_colors.push(TokenRule {
+                name: entry
+                    .get("name")
+                    .and_then(Value::as_string)
+                    .map(str::to_owned),
+                scope: ScopeSpec::One(scope.to_owned()),
+                settings: token_settings(settings),
+            }),
+            _ if colors.is_empty() => {
+                for (setting, keys) in GLOBALS {
+                    if let Some(color) = color(settings, setting) {
+                        for key in *keys {
+                            colors.insert((*key).to_owned(), color.to_string());
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let dark = colors
+        .get("editor.background")
+        .and_then(|c| c.parse::<Color>().ok())
+        .is_none_or(|c| c.relative_luminance() < MID_LUMINANCE);
+    Ok(Theme {
+        name: root
+            .get("name")
+            .and_then(Value::as_string)
+            .map(str::to_owned),
+        kind: Some(if dark { "dark" } else { "light" }.to_owned()),
+        colors,
+        token_colors,
+        ..Theme::default()
+    })
+}
+
+fn token_settings(settings: &Dictionary) -> TokenSettings {
+    let font_style = settings
+        .get("fontStyle")
+        .and_then(Value::as_string)
+        .map(|style| style.split_whitespace().collect::<Vec<_>>().join(" "));
+    TokenSettings {
+        foreground: color(settings, "foreground").map(|c| c.to_string()),
+        background: color(settings, "background").map(|c| c.to_string()),
+        font_style,
+    }
+}
+
+fn color(settings: &Dictionary, key: &str) -> Option<Color> {
+    settings.get(key)?.as_string()?.trim().parse().ok()
+}
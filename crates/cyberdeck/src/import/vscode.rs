@@ -0,0 +1,45 @@
+//! Sampling VS Code color themes.
+
+use indexmap::IndexMap;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::theme::Theme;
+
+use super::Sample;
+
+/// Every color the theme references, weighted by reference count, with the
+/// roles [`resolve_roles`] finds attached to the exact matching color.
+pub fn samples(theme: &Theme) -> Vec<Sample> {
+    let mut weights: IndexMap<Color, usize> = IndexMap::new();
+    for color_ref in theme.color_refs() {
+        if let Ok(color) = color_ref.value.parse::<Color>() {
+            *weights.entry(color.opaque()).or_default() += 1;
+        }
+    }
+
+    let mut samples: IndexMap<Color, Sample> = weights
+        .into_iter()
+        .map(|(color, weight)| {
+            let sample = Sample {
+                color,
+                weight,
+                roles: Vec::new(),
+            };
+            (color, sample)
+        })
+        .collect();
+    for (role, color) in resolve_roles(theme) {
+        let color = color.opaque();
+        samples
+            .entry(color)
+            .or_insert_with(|| Sample {
+                color,
+                weight: 1,
+                roles: Vec::new(),
+            })
+            .roles
+            .push(role.to_owned());
+    }
+    samples.into_values().collect()
+}
@@ -0,0 +1,202 @@
+//! Reading the color themes a VS Code extension contributes, from a `.vsix`
+//! file or an extension downloaded the way `grammar fetch` does (Open VSX
+//! by default, the Marketplace with `marketplace:` and a version).
+//!
+//! Each entry of the manifest's `contributes.themes` is loaded as VS Code
+//! would: a theme's `include` is layered under it, and `tokenColors` may
+//! name a `.tmTheme` in the archive instead of listing rules.
+
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use plist::Value as Plist;
+use serde_json::Value;
+use zip::ZipArchive;
+
+use crate::grammar::{read_entry, read_manifest, Source};
+use crate::jsonc;
+use crate::theme::Theme;
+
+use super::tmtheme;
+
+/// How many `include` links to follow before giving up on a cycle.
+const MAX_DEPTH: usize = 8;
+
+/// Extensions of files the importers read, which a missing file's name
+/// would end in where an extension ID has its name.
+const FILE_EXTENSIONS: &[&str] = &[
+    "vsix",
+    "zip",
+    "json",
+    "jsonc",
+    "tmtheme",
+    "sublime-color-scheme",
+    "icls",
+    "toml",
+    "lua",
+    "vim",
+    "yaml",
+    "yml",
+];
+
+/// One theme an extension contributes.
+#[derive(Debug, Clone)]
+pub struct ContributedTheme {
+    /// The theme's label in the theme picker.
+    pub label: String,
+    pub theme: Theme,
+}
+
+/// The themes of a `.vsix` file, or of an extension by source as `grammar
+/// fetch` takes them (a bare `publisher.name` is looked up on Open VSX),
+/// with an optional `@version` (required for the Marketplace).
+pub fn load(spec: &str) -> Result<Vec<ContributedTheme>> {
+    if Path::new(spec).is_file() {
+        let bytes = fs::read(spec).with_context(|| format!("failed to read {spec}"))?;
+        return themes(&bytes).with_context(|| format!("failed to read the themes of {spec}"));
+    }
+    let (source, version) = match spec.rsplit_once('@') {
+        Some((source, version)) if !spec.starts_with("github:") => (source, Some(version)),
+        _ => (spec, None),
+    };
+    // Only something shaped like `publisher.name` is looked up online, so a
+    // mistyped path fails here instead of turning into a download.
+    if !source.contains(':') && !is_extension_id(source) {
+        bail!("{spec}: file not found");
+    }
+    let source: Source = source
+        .parse()
+        .with_context(|| format!("`{spec}` is neither a file nor an extension ID"))?;
+    if matches!(source, Source::Marketplace { .. }) && version.is_none() {
+        bail!("{source} needs a version (`{source}@1.2.3`); the marketplace has no stable `latest` link");
+    }
+    let archive = source.download(version)?;
+    themes(&archive.bytes).with_context(|| format!("failed to read the themes of {source}"))
+}
+
+/// Whether `source` is a bare extension ID: `publisher.name`, both parts
+/// word characters, and the name not a file extension.
+fn is_extension_id(source: &str) -> bool {
+    let word = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    };
+    source.split_once('.').is_some_and(|(publisher, name)| {
+        word(publisher)
+            && word(name)
+            && !FILE_EXTENSIONS.contains(&name.to_ascii_lowercase().as_str())
+    })
+}
+
+/// The themes an extension archive contributes, in manifest order. Themes
+/// that fail to load are reported and skipped.
+pub fn themes(bytes: &[u8]) -> Result<Vec<ContributedTheme>> {
+    let mut zip = ZipArchive::new(Cursor::new(bytes)).context("not a zip archive")?;
+    let (base, manifest) = read_manifest(&mut zip)?;
+    let mut themes = Vec::new();
+    let contributed = manifest
+        .pointer("/contributes/themes")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten();
+    for entry in contributed {
+        let field = |key: &str| entry.get(key).and_then(Value::as_str);
+        let Some(path) = field("path") else {
+            continue;
+        };
+        let label = field("label").or(field("id")).unwrap_or(path).to_owned();
+        let entry_name = join(&base, path);
+        let loaded = load_entry(&mut zip, &entry_name, 0)
+            .with_context(|| format!("failed to load {entry_name}"));
+        let mut theme = match loaded {
+            Ok(theme) => theme,
+            Err(err) => {
+                eprintln!("warning: skipping {label}: {err:#}");
+                continue;
+            }
+        };
+        if theme.kind.is_none() {
+            theme.kind = field("uiTheme").map(|ui| {
+                match ui {
+                    "vs" | "hc-light" => "light",
+                    _ => "dark",
+                }
+                .to_owned()
+            });
+        }
+        theme.name.get_or_insert_with(|| label.clone());
+        themes.push(ContributedTheme { label, theme });
+    }
+    if themes.is_empty() {
+        bail!("the extension contributes no color themes it could load");
+    }
+    Ok(themes)
+}
+
+/// A theme file of the archive, layered over what it `include`s.
+fn load_entry(zip: &mut ZipArchive<Cursor<&[u8]>>, name: &str, depth: usize) -> Result<Theme> {
+    let text = read_entry(zip, name)?;
+    if name.to_ascii_lowercase().ends_with(".tmtheme") {
+        let value = Plist::from_reader(Cursor::new(text.as_bytes()))?;
+        return tmtheme::from_plist(&value);
+    }
+    let mut value: Value = jsonc::from_str(&text)?;
+    let dir = name.rsplit_once('/').map_or("", |(dir, _)| dir);
+    // `tokenColors` naming a `.tmTheme` takes that file's rules.
+    let token_file = value
+        .get("tokenColors")
+        .and_then(Value::as_str)
+        .map(|path| join(dir, path));
+    if let Some(object) = value.as_object_mut() {
+        if token_file.is_some() {
+            object.remove("tokenColors");
+        }
+    }
+    let include = value
+        .get("include")
+        .and_then(Value::as_str)
+        .map(|path| join(dir, path));
+    let mut theme: Theme = serde_json::from_value(value)?;
+    if let Some(file) = token_file {
+        theme.token_colors = load_entry(zip, &file, depth + 1)
+            .with_context(|| format!("failed to load {file}"))?
+            .token_colors;
+    }
+
+    let Some(include) = include else {
+        return Ok(theme);
+    };
+    if depth >= MAX_DEPTH {
+        bail!("`include` nests deeper than {MAX_DEPTH} levels");
+    }
+    let mut merged = load_entry(zip, &include, depth + 1)
+        .with_context(|| format!("failed to load {include}"))?;
+    merged.name = theme.name.or(merged.name);
+    merged.kind = theme.kind.or(merged.kind);
+    merged.semantic_highlighting = theme.semantic_highlighting.or(merged.semantic_highlighting);
+    merged.colors.extend(theme.colors);
+    merged
+        .semantic_token_colors
+        .extend(theme.semantic_token_colors);
+    merged.token_colors.extend(theme.token_colors);
+    Ok(merged)
+}
+
+/// `path` relative to the archive directory `dir`, with `.` and `..`
+/// resolved.
+fn join(dir: &str, path: &str) -> String {
+    let mut parts: Vec<&str> = dir.split('/').filter(|p| !p.is_empty()).collect();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
@@ -0,0 +1,80 @@
+//! Reading the JSON-with-comments dialect VS Code uses for theme files.
+//!
+//! Comments and trailing commas are blanked out with spaces rather than
+//! removed, so byte offsets (and therefore serde's line/column positions)
+//! still point into the original text.
+
+mod tree;
+
+use serde::de::DeserializeOwned;
+
+pub use tree::{line_column, parse_tree, Comment, Element, Member, Node, ParseError};
+
+/// Replaces `//` and `/* */` comments and trailing commas with whitespace.
+pub fn strip(input: &str) -> String {
+    let mut out = input.as_bytes().to_vec();
+    let bytes = input.as_bytes();
+    let mut i = 0;
+    let mut in_string = false;
+    // Position of the last comma seen outside a string, if only whitespace
+    // and comments have followed it.
+    let mut pending_comma: Option<usize> = None;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        if in_string {
+            match c {
+                b'\\' => i += 1,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+        match c {
+            b'"' => {
+                in_string = true;
+                pending_comma = None;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    out[i] = b' ';
+                    i += 1;
+                }
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let start = i;
+                i += 2;
+                while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    i += 1;
+                }
+                let end = (i + 2).min(bytes.len());
+                for (j, b) in out.iter_mut().enumerate().take(end).skip(start) {
+                    if bytes[j] != b'\n' {
+                        *b = b' ';
+                    }
+                }
+                i = end;
+                continue;
+            }
+            b',' => pending_comma = Some(i),
+            b'}' | b']' => {
+                if let Some(comma) = pending_comma.take() {
+                    out[comma] = b' ';
+                }
+            }
+            c if c.is_ascii_whitespace() => {}
+            _ => pending_comma = None,
+        }
+        i += 1;
+    }
+
+    // Comments are blanked byte-for-byte and commas are ASCII, so this stays UTF-8.
+    String::from_utf8(out).expect("comment stripping preserves UTF-8")
+}
+
+/// Parses JSONC text into any deserializable type.
+pub fn from_str<T: DeserializeOwned>(input: &str) -> serde_json::Result<T> {
+    serde_json::from_str(&strip(input))
+}
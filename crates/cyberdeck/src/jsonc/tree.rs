@@ -0,0 +1,441 @@
+//! A comment-preserving JSONC syntax tree, for tools that rewrite theme files
+//! rather than just read them.
+
+use std::ops::Range;
+
+use thiserror::Error;
+
+/// A comment or a blank line separating items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Comment {
+    /// `// text`, stored with the slashes.
+    Line(String),
+    /// `/* text */`, stored with the delimiters.
+    Block(String),
+    /// One or more empty lines.
+    Blank,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    /// A number, `true`, `false` or `null`, kept as written.
+    Scalar(String),
+    /// A decoded string.
+    String(String),
+    Array {
+        elements: Vec<Element>,
+        /// Comments after the last element.
+        dangling: Vec<Comment>,
+    },
+    Object {
+        members: Vec<Member>,
+        /// Comments after the last member.
+        dangling: Vec<Comment>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub leading: Vec<Comment>,
+    pub key: String,
+    pub value: Node,
+    /// Byte ranges of the quoted key and of the value in the parsed text.
+    pub key_span: Range<usize>,
+    pub value_span: Range<usize>,
+    /// A `//` comment on the same line as the value.
+    pub trailing: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    pub leading: Vec<Comment>,
+    pub value: Node,
+    /// Byte range of the value in the parsed text; empty for elements built
+    /// rather than parsed.
+    pub span: Range<usize>,
+    pub trailing: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message} at line {line}, column {column}")]
+pub struct ParseError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The 1-based line and column of byte `offset` in `text`, counting
+/// columns in characters.
+pub fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before[before.rfind('\n').map_or(0, |i| i + 1)..]
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+/// Parses JSONC text into a tree that remembers comments and blank lines.
+pub fn parse_tree(text: &str) -> Result<Node, ParseError> {
+    let mut parser = Parser { text, pos: 0 };
+    // Comments outside the root value have nowhere to live and are dropped.
+    parser.comments();
+    let root = parser.value()?;
+    parser.comments();
+    if parser.pos < text.len() {
+        return Err(parser.error("unexpected trailing content"));
+    }
+    Ok(root)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        let (line, column) = line_column(self.text, self.pos);
+        ParseError {
+            message: message.to_owned(),
+            line,
+            column,
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), ParseError> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", byte as char)))
+        }
+    }
+
+    /// Skips whitespace and collects comments, noting blank lines.
+    fn comments(&mut self) -> Vec<Comment> {
+        let bytes = self.text.as_bytes();
+        let mut comments = Vec::new();
+        let mut newlines = 0;
+        loop {
+            match self.peek() {
+                Some(b'\n') => {
+                    newlines += 1;
+                    self.pos += 1;
+                }
+                Some(c) if c.is_ascii_whitespace() => self.pos += 1,
+                Some(b'/') if bytes.get(self.pos + 1) == Some(&b'/') => {
+                    if newlines >= 2 {
+                        comments.push(Comment::Blank);
+                    }
+                    newlines = 0;
+                    let end = self.text[self.pos..]
+                        .find('\n')
+                        .map_or(self.text.len(), |i| self.pos + i);
+                    comments.push(Comment::Line(
+                        self.text[self.pos..end].trim_end().to_owned(),
+                    ));
+                    self.pos = end;
+                }
+                Some(b'/') if bytes.get(self.pos + 1) == Some(&b'*') => {
+                    if newlines >= 2 {
+                        comments.push(Comment::Blank);
+                    }
+                    newlines = 0;
+                    let end = self.text[self.pos + 2..]
+                        .find("*/")
+                        .map_or(self.text.len(), |i| self.pos + 2 + i + 2);
+                    comments.push(Comment::Block(self.text[self.pos..end].to_owned()));
+                    self.pos = end;
+                }
+                _ => break,
+            }
+        }
+        if newlines >= 2 && !comments.is_empty() {
+            comments.push(Comment::Blank);
+        }
+        comments
+    }
+
+    /// After a value: consumes an optional comma and a same-line `//`
+    /// comment. Returns the comment and whether a comma was seen.
+    fn after_value(&mut self) -> (Option<String>, bool) {
+        let skip_inline = |p: &mut Self| {
+            while matches!(p.peek(), Some(b' ' | b'\t' | b'\r')) {
+                p.pos += 1;
+            }
+        };
+        skip_inline(self);
+        let comma = self.peek() == Some(b',');
+        if comma {
+            self.pos += 1;
+            skip_inline(self);
+        }
+        if self.text[self.pos..].starts_with("//") {
+            let end = self.text[self.pos..]
+                .find('\n')
+                .map_or(self.text.len(), |i| self.pos + i);
+            let comment = self.text[self.pos..end].trim_end().to_owned();
+            self.pos = end;
+            return (Some(comment), comma);
+        }
+        (None, comma)
+    }
+
+    fn value(&mut self) -> Result<Node, ParseError> {
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => Ok(Node::String(self.string()?)),
+            Some(_) => {
+                let start = self.pos;
+                while let Some(c) = self.peek() {
+                    if c.is_ascii_alphanumeric() || matches!(c, b'-' | b'+' | b'.') {
+                        self.pos += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let raw = &self.text[start..self.pos];
+                if raw.is_empty() || serde_json::from_str::<serde_json::Value>(raw).is_err() {
+                    self.pos = start;
+                    return Err(self.error("expected a value"));
+                }
+                Ok(Node::Scalar(raw.to_owned()))
+            }
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        self.expect(b'"')?;
+        let bytes = self.text.as_bytes();
+        while let Some(&c) = bytes.get(self.pos) {
+            self.pos += 1;
+            match c {
+                b'\\' => self.pos += 1,
+                b'"' => {
+                    return serde_json::from_str(&self.text[start..self.pos]).map_err(|e| {
+                        self.pos = start;
+                        self.error(&e.to_string())
+                    });
+                }
+                _ => {}
+            }
+        }
+        self.pos = start;
+        Err(self.error("unterminated string"))
+    }
+
+    fn object(&mut self) -> Result<Node, ParseError> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        let mut pending = Vec::new();
+        loop {
+            pending.extend(self.comments());
+            if self.peek() == Some(b'}') {
+                self.pos += 1;
+                return Ok(Node::Object {
+                    members,
+                    dangling: pending,
+                });
+            }
+            let key_start = self.pos;
+            let key = self.string()?;
+            let key_span = key_start..self.pos;
+            self.comments();
+            self.expect(b':')?;
+            self.comments();
+            let value_start = self.pos;
+            let value = self.value()?;
+            let value_span = value_start..self.pos;
+            let (trailing, comma) = self.after_value();
+            members.push(Member {
+                leading: std::mem::take(&mut pending),
+                key,
+                value,
+                key_span,
+                value_span,
+                trailing,
+            });
+            if !comma {
+                pending.extend(self.comments());
+                match self.peek() {
+                    Some(b'}') => {}
+                    Some(b',') => self.pos += 1,
+                    _ => return Err(self.error("expected `,` or `}`")),
+                }
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Node, ParseError> {
+        self.expect(b'[')?;
+        let mut elements = Vec::new();
+        let mut pending = Vec::new();
+        loop {
+            pending.extend(self.comments());
+            if self.peek() == Some(b']') {
+                self.pos += 1;
+                return Ok(Node::Array {
+                    elements,
+                    dangling: pending,
+                });
+            }
+            let start = self.pos;
+            let value = self.value()?;
+            let span = start..self.pos;
+            let (trailing, comma) = self.after_value();
+            elements.push(Element {
+                leading: std::mem::take(&mut pending),
+                value,
+                span,
+                trailing,
+            });
+            if !comma {
+                pending.extend(self.comments());
+                match self.peek() {
+                    Some(b']') => {}
+                    Some(b',') => self.pos += 1,
+                    _ => return Err(self.error("expected `,` or `]`")),
+                }
+            }
+        }
+    }
+}
+
+impl Node {
+    /// Renders the tree with two-space indentation, one member or element
+    /// per line, keeping comments and single blank lines.
+    pub fn to_pretty_string(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, 0);
+        out.push('\n');
+        out
+    }
+
+    fn write(&self, out: &mut String, depth: usize) {
+        match self {
+            Node::Scalar(raw) => out.push_str(raw),
+            Node::String(s) => out.push_str(&quote(s)),
+            Node::Array { elements, dangling } => {
+                let items = elements
+                    .iter()
+                    .map(|e| (&e.leading, None, &e.value, &e.trailing));
+                write_container(out, depth, ('[', ']'), items, dangling);
+            }
+            Node::Object { members, dangling } => {
+                let items = members
+                    .iter()
+                    .map(|m| (&m.leading, Some(m.key.as_str()), &m.value, &m.trailing));
+                write_container(out, depth, ('{', '}'), items, dangling);
+            }
+        }
+    }
+
+    /// The member named `key`, if this is an object.
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        match self {
+            Node::Object { members, .. } => members
+                .iter()
+                .rev()
+                .find(|m| m.key == key)
+                .map(|m| &m.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Node::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+type Item<'a> = (
+    &'a Vec<Comment>,
+    Option<&'a str>,
+    &'a Node,
+    &'a Option<String>,
+);
+
+fn write_container<'a>(
+    out: &mut String,
+    depth: usize,
+    (open, close): (char, char),
+    items: impl ExactSizeIterator<Item = Item<'a>>,
+    dangling: &[Comment],
+) {
+    let count = items.len();
+    let dangling_visible = dangling.iter().any(|c| *c != Comment::Blank);
+    if count == 0 && !dangling_visible {
+        out.push(open);
+        out.push(close);
+        return;
+    }
+    let indent = "  ".repeat(depth + 1);
+    out.push(open);
+    out.push('\n');
+    let mut first_line = true;
+    for (i, (leading, key, value, trailing)) in items.enumerate() {
+        write_comments(out, &indent, leading, &mut first_line);
+        out.push_str(&indent);
+        if let Some(key) = key {
+            out.push_str(&quote(key));
+            out.push_str(": ");
+        }
+        value.write(out, depth + 1);
+        if i + 1 < count {
+            out.push(',');
+        }
+        if let Some(trailing) = trailing {
+            out.push(' ');
+            out.push_str(trailing);
+        }
+        out.push('\n');
+        first_line = false;
+    }
+    let trimmed: Vec<Comment> = {
+        let mut d = dangling.to_vec();
+        while d.last() == Some(&Comment::Blank) {
+            d.pop();
+        }
+        d
+    };
+    write_comments(out, &indent, &trimmed, &mut first_line);
+    out.push_str(&"  ".repeat(depth));
+    out.push(close);
+}
+
+fn write_comments(out: &mut String, indent: &str, comments: &[Comment], first_line: &mut bool) {
+    let mut previous_blank = false;
+    for comment in comments {
+        match comment {
+            Comment::Blank => {
+                if !*first_line && !previous_blank {
+                    out.push('\n');
+                }
+                previous_blank = true;
+                continue;
+            }
+            Comment::Line(text) | Comment::Block(text) => {
+                out.push_str(indent);
+                out.push_str(text);
+                out.push('\n');
+            }
+        }
+        previous_blank = false;
+        *first_line = false;
+    }
+}
+
+fn quote(s: &str) -> String {
+    serde_json::to_string(s).expect("strings always serialize")
+}
@@ -0,0 +1,33 @@
+//! Development tooling for the Cyberdeck 2025 VS Code theme: analysis,
+//! formatting and conversion of the theme files shipped in this repository.
+
+pub mod brackets;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod color;
+#[cfg(feature = "cli")]
+pub mod commands;
+pub mod config;
+pub mod coverage;
+pub mod demos;
+pub mod derive;
+pub mod diff;
+pub mod export;
+pub mod format;
+pub mod git;
+pub mod grammar;
+pub mod highlight;
+pub mod import;
+pub mod jsonc;
+pub mod lint;
+pub mod palette;
+pub mod parity;
+pub mod project;
+pub mod render;
+pub mod roles;
+pub mod schema;
+pub mod scope;
+pub mod semantic;
+pub mod shadow;
+pub mod suggest;
+pub mod theme;
@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::Severity;
+
+/// Default lint configuration file name, looked up in the project root.
+pub const CONFIG_FILE: &str = "cyberdeck-lint.toml";
+
+/// Per-rule settings, keyed by rule id:
+///
+/// ```toml
+/// [rules.selection-alpha]
+/// severity = "error"
+/// min = 0x20
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LintConfig {
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RuleConfig {
+    pub severity: Option<Severity>,
+    /// Rule-specific options; everything besides `severity`.
+    #[serde(flatten)]
+    pub options: toml::Table,
+}
+
+impl LintConfig {
+    pub fn load(path: &Path) -> Result<LintConfig> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Loads `cyberdeck-lint.toml` from `root`, or the defaults if absent.
+    pub fn load_or_default(root: &Path) -> Result<LintConfig> {
+        let path = root.join(CONFIG_FILE);
+        if path.is_file() {
+            LintConfig::load(&path)
+        } else {
+            Ok(LintConfig::default())
+        }
+    }
+}
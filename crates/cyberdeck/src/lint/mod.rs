@@ -0,0 +1,106 @@
+//! A small, configurable lint engine for theme files.
+//!
+//! Each [`Rule`] inspects a theme and reports [`Diagnostic`]s. Rules are
+//! switched on and off, re-levelled and tuned through a [`LintConfig`].
+
+mod config;
+mod rules;
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::palette::Palette;
+use crate::theme::Theme;
+
+pub use config::{LintConfig, RuleConfig};
+pub use rules::{all_rules, TERMINAL_ANSI_KEYS};
+
+/// How seriously a diagnostic should be taken. `Off` disables a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Off,
+    Info,
+    Warn,
+    Error,
+}
+
+/// One problem found by a rule.
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub rule: &'static str,
+    pub severity: Severity,
+    /// Where the problem is, e.g. `colors.editor.selectionBackground`.
+    pub location: String,
+    pub message: String,
+}
+
+/// Everything a rule may look at.
+pub struct LintContext<'a> {
+    pub theme: &'a Theme,
+    pub palette: &'a Palette,
+    pub options: &'a toml::Table,
+}
+
+pub trait Rule {
+    /// Stable kebab-case identifier used in config files and output.
+    fn id(&self) -> &'static str;
+
+    fn description(&self) -> &'static str;
+
+    fn default_severity(&self) -> Severity;
+
+    /// Reports problems as `(location, message)` pairs.
+    fn check(&self, cx: &LintContext<'_>, report: &mut dyn FnMut(String, String));
+}
+
+/// Runs every enabled rule against `theme`.
+pub fn lint(theme: &Theme, palette: &Palette, config: &LintConfig) -> Vec<Diagnostic> {
+    let empty = toml::Table::new();
+    let mut diagnostics = Vec::new();
+    for rule in all_rules() {
+        let rule_config = config.rules.get(rule.id());
+        let severity = rule_config
+            .and_then(|c| c.severity)
+            .unwrap_or_else(|| rule.default_severity());
+        if severity == Severity::Off {
+            continue;
+        }
+        let cx = LintContext {
+            theme,
+            palette,
+            options: rule_config.map_or(&empty, |c| &c.options),
+        };
+        rule.check(&cx, &mut |location, message| {
+            diagnostics.push(Diagnostic {
+                rule: rule.id(),
+                severity,
+                location,
+                message,
+            });
+        });
+    }
+    diagnostics
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Off => "off",
+            Severity::Info => "info",
+            Severity::Warn => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}]: {}: {}",
+            self.severity, self.rule, self.location, self.message
+        )
+    }
+}
@@ -0,0 +1,362 @@
+//! The built-in lint rules.
+
+use crate::color::Color;
+use crate::schema;
+use crate::shadow;
+use crate::theme::{ColorLocation, SemanticStyle};
+
+use super::{LintContext, Rule, Severity};
+
+/// Every built-in rule, in reporting order.
+pub fn all_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(ValidColor),
+        Box::new(PaletteOnly),
+        Box::new(NoBoldItalic),
+        Box::new(SelectionAlpha),
+        Box::new(TerminalAnsiComplete),
+        Box::new(KnownColorId),
+        Box::new(ShadowedScope),
+        Box::new(MergeableRules),
+    ]
+}
+
+/// The sixteen ANSI color keys of the integrated terminal.
+pub const TERMINAL_ANSI_KEYS: [&str; 16] = [
+    "terminal.ansiBlack",
+    "terminal.ansiRed",
+    "terminal.ansiGreen",
+    "terminal.ansiYellow",
+    "terminal.ansiBlue",
+    "terminal.ansiMagenta",
+    "terminal.ansiCyan",
+    "terminal.ansiWhite",
+    "terminal.ansiBrightBlack",
+    "terminal.ansiBrightRed",
+    "terminal.ansiBrightGreen",
+    "terminal.ansiBrightYellow",
+    "terminal.ansiBrightBlue",
+    "terminal.ansiBrightMagenta",
+    "terminal.ansiBrightCyan",
+    "terminal.ansiBrightWhite",
+];
+
+/// Selection backgrounds that are drawn over text and must stay translucent.
+const SELECTION_KEYS: [&str; 5] = [
+    "editor.selectionBackground",
+    "editor.inactiveSelectionBackground",
+    "editor.selectionHighlightBackground",
+    "terminal.selectionBackground",
+    "terminal.inactiveSelectionBackground",
+];
+
+fn int_option(cx: &LintContext<'_>, key: &str, default: i64) -> i64 {
+    cx.options
+        .get(key)
+        .and_then(toml::Value::as_integer)
+        .unwrap_or(default)
+}
+
+fn string_list_option(cx: &LintContext<'_>, key: &str) -> Option<Vec<String>> {
+    let list = cx.options.get(key)?.as_array()?;
+    Some(
+        list.iter()
+            .filter_map(|v| v.as_str().map(str::to_owned))
+            .collect(),
+    )
+}
+
+struct ValidColor;
+
+impl Rule for ValidColor {
+    fn id(&self) -> &'static str {
+        "valid-color"
+    }
+
+    fn description(&self) -> &'static str {
+        "color values must be #rgb, #rgba, #rrggbb or #rrggbbaa"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, cx: &LintContext<'_>, report: &mut dyn FnMut(String, String)) {
+        for color_ref in cx.theme.color_refs() {
+            if let Err(err) = color_ref.value.parse::<Color>() {
+                report(color_ref.location.to_string(), err.to_string());
+            }
+        }
+    }
+}
+
+/// "No raw hex outside the palette." Options: `allow` (list of extra colors).
+struct PaletteOnly;
+
+impl Rule for PaletteOnly {
+    fn id(&self) -> &'static str {
+        "palette-only"
+    }
+
+    fn description(&self) -> &'static str {
+        "every color (ignoring alpha) must be a palette entry"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, cx: &LintContext<'_>, report: &mut dyn FnMut(String, String)) {
+        let allow: Vec<Color> = string_list_option(cx, "allow")
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|s| s.parse::<Color>().ok())
+            .map(Color::opaque)
+            .collect();
+        for color_ref in cx.theme.color_refs() {
+            let Ok(color) = color_ref.value.parse::<Color>() else {
+                continue;
+            };
+            if cx.palette.find(color).is_none() && !allow.contains(&color.opaque()) {
+                report(
+                    color_ref.location.to_string(),
+                    format!("{} is not a palette color", color.to_hex_rgb()),
+                );
+            }
+        }
+    }
+}
+
+struct NoBoldItalic;
+
+impl Rule for NoBoldItalic {
+    fn id(&self) -> &'static str {
+        "no-bold-italic"
+    }
+
+    fn description(&self) -> &'static str {
+        "fontStyle must not combine bold and italic"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, cx: &LintContext<'_>, report: &mut dyn FnMut(String, String)) {
+        let is_bold_italic = |style: &str| {
+            let words: Vec<&str> = style.split_whitespace().collect();
+            words.contains(&"bold") && words.contains(&"italic")
+        };
+        for (index, rule) in cx.theme.token_colors.iter().enumerate() {
+            if rule
+                .settings
+                .font_style
+                .as_deref()
+                .is_some_and(is_bold_italic)
+            {
+                report(
+                    format!("tokenColors[{index}].settings.fontStyle"),
+                    "combines bold and italic".to_owned(),
+                );
+            }
+        }
+        for (selector, style) in &cx.theme.semantic_token_colors {
+            let SemanticStyle::Style(style) = style else {
+                continue;
+            };
+            let flags = style.bold == Some(true) && style.italic == Some(true);
+            if flags || style.font_style.as_deref().is_some_and(is_bold_italic) {
+                report(
+                    ColorLocation::Semantic(selector.clone()).to_string(),
+                    "combines bold and italic".to_owned(),
+                );
+            }
+        }
+    }
+}
+
+/// Options: `min` and `max` alpha (0-255), `keys` to check.
+struct SelectionAlpha;
+
+impl Rule for SelectionAlpha {
+    fn id(&self) -> &'static str {
+        "selection-alpha"
+    }
+
+    fn description(&self) -> &'static str {
+        "selection backgrounds must be translucent so decorations show through"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, cx: &LintContext<'_>, report: &mut dyn FnMut(String, String)) {
+        let min = int_option(cx, "min", 0x20);
+        let max = int_option(cx, "max", 0xcc);
+        let keys = string_list_option(cx, "keys")
+            .unwrap_or_else(|| SELECTION_KEYS.iter().map(|k| k.to_string()).collect());
+        for key in keys {
+            let Some(color) = cx
+                .theme
+                .colors
+                .get(&key)
+                .and_then(|v| v.parse::<Color>().ok())
+            else {
+                continue;
+            };
+            let alpha = i64::from(color.a);
+            if alpha < min || alpha > max {
+                report(
+                    ColorLocation::Workbench(key).to_string(),
+                    format!("alpha {alpha:#04x} is outside {min:#04x}..={max:#04x}"),
+                );
+            }
+        }
+    }
+}
+
+struct TerminalAnsiComplete;
+
+impl Rule for TerminalAnsiComplete {
+    fn id(&self) -> &'static str {
+        "terminal-ansi-complete"
+    }
+
+    fn description(&self) -> &'static str {
+        "all sixteen terminal.ansi* colors must be defined"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, cx: &LintContext<'_>, report: &mut dyn FnMut(String, String)) {
+        for key in TERMINAL_ANSI_KEYS {
+            if !cx.theme.colors.contains_key(key) {
+                report(
+                    ColorLocation::Workbench(key.to_owned()).to_string(),
+                    "is not defined".to_owned(),
+                );
+            }
+        }
+    }
+}
+
+/// Workbench keys VS Code ignores: not in its color registry, or deprecated.
+/// Options: `allow` (ids newer than the registry), `vscode` (the version to
+/// check deprecations against, e.g. `"1.104"`; any version by default).
+struct KnownColorId;
+
+impl Rule for KnownColorId {
+    fn id(&self) -> &'static str {
+        "known-color-id"
+    }
+
+    fn description(&self) -> &'static str {
+        "workbench color keys must be current VS Code color ids"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, cx: &LintContext<'_>, report: &mut dyn FnMut(String, String)) {
+        let allow = string_list_option(cx, "allow").unwrap_or_default();
+        let target = cx
+            .options
+            .get("vscode")
+            .and_then(toml::Value::as_str)
+            .and_then(schema::engine_version);
+        for key in cx.theme.colors.keys() {
+            if allow.contains(key) {
+                continue;
+            }
+            if let Some(message) = schema::color_id_problem(key, target) {
+                report(ColorLocation::Workbench(key.clone()).to_string(), message);
+            }
+        }
+    }
+}
+
+/// Token rule selectors written twice, or overridden by later rules.
+struct ShadowedScope;
+
+impl Rule for ShadowedScope {
+    fn id(&self) -> &'static str {
+        "shadowed-scope"
+    }
+
+    fn description(&self) -> &'static str {
+        "token rule selectors must not be duplicated or overridden by later rules"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, cx: &LintContext<'_>, report: &mut dyn FnMut(String, String)) {
+        let shadowed = shadow::shadowed(cx.theme);
+        let rules = |rules: &[usize]| {
+            rules
+                .iter()
+                .map(|index| format!("tokenColors[{index}]"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        for (index, selector) in shadow::duplicates(cx.theme) {
+            report(
+                format!("tokenColors[{index}].scope"),
+                format!("`{selector}` is listed twice"),
+            );
+        }
+        for s in shadowed {
+            let message = if s.full {
+                format!(
+                    "`{}` never takes effect, overridden by {} (`cyberdeck fmt --minimize` drops it)",
+                    s.selector,
+                    rules(&s.by)
+                )
+            } else {
+                format!(
+                    "{} for `{}` is overridden by {}",
+                    s.fields.join(" and "),
+                    s.selector,
+                    rules(&s.by)
+                )
+            };
+            report(format!("tokenColors[{}].scope", s.rule), message);
+        }
+    }
+}
+
+/// Token rules with identical settings that could be one rule.
+struct MergeableRules;
+
+impl Rule for MergeableRules {
+    fn id(&self) -> &'static str {
+        "mergeable-rules"
+    }
+
+    fn description(&self) -> &'static str {
+        "token rules with identical settings should be one rule"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn check(&self, cx: &LintContext<'_>, report: &mut dyn FnMut(String, String)) {
+        for group in shadow::mergeable(cx.theme) {
+            let (first, rest) = group.split_first().expect("groups have two or more rules");
+            let rest: Vec<String> = rest.iter().map(|i| format!("tokenColors[{i}]")).collect();
+            report(
+                format!("tokenColors[{first}]"),
+                format!(
+                    "same settings as {}; `cyberdeck fmt --minimize` merges them",
+                    rest.join(", ")
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use anyhow::Result;
+use clap::Parser;
+
+use cyberdeck::cli::Cli;
+
+fn main() -> Result<()> {
+    Cli::parse().run()
+}
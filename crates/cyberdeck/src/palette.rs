@@ -0,0 +1,48 @@
+//! The named palette the theme is built from (`palette/*.toml`).
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::color::Color;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Palette {
+    pub name: String,
+    #[serde(rename = "color", default)]
+    pub colors: Vec<PaletteColor>,
+}
+
+/// A named palette entry. `roles` say what the color is for, e.g. `keyword`
+/// or `background.secondary`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PaletteColor {
+    pub name: String,
+    pub hex: Color,
+    pub family: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+impl Palette {
+    pub fn load(path: &Path) -> Result<Palette> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// The entry whose RGB matches `color`, ignoring alpha.
+    pub fn find(&self, color: Color) -> Option<&PaletteColor> {
+        let rgb = color.opaque();
+        self.colors.iter().find(|c| c.hex.opaque() == rgb)
+    }
+
+    /// The entry carrying `role`, if any.
+    pub fn role(&self, role: &str) -> Option<&PaletteColor> {
+        self.colors
+            .iter()
+            .find(|c| c.roles.iter().any(|r| r == role))
+    }
+}
@@ -0,0 +1,355 @@
+//! Consistency between theme variants.
+//!
+//! Variants of one theme (a dark and a light one, say) should differ only in
+//! their colors. [`compare`] checks a variant against the primary one: the
+//! same workbench keys, the same token rule selectors making the same
+//! settings with the same font styles, the same semantic token selectors,
+//! and every color that plays a palette role in one playing the same role
+//! in the other. A fix made to one variant and not ported to the others
+//! shows up as a mismatch.
+
+use indexmap::IndexMap;
+
+use crate::color::Color;
+use crate::roles::resolve_roles;
+use crate::theme::{ColorLocation, SemanticStyle, Theme, TokenSettings};
+
+/// One way a variant differs from the primary variant other than in color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Where in the variant, e.g. `tokenColors[3].scope`.
+    pub location: String,
+    pub message: String,
+}
+
+/// What a token rule or semantic rule sets, apart from colors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Shape {
+    /// The settings made, by name.
+    fields: Vec<&'static str>,
+    /// Font style words, sorted, and `bold`/`italic`-style flags.
+    style: Vec<String>,
+}
+
+impl Shape {
+    /// E.g. "foreground and fontStyle `bold italic`".
+    fn describe(&self) -> String {
+        let parts: Vec<String> = self
+            .fields
+            .iter()
+            .map(|&field| match field {
+                "fontStyle" => format!("fontStyle `{}`", self.style.join(" ")),
+                field => field.to_owned(),
+            })
+            .collect();
+        if parts.is_empty() {
+            "nothing".to_owned()
+        } else {
+            parts.join(" and ")
+        }
+    }
+}
+
+fn style_words(font_style: Option<&str>) -> Vec<String> {
+    let mut words: Vec<String> = font_style
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_owned)
+        .collect();
+    words.sort_unstable();
+    words.dedup();
+    words
+}
+
+fn token_shape(settings: &TokenSettings) -> Shape {
+    let mut fields = Vec::new();
+    if settings.foreground.is_some() {
+        fields.push("foreground");
+    }
+    if settings.background.is_some() {
+        fields.push("background");
+    }
+    if settings.font_style.is_some() {
+        fields.push("fontStyle");
+    }
+    Shape {
+        fields,
+        style: style_words(settings.font_style.as_deref()),
+    }
+}
+
+fn semantic_shape(style: &SemanticStyle) -> Shape {
+    let style = match style {
+        SemanticStyle::Color(_) => {
+            return Shape {
+                fields: vec!["foreground"],
+                style: Vec::new(),
+            }
+        }
+        SemanticStyle::Style(style) => style,
+    };
+    let mut fields = Vec::new();
+    if style.foreground.is_some() {
+        fields.push("foreground");
+    }
+    if style.font_style.is_some() {
+        fields.push("fontStyle");
+    }
+    let mut words = style_words(style.font_style.as_deref());
+    for (flag, value) in [
+        ("bold", style.bold),
+        ("italic", style.italic),
+        ("underline", style.underline),
+        ("strikethrough", style.strikethrough),
+    ] {
+        match value {
+            Some(true) => words.push(flag.to_owned()),
+            Some(false) => words.push(format!("no-{flag}")),
+            None => {}
+        }
+    }
+    Shape {
+        fields,
+        style: words,
+    }
+}
+
+/// The shape of every token rule listing each selector, with the rule's
+/// index, in order. Rules without a scope count as `(default)`.
+fn token_shapes(theme: &Theme) -> IndexMap<String, Vec<(usize, Shape)>> {
+    let mut shapes: IndexMap<String, Vec<(usize, Shape)>> = IndexMap::new();
+    for (index, rule) in theme.token_colors.iter().enumerate() {
+        let selectors = rule.scope.selectors();
+        let selectors = if selectors.is_empty() {
+            vec!["(default)"]
+        } else {
+            selectors
+        };
+        for selector in selectors {
+            shapes
+                .entry(selector.to_owned())
+                .or_default()
+                .push((index, token_shape(&rule.settings)));
+        }
+    }
+    shapes
+}
+
+/// Every foreground-like color in the theme, keyed so the same place can be
+/// found in another variant: workbench keys, token selectors (the last rule
+/// setting a foreground wins) and semantic selectors.
+fn placed_colors(theme: &Theme) -> IndexMap<(&'static str, String), (ColorLocation, Color)> {
+    let mut placed = IndexMap::new();
+    for (key, value) in &theme.colors {
+        if let Ok(color) = value.parse() {
+            placed.insert(
+                ("colors", key.clone()),
+                (ColorLocation::Workbench(key.clone()), color),
+            );
+        }
+    }
+    for (index, rule) in theme.token_colors.iter().enumerate() {
+        let Some(Ok(color)) = rule.settings.foreground.as_deref().map(str::parse) else {
+            continue;
+        };
+        for selector in rule.scope.selectors() {
+            let location = ColorLocation::Token {
+                index,
+                field: "foreground",
+            };
+            placed.insert(("tokenColors", selector.to_owned()), (location, color));
+        }
+    }
+    for (selector, style) in &theme.semantic_token_colors {
+        if let Some(Ok(color)) = style.foreground().map(str::parse) {
+            placed.insert(
+                ("semanticTokenColors", selector.clone()),
+                (ColorLocation::Semantic(selector.clone()), color),
+            );
+        }
+    }
+    placed
+}
+
+/// Roles by the color that plays them.
+fn roles_by_color(theme: &Theme) -> IndexMap<Color, Vec<&'static str>> {
+    let mut by_color: IndexMap<Color, Vec<&'static str>> = IndexMap::new();
+    for (role, color) in resolve_roles(theme) {
+        by_color.entry(color).or_default().push(role);
+    }
+    by_color
+}
+
+/// The selectors as the subject of `verb`, e.g. "`a` is", "`a` and `b`
+/// are" or "`a`, `b` and 3 more are" for `("is", "are")`.
+fn selector_list(selectors: &[&str], (singular, plural): (&str, &str)) -> String {
+    let quoted: Vec<String> = selectors.iter().map(|s| format!("`{s}`")).collect();
+    match quoted.as_slice() {
+        [one] => format!("{one} {singular}"),
+        [first, second] => format!("{first} and {second} {plural}"),
+        [first, second, rest @ ..] => {
+            format!("{first}, {second} and {} more {plural}", rest.len())
+        }
+        [] => unreachable!("groups have at least one selector"),
+    }
+}
+
+fn quoted(names: &[&str]) -> String {
+    names
+        .iter()
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Every way `variant` differs from `primary` other than in which colors it
+/// uses: structural differences by section, then role swaps.
+pub fn compare(primary: &Theme, variant: &Theme) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    let mut report = |location: String, message: String| {
+        mismatches.push(Mismatch { location, message });
+    };
+
+    if primary.semantic_highlighting != variant.semantic_highlighting {
+        let value = |v: Option<bool>| v.map_or_else(|| "unset".to_owned(), |v| v.to_string());
+        report(
+            "semanticHighlighting".to_owned(),
+            format!(
+                "is {} here but {} in the primary variant",
+                value(variant.semantic_highlighting),
+                value(primary.semantic_highlighting)
+            ),
+        );
+    }
+
+    // Structural parity: the same keys and selectors on both sides.
+    for key in variant.colors.keys() {
+        if !primary.colors.contains_key(key) {
+            report(
+                ColorLocation::Workbench(key.clone()).to_string(),
+                "is not set in the primary variant".to_owned(),
+            );
+        }
+    }
+    for key in primary.colors.keys() {
+        if !variant.colors.contains_key(key) {
+            report(
+                ColorLocation::Workbench(key.clone()).to_string(),
+                "is set in the primary variant but not here".to_owned(),
+            );
+        }
+    }
+
+    // Token selectors are grouped by the rule that first lists them, so a
+    // rule that changed reads as one mismatch rather than one per selector.
+    let (ours, theirs) = (token_shapes(variant), token_shapes(primary));
+    let mut grouped: IndexMap<(String, String), Vec<&str>> = IndexMap::new();
+    let shapes = |rules: &[(usize, Shape)]| {
+        rules
+            .iter()
+            .map(|(_, shape)| shape.describe())
+            .collect::<Vec<_>>()
+            .join(", then ")
+    };
+    for (selector, rules) in &ours {
+        let location = format!("tokenColors[{}].scope", rules[0].0);
+        let message = match theirs.get(selector) {
+            None => "not styled in the primary variant".to_owned(),
+            Some(primary_rules) if shapes(rules) != shapes(primary_rules) => format!(
+                "styled with {} here but {} in the primary variant",
+                shapes(rules),
+                shapes(primary_rules)
+            ),
+            Some(_) => continue,
+        };
+        grouped
+            .entry((location, message))
+            .or_default()
+            .push(selector);
+    }
+    for (selector, rules) in &theirs {
+        if !ours.contains_key(selector) {
+            let message = format!(
+                "styled in the primary variant (tokenColors[{}]) but not here",
+                rules[0].0
+            );
+            grouped
+                .entry(("tokenColors".to_owned(), message))
+                .or_default()
+                .push(selector);
+        }
+    }
+    for ((location, message), selectors) in grouped {
+        report(
+            location,
+            format!("{} {message}", selector_list(&selectors, ("is", "are"))),
+        );
+    }
+
+    for (selector, style) in &variant.semantic_token_colors {
+        let location = ColorLocation::Semantic(selector.clone()).to_string();
+        match primary.semantic_token_colors.get(selector) {
+            None => report(location, "is not styled in the primary variant".to_owned()),
+            Some(primary_style) => {
+                let (ours, theirs) = (semantic_shape(style), semantic_shape(primary_style));
+                if ours != theirs {
+                    report(
+                        location,
+                        format!(
+                            "sets {} here but {} in the primary variant",
+                            ours.describe(),
+                            theirs.describe()
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    for selector in primary.semantic_token_colors.keys() {
+        if !variant.semantic_token_colors.contains_key(selector) {
+            report(
+                ColorLocation::Semantic(selector.clone()).to_string(),
+                "is styled in the primary variant but not here".to_owned(),
+            );
+        }
+    }
+
+    // Role semantics: a place colored with one role's color in the primary
+    // variant must not get another role's color here. Places whose color
+    // plays no role on one side are free to vary.
+    let (primary_roles, variant_roles) = (roles_by_color(primary), roles_by_color(variant));
+    let primary_colors = placed_colors(primary);
+    let mut grouped: IndexMap<(String, String), Vec<&str>> = IndexMap::new();
+    let variant_colors = placed_colors(variant);
+    for (key, (location, color)) in &variant_colors {
+        let Some((_, primary_color)) = primary_colors.get(key) else {
+            continue;
+        };
+        let (Some(expected), Some(actual)) =
+            (primary_roles.get(primary_color), variant_roles.get(color))
+        else {
+            continue;
+        };
+        if !actual.iter().any(|role| expected.contains(role)) {
+            let message = format!(
+                "the {} color here but the {} color in the primary variant",
+                quoted(actual),
+                quoted(expected)
+            );
+            let selectors = grouped.entry((location.to_string(), message)).or_default();
+            if let ("tokenColors", selector) = key {
+                selectors.push(selector);
+            }
+        }
+    }
+    for ((location, message), selectors) in grouped {
+        let subject = if selectors.is_empty() {
+            "has".to_owned()
+        } else {
+            selector_list(&selectors, ("has", "have"))
+        };
+        report(location, format!("{subject} {message}"));
+    }
+
+    mismatches
+}
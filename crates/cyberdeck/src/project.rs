@@ -0,0 +1,188 @@
+//! Locating the theme repository and the themes its extension manifest
+//! contributes.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::config::{self, Config};
+use crate::lint::LintConfig;
+use crate::theme::Theme;
+
+/// Default location of the machine-readable palette, relative to the root.
+pub const DEFAULT_PALETTE: &str = "palette/cyberdeck-2025.toml";
+
+/// Directory of per-language sample files, relative to the root.
+pub const DEMOS_DIR: &str = "language_demos";
+
+/// The repository root, its parsed `package.json` and its `cyberdeck.toml`
+/// with the active profile applied.
+#[derive(Debug, Clone)]
+pub struct Project {
+    pub root: PathBuf,
+    pub manifest: Manifest,
+    pub config: Config,
+}
+
+/// The subset of the extension manifest the tooling cares about.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub contributes: Contributes,
+    #[serde(default)]
+    pub engines: Engines,
+}
+
+/// The `engines` field: which VS Code versions the extension supports.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Engines {
+    /// A semver range, e.g. `^1.104.0`.
+    #[serde(default)]
+    pub vscode: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Contributes {
+    #[serde(default)]
+    pub themes: Vec<ThemeEntry>,
+}
+
+/// One entry of `contributes.themes`; each is a theme variant.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeEntry {
+    pub label: String,
+    pub ui_theme: String,
+    pub path: String,
+}
+
+impl ThemeEntry {
+    /// Whether `name` refers to this theme, by label or path.
+    pub fn matches(&self, name: &str) -> bool {
+        self.label == name || self.path.trim_start_matches("./") == name.trim_start_matches("./")
+    }
+}
+
+impl Project {
+    /// Finds the project by walking up from `start` to the first directory
+    /// containing a `package.json` with contributed themes.
+    pub fn discover(start: &Path) -> Result<Project> {
+        let start = start
+            .canonicalize()
+            .with_context(|| format!("failed to resolve {}", start.display()))?;
+        for dir in start.ancestors() {
+            let manifest = dir.join("package.json");
+            if manifest.is_file() {
+                let project = Project::open(dir)?;
+                if !project.manifest.contributes.themes.is_empty() {
+                    return Ok(project);
+                }
+            }
+        }
+        bail!(
+            "no package.json contributing themes found in {} or its parents",
+            start.display()
+        )
+    }
+
+    /// Discovers the project from the current working directory.
+    pub fn current() -> Result<Project> {
+        Project::discover(&std::env::current_dir()?)
+    }
+
+    pub fn open(root: &Path) -> Result<Project> {
+        let path = root.join("package.json");
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let manifest = serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        let config = Config::load(root, config::active_profile().as_deref())?;
+        Ok(Project {
+            root: root.to_path_buf(),
+            manifest,
+            config,
+        })
+    }
+
+    pub fn themes(&self) -> &[ThemeEntry] {
+        &self.manifest.contributes.themes
+    }
+
+    pub fn theme_path(&self, entry: &ThemeEntry) -> PathBuf {
+        self.root.join(entry.path.trim_start_matches("./"))
+    }
+
+    /// The variants enabled in `cyberdeck.toml`, in contribution order;
+    /// every contributed theme unless the config lists them.
+    pub fn variants(&self) -> Result<Vec<&ThemeEntry>> {
+        let Some(enabled) = &self.config.variants else {
+            return Ok(self.themes().iter().collect());
+        };
+        for name in enabled {
+            if !self.themes().iter().any(|t| t.matches(name)) {
+                bail!(
+                    "{}: variant `{name}` is not a theme contributed by package.json",
+                    config::CONFIG_FILE
+                );
+            }
+        }
+        Ok(self
+            .themes()
+            .iter()
+            .filter(|t| enabled.iter().any(|name| t.matches(name)))
+            .collect())
+    }
+
+    /// The first enabled variant, which is the primary one.
+    pub fn primary_theme(&self) -> Result<&ThemeEntry> {
+        self.variants()?
+            .into_iter()
+            .next()
+            .context("package.json does not contribute any enabled themes")
+    }
+
+    pub fn load_theme(&self, entry: &ThemeEntry) -> Result<Theme> {
+        Theme::load(&self.theme_path(entry))
+    }
+
+    /// The configured palette, else [`DEFAULT_PALETTE`].
+    pub fn palette_path(&self) -> PathBuf {
+        match &self.config.palette {
+            Some(path) => self.root.join(path),
+            None => self.root.join(DEFAULT_PALETTE),
+        }
+    }
+
+    /// The `[lint]` settings of `cyberdeck.toml`, else `cyberdeck-lint.toml`.
+    pub fn lint_config(&self) -> Result<LintConfig> {
+        match &self.config.lint {
+            Some(lint) => Ok(lint.clone()),
+            None => LintConfig::load_or_default(&self.root),
+        }
+    }
+
+    /// The language demo files, sorted by name.
+    pub fn demo_files(&self) -> Result<Vec<PathBuf>> {
+        let dir = self.root.join(DEMOS_DIR);
+        let mut files = Vec::new();
+        for entry in
+            fs::read_dir(&dir).with_context(|| format!("failed to read {}", dir.display()))?
+        {
+            let path = entry?.path();
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
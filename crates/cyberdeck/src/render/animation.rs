@@ -0,0 +1,82 @@
+//! Encoding rasterized frames as a looping animation, for showing every
+//! variant of the theme in one marketplace gallery image.
+//!
+//! APNG keeps full color and smooth alpha; GIF is quantized to 256 colors
+//! with one-bit transparency but displays everywhere.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Gif,
+    Apng,
+}
+
+impl Format {
+    /// The format a file name asks for: `.gif`, or `.png` / `.apng`.
+    pub fn from_path(path: &Path) -> Result<Format> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        Ok(match extension.as_deref() {
+            Some("gif") => Format::Gif,
+            Some("png" | "apng") => Format::Apng,
+            _ => bail!(
+                "cannot tell the animation format of {}: use .gif, .png or .apng",
+                path.display()
+            ),
+        })
+    }
+}
+
+/// Encodes `frames`, each shown for `delay_ms` and looping forever. The
+/// frames must all be the same size.
+pub fn animate(frames: &[RgbaImage], delay_ms: u16, format: Format) -> Result<Vec<u8>> {
+    let Some(first) = frames.first() else {
+        bail!("an animation needs at least one frame");
+    };
+    let (width, height) = first.dimensions();
+    if frames
+        .iter()
+        .any(|frame| frame.dimensions() != (width, height))
+    {
+        bail!("animation frames differ in size");
+    }
+    let mut encoded = Vec::new();
+    match format {
+        Format::Gif => {
+            let mut encoder = GifEncoder::new_with_speed(&mut encoded, 10);
+            encoder.set_repeat(Repeat::Infinite)?;
+            encoder
+                .encode_frames(frames.iter().map(|frame| {
+                    Frame::from_parts(
+                        frame.clone(),
+                        0,
+                        0,
+                        Delay::from_numer_denom_ms(u32::from(delay_ms), 1),
+                    )
+                }))
+                .context("failed to encode the GIF")?;
+        }
+        Format::Apng => {
+            let mut encoder = png::Encoder::new(&mut encoded, width, height);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_animated(frames.len() as u32, 0)?;
+            encoder.set_frame_delay(delay_ms, 1000)?;
+            let mut writer = encoder.write_header()?;
+            for frame in frames {
+                writer
+                    .write_image_data(frame.as_raw())
+                    .context("failed to encode the APNG")?;
+            }
+            writer.finish()?;
+        }
+    }
+    Ok(encoded)
+}
@@ -0,0 +1,78 @@
+//! Highlighted code as 24-bit ANSI escape sequences, for a quick look at
+//! highlighting decisions in a terminal or through `less -R`.
+//!
+//! Every line is padded to the widest so the editor background reads as a
+//! block, and ends with a reset so nothing bleeds into the next line.
+
+use std::fmt::Write;
+
+use crate::color::Color;
+
+use super::{Highlighted, Style};
+
+const RESET: &str = "\x1b[0m";
+
+/// The code, with line numbers in `numbers` if given.
+pub fn ansi(code: &Highlighted, numbers: Option<Color>) -> String {
+    let width = code.width();
+    let gutter = (code.first_line + code.lines.len()).to_string().len();
+    let mut out = String::new();
+    for (offset, line) in code.lines.iter().enumerate() {
+        if let Some(numbers) = numbers {
+            let _ = write!(
+                out,
+                "{}{:>gutter$}  ",
+                sgr(&Style::plain(numbers), code.background),
+                code.first_line + offset
+            );
+        }
+        let mut columns = 0;
+        let mut current = None;
+        for span in line {
+            // Adjacent tokens often share a style; switch only on changes.
+            if current != Some(span.style) {
+                out.push_str(&sgr(&span.style, code.background));
+                current = Some(span.style);
+            }
+            out.push_str(&span.text);
+            columns += span.text.chars().count();
+        }
+        if columns < width {
+            let _ = write!(
+                out,
+                "{}{:pad$}",
+                sgr(&Style::plain(code.foreground), code.background),
+                "",
+                pad = width - columns
+            );
+        }
+        let _ = writeln!(out, "{RESET}");
+    }
+    out
+}
+
+/// The escape sequence that switches to `style`, from a reset.
+fn sgr(style: &Style, background: Color) -> String {
+    let mut codes = vec!["0".to_owned()];
+    for (set, code) in [
+        (style.bold, "1"),
+        (style.italic, "3"),
+        (style.underline, "4"),
+        (style.strikethrough, "9"),
+    ] {
+        if set {
+            codes.push(code.to_owned());
+        }
+    }
+    let foreground = style.foreground;
+    let background = style.background.unwrap_or(background);
+    codes.push(format!(
+        "38;2;{};{};{}",
+        foreground.r, foreground.g, foreground.b
+    ));
+    codes.push(format!(
+        "48;2;{};{};{}",
+        background.r, background.g, background.b
+    ));
+    format!("\x1b[{}m", codes.join(";"))
+}
@@ -0,0 +1,242 @@
+//! Promotional artwork drawn from the palette: the repository's social
+//! preview and the marketplace listing banner.
+//!
+//! Both share a layout: the theme name in the primary accent over a rule
+//! of the three accents and a tagline on the left, a code window on the
+//! right, and a strip of the palette's chromatic colors along the bottom,
+//! in hue order. Text and the window are sized to the canvas, so the same
+//! layout works at either aspect ratio.
+
+use crate::color::{Color, NEUTRAL_CHROMA};
+use crate::export::theme_name;
+use crate::palette::Palette;
+use crate::theme::Theme;
+
+use super::screenshot::{baseline, Item, Scene, Screenshot, ASCENT, CORNER_RADIUS, LINE_HEIGHT};
+use super::{Highlighted, Style};
+
+/// The snippet in the code window.
+pub const SAMPLE: &str = r#"use std::net::TcpStream;
+
+/// Jacks into the net and pings the ICE.
+fn jack_in(host: &str, port: u16) -> Result<Link, Flatline> {
+    let stream = TcpStream::connect((host, port))?;
+    let mut link = Link::new(stream, Protocol::Neon);
+    link.handshake(b"cyberdeck-2025")?;
+    if link.latency_ms() > 42 {
+        return Err(Flatline::Ice("black ice detected"));
+    }
+    Ok(link)
+}"#;
+pub const SAMPLE_NAME: &str = "deck.rs";
+
+/// Largest and smallest code font sizes tried when fitting the window.
+const CODE_SIZES: std::ops::RangeInclusive<u32> = 11..=18;
+/// Margin around the content, as a fraction of the canvas height.
+const MARGIN: f64 = 0.1;
+/// Height of the swatch strip, as a fraction of the canvas height.
+const STRIP: f64 = 0.04;
+/// Contrast a palette color needs against the canvas to get a swatch;
+/// the background and border shades would read as gaps.
+const SWATCH_CONTRAST: f64 = 3.0;
+/// Height of the accent rule under the name.
+const RULE: f64 = 5.0;
+
+/// A piece of artwork and its canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Artwork {
+    /// GitHub's repository social preview, at its recommended size.
+    SocialPreview,
+    /// The header image for the marketplace listing and README.
+    Banner,
+}
+
+impl Artwork {
+    pub const ALL: [Artwork; 2] = [Artwork::SocialPreview, Artwork::Banner];
+
+    /// File-name stem.
+    pub fn name(self) -> &'static str {
+        match self {
+            Artwork::SocialPreview => "social-preview",
+            Artwork::Banner => "banner",
+        }
+    }
+
+    /// Width and height, in pixels.
+    pub fn size(self) -> (f64, f64) {
+        match self {
+            Artwork::SocialPreview => (1280.0, 640.0),
+            Artwork::Banner => (1500.0, 500.0),
+        }
+    }
+
+    /// Lays out the artwork. `advance` is the font's cell width in ems.
+    pub fn scene(
+        self,
+        theme: &Theme,
+        palette: &Palette,
+        code: &Highlighted,
+        tagline: &str,
+        advance: f64,
+    ) -> Scene {
+        let (width, height) = self.size();
+        let role = |role: &str, fallback: Color| {
+            palette
+                .role(role)
+                .map_or(fallback, |color| color.hex.opaque())
+        };
+        let canvas = role("background.secondary", code.background);
+        let accents = [
+            role("accent.primary", code.foreground),
+            role("accent.secondary", code.foreground),
+            role("accent.tertiary", code.foreground),
+        ];
+        let margin = (height * MARGIN).round();
+        let strip = (height * STRIP).round();
+        let mut scene = Scene {
+            width,
+            height,
+            items: vec![rect(0.0, 0.0, width, height, 0.0, canvas)],
+        };
+
+        // The code window, as large as fits the right half.
+        let (right, room) = (
+            width / 2.0,
+            (width / 2.0 - margin, height - strip - 2.0 * margin),
+        );
+        let window = CODE_SIZES
+            .rev()
+            .map(|size| {
+                Screenshot {
+                    font_size: size as f64,
+                    advance,
+                    padding: 0.0,
+                    line_numbers: true,
+                    title: Some(SAMPLE_NAME.to_owned()),
+                    ..Screenshot::default()
+                }
+                .scene(theme, code)
+            })
+            .find(|window| window.width <= room.0 && window.height <= room.1);
+        if let Some(window) = window {
+            let left = right + (room.0 - window.width) / 2.0;
+            let top = margin + (room.1 - window.height) / 2.0;
+            let border = role("border", code.foreground);
+            scene.items.push(rect(
+                left - 1.0,
+                top - 1.0,
+                window.width + 2.0,
+                window.height + 2.0,
+                CORNER_RADIUS + 1.0,
+                border,
+            ));
+            scene.place(window, (left, top));
+        }
+
+        // The name, rule and tagline, centered on the left.
+        let column = right - 2.0 * margin;
+        let name = theme_name(theme);
+        let name_size = (column / (name.chars().count() as f64 * advance))
+            .min(height * 0.14)
+            .floor();
+        let name_width = name.chars().count() as f64 * advance * name_size;
+        let tagline_size = (name_size * 0.3).max(16.0).round();
+        let tagline = wrap(tagline, (column / (advance * tagline_size)) as usize);
+        let gap = tagline_size;
+        let block =
+            name_size + gap / 2.0 + RULE + gap + tagline.len() as f64 * LINE_HEIGHT * tagline_size;
+        let top = ((height - strip - block) / 2.0).round();
+        scene.items.push(Item::Text {
+            x: margin,
+            y: top + ASCENT * name_size,
+            size: name_size,
+            text: name.to_owned(),
+            style: Style {
+                bold: true,
+                ..Style::plain(accents[0])
+            },
+        });
+        let rule_top = top + name_size + gap / 2.0;
+        let segment = name_width / accents.len() as f64;
+        for (index, accent) in accents.into_iter().enumerate() {
+            scene.items.push(rect(
+                margin + index as f64 * segment,
+                rule_top,
+                segment,
+                RULE,
+                0.0,
+                accent,
+            ));
+        }
+        let tagline_top = rule_top + RULE + gap;
+        let muted = role("foreground.secondary", code.foreground);
+        for (index, line) in tagline.into_iter().enumerate() {
+            scene.items.push(Item::Text {
+                x: margin,
+                y: tagline_top + index as f64 * LINE_HEIGHT * tagline_size + baseline(tagline_size),
+                size: tagline_size,
+                text: line,
+                style: Style::plain(muted),
+            });
+        }
+
+        // The swatch strip.
+        let swatches = swatches(palette, canvas);
+        let swatch = width / swatches.len().max(1) as f64;
+        for (index, color) in swatches.into_iter().enumerate() {
+            scene.items.push(rect(
+                index as f64 * swatch,
+                height - strip,
+                swatch,
+                strip,
+                0.0,
+                color,
+            ));
+        }
+        scene
+    }
+}
+
+/// The palette's chromatic colors that stand out from the canvas, once
+/// each, in hue order.
+fn swatches(palette: &Palette, canvas: Color) -> Vec<Color> {
+    let mut colors: Vec<(Color, f64)> = Vec::new();
+    for entry in &palette.colors {
+        let color = entry.hex.opaque();
+        let lch = color.to_oklch();
+        if lch.c >= NEUTRAL_CHROMA
+            && color.contrast_ratio(canvas) >= SWATCH_CONTRAST
+            && !colors.iter().any(|(seen, _)| *seen == color)
+        {
+            colors.push((color, lch.h));
+        }
+    }
+    colors.sort_by(|a, b| a.1.total_cmp(&b.1));
+    colors.into_iter().map(|(color, _)| color).collect()
+}
+
+/// Breaks `text` into lines of at most `columns` characters at spaces.
+fn wrap(text: &str, columns: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= columns => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_owned()),
+        }
+    }
+    lines
+}
+
+fn rect(x: f64, y: f64, width: f64, height: f64, radius: f64, fill: Color) -> Item {
+    Item::Rect {
+        x,
+        y,
+        width,
+        height,
+        radius,
+        fill,
+    }
+}
@@ -0,0 +1,472 @@
+//! Sample editor decorations over the code, for previewing colors that only
+//! show up while working: diagnostic squiggles with Error Lens messages,
+//! git changes in the gutter and as an inline diff, find matches and word
+//! highlights.
+//!
+//! Where each decoration goes is picked from the code itself so that any
+//! file works: squiggles, diff lines and gutter marks spread over the lines
+//! with words on them, and the highlights land on the two most repeated
+//! words.
+//! Keys the theme leaves unset fall back to VS Code's and Error Lens'
+//! defaults.
+
+use std::ops::Range;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::screenshot::{baseline, Item, LINE_HEIGHT};
+use super::{workbench, Span, Style};
+
+/// Columns between the end of a line and its Error Lens message.
+const LENS_GAP: usize = 4;
+/// Width of a gutter change mark, in pixels.
+const GUTTER_MARK: f64 = 3.0;
+/// Words shorter than this are mostly keywords, too common to highlight.
+const MIN_WORD: usize = 4;
+
+/// A diagnostic: its severity's squiggle key, the Error Lens keys for the
+/// line, and the message, with `{}` standing for the squiggled word.
+struct Problem {
+    squiggle: &'static str,
+    lens_background: &'static str,
+    lens_foreground: &'static str,
+    fallback: Color,
+    message: &'static str,
+}
+
+const PROBLEMS: [Problem; 3] = [
+    Problem {
+        squiggle: "editorError.foreground",
+        lens_background: "errorLens.errorBackground",
+        lens_foreground: "errorLens.errorForeground",
+        fallback: Color::rgb(0xf1, 0x4c, 0x4c),
+        message: "cannot find `{}` in this scope",
+    },
+    Problem {
+        squiggle: "editorWarning.foreground",
+        lens_background: "errorLens.warningBackground",
+        lens_foreground: "errorLens.warningForeground",
+        fallback: Color::rgb(0xcc, 0xa7, 0x00),
+        message: "`{}` is never used",
+    },
+    Problem {
+        squiggle: "editorInfo.foreground",
+        lens_background: "errorLens.infoBackground",
+        lens_foreground: "errorLens.infoForeground",
+        fallback: Color::rgb(0x37, 0x94, 0xff),
+        message: "`{}` could be simplified",
+    },
+];
+
+/// A word's position: the row it's on and its column range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Word {
+    row: usize,
+    start: usize,
+    end: usize,
+}
+
+/// Where the code sits, for laying decorations over it.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Grid {
+    /// The left of the first column.
+    pub left: f64,
+    pub top: f64,
+    pub cell: f64,
+    pub font_size: f64,
+    /// How far whole-line backgrounds reach either side.
+    pub line_left: f64,
+    pub line_right: f64,
+}
+
+impl Word {
+    fn in_line(self, line: &str) -> String {
+        line.chars()
+            .skip(self.start)
+            .take(self.end - self.start)
+            .collect()
+    }
+}
+
+impl Grid {
+    fn line_height(&self) -> f64 {
+        LINE_HEIGHT * self.font_size
+    }
+
+    fn row_top(&self, row: usize) -> f64 {
+        self.top + row as f64 * self.line_height()
+    }
+
+    fn x(&self, column: usize) -> f64 {
+        self.left + column as f64 * self.cell
+    }
+}
+
+/// The decorations planned for some code.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Decorations {
+    /// Squiggled words, in the order of [`PROBLEMS`], with their messages
+    /// and the column those start at.
+    problems: Vec<(Word, String, usize)>,
+    /// The word changed on the removed and the inserted line.
+    removed: Option<Word>,
+    inserted: Option<Word>,
+    /// Gutter marks: modified and added rows, and the row a deletion sits
+    /// above.
+    modified: Vec<usize>,
+    added: Vec<usize>,
+    deleted: Option<usize>,
+    /// Occurrences of the searched word, the current match first.
+    matches: Vec<Word>,
+    /// Occurrences of the word under the cursor, the written one first.
+    occurrences: Vec<Word>,
+    /// The widest row in columns, Error Lens messages included.
+    width: usize,
+}
+
+impl Decorations {
+    pub(super) fn new(lines: &[Vec<Span>]) -> Decorations {
+        let texts: Vec<String> = lines
+            .iter()
+            .map(|line| line.iter().map(|span| span.text.as_str()).collect())
+            .collect();
+        let words_on = |row: usize| words(&texts[row], row);
+        let rows: Vec<usize> = (0..texts.len())
+            .filter(|&row| !words_on(row).is_empty())
+            .collect();
+        if rows.is_empty() {
+            return Decorations::default();
+        }
+        // Eight spots spread over the rows with words.
+        let spot = |index: usize| rows[index * rows.len() / 8];
+
+        let problems: Vec<(Word, String, usize)> = [1, 3, 6]
+            .into_iter()
+            .zip(&PROBLEMS)
+            .map(|(index, problem)| {
+                // The longest word, the first of those on a tie.
+                let word = words_on(spot(index))
+                    .into_iter()
+                    .rev()
+                    .max_by_key(|word| word.end - word.start)
+                    .expect("spots have words");
+                let name = word.in_line(&texts[word.row]);
+                let lens = texts[word.row].chars().count() + LENS_GAP;
+                (word, problem.message.replace("{}", &name), lens)
+            })
+            .collect();
+
+        let removed_row = spot(2);
+        let inserted_row = (removed_row + 1..texts.len())
+            .find(|row| rows.contains(row))
+            .unwrap_or(removed_row);
+        let last_word = |row: usize| words_on(row).pop().expect("spots have words");
+
+        // The two most repeated words, first seen first on a tie.
+        let mut counts: Vec<(String, Vec<Word>)> = Vec::new();
+        for (row, text) in texts.iter().enumerate() {
+            for word in words_on(row) {
+                let name = word.in_line(text);
+                match counts.iter_mut().find(|(seen, _)| *seen == name) {
+                    Some((_, positions)) => positions.push(word),
+                    None => counts.push((name, vec![word])),
+                }
+            }
+        }
+        counts.retain(|(_, positions)| positions.len() > 1);
+        counts.sort_by_key(|(_, positions)| std::cmp::Reverse(positions.len()));
+        let mut repeated = counts.into_iter().map(|(_, positions)| positions);
+
+        let width = (0..texts.len())
+            .map(|row| {
+                let length = texts[row].chars().count();
+                problems
+                    .iter()
+                    .filter(|(word, ..)| word.row == row)
+                    .map(|(_, message, lens)| lens + message.chars().count())
+                    .max()
+                    .unwrap_or(length)
+            })
+            .max()
+            .unwrap_or(0);
+
+        Decorations {
+            problems,
+            removed: Some(last_word(removed_row)),
+            inserted: Some(last_word(inserted_row)),
+            modified: vec![spot(0)],
+            added: vec![inserted_row, spot(4)],
+            deleted: Some(spot(5)),
+            matches: repeated.next().unwrap_or_default(),
+            occurrences: repeated.next().unwrap_or_default(),
+            width,
+        }
+    }
+
+    /// The widest row in columns, Error Lens messages included.
+    pub(super) fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The ranges the minimap highlights, as rows, column ranges and the
+    /// keys they're colored with.
+    pub(super) fn minimap_ranges(&self) -> Vec<(usize, Range<usize>, &'static str)> {
+        let keys = [
+            "minimap.errorHighlight",
+            "minimap.warningHighlight",
+            "minimap.infoHighlight",
+        ];
+        let problems = self
+            .problems
+            .iter()
+            .zip(keys)
+            .map(|((word, ..), key)| (*word, key));
+        let matches = self
+            .matches
+            .iter()
+            .map(|word| (*word, "minimap.findMatchHighlight"));
+        let occurrences = self.occurrences.iter().enumerate().map(|(index, word)| {
+            let key = if index == 0 {
+                "minimap.selectionHighlight"
+            } else {
+                "minimap.selectionOccurrenceHighlight"
+            };
+            (*word, key)
+        });
+        problems
+            .chain(matches)
+            .chain(occurrences)
+            .map(|(word, key)| (word.row, word.start..word.end, key))
+            .collect()
+    }
+
+    /// The rows the minimap marks in its gutter, with the keys they're
+    /// colored with.
+    pub(super) fn minimap_gutter(&self) -> Vec<(usize, &'static str)> {
+        let modified = self
+            .modified
+            .iter()
+            .map(|&row| (row, "minimapGutter.modifiedBackground"));
+        let added = self
+            .added
+            .iter()
+            .map(|&row| (row, "minimapGutter.addedBackground"));
+        let deleted = self
+            .deleted
+            .map(|row| (row, "minimapGutter.deletedBackground"));
+        modified.chain(added).chain(deleted).collect()
+    }
+
+    /// What goes under the text: line and word backgrounds, and gutter
+    /// marks just left of the code.
+    pub(super) fn underlay(&self, items: &mut Vec<Item>, theme: &Theme, bg: Color, grid: Grid) {
+        let key = |key: &str| workbench(theme, key, bg);
+        let line_height = grid.line_height();
+        let line = |items: &mut Vec<Item>, row: usize, fill: Color| {
+            items.push(rect(
+                grid.line_left,
+                grid.row_top(row),
+                grid.line_right - grid.line_left,
+                line_height,
+                fill,
+            ));
+        };
+        let word = |items: &mut Vec<Item>, word: Word, fill: Color| {
+            items.push(rect(
+                grid.x(word.start),
+                grid.row_top(word.row),
+                (word.end - word.start) as f64 * grid.cell,
+                line_height,
+                fill,
+            ));
+        };
+
+        for ((word, ..), problem) in self.problems.iter().zip(&PROBLEMS) {
+            let color = key(problem.squiggle).unwrap_or(problem.fallback);
+            let fill =
+                key(problem.lens_background).unwrap_or_else(|| color.with_alpha(0x1b).over(bg));
+            line(items, word.row, fill);
+        }
+
+        for (changed, line_key, text_key, fallback) in [
+            (
+                self.removed,
+                "diffEditor.removedLineBackground",
+                "diffEditor.removedTextBackground",
+                Color::rgba(0xff, 0x00, 0x00, 0x33),
+            ),
+            (
+                self.inserted,
+                "diffEditor.insertedLineBackground",
+                "diffEditor.insertedTextBackground",
+                Color::rgba(0x9c, 0xcc, 0x2c, 0x33),
+            ),
+        ] {
+            let Some(changed) = changed else { continue };
+            if let Some(fill) = key(line_key) {
+                line(items, changed.row, fill);
+            }
+            word(
+                items,
+                changed,
+                key(text_key).unwrap_or_else(|| fallback.over(bg)),
+            );
+        }
+
+        let gutter = grid.left - 1.2 * grid.cell;
+        for (rows, key_name, fallback) in [
+            (
+                &self.modified,
+                "editorGutter.modifiedBackground",
+                Color::rgb(0x1b, 0x81, 0xa8),
+            ),
+            (
+                &self.added,
+                "editorGutter.addedBackground",
+                Color::rgb(0x48, 0x79, 0x85),
+            ),
+        ] {
+            let fill = key(key_name).unwrap_or(fallback);
+            for &row in rows {
+                items.push(rect(
+                    gutter,
+                    grid.row_top(row),
+                    GUTTER_MARK,
+                    line_height,
+                    fill,
+                ));
+            }
+        }
+        if let Some(row) = self.deleted {
+            let fill =
+                key("editorGutter.deletedBackground").unwrap_or(Color::rgb(0xf1, 0x4c, 0x4c));
+            items.push(rect(
+                gutter,
+                grid.row_top(row) - GUTTER_MARK / 2.0,
+                2.0 * GUTTER_MARK,
+                GUTTER_MARK,
+                fill,
+            ));
+        }
+
+        let highlights = [
+            (
+                &self.matches,
+                ["editor.findMatchBackground", "editor.findMatchBorder"],
+                [
+                    "editor.findMatchHighlightBackground",
+                    "editor.findMatchHighlightBorder",
+                ],
+                [
+                    Color::rgba(0x51, 0x5c, 0x6a, 0xff),
+                    Color::rgba(0xea, 0x5c, 0x00, 0x55),
+                ],
+            ),
+            (
+                &self.occurrences,
+                [
+                    "editor.wordHighlightStrongBackground",
+                    "editor.wordHighlightStrongBorder",
+                ],
+                [
+                    "editor.wordHighlightBackground",
+                    "editor.wordHighlightBorder",
+                ],
+                [
+                    Color::rgba(0x00, 0x49, 0x72, 0xb8),
+                    Color::rgba(0x57, 0x57, 0x57, 0xb8),
+                ],
+            ),
+        ];
+        for (words, first, rest, fallbacks) in highlights {
+            for (index, &occurrence) in words.iter().enumerate() {
+                let ([background, border], fallback) = if index == 0 {
+                    (first, fallbacks[0])
+                } else {
+                    (rest, fallbacks[1])
+                };
+                word(
+                    items,
+                    occurrence,
+                    key(background).unwrap_or_else(|| fallback.over(bg)),
+                );
+                if let Some(border) = key(border) {
+                    outline(items, occurrence, grid, border);
+                }
+            }
+        }
+    }
+
+    /// What goes over the text: squiggles, and the Error Lens messages
+    /// after their lines.
+    pub(super) fn overlay(&self, items: &mut Vec<Item>, theme: &Theme, bg: Color, grid: Grid) {
+        let key = |key: &str| workbench(theme, key, bg);
+        for ((word, message, lens), problem) in self.problems.iter().zip(&PROBLEMS) {
+            let color = key(problem.squiggle).unwrap_or(problem.fallback);
+            let top = grid.row_top(word.row);
+            items.push(Item::Squiggle {
+                x: grid.x(word.start),
+                y: top + grid.line_height() - 4.0,
+                width: (word.end - word.start) as f64 * grid.cell,
+                stroke: color,
+            });
+            items.push(Item::Text {
+                x: grid.x(*lens),
+                y: top + baseline(grid.font_size),
+                size: grid.font_size,
+                text: message.clone(),
+                style: Style::plain(key(problem.lens_foreground).unwrap_or(color)),
+            });
+        }
+    }
+}
+
+/// The identifier-like words of a line, at least [`MIN_WORD`] long.
+fn words(text: &str, row: usize) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (column, c) in text.chars().chain([' ']).enumerate() {
+        let inside = c.is_alphanumeric() || c == '_';
+        match (start, inside) {
+            (None, true) => start = Some(column),
+            (Some(begin), false) => {
+                let starts_with_digit = text.chars().nth(begin).is_some_and(|c| c.is_ascii_digit());
+                if column - begin >= MIN_WORD && !starts_with_digit {
+                    words.push(Word {
+                        row,
+                        start: begin,
+                        end: column,
+                    });
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+fn rect(x: f64, y: f64, width: f64, height: f64, fill: Color) -> Item {
+    Item::Rect {
+        x,
+        y,
+        width,
+        height,
+        radius: 0.0,
+        fill,
+    }
+}
+
+/// A one-pixel border around a word.
+fn outline(items: &mut Vec<Item>, word: Word, grid: Grid, color: Color) {
+    let (x, y) = (grid.x(word.start), grid.row_top(word.row));
+    let width = (word.end - word.start) as f64 * grid.cell;
+    let height = grid.line_height();
+    items.extend([
+        rect(x, y, width, 1.0, color),
+        rect(x, y + height - 1.0, width, 1.0, color),
+        rect(x, y, 1.0, height, color),
+        rect(x + width - 1.0, y, 1.0, height, color),
+    ]);
+}
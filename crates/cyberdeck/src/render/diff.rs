@@ -0,0 +1,135 @@
+//! Before/after renderings of the same code under two versions of a theme,
+//! as visual evidence for changes to colors.
+//!
+//! Every file whose screenshot changes gets a side-by-side image, and an
+//! index page stacks each pair under a slider that wipes between them.
+//! Files that render identically are left out.
+
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use anyhow::Result;
+
+use crate::export::{xml_escape, Artifact};
+use crate::highlight::Document;
+use crate::theme::Theme;
+
+use super::font::Font;
+use super::png::png;
+use super::screenshot::Screenshot;
+use super::svg::{svg, Text};
+use super::Highlighted;
+
+const STYLESHEET: &str = "\
+body {
+  margin: 0;
+  padding: 1rem 2rem;
+  background: #1e1e1e;
+  color: #d4d4d4;
+  font: 15px/1.5 system-ui, sans-serif;
+}
+figure { margin: 2rem 0; display: inline-block; max-width: 100%; }
+figcaption { margin-bottom: 0.5rem; }
+.stack { position: relative; }
+.stack svg { display: block; max-width: 100%; height: auto; }
+.stack svg text { white-space: pre; }
+.after { position: absolute; inset: 0; clip-path: inset(0 0 0 var(--split)); }
+input { width: 100%; }
+";
+
+/// One side of the comparison.
+#[derive(Debug, Clone, Copy)]
+pub struct Version<'a> {
+    /// What the version is called in titles, e.g. a git tag.
+    pub label: &'a str,
+    pub theme: &'a Theme,
+}
+
+/// How the renderings are made.
+#[derive(Debug, Clone)]
+pub struct VisualDiff<'a> {
+    pub screenshot: Screenshot,
+    pub lines: Option<RangeInclusive<usize>>,
+    pub text: Text<'a>,
+    /// Also rasterize the side-by-side images with this font, at this scale.
+    pub png: Option<(&'a Font, f64)>,
+    /// Color brackets by nesting depth.
+    pub bracket_pairs: bool,
+}
+
+impl VisualDiff<'_> {
+    /// The side-by-side images and the slider page, and how many files
+    /// changed.
+    pub fn render(
+        &self,
+        before: Version,
+        after: Version,
+        documents: &[Document],
+    ) -> Result<(Vec<Artifact>, usize)> {
+        let mut artifacts = Vec::new();
+        let mut figures = String::new();
+        let mut changed = 0;
+        for document in documents {
+            let name = document.title();
+            let scene = |version: Version, title: String| {
+                let mut code = if self.bracket_pairs {
+                    Highlighted::with_bracket_pairs(version.theme, document)
+                } else {
+                    Highlighted::new(version.theme, document)
+                };
+                if let Some(lines) = &self.lines {
+                    code = code.region(lines.clone());
+                }
+                Screenshot {
+                    title: Some(title),
+                    ..self.screenshot.clone()
+                }
+                .scene(version.theme, &code)
+            };
+            // The slider wipes between identical frames; the side-by-side
+            // image names the versions in the title bars.
+            let (old, new) = (scene(before, name.clone()), scene(after, name.clone()));
+            if old == new {
+                continue;
+            }
+            changed += 1;
+
+            let _ = writeln!(
+                figures,
+                "<figure style=\"--split: 50%\">\n<figcaption>{}</figcaption>\n\
+                 <div class=\"stack\">\n<div class=\"before\">{}</div>\n<div class=\"after\">{}</div>\n</div>\n\
+                 <input type=\"range\" min=\"0\" max=\"100\" value=\"50\" aria-label=\"Split\" \
+                 oninput=\"this.parentNode.style.setProperty('--split', this.value + '%')\">\n</figure>",
+                xml_escape(&name),
+                svg(&old, self.text),
+                svg(&new, self.text),
+            );
+            let pair = scene(before, format!("{name} · {}", before.label))
+                .beside(scene(after, format!("{name} · {}", after.label)));
+            if let Some((font, scale)) = self.png {
+                artifacts.push(Artifact::new(
+                    format!("{name}.png"),
+                    png(&pair, font, scale)?,
+                ));
+            }
+            artifacts.push(Artifact::new(format!("{name}.svg"), svg(&pair, self.text)));
+        }
+
+        let title = format!("{} → {}", before.label, after.label);
+        if figures.is_empty() {
+            figures.push_str("<p>No visible changes.</p>\n");
+        }
+        artifacts.push(Artifact::new(
+            "index.html",
+            format!(
+                "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+                 <title>{title}</title>\n<style>\n{STYLESHEET}</style>\n</head>\n<body>\n\
+                 <h1>{title}</h1>\n<p>Drag a slider: {} on the left, {} on the right.</p>\n{figures}</body>\n</html>\n",
+                xml_escape(before.label),
+                xml_escape(after.label),
+                title = xml_escape(&title),
+            ),
+        ));
+        Ok((artifacts, changed))
+    }
+}
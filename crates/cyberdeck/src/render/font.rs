@@ -0,0 +1,162 @@
+//! Font files for the image renderers: metrics for laying out the
+//! monospace grid, shaping for ligatures and glyph outlines for output that
+//! can't rely on the viewer having the font.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use ttf_parser::{Face, GlyphId, OutlineBuilder};
+
+/// A TrueType or OpenType font read into memory.
+#[derive(Debug, Clone)]
+pub struct Font {
+    pub data: Vec<u8>,
+    /// The family name the font reports.
+    pub family: String,
+}
+
+impl Font {
+    pub fn load(path: &Path) -> Result<Font> {
+        let data = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        Font::new(data).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn new(data: Vec<u8>) -> Result<Font> {
+        let face = Face::parse(&data, 0)?;
+        let Some(advance) = face
+            .glyph_index('0')
+            .and_then(|glyph| face.glyph_hor_advance(glyph))
+        else {
+            bail!("the font has no digits to measure");
+        };
+        if face
+            .glyph_index('i')
+            .and_then(|glyph| face.glyph_hor_advance(glyph))
+            != Some(advance)
+        {
+            bail!("the font is not monospaced");
+        }
+        let family = face
+            .names()
+            .into_iter()
+            .filter(|name| name.name_id == ttf_parser::name_id::FAMILY)
+            .find_map(|name| name.to_string())
+            .unwrap_or_else(|| "Embedded".to_owned());
+        Ok(Font { data, family })
+    }
+
+    pub fn face(&self) -> Face<'_> {
+        Face::parse(&self.data, 0).expect("parsed when the font was loaded")
+    }
+
+    /// The width of a cell at `size` pixels per em.
+    pub fn advance(&self, size: f64) -> f64 {
+        let face = self.face();
+        let advance = face
+            .glyph_index('0')
+            .and_then(|glyph| face.glyph_hor_advance(glyph))
+            .unwrap_or(0);
+        f64::from(advance) * size / f64::from(face.units_per_em())
+    }
+
+    /// Shapes `text` with the font's default features, so coding ligatures
+    /// apply, and places each glyph in the cell of the character it starts
+    /// at to keep the grid exact.
+    pub fn shape(&self, text: &str, size: f64) -> Vec<Glyph> {
+        let Some(face) = rustybuzz::Face::from_slice(&self.data, 0) else {
+            return Vec::new();
+        };
+        let scale = size / f64::from(face.units_per_em());
+        let advance = self.advance(size);
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(text);
+        let shaped = rustybuzz::shape(&face, &[], buffer);
+        shaped
+            .glyph_infos()
+            .iter()
+            .zip(shaped.glyph_positions())
+            .filter_map(|(info, position)| {
+                let column = text.get(..info.cluster as usize)?.chars().count();
+                Some(Glyph {
+                    id: GlyphId(u16::try_from(info.glyph_id).ok()?),
+                    x: column as f64 * advance + f64::from(position.x_offset) * scale,
+                    y: -f64::from(position.y_offset) * scale,
+                })
+            })
+            .collect()
+    }
+
+    /// The outline of `text` from `(x, baseline)`, as SVG path data.
+    pub fn outline(&self, text: &str, x: f64, baseline: f64, size: f64) -> String {
+        let face = self.face();
+        let mut path = PathData {
+            data: String::new(),
+            x,
+            y: baseline,
+            scale: size / f64::from(face.units_per_em()),
+        };
+        for glyph in self.shape(text, size) {
+            path.x = x + glyph.x;
+            path.y = baseline + glyph.y;
+            face.outline_glyph(glyph.id, &mut path);
+        }
+        path.data
+    }
+}
+
+/// A shaped glyph, offset in pixels from the start of its run's baseline.
+#[derive(Debug, Clone, Copy)]
+pub struct Glyph {
+    pub id: GlyphId,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Collects glyph outlines as SVG path commands, flipping the font's
+/// y-up units into the y-down canvas.
+struct PathData {
+    data: String,
+    x: f64,
+    y: f64,
+    scale: f64,
+}
+
+impl PathData {
+    fn point(&self, x: f32, y: f32) -> (f64, f64) {
+        (
+            self.x + f64::from(x) * self.scale,
+            self.y - f64::from(y) * self.scale,
+        )
+    }
+}
+
+impl OutlineBuilder for PathData {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        let _ = write!(self.data, "M{x:.2} {y:.2}");
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        let _ = write!(self.data, "L{x:.2} {y:.2}");
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x, y) = self.point(x, y);
+        let _ = write!(self.data, "Q{x1:.2} {y1:.2} {x:.2} {y:.2}");
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x2, y2) = self.point(x2, y2);
+        let (x, y) = self.point(x, y);
+        let _ = write!(self.data, "C{x1:.2} {y1:.2} {x2:.2} {y2:.2} {x:.2} {y:.2}");
+    }
+
+    fn close(&mut self) {
+        self.data.push('Z');
+    }
+}
@@ -0,0 +1,380 @@
+//! A static HTML gallery of the demo corpus, for hosting on GitHub Pages:
+//! one highlighted page per demo per variant, each with a switcher to the
+//! same demo in the other variants, an index and a palette swatch page.
+//!
+//! Pages take their chrome colors from the variant's workbench colors and
+//! style code with inline `style` attributes, so the site is plain files
+//! with no build step or script beyond the switcher's `onchange`.
+
+use std::fmt::Write;
+
+use crate::color::Color;
+use crate::export::{is_dark, theme_name, theme_slug, xml_escape, Artifact};
+use crate::highlight::Document;
+use crate::palette::Palette;
+use crate::theme::Theme;
+
+use super::{workbench, Highlighted, Span, Style};
+
+/// Layout shared by every page; colors come from each page's variables.
+const STYLESHEET: &str = "\
+*, *::before, *::after { box-sizing: border-box; }
+body {
+  margin: 0;
+  display: flex;
+  min-height: 100vh;
+  background: var(--bg);
+  color: var(--fg);
+  font: 15px/1.5 system-ui, sans-serif;
+}
+a { color: var(--link); }
+nav {
+  flex: 0 0 15rem;
+  padding: 1rem;
+  background: var(--nav-bg);
+  color: var(--nav-fg);
+  border-right: 1px solid var(--border);
+}
+nav a { color: inherit; text-decoration: none; }
+nav a:hover, nav a[aria-current] { color: var(--link); }
+nav ul { list-style: none; padding: 0; }
+nav li { padding: 0.15rem 0; }
+nav select { width: 100%; margin-top: 0.25rem; }
+.home { display: block; font-weight: bold; margin-bottom: 1rem; }
+main { flex: 1; min-width: 0; padding: 1rem 2rem; }
+h1 { font-size: 1.25rem; font-weight: normal; }
+h1 small { color: var(--muted); margin-left: 0.5rem; }
+pre {
+  margin: 0;
+  overflow-x: auto;
+  font: 14px/1.5 'Fira Code', 'JetBrains Mono', 'Cascadia Code', Consolas, monospace;
+}
+.ln { display: inline-block; min-width: 3em; padding-right: 1.5em; text-align: right; color: var(--muted); user-select: none; }
+table { border-collapse: collapse; }
+th, td { padding: 0.35rem 0.75rem; text-align: left; border-bottom: 1px solid var(--border); }
+.swatches { display: grid; grid-template-columns: repeat(auto-fill, minmax(11rem, 1fr)); gap: 1rem; }
+.swatch { border: 1px solid var(--border); border-radius: 6px; overflow: hidden; }
+.swatch .chip { height: 4.5rem; }
+.swatch p { margin: 0.5rem; font-size: 13px; }
+.swatch code { color: var(--muted); }
+";
+
+/// The site's files: `index.html`, `palette.html` when a palette is given,
+/// `style.css` and `<variant>/<demo file>.html` per demo and variant.
+pub fn gallery(
+    title: &str,
+    themes: &[Theme],
+    documents: &[Document],
+    palette: Option<&Palette>,
+) -> Vec<Artifact> {
+    let mut artifacts = vec![
+        Artifact::new("style.css", STYLESHEET),
+        Artifact::new("index.html", index(title, themes, documents, palette)),
+    ];
+    if let (Some(palette), Some(theme)) = (palette, themes.first()) {
+        artifacts.push(Artifact::new(
+            "palette.html",
+            palette_page(title, theme, palette, documents),
+        ));
+    }
+    for (index, theme) in themes.iter().enumerate() {
+        for document in documents {
+            let highlighted = Highlighted::new(theme, document);
+            let page = demo_page(
+                title,
+                themes,
+                index,
+                documents,
+                palette.is_some(),
+                document,
+                &highlighted,
+            );
+            artifacts.push(Artifact::new(
+                format!("{}/{}", theme_slug(theme), page_name(document)),
+                page,
+            ));
+        }
+    }
+    artifacts
+}
+
+fn page_name(document: &Document) -> String {
+    format!("{}.html", document.title())
+}
+
+fn demo_page(
+    title: &str,
+    themes: &[Theme],
+    current: usize,
+    documents: &[Document],
+    has_palette: bool,
+    document: &Document,
+    highlighted: &Highlighted,
+) -> String {
+    let theme = &themes[current];
+    let page = page_name(document);
+    let mut switcher = String::new();
+    for (index, other) in themes.iter().enumerate() {
+        let _ = write!(
+            switcher,
+            "<option value=\"../{}/{page}\"{}>{}</option>",
+            theme_slug(other),
+            if index == current { " selected" } else { "" },
+            xml_escape(theme_name(other)),
+        );
+    }
+    let mut links = String::new();
+    for other in documents {
+        let name = page_name(other);
+        let _ = write!(
+            links,
+            "<li><a href=\"{name}\"{}>{}</a></li>",
+            if name == page {
+                " aria-current=\"page\""
+            } else {
+                ""
+            },
+            xml_escape(&other.title()),
+        );
+    }
+    let palette_link = if has_palette {
+        "<a href=\"../palette.html\">Palette</a>"
+    } else {
+        ""
+    };
+    let nav = format!(
+        "<a class=\"home\" href=\"../index.html\">{}</a>\
+         <label>Variant <select onchange=\"location.href = this.value\">{switcher}</select></label>\
+         <ul>{links}</ul>{palette_link}",
+        xml_escape(title),
+    );
+
+    let mut code = String::new();
+    let width = (highlighted.first_line + highlighted.lines.len())
+        .to_string()
+        .len();
+    for (offset, line) in highlighted.lines.iter().enumerate() {
+        let _ = write!(
+            code,
+            "<span class=\"ln\">{:>width$}</span>",
+            highlighted.first_line + offset
+        );
+        code.push_str(&spans_html(line, highlighted.foreground));
+        code.push('\n');
+    }
+    let language = document.language.as_deref().unwrap_or("plain text");
+    let body = format!(
+        "<h1>{}<small>{}</small></h1>\
+         <pre style=\"color:{};background:{}\"><code>{code}</code></pre>",
+        xml_escape(&document.title()),
+        xml_escape(language),
+        highlighted.foreground,
+        highlighted.background,
+    );
+    html(
+        &format!("{} · {}", document.title(), theme_name(theme)),
+        "../",
+        theme,
+        &nav,
+        &body,
+    )
+}
+
+fn index(
+    title: &str,
+    themes: &[Theme],
+    documents: &[Document],
+    palette: Option<&Palette>,
+) -> String {
+    let Some(first) = themes.first() else {
+        return html(title, "", &Theme::default(), "", "");
+    };
+    let mut header = String::from("<tr><th>Demo</th>");
+    for theme in themes {
+        let _ = write!(header, "<th>{}</th>", xml_escape(theme_name(theme)));
+    }
+    header.push_str("</tr>");
+    let mut rows = String::new();
+    for document in documents {
+        let language = document.language.as_deref().unwrap_or("plain text");
+        let _ = write!(
+            rows,
+            "<tr><td>{} <small>{}</small></td>",
+            xml_escape(&document.title()),
+            xml_escape(language),
+        );
+        for theme in themes {
+            let _ = write!(
+                rows,
+                "<td><a href=\"{}/{}\">view</a></td>",
+                theme_slug(theme),
+                page_name(document),
+            );
+        }
+        rows.push_str("</tr>");
+    }
+    let palette_link = match palette {
+        Some(palette) => format!(
+            "<p><a href=\"palette.html\">{} palette</a> · {} colors</p>",
+            xml_escape(&palette.name),
+            palette.colors.len()
+        ),
+        None => String::new(),
+    };
+    let body = format!(
+        "<h1>{}</h1>{palette_link}<table>{header}{rows}</table>",
+        xml_escape(title)
+    );
+    html(title, "", first, "", &body)
+}
+
+fn palette_page(title: &str, theme: &Theme, palette: &Palette, documents: &[Document]) -> String {
+    let mut swatches = String::new();
+    for color in &palette.colors {
+        let roles = if color.roles.is_empty() {
+            String::new()
+        } else {
+            format!("<br><code>{}</code>", xml_escape(&color.roles.join(", ")))
+        };
+        let _ = write!(
+            swatches,
+            "<div class=\"swatch\"><div class=\"chip\" style=\"background:{}\"></div>\
+             <p>{}<br><code>{}</code> · {}{roles}</p></div>",
+            color.hex.opaque(),
+            xml_escape(&color.name),
+            color.hex,
+            xml_escape(&color.family),
+        );
+    }
+    let first_demo = documents.first().map_or(String::new(), |d| {
+        format!(
+            "<a href=\"{}/{}\">Demos</a>",
+            theme_slug(theme),
+            page_name(d)
+        )
+    });
+    let nav = format!(
+        "<a class=\"home\" href=\"index.html\">{}</a>{first_demo}",
+        xml_escape(title)
+    );
+    let body = format!(
+        "<h1>{}<small>{} colors</small></h1><div class=\"swatches\">{swatches}</div>",
+        xml_escape(&palette.name),
+        palette.colors.len()
+    );
+    html(&format!("{} palette", palette.name), "", theme, &nav, &body)
+}
+
+/// A page in `theme`'s chrome colors; `root` leads back to the site root.
+fn html(title: &str, root: &str, theme: &Theme, nav: &str, body: &str) -> String {
+    let background =
+        workbench(theme, "editor.background", Color::rgb(0, 0, 0)).unwrap_or(if is_dark(theme) {
+            Color::rgb(0x1e, 0x1e, 0x1e)
+        } else {
+            Color::rgb(0xff, 0xff, 0xff)
+        });
+    let color = |keys: &[&str], fallback: Color| {
+        keys.iter()
+            .find_map(|key| workbench(theme, key, background))
+            .unwrap_or(fallback)
+    };
+    let foreground = color(
+        &["editor.foreground", "foreground"],
+        if is_dark(theme) {
+            Color::rgb(0xd4, 0xd4, 0xd4)
+        } else {
+            Color::rgb(0x33, 0x33, 0x33)
+        },
+    );
+    let variables = [
+        ("--bg", background),
+        ("--fg", foreground),
+        ("--nav-bg", color(&["sideBar.background"], background)),
+        ("--nav-fg", color(&["sideBar.foreground"], foreground)),
+        (
+            "--link",
+            color(&["textLink.foreground", "focusBorder"], foreground),
+        ),
+        (
+            "--muted",
+            color(
+                &["editorLineNumber.foreground", "descriptionForeground"],
+                foreground,
+            ),
+        ),
+        (
+            "--border",
+            color(
+                &["editorGroup.border", "sideBar.border", "panel.border"],
+                foreground,
+            ),
+        ),
+    ]
+    .iter()
+    .map(|(name, color)| format!("{name}: {color};"))
+    .collect::<Vec<_>>()
+    .join(" ");
+    let nav = if nav.is_empty() {
+        String::new()
+    } else {
+        format!("<nav>{nav}</nav>")
+    };
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<link rel=\"stylesheet\" href=\"{root}style.css\">\n\
+         <style>:root {{ {variables} }}</style>\n</head>\n\
+         <body>{nav}<main>{body}</main></body>\n</html>\n",
+        xml_escape(title),
+    )
+}
+
+/// A line's spans as HTML, merging neighbors of the same style and leaving
+/// default-styled text bare.
+pub fn spans_html(line: &[Span], foreground: Color) -> String {
+    let mut html = String::new();
+    let mut runs: Vec<(Style, String)> = Vec::new();
+    for span in line {
+        match runs.last_mut() {
+            Some((style, text)) if *style == span.style => text.push_str(&span.text),
+            _ => runs.push((span.style, span.text.clone())),
+        }
+    }
+    for (style, text) in runs {
+        let css = css(&style, foreground);
+        if css.is_empty() {
+            html.push_str(&xml_escape(&text));
+        } else {
+            let _ = write!(html, "<span style=\"{css}\">{}</span>", xml_escape(&text));
+        }
+    }
+    html
+}
+
+/// Inline CSS for a style, leaving out what the page already sets.
+fn css(style: &Style, foreground: Color) -> String {
+    let mut declarations = Vec::new();
+    if style.foreground != foreground {
+        declarations.push(format!("color:{}", style.foreground));
+    }
+    if let Some(background) = style.background {
+        declarations.push(format!("background:{background}"));
+    }
+    if style.bold {
+        declarations.push("font-weight:bold".to_owned());
+    }
+    if style.italic {
+        declarations.push("font-style:italic".to_owned());
+    }
+    let decorations: Vec<&str> = [
+        (style.underline, "underline"),
+        (style.strikethrough, "line-through"),
+    ]
+    .into_iter()
+    .filter_map(|(set, line)| set.then_some(line))
+    .collect();
+    if !decorations.is_empty() {
+        declarations.push(format!("text-decoration:{}", decorations.join(" ")));
+    }
+    declarations.join(";")
+}
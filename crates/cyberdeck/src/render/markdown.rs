@@ -0,0 +1,237 @@
+//! A stand-in for VS Code's Markdown preview, for checking the `textLink`,
+//! `textCodeBlock`, `textPreformat`, `textBlockQuote` and related colors
+//! outside the editor.
+//!
+//! The preview is a webview: VS Code hands it the theme's colors as
+//! `--vscode-*` custom properties, alpha and all, and the built-in
+//! stylesheet reads them. [`stylesheet`] declares the properties the
+//! preview uses, falling back to VS Code's defaults for keys the theme
+//! leaves unset, followed by the rules of that stylesheet that give them
+//! meaning. Fenced code isn't highlighted: the preview colors it with
+//! highlight.js's own palette, not the theme's token colors.
+
+use std::fmt::Write;
+
+use pulldown_cmark::{html::push_html, Options, Parser};
+
+use crate::color::Color;
+use crate::export::{is_dark, theme_name, xml_escape};
+use crate::theme::Theme;
+
+/// Exercises every element the preview styles from the theme.
+pub const SAMPLE: &str = r#"# Cyberdeck preview
+
+A paragraph with a [link](https://code.visualstudio.com/api/references/theme-color),
+**bold**, *italic*, ~~struck~~ and `inline code` text, and a bare
+autolink: <https://github.com/ex1tium/cyberdeck-2025_vscode_theme>.
+
+## Quotes and keys
+
+> Block quotes take `textBlockQuote.background` and a border in
+> `textBlockQuote.border`, with [links](#tables) inside.
+
+Press <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>V</kbd> to open the preview.
+
+## Code
+
+```rust
+fn main() {
+    let greeting = "hello, preview";
+    println!("{greeting}");
+}
+```
+
+### Lists
+
+1. Ordered item
+2. With `code` and a [link](#code)
+   - Nested bullet
+   - [x] Finished task
+   - [ ] Open task
+
+---
+
+## Tables
+
+| Key                        | Used for            |
+| :------------------------- | :------------------ |
+| `textLink.foreground`      | Links               |
+| `textPreformat.foreground` | Inline code         |
+| `textCodeBlock.background` | Fenced code blocks  |
+| `textSeparator.foreground` | Horizontal rules    |
+"#;
+
+/// The workbench keys the preview reads, with VS Code's dark and light
+/// defaults.
+const KEYS: &[(&str, Color, Color)] = &[
+    (
+        "editor.background",
+        Color::rgb(0x1e, 0x1e, 0x1e),
+        Color::rgb(0xff, 0xff, 0xff),
+    ),
+    (
+        "editor.foreground",
+        Color::rgb(0xd4, 0xd4, 0xd4),
+        Color::rgb(0x00, 0x00, 0x00),
+    ),
+    (
+        "textLink.foreground",
+        Color::rgb(0x37, 0x94, 0xff),
+        Color::rgb(0x00, 0x6a, 0xb1),
+    ),
+    (
+        "textLink.activeForeground",
+        Color::rgb(0x37, 0x94, 0xff),
+        Color::rgb(0x00, 0x6a, 0xb1),
+    ),
+    (
+        "textPreformat.foreground",
+        Color::rgb(0xd7, 0xba, 0x7d),
+        Color::rgb(0xa3, 0x15, 0x15),
+    ),
+    (
+        "textPreformat.background",
+        Color::rgba(0xff, 0xff, 0xff, 0x1a),
+        Color::rgba(0x00, 0x00, 0x00, 0x1a),
+    ),
+    (
+        "textBlockQuote.background",
+        Color::rgb(0x22, 0x22, 0x22),
+        Color::rgb(0xf2, 0xf2, 0xf2),
+    ),
+    (
+        "textBlockQuote.border",
+        Color::rgba(0x00, 0x7a, 0xcc, 0x80),
+        Color::rgba(0x00, 0x64, 0xbf, 0x80),
+    ),
+    (
+        "textCodeBlock.background",
+        Color::rgba(0x0a, 0x0a, 0x0a, 0x66),
+        Color::rgba(0xdc, 0xdc, 0xdc, 0x66),
+    ),
+    (
+        "textSeparator.foreground",
+        Color::rgba(0xff, 0xff, 0xff, 0x2e),
+        Color::rgba(0x00, 0x00, 0x00, 0x2e),
+    ),
+    (
+        "focusBorder",
+        Color::rgb(0x00, 0x7f, 0xd4),
+        Color::rgb(0x00, 0x90, 0xf1),
+    ),
+    (
+        "keybindingLabel.background",
+        Color::rgba(0x80, 0x80, 0x80, 0x2b),
+        Color::rgba(0xdd, 0xdd, 0xdd, 0x66),
+    ),
+    (
+        "keybindingLabel.foreground",
+        Color::rgb(0xcc, 0xcc, 0xcc),
+        Color::rgb(0x55, 0x55, 0x55),
+    ),
+    (
+        "keybindingLabel.border",
+        Color::rgba(0x33, 0x33, 0x33, 0x99),
+        Color::rgba(0xcc, 0xcc, 0xcc, 0x66),
+    ),
+    (
+        "keybindingLabel.bottomBorder",
+        Color::rgba(0x44, 0x44, 0x44, 0x99),
+        Color::rgba(0xbb, 0xbb, 0xbb, 0x66),
+    ),
+];
+
+/// The preview's own rules, after VS Code's `markdown.css`. Heading rules
+/// and table borders aren't theme colors there, just white or black at a
+/// fixed opacity, declared with the properties as `--markdown-rule` and
+/// `--markdown-table-head`.
+const RULES: &str = "\
+body {
+  margin: 0 auto;
+  max-width: 882px;
+  padding: 0 26px 26px;
+  background: var(--vscode-editor-background);
+  color: var(--vscode-editor-foreground);
+  font: 14px/22px -apple-system, BlinkMacSystemFont, \"Segoe WPC\", \"Segoe UI\", system-ui, Ubuntu, \"Droid Sans\", sans-serif;
+  word-wrap: break-word;
+}
+a { color: var(--vscode-textLink-foreground); text-decoration: none; }
+a:hover { color: var(--vscode-textLink-activeForeground); text-decoration: underline; }
+a:focus { outline: 1px solid var(--vscode-focusBorder); outline-offset: -1px; }
+h1 { padding-bottom: 0.3em; line-height: 1.2; border-bottom: 1px solid var(--markdown-rule); }
+h1, h2, h3 { font-weight: normal; }
+hr { height: 1px; border: 0; border-bottom: 1px solid var(--vscode-textSeparator-foreground); }
+blockquote {
+  margin: 0 7px 0 5px;
+  padding: 2px 16px 2px 10px;
+  background: var(--vscode-textBlockQuote-background);
+  border-left: 5px solid var(--vscode-textBlockQuote-border);
+}
+code {
+  padding: 1px 3px;
+  border-radius: 4px;
+  color: var(--vscode-textPreformat-foreground);
+  background: var(--vscode-textPreformat-background);
+  font: 1em/1.357em Menlo, Monaco, Consolas, \"Droid Sans Mono\", \"Courier New\", monospace;
+}
+pre {
+  padding: 16px;
+  border-radius: 3px;
+  overflow: auto;
+  background: var(--vscode-textCodeBlock-background);
+}
+pre code { padding: 0; color: var(--vscode-editor-foreground); background: none; tab-size: 4; }
+table { border-collapse: collapse; }
+th { text-align: start; border-bottom: 1px solid var(--markdown-table-head); }
+th, td { padding: 5px 10px; }
+tbody tr + tr td { border-top: 1px solid var(--markdown-rule); }
+kbd {
+  padding: 1px 4px;
+  border: 1px solid var(--vscode-keybindingLabel-border);
+  border-bottom-color: var(--vscode-keybindingLabel-bottomBorder);
+  border-radius: 3px;
+  color: var(--vscode-keybindingLabel-foreground);
+  background: var(--vscode-keybindingLabel-background);
+  font-size: 11px;
+  vertical-align: middle;
+}
+li > input[type=checkbox] { margin: 0 0.3em 0.2em -1.6em; vertical-align: middle; }
+ul:has(> li > input[type=checkbox]) { list-style: none; }
+";
+
+/// The preview stylesheet with the theme's colors.
+pub fn stylesheet(theme: &Theme) -> String {
+    let dark = is_dark(theme);
+    let mut css = String::from(":root {\n");
+    for &(key, dark_default, light_default) in KEYS {
+        let color = theme
+            .colors
+            .get(key)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(if dark { dark_default } else { light_default });
+        let _ = writeln!(css, "  --vscode-{}: {color};", key.replace('.', "-"));
+    }
+    let ink = if dark { "255, 255, 255" } else { "0, 0, 0" };
+    let _ = writeln!(css, "  --markdown-rule: rgba({ink}, 0.18);");
+    let _ = writeln!(css, "  --markdown-table-head: rgba({ink}, 0.69);");
+    css.push_str("}\n");
+    css.push_str(RULES);
+    css
+}
+
+/// `markdown` as a standalone page styled like the preview under the
+/// theme, with GitHub's tables, task lists and strikethrough.
+pub fn html(theme: &Theme, title: &str, markdown: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH;
+    let mut body = String::new();
+    push_html(&mut body, Parser::new_ext(markdown, options));
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{} · {}</title>\n<style>\n{}</style>\n</head>\n<body class=\"vscode-body {}\">\n{body}</body>\n</html>\n",
+        xml_escape(title),
+        xml_escape(theme_name(theme)),
+        stylesheet(theme),
+        if is_dark(theme) { "vscode-dark" } else { "vscode-light" },
+    )
+}
@@ -0,0 +1,177 @@
+//! A simulation of VS Code's minimap beside the code, for tuning the
+//! `minimap*` keys: every character a block in its token's color at
+//! `minimap.foregroundOpacity`, the slider over the lines in view, and, with
+//! sample decorations, their highlights and gutter marks.
+//!
+//! Blocks use VS Code's default scale with `renderCharacters` off: a pixel
+//! wide and two tall per character. The slider is drawn at rest; hovered
+//! and dragged sliders only differ in color.
+
+use std::ops::Range;
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::decorations::Decorations;
+use super::screenshot::Item;
+use super::{workbench, Span};
+
+/// Width of the minimap, in columns (and pixels).
+pub const WIDTH: f64 = 120.0;
+/// Height of a line in the minimap.
+const LINE: f64 = 2.0;
+/// Width of a minimap gutter mark.
+const GUTTER_MARK: f64 = 2.0;
+
+/// VS Code's defaults for the keys the minimap uses.
+const DEFAULTS: &[(&str, Color)] = &[
+    (
+        "minimapSlider.background",
+        Color::rgba(0x79, 0x79, 0x79, 0x33),
+    ),
+    (
+        "minimap.errorHighlight",
+        Color::rgba(0xff, 0x12, 0x12, 0xb3),
+    ),
+    ("minimap.warningHighlight", Color::rgb(0xcc, 0xa7, 0x00)),
+    ("minimap.infoHighlight", Color::rgb(0x37, 0x94, 0xff)),
+    ("minimap.findMatchHighlight", Color::rgb(0xd1, 0x86, 0x16)),
+    ("minimap.selectionHighlight", Color::rgb(0x26, 0x4f, 0x78)),
+    (
+        "minimap.selectionOccurrenceHighlight",
+        Color::rgb(0x67, 0x67, 0x67),
+    ),
+    (
+        "minimapGutter.modifiedBackground",
+        Color::rgb(0x1b, 0x81, 0xa8),
+    ),
+    (
+        "minimapGutter.addedBackground",
+        Color::rgb(0x48, 0x79, 0x85),
+    ),
+    (
+        "minimapGutter.deletedBackground",
+        Color::rgb(0xf1, 0x4c, 0x4c),
+    ),
+];
+
+/// Where the minimap goes and what it shows.
+#[derive(Debug, Clone)]
+pub(super) struct Minimap<'a> {
+    pub lines: &'a [Vec<Span>],
+    /// The rows in view, which the slider covers.
+    pub visible: Range<usize>,
+    pub decorations: Option<&'a Decorations>,
+}
+
+impl Minimap<'_> {
+    /// Draws the minimap with its top left at `(x, y)`, showing as many
+    /// lines as fit in `height`.
+    pub(super) fn draw(
+        &self,
+        items: &mut Vec<Item>,
+        theme: &Theme,
+        editor_background: Color,
+        (x, y): (f64, f64),
+        height: f64,
+    ) {
+        let background =
+            workbench(theme, "minimap.background", editor_background).unwrap_or(editor_background);
+        // As set, alpha and all: the slider stays translucent over the
+        // blocks.
+        let raw = |key: &str| {
+            theme
+                .colors
+                .get(key)
+                .and_then(|value| value.parse::<Color>().ok())
+                .or_else(|| {
+                    DEFAULTS
+                        .iter()
+                        .find(|(name, _)| *name == key)
+                        .map(|(_, color)| *color)
+                })
+        };
+        let color = |key: &str| {
+            raw(key)
+                .unwrap_or(Color::rgb(0x79, 0x79, 0x79))
+                .over(background)
+        };
+        // Only the alpha of `minimap.foregroundOpacity` counts.
+        let opacity = raw("minimap.foregroundOpacity").map_or(0xff, |color| color.a);
+        let rows = self.lines.len().min((height / LINE) as usize);
+        let block = |items: &mut Vec<Item>, row: usize, columns: Range<usize>, fill: Color| {
+            let start = (columns.start as f64).min(WIDTH);
+            let end = (columns.end as f64).min(WIDTH);
+            if row < rows && end > start {
+                items.push(rect(
+                    x + start,
+                    y + row as f64 * LINE,
+                    end - start,
+                    LINE,
+                    fill,
+                ));
+            }
+        };
+
+        items.push(rect(x, y, WIDTH, height, background));
+        if let Some(decorations) = self.decorations {
+            for (row, columns, key) in decorations.minimap_ranges() {
+                block(items, row, columns, color(key));
+            }
+        }
+        for (row, line) in self.lines[..rows].iter().enumerate() {
+            let mut column = 0;
+            for span in line {
+                let fill = span.style.foreground.with_alpha(opacity).over(background);
+                // A block per run of non-blank characters.
+                let mut run = None;
+                for c in span.text.chars().chain([' ']) {
+                    match (run, c.is_whitespace()) {
+                        (None, false) => run = Some(column),
+                        (Some(start), true) => {
+                            block(items, row, start..column, fill);
+                            run = None;
+                        }
+                        _ => {}
+                    }
+                    column += 1;
+                }
+                // The trailing space counted above isn't in the text.
+                column -= 1;
+            }
+        }
+        if let Some(decorations) = self.decorations {
+            for (row, key) in decorations.minimap_gutter() {
+                if row < rows {
+                    items.push(rect(
+                        x,
+                        y + row as f64 * LINE,
+                        GUTTER_MARK,
+                        LINE,
+                        color(key),
+                    ));
+                }
+            }
+        }
+        let first = self.visible.start.min(rows);
+        let last = self.visible.end.min(rows);
+        items.push(rect(
+            x,
+            y + first as f64 * LINE,
+            WIDTH,
+            (last - first) as f64 * LINE,
+            raw("minimapSlider.background").unwrap_or(Color::rgba(0x79, 0x79, 0x79, 0x33)),
+        ));
+    }
+}
+
+fn rect(x: f64, y: f64, width: f64, height: f64, fill: Color) -> Item {
+    Item::Rect {
+        x,
+        y,
+        width,
+        height,
+        radius: 0.0,
+        fill,
+    }
+}
@@ -0,0 +1,219 @@
+//! Rendering highlighted code outside the editor, for reviewing highlighting
+//! decisions and producing published imagery.
+//!
+//! Every renderer works from a [`Highlighted`] document: the tokens of a
+//! [`Document`] with their resolved styles flattened to opaque colors over
+//! the editor background, since most output formats have no alpha.
+
+pub mod animation;
+pub mod ansi;
+pub mod artwork;
+pub mod decorations;
+pub mod diff;
+pub mod font;
+pub mod gallery;
+pub mod markdown;
+pub mod minimap;
+pub mod png;
+pub mod screenshot;
+pub mod svg;
+pub mod terminal;
+pub mod workbench;
+
+use std::ops::RangeInclusive;
+
+use crate::brackets::{colorizes, Colorizer};
+use crate::color::Color;
+use crate::highlight::{Document, Styler};
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+/// Columns a tab advances to, as VS Code's default `editor.tabSize`.
+pub const TAB_WIDTH: usize = 4;
+
+/// A token style with its colors composited over the editor background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub foreground: Color,
+    /// Only set when the theme gives the token a background of its own.
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+
+impl Style {
+    /// Just a foreground color.
+    pub fn plain(foreground: Color) -> Style {
+        Style {
+            foreground,
+            background: None,
+            bold: false,
+            italic: false,
+            underline: false,
+            strikethrough: false,
+        }
+    }
+}
+
+/// A run of text in one style, tabs expanded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+/// A document's lines as styled spans.
+#[derive(Debug, Clone)]
+pub struct Highlighted {
+    pub title: String,
+    pub background: Color,
+    pub foreground: Color,
+    /// The lines, with the 1-based number of the first.
+    pub first_line: usize,
+    pub lines: Vec<Vec<Span>>,
+}
+
+impl Highlighted {
+    pub fn new(theme: &Theme, document: &Document) -> Highlighted {
+        Highlighted::build(theme, document, None)
+    }
+
+    /// With brackets colored by nesting depth, as VS Code does with
+    /// `editor.bracketPairColorization.enabled`.
+    pub fn with_bracket_pairs(theme: &Theme, document: &Document) -> Highlighted {
+        Highlighted::build(theme, document, Some(Colorizer::new(theme)))
+    }
+
+    fn build(theme: &Theme, document: &Document, mut brackets: Option<Colorizer>) -> Highlighted {
+        let resolver = Resolver::new(theme);
+        let defaults = resolver.defaults();
+        let background = defaults
+            .background
+            .unwrap_or(Color::rgb(0, 0, 0))
+            .with_alpha(0xff);
+        let foreground = defaults
+            .foreground
+            .unwrap_or(Color::rgb(0xff, 0xff, 0xff))
+            .over(background);
+
+        let mut styler = Styler::new(&resolver);
+        let lines = document
+            .lines
+            .iter()
+            .map(|tokens| {
+                let mut column = 0;
+                let mut spans = Vec::new();
+                for token in tokens {
+                    let resolved = styler.style(token);
+                    let words = resolved.font_style.as_deref().unwrap_or("");
+                    let has = |word: &str| words.split_whitespace().any(|w| w == word);
+                    let style = Style {
+                        foreground: resolved
+                            .foreground
+                            .map_or(foreground, |c| c.over(background)),
+                        background: resolved
+                            .background
+                            .map(|c| c.over(background))
+                            .filter(|c| *c != background),
+                        bold: has("bold"),
+                        italic: has("italic"),
+                        underline: has("underline"),
+                        strikethrough: has("strikethrough"),
+                    };
+                    let Some(colorizer) = brackets.as_mut().filter(|_| colorizes(&token.scopes))
+                    else {
+                        spans.push(Span {
+                            text: expand_tabs(&token.text, &mut column),
+                            style,
+                        });
+                        continue;
+                    };
+                    // Split the token around its brackets.
+                    let mut rest = String::new();
+                    for c in token.text.chars() {
+                        let Some(color) = colorizer.color(c) else {
+                            rest.push(c);
+                            continue;
+                        };
+                        if !rest.is_empty() {
+                            spans.push(Span {
+                                text: expand_tabs(&rest, &mut column),
+                                style,
+                            });
+                            rest.clear();
+                        }
+                        spans.push(Span {
+                            text: expand_tabs(&c.to_string(), &mut column),
+                            style: Style {
+                                foreground: color.over(background),
+                                ..style
+                            },
+                        });
+                    }
+                    if !rest.is_empty() {
+                        spans.push(Span {
+                            text: expand_tabs(&rest, &mut column),
+                            style,
+                        });
+                    }
+                }
+                spans
+            })
+            .collect();
+        Highlighted {
+            title: document.title(),
+            background,
+            foreground,
+            first_line: 1,
+            lines,
+        }
+    }
+
+    /// Only the given 1-based, inclusive line range.
+    pub fn region(mut self, lines: RangeInclusive<usize>) -> Highlighted {
+        let start = (*lines.start()).max(1);
+        let end = (*lines.end()).min(self.lines.len());
+        self.lines = self
+            .lines
+            .drain(..)
+            .skip(start - 1)
+            .take((end + 1).saturating_sub(start))
+            .collect();
+        self.first_line = start;
+        self
+    }
+
+    /// The widest line, in columns.
+    pub fn width(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|line| line.iter().map(|s| s.text.chars().count()).sum())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// A workbench color composited over `background`, if the theme sets it.
+pub fn workbench(theme: &Theme, key: &str, background: Color) -> Option<Color> {
+    let color: Color = theme.colors.get(key)?.parse().ok()?;
+    Some(color.over(background))
+}
+
+/// Replaces tabs with spaces to the next tab stop, tracking the column
+/// across the tokens of a line.
+fn expand_tabs(text: &str, column: &mut usize) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == '\t' {
+            let spaces = TAB_WIDTH - *column % TAB_WIDTH;
+            expanded.extend(std::iter::repeat_n(' ', spaces));
+            *column += spaces;
+        } else {
+            expanded.push(c);
+            *column += 1;
+        }
+    }
+    expanded
+}
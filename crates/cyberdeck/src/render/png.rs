@@ -0,0 +1,247 @@
+//! Rasterizing a [`Scene`] to PNG with a font file, for places that don't
+//! take SVG: the marketplace listing and README images.
+//!
+//! Text is shaped (so coding ligatures apply) and filled from the glyph
+//! outlines with anti-aliasing. Bold and italic are synthesized from the
+//! regular face the same way the SVG outlines are.
+
+use anyhow::{Context, Result};
+use image::codecs::png::PngEncoder;
+use image::{ExtendedColorType, ImageEncoder, RgbaImage};
+use tiny_skia::{FillRule, Paint, Path, PathBuilder, Pixmap, Rect, Stroke, Transform};
+use ttf_parser::OutlineBuilder;
+
+use crate::color::Color;
+
+use super::font::Font;
+use super::screenshot::{Item, Scene};
+
+/// Slant of synthesized italics, as the tangent of the angle.
+const ITALIC_SKEW: f64 = 0.176;
+/// Stroke width of synthesized bold, in ems.
+const BOLD_STROKE: f64 = 0.04;
+
+/// Encodes the scene at `scale` device pixels per scene pixel.
+pub fn png(scene: &Scene, font: &Font, scale: f64) -> Result<Vec<u8>> {
+    let image = rasterize(scene, font, scale)?;
+    let mut encoded = Vec::new();
+    PngEncoder::new(&mut encoded)
+        .write_image(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            ExtendedColorType::Rgba8,
+        )
+        .context("failed to encode the PNG")?;
+    Ok(encoded)
+}
+
+/// Draws the scene at `scale` device pixels per scene pixel.
+pub fn rasterize(scene: &Scene, font: &Font, scale: f64) -> Result<RgbaImage> {
+    let width = (scene.width * scale).ceil() as u32;
+    let height = (scene.height * scale).ceil() as u32;
+    let mut pixmap = Pixmap::new(width, height)
+        .with_context(|| format!("cannot allocate a {width}×{height} image"))?;
+    let transform = Transform::from_scale(scale as f32, scale as f32);
+    let face = font.face();
+
+    for item in &scene.items {
+        match item {
+            Item::Rect {
+                x,
+                y,
+                width,
+                height,
+                radius,
+                fill,
+            } => {
+                if let Some(path) = rounded_rect(*x, *y, *width, *height, *radius) {
+                    pixmap.fill_path(&path, &paint(*fill), FillRule::Winding, transform, None);
+                }
+            }
+            Item::Circle { x, y, radius, fill } => {
+                if let Some(path) = PathBuilder::from_circle(*x as f32, *y as f32, *radius as f32) {
+                    pixmap.fill_path(&path, &paint(*fill), FillRule::Winding, transform, None);
+                }
+            }
+            Item::Text {
+                x,
+                y,
+                size,
+                text,
+                style,
+            } => {
+                let paint = paint(style.foreground);
+                let mut pen = Pen {
+                    builder: PathBuilder::new(),
+                    x: *x,
+                    y: *y,
+                    scale: size / f64::from(face.units_per_em()),
+                    skew: if style.italic { ITALIC_SKEW } else { 0.0 },
+                };
+                for glyph in font.shape(text, *size) {
+                    pen.x = x + glyph.x;
+                    pen.y = y + glyph.y;
+                    face.outline_glyph(glyph.id, &mut pen);
+                }
+                if let Some(path) = pen.builder.finish() {
+                    pixmap.fill_path(&path, &paint, FillRule::Winding, transform, None);
+                    if style.bold {
+                        let stroke = Stroke {
+                            width: (size * BOLD_STROKE) as f32,
+                            ..Stroke::default()
+                        };
+                        pixmap.stroke_path(&path, &paint, &stroke, transform, None);
+                    }
+                }
+                let width = text.chars().count() as f64 * font.advance(*size);
+                for (set, offset) in [(style.underline, 0.12), (style.strikethrough, -0.3)] {
+                    let rect = Rect::from_xywh(
+                        *x as f32,
+                        (y + offset * size) as f32,
+                        width as f32,
+                        (size * 0.06) as f32,
+                    );
+                    if let (true, Some(rect)) = (set, rect) {
+                        pixmap.fill_rect(rect, &paint, transform, None);
+                    }
+                }
+            }
+            Item::Squiggle {
+                x,
+                y,
+                width,
+                stroke,
+            } => {
+                let mut path = PathBuilder::new();
+                for (index, (x, y)) in Item::squiggle(*x, *y, *width).into_iter().enumerate() {
+                    if index == 0 {
+                        path.move_to(x as f32, y as f32);
+                    } else {
+                        path.line_to(x as f32, y as f32);
+                    }
+                }
+                if let Some(path) = path.finish() {
+                    pixmap.stroke_path(&path, &paint(*stroke), &Stroke::default(), transform, None);
+                }
+            }
+        }
+    }
+
+    let pixels: Vec<u8> = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    Ok(RgbaImage::from_raw(width, height, pixels).expect("one pixel per pixel"))
+}
+
+fn paint(color: Color) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(color.r, color.g, color.b, color.a);
+    paint.anti_alias = true;
+    paint
+}
+
+fn rounded_rect(x: f64, y: f64, width: f64, height: f64, radius: f64) -> Option<Path> {
+    let rect = Rect::from_xywh(x as f32, y as f32, width as f32, height as f32)?;
+    let radius = radius.min(width / 2.0).min(height / 2.0) as f32;
+    if radius <= 0.0 {
+        return Some(PathBuilder::from_rect(rect));
+    }
+    // Quarter circles as cubics, with the usual control point distance.
+    let k = radius * 0.552_284_8;
+    let (left, top, right, bottom) = (rect.left(), rect.top(), rect.right(), rect.bottom());
+    let mut path = PathBuilder::new();
+    path.move_to(left + radius, top);
+    path.line_to(right - radius, top);
+    path.cubic_to(
+        right - radius + k,
+        top,
+        right,
+        top + radius - k,
+        right,
+        top + radius,
+    );
+    path.line_to(right, bottom - radius);
+    path.cubic_to(
+        right,
+        bottom - radius + k,
+        right - radius + k,
+        bottom,
+        right - radius,
+        bottom,
+    );
+    path.line_to(left + radius, bottom);
+    path.cubic_to(
+        left + radius - k,
+        bottom,
+        left,
+        bottom - radius + k,
+        left,
+        bottom - radius,
+    );
+    path.line_to(left, top + radius);
+    path.cubic_to(
+        left,
+        top + radius - k,
+        left + radius - k,
+        top,
+        left + radius,
+        top,
+    );
+    path.close();
+    path.finish()
+}
+
+/// Traces glyph outlines into a path, flipping the font's y-up units into
+/// the y-down canvas and slanting by `skew` about the baseline.
+struct Pen {
+    builder: PathBuilder,
+    x: f64,
+    y: f64,
+    scale: f64,
+    skew: f64,
+}
+
+impl Pen {
+    fn point(&self, x: f32, y: f32) -> (f32, f32) {
+        let (x, y) = (f64::from(x), f64::from(y));
+        (
+            (self.x + (x + y * self.skew) * self.scale) as f32,
+            (self.y - y * self.scale) as f32,
+        )
+    }
+}
+
+impl OutlineBuilder for Pen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        self.builder.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        self.builder.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x, y) = self.point(x, y);
+        self.builder.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x2, y2) = self.point(x2, y2);
+        let (x, y) = self.point(x, y);
+        self.builder.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
@@ -0,0 +1,343 @@
+//! Laying out a code screenshot: a rounded editor window with a title bar,
+//! optional line numbers and the highlighted code on a monospace grid.
+//!
+//! The layout is a [`Scene`] of plain shapes and text runs in pixels, which
+//! the vector and raster backends draw the same way.
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+use super::decorations::{Decorations, Grid};
+use super::minimap::{self, Minimap};
+use super::{workbench, Highlighted, Span, Style};
+
+/// Line height as a multiple of the font size.
+pub(super) const LINE_HEIGHT: f64 = 1.5;
+/// Where the baseline sits below the top of the font size box, in ems.
+pub(super) const ASCENT: f64 = 0.8;
+/// Monospace cell width in ems when no font is measured.
+pub const DEFAULT_ADVANCE: f64 = 0.6;
+
+/// Half a squiggle's wavelength and its height, as VS Code draws them.
+const SQUIGGLE_STEP: f64 = 3.0;
+const SQUIGGLE_DEPTH: f64 = 3.0;
+
+const TITLE_BAR: f64 = 36.0;
+pub(super) const CORNER_RADIUS: f64 = 10.0;
+/// Space between the window edge and the code.
+const INSET: f64 = 20.0;
+/// The title bar's close, minimize and zoom buttons.
+pub(super) const BUTTONS: [Color; 3] = [
+    Color::rgb(0xff, 0x5f, 0x57),
+    Color::rgb(0xfe, 0xbc, 0x2e),
+    Color::rgb(0x28, 0xc8, 0x40),
+];
+
+/// Shapes and text in drawing order, on a canvas of `width` × `height`
+/// pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scene {
+    pub width: f64,
+    pub height: f64,
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        radius: f64,
+        fill: Color,
+    },
+    Circle {
+        x: f64,
+        y: f64,
+        radius: f64,
+        fill: Color,
+    },
+    /// A run of monospace text whose baseline starts at `(x, y)`.
+    Text {
+        x: f64,
+        y: f64,
+        size: f64,
+        text: String,
+        style: Style,
+    },
+    /// A wavy underline whose crests touch `y`, as under a diagnostic.
+    Squiggle {
+        x: f64,
+        y: f64,
+        width: f64,
+        stroke: Color,
+    },
+}
+
+impl Item {
+    /// The vertices of a [`Item::Squiggle`]'s zigzag.
+    pub fn squiggle(x: f64, y: f64, width: f64) -> Vec<(f64, f64)> {
+        let steps = (width / SQUIGGLE_STEP).ceil().max(1.0) as usize;
+        (0..=steps)
+            .map(|step| {
+                let dx = (step as f64 * SQUIGGLE_STEP).min(width);
+                (x + dx, y + if step % 2 == 0 { 0.0 } else { SQUIGGLE_DEPTH })
+            })
+            .collect()
+    }
+}
+
+impl Scene {
+    /// This scene with `other` to its right, top-aligned.
+    pub fn beside(mut self, other: Scene) -> Scene {
+        let (width, height) = (other.width, other.height);
+        self.place(other, (self.width, 0.0));
+        self.width += width;
+        self.height = self.height.max(height);
+        self
+    }
+
+    /// Draws `other` over this scene with its top left at `(left, top)`,
+    /// leaving the canvas size alone.
+    pub fn place(&mut self, other: Scene, (left, top): (f64, f64)) {
+        self.items.extend(other.items.into_iter().map(|mut item| {
+            match &mut item {
+                Item::Rect { x, y, .. }
+                | Item::Circle { x, y, .. }
+                | Item::Text { x, y, .. }
+                | Item::Squiggle { x, y, .. } => {
+                    *x += left;
+                    *y += top;
+                }
+            }
+            item
+        }));
+    }
+}
+
+/// What frames the code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Chrome {
+    /// A bare editor window with a title bar.
+    #[default]
+    Window,
+    /// A mock-up of the whole VS Code workbench around the editor.
+    Workbench,
+}
+
+/// Screenshot settings.
+#[derive(Debug, Clone)]
+pub struct Screenshot {
+    pub font_size: f64,
+    /// The font's cell width in ems.
+    pub advance: f64,
+    /// Transparent space around the window.
+    pub padding: f64,
+    pub line_numbers: bool,
+    /// Title bar text; the file name, usually.
+    pub title: Option<String>,
+    pub chrome: Chrome,
+    /// Lay sample diagnostics, git changes and highlights over the code.
+    pub decorations: bool,
+    /// Show a simulated minimap at the right of the editor.
+    pub minimap: bool,
+}
+
+impl Default for Screenshot {
+    fn default() -> Screenshot {
+        Screenshot {
+            font_size: 14.0,
+            advance: DEFAULT_ADVANCE,
+            padding: 32.0,
+            line_numbers: false,
+            title: None,
+            chrome: Chrome::Window,
+            decorations: false,
+            minimap: false,
+        }
+    }
+}
+
+impl Screenshot {
+    pub fn scene(&self, theme: &Theme, code: &Highlighted) -> Scene {
+        if self.chrome == Chrome::Workbench {
+            return super::workbench::scene(self, theme, code);
+        }
+        let background = code.background;
+        let color = |keys: &[&str], fallback: Color| {
+            keys.iter()
+                .find_map(|key| workbench(theme, key, background))
+                .unwrap_or(fallback)
+        };
+        let cell = self.advance * self.font_size;
+        let line_height = LINE_HEIGHT * self.font_size;
+        let gutter = if self.line_numbers {
+            (code.first_line + code.lines.len()).to_string().len() + 2
+        } else {
+            0
+        };
+        let decorations = self.decorations.then(|| Decorations::new(&code.lines));
+        let width = code
+            .width()
+            .max(decorations.as_ref().map_or(0, Decorations::width));
+        let columns = (gutter + width).max(20);
+
+        let minimap_width = if self.minimap { minimap::WIDTH } else { 0.0 };
+        let window_width = 2.0 * INSET + columns as f64 * cell + minimap_width;
+        let window_height = TITLE_BAR + 2.0 * INSET + code.lines.len() as f64 * line_height;
+        let (left, top) = (self.padding, self.padding);
+        let mut items = vec![
+            Item::Rect {
+                x: left,
+                y: top,
+                width: window_width,
+                height: window_height,
+                radius: CORNER_RADIUS,
+                fill: background,
+            },
+            // Square off the title bar's bottom corners under the body.
+            Item::Rect {
+                x: left,
+                y: top,
+                width: window_width,
+                height: TITLE_BAR,
+                radius: CORNER_RADIUS,
+                fill: color(&["titleBar.activeBackground"], background),
+            },
+            Item::Rect {
+                x: left,
+                y: top + TITLE_BAR - CORNER_RADIUS,
+                width: window_width,
+                height: CORNER_RADIUS,
+                radius: 0.0,
+                fill: color(&["titleBar.activeBackground"], background),
+            },
+        ];
+        for (index, fill) in BUTTONS.into_iter().enumerate() {
+            items.push(Item::Circle {
+                x: left + 20.0 + index as f64 * 20.0,
+                y: top + TITLE_BAR / 2.0,
+                radius: 6.0,
+                fill,
+            });
+        }
+        if let Some(title) = &self.title {
+            let size = self.font_size * 0.9;
+            let width = title.chars().count() as f64 * self.advance * size;
+            items.push(Item::Text {
+                x: left + (window_width - width) / 2.0,
+                y: top + TITLE_BAR / 2.0 + size * (ASCENT - 0.5),
+                size,
+                text: title.clone(),
+                style: Style::plain(color(
+                    &["titleBar.activeForeground", "foreground"],
+                    code.foreground,
+                )),
+            });
+        }
+
+        let numbers = Style::plain(color(&["editorLineNumber.foreground"], code.foreground));
+        let code_left = left + INSET;
+        let code_top = top + TITLE_BAR + INSET;
+        let grid = Grid {
+            left: code_left + gutter as f64 * cell,
+            top: code_top,
+            cell,
+            font_size: self.font_size,
+            line_left: left,
+            line_right: left + window_width,
+        };
+        if let Some(decorations) = &decorations {
+            decorations.underlay(&mut items, theme, background, grid);
+        }
+        if self.line_numbers {
+            for offset in 0..code.lines.len() {
+                items.push(Item::Text {
+                    x: code_left,
+                    y: code_top + offset as f64 * line_height + baseline(self.font_size),
+                    size: self.font_size,
+                    text: format!("{:>width$}", code.first_line + offset, width = gutter - 2),
+                    style: numbers,
+                });
+            }
+        }
+        draw_lines(
+            &mut items,
+            &code.lines,
+            grid.left,
+            code_top,
+            cell,
+            self.font_size,
+        );
+        if let Some(decorations) = &decorations {
+            decorations.overlay(&mut items, theme, background, grid);
+        }
+        if self.minimap {
+            let minimap = Minimap {
+                lines: &code.lines,
+                visible: 0..code.lines.len(),
+                decorations: decorations.as_ref(),
+            };
+            minimap.draw(
+                &mut items,
+                theme,
+                background,
+                (left + window_width - minimap_width, top + TITLE_BAR),
+                window_height - TITLE_BAR - CORNER_RADIUS,
+            );
+        }
+
+        Scene {
+            width: window_width + 2.0 * self.padding,
+            height: window_height + 2.0 * self.padding,
+            items,
+        }
+    }
+}
+
+/// Where the baseline sits below the top of a line, in pixels.
+pub(super) fn baseline(font_size: f64) -> f64 {
+    (LINE_HEIGHT - 1.0) * font_size / 2.0 + ASCENT * font_size
+}
+
+/// Draws `lines` on the monospace grid from `(left, top)`: span backgrounds
+/// as rectangles under the text.
+pub(super) fn draw_lines(
+    items: &mut Vec<Item>,
+    lines: &[Vec<Span>],
+    left: f64,
+    top: f64,
+    cell: f64,
+    font_size: f64,
+) {
+    let line_height = LINE_HEIGHT * font_size;
+    for (offset, line) in lines.iter().enumerate() {
+        let y = top + offset as f64 * line_height;
+        let mut column = 0;
+        for span in line {
+            let length = span.text.chars().count();
+            let x = left + column as f64 * cell;
+            if let Some(fill) = span.style.background {
+                items.push(Item::Rect {
+                    x,
+                    y,
+                    width: length as f64 * cell,
+                    height: line_height,
+                    radius: 0.0,
+                    fill,
+                });
+            }
+            if !span.text.trim().is_empty() {
+                items.push(Item::Text {
+                    x,
+                    y: y + baseline(font_size),
+                    size: font_size,
+                    text: span.text.clone(),
+                    style: span.style,
+                });
+            }
+            column += length;
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Drawing a [`Scene`] as SVG.
+//!
+//! Text is either live `<text>` in a font the viewer must have, the same
+//! with the font file embedded as a data URL, or glyph outlines traced from
+//! a font file, which render identically everywhere. Coordinates are
+//! rounded to two decimals so regenerated images diff cleanly.
+
+use std::fmt::Write;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::export::xml_escape;
+
+use super::font::Font;
+use super::screenshot::{Item, Scene};
+
+/// The fonts `<text>` asks for when no font file is given.
+pub const DEFAULT_FAMILIES: &str =
+    "'Fira Code', 'JetBrains Mono', 'Cascadia Code', Consolas, monospace";
+
+/// How text is drawn.
+#[derive(Debug, Clone, Copy)]
+pub enum Text<'a> {
+    /// `<text>` in [`DEFAULT_FAMILIES`].
+    Live,
+    /// `<text>` in the font, embedded in the document.
+    Embedded(&'a Font),
+    /// Glyph outlines from the font.
+    Outlined(&'a Font),
+}
+
+pub fn svg(scene: &Scene, text: Text) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = number(scene.width),
+        h = number(scene.height),
+    );
+    let families = match text {
+        Text::Live => DEFAULT_FAMILIES.to_owned(),
+        Text::Embedded(font) => {
+            let _ = writeln!(
+                svg,
+                "<style>@font-face {{ font-family: '{}'; src: url(data:font/ttf;base64,{}); }}</style>",
+                xml_escape(&font.family),
+                STANDARD.encode(&font.data),
+            );
+            format!("'{}', monospace", xml_escape(&font.family))
+        }
+        Text::Outlined(_) => String::new(),
+    };
+    let _ = writeln!(svg, "<g font-family=\"{families}\" xml:space=\"preserve\">");
+    for item in &scene.items {
+        match item {
+            Item::Rect {
+                x,
+                y,
+                width,
+                height,
+                radius,
+                fill,
+            } => {
+                let corners = if *radius > 0.0 {
+                    format!(" rx=\"{}\"", number(*radius))
+                } else {
+                    String::new()
+                };
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"{corners} fill=\"{fill}\"/>",
+                    number(*x),
+                    number(*y),
+                    number(*width),
+                    number(*height),
+                );
+            }
+            Item::Circle { x, y, radius, fill } => {
+                let _ = writeln!(
+                    svg,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{fill}\"/>",
+                    number(*x),
+                    number(*y),
+                    number(*radius),
+                );
+            }
+            Item::Text {
+                x,
+                y,
+                size,
+                text: run,
+                style,
+            } => {
+                if let Text::Outlined(font) = text {
+                    // Outlines have no weight, slant or decoration: embolden
+                    // with a stroke, slant with a skew about the baseline and
+                    // draw the lines as rectangles.
+                    let width = run.chars().count() as f64 * font.advance(*size);
+                    for (set, offset) in [(style.underline, 0.12), (style.strikethrough, -0.3)] {
+                        if set {
+                            let _ = writeln!(
+                                svg,
+                                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                                number(*x),
+                                number(y + offset * size),
+                                number(width),
+                                number(size * 0.06),
+                                style.foreground,
+                            );
+                        }
+                    }
+                    let mut path = format!(
+                        "<path d=\"{}\" fill=\"{}\"",
+                        font.outline(run, *x, *y, *size),
+                        style.foreground
+                    );
+                    if style.bold {
+                        let _ = write!(
+                            path,
+                            " stroke=\"{}\" stroke-width=\"{}\"",
+                            style.foreground,
+                            number(size * 0.04)
+                        );
+                    }
+                    if style.italic {
+                        let _ = write!(
+                            path,
+                            " transform=\"translate(0 {y}) skewX(-10) translate(0 -{y})\"",
+                            y = number(*y)
+                        );
+                    }
+                    let _ = writeln!(svg, "{path}/>");
+                } else {
+                    let mut attributes = String::new();
+                    if style.bold {
+                        attributes.push_str(" font-weight=\"bold\"");
+                    }
+                    if style.italic {
+                        attributes.push_str(" font-style=\"italic\"");
+                    }
+                    let decorations: Vec<&str> = [
+                        (style.underline, "underline"),
+                        (style.strikethrough, "line-through"),
+                    ]
+                    .into_iter()
+                    .filter_map(|(set, line)| set.then_some(line))
+                    .collect();
+                    if !decorations.is_empty() {
+                        let _ =
+                            write!(attributes, " text-decoration=\"{}\"", decorations.join(" "));
+                    }
+                    let _ = writeln!(
+                        svg,
+                        "<text x=\"{}\" y=\"{}\" font-size=\"{}\" fill=\"{}\"{attributes}>{}</text>",
+                        number(*x),
+                        number(*y),
+                        number(*size),
+                        style.foreground,
+                        xml_escape(run),
+                    );
+                }
+            }
+            Item::Squiggle {
+                x,
+                y,
+                width,
+                stroke,
+            } => {
+                let points: Vec<String> = Item::squiggle(*x, *y, *width)
+                    .into_iter()
+                    .map(|(x, y)| format!("{},{}", number(x), number(y)))
+                    .collect();
+                let _ = writeln!(
+                    svg,
+                    "<polyline points=\"{}\" fill=\"none\" stroke=\"{stroke}\"/>",
+                    points.join(" "),
+                );
+            }
+        }
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+/// A coordinate with at most two decimals and no trailing zeros.
+fn number(value: f64) -> String {
+    let text = format!("{value:.2}");
+    text.trim_end_matches('0').trim_end_matches('.').to_owned()
+}
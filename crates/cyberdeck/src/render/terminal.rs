@@ -0,0 +1,224 @@
+//! A sample integrated-terminal session in the theme's ANSI palette, for
+//! designing the sixteen `terminal.ansi*` colors together: a prompt, `ls`
+//! output in the usual `LS_COLORS`, a `git diff`, compiler diagnostics and
+//! a color test showing every slot as text and as a background.
+//!
+//! The session is a [`Highlighted`] document on the terminal's background,
+//! so every screenshot format draws it. Slots the theme leaves unset keep
+//! xterm's defaults, as in [`TerminalColors`].
+
+use crate::export::{TerminalColors, ANSI_NAMES};
+use crate::theme::Theme;
+
+use super::{Highlighted, Span, Style};
+
+const BLACK: u8 = 0;
+const RED: u8 = 1;
+const GREEN: u8 = 2;
+const YELLOW: u8 = 3;
+const BLUE: u8 = 4;
+const MAGENTA: u8 = 5;
+const CYAN: u8 = 6;
+/// Added to a slot for its bright variant.
+const BRIGHT: u8 = 8;
+
+/// How a run of output is drawn: an ANSI slot or the default foreground,
+/// bold or not.
+#[derive(Debug, Clone, Copy)]
+struct Pen {
+    slot: Option<u8>,
+    bold: bool,
+}
+
+const PLAIN: Pen = Pen {
+    slot: None,
+    bold: false,
+};
+const BOLD: Pen = Pen {
+    slot: None,
+    bold: true,
+};
+
+const fn fg(slot: u8) -> Pen {
+    Pen {
+        slot: Some(slot),
+        bold: false,
+    }
+}
+
+const fn bold(slot: u8) -> Pen {
+    Pen {
+        slot: Some(slot),
+        bold: true,
+    }
+}
+
+/// The prompt, before each command.
+const PROMPT: &[(&str, Pen)] = &[
+    ("[09:41] ", fg(BLACK + BRIGHT)),
+    ("~/cyberdeck", bold(BLUE)),
+    (" on ", PLAIN),
+    ("main", fg(MAGENTA)),
+    ("*", fg(YELLOW + BRIGHT)),
+    (" $ ", fg(GREEN)),
+];
+
+/// What runs, and what it prints: the first line and each one after an
+/// empty line are commands, after the prompt.
+const SESSION: &[&[(&str, Pen)]] = &[
+    &[("ls -F --color", PLAIN)],
+    &[
+        ("Cargo.toml  ", PLAIN),
+        ("crates/", bold(BLUE)),
+        ("  ", PLAIN),
+        ("icon.png", bold(MAGENTA)),
+        ("  ", PLAIN),
+        ("latest.vsix", bold(RED)),
+        ("  ", PLAIN),
+        ("README -> README.md", bold(CYAN)),
+        ("  ", PLAIN),
+        ("release.sh*", bold(GREEN)),
+    ],
+    &[],
+    &[("git diff --stat -p palette/", PLAIN)],
+    &[
+        (" palette/cyberdeck-2025.toml | 3 ", PLAIN),
+        ("++", fg(GREEN)),
+        ("-", fg(RED)),
+    ],
+    &[(
+        "diff --git a/palette/cyberdeck-2025.toml b/palette/cyberdeck-2025.toml",
+        BOLD,
+    )],
+    &[("@@ -118,7 +118,8 @@", fg(CYAN)), (" [[color]]", PLAIN)],
+    &[(" name = \"Electric Cyan\"", PLAIN)],
+    &[("-hex = \"#00b7c3\"", fg(RED))],
+    &[("+hex = \"#00d9ff\"", fg(GREEN))],
+    &[(
+        "+# Brightened to clear 4.5:1 on the terminal background.",
+        fg(GREEN),
+    )],
+    &[],
+    &[("cargo build", PLAIN)],
+    &[("   Compiling", bold(GREEN)), (" cyberdeck v1.1.2", PLAIN)],
+    &[
+        ("warning", bold(YELLOW)),
+        (": unused variable: `slot`", BOLD),
+    ],
+    &[
+        ("  --> ", bold(BLUE)),
+        ("src/render/terminal.rs:42:13", PLAIN),
+    ],
+    &[("error[E0308]", bold(RED)), (": mismatched types", BOLD)],
+    &[
+        ("  --> ", bold(BLUE)),
+        ("src/render/terminal.rs:57:22", PLAIN),
+    ],
+    &[("   |", bold(BLUE))],
+    &[
+        ("57 |", bold(BLUE)),
+        ("     let index: u8 = slots.len();", PLAIN),
+    ],
+    &[
+        ("   |", bold(BLUE)),
+        ("                --   ", bold(BLUE)),
+        ("^^^^^^^^^^^ expected `u8`, found `usize`", bold(RED)),
+    ],
+    &[("   |", bold(BLUE)), ("                |", bold(BLUE))],
+    &[
+        ("   |", bold(BLUE)),
+        ("                expected due to this", bold(BLUE)),
+    ],
+    &[
+        ("error", bold(RED)),
+        (
+            ": could not compile `cyberdeck` due to 1 previous error",
+            BOLD,
+        ),
+    ],
+    &[],
+    &[("colortest", PLAIN)],
+];
+
+/// Width of a color test cell, in columns.
+const CELL: usize = 9;
+
+/// The session under the theme's terminal colors, ending at a prompt with
+/// the cursor.
+pub fn session(theme: &Theme) -> Highlighted {
+    let colors = TerminalColors::new(theme);
+    let style = |pen: Pen| Style {
+        bold: pen.bold,
+        ..Style::plain(
+            pen.slot
+                .map_or(colors.foreground, |slot| colors.ansi[usize::from(slot)]),
+        )
+    };
+    let runs = |pens: &[(&str, Pen)]| -> Vec<Span> {
+        pens.iter()
+            .map(|&(text, pen)| Span {
+                text: text.to_owned(),
+                style: style(pen),
+            })
+            .collect()
+    };
+
+    let mut lines: Vec<Vec<Span>> = Vec::new();
+    let mut at_prompt = true;
+    for line in SESSION {
+        if at_prompt {
+            let mut prompt = runs(PROMPT);
+            prompt.extend(runs(line));
+            lines.push(prompt);
+            at_prompt = false;
+        } else if line.is_empty() {
+            at_prompt = true;
+        } else {
+            lines.push(runs(line));
+        }
+    }
+
+    // Each slot named in its color, then as a background, normal and
+    // bright.
+    for (label, base) in [("normal ", 0), ("bright ", BRIGHT)] {
+        let mut names = runs(&[(label, PLAIN)]);
+        let mut swatches = vec![Span {
+            text: " ".repeat(label.len()),
+            style: style(PLAIN),
+        }];
+        for (name, slot) in ANSI_NAMES.iter().zip(base..) {
+            names.push(Span {
+                text: format!("{name:<CELL$}"),
+                style: style(fg(slot)),
+            });
+            swatches.push(Span {
+                text: " ".repeat(CELL - 1),
+                style: Style {
+                    background: Some(colors.ansi[usize::from(slot)]),
+                    ..style(PLAIN)
+                },
+            });
+            swatches.extend(runs(&[(" ", PLAIN)]));
+        }
+        lines.push(names);
+        lines.push(swatches);
+    }
+
+    let mut prompt = runs(PROMPT);
+    prompt.push(Span {
+        text: " ".to_owned(),
+        style: Style {
+            background: Some(colors.cursor),
+            ..Style::plain(colors.cursor_text)
+        },
+    });
+    lines.push(prompt);
+
+    Highlighted {
+        title: "zsh — ~/cyberdeck".to_owned(),
+        background: colors.background,
+        foreground: colors.foreground,
+        first_line: 1,
+        lines,
+    }
+}
@@ -0,0 +1,643 @@
+//! A mock-up of the VS Code workbench around the highlighted code: title
+//! bar, activity bar, explorer, tabs, breadcrumbs, editor, terminal panel
+//! and status bar, each filled from the theme's workbench colors.
+//!
+//! Sizes follow VS Code's defaults. Keys the theme leaves unset fall back to
+//! what VS Code derives them from, roughly, so partial themes still draw a
+//! plausible window. The editor shows at most [`MAX_LINES`] lines.
+
+use crate::color::Color;
+use crate::export::is_dark;
+use crate::theme::Theme;
+
+use super::decorations::{Decorations, Grid};
+use super::minimap::{self, Minimap};
+use super::screenshot::{
+    baseline, draw_lines, Item, Scene, Screenshot, BUTTONS, CORNER_RADIUS, LINE_HEIGHT,
+};
+use super::{workbench, Highlighted, Style};
+
+/// Editor lines shown; the rest is scrolled out of view.
+pub const MAX_LINES: usize = 40;
+
+const TITLE_BAR: f64 = 30.0;
+const ACTIVITY_BAR: f64 = 48.0;
+const SIDE_BAR: f64 = 240.0;
+const TABS: f64 = 35.0;
+const BREADCRUMBS: f64 = 22.0;
+const PANEL: f64 = 180.0;
+const PANEL_TABS: f64 = 35.0;
+const STATUS_BAR: f64 = 22.0;
+const ROW: f64 = 22.0;
+/// Columns the editor is at least wide.
+const MIN_COLUMNS: usize = 72;
+/// The line the cursor is on, counting from the first shown.
+const CURSOR_LINE: usize = 2;
+/// The explorer row drawn as hovered, to show `list.hoverBackground`.
+const HOVERED_ROW: usize = 6;
+
+/// Terminal output in ANSI color slots: a build and test run.
+const TERMINAL: &[&[(&str, Option<&str>)]] = &[
+    &[
+        ("~/cyberdeck", Some("terminal.ansiBlue")),
+        (" on ", None),
+        ("main", Some("terminal.ansiMagenta")),
+        (" > ", Some("terminal.ansiGreen")),
+        ("cargo test", None),
+    ],
+    &[
+        ("   Compiling", Some("terminal.ansiGreen")),
+        (" cyberdeck v1.1.2", None),
+    ],
+    &[
+        ("warning", Some("terminal.ansiYellow")),
+        (": unused variable: `scope`", None),
+    ],
+    &[
+        ("    Finished", Some("terminal.ansiGreen")),
+        (" `test` profile in 2.31s", None),
+    ],
+    &[
+        ("test result: ", None),
+        ("ok", Some("terminal.ansiGreen")),
+        (". 42 passed; ", None),
+        ("0 failed", Some("terminal.ansiRed")),
+    ],
+    &[
+        ("~/cyberdeck", Some("terminal.ansiBlue")),
+        (" on ", None),
+        ("main", Some("terminal.ansiMagenta")),
+        (" > ", Some("terminal.ansiGreen")),
+    ],
+];
+
+pub(super) fn scene(settings: &Screenshot, theme: &Theme, code: &Highlighted) -> Scene {
+    let bg = code.background;
+    let fg = code.foreground;
+    let dark = is_dark(theme);
+    let key = |key: &str, background: Color| workbench(theme, key, background);
+    let faded =
+        |color: Color, alpha: u8, background: Color| color.with_alpha(alpha).over(background);
+
+    let font_size = settings.font_size;
+    let cell = settings.advance * font_size;
+    let line_height = LINE_HEIGHT * font_size;
+    let ui_size = (font_size * 0.93).round();
+    let ui_cell = settings.advance * ui_size;
+
+    let lines = &code.lines[..code.lines.len().min(MAX_LINES)];
+    let last_line = code.first_line + lines.len().saturating_sub(1);
+    let gutter = if settings.line_numbers {
+        last_line.to_string().len().max(2) + 3
+    } else {
+        2
+    };
+    let decorations = settings.decorations.then(|| Decorations::new(lines));
+    let code_width = code
+        .width()
+        .max(decorations.as_ref().map_or(0, Decorations::width));
+    let columns = (gutter + code_width + 2).max(MIN_COLUMNS);
+    let minimap_width = if settings.minimap {
+        minimap::WIDTH
+    } else {
+        0.0
+    };
+    let editor_width = columns as f64 * cell + minimap_width;
+    let editor_height = (lines.len().max(16) as f64 + 1.0) * line_height;
+    let width = ACTIVITY_BAR + SIDE_BAR + editor_width;
+    let height = TITLE_BAR + TABS + BREADCRUMBS + editor_height + PANEL + STATUS_BAR;
+    let (left, top) = (settings.padding, settings.padding);
+
+    let mut items = Vec::new();
+    let rect = |items: &mut Vec<Item>, x: f64, y: f64, w: f64, h: f64, fill: Color| {
+        items.push(Item::Rect {
+            x,
+            y,
+            width: w,
+            height: h,
+            radius: 0.0,
+            fill,
+        });
+    };
+    let text = |items: &mut Vec<Item>, x: f64, y: f64, run: &str, color: Color| {
+        items.push(Item::Text {
+            x,
+            y: y + (ROW - ui_size) / 2.0 + ui_size * 0.8,
+            size: ui_size,
+            text: run.to_owned(),
+            style: Style::plain(color),
+        });
+    };
+
+    // Window and title bar.
+    items.push(Item::Rect {
+        x: left,
+        y: top,
+        width,
+        height,
+        radius: CORNER_RADIUS,
+        fill: bg,
+    });
+    // The status bar goes under the body so that the panels above square
+    // off its top corners.
+    let status_top = top + height - STATUS_BAR;
+    let status_bg = key("statusBar.background", bg).unwrap_or(if dark {
+        Color::rgb(0x00, 0x7a, 0xcc)
+    } else {
+        Color::rgb(0x00, 0x66, 0xb8)
+    });
+    let status_fg = key("statusBar.foreground", status_bg).unwrap_or(Color::rgb(0xff, 0xff, 0xff));
+    items.push(Item::Rect {
+        x: left,
+        y: status_top - CORNER_RADIUS,
+        width,
+        height: STATUS_BAR + CORNER_RADIUS,
+        radius: CORNER_RADIUS,
+        fill: status_bg,
+    });
+    let title_bg = key("titleBar.activeBackground", bg).unwrap_or(bg);
+    let title_fg = key("titleBar.activeForeground", title_bg).unwrap_or(fg);
+    items.push(Item::Rect {
+        x: left,
+        y: top,
+        width,
+        height: TITLE_BAR,
+        radius: CORNER_RADIUS,
+        fill: title_bg,
+    });
+    rect(
+        &mut items,
+        left,
+        top + TITLE_BAR - CORNER_RADIUS,
+        width,
+        CORNER_RADIUS,
+        title_bg,
+    );
+    for (index, fill) in BUTTONS.into_iter().enumerate() {
+        items.push(Item::Circle {
+            x: left + 18.0 + index as f64 * 20.0,
+            y: top + TITLE_BAR / 2.0,
+            radius: 6.0,
+            fill,
+        });
+    }
+    let title = format!(
+        "{} — cyberdeck",
+        settings.title.as_deref().unwrap_or(&code.title)
+    );
+    text(
+        &mut items,
+        left + (width - title.chars().count() as f64 * ui_cell) / 2.0,
+        top + (TITLE_BAR - ROW) / 2.0,
+        &title,
+        title_fg,
+    );
+    if let Some(border) = key("titleBar.border", title_bg) {
+        rect(&mut items, left, top + TITLE_BAR - 1.0, width, 1.0, border);
+    }
+
+    let body_top = top + TITLE_BAR;
+    let body_height = height - TITLE_BAR - STATUS_BAR;
+
+    // Activity bar: the explorer icon active, with a badge on source control.
+    let activity_bg = key("activityBar.background", bg).unwrap_or(bg);
+    let active_icon = key("activityBar.foreground", activity_bg).unwrap_or(fg);
+    let inactive_icon = key("activityBar.inactiveForeground", activity_bg)
+        .unwrap_or_else(|| faded(active_icon, 0x66, activity_bg));
+    rect(
+        &mut items,
+        left,
+        body_top,
+        ACTIVITY_BAR,
+        body_height,
+        activity_bg,
+    );
+    for index in 0..5 {
+        let y = body_top + 12.0 + index as f64 * 48.0;
+        let color = if index == 0 {
+            active_icon
+        } else {
+            inactive_icon
+        };
+        items.push(Item::Rect {
+            x: left + 13.0,
+            y,
+            width: 22.0,
+            height: 22.0,
+            radius: 4.0,
+            fill: color,
+        });
+        items.push(Item::Rect {
+            x: left + 16.0,
+            y: y + 3.0,
+            width: 16.0,
+            height: 16.0,
+            radius: 2.0,
+            fill: activity_bg,
+        });
+    }
+    if let Some(border) = key("activityBar.activeBorder", activity_bg) {
+        rect(&mut items, left, body_top, 2.0, 48.0, border);
+    }
+    let badge_bg =
+        key("activityBarBadge.background", activity_bg).unwrap_or(Color::rgb(0x00, 0x78, 0xd4));
+    let badge_fg =
+        key("activityBarBadge.foreground", badge_bg).unwrap_or(Color::rgb(0xff, 0xff, 0xff));
+    items.push(Item::Circle {
+        x: left + 36.0,
+        y: body_top + 12.0 + 2.0 * 48.0 + 20.0,
+        radius: 8.0,
+        fill: badge_bg,
+    });
+    text(
+        &mut items,
+        left + 36.0 - ui_cell / 2.0,
+        body_top + 12.0 + 2.0 * 48.0 + 20.0 - ROW / 2.0,
+        "3",
+        badge_fg,
+    );
+    if let Some(border) = key("activityBar.border", activity_bg) {
+        rect(
+            &mut items,
+            left + ACTIVITY_BAR - 1.0,
+            body_top,
+            1.0,
+            body_height,
+            border,
+        );
+    }
+
+    // Side bar: the explorer with the file open in the editor selected.
+    let side_left = left + ACTIVITY_BAR;
+    let side_bg = key("sideBar.background", bg).unwrap_or(bg);
+    let side_fg = key("sideBar.foreground", side_bg).unwrap_or(fg);
+    rect(
+        &mut items,
+        side_left,
+        body_top,
+        SIDE_BAR,
+        body_height,
+        side_bg,
+    );
+    text(
+        &mut items,
+        side_left + 20.0,
+        body_top + 8.0,
+        "EXPLORER",
+        key("sideBarTitle.foreground", side_bg).unwrap_or(side_fg),
+    );
+    let header_top = body_top + 35.0;
+    let header_bg = key("sideBarSectionHeader.background", side_bg).unwrap_or(side_bg);
+    rect(&mut items, side_left, header_top, SIDE_BAR, ROW, header_bg);
+    text(
+        &mut items,
+        side_left + 8.0,
+        header_top,
+        "▾ CYBERDECK",
+        key("sideBarSectionHeader.foreground", header_bg).unwrap_or(side_fg),
+    );
+    // Rows as (name, depth); the open file is selected.
+    let tree = [
+        ("▸ .github", 0),
+        ("▸ crates", 0),
+        ("▾ language_demos", 0),
+        (code.title.as_str(), 2),
+        ("▸ palette", 0),
+        ("▸ themes", 0),
+        ("package.json", 1),
+        ("README.md", 1),
+    ];
+    let selection_bg =
+        key("list.activeSelectionBackground", side_bg).unwrap_or_else(|| faded(fg, 0x33, side_bg));
+    let selection_fg = key("list.activeSelectionForeground", selection_bg).unwrap_or(side_fg);
+    let hover_bg = key("list.hoverBackground", side_bg).unwrap_or_else(|| faded(fg, 0x14, side_bg));
+    for (index, (name, depth)) in tree.into_iter().enumerate() {
+        let y = header_top + ROW * (index as f64 + 1.0);
+        let color = if depth == 2 {
+            rect(&mut items, side_left, y, SIDE_BAR, ROW, selection_bg);
+            if let Some(border) = key("list.focusOutline", selection_bg) {
+                rect(&mut items, side_left, y, 1.0, ROW, border);
+            }
+            selection_fg
+        } else if index == HOVERED_ROW {
+            rect(&mut items, side_left, y, SIDE_BAR, ROW, hover_bg);
+            key("list.hoverForeground", hover_bg).unwrap_or(side_fg)
+        } else {
+            side_fg
+        };
+        text(
+            &mut items,
+            side_left + 12.0 * (depth as f64 + 1.0),
+            y,
+            name,
+            color,
+        );
+    }
+    if let Some(border) = key("sideBar.border", side_bg) {
+        rect(
+            &mut items,
+            side_left + SIDE_BAR - 1.0,
+            body_top,
+            1.0,
+            body_height,
+            border,
+        );
+    }
+
+    // Tabs: the file active, two more inactive.
+    let editor_left = side_left + SIDE_BAR;
+    let tabs_bg = key("editorGroupHeader.tabsBackground", bg).unwrap_or(side_bg);
+    rect(
+        &mut items,
+        editor_left,
+        body_top,
+        editor_width,
+        TABS,
+        tabs_bg,
+    );
+    let mut tab_left = editor_left;
+    for (index, name) in [code.title.as_str(), "package.json", "README.md"]
+        .into_iter()
+        .enumerate()
+    {
+        let active = index == 0;
+        let tab_width = (name.chars().count() as f64 + 6.0) * ui_cell;
+        let tab_bg = if active {
+            key("tab.activeBackground", tabs_bg).unwrap_or(bg)
+        } else {
+            key("tab.inactiveBackground", tabs_bg).unwrap_or(tabs_bg)
+        };
+        let tab_fg = if active {
+            key("tab.activeForeground", tab_bg).unwrap_or(fg)
+        } else {
+            key("tab.inactiveForeground", tab_bg).unwrap_or_else(|| faded(fg, 0x80, tab_bg))
+        };
+        rect(&mut items, tab_left, body_top, tab_width, TABS, tab_bg);
+        if active {
+            if let Some(border) = key("tab.activeBorderTop", tab_bg) {
+                rect(&mut items, tab_left, body_top, tab_width, 1.0, border);
+            }
+            if let Some(border) = key("tab.activeBorder", tab_bg) {
+                rect(
+                    &mut items,
+                    tab_left,
+                    body_top + TABS - 1.0,
+                    tab_width,
+                    1.0,
+                    border,
+                );
+            }
+        }
+        text(
+            &mut items,
+            tab_left + 2.0 * ui_cell,
+            body_top + (TABS - ROW) / 2.0,
+            name,
+            tab_fg,
+        );
+        tab_left += tab_width;
+        if let Some(border) = key("tab.border", tabs_bg) {
+            rect(&mut items, tab_left - 1.0, body_top, 1.0, TABS, border);
+        }
+    }
+    if let Some(border) = key("editorGroupHeader.tabsBorder", tabs_bg) {
+        rect(
+            &mut items,
+            editor_left,
+            body_top + TABS - 1.0,
+            editor_width,
+            1.0,
+            border,
+        );
+    }
+
+    // Breadcrumbs.
+    let crumbs_top = body_top + TABS;
+    let crumbs_bg = key("breadcrumb.background", bg).unwrap_or(bg);
+    rect(
+        &mut items,
+        editor_left,
+        crumbs_top,
+        editor_width,
+        BREADCRUMBS,
+        crumbs_bg,
+    );
+    text(
+        &mut items,
+        editor_left + 2.0 * ui_cell,
+        crumbs_top,
+        &format!("language_demos › {}", code.title),
+        key("breadcrumb.foreground", crumbs_bg).unwrap_or_else(|| faded(fg, 0xcc, crumbs_bg)),
+    );
+
+    // Editor: line numbers, the cursor line and the code.
+    let editor_top = crumbs_top + BREADCRUMBS;
+    let cursor = CURSOR_LINE.min(lines.len().saturating_sub(1));
+    let cursor_y = editor_top + cursor as f64 * line_height;
+    if let Some(highlight) = key("editor.lineHighlightBackground", bg) {
+        rect(
+            &mut items,
+            editor_left,
+            cursor_y,
+            editor_width,
+            line_height,
+            highlight,
+        );
+    }
+    if let Some(border) = key("editor.lineHighlightBorder", bg) {
+        rect(&mut items, editor_left, cursor_y, editor_width, 1.0, border);
+        rect(
+            &mut items,
+            editor_left,
+            cursor_y + line_height - 1.0,
+            editor_width,
+            1.0,
+            border,
+        );
+    }
+    let code_left = editor_left + gutter as f64 * cell;
+    let grid = Grid {
+        left: code_left,
+        top: editor_top,
+        cell,
+        font_size,
+        line_left: editor_left,
+        line_right: editor_left + editor_width,
+    };
+    if let Some(decorations) = &decorations {
+        decorations.underlay(&mut items, theme, bg, grid);
+    }
+    if settings.line_numbers {
+        let inactive =
+            key("editorLineNumber.foreground", bg).unwrap_or_else(|| faded(fg, 0x66, bg));
+        let active = key("editorLineNumber.activeForeground", bg).unwrap_or(fg);
+        for offset in 0..lines.len() {
+            items.push(Item::Text {
+                x: editor_left + cell,
+                y: editor_top + offset as f64 * line_height + baseline(font_size),
+                size: font_size,
+                text: format!("{:>width$}", code.first_line + offset, width = gutter - 3),
+                style: Style::plain(if offset == cursor { active } else { inactive }),
+            });
+        }
+    }
+    draw_lines(&mut items, lines, code_left, editor_top, cell, font_size);
+    if let Some(decorations) = &decorations {
+        decorations.overlay(&mut items, theme, bg, grid);
+    }
+    if settings.minimap {
+        // The whole file, with the slider over the lines in view.
+        let minimap = Minimap {
+            lines: &code.lines,
+            visible: 0..lines.len(),
+            decorations: decorations.as_ref(),
+        };
+        minimap.draw(
+            &mut items,
+            theme,
+            bg,
+            (editor_left + editor_width - minimap_width, editor_top),
+            editor_height,
+        );
+    }
+    let cursor_column: usize = lines
+        .get(cursor)
+        .map_or(0, |line| line.iter().map(|s| s.text.chars().count()).sum());
+    rect(
+        &mut items,
+        code_left + cursor_column as f64 * cell,
+        cursor_y + 2.0,
+        2.0,
+        line_height - 4.0,
+        key("editorCursor.foreground", bg).unwrap_or(fg),
+    );
+
+    // Panel: the terminal, with its tab active.
+    let panel_top = editor_top + editor_height;
+    let panel_bg = key("panel.background", bg).unwrap_or(bg);
+    rect(
+        &mut items,
+        editor_left,
+        panel_top,
+        editor_width,
+        PANEL,
+        panel_bg,
+    );
+    rect(
+        &mut items,
+        editor_left,
+        panel_top,
+        editor_width,
+        1.0,
+        key("panel.border", panel_bg).unwrap_or_else(|| faded(fg, 0x33, panel_bg)),
+    );
+    let panel_active = key("panelTitle.activeForeground", panel_bg).unwrap_or(fg);
+    let panel_inactive = key("panelTitle.inactiveForeground", panel_bg)
+        .unwrap_or_else(|| faded(panel_active, 0x99, panel_bg));
+    let mut tab_x = editor_left + 2.0 * ui_cell;
+    for (index, name) in ["PROBLEMS", "OUTPUT", "DEBUG CONSOLE", "TERMINAL"]
+        .into_iter()
+        .enumerate()
+    {
+        let active = index == 3;
+        let length = name.chars().count() as f64 * ui_cell;
+        text(
+            &mut items,
+            tab_x,
+            panel_top + (PANEL_TABS - ROW) / 2.0,
+            name,
+            if active { panel_active } else { panel_inactive },
+        );
+        if active {
+            rect(
+                &mut items,
+                tab_x,
+                panel_top + PANEL_TABS - 6.0,
+                length,
+                1.0,
+                key("panelTitle.activeBorder", panel_bg).unwrap_or(panel_active),
+            );
+        }
+        tab_x += length + 3.0 * ui_cell;
+    }
+    let terminal_bg = key("terminal.background", panel_bg).unwrap_or(panel_bg);
+    let terminal_fg = key("terminal.foreground", terminal_bg).unwrap_or(fg);
+    let terminal_top = panel_top + PANEL_TABS;
+    rect(
+        &mut items,
+        editor_left,
+        terminal_top,
+        editor_width,
+        PANEL - PANEL_TABS,
+        terminal_bg,
+    );
+    let terminal_left = editor_left + 2.0 * ui_cell;
+    let mut prompt_end = terminal_left;
+    for (row, runs) in TERMINAL.iter().enumerate() {
+        let y = terminal_top + row as f64 * ROW;
+        let mut x = terminal_left;
+        for &(run, slot) in runs.iter() {
+            let color = slot
+                .and_then(|slot| key(slot, terminal_bg))
+                .unwrap_or(terminal_fg);
+            text(&mut items, x, y, run, color);
+            x += run.chars().count() as f64 * ui_cell;
+        }
+        prompt_end = x;
+    }
+    rect(
+        &mut items,
+        prompt_end,
+        terminal_top + (TERMINAL.len() as f64 - 1.0) * ROW + 3.0,
+        ui_cell,
+        ROW - 6.0,
+        key("terminalCursor.foreground", terminal_bg).unwrap_or(terminal_fg),
+    );
+
+    // Status bar.
+    let remote_bg = key("statusBarItem.remoteBackground", status_bg).unwrap_or(status_bg);
+    let remote_width = 4.0 * ui_cell;
+    items.push(Item::Rect {
+        x: left,
+        y: status_top,
+        width: remote_width,
+        height: STATUS_BAR,
+        radius: 0.0,
+        fill: remote_bg,
+    });
+    text(
+        &mut items,
+        left + ui_cell,
+        status_top,
+        "><",
+        key("statusBarItem.remoteForeground", remote_bg).unwrap_or(status_fg),
+    );
+    text(
+        &mut items,
+        left + remote_width + ui_cell,
+        status_top,
+        "main*  0 errors  1 warning",
+        status_fg,
+    );
+    let position = format!(
+        "Ln {}, Col {}   Spaces: 4   UTF-8   LF",
+        code.first_line + cursor,
+        cursor_column + 1
+    );
+    text(
+        &mut items,
+        left + width - (position.chars().count() as f64 + 2.0) * ui_cell,
+        status_top,
+        &position,
+        status_fg,
+    );
+    if let Some(border) = key("statusBar.border", status_bg) {
+        rect(&mut items, left, status_top, width, 1.0, border);
+    }
+
+    Scene {
+        width: width + 2.0 * settings.padding,
+        height: height + 2.0 * settings.padding,
+        items,
+    }
+}
@@ -0,0 +1,167 @@
+//! Palette roles and where a VS Code theme expresses each of them.
+//!
+//! Roles are the vocabulary shared by the palette file, importers and
+//! exporters: a role such as `keyword` or `background.secondary` names what
+//! a color is for, independent of any one editor's keys.
+
+use indexmap::IndexMap;
+
+use crate::color::Color;
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+/// Where a role's color is read from in a VS Code theme.
+#[derive(Debug, Clone, Copy)]
+pub enum RoleSource {
+    /// The first of these workbench keys that the theme defines.
+    Workbench(&'static [&'static str]),
+    /// The foreground the first of these scopes resolves to.
+    Token(&'static [&'static str]),
+}
+
+/// Every role, in palette order, with its source.
+pub const ROLES: &[(&str, RoleSource)] = &[
+    ("background", RoleSource::Workbench(&["editor.background"])),
+    (
+        "background.secondary",
+        RoleSource::Workbench(&[
+            "sideBar.background",
+            "terminal.background",
+            "panel.background",
+        ]),
+    ),
+    (
+        "background.tertiary",
+        RoleSource::Workbench(&["peekViewEditor.background", "editorWidget.background"]),
+    ),
+    (
+        "highlight",
+        RoleSource::Workbench(&["editor.findMatchBackground", "editor.selectionBackground"]),
+    ),
+    (
+        "border",
+        RoleSource::Workbench(&["widget.border", "editorGroup.border", "panel.border"]),
+    ),
+    (
+        "foreground",
+        RoleSource::Workbench(&["editor.foreground", "foreground"]),
+    ),
+    (
+        "foreground.secondary",
+        RoleSource::Workbench(&["input.foreground", "sideBar.foreground"]),
+    ),
+    (
+        "foreground.muted",
+        RoleSource::Workbench(&["editorLineNumber.foreground", "descriptionForeground"]),
+    ),
+    (
+        "accent.primary",
+        RoleSource::Workbench(&[
+            "activityBar.activeBorder",
+            "badge.background",
+            "focusBorder",
+        ]),
+    ),
+    (
+        "accent.secondary",
+        RoleSource::Workbench(&["activityBarBadge.background", "tab.activeBorder"]),
+    ),
+    (
+        "accent.tertiary",
+        RoleSource::Workbench(&["statusBar.background", "panelTitle.activeBorder"]),
+    ),
+    (
+        "cursor",
+        RoleSource::Workbench(&["editorCursor.foreground"]),
+    ),
+    (
+        "error",
+        RoleSource::Workbench(&["editorError.foreground", "errorForeground"]),
+    ),
+    (
+        "warning",
+        RoleSource::Workbench(&["editorWarning.foreground"]),
+    ),
+    (
+        "success",
+        RoleSource::Workbench(&[
+            "gitDecoration.untrackedResourceForeground",
+            "testing.iconPassed",
+        ]),
+    ),
+    (
+        "inserted",
+        RoleSource::Workbench(&[
+            "gitDecoration.addedResourceForeground",
+            "editorGutter.addedBackground",
+        ]),
+    ),
+    (
+        "modified",
+        RoleSource::Workbench(&[
+            "gitDecoration.modifiedResourceForeground",
+            "editorGutter.modifiedBackground",
+        ]),
+    ),
+    (
+        "deleted",
+        RoleSource::Workbench(&[
+            "gitDecoration.deletedResourceForeground",
+            "editorGutter.deletedBackground",
+        ]),
+    ),
+    ("comment", RoleSource::Token(&["comment.line", "comment"])),
+    (
+        "keyword",
+        RoleSource::Token(&["keyword.control", "keyword"]),
+    ),
+    ("storage", RoleSource::Token(&["storage.type", "storage"])),
+    ("string", RoleSource::Token(&["string.quoted", "string"])),
+    ("number", RoleSource::Token(&["constant.numeric"])),
+    (
+        "constant",
+        RoleSource::Token(&["constant.language", "constant"]),
+    ),
+    ("function", RoleSource::Token(&["entity.name.function"])),
+    (
+        "type",
+        RoleSource::Token(&["entity.name.type", "support.type"]),
+    ),
+    (
+        "class",
+        RoleSource::Token(&["entity.name.class", "entity.name.type.class"]),
+    ),
+    (
+        "variable",
+        RoleSource::Token(&["variable.other", "variable"]),
+    ),
+    ("parameter", RoleSource::Token(&["variable.parameter"])),
+    (
+        "property",
+        RoleSource::Token(&["variable.other.property", "support.variable.property"]),
+    ),
+];
+
+/// Reads every role the theme expresses. Token roles only count when a rule
+/// actually matches; the default foreground is not attributed to them.
+pub fn resolve_roles(theme: &Theme) -> IndexMap<&'static str, Color> {
+    let resolver = Resolver::new(theme);
+    let mut roles = IndexMap::new();
+    for &(role, source) in ROLES {
+        if let Some(color) = resolve_source(theme, &resolver, source) {
+            roles.insert(role, color);
+        }
+    }
+    roles
+}
+
+fn resolve_source(theme: &Theme, resolver: &Resolver, source: RoleSource) -> Option<Color> {
+    match source {
+        RoleSource::Workbench(keys) => keys
+            .iter()
+            .find_map(|key| theme.colors.get(*key).and_then(|v| v.parse().ok())),
+        RoleSource::Token(scopes) => scopes
+            .iter()
+            .find_map(|s| resolver.resolve_matched(&["source", s]).foreground),
+    }
+}
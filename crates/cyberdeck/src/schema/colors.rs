@@ -0,0 +1,950 @@
+//! Workbench color ids VS Code contributes, for flagging keys under
+//! `colors` that it would ignore, and the ones it has retired.
+//!
+//! [`COLOR_IDS`] is kept sorted for binary search and excludes deprecated
+//! ids. Ids VS Code has added since are reported as unknown until they're
+//! listed here.
+
+/// A color id VS Code no longer honors (or warns about) from `since` on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deprecation {
+    pub id: &'static str,
+    /// VS Code `(major, minor)` that deprecated it.
+    pub since: (u32, u32),
+    pub replacement: Option<&'static str>,
+}
+
+const fn deprecated(
+    id: &'static str,
+    since: (u32, u32),
+    replacement: Option<&'static str>,
+) -> Deprecation {
+    Deprecation {
+        id,
+        since,
+        replacement,
+    }
+}
+
+pub const DEPRECATED_COLOR_IDS: &[Deprecation] = &[
+    deprecated(
+        "editorGroup.background",
+        (1, 25),
+        Some("editorGroup.emptyBackground"),
+    ),
+    deprecated(
+        "editorUnnecessary.foreground",
+        (1, 31),
+        Some("editorUnnecessaryCode.opacity"),
+    ),
+    deprecated(
+        "notebook.focusedRowBorder",
+        (1, 48),
+        Some("notebook.focusedCellBorder"),
+    ),
+    deprecated(
+        "notebook.rowHoverBackground",
+        (1, 48),
+        Some("notebook.cellHoverBackground"),
+    ),
+    deprecated(
+        "quickInput.list.focusBackground",
+        (1, 55),
+        Some("quickInputList.focusBackground"),
+    ),
+    deprecated(
+        "welcomePage.buttonBackground",
+        (1, 58),
+        Some("welcomePage.tileBackground"),
+    ),
+    deprecated(
+        "welcomePage.buttonHoverBackground",
+        (1, 58),
+        Some("welcomePage.tileHoverBackground"),
+    ),
+    deprecated(
+        "editorIndentGuide.activeBackground",
+        (1, 66),
+        Some("editorIndentGuide.activeBackground1"),
+    ),
+    deprecated(
+        "editorIndentGuide.background",
+        (1, 66),
+        Some("editorIndentGuide.background1"),
+    ),
+    deprecated("scm.providerBorder", (1, 70), None),
+];
+
+pub const COLOR_IDS: &[&str] = &[
+    "actionBar.toggledBackground",
+    "activityBar.activeBackground",
+    "activityBar.activeBorder",
+    "activityBar.activeFocusBorder",
+    "activityBar.background",
+    "activityBar.border",
+    "activityBar.dropBorder",
+    "activityBar.foreground",
+    "activityBar.inactiveForeground",
+    "activityBarBadge.background",
+    "activityBarBadge.foreground",
+    "activityBarTop.activeBackground",
+    "activityBarTop.activeBorder",
+    "activityBarTop.background",
+    "activityBarTop.dropBorder",
+    "activityBarTop.foreground",
+    "activityBarTop.inactiveForeground",
+    "activityErrorBadge.background",
+    "activityErrorBadge.foreground",
+    "activityWarningBadge.background",
+    "activityWarningBadge.foreground",
+    "badge.background",
+    "badge.foreground",
+    "banner.background",
+    "banner.foreground",
+    "banner.iconForeground",
+    "breadcrumb.activeSelectionForeground",
+    "breadcrumb.background",
+    "breadcrumb.focusForeground",
+    "breadcrumb.foreground",
+    "breadcrumbPicker.background",
+    "button.background",
+    "button.border",
+    "button.foreground",
+    "button.hoverBackground",
+    "button.secondaryBackground",
+    "button.secondaryForeground",
+    "button.secondaryHoverBackground",
+    "button.separator",
+    "charts.blue",
+    "charts.foreground",
+    "charts.green",
+    "charts.lines",
+    "charts.orange",
+    "charts.purple",
+    "charts.red",
+    "charts.yellow",
+    "chat.avatarBackground",
+    "chat.avatarForeground",
+    "chat.checkpointSeparator",
+    "chat.editedFileForeground",
+    "chat.linesAddedForeground",
+    "chat.linesRemovedForeground",
+    "chat.requestBackground",
+    "chat.requestBorder",
+    "chat.requestBubbleBackground",
+    "chat.requestBubbleHoverBackground",
+    "chat.requestCodeBorder",
+    "chat.slashCommandBackground",
+    "chat.slashCommandForeground",
+    "checkbox.background",
+    "checkbox.border",
+    "checkbox.disabled.background",
+    "checkbox.disabled.foreground",
+    "checkbox.foreground",
+    "checkbox.selectBackground",
+    "checkbox.selectBorder",
+    "commandCenter.activeBackground",
+    "commandCenter.activeBorder",
+    "commandCenter.activeForeground",
+    "commandCenter.background",
+    "commandCenter.border",
+    "commandCenter.debuggingBackground",
+    "commandCenter.foreground",
+    "commandCenter.inactiveBorder",
+    "commandCenter.inactiveForeground",
+    "commentsView.resolvedIcon",
+    "commentsView.unresolvedIcon",
+    "contrastActiveBorder",
+    "contrastBorder",
+    "debugConsole.errorForeground",
+    "debugConsole.infoForeground",
+    "debugConsole.sourceForeground",
+    "debugConsole.warningForeground",
+    "debugConsoleInputIcon.foreground",
+    "debugExceptionWidget.background",
+    "debugExceptionWidget.border",
+    "debugIcon.breakpointCurrentStackframeForeground",
+    "debugIcon.breakpointDisabledForeground",
+    "debugIcon.breakpointForeground",
+    "debugIcon.breakpointStackframeForeground",
+    "debugIcon.breakpointUnverifiedForeground",
+    "debugIcon.continueForeground",
+    "debugIcon.disconnectForeground",
+    "debugIcon.pauseForeground",
+    "debugIcon.restartForeground",
+    "debugIcon.startForeground",
+    "debugIcon.stepBackForeground",
+    "debugIcon.stepIntoForeground",
+    "debugIcon.stepOutForeground",
+    "debugIcon.stepOverForeground",
+    "debugIcon.stopForeground",
+    "debugTokenExpression.boolean",
+    "debugTokenExpression.error",
+    "debugTokenExpression.name",
+    "debugTokenExpression.number",
+    "debugTokenExpression.string",
+    "debugTokenExpression.type",
+    "debugTokenExpression.value",
+    "debugToolBar.background",
+    "debugToolBar.border",
+    "debugView.exceptionLabelBackground",
+    "debugView.exceptionLabelForeground",
+    "debugView.stateLabelBackground",
+    "debugView.stateLabelForeground",
+    "debugView.valueChangedHighlight",
+    "descriptionForeground",
+    "diffEditor.border",
+    "diffEditor.diagonalFill",
+    "diffEditor.insertedLineBackground",
+    "diffEditor.insertedTextBackground",
+    "diffEditor.insertedTextBorder",
+    "diffEditor.move.border",
+    "diffEditor.moveActive.border",
+    "diffEditor.removedLineBackground",
+    "diffEditor.removedTextBackground",
+    "diffEditor.removedTextBorder",
+    "diffEditor.unchangedCodeBackground",
+    "diffEditor.unchangedRegionBackground",
+    "diffEditor.unchangedRegionForeground",
+    "diffEditor.unchangedRegionShadow",
+    "diffEditorGutter.insertedLineBackground",
+    "diffEditorGutter.removedLineBackground",
+    "diffEditorOverview.insertedForeground",
+    "diffEditorOverview.removedForeground",
+    "disabledForeground",
+    "dropdown.background",
+    "dropdown.border",
+    "dropdown.foreground",
+    "dropdown.listBackground",
+    "editor.background",
+    "editor.compositionBorder",
+    "editor.findMatchBackground",
+    "editor.findMatchBorder",
+    "editor.findMatchForeground",
+    "editor.findMatchHighlightBackground",
+    "editor.findMatchHighlightBorder",
+    "editor.findMatchHighlightForeground",
+    "editor.findRangeHighlightBackground",
+    "editor.findRangeHighlightBorder",
+    "editor.focusedStackFrameHighlightBackground",
+    "editor.foldBackground",
+    "editor.foldPlaceholderForeground",
+    "editor.foreground",
+    "editor.hoverHighlightBackground",
+    "editor.inactiveSelectionBackground",
+    "editor.inlineValuesBackground",
+    "editor.inlineValuesForeground",
+    "editor.lineHighlightBackground",
+    "editor.lineHighlightBorder",
+    "editor.linkedEditingBackground",
+    "editor.placeholder.foreground",
+    "editor.rangeHighlightBackground",
+    "editor.rangeHighlightBorder",
+    "editor.selectionBackground",
+    "editor.selectionForeground",
+    "editor.selectionHighlightBackground",
+    "editor.selectionHighlightBorder",
+    "editor.snippetFinalTabstopHighlightBackground",
+    "editor.snippetFinalTabstopHighlightBorder",
+    "editor.snippetTabstopHighlightBackground",
+    "editor.snippetTabstopHighlightBorder",
+    "editor.stackFrameHighlightBackground",
+    "editor.symbolHighlightBackground",
+    "editor.symbolHighlightBorder",
+    "editor.wordHighlightBackground",
+    "editor.wordHighlightBorder",
+    "editor.wordHighlightStrongBackground",
+    "editor.wordHighlightStrongBorder",
+    "editor.wordHighlightTextBackground",
+    "editor.wordHighlightTextBorder",
+    "editorActionList.background",
+    "editorActionList.focusBackground",
+    "editorActionList.focusForeground",
+    "editorActionList.foreground",
+    "editorBracketHighlight.foreground1",
+    "editorBracketHighlight.foreground2",
+    "editorBracketHighlight.foreground3",
+    "editorBracketHighlight.foreground4",
+    "editorBracketHighlight.foreground5",
+    "editorBracketHighlight.foreground6",
+    "editorBracketHighlight.unexpectedBracket.foreground",
+    "editorBracketMatch.background",
+    "editorBracketMatch.border",
+    "editorBracketMatch.foreground",
+    "editorBracketPairGuide.activeBackground1",
+    "editorBracketPairGuide.activeBackground2",
+    "editorBracketPairGuide.activeBackground3",
+    "editorBracketPairGuide.activeBackground4",
+    "editorBracketPairGuide.activeBackground5",
+    "editorBracketPairGuide.activeBackground6",
+    "editorBracketPairGuide.background1",
+    "editorBracketPairGuide.background2",
+    "editorBracketPairGuide.background3",
+    "editorBracketPairGuide.background4",
+    "editorBracketPairGuide.background5",
+    "editorBracketPairGuide.background6",
+    "editorCodeLens.foreground",
+    "editorCommentsWidget.rangeActiveBackground",
+    "editorCommentsWidget.rangeBackground",
+    "editorCommentsWidget.replyInputBackground",
+    "editorCommentsWidget.resolvedBorder",
+    "editorCommentsWidget.unresolvedBorder",
+    "editorCursor.background",
+    "editorCursor.foreground",
+    "editorError.background",
+    "editorError.border",
+    "editorError.foreground",
+    "editorGhostText.background",
+    "editorGhostText.border",
+    "editorGhostText.foreground",
+    "editorGroup.border",
+    "editorGroup.dropBackground",
+    "editorGroup.dropIntoPromptBackground",
+    "editorGroup.dropIntoPromptBorder",
+    "editorGroup.dropIntoPromptForeground",
+    "editorGroup.emptyBackground",
+    "editorGroup.focusedEmptyBorder",
+    "editorGroupHeader.border",
+    "editorGroupHeader.noTabsBackground",
+    "editorGroupHeader.tabsBackground",
+    "editorGroupHeader.tabsBorder",
+    "editorGutter.addedBackground",
+    "editorGutter.addedSecondaryBackground",
+    "editorGutter.background",
+    "editorGutter.commentGlyphForeground",
+    "editorGutter.commentRangeForeground",
+    "editorGutter.commentUnresolvedGlyphForeground",
+    "editorGutter.deletedBackground",
+    "editorGutter.deletedSecondaryBackground",
+    "editorGutter.foldingControlForeground",
+    "editorGutter.itemBackground",
+    "editorGutter.itemGlyphForeground",
+    "editorGutter.modifiedBackground",
+    "editorGutter.modifiedSecondaryBackground",
+    "editorHint.border",
+    "editorHint.foreground",
+    "editorHoverWidget.background",
+    "editorHoverWidget.border",
+    "editorHoverWidget.foreground",
+    "editorHoverWidget.highlightForeground",
+    "editorHoverWidget.statusBarBackground",
+    "editorIndentGuide.activeBackground1",
+    "editorIndentGuide.activeBackground2",
+    "editorIndentGuide.activeBackground3",
+    "editorIndentGuide.activeBackground4",
+    "editorIndentGuide.activeBackground5",
+    "editorIndentGuide.activeBackground6",
+    "editorIndentGuide.background1",
+    "editorIndentGuide.background2",
+    "editorIndentGuide.background3",
+    "editorIndentGuide.background4",
+    "editorIndentGuide.background5",
+    "editorIndentGuide.background6",
+    "editorInfo.background",
+    "editorInfo.border",
+    "editorInfo.foreground",
+    "editorInlayHint.background",
+    "editorInlayHint.foreground",
+    "editorInlayHint.parameterBackground",
+    "editorInlayHint.parameterForeground",
+    "editorInlayHint.typeBackground",
+    "editorInlayHint.typeForeground",
+    "editorLightBulb.foreground",
+    "editorLightBulbAi.foreground",
+    "editorLightBulbAutoFix.foreground",
+    "editorLineNumber.activeForeground",
+    "editorLineNumber.dimmedForeground",
+    "editorLineNumber.foreground",
+    "editorLink.activeForeground",
+    "editorMarkerNavigation.background",
+    "editorMarkerNavigationError.background",
+    "editorMarkerNavigationError.headerBackground",
+    "editorMarkerNavigationInfo.background",
+    "editorMarkerNavigationInfo.headerBackground",
+    "editorMarkerNavigationWarning.background",
+    "editorMarkerNavigationWarning.headerBackground",
+    "editorMultiCursor.primary.background",
+    "editorMultiCursor.primary.foreground",
+    "editorMultiCursor.secondary.background",
+    "editorMultiCursor.secondary.foreground",
+    "editorOverviewRuler.addedForeground",
+    "editorOverviewRuler.background",
+    "editorOverviewRuler.border",
+    "editorOverviewRuler.bracketMatchForeground",
+    "editorOverviewRuler.commentForeground",
+    "editorOverviewRuler.commentUnresolvedForeground",
+    "editorOverviewRuler.commonContentForeground",
+    "editorOverviewRuler.currentContentForeground",
+    "editorOverviewRuler.deletedForeground",
+    "editorOverviewRuler.errorForeground",
+    "editorOverviewRuler.findMatchForeground",
+    "editorOverviewRuler.incomingContentForeground",
+    "editorOverviewRuler.infoForeground",
+    "editorOverviewRuler.inlineChatInserted",
+    "editorOverviewRuler.inlineChatRemoved",
+    "editorOverviewRuler.modifiedForeground",
+    "editorOverviewRuler.rangeHighlightForeground",
+    "editorOverviewRuler.selectionHighlightForeground",
+    "editorOverviewRuler.warningForeground",
+    "editorOverviewRuler.wordHighlightForeground",
+    "editorOverviewRuler.wordHighlightStrongForeground",
+    "editorOverviewRuler.wordHighlightTextForeground",
+    "editorPane.background",
+    "editorRuler.foreground",
+    "editorStickyScroll.background",
+    "editorStickyScroll.border",
+    "editorStickyScroll.shadow",
+    "editorStickyScrollGutter.background",
+    "editorStickyScrollHover.background",
+    "editorSuggestWidget.background",
+    "editorSuggestWidget.border",
+    "editorSuggestWidget.focusHighlightForeground",
+    "editorSuggestWidget.foreground",
+    "editorSuggestWidget.highlightForeground",
+    "editorSuggestWidget.selectedBackground",
+    "editorSuggestWidget.selectedForeground",
+    "editorSuggestWidget.selectedIconForeground",
+    "editorSuggestWidgetStatus.foreground",
+    "editorUnicodeHighlight.background",
+    "editorUnicodeHighlight.border",
+    "editorUnnecessaryCode.border",
+    "editorUnnecessaryCode.opacity",
+    "editorWarning.background",
+    "editorWarning.border",
+    "editorWarning.foreground",
+    "editorWatermark.foreground",
+    "editorWhitespace.foreground",
+    "editorWidget.background",
+    "editorWidget.border",
+    "editorWidget.foreground",
+    "editorWidget.resizeBorder",
+    "errorForeground",
+    "extensionBadge.remoteBackground",
+    "extensionBadge.remoteForeground",
+    "extensionButton.background",
+    "extensionButton.foreground",
+    "extensionButton.hoverBackground",
+    "extensionButton.prominentBackground",
+    "extensionButton.prominentForeground",
+    "extensionButton.prominentHoverBackground",
+    "extensionButton.separator",
+    "extensionIcon.preReleaseForeground",
+    "extensionIcon.privateForeground",
+    "extensionIcon.sponsorForeground",
+    "extensionIcon.starForeground",
+    "extensionIcon.verifiedForeground",
+    "focusBorder",
+    "foreground",
+    "gauge.background",
+    "gauge.border",
+    "gauge.errorBackground",
+    "gauge.errorForeground",
+    "gauge.foreground",
+    "gauge.warningBackground",
+    "gauge.warningForeground",
+    "gitDecoration.addedResourceForeground",
+    "gitDecoration.conflictingResourceForeground",
+    "gitDecoration.deletedResourceForeground",
+    "gitDecoration.ignoredResourceForeground",
+    "gitDecoration.modifiedResourceForeground",
+    "gitDecoration.renamedResourceForeground",
+    "gitDecoration.stageDeletedResourceForeground",
+    "gitDecoration.stageModifiedResourceForeground",
+    "gitDecoration.submoduleResourceForeground",
+    "gitDecoration.untrackedResourceForeground",
+    "icon.foreground",
+    "inlineChat.background",
+    "inlineChat.border",
+    "inlineChat.foreground",
+    "inlineChat.shadow",
+    "inlineChatDiff.inserted",
+    "inlineChatDiff.removed",
+    "inlineChatInput.background",
+    "inlineChatInput.border",
+    "inlineChatInput.focusBorder",
+    "inlineChatInput.placeholderForeground",
+    "inlineEdit.gutterIndicator.background",
+    "inlineEdit.gutterIndicator.primaryBackground",
+    "inlineEdit.gutterIndicator.primaryBorder",
+    "inlineEdit.gutterIndicator.primaryForeground",
+    "inlineEdit.gutterIndicator.secondaryBackground",
+    "inlineEdit.gutterIndicator.secondaryBorder",
+    "inlineEdit.gutterIndicator.secondaryForeground",
+    "inlineEdit.gutterIndicator.successfulBackground",
+    "inlineEdit.gutterIndicator.successfulBorder",
+    "inlineEdit.gutterIndicator.successfulForeground",
+    "inlineEdit.modifiedBackground",
+    "inlineEdit.modifiedBorder",
+    "inlineEdit.modifiedChangedLineBackground",
+    "inlineEdit.modifiedChangedTextBackground",
+    "inlineEdit.originalBackground",
+    "inlineEdit.originalBorder",
+    "inlineEdit.originalChangedLineBackground",
+    "inlineEdit.originalChangedTextBackground",
+    "inlineEdit.tabWillAcceptModifiedBorder",
+    "inlineEdit.tabWillAcceptOriginalBorder",
+    "input.background",
+    "input.border",
+    "input.foreground",
+    "input.placeholderForeground",
+    "inputOption.activeBackground",
+    "inputOption.activeBorder",
+    "inputOption.activeForeground",
+    "inputOption.hoverBackground",
+    "inputValidation.errorBackground",
+    "inputValidation.errorBorder",
+    "inputValidation.errorForeground",
+    "inputValidation.infoBackground",
+    "inputValidation.infoBorder",
+    "inputValidation.infoForeground",
+    "inputValidation.warningBackground",
+    "inputValidation.warningBorder",
+    "inputValidation.warningForeground",
+    "interactive.activeCodeBorder",
+    "interactive.inactiveCodeBorder",
+    "keybindingLabel.background",
+    "keybindingLabel.border",
+    "keybindingLabel.bottomBorder",
+    "keybindingLabel.foreground",
+    "keybindingTable.headerBackground",
+    "keybindingTable.rowsBackground",
+    "list.activeSelectionBackground",
+    "list.activeSelectionForeground",
+    "list.activeSelectionIconForeground",
+    "list.deemphasizedForeground",
+    "list.dropBackground",
+    "list.dropBetweenBackground",
+    "list.errorForeground",
+    "list.filterMatchBackground",
+    "list.filterMatchBorder",
+    "list.focusAndSelectionOutline",
+    "list.focusBackground",
+    "list.focusForeground",
+    "list.focusHighlightForeground",
+    "list.focusOutline",
+    "list.highlightForeground",
+    "list.hoverBackground",
+    "list.hoverForeground",
+    "list.inactiveFocusBackground",
+    "list.inactiveFocusOutline",
+    "list.inactiveSelectionBackground",
+    "list.inactiveSelectionForeground",
+    "list.inactiveSelectionIconForeground",
+    "list.invalidItemForeground",
+    "list.warningForeground",
+    "listFilterWidget.background",
+    "listFilterWidget.noMatchesOutline",
+    "listFilterWidget.outline",
+    "listFilterWidget.shadow",
+    "menu.background",
+    "menu.border",
+    "menu.foreground",
+    "menu.selectionBackground",
+    "menu.selectionBorder",
+    "menu.selectionForeground",
+    "menu.separatorBackground",
+    "menubar.selectionBackground",
+    "menubar.selectionBorder",
+    "menubar.selectionForeground",
+    "merge.border",
+    "merge.commonContentBackground",
+    "merge.commonHeaderBackground",
+    "merge.currentContentBackground",
+    "merge.currentHeaderBackground",
+    "merge.incomingContentBackground",
+    "merge.incomingHeaderBackground",
+    "mergeEditor.change.background",
+    "mergeEditor.change.word.background",
+    "mergeEditor.changeBase.background",
+    "mergeEditor.changeBase.word.background",
+    "mergeEditor.conflict.handled.minimapOverViewRuler",
+    "mergeEditor.conflict.handledFocused.border",
+    "mergeEditor.conflict.handledUnfocused.border",
+    "mergeEditor.conflict.input1.background",
+    "mergeEditor.conflict.input2.background",
+    "mergeEditor.conflict.unhandled.minimapOverViewRuler",
+    "mergeEditor.conflict.unhandledFocused.border",
+    "mergeEditor.conflict.unhandledUnfocused.border",
+    "mergeEditor.conflictingLines.background",
+    "minimap.background",
+    "minimap.chatEditHighlight",
+    "minimap.errorHighlight",
+    "minimap.findMatchHighlight",
+    "minimap.foregroundOpacity",
+    "minimap.infoHighlight",
+    "minimap.selectionHighlight",
+    "minimap.selectionOccurrenceHighlight",
+    "minimap.warningHighlight",
+    "minimapGutter.addedBackground",
+    "minimapGutter.deletedBackground",
+    "minimapGutter.modifiedBackground",
+    "minimapSlider.activeBackground",
+    "minimapSlider.background",
+    "minimapSlider.hoverBackground",
+    "multiDiffEditor.background",
+    "multiDiffEditor.border",
+    "multiDiffEditor.headerBackground",
+    "notebook.cellBorderColor",
+    "notebook.cellEditorBackground",
+    "notebook.cellHoverBackground",
+    "notebook.cellInsertionIndicator",
+    "notebook.cellStatusBarItemHoverBackground",
+    "notebook.cellToolbarSeparator",
+    "notebook.editorBackground",
+    "notebook.focusedCellBackground",
+    "notebook.focusedCellBorder",
+    "notebook.focusedEditorBorder",
+    "notebook.inactiveFocusedCellBorder",
+    "notebook.inactiveSelectedCellBorder",
+    "notebook.outputContainerBackgroundColor",
+    "notebook.outputContainerBorderColor",
+    "notebook.selectedCellBackground",
+    "notebook.selectedCellBorder",
+    "notebook.symbolHighlightBackground",
+    "notebookEditorOverviewRuler.runningCellForeground",
+    "notebookScrollbarSlider.activeBackground",
+    "notebookScrollbarSlider.background",
+    "notebookScrollbarSlider.hoverBackground",
+    "notebookStatusErrorIcon.foreground",
+    "notebookStatusRunningIcon.foreground",
+    "notebookStatusSuccessIcon.foreground",
+    "notificationCenter.border",
+    "notificationCenterHeader.background",
+    "notificationCenterHeader.foreground",
+    "notificationLink.foreground",
+    "notificationToast.border",
+    "notifications.background",
+    "notifications.border",
+    "notifications.foreground",
+    "notificationsErrorIcon.foreground",
+    "notificationsInfoIcon.foreground",
+    "notificationsWarningIcon.foreground",
+    "outputView.background",
+    "outputViewStickyScroll.background",
+    "panel.background",
+    "panel.border",
+    "panel.dropBorder",
+    "panelInput.border",
+    "panelSection.border",
+    "panelSection.dropBackground",
+    "panelSectionHeader.background",
+    "panelSectionHeader.border",
+    "panelSectionHeader.foreground",
+    "panelStickyScroll.background",
+    "panelStickyScroll.border",
+    "panelStickyScroll.shadow",
+    "panelTitle.activeBorder",
+    "panelTitle.activeForeground",
+    "panelTitle.border",
+    "panelTitle.inactiveForeground",
+    "panelTitleBadge.background",
+    "panelTitleBadge.foreground",
+    "peekView.border",
+    "peekViewEditor.background",
+    "peekViewEditor.matchHighlightBackground",
+    "peekViewEditor.matchHighlightBorder",
+    "peekViewEditorGutter.background",
+    "peekViewEditorStickyScroll.background",
+    "peekViewEditorStickyScrollGutter.background",
+    "peekViewResult.background",
+    "peekViewResult.fileForeground",
+    "peekViewResult.lineForeground",
+    "peekViewResult.matchHighlightBackground",
+    "peekViewResult.selectionBackground",
+    "peekViewResult.selectionForeground",
+    "peekViewTitle.background",
+    "peekViewTitleDescription.foreground",
+    "peekViewTitleLabel.foreground",
+    "pickerGroup.border",
+    "pickerGroup.foreground",
+    "ports.iconRunningProcessForeground",
+    "problemsErrorIcon.foreground",
+    "problemsInfoIcon.foreground",
+    "problemsWarningIcon.foreground",
+    "profileBadge.background",
+    "profileBadge.foreground",
+    "progressBar.background",
+    "quickInput.background",
+    "quickInput.foreground",
+    "quickInputList.focusBackground",
+    "quickInputList.focusForeground",
+    "quickInputList.focusIconForeground",
+    "quickInputTitle.background",
+    "radio.activeBackground",
+    "radio.activeBorder",
+    "radio.activeForeground",
+    "radio.inactiveBackground",
+    "radio.inactiveBorder",
+    "radio.inactiveForeground",
+    "radio.inactiveHoverBackground",
+    "sash.hoverBorder",
+    "scmGraph.foreground1",
+    "scmGraph.foreground2",
+    "scmGraph.foreground3",
+    "scmGraph.foreground4",
+    "scmGraph.foreground5",
+    "scmGraph.historyItemBaseRefColor",
+    "scmGraph.historyItemHoverAdditionsForeground",
+    "scmGraph.historyItemHoverDefaultLabelBackground",
+    "scmGraph.historyItemHoverDefaultLabelForeground",
+    "scmGraph.historyItemHoverDeletionsForeground",
+    "scmGraph.historyItemHoverLabelForeground",
+    "scmGraph.historyItemRefColor",
+    "scmGraph.historyItemRemoteRefColor",
+    "scrollbar.shadow",
+    "scrollbarSlider.activeBackground",
+    "scrollbarSlider.background",
+    "scrollbarSlider.hoverBackground",
+    "search.resultsInfoForeground",
+    "searchEditor.findMatchBackground",
+    "searchEditor.findMatchBorder",
+    "searchEditor.textInputBorder",
+    "selection.background",
+    "settings.checkboxBackground",
+    "settings.checkboxBorder",
+    "settings.checkboxForeground",
+    "settings.dropdownBackground",
+    "settings.dropdownBorder",
+    "settings.dropdownForeground",
+    "settings.dropdownListBorder",
+    "settings.focusedRowBackground",
+    "settings.focusedRowBorder",
+    "settings.headerBorder",
+    "settings.headerForeground",
+    "settings.modifiedItemIndicator",
+    "settings.numberInputBackground",
+    "settings.numberInputBorder",
+    "settings.numberInputForeground",
+    "settings.rowHoverBackground",
+    "settings.sashBorder",
+    "settings.settingsHeaderHoverForeground",
+    "settings.textInputBackground",
+    "settings.textInputBorder",
+    "settings.textInputForeground",
+    "sideBar.background",
+    "sideBar.border",
+    "sideBar.dropBackground",
+    "sideBar.foreground",
+    "sideBarActivityBarTop.border",
+    "sideBarSectionHeader.background",
+    "sideBarSectionHeader.border",
+    "sideBarSectionHeader.foreground",
+    "sideBarStickyScroll.background",
+    "sideBarStickyScroll.border",
+    "sideBarStickyScroll.shadow",
+    "sideBarTitle.background",
+    "sideBarTitle.border",
+    "sideBarTitle.foreground",
+    "sideBySideEditor.horizontalBorder",
+    "sideBySideEditor.verticalBorder",
+    "simpleFindWidget.sashBorder",
+    "statusBar.background",
+    "statusBar.border",
+    "statusBar.debuggingBackground",
+    "statusBar.debuggingBorder",
+    "statusBar.debuggingForeground",
+    "statusBar.focusBorder",
+    "statusBar.foreground",
+    "statusBar.noFolderBackground",
+    "statusBar.noFolderBorder",
+    "statusBar.noFolderForeground",
+    "statusBarItem.activeBackground",
+    "statusBarItem.compactHoverBackground",
+    "statusBarItem.errorBackground",
+    "statusBarItem.errorForeground",
+    "statusBarItem.errorHoverBackground",
+    "statusBarItem.errorHoverForeground",
+    "statusBarItem.focusBorder",
+    "statusBarItem.hoverBackground",
+    "statusBarItem.hoverForeground",
+    "statusBarItem.offlineBackground",
+    "statusBarItem.offlineForeground",
+    "statusBarItem.offlineHoverBackground",
+    "statusBarItem.offlineHoverForeground",
+    "statusBarItem.prominentBackground",
+    "statusBarItem.prominentForeground",
+    "statusBarItem.prominentHoverBackground",
+    "statusBarItem.prominentHoverForeground",
+    "statusBarItem.remoteBackground",
+    "statusBarItem.remoteForeground",
+    "statusBarItem.remoteHoverBackground",
+    "statusBarItem.remoteHoverForeground",
+    "statusBarItem.warningBackground",
+    "statusBarItem.warningForeground",
+    "statusBarItem.warningHoverBackground",
+    "statusBarItem.warningHoverForeground",
+    "symbolIcon.arrayForeground",
+    "symbolIcon.booleanForeground",
+    "symbolIcon.classForeground",
+    "symbolIcon.colorForeground",
+    "symbolIcon.constantForeground",
+    "symbolIcon.constructorForeground",
+    "symbolIcon.enumeratorForeground",
+    "symbolIcon.enumeratorMemberForeground",
+    "symbolIcon.eventForeground",
+    "symbolIcon.fieldForeground",
+    "symbolIcon.fileForeground",
+    "symbolIcon.folderForeground",
+    "symbolIcon.functionForeground",
+    "symbolIcon.interfaceForeground",
+    "symbolIcon.keyForeground",
+    "symbolIcon.keywordForeground",
+    "symbolIcon.methodForeground",
+    "symbolIcon.moduleForeground",
+    "symbolIcon.namespaceForeground",
+    "symbolIcon.nullForeground",
+    "symbolIcon.numberForeground",
+    "symbolIcon.objectForeground",
+    "symbolIcon.operatorForeground",
+    "symbolIcon.packageForeground",
+    "symbolIcon.propertyForeground",
+    "symbolIcon.referenceForeground",
+    "symbolIcon.snippetForeground",
+    "symbolIcon.stringForeground",
+    "symbolIcon.structForeground",
+    "symbolIcon.textForeground",
+    "symbolIcon.typeParameterForeground",
+    "symbolIcon.unitForeground",
+    "symbolIcon.variableForeground",
+    "tab.activeBackground",
+    "tab.activeBorder",
+    "tab.activeBorderTop",
+    "tab.activeForeground",
+    "tab.activeModifiedBorder",
+    "tab.border",
+    "tab.dragAndDropBorder",
+    "tab.hoverBackground",
+    "tab.hoverBorder",
+    "tab.hoverForeground",
+    "tab.inactiveBackground",
+    "tab.inactiveForeground",
+    "tab.inactiveModifiedBorder",
+    "tab.lastPinnedBorder",
+    "tab.selectedBackground",
+    "tab.selectedBorderTop",
+    "tab.selectedForeground",
+    "tab.unfocusedActiveBackground",
+    "tab.unfocusedActiveBorder",
+    "tab.unfocusedActiveBorderTop",
+    "tab.unfocusedActiveForeground",
+    "tab.unfocusedActiveModifiedBorder",
+    "tab.unfocusedHoverBackground",
+    "tab.unfocusedHoverBorder",
+    "tab.unfocusedHoverForeground",
+    "tab.unfocusedInactiveBackground",
+    "tab.unfocusedInactiveForeground",
+    "tab.unfocusedInactiveModifiedBorder",
+    "terminal.ansiBlack",
+    "terminal.ansiBlue",
+    "terminal.ansiBrightBlack",
+    "terminal.ansiBrightBlue",
+    "terminal.ansiBrightCyan",
+    "terminal.ansiBrightGreen",
+    "terminal.ansiBrightMagenta",
+    "terminal.ansiBrightRed",
+    "terminal.ansiBrightWhite",
+    "terminal.ansiBrightYellow",
+    "terminal.ansiCyan",
+    "terminal.ansiGreen",
+    "terminal.ansiMagenta",
+    "terminal.ansiRed",
+    "terminal.ansiWhite",
+    "terminal.ansiYellow",
+    "terminal.background",
+    "terminal.border",
+    "terminal.dropBackground",
+    "terminal.findMatchBackground",
+    "terminal.findMatchBorder",
+    "terminal.findMatchHighlightBackground",
+    "terminal.findMatchHighlightBorder",
+    "terminal.foreground",
+    "terminal.hoverHighlightBackground",
+    "terminal.inactiveSelectionBackground",
+    "terminal.initialHintForeground",
+    "terminal.selectionBackground",
+    "terminal.selectionForeground",
+    "terminal.tab.activeBorder",
+    "terminalCommandDecoration.defaultBackground",
+    "terminalCommandDecoration.errorBackground",
+    "terminalCommandDecoration.successBackground",
+    "terminalCommandGuide.foreground",
+    "terminalCursor.background",
+    "terminalCursor.foreground",
+    "terminalOverviewRuler.border",
+    "terminalOverviewRuler.cursorForeground",
+    "terminalOverviewRuler.findMatchForeground",
+    "terminalStickyScroll.background",
+    "terminalStickyScroll.border",
+    "terminalStickyScrollHover.background",
+    "terminalSymbolIcon.aliasForeground",
+    "terminalSymbolIcon.argumentForeground",
+    "terminalSymbolIcon.fileForeground",
+    "terminalSymbolIcon.flagForeground",
+    "terminalSymbolIcon.folderForeground",
+    "terminalSymbolIcon.inlineSuggestionForeground",
+    "terminalSymbolIcon.methodForeground",
+    "terminalSymbolIcon.optionForeground",
+    "terminalSymbolIcon.optionValueForeground",
+    "testing.coverCountBadgeBackground",
+    "testing.coverCountBadgeForeground",
+    "testing.coveredBackground",
+    "testing.coveredBorder",
+    "testing.coveredGutterBackground",
+    "testing.iconErrored",
+    "testing.iconErrored.retired",
+    "testing.iconFailed",
+    "testing.iconFailed.retired",
+    "testing.iconPassed",
+    "testing.iconPassed.retired",
+    "testing.iconQueued",
+    "testing.iconQueued.retired",
+    "testing.iconSkipped",
+    "testing.iconSkipped.retired",
+    "testing.iconUnset",
+    "testing.iconUnset.retired",
+    "testing.message.error.decorationForeground",
+    "testing.message.error.lineBackground",
+    "testing.message.info.decorationForeground",
+    "testing.message.info.lineBackground",
+    "testing.messagePeekBorder",
+    "testing.messagePeekHeaderBackground",
+    "testing.peekBorder",
+    "testing.peekHeaderBackground",
+    "testing.runAction",
+    "testing.uncoveredBackground",
+    "testing.uncoveredBorder",
+    "testing.uncoveredBranchBackground",
+    "testing.uncoveredGutterBackground",
+    "textBlockQuote.background",
+    "textBlockQuote.border",
+    "textCodeBlock.background",
+    "textLink.activeForeground",
+    "textLink.foreground",
+    "textPreformat.background",
+    "textPreformat.foreground",
+    "textSeparator.foreground",
+    "titleBar.activeBackground",
+    "titleBar.activeForeground",
+    "titleBar.border",
+    "titleBar.inactiveBackground",
+    "titleBar.inactiveForeground",
+    "toolbar.activeBackground",
+    "toolbar.hoverBackground",
+    "toolbar.hoverOutline",
+    "tree.inactiveIndentGuidesStroke",
+    "tree.indentGuidesStroke",
+    "tree.tableColumnsBorder",
+    "tree.tableOddRowsBackground",
+    "walkThrough.embeddedEditorBackground",
+    "walkthrough.stepTitle.foreground",
+    "welcomePage.background",
+    "welcomePage.progress.background",
+    "welcomePage.progress.foreground",
+    "welcomePage.tileBackground",
+    "welcomePage.tileBorder",
+    "welcomePage.tileHoverBackground",
+    "widget.border",
+    "widget.shadow",
+    "window.activeBorder",
+    "window.inactiveBorder",
+];
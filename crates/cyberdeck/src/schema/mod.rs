@@ -0,0 +1,449 @@
+//! Validation of theme files against VS Code's color-theme schema, with
+//! line and column positions from the JSONC tree.
+//!
+//! [`Theme::parse`](crate::theme::Theme::parse) accepts anything shaped
+//! roughly like a theme and skips what it doesn't understand. This reports
+//! what VS Code would reject or silently ignore instead: malformed colors,
+//! invalid `fontStyle`s and wrongly typed values are errors; unknown
+//! properties, unknown color ids and duplicate keys are warnings.
+
+mod colors;
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::jsonc::{self, Node};
+use crate::lint::Severity;
+
+pub use colors::{Deprecation, COLOR_IDS, DEPRECATED_COLOR_IDS};
+
+/// Values of the top-level `type` property.
+const THEME_TYPES: &[&str] = &["dark", "light", "hcDark", "hcLight"];
+
+/// Words a `fontStyle` may combine.
+const FONT_STYLES: &[&str] = &["italic", "bold", "underline", "strikethrough"];
+
+/// Boolean style flags a semantic token rule may set.
+const SEMANTIC_FLAGS: &[&str] = &["bold", "italic", "underline", "strikethrough"];
+
+/// One problem, at the position VS Code's editor would underline.
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// 1-based line and column in the file.
+    pub line: usize,
+    pub column: usize,
+    /// Where in the document, e.g. `tokenColors[3].settings.fontStyle`;
+    /// empty for the root.
+    pub path: String,
+    pub message: String,
+}
+
+/// Checks theme JSONC `text` against the color-theme schema. Color ids
+/// deprecated as of VS Code `target` (`(major, minor)`, or any version if
+/// `None`) are flagged. A file that doesn't parse gets a single error at the
+/// syntax error.
+pub fn validate(text: &str, target: Option<(u32, u32)>) -> Vec<Diagnostic> {
+    let root = match jsonc::parse_tree(text) {
+        Ok(root) => root,
+        Err(e) => {
+            return vec![Diagnostic {
+                severity: Severity::Error,
+                line: e.line,
+                column: e.column,
+                path: String::new(),
+                message: e.message,
+            }];
+        }
+    };
+    let mut validator = Validator {
+        text,
+        target,
+        diagnostics: Vec::new(),
+    };
+    validator.theme(&root, text.len() - text.trim_start().len());
+    let mut diagnostics = validator.diagnostics;
+    diagnostics.sort_by_key(|d| (d.line, d.column));
+    diagnostics
+}
+
+struct Validator<'a> {
+    text: &'a str,
+    target: Option<(u32, u32)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, severity: Severity, offset: usize, path: &str, message: String) {
+        let (line, column) = jsonc::line_column(self.text, offset);
+        self.diagnostics.push(Diagnostic {
+            severity,
+            line,
+            column,
+            path: path.to_owned(),
+            message,
+        });
+    }
+
+    /// Reports that the value at `offset` should be `expected`, unless it is.
+    fn expect(&mut self, ok: bool, node: &Node, offset: usize, path: &str, expected: &str) {
+        if !ok {
+            self.report(
+                Severity::Error,
+                offset,
+                path,
+                format!("expected {expected}, found {}", kind(node)),
+            );
+        }
+    }
+
+    /// The members of an object, reporting duplicate keys, or `None` after
+    /// reporting that it isn't one.
+    fn object<'n>(
+        &mut self,
+        node: &'n Node,
+        offset: usize,
+        path: &str,
+    ) -> Option<&'n [jsonc::Member]> {
+        let Node::Object { members, .. } = node else {
+            self.expect(false, node, offset, path, "an object");
+            return None;
+        };
+        let mut seen = HashSet::new();
+        for member in members {
+            if !seen.insert(member.key.as_str()) {
+                self.report(
+                    Severity::Warn,
+                    member.key_span.start,
+                    &join(path, &member.key),
+                    format!("duplicate key `{}`; only the last one is used", member.key),
+                );
+            }
+        }
+        Some(members)
+    }
+
+    fn string(&mut self, node: &Node, offset: usize, path: &str) {
+        self.expect(node.as_str().is_some(), node, offset, path, "a string");
+    }
+
+    fn boolean(&mut self, node: &Node, offset: usize, path: &str) {
+        let ok = matches!(node, Node::Scalar(raw) if raw == "true" || raw == "false");
+        self.expect(ok, node, offset, path, "a boolean");
+    }
+
+    fn color(&mut self, node: &Node, offset: usize, path: &str) {
+        let Some(value) = node.as_str() else {
+            self.expect(false, node, offset, path, "a color string");
+            return;
+        };
+        if !is_color(value) {
+            self.report(
+                Severity::Error,
+                offset,
+                path,
+                format!("malformed color `{value}`; expected #RGB, #RGBA, #RRGGBB or #RRGGBBAA"),
+            );
+        }
+    }
+
+    fn font_style(&mut self, node: &Node, offset: usize, path: &str) {
+        let Some(value) = node.as_str() else {
+            self.expect(false, node, offset, path, "a string");
+            return;
+        };
+        for word in value.split_whitespace() {
+            if !FONT_STYLES.contains(&word) {
+                self.report(
+                    Severity::Error,
+                    offset,
+                    path,
+                    format!(
+                        "invalid fontStyle `{word}`; expected a combination of {} or an empty string",
+                        FONT_STYLES.join(", ")
+                    ),
+                );
+            }
+        }
+    }
+
+    fn unknown(&mut self, member: &jsonc::Member, path: &str, what: &str) {
+        self.report(
+            Severity::Warn,
+            member.key_span.start,
+            &join(path, &member.key),
+            format!("unknown {what} `{}`", member.key),
+        );
+    }
+
+    fn theme(&mut self, root: &Node, offset: usize) {
+        let Some(members) = self.object(root, offset, "") else {
+            return;
+        };
+        for member in members {
+            let (value, at, path) = (&member.value, member.value_span.start, member.key.as_str());
+            match path {
+                "$schema" | "name" | "include" => self.string(value, at, path),
+                "type" => match value.as_str() {
+                    Some(kind) if !THEME_TYPES.contains(&kind) => self.report(
+                        Severity::Error,
+                        at,
+                        path,
+                        format!(
+                            "invalid type `{kind}`; expected one of {}",
+                            THEME_TYPES.join(", ")
+                        ),
+                    ),
+                    _ => self.string(value, at, path),
+                },
+                "semanticHighlighting" => self.boolean(value, at, path),
+                "colors" => self.colors(value, at),
+                "tokenColors" => self.token_colors(value, at),
+                "semanticTokenColors" => self.semantic_token_colors(value, at),
+                _ => self.unknown(member, "", "property"),
+            }
+        }
+    }
+
+    fn colors(&mut self, node: &Node, offset: usize) {
+        let Some(members) = self.object(node, offset, "colors") else {
+            return;
+        };
+        for member in members {
+            let path = join("colors", &member.key);
+            let message = color_id_problem(&member.key, self.target);
+            if let Some(message) = message {
+                self.report(Severity::Warn, member.key_span.start, &path, message);
+            }
+            self.color(&member.value, member.value_span.start, &path);
+        }
+    }
+
+    fn token_colors(&mut self, node: &Node, offset: usize) {
+        // A string names a TextMate theme file to take the rules from.
+        if node.as_str().is_some() {
+            return;
+        }
+        let Node::Array { elements, .. } = node else {
+            self.expect(
+                false,
+                node,
+                offset,
+                "tokenColors",
+                "an array of rules or a file path",
+            );
+            return;
+        };
+        for (index, element) in elements.iter().enumerate() {
+            self.token_rule(
+                &element.value,
+                element.span.start,
+                &format!("tokenColors[{index}]"),
+            );
+        }
+    }
+
+    fn token_rule(&mut self, node: &Node, offset: usize, path: &str) {
+        let Some(members) = self.object(node, offset, path) else {
+            return;
+        };
+        if !members.iter().any(|m| m.key == "settings") {
+            self.report(
+                Severity::Error,
+                offset,
+                path,
+                "rule has no `settings`".to_owned(),
+            );
+        }
+        for member in members {
+            let (value, at, member_path) = (
+                &member.value,
+                member.value_span.start,
+                join(path, &member.key),
+            );
+            match member.key.as_str() {
+                "name" => self.string(value, at, &member_path),
+                "scope" => self.scope(value, at, &member_path),
+                "settings" => self.settings(value, at, &member_path),
+                _ => self.unknown(member, path, "rule property"),
+            }
+        }
+    }
+
+    fn scope(&mut self, node: &Node, offset: usize, path: &str) {
+        match node {
+            Node::String(_) => {}
+            Node::Array { elements, .. } => {
+                for (index, element) in elements.iter().enumerate() {
+                    self.string(
+                        &element.value,
+                        element.span.start,
+                        &format!("{path}[{index}]"),
+                    );
+                }
+            }
+            _ => {
+                self.expect(false, node, offset, path, "a string or an array of strings");
+            }
+        }
+    }
+
+    fn settings(&mut self, node: &Node, offset: usize, path: &str) {
+        let Some(members) = self.object(node, offset, path) else {
+            return;
+        };
+        for member in members {
+            let (value, at, member_path) = (
+                &member.value,
+                member.value_span.start,
+                join(path, &member.key),
+            );
+            match member.key.as_str() {
+                "foreground" | "background" => self.color(value, at, &member_path),
+                "fontStyle" => self.font_style(value, at, &member_path),
+                _ => self.unknown(member, path, "setting"),
+            }
+        }
+    }
+
+    fn semantic_token_colors(&mut self, node: &Node, offset: usize) {
+        let Some(members) = self.object(node, offset, "semanticTokenColors") else {
+            return;
+        };
+        for member in members {
+            let (value, at, path) = (
+                &member.value,
+                member.value_span.start,
+                join("semanticTokenColors", &member.key),
+            );
+            // Either a bare foreground color or a style object.
+            if value.as_str().is_some() {
+                self.color(value, at, &path);
+                continue;
+            }
+            let Some(style) = self.object(value, at, &path) else {
+                continue;
+            };
+            for member in style {
+                let (value, at, member_path) = (
+                    &member.value,
+                    member.value_span.start,
+                    join(&path, &member.key),
+                );
+                match member.key.as_str() {
+                    "foreground" | "background" => self.color(value, at, &member_path),
+                    "fontStyle" => self.font_style(value, at, &member_path),
+                    flag if SEMANTIC_FLAGS.contains(&flag) => self.boolean(value, at, &member_path),
+                    _ => self.unknown(member, &path, "style property"),
+                }
+            }
+        }
+    }
+}
+
+/// Why VS Code would ignore the `colors` key `id` as of `target` (any
+/// version if `None`): it's deprecated, or not a color id at all, with the
+/// likely intended one. `None` if the id is fine.
+pub fn color_id_problem(id: &str, target: Option<(u32, u32)>) -> Option<String> {
+    if let Some(d) = DEPRECATED_COLOR_IDS.iter().find(|d| d.id == id) {
+        if target.is_some_and(|target| target < d.since) {
+            return None;
+        }
+        let message = format!(
+            "color id `{id}` is deprecated since VS Code {}.{}",
+            d.since.0, d.since.1
+        );
+        return Some(match d.replacement {
+            Some(replacement) => format!("{message}; use `{replacement}`"),
+            None => format!("{message} and has no effect"),
+        });
+    }
+    if COLOR_IDS.binary_search(&id).is_ok() {
+        return None;
+    }
+    Some(match closest(id, COLOR_IDS) {
+        Some(candidate) => format!("unknown color id `{id}`; did you mean `{candidate}`?"),
+        None => format!("unknown color id `{id}`"),
+    })
+}
+
+/// Whether `value` is a hex color in one of the forms VS Code accepts.
+fn is_color(value: &str) -> bool {
+    value.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 4 | 6 | 8) && hex.bytes().all(|b| b.is_ascii_hexdigit())
+    })
+}
+
+/// How a value reads in "expected X, found Y".
+fn kind(node: &Node) -> &'static str {
+    match node {
+        Node::String(_) => "a string",
+        Node::Array { .. } => "an array",
+        Node::Object { .. } => "an object",
+        Node::Scalar(raw) if raw == "true" || raw == "false" => "a boolean",
+        Node::Scalar(raw) if raw == "null" => "null",
+        Node::Scalar(_) => "a number",
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// The candidate nearest `key`, for typo suggestions: one differing only
+/// in case, else the closest within a couple of edits (more for long ids).
+fn closest<'a>(key: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    if let Some(candidate) = candidates.iter().find(|c| c.eq_ignore_ascii_case(key)) {
+        return Some(candidate);
+    }
+    let limit = (key.chars().count() / 10).max(2);
+    candidates
+        .iter()
+        .map(|candidate| (distance(key, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The `(major, minor)` of the oldest VS Code an `engines.vscode` range
+/// such as `^1.104.0` admits.
+pub fn engine_version(range: &str) -> Option<(u32, u32)> {
+    let mut parts = range
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Levenshtein distance, in characters.
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}: ", self.line, self.column, self.severity)?;
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        f.write_str(&self.message)
+    }
+}
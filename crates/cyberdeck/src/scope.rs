@@ -0,0 +1,237 @@
+//! TextMate scope selectors and theme rule resolution, following the
+//! matching rules VS Code applies to `tokenColors`.
+//!
+//! A selector such as `source.rust meta.attribute string` matches a token
+//! whose innermost scope starts with `string` and whose ancestors include,
+//! in order, scopes starting with `meta.attribute` and `source.rust`. When
+//! several rules match, the one whose last selector part has more segments
+//! wins, then the one with more parent parts, then the later rule.
+
+use crate::color::Color;
+use crate::theme::Theme;
+
+/// One space-separated selector, e.g. `source.go storage.type`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    parts: Vec<String>,
+}
+
+/// Ordering key of a successful match; larger is more specific.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Specificity {
+    pub depth: usize,
+    pub parents: usize,
+    pub rule: usize,
+}
+
+/// The style a scope stack resolves to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolvedStyle {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    /// Space-separated `italic`, `bold`, `underline`, `strikethrough`.
+    pub font_style: Option<String>,
+}
+
+impl Selector {
+    pub fn parse(text: &str) -> Option<Selector> {
+        let parts: Vec<String> = text.split_whitespace().map(str::to_owned).collect();
+        // Negative selectors (`a - b`) are rare in themes and not supported.
+        if parts.is_empty() || parts.iter().any(|p| p == "-") {
+            return None;
+        }
+        Some(Selector { parts })
+    }
+
+    pub fn parts(&self) -> &[String] {
+        &self.parts
+    }
+
+    /// Matches against a scope stack ordered outermost first.
+    pub fn matches(&self, stack: &[&str]) -> Option<(usize, usize)> {
+        let (target, parents) = self.parts.split_last()?;
+        let (innermost, ancestors) = stack.split_last()?;
+        if !scope_prefix(target, innermost) {
+            return None;
+        }
+        let mut ancestors = ancestors.iter().rev();
+        for parent in parents.iter().rev() {
+            ancestors.find(|scope| scope_prefix(parent, scope))?;
+        }
+        Some((target.split('.').count(), parents.len()))
+    }
+}
+
+impl std::fmt::Display for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.parts.join(" "))
+    }
+}
+
+/// Whether `prefix` equals `scope` or is a dot-segment prefix of it.
+pub fn scope_prefix(prefix: &str, scope: &str) -> bool {
+    scope == prefix
+        || (scope.starts_with(prefix) && scope.as_bytes().get(prefix.len()) == Some(&b'.'))
+}
+
+/// A theme's `tokenColors` with selectors and colors parsed once, for
+/// resolving many scope stacks.
+#[derive(Debug, Clone)]
+pub struct Resolver {
+    rules: Vec<CompiledRule>,
+    defaults: ResolvedStyle,
+}
+
+/// The rule that supplies a resolved foreground.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleMatch<'a> {
+    /// Index among the theme's scoped `tokenColors` rules.
+    pub rule: usize,
+    pub selector: &'a Selector,
+    pub specificity: Specificity,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    selectors: Vec<Selector>,
+    style: ResolvedStyle,
+}
+
+impl Resolver {
+    pub fn new(theme: &Theme) -> Resolver {
+        let mut defaults = ResolvedStyle::default();
+        let mut rules = Vec::new();
+        for rule in &theme.token_colors {
+            let settings = &rule.settings;
+            let style = ResolvedStyle {
+                foreground: settings.foreground.as_deref().and_then(|c| c.parse().ok()),
+                background: settings.background.as_deref().and_then(|c| c.parse().ok()),
+                font_style: settings.font_style.clone(),
+            };
+            if rule.scope.is_empty() {
+                defaults = style;
+                continue;
+            }
+            rules.push(CompiledRule {
+                selectors: rule
+                    .scope
+                    .selectors()
+                    .into_iter()
+                    .filter_map(Selector::parse)
+                    .collect(),
+                style,
+            });
+        }
+        let workbench = |key: &str| theme.colors.get(key).and_then(|c| c.parse().ok());
+        defaults.foreground = defaults
+            .foreground
+            .or_else(|| workbench("editor.foreground"));
+        defaults.background = defaults
+            .background
+            .or_else(|| workbench("editor.background"));
+        Resolver { rules, defaults }
+    }
+
+    /// The style from scope-less `tokenColors` rules, falling back to the
+    /// editor's workbench colors.
+    pub fn defaults(&self) -> &ResolvedStyle {
+        &self.defaults
+    }
+
+    /// Resolves the style for a scope stack (outermost first). Unset
+    /// properties fall back to [`Resolver::defaults`].
+    pub fn resolve<S: AsRef<str>>(&self, stack: &[S]) -> ResolvedStyle {
+        let matched = self.resolve_matched(stack);
+        ResolvedStyle {
+            foreground: matched.foreground.or(self.defaults.foreground),
+            background: matched.background.or(self.defaults.background),
+            font_style: matched
+                .font_style
+                .or_else(|| self.defaults.font_style.clone()),
+        }
+    }
+
+    /// Like [`Resolver::resolve`], but only considers rules whose selectors
+    /// match.
+    pub fn resolve_matched<S: AsRef<str>>(&self, stack: &[S]) -> ResolvedStyle {
+        let stack: Vec<&str> = stack.iter().map(AsRef::as_ref).collect();
+        let mut best_fg: Option<(Specificity, Color)> = None;
+        let mut best_bg: Option<(Specificity, Color)> = None;
+        let mut best_style: Option<(Specificity, &String)> = None;
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            let Some((specificity, _)) = self.match_rule(index, &stack) else {
+                continue;
+            };
+            let style = &rule.style;
+            if let Some(color) = style.foreground {
+                if beats(specificity, &best_fg) {
+                    best_fg = Some((specificity, color));
+                }
+            }
+            if let Some(color) = style.background {
+                if beats(specificity, &best_bg) {
+                    best_bg = Some((specificity, color));
+                }
+            }
+            if let Some(font_style) = &style.font_style {
+                if beats(specificity, &best_style) {
+                    best_style = Some((specificity, font_style));
+                }
+            }
+        }
+
+        ResolvedStyle {
+            foreground: best_fg.map(|(_, c)| c),
+            background: best_bg.map(|(_, c)| c),
+            font_style: best_style.map(|(_, s)| s.clone()),
+        }
+    }
+
+    /// The rule and selector that decide the foreground of `stack`.
+    pub fn foreground_match<S: AsRef<str>>(&self, stack: &[S]) -> Option<RuleMatch<'_>> {
+        let stack: Vec<&str> = stack.iter().map(AsRef::as_ref).collect();
+        (0..self.rules.len())
+            .filter(|&index| self.rules[index].style.foreground.is_some())
+            .filter_map(|index| self.match_rule(index, &stack))
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(specificity, selector)| RuleMatch {
+                rule: specificity.rule,
+                selector,
+                specificity,
+            })
+    }
+
+    /// How specifically the `index`th scoped rule matches `stack`, if at
+    /// all, and through which of its selectors.
+    fn match_rule(&self, index: usize, stack: &[&str]) -> Option<(Specificity, &Selector)> {
+        self.rules[index]
+            .selectors
+            .iter()
+            .filter_map(|s| s.matches(stack).map(|m| (m, s)))
+            .max_by_key(|(m, _)| *m)
+            .map(|((depth, parents), selector)| {
+                let specificity = Specificity {
+                    depth,
+                    parents,
+                    rule: index,
+                };
+                (specificity, selector)
+            })
+    }
+}
+
+/// Resolves one scope stack against `theme`; see [`Resolver::resolve`].
+pub fn resolve(theme: &Theme, stack: &[&str]) -> ResolvedStyle {
+    Resolver::new(theme).resolve(stack)
+}
+
+/// Resolves one scope stack considering only matching rules; see
+/// [`Resolver::resolve_matched`].
+pub fn resolve_matched(theme: &Theme, stack: &[&str]) -> ResolvedStyle {
+    Resolver::new(theme).resolve_matched(stack)
+}
+
+fn beats<T>(specificity: Specificity, best: &Option<(Specificity, T)>) -> bool {
+    best.as_ref().is_none_or(|(s, _)| specificity > *s)
+}
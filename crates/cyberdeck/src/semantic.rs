@@ -0,0 +1,415 @@
+//! Semantic token legends of the language servers the theme is tuned for,
+//! and whether the theme styles each token type and modifier they emit.
+//!
+//! VS Code styles a semantic token from the most specific matching
+//! `semanticTokenColors` selector (`type.modifier:language`, with `*` for
+//! any type), falling back to the token type's supertype and then to the
+//! TextMate scopes it maps standard types to, resolved through
+//! `tokenColors`. A token none of those reach gets the default foreground.
+
+use crate::scope::Resolver;
+use crate::theme::Theme;
+
+/// The token types and modifiers one language server emits.
+#[derive(Debug, Clone, Copy)]
+pub struct Legend {
+    pub server: &'static str,
+    /// The VS Code language id its tokens are for.
+    pub language: &'static str,
+    /// Token types, with the supertype the server's extension declares for
+    /// the non-standard ones.
+    pub types: &'static [(&'static str, Option<&'static str>)],
+    pub modifiers: &'static [&'static str],
+}
+
+/// The LSP specification's standard token types.
+const STANDARD_TYPES: &[(&str, Option<&str>)] = &[
+    ("namespace", None),
+    ("type", None),
+    ("class", None),
+    ("enum", None),
+    ("interface", None),
+    ("struct", None),
+    ("typeParameter", None),
+    ("parameter", None),
+    ("variable", None),
+    ("property", None),
+    ("enumMember", None),
+    ("event", None),
+    ("function", None),
+    ("method", None),
+    ("macro", None),
+    ("keyword", None),
+    ("modifier", None),
+    ("comment", None),
+    ("string", None),
+    ("number", None),
+    ("regexp", None),
+    ("operator", None),
+    ("decorator", None),
+];
+
+/// The language servers whose tokens the theme is checked against.
+pub const LEGENDS: &[Legend] = &[
+    Legend {
+        server: "rust-analyzer",
+        language: "rust",
+        types: &[
+            ("namespace", None),
+            ("type", None),
+            ("enum", None),
+            ("interface", None),
+            ("struct", None),
+            ("typeParameter", None),
+            ("parameter", None),
+            ("variable", None),
+            ("property", None),
+            ("enumMember", None),
+            ("function", None),
+            ("method", None),
+            ("macro", None),
+            ("keyword", None),
+            ("comment", None),
+            ("string", None),
+            ("number", None),
+            ("operator", None),
+            ("decorator", None),
+            ("angle", Some("punctuation")),
+            ("arithmetic", Some("operator")),
+            ("attribute", None),
+            ("attributeBracket", Some("punctuation")),
+            ("bitwise", Some("operator")),
+            ("boolean", None),
+            ("brace", Some("punctuation")),
+            ("bracket", Some("punctuation")),
+            ("builtinAttribute", Some("attribute")),
+            ("builtinType", None),
+            ("character", Some("string")),
+            ("colon", Some("punctuation")),
+            ("comma", Some("punctuation")),
+            ("comparison", Some("operator")),
+            ("const", None),
+            ("constParameter", None),
+            ("derive", Some("attribute")),
+            ("deriveHelper", Some("attribute")),
+            ("dot", Some("punctuation")),
+            ("escapeSequence", Some("string")),
+            ("formatSpecifier", Some("punctuation")),
+            ("invalidEscapeSequence", None),
+            ("label", None),
+            ("lifetime", None),
+            ("logical", Some("operator")),
+            ("macroBang", Some("macro")),
+            ("parenthesis", Some("punctuation")),
+            ("procMacro", None),
+            ("punctuation", None),
+            ("selfKeyword", Some("keyword")),
+            ("selfTypeKeyword", Some("keyword")),
+            ("semicolon", Some("punctuation")),
+            ("static", None),
+            ("toolModule", Some("decorator")),
+            ("typeAlias", None),
+            ("union", None),
+            ("unresolvedReference", None),
+        ],
+        modifiers: &[
+            "declaration",
+            "definition",
+            "documentation",
+            "readonly",
+            "static",
+            "defaultLibrary",
+            "deprecated",
+            "async",
+            "associated",
+            "attribute",
+            "callable",
+            "constant",
+            "consuming",
+            "controlFlow",
+            "crateRoot",
+            "injected",
+            "intraDocLink",
+            "library",
+            "macro",
+            "mutable",
+            "procMacro",
+            "public",
+            "reference",
+            "trait",
+            "unsafe",
+        ],
+    },
+    Legend {
+        server: "typescript-language-features",
+        language: "typescript",
+        types: &[
+            ("class", None),
+            ("enum", None),
+            ("interface", None),
+            ("namespace", None),
+            ("typeParameter", None),
+            ("type", None),
+            ("parameter", None),
+            ("variable", None),
+            ("enumMember", None),
+            ("property", None),
+            ("function", None),
+            ("method", None),
+        ],
+        modifiers: &[
+            "declaration",
+            "static",
+            "async",
+            "readonly",
+            "defaultLibrary",
+            "local",
+        ],
+    },
+    Legend {
+        server: "pylance",
+        language: "python",
+        types: &[
+            ("namespace", None),
+            ("class", None),
+            ("enum", None),
+            ("typeParameter", None),
+            ("type", None),
+            ("parameter", None),
+            ("variable", None),
+            ("property", None),
+            ("enumMember", None),
+            ("function", None),
+            ("method", None),
+            ("decorator", None),
+            ("module", Some("namespace")),
+            ("selfParameter", Some("parameter")),
+            ("clsParameter", Some("parameter")),
+            ("magicFunction", Some("function")),
+            ("builtinConstant", Some("variable")),
+        ],
+        modifiers: &[
+            "declaration",
+            "readonly",
+            "static",
+            "async",
+            "defaultLibrary",
+            "builtin",
+            "typeHint",
+            "typeHintComment",
+        ],
+    },
+    Legend {
+        server: "gopls",
+        language: "go",
+        types: STANDARD_TYPES,
+        modifiers: &[
+            "declaration",
+            "definition",
+            "readonly",
+            "static",
+            "deprecated",
+            "abstract",
+            "async",
+            "modification",
+            "documentation",
+            "defaultLibrary",
+            "array",
+            "bool",
+            "chan",
+            "format",
+            "interface",
+            "map",
+            "number",
+            "pointer",
+            "signature",
+            "slice",
+            "string",
+            "struct",
+        ],
+    },
+    Legend {
+        server: "clangd",
+        language: "cpp",
+        types: &[
+            ("variable", None),
+            ("parameter", None),
+            ("function", None),
+            ("method", None),
+            ("property", None),
+            ("class", None),
+            ("interface", None),
+            ("enum", None),
+            ("enumMember", None),
+            ("type", None),
+            ("unknown", None),
+            ("namespace", None),
+            ("typeParameter", None),
+            ("concept", None),
+            ("macro", None),
+            ("modifier", None),
+            ("operator", None),
+            ("bracket", None),
+            ("label", None),
+            ("comment", None),
+        ],
+        modifiers: &[
+            "declaration",
+            "definition",
+            "deprecated",
+            "deduced",
+            "readonly",
+            "static",
+            "abstract",
+            "virtual",
+            "dependentName",
+            "defaultLibrary",
+            "usedAsMutableReference",
+            "usedAsMutablePointer",
+            "constructorOrDestructor",
+            "userDefined",
+            "functionScope",
+            "classScope",
+            "fileScope",
+            "globalScope",
+        ],
+    },
+];
+
+/// The TextMate scopes VS Code styles standard token types (and some
+/// type-modifier pairs) with when no semantic rule applies.
+const FALLBACK_SCOPES: &[(&str, &[&str])] = &[
+    ("comment", &["comment"]),
+    ("string", &["string"]),
+    ("keyword", &["keyword.control"]),
+    ("number", &["constant.numeric"]),
+    ("regexp", &["constant.regexp"]),
+    ("operator", &["keyword.operator"]),
+    ("namespace", &["entity.name.namespace"]),
+    ("type", &["entity.name.type", "support.type"]),
+    ("struct", &["entity.name.type.struct"]),
+    ("class", &["entity.name.type.class", "support.class"]),
+    ("interface", &["entity.name.type.interface"]),
+    ("enum", &["entity.name.type.enum"]),
+    ("typeParameter", &["entity.name.type.parameter"]),
+    ("function", &["entity.name.function", "support.function"]),
+    (
+        "method",
+        &["entity.name.function.member", "support.function"],
+    ),
+    ("macro", &["entity.name.function.preprocessor"]),
+    (
+        "variable",
+        &["variable.other.readwrite", "entity.name.variable"],
+    ),
+    ("parameter", &["variable.parameter"]),
+    ("property", &["variable.other.property"]),
+    ("enumMember", &["variable.other.enummember"]),
+    ("event", &["variable.other.event"]),
+    (
+        "decorator",
+        &["entity.name.decorator", "entity.name.function"],
+    ),
+    ("variable.readonly", &["variable.other.constant"]),
+    ("property.readonly", &["variable.other.constant.property"]),
+    ("type.defaultLibrary", &["support.type"]),
+    ("class.defaultLibrary", &["support.class"]),
+    ("interface.defaultLibrary", &["support.class"]),
+    (
+        "variable.defaultLibrary",
+        &["support.variable", "support.other.variable"],
+    ),
+    ("variable.readonly.defaultLibrary", &["support.constant"]),
+    ("property.defaultLibrary", &["support.variable.property"]),
+    (
+        "property.readonly.defaultLibrary",
+        &["support.constant.property"],
+    ),
+    ("function.defaultLibrary", &["support.function"]),
+    ("member.defaultLibrary", &["support.function"]),
+];
+
+/// A `semanticTokenColors` selector, split into its parts.
+struct Selector<'a> {
+    token_type: &'a str,
+    modifiers: Vec<&'a str>,
+    language: Option<&'a str>,
+}
+
+fn parse_selector(selector: &str) -> Selector<'_> {
+    let (rest, language) = match selector.split_once(':') {
+        Some((rest, language)) => (rest, Some(language)),
+        None => (selector, None),
+    };
+    let mut parts = rest.split('.');
+    Selector {
+        token_type: parts.next().unwrap_or_default(),
+        modifiers: parts.collect(),
+        language,
+    }
+}
+
+impl Legend {
+    /// `token_type` and the supertypes it falls back to, nearest first.
+    fn lineage(&self, token_type: &'static str) -> Vec<&'static str> {
+        let mut lineage = vec![token_type];
+        while let Some(parent) = self
+            .types
+            .iter()
+            .find(|(name, _)| Some(name) == lineage.last())
+            .and_then(|(_, parent)| *parent)
+        {
+            if lineage.contains(&parent) {
+                break;
+            }
+            lineage.push(parent);
+        }
+        lineage
+    }
+
+    /// Whether a token of `token_type` with no modifiers gets a color other
+    /// than the default foreground.
+    pub fn type_styled(
+        &self,
+        theme: &Theme,
+        resolver: &Resolver,
+        token_type: &'static str,
+    ) -> bool {
+        self.lineage(token_type).into_iter().any(|ty| {
+            theme.semantic_token_colors.keys().any(|selector| {
+                let selector = parse_selector(selector);
+                selector.token_type == ty
+                    && selector.modifiers.is_empty()
+                    && selector.language.is_none_or(|l| l == self.language)
+            }) || fallback_styled(resolver, ty)
+        })
+    }
+
+    /// Whether any selector styles tokens by `modifier`: `*.modifier`, a
+    /// type of this server's with the modifier, or a TextMate fallback for
+    /// such a pair.
+    pub fn modifier_styled(&self, theme: &Theme, resolver: &Resolver, modifier: &str) -> bool {
+        let by_selector = theme.semantic_token_colors.keys().any(|selector| {
+            let selector = parse_selector(selector);
+            selector.modifiers.contains(&modifier)
+                && selector.language.is_none_or(|l| l == self.language)
+                && (selector.token_type == "*"
+                    || self.types.iter().any(|(ty, _)| *ty == selector.token_type))
+        });
+        by_selector
+            || self
+                .types
+                .iter()
+                .any(|(ty, _)| fallback_styled(resolver, &format!("{ty}.{modifier}")))
+    }
+}
+
+/// Whether the TextMate scopes VS Code maps `key` (a type, or a type and
+/// modifiers) to get a foreground from `tokenColors`.
+fn fallback_styled(resolver: &Resolver, key: &str) -> bool {
+    FALLBACK_SCOPES
+        .iter()
+        .filter(|(name, _)| *name == key)
+        .flat_map(|(_, scopes)| scopes.iter())
+        .any(|scope| resolver.resolve_matched(&[*scope]).foreground.is_some())
+}
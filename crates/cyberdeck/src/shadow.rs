@@ -0,0 +1,311 @@
+//! Token rules that never take effect, and rules that could be one.
+//!
+//! A later selector *covers* an earlier one when it matches every scope
+//! stack the earlier one does at the same specificity: the same innermost
+//! part and as many parents, each the same as or a prefix of the earlier
+//! selector's. VS Code breaks that tie in favor of the later rule (see
+//! [`scope`](crate::scope)), so each setting the later rule makes is lost
+//! from the earlier one. A selector whose every setting is lost is dead
+//! weight; [`minimize`] drops it, and merges rules whose settings are
+//! identical when the move can't change any tie.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use crate::format;
+use crate::jsonc::{self, Comment, Node};
+use crate::scope::{scope_prefix, Selector};
+use crate::theme::{ScopeSpec, Theme, TokenRule};
+
+/// How one selector of one rule is overridden by later rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shadowed {
+    /// Index in `tokenColors`.
+    pub rule: usize,
+    pub selector: String,
+    /// The settings the rule makes that later rules override.
+    pub fields: Vec<&'static str>,
+    /// The overriding rules, in order.
+    pub by: Vec<usize>,
+    /// Whether every setting the rule makes is overridden, so the selector
+    /// never takes effect.
+    pub full: bool,
+}
+
+/// What [`minimize`] changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Minimized {
+    pub text: String,
+    /// Selectors dropped because they never take effect.
+    pub dropped: usize,
+    /// Rules folded into an earlier rule with the same settings.
+    pub merged: usize,
+}
+
+/// The settings a rule makes, by name.
+fn fields(rule: &TokenRule) -> Vec<&'static str> {
+    let settings = &rule.settings;
+    [
+        ("foreground", settings.foreground.is_some()),
+        ("background", settings.background.is_some()),
+        ("fontStyle", settings.font_style.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(name, set)| set.then_some(name))
+    .collect()
+}
+
+/// A rule's selectors, parsed; unsupported ones are skipped.
+fn selectors(rule: &TokenRule) -> Vec<Selector> {
+    rule.scope
+        .selectors()
+        .into_iter()
+        .filter_map(Selector::parse)
+        .collect()
+}
+
+/// Whether `later` matches whatever `earlier` does, equally specifically.
+pub fn covers(later: &Selector, earlier: &Selector) -> bool {
+    let (Some((later_target, later_parents)), Some((earlier_target, earlier_parents))) =
+        (later.parts().split_last(), earlier.parts().split_last())
+    else {
+        return false;
+    };
+    later_target == earlier_target
+        && later_parents.len() == earlier_parents.len()
+        && later_parents
+            .iter()
+            .zip(earlier_parents)
+            .all(|(later, earlier)| scope_prefix(later, earlier))
+}
+
+/// Whether `a` and `b` could both match one stack at the same specificity,
+/// so their order decides between them.
+pub(crate) fn may_tie(a: &Selector, b: &Selector) -> bool {
+    a.parts().last() == b.parts().last() && a.parts().len() == b.parts().len()
+}
+
+/// Every selector of a scoped rule that a later rule overrides at least
+/// one setting of.
+pub fn shadowed(theme: &Theme) -> Vec<Shadowed> {
+    let rules: Vec<(Vec<Selector>, Vec<&'static str>)> = theme
+        .token_colors
+        .iter()
+        .map(|rule| (selectors(rule), fields(rule)))
+        .collect();
+    let mut found = Vec::new();
+    for (index, (selectors, own)) in rules.iter().enumerate() {
+        for selector in selectors {
+            let mut fields = Vec::new();
+            let mut by = Vec::new();
+            for (later, (later_selectors, later_fields)) in rules.iter().enumerate().skip(index + 1)
+            {
+                if !later_selectors.iter().any(|s| covers(s, selector)) {
+                    continue;
+                }
+                let lost: Vec<_> = own.iter().filter(|f| later_fields.contains(f)).collect();
+                if lost.is_empty() {
+                    continue;
+                }
+                by.push(later);
+                for field in lost {
+                    if !fields.contains(field) {
+                        fields.push(*field);
+                    }
+                }
+            }
+            if !by.is_empty() {
+                found.push(Shadowed {
+                    rule: index,
+                    selector: selector.to_string(),
+                    full: fields.len() == own.len(),
+                    fields,
+                    by,
+                });
+            }
+        }
+    }
+    found
+}
+
+/// Selectors a rule lists more than once, by rule. A selector shared
+/// between rules is either shadowed, mergeable or layering disjoint
+/// settings, which is deliberate.
+pub fn duplicates(theme: &Theme) -> Vec<(usize, String)> {
+    let mut found = Vec::new();
+    for (index, rule) in theme.token_colors.iter().enumerate() {
+        let mut seen = HashSet::new();
+        for selector in selectors(rule) {
+            let selector = selector.to_string();
+            if !seen.insert(selector.clone()) && !found.contains(&(index, selector.clone())) {
+                found.push((index, selector));
+            }
+        }
+    }
+    found
+}
+
+/// The settings with hex lowercased and font style words sorted, so rules
+/// that style tokens the same compare equal.
+fn settings_key(rule: &TokenRule) -> (Option<String>, Option<String>, Option<String>) {
+    let settings = &rule.settings;
+    let font_style = settings.font_style.as_deref().map(|style| {
+        let mut words: Vec<&str> = style.split_whitespace().collect();
+        words.sort_unstable();
+        words.dedup();
+        words.join(" ")
+    });
+    (
+        settings.foreground.as_deref().map(str::to_ascii_lowercase),
+        settings.background.as_deref().map(str::to_ascii_lowercase),
+        font_style,
+    )
+}
+
+/// Groups of scoped rules with identical settings, each led by the rule the
+/// rest can be folded into. A later rule joins only if no rule in between
+/// could tie with one of its selectors on a setting they share, since
+/// moving it earlier would hand that tie to the rule in between.
+pub fn mergeable(theme: &Theme) -> Vec<Vec<usize>> {
+    let rules = &theme.token_colors;
+    let parsed: Vec<Vec<Selector>> = rules.iter().map(selectors).collect();
+    let mut grouped = vec![false; rules.len()];
+    let mut groups = Vec::new();
+    for first in 0..rules.len() {
+        if grouped[first] || rules[first].scope.is_empty() || fields(&rules[first]).is_empty() {
+            continue;
+        }
+        let key = settings_key(&rules[first]);
+        let own = fields(&rules[first]);
+        let mut group = vec![first];
+        for later in first + 1..rules.len() {
+            if grouped[later] || rules[later].scope.is_empty() || settings_key(&rules[later]) != key
+            {
+                continue;
+            }
+            let blocked = (first + 1..later)
+                .filter(|between| !group.contains(between))
+                .filter(|&between| fields(&rules[between]).iter().any(|f| own.contains(f)))
+                .any(|between| {
+                    parsed[later]
+                        .iter()
+                        .any(|s| parsed[between].iter().any(|t| may_tie(s, t)))
+                });
+            if !blocked {
+                group.push(later);
+            }
+        }
+        if group.len() > 1 {
+            for &index in &group {
+                grouped[index] = true;
+            }
+            groups.push(group);
+        }
+    }
+    groups
+}
+
+/// Rewrites theme source without the selectors that never take effect and
+/// with rules of identical settings merged, then formats it. What VS Code
+/// renders doesn't change.
+pub fn minimize(text: &str) -> Result<Minimized> {
+    let theme: Theme = jsonc::from_str(text)?;
+    let mut root = jsonc::parse_tree(text)?;
+
+    let dead: HashSet<(usize, String)> = shadowed(&theme)
+        .into_iter()
+        .filter(|s| s.full)
+        .map(|s| (s.rule, s.selector))
+        .collect();
+    let mut minimized = Minimized {
+        dropped: dead.len(),
+        ..Minimized::default()
+    };
+
+    // The selectors each rule keeps, with merged rules' moved to the head
+    // of their group and `None` for rules that go away.
+    let mut kept: Vec<Option<Vec<String>>> = theme
+        .token_colors
+        .iter()
+        .enumerate()
+        .map(|(index, rule)| {
+            Some(
+                selectors(rule)
+                    .iter()
+                    .map(ToString::to_string)
+                    .filter(|s| !dead.contains(&(index, s.clone())))
+                    .collect(),
+            )
+        })
+        .collect();
+    for (index, rule) in theme.token_colors.iter().enumerate() {
+        if !rule.scope.is_empty() && kept[index].as_ref().is_some_and(Vec::is_empty) {
+            kept[index] = None;
+        }
+    }
+    let survivors = Theme {
+        token_colors: theme
+            .token_colors
+            .iter()
+            .zip(&kept)
+            .map(|(rule, kept)| match kept {
+                Some(selectors) if !rule.scope.is_empty() => TokenRule {
+                    scope: ScopeSpec::Many(selectors.clone()),
+                    ..rule.clone()
+                },
+                Some(_) => rule.clone(),
+                None => TokenRule::default(),
+            })
+            .collect(),
+        ..theme.clone()
+    };
+    for group in mergeable(&survivors) {
+        let (&head, rest) = group.split_first().expect("groups have two or more rules");
+        for &index in rest {
+            if let Some(moved) = kept[index].take() {
+                minimized.merged += 1;
+                let target = kept[head].get_or_insert_with(Vec::new);
+                for selector in moved {
+                    if !target.contains(&selector) {
+                        target.push(selector);
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(Node::Array { elements, dangling }) = match &mut root {
+        Node::Object { members, .. } => members
+            .iter_mut()
+            .rev()
+            .find(|m| m.key == "tokenColors")
+            .map(|m| &mut m.value),
+        _ => None,
+    } {
+        let mut carry: Vec<Comment> = Vec::new();
+        let mut rules = Vec::new();
+        for ((mut element, rule), kept) in std::mem::take(elements)
+            .into_iter()
+            .zip(&theme.token_colors)
+            .zip(kept)
+        {
+            let Some(selectors) = kept else {
+                // A removed rule's comments move to the next one.
+                carry.extend(std::mem::take(&mut element.leading));
+                continue;
+            };
+            if !rule.scope.is_empty() {
+                let was_array = matches!(element.value.get("scope"), Some(Node::Array { .. }));
+                format::set_scope(&mut element.value, &selectors, was_array);
+            }
+            element.leading.splice(0..0, carry.drain(..));
+            rules.push(element);
+        }
+        *elements = rules;
+        dangling.splice(0..0, carry);
+    }
+
+    minimized.text = format::format_theme(&root.to_pretty_string())?;
+    Ok(minimized)
+}
@@ -0,0 +1,156 @@
+//! Candidate palette colors for scopes a theme leaves unstyled.
+//!
+//! Two sources are consulted, best first: the rule whose selector shares the
+//! longest dot-segment prefix with the scope (its "sibling"), and the palette
+//! role of the scope's semantic category.
+
+use crate::color::Color;
+use crate::palette::{Palette, PaletteColor};
+use crate::scope::{scope_prefix, Selector};
+use crate::theme::{ScopeSpec, Theme, TokenRule, TokenSettings};
+
+/// Scope prefixes and the palette role for tokens under them. The longest
+/// matching prefix wins.
+const CATEGORIES: &[(&str, &str)] = &[
+    ("comment", "comment"),
+    ("punctuation.definition.comment", "comment"),
+    ("string", "string"),
+    ("punctuation.definition.string", "string"),
+    ("constant.numeric", "number"),
+    ("constant", "constant"),
+    ("keyword", "keyword"),
+    ("storage", "storage"),
+    ("entity.name.function", "function"),
+    ("support.function", "function"),
+    ("meta.function-call", "function"),
+    ("entity.name", "type"),
+    ("entity.name.type", "type"),
+    ("support.type", "type"),
+    ("entity.name.class", "class"),
+    ("entity.other.inherited-class", "class"),
+    ("support.class", "class"),
+    ("entity.name.tag", "keyword"),
+    ("entity.other.attribute-name", "property"),
+    ("variable", "variable"),
+    ("variable.parameter", "parameter"),
+    ("variable.other.property", "property"),
+    ("variable.other.member", "property"),
+    ("support.variable.property", "property"),
+    ("markup.inserted", "inserted"),
+    ("markup.deleted", "deleted"),
+    ("markup.changed", "modified"),
+    ("invalid", "error"),
+    ("punctuation", "foreground.secondary"),
+    ("meta", "foreground"),
+];
+
+/// Why a color was suggested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// The color of the rule with this closely related selector.
+    Sibling(String),
+    /// The palette color for this role.
+    Role(&'static str),
+}
+
+impl std::fmt::Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::Sibling(selector) => write!(f, "sibling of {selector}"),
+            Reason::Role(role) => write!(f, "role {role}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Candidate<'p> {
+    pub color: &'p PaletteColor,
+    pub reason: Reason,
+}
+
+/// The palette role for a scope's semantic category.
+pub fn category(scope: &str) -> Option<&'static str> {
+    CATEGORIES
+        .iter()
+        .filter(|(prefix, _)| scope_prefix(prefix, scope))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|&(_, role)| role)
+}
+
+/// Candidate colors for `scope`, best first, without repeats. Only palette
+/// colors are suggested.
+pub fn candidates<'p>(theme: &Theme, palette: &'p Palette, scope: &str) -> Vec<Candidate<'p>> {
+    let mut found: Vec<Candidate> = Vec::new();
+    let mut push = |color: Option<&'p PaletteColor>, reason: Reason| {
+        if let Some(color) = color {
+            if !found.iter().any(|c| c.color.name == color.name) {
+                found.push(Candidate { color, reason });
+            }
+        }
+    };
+    if let Some((selector, color)) = sibling(theme, scope) {
+        push(palette.find(color), Reason::Sibling(selector));
+    }
+    if let Some(role) = category(scope) {
+        push(palette.role(role), Reason::Role(role));
+    }
+    found
+}
+
+/// The single-part selector sharing the most leading segments with `scope`,
+/// and its foreground. Later rules win ties, as they would in VS Code.
+fn sibling(theme: &Theme, scope: &str) -> Option<(String, Color)> {
+    let mut best: Option<(usize, String, Color)> = None;
+    for rule in &theme.token_colors {
+        let Some(color) = rule
+            .settings
+            .foreground
+            .as_deref()
+            .and_then(|c| c.parse::<Color>().ok())
+        else {
+            continue;
+        };
+        for selector in rule.scope.selectors() {
+            let Some(parsed) = Selector::parse(selector) else {
+                continue;
+            };
+            let [part] = parsed.parts() else {
+                continue;
+            };
+            let shared = common_segments(part, scope);
+            if shared > 0 && best.as_ref().is_none_or(|(n, ..)| shared >= *n) {
+                best = Some((shared, part.clone(), color));
+            }
+        }
+    }
+    best.map(|(_, selector, color)| (selector, color))
+}
+
+fn common_segments(a: &str, b: &str) -> usize {
+    a.split('.')
+        .zip(b.split('.'))
+        .take_while(|(x, y)| x == y)
+        .count()
+}
+
+/// A `tokenColors` entry styling `scope` with `color`, named after the scope.
+pub fn token_rule(scope: &str, color: &PaletteColor) -> TokenRule {
+    let name = scope
+        .split(['.', '-'])
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    TokenRule {
+        name: Some(name),
+        scope: ScopeSpec::One(scope.to_owned()),
+        settings: TokenSettings {
+            foreground: Some(color.hex.to_string()),
+            ..TokenSettings::default()
+        },
+    }
+}
@@ -0,0 +1,230 @@
+//! The VS Code color theme document model.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use indexmap::IndexMap;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::color::Color;
+use crate::jsonc;
+
+/// A VS Code color theme (`*-color-theme.json`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Theme {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub semantic_highlighting: Option<bool>,
+    /// Workbench colors. `null` entries (which VS Code treats as unset) are
+    /// dropped on load.
+    #[serde(default, deserialize_with = "non_null_colors")]
+    pub colors: IndexMap<String, String>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub semantic_token_colors: IndexMap<String, SemanticStyle>,
+    #[serde(default)]
+    pub token_colors: Vec<TokenRule>,
+}
+
+/// One entry of `tokenColors`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct TokenRule {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "ScopeSpec::is_empty")]
+    pub scope: ScopeSpec,
+    #[serde(default)]
+    pub settings: TokenSettings,
+}
+
+/// A rule's `scope`: either a single (possibly comma-separated) string or a
+/// list of selectors.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ScopeSpec {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreground: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_style: Option<String>,
+}
+
+/// A `semanticTokenColors` value: a bare color or a style object.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum SemanticStyle {
+    Color(String),
+    Style(SemanticStyleObject),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticStyleObject {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreground: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_style: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underline: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strikethrough: Option<bool>,
+}
+
+/// Where in a theme a color value was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorLocation {
+    /// A workbench color key.
+    Workbench(String),
+    /// `tokenColors[index].settings.<field>`.
+    Token { index: usize, field: &'static str },
+    /// A `semanticTokenColors` selector.
+    Semantic(String),
+}
+
+/// A color value referenced somewhere in a theme.
+#[derive(Debug, Clone)]
+pub struct ColorRef<'a> {
+    pub location: ColorLocation,
+    pub value: &'a str,
+}
+
+impl Theme {
+    pub fn load(path: &Path) -> Result<Theme> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Theme::parse(&text).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Theme> {
+        Ok(jsonc::from_str(text)?)
+    }
+
+    /// Every color value in the theme, in document order.
+    pub fn color_refs(&self) -> Vec<ColorRef<'_>> {
+        let mut refs = Vec::new();
+        for (key, value) in &self.colors {
+            refs.push(ColorRef {
+                location: ColorLocation::Workbench(key.clone()),
+                value,
+            });
+        }
+        for (selector, style) in &self.semantic_token_colors {
+            if let Some(value) = style.foreground() {
+                refs.push(ColorRef {
+                    location: ColorLocation::Semantic(selector.clone()),
+                    value,
+                });
+            }
+        }
+        for (index, rule) in self.token_colors.iter().enumerate() {
+            let fields = [
+                ("foreground", &rule.settings.foreground),
+                ("background", &rule.settings.background),
+            ];
+            for (field, value) in fields {
+                if let Some(value) = value {
+                    refs.push(ColorRef {
+                        location: ColorLocation::Token { index, field },
+                        value,
+                    });
+                }
+            }
+        }
+        refs
+    }
+
+    /// Replaces every color value with `f` of it, in the same places
+    /// [`Theme::color_refs`] finds them. Values that don't parse are left
+    /// as they are.
+    pub fn map_colors(&mut self, mut f: impl FnMut(Color) -> Color) {
+        let mut map = |value: &mut String| {
+            if let Ok(color) = value.parse() {
+                *value = f(color).to_string();
+            }
+        };
+        self.colors.values_mut().for_each(&mut map);
+        for style in self.semantic_token_colors.values_mut() {
+            match style {
+                SemanticStyle::Color(value) => map(value),
+                SemanticStyle::Style(style) => style.foreground.iter_mut().for_each(&mut map),
+            }
+        }
+        for rule in &mut self.token_colors {
+            rule.settings.foreground.iter_mut().for_each(&mut map);
+            rule.settings.background.iter_mut().for_each(&mut map);
+        }
+    }
+}
+
+impl ScopeSpec {
+    /// The individual scope selectors, with comma-separated strings split.
+    pub fn selectors(&self) -> Vec<&str> {
+        fn split(s: &str) -> impl Iterator<Item = &str> {
+            s.split(',').map(str::trim).filter(|s| !s.is_empty())
+        }
+        match self {
+            ScopeSpec::One(s) => split(s).collect(),
+            ScopeSpec::Many(list) => list.iter().flat_map(|s| split(s)).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selectors().is_empty()
+    }
+}
+
+impl Default for ScopeSpec {
+    fn default() -> Self {
+        ScopeSpec::Many(Vec::new())
+    }
+}
+
+impl SemanticStyle {
+    pub fn foreground(&self) -> Option<&str> {
+        match self {
+            SemanticStyle::Color(c) => Some(c),
+            SemanticStyle::Style(s) => s.foreground.as_deref(),
+        }
+    }
+}
+
+impl std::fmt::Display for ColorLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorLocation::Workbench(key) => write!(f, "colors.{key}"),
+            ColorLocation::Token { index, field } => {
+                write!(f, "tokenColors[{index}].settings.{field}")
+            }
+            ColorLocation::Semantic(selector) => write!(f, "semanticTokenColors.{selector}"),
+        }
+    }
+}
+
+fn non_null_colors<'de, D>(deserializer: D) -> Result<IndexMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = IndexMap::<String, Option<String>>::deserialize(deserializer)?;
+    Ok(raw
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|v| (key, v)))
+        .collect())
+}
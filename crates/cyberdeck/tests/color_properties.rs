@@ -0,0 +1,201 @@
+//! Property tests for the color module: conversions that round-trip,
+//! clamping that lands in gamut, compositing that is associative and hex
+//! that prints back as it parses.
+
+use cyberdeck::color::{Color, Oklch};
+use proptest::prelude::*;
+
+fn color() -> impl Strategy<Value = Color> {
+    any::<[u8; 4]>().prop_map(|[r, g, b, a]| Color::rgba(r, g, b, a))
+}
+
+fn opaque_color() -> impl Strategy<Value = Color> {
+    any::<[u8; 3]>().prop_map(|[r, g, b]| Color::rgb(r, g, b))
+}
+
+/// The largest difference between two colors in any channel.
+fn channel_distance(x: Color, y: Color) -> u8 {
+    [
+        x.r.abs_diff(y.r),
+        x.g.abs_diff(y.g),
+        x.b.abs_diff(y.b),
+        x.a.abs_diff(y.a),
+    ]
+    .into_iter()
+    .max()
+    .unwrap_or(0)
+}
+
+/// Composites `top` over a possibly translucent `below`, keeping the alpha
+/// of the result (straight-alpha source-over), so two layers can be
+/// flattened into one before they meet an opaque background.
+fn flatten(top: Color, below: Color) -> Color {
+    let (top_alpha, below_alpha) = (f64::from(top.a) / 255.0, f64::from(below.a) / 255.0);
+    let alpha = top_alpha + below_alpha * (1.0 - top_alpha);
+    if alpha == 0.0 {
+        return Color::rgba(0, 0, 0, 0);
+    }
+    let mix = |top: u8, below: u8| -> u8 {
+        let premultiplied =
+            f64::from(top) * top_alpha + f64::from(below) * below_alpha * (1.0 - top_alpha);
+        (premultiplied / alpha).round() as u8
+    };
+    Color::rgba(
+        mix(top.r, below.r),
+        mix(top.g, below.g),
+        mix(top.b, below.b),
+        (alpha * 255.0).round() as u8,
+    )
+}
+
+proptest! {
+    #[test]
+    fn oklch_round_trips(color in opaque_color()) {
+        let back = Color::from_oklch(color.to_oklch());
+        prop_assert!(channel_distance(color, back) <= 1, "{color} came back as {back}");
+    }
+
+    #[test]
+    fn oklch_is_in_range(color in opaque_color()) {
+        let lch = color.to_oklch();
+        prop_assert!((-1e-9..=1.0 + 1e-9).contains(&lch.l), "lightness {}", lch.l);
+        prop_assert!(lch.c >= 0.0);
+        prop_assert!((0.0..360.0).contains(&lch.h), "hue {}", lch.h);
+    }
+
+    /// Out-of-gamut OKLCH clamps to an opaque color that is itself stable
+    /// under another round trip.
+    #[test]
+    fn out_of_gamut_clamps_into_gamut(
+        l in -0.5f64..1.5,
+        c in 0.0f64..0.6,
+        h in 0.0f64..360.0,
+    ) {
+        let clamped = Color::from_oklch(Oklch { l, c, h });
+        prop_assert!(clamped.is_opaque());
+        let again = Color::from_oklch(clamped.to_oklch());
+        prop_assert!(channel_distance(clamped, again) <= 1, "{clamped} became {again}");
+    }
+
+    #[test]
+    fn lightness_extremes_clamp_to_black_and_white(l in 0.0f64..2.0, h in 0.0f64..360.0) {
+        prop_assert_eq!(Color::from_oklch(Oklch { l: -l, c: 0.0, h }), Color::rgb(0, 0, 0));
+        prop_assert_eq!(
+            Color::from_oklch(Oklch { l: 1.0 + l, c: 0.0, h }),
+            Color::rgb(0xff, 0xff, 0xff)
+        );
+    }
+
+    /// `(top over middle) over background` matches `top over (middle over
+    /// background)`, within 8-bit rounding.
+    #[test]
+    fn compositing_is_associative(top in color(), middle in color(), background in opaque_color()) {
+        let stepwise = top.over(middle.over(background));
+        let flattened = flatten(top, middle).over(background);
+        prop_assert!(
+            channel_distance(stepwise, flattened) <= 2,
+            "{stepwise} vs {flattened}"
+        );
+    }
+
+    /// Flattening three translucent layers doesn't depend on which two go
+    /// first.
+    #[test]
+    fn flattening_is_associative(
+        a in color(),
+        b in color(),
+        c in color(),
+        background in opaque_color(),
+    ) {
+        let left = flatten(flatten(a, b), c);
+        let right = flatten(a, flatten(b, c));
+        prop_assert!(left.a.abs_diff(right.a) <= 1, "{left} vs {right}");
+        // Low-alpha results have imprecise channels that can't be seen, so
+        // compare what they look like.
+        let (left, right) = (left.over(background), right.over(background));
+        prop_assert!(channel_distance(left, right) <= 2, "{left} vs {right}");
+    }
+
+    /// Layering a color twice, at alphas `first` then `second`, looks the
+    /// same as layering it once at the alpha the two add up to.
+    #[test]
+    fn compositing_accumulates_alpha(
+        color in opaque_color(),
+        first in any::<u8>(),
+        second in any::<u8>(),
+        background in opaque_color(),
+    ) {
+        let stepwise = color.with_alpha(second).over(color.with_alpha(first).over(background));
+        let uncovered = (1.0 - f64::from(first) / 255.0) * (1.0 - f64::from(second) / 255.0);
+        let combined = ((1.0 - uncovered) * 255.0).round() as u8;
+        let once = color.with_alpha(combined).over(background);
+        prop_assert!(channel_distance(stepwise, once) <= 2, "{stepwise} vs {once}");
+    }
+
+    /// Each channel of a composite lies between the layer's and the
+    /// background's.
+    #[test]
+    fn compositing_stays_between(top in color(), background in opaque_color()) {
+        let result = top.over(background);
+        for (r, t, b) in [
+            (result.r, top.r, background.r),
+            (result.g, top.g, background.g),
+            (result.b, top.b, background.b),
+        ] {
+            prop_assert!((t.min(b)..=t.max(b)).contains(&r), "{result} from {top} over {background}");
+        }
+        prop_assert!(result.is_opaque());
+    }
+
+    #[test]
+    fn compositing_opaque_or_transparent(color in color(), background in opaque_color()) {
+        prop_assert_eq!(color.opaque().over(background), color.opaque());
+        prop_assert_eq!(color.with_alpha(0).over(background), background);
+    }
+
+    #[test]
+    fn hex_round_trips(color in color()) {
+        let printed = color.to_string();
+        prop_assert_eq!(printed.parse::<Color>(), Ok(color));
+        prop_assert_eq!(printed.len(), if color.is_opaque() { 7 } else { 9 });
+        prop_assert_eq!(printed.to_lowercase(), printed);
+    }
+
+    /// Parsing then printing any accepted spelling gives the canonical form,
+    /// which prints as itself.
+    #[test]
+    fn hex_printing_is_idempotent(
+        text in "#([0-9a-fA-F]{3}|[0-9a-fA-F]{4}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})",
+    ) {
+        let color: Color = text.parse().unwrap();
+        let canonical = color.to_string();
+        prop_assert_eq!(canonical.parse::<Color>().unwrap().to_string(), canonical.as_str());
+        // Long forms only change case, unless they spell out an opaque alpha.
+        if text.len() == 7 || text.len() == 9 && !color.is_opaque() {
+            prop_assert_eq!(canonical, text.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn shorthand_hex_doubles_each_digit(digits in "[0-9a-f]{3,4}") {
+        let long: String = digits.chars().flat_map(|d| [d, d]).collect();
+        prop_assert_eq!(
+            format!("#{digits}").parse::<Color>(),
+            format!("#{long}").parse::<Color>()
+        );
+    }
+
+    #[test]
+    fn contrast_ratio_is_symmetric_and_bounded(a in opaque_color(), b in opaque_color()) {
+        let ratio = a.contrast_ratio(b);
+        prop_assert!((1.0..=21.0 + 1e-9).contains(&ratio), "ratio {ratio}");
+        prop_assert_eq!(ratio, b.contrast_ratio(a));
+    }
+}
+
+#[test]
+fn xterm256_round_trips_outside_the_system_colors() {
+    for index in 16..=255u8 {
+        assert_eq!(Color::from_xterm256(index).to_xterm256(), index);
+    }
+}
@@ -0,0 +1,112 @@
+//! Golden tests for the exporters: everything each registered target writes
+//! for the primary theme.
+//!
+//! An exporter change, or a theme edit that reaches a target, shows up as a
+//! snapshot diff. Accept an intended change with `cargo insta review`.
+
+use std::path::Path;
+
+use cyberdeck::export::Registry;
+use cyberdeck::project::Project;
+
+/// Everything `target` writes for the primary theme, one artifact after
+/// another under its path.
+fn exported(target: &str) -> String {
+    let project = Project::open(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")).unwrap();
+    let theme = project
+        .load_theme(project.primary_theme().unwrap())
+        .unwrap();
+    let registry = Registry::with_builtins();
+    let mut out = String::new();
+    for artifact in registry.get(target).unwrap().export(&theme).unwrap() {
+        out.push_str(&format!("===== {} =====\n", artifact.path.display()));
+        out.push_str(&String::from_utf8(artifact.contents).unwrap());
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// One test per target, so a changed artifact doesn't hide the targets
+/// after it, plus a check that no registered target goes untested.
+macro_rules! exporter_tests {
+    ($($test:ident: $target:literal,)*) => {
+        $(
+            #[test]
+            fn $test() {
+                insta::assert_snapshot!($target, exported($target));
+            }
+        )*
+
+        #[test]
+        fn every_target_is_tested() {
+            let tested = [$($target),*];
+            for exporter in Registry::with_builtins().iter() {
+                assert!(
+                    tested.contains(&exporter.name()),
+                    "`{}` has no snapshot test",
+                    exporter.name()
+                );
+            }
+        }
+    };
+}
+
+exporter_tests! {
+    ace: "ace",
+    alacritty: "alacritty",
+    base16: "base16",
+    bat: "bat",
+    btop: "btop",
+    chroma: "chroma",
+    codemirror: "codemirror",
+    css_variables: "css-variables",
+    dtcg: "dtcg",
+    emacs: "emacs",
+    fish: "fish",
+    foot: "foot",
+    fzf: "fzf",
+    ghostty: "ghostty",
+    gitui: "gitui",
+    gnome_terminal: "gnome-terminal",
+    gtksourceview: "gtksourceview",
+    helix: "helix",
+    highlightjs: "highlightjs",
+    iterm2: "iterm2",
+    jetbrains: "jetbrains",
+    kate: "kate",
+    kitty: "kitty",
+    konsole: "konsole",
+    lazygit: "lazygit",
+    ls_colors: "ls-colors",
+    mc: "mc",
+    monaco: "monaco",
+    neovim: "neovim",
+    notepadpp: "notepadpp",
+    obsidian: "obsidian",
+    polybar: "polybar",
+    prism: "prism",
+    putty: "putty",
+    pygments: "pygments",
+    qutebrowser: "qutebrowser",
+    rofi: "rofi",
+    roles: "roles",
+    scss: "scss",
+    shiki: "shiki",
+    slack: "slack",
+    starship: "starship",
+    sublime: "sublime",
+    sway: "sway",
+    tailwind: "tailwind",
+    tmux: "tmux",
+    vim: "vim",
+    visualstudio: "visualstudio",
+    waybar: "waybar",
+    wezterm: "wezterm",
+    windows_terminal: "windows-terminal",
+    xcode: "xcode",
+    zathura: "zathura",
+    zed: "zed",
+    zsh_syntax_highlighting: "zsh-syntax-highlighting",
+}
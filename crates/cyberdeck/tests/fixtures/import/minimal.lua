@@ -0,0 +1,13 @@
+vim.g.colors_name = "minimal"
+
+local c = {
+  bg = "#1a1b26",
+  fg = "#c0caf5",
+  purple = "#bb9af7",
+}
+
+local set = vim.api.nvim_set_hl
+set(0, "Normal", { fg = c.fg, bg = c.bg })
+set(0, "Keyword", { fg = c.purple, bold = true })
+set(0, "@keyword.return", { link = "Keyword" })
+set(0, "@comment", { fg = "#565f89", italic = true })
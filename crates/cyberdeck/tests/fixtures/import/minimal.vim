@@ -0,0 +1,5 @@
+hi clear
+let g:colors_name = "minimal"
+hi Normal guifg=#c0caf5 guibg=#1a1b26
+hi String guifg=#9ece6a gui=italic,undercurl
+hi! link Character String
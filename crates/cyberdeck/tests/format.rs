@@ -0,0 +1,99 @@
+//! `cyberdeck fmt` must never change what VS Code renders: every token of
+//! every language demo resolves to the same style before and after
+//! formatting.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use cyberdeck::format::format_theme;
+use cyberdeck::highlight::{Document, Grammars, Styler};
+use cyberdeck::scope::Resolver;
+use cyberdeck::theme::Theme;
+
+const THEME: &str = "themes/Cyberdeck-2025-color-theme.json";
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+fn demos() -> Vec<Document> {
+    let grammars = Grammars::bundled();
+    let mut paths: Vec<_> = std::fs::read_dir(root().join("language_demos"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .filter_map(|path| grammars.load(path).ok())
+        .collect()
+}
+
+/// One line per token: its scope stack and resolved style.
+fn dump(theme_text: &str, documents: &[Document]) -> String {
+    let theme = Theme::parse(theme_text).unwrap();
+    let resolver = Resolver::new(&theme);
+    let mut styler = Styler::new(&resolver);
+    let mut out = String::new();
+    for document in documents {
+        for token in document.lines.iter().flatten() {
+            let style = styler.style(token);
+            let _ = writeln!(
+                out,
+                "[{}] {:?} {:?} {:?}",
+                token.scopes.join(" "),
+                style.foreground,
+                style.background,
+                style.font_style
+            );
+        }
+    }
+    out
+}
+
+fn assert_same_rendering(text: &str, documents: &[Document]) {
+    let formatted = format_theme(text).unwrap();
+    let (before, after) = (dump(text, documents), dump(&formatted, documents));
+    let changed: Vec<_> = before
+        .lines()
+        .zip(after.lines())
+        .filter(|(before, after)| before != after)
+        .take(10)
+        .collect();
+    assert!(changed.is_empty(), "fmt changed token styles: {changed:#?}");
+}
+
+#[test]
+fn fmt_keeps_the_theme_rendering() {
+    let text = std::fs::read_to_string(root().join(THEME)).unwrap();
+    assert_same_rendering(&text, &demos());
+}
+
+/// The theme's rules in reverse: far from canonical order, so formatting
+/// has to move most of them.
+#[test]
+fn fmt_keeps_reordered_rules_rendering() {
+    let text = std::fs::read_to_string(root().join(THEME)).unwrap();
+    let mut theme: serde_json::Value = cyberdeck::jsonc::from_str(&text).unwrap();
+    theme["tokenColors"].as_array_mut().unwrap().reverse();
+    assert_same_rendering(&serde_json::to_string_pretty(&theme).unwrap(), &demos());
+}
+
+/// A later rule that ties with an earlier one must stay later, even when
+/// its first selector sorts first.
+#[test]
+fn fmt_keeps_tied_rules_in_order() {
+    let text = r##"{
+        "tokenColors": [
+            { "scope": "string", "settings": { "foreground": "#f00", "fontStyle": "italic" } },
+            { "scope": ["comment", "string"], "settings": { "fontStyle": "bold" } }
+        ]
+    }"##;
+    let style = |text: &str| {
+        let theme = Theme::parse(text).unwrap();
+        Resolver::new(&theme).resolve(&["source", "string"])
+    };
+    let formatted = format_theme(text).unwrap();
+    assert_eq!(style(&formatted), style(text));
+    assert_eq!(style(&formatted).font_style.as_deref(), Some("bold"));
+}
@@ -0,0 +1,50 @@
+//! Golden tests for the HTML renderer: the gallery pages for a fixed region
+//! of several demos under every variant.
+//!
+//! These catch changes to the rendering pipeline itself (span merging,
+//! inline styles, escaping, page chrome) as much as to the colors. Accept an
+//! intended change with `cargo insta review`.
+
+use std::ops::Range;
+use std::path::Path;
+
+use cyberdeck::highlight::Grammars;
+use cyberdeck::palette::Palette;
+use cyberdeck::project::Project;
+use cyberdeck::render::gallery::gallery;
+
+/// Demos and the 0-based line ranges rendered from each: enough to cover
+/// comments, imports, declarations, strings and control flow.
+const REGIONS: &[(&str, Range<usize>)] = &[
+    ("language_demos/rust_demo.rs", 0..40),
+    ("language_demos/python_demo.py", 0..40),
+    ("language_demos/typescript_demo.ts", 0..40),
+    ("language_demos/go_demo.go", 0..40),
+];
+
+#[test]
+fn gallery_pages() {
+    let project = Project::open(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")).unwrap();
+    let themes: Vec<_> = project
+        .variants()
+        .unwrap()
+        .into_iter()
+        .map(|entry| project.load_theme(entry).unwrap())
+        .collect();
+    let palette = Palette::load(&project.palette_path()).unwrap();
+    let grammars = Grammars::bundled();
+    let documents: Vec<_> = REGIONS
+        .iter()
+        .map(|(path, lines)| {
+            let mut document = grammars.load(&project.root.join(path)).unwrap();
+            document.lines = document.lines[lines.clone()].to_vec();
+            document
+        })
+        .collect();
+
+    for artifact in gallery("Cyberdeck 2025", &themes, &documents, Some(&palette)) {
+        let name = artifact.path.to_string_lossy().replace(['/', '.'], "_");
+        let contents = String::from_utf8(artifact.contents).unwrap();
+        insta::assert_snapshot!(name, contents);
+    }
+}
@@ -0,0 +1,46 @@
+//! Parsing `grammar fetch` sources, whose parts end up in download URLs.
+
+use cyberdeck::grammar::Source;
+
+fn error(spec: &str) -> String {
+    format!("{:#}", spec.parse::<Source>().unwrap_err())
+}
+
+#[test]
+fn parses_each_kind() {
+    for spec in [
+        "open-vsx:rust-lang.rust-analyzer",
+        "marketplace:ms-vscode.cpptools",
+        "github:microsoft/vscode@1.95.0",
+        "github:tree-sitter/tree-sitter_rust.v2",
+        "file:grammars/local.vsix",
+    ] {
+        assert_eq!(spec.parse::<Source>().unwrap().to_string(), spec);
+    }
+    assert_eq!(
+        "redhat.vscode-yaml".parse::<Source>().unwrap().to_string(),
+        "open-vsx:redhat.vscode-yaml"
+    );
+}
+
+#[test]
+fn rejects_parts_unsafe_in_a_url() {
+    assert_eq!(
+        error("open-vsx:rust-lang.rust?analyzer"),
+        "`rust?analyzer` in `rust-lang.rust?analyzer` may only contain letters, digits, `.`, `_` and `-`"
+    );
+    for spec in [
+        "marketplace:ms vscode.cpptools",
+        "marketplace:ms-vscode.cpp/../tools",
+        "github:../vscode",
+        "github:microsoft/vscode#main",
+        "github:microsoft/",
+        "github:micro%2Fsoft/vscode",
+    ] {
+        assert!(
+            error(spec).contains("may only contain"),
+            "{spec}: {}",
+            error(spec)
+        );
+    }
+}
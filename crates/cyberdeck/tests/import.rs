@@ -0,0 +1,393 @@
+//! Each importer against a minimal input in its format, checked on the
+//! colors and token rules (or palette samples) it comes out with.
+//!
+//! Fixtures live in `tests/fixtures/import/`; the `.vsix` archive and the
+//! image are built by the tests themselves.
+
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use cyberdeck::color::Color;
+use cyberdeck::import::{self, base16, dtcg, figma, image, terminal, vscode, vsix};
+use cyberdeck::theme::Theme;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/import")
+        .join(name)
+}
+
+/// The theme `import::load_theme` makes of a fixture, as JSON.
+fn imported(name: &str) -> serde_json::Value {
+    let theme = import::load_theme(&fixture(name)).unwrap();
+    serde_json::to_value(theme).unwrap()
+}
+
+fn hex(color: &Color) -> String {
+    color.to_string()
+}
+
+#[test]
+fn tmtheme() {
+    assert_eq!(
+        imported("minimal.tmTheme"),
+        json!({
+            "name": "Minimal",
+            "type": "dark",
+            "colors": { "editor.background": "#1a1b26", "editor.foreground": "#c0caf5" },
+            "tokenColors": [
+                {
+                    "name": "Comment",
+                    "scope": "comment, punctuation.definition.comment",
+                    "settings": { "foreground": "#565f89", "fontStyle": "italic underline" }
+                },
+                { "scope": "keyword", "settings": { "foreground": "#bb9af7" } }
+            ]
+        })
+    );
+}
+
+#[test]
+fn sublime_color_scheme() {
+    assert_eq!(
+        imported("minimal.sublime-color-scheme"),
+        json!({
+            "name": "Minimal",
+            "type": "light",
+            "colors": {
+                "editor.background": "#fafafa",
+                "editor.foreground": "#383a42",
+                "editor.selectionBackground": "#66339940"
+            },
+            "tokenColors": [
+                {
+                    "name": "Keyword",
+                    "scope": "keyword",
+                    "settings": { "foreground": "#663399", "fontStyle": "bold" }
+                },
+                { "scope": "string", "settings": { "foreground": "#008080" } }
+            ]
+        })
+    );
+}
+
+#[test]
+fn jetbrains_icls() {
+    assert_eq!(
+        imported("minimal.icls"),
+        json!({
+            "name": "Minimal",
+            "type": "dark",
+            "colors": {
+                "editorCursor.foreground": "#bbbbbb",
+                "editor.selectionBackground": "#214283",
+                "editor.background": "#2b2b2b",
+                "editor.foreground": "#a9b7c6",
+                "editorError.foreground": "#ff0000"
+            },
+            "tokenColors": [
+                {
+                    "name": "DEFAULT_KEYWORD",
+                    "scope": "keyword",
+                    "settings": { "foreground": "#cc7832", "fontStyle": "bold" }
+                },
+                {
+                    "name": "DEFAULT_LINE_COMMENT",
+                    "scope": "comment.line",
+                    "settings": { "foreground": "#808080", "fontStyle": "italic" }
+                },
+                {
+                    "name": "DEFAULT_BLOCK_COMMENT",
+                    "scope": "comment.block",
+                    "settings": { "foreground": "#808080", "fontStyle": "italic" }
+                }
+            ]
+        })
+    );
+}
+
+#[test]
+fn helix() {
+    assert_eq!(
+        imported("minimal.toml"),
+        json!({
+            "name": "minimal",
+            "type": "dark",
+            "colors": {
+                "editor.background": "#1a1b26",
+                "editor.foreground": "#c0caf5",
+                "editor.selectionBackground": "#33467c"
+            },
+            "tokenColors": [
+                {
+                    "name": "comment",
+                    "scope": "comment",
+                    "settings": { "foreground": "#808080", "fontStyle": "italic" }
+                },
+                {
+                    "name": "function.builtin",
+                    "scope": "support.function",
+                    "settings": { "foreground": "#7aa2f7" }
+                },
+                {
+                    "name": "keyword.control",
+                    "scope": "keyword.control",
+                    "settings": { "foreground": "#bb9af7", "fontStyle": "bold underline" }
+                }
+            ]
+        })
+    );
+}
+
+#[test]
+fn neovim_lua() {
+    assert_eq!(
+        imported("minimal.lua"),
+        json!({
+            "name": "minimal",
+            "type": "dark",
+            "colors": { "editor.background": "#1a1b26", "editor.foreground": "#c0caf5" },
+            "tokenColors": [
+                {
+                    "name": "Keyword",
+                    "scope": "keyword",
+                    "settings": { "foreground": "#bb9af7", "fontStyle": "bold" }
+                },
+                {
+                    "name": "@keyword.return",
+                    "scope": "keyword.return",
+                    "settings": { "foreground": "#bb9af7", "fontStyle": "bold" }
+                },
+                {
+                    "name": "@comment",
+                    "scope": "comment",
+                    "settings": { "foreground": "#565f89", "fontStyle": "italic" }
+                }
+            ]
+        })
+    );
+}
+
+#[test]
+fn neovim_vim() {
+    assert_eq!(
+        imported("minimal.vim"),
+        json!({
+            "name": "minimal",
+            "type": "dark",
+            "colors": { "editor.background": "#1a1b26", "editor.foreground": "#c0caf5" },
+            "tokenColors": [
+                {
+                    "name": "String",
+                    "scope": "string",
+                    "settings": { "foreground": "#9ece6a", "fontStyle": "italic underline" }
+                },
+                {
+                    "name": "Character",
+                    "scope": "constant.character",
+                    "settings": { "foreground": "#9ece6a", "fontStyle": "italic underline" }
+                }
+            ]
+        })
+    );
+}
+
+#[test]
+fn base16_scheme() {
+    let scheme = base16::Scheme::load(&fixture("base16.yaml")).unwrap();
+    assert_eq!(scheme.name.as_deref(), Some("Minimal"));
+    let colors: Vec<(&str, String)> = scheme
+        .colors
+        .iter()
+        .map(|(slot, color)| (slot.as_str(), hex(color)))
+        .collect();
+    assert_eq!(
+        colors,
+        [
+            ("base00", "#1a1b26".to_owned()),
+            ("base01", "#16161e".to_owned()),
+            ("base05", "#c0caf5".to_owned()),
+            ("base08", "#f7768e".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn dtcg_tokens() {
+    let tokens = dtcg::Tokens::load(&fixture("tokens.json")).unwrap();
+    let colors: Vec<(&str, String)> = tokens
+        .colors
+        .iter()
+        .map(|(path, color)| (path.as_str(), hex(color)))
+        .collect();
+    assert_eq!(
+        colors,
+        [
+            ("color.purple", "#bb9af7".to_owned()),
+            ("color.background", "#1a334d".to_owned()),
+            ("color.accent-primary", "#bb9af7".to_owned()),
+        ]
+    );
+
+    // Tokens named after a role play it, `-` standing in for `.`.
+    let mapping = tokens.mapping(Default::default()).unwrap();
+    assert_eq!(
+        serde_json::to_value(mapping).unwrap(),
+        json!({
+            "color.background": ["background"],
+            "color.accent-primary": ["accent.primary"]
+        })
+    );
+}
+
+#[test]
+fn figma_variables() {
+    let variables = figma::Variables::load(&fixture("figma.json")).unwrap();
+    assert_eq!(variables.modes(), ["Dark", "Light"]);
+    let colors = |mode: &str| -> Vec<(String, String)> {
+        let tokens = variables.tokens(mode).unwrap();
+        tokens
+            .colors
+            .iter()
+            .map(|(path, color)| (path.clone(), hex(color)))
+            .collect()
+    };
+    let expected = |background: &str| {
+        [
+            ("purple.500", "#bb9af7"),
+            ("navy.900", "#000080"),
+            ("bg.primary", background),
+            ("accent", "#bb9af7"),
+        ]
+        .map(|(path, color)| (path.to_owned(), color.to_owned()))
+    };
+    assert_eq!(colors("Dark"), expected("#000080"));
+    assert_eq!(colors("Light"), expected("#ffffff"));
+}
+
+#[test]
+fn terminal_scheme() {
+    let scheme = terminal::Scheme::load(&fixture("gogh.yml")).unwrap();
+    assert_eq!(scheme.name.as_deref(), Some("Minimal"));
+    assert_eq!(hex(&scheme.background), "#1a1b26");
+    assert_eq!(hex(&scheme.foreground), "#c0caf5");
+    // Written without the `#`.
+    assert_eq!(scheme.cursor.as_ref().map(hex).as_deref(), Some("#c0caf5"));
+    assert_eq!(scheme.selection, None);
+    assert_eq!(hex(&scheme.ansi[0]), "#15161e");
+    assert_eq!(hex(&scheme.ansi[1]), "#f7768e");
+    assert_eq!(hex(&scheme.ansi[15]), "#c0caf5");
+}
+
+#[test]
+fn vscode_samples() {
+    let theme = Theme::parse(
+        r##"{
+            "colors": {
+                "editor.background": "#1a1b26",
+                "editor.foreground": "#c0caf5",
+                "panel.background": "#1a1b26cc"
+            },
+            "tokenColors": [{ "scope": "keyword", "settings": { "foreground": "#bb9af7" } }]
+        }"##,
+    )
+    .unwrap();
+    let samples = vscode::samples(&theme);
+    let background = samples
+        .iter()
+        .find(|sample| hex(&sample.color) == "#1a1b26")
+        .unwrap();
+    // Both references count, alpha aside.
+    assert_eq!(background.weight, 2);
+    assert!(background.roles.iter().any(|role| role == "background"));
+    assert!(samples.iter().any(|sample| hex(&sample.color) == "#bb9af7"));
+}
+
+#[test]
+fn vsix_themes() {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let files = [
+        (
+            "extension/package.json",
+            r#"{ "contributes": { "themes": [
+                { "label": "Minimal Dark", "uiTheme": "vs-dark", "path": "./themes/dark.json" },
+                { "label": "Broken", "path": "./themes/missing.json" }
+            ] } }"#,
+        ),
+        (
+            "extension/themes/dark.json",
+            r##"{
+                "include": "./base.json",
+                "colors": { "editor.foreground": "#c0caf5" },
+                "tokenColors": "../syntax/dark.tmTheme"
+            }"##,
+        ),
+        (
+            "extension/themes/base.json",
+            r##"{
+                "name": "Base",
+                "colors": { "editor.background": "#1a1b26", "editor.foreground": "#ffffff" }
+            }"##,
+        ),
+        (
+            "extension/syntax/dark.tmTheme",
+            include_str!("fixtures/import/minimal.tmTheme"),
+        ),
+    ];
+    for (name, text) in files {
+        zip.start_file(name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(text.as_bytes()).unwrap();
+    }
+    let bytes = zip.finish().unwrap().into_inner();
+
+    // The theme that fails to load is skipped; the other is layered over its
+    // `include` and takes its rules from the `.tmTheme` it names.
+    let themes = vsix::themes(&bytes).unwrap();
+    assert_eq!(themes.len(), 1);
+    assert_eq!(themes[0].label, "Minimal Dark");
+    assert_eq!(
+        serde_json::to_value(&themes[0].theme).unwrap(),
+        json!({
+            "name": "Base",
+            "type": "dark",
+            "colors": { "editor.background": "#1a1b26", "editor.foreground": "#c0caf5" },
+            "tokenColors": [
+                {
+                    "name": "Comment",
+                    "scope": "comment, punctuation.definition.comment",
+                    "settings": { "foreground": "#565f89", "fontStyle": "italic underline" }
+                },
+                { "scope": "keyword", "settings": { "foreground": "#bb9af7" } }
+            ]
+        })
+    );
+}
+
+#[test]
+fn image_clusters() {
+    // Three quarters background, a quarter accent.
+    let path = std::env::temp_dir().join(format!("cyberdeck-import-{}.png", std::process::id()));
+    ::image::RgbImage::from_fn(64, 32, |x, _| {
+        if x < 48 {
+            ::image::Rgb([0x1a, 0x1b, 0x26])
+        } else {
+            ::image::Rgb([0xf7, 0x76, 0x8e])
+        }
+    })
+    .save(&path)
+    .unwrap();
+    let clusters = image::clusters(&path);
+    std::fs::remove_file(&path).unwrap();
+    let clusters = clusters.unwrap();
+
+    let background: Color = "#1a1b26".parse().unwrap();
+    let accent: Color = "#f7768e".parse().unwrap();
+    assert!(clusters[0].center.distance(background.to_oklab()) < 0.01);
+    assert!(clusters[1].center.distance(accent.to_oklab()) < 0.01);
+    let total: usize = clusters.iter().map(|c| c.weight).sum();
+    let share = clusters[0].weight as f64 / total as f64;
+    assert!((0.7..0.8).contains(&share), "background share {share}");
+}
@@ -0,0 +1,226 @@
+//! Each built-in lint rule against a small theme it should flag and one it
+//! should pass.
+//!
+//! `shadowed-scope` and `mergeable-rules` lean on an approximation of
+//! selector specificity (two selectors tie when they end in the same scope
+//! and have as many parts), so they get a case on each side of it.
+
+use cyberdeck::color::Color;
+use cyberdeck::lint::{lint, LintConfig, TERMINAL_ANSI_KEYS};
+use cyberdeck::palette::{Palette, PaletteColor};
+use cyberdeck::theme::Theme;
+
+fn palette() -> Palette {
+    Palette {
+        name: "test".to_owned(),
+        colors: vec![PaletteColor {
+            name: "ink".to_owned(),
+            hex: Color::rgb(0x11, 0x22, 0x33),
+            family: "neutral".to_owned(),
+            roles: Vec::new(),
+        }],
+    }
+}
+
+/// What `rule` reports on `theme` under the default configuration, as
+/// `location: message`.
+fn reports(rule: &str, theme: &str) -> Vec<String> {
+    let theme = Theme::parse(theme).unwrap();
+    lint(&theme, &palette(), &LintConfig::default())
+        .into_iter()
+        .filter(|diagnostic| diagnostic.rule == rule)
+        .map(|diagnostic| format!("{}: {}", diagnostic.location, diagnostic.message))
+        .collect()
+}
+
+/// The locations `rule` reports on `theme`.
+fn locations(rule: &str, theme: &str) -> Vec<String> {
+    reports(rule, theme)
+        .into_iter()
+        .map(|report| report.split_once(": ").unwrap().0.to_owned())
+        .collect()
+}
+
+#[test]
+fn valid_color() {
+    let theme = r##"{
+        "colors": { "editor.background": "#112233", "editor.foreground": "#11223" },
+        "tokenColors": [{ "scope": "comment", "settings": { "foreground": "112233" } }]
+    }"##;
+    assert_eq!(
+        locations("valid-color", theme),
+        [
+            "colors.editor.foreground",
+            "tokenColors[0].settings.foreground"
+        ]
+    );
+
+    let theme =
+        r##"{ "colors": { "editor.background": "#123", "editor.foreground": "#11223344" } }"##;
+    assert!(reports("valid-color", theme).is_empty());
+}
+
+#[test]
+fn palette_only() {
+    let theme = r##"{
+        "colors": { "editor.background": "#112233", "editor.foreground": "#445566" }
+    }"##;
+    assert_eq!(
+        reports("palette-only", theme),
+        ["colors.editor.foreground: #445566 is not a palette color"]
+    );
+
+    // Alpha doesn't matter, only the RGB.
+    let theme = r##"{ "colors": { "editor.selectionBackground": "#11223340" } }"##;
+    assert!(reports("palette-only", theme).is_empty());
+}
+
+#[test]
+fn no_bold_italic() {
+    let theme = r#"{
+        "tokenColors": [{ "scope": "markup.bold", "settings": { "fontStyle": "italic bold" } }],
+        "semanticTokenColors": { "function": { "bold": true, "italic": true } }
+    }"#;
+    assert_eq!(
+        locations("no-bold-italic", theme),
+        [
+            "tokenColors[0].settings.fontStyle",
+            "semanticTokenColors.function"
+        ]
+    );
+
+    let theme = r#"{
+        "tokenColors": [{ "scope": "markup.bold", "settings": { "fontStyle": "bold underline" } }],
+        "semanticTokenColors": { "function": { "bold": true, "italic": false } }
+    }"#;
+    assert!(reports("no-bold-italic", theme).is_empty());
+}
+
+#[test]
+fn selection_alpha() {
+    let theme = r##"{
+        "colors": {
+            "editor.selectionBackground": "#112233",
+            "terminal.selectionBackground": "#11223308"
+        }
+    }"##;
+    assert_eq!(
+        reports("selection-alpha", theme),
+        [
+            "colors.editor.selectionBackground: alpha 0xff is outside 0x20..=0xcc",
+            "colors.terminal.selectionBackground: alpha 0x08 is outside 0x20..=0xcc"
+        ]
+    );
+
+    let theme = r##"{ "colors": { "editor.selectionBackground": "#11223366" } }"##;
+    assert!(reports("selection-alpha", theme).is_empty());
+}
+
+#[test]
+fn terminal_ansi_complete() {
+    let colors = |keys: &[&str]| {
+        let entries: Vec<String> = keys
+            .iter()
+            .map(|k| format!(r##""{k}": "#112233""##))
+            .collect();
+        format!(r#"{{ "colors": {{ {} }} }}"#, entries.join(", "))
+    };
+    assert!(reports("terminal-ansi-complete", &colors(&TERMINAL_ANSI_KEYS)).is_empty());
+    assert_eq!(
+        reports("terminal-ansi-complete", &colors(&TERMINAL_ANSI_KEYS[1..])),
+        ["colors.terminal.ansiBlack: is not defined"]
+    );
+}
+
+#[test]
+fn known_color_id() {
+    let theme = r##"{
+        "colors": { "editor.background": "#112233", "editor.notAColor": "#112233" }
+    }"##;
+    assert_eq!(
+        locations("known-color-id", theme),
+        ["colors.editor.notAColor"]
+    );
+
+    let theme = r##"{ "colors": { "editor.background": "#112233" } }"##;
+    assert!(reports("known-color-id", theme).is_empty());
+}
+
+#[test]
+fn shadowed_scope() {
+    // A later rule with the same selector overrides the earlier one
+    // entirely, or one of its settings; a selector listed twice in a rule
+    // is reported on its own.
+    let theme = r##"{
+        "tokenColors": [
+            { "scope": "comment", "settings": { "foreground": "#112233" } },
+            { "scope": "string", "settings": { "foreground": "#112233", "fontStyle": "italic" } },
+            { "scope": ["keyword", "keyword"], "settings": { "foreground": "#112233" } },
+            { "scope": ["comment", "string"], "settings": { "foreground": "#445566" } }
+        ]
+    }"##;
+    assert_eq!(
+        reports("shadowed-scope", theme),
+        [
+            "tokenColors[2].scope: `keyword` is listed twice",
+            "tokenColors[0].scope: `comment` never takes effect, overridden by tokenColors[3] \
+             (`cyberdeck fmt --minimize` drops it)",
+            "tokenColors[1].scope: foreground for `string` is overridden by tokenColors[3]",
+        ]
+    );
+
+    // Later rules that are more specific, or that only add settings, layer
+    // on top rather than override.
+    let theme = r##"{
+        "tokenColors": [
+            { "scope": "comment", "settings": { "foreground": "#112233" } },
+            { "scope": "comment.line", "settings": { "foreground": "#445566" } },
+            { "scope": "source comment", "settings": { "foreground": "#445566" } },
+            { "scope": "comment", "settings": { "fontStyle": "italic" } }
+        ]
+    }"##;
+    assert!(reports("shadowed-scope", theme).is_empty());
+}
+
+#[test]
+fn mergeable_rules() {
+    let theme = r##"{
+        "tokenColors": [
+            { "scope": "keyword", "settings": { "foreground": "#112233" } },
+            { "scope": "string", "settings": { "foreground": "#445566" } },
+            { "scope": "storage", "settings": { "foreground": "#112233" } }
+        ]
+    }"##;
+    assert_eq!(
+        reports("mergeable-rules", theme),
+        ["tokenColors[0]: same settings as tokenColors[2]; \
+             `cyberdeck fmt --minimize` merges them"]
+    );
+
+    // Merging would move `storage` ahead of a rule it ties with on the
+    // foreground, handing that rule the tie.
+    let theme = r##"{
+        "tokenColors": [
+            { "scope": "keyword", "settings": { "foreground": "#112233" } },
+            { "scope": "storage", "settings": { "foreground": "#445566" } },
+            { "scope": "storage", "settings": { "foreground": "#112233" } }
+        ]
+    }"##;
+    assert!(reports("mergeable-rules", theme).is_empty());
+
+    // Neither a deeper scope nor one with a parent ties with `storage`, and
+    // a rule that sets only other settings can't take the tie at all.
+    let theme = r##"{
+        "tokenColors": [
+            { "scope": "keyword", "settings": { "foreground": "#112233" } },
+            { "scope": ["storage.type", "source storage"], "settings": { "foreground": "#445566" } },
+            { "scope": "storage", "settings": { "fontStyle": "italic" } },
+            { "scope": "storage", "settings": { "foreground": "#112233" } }
+        ]
+    }"##;
+    assert_eq!(
+        reports("mergeable-rules", theme),
+        ["tokenColors[0]: same settings as tokenColors[3]; \
+             `cyberdeck fmt --minimize` merges them"]
+    );
+}
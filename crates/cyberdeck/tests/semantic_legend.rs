@@ -0,0 +1,117 @@
+//! Every semantic token type and modifier the supported language servers
+//! emit is either styled by each variant or deliberately left at the
+//! default foreground.
+//!
+//! A server update that adds a type shows up here instead of as unstyled
+//! code in the editor. Add a selector for it to `semanticTokenColors`, or
+//! an allowlist entry saying why the default is right.
+
+use std::path::Path;
+
+use cyberdeck::project::Project;
+use cyberdeck::scope::Resolver;
+use cyberdeck::semantic::LEGENDS;
+
+/// Token types left at the default foreground, as `(server, type)`; `*`
+/// matches every server.
+const ALLOWED_TYPES: &[(&str, &str)] = &[
+    // Storage modifiers such as `const` and `static`, which the TextMate
+    // grammars already color as keywords underneath.
+    ("*", "modifier"),
+    // Tokens clangd couldn't resolve, e.g. in uninstantiated templates;
+    // coloring them would assert a kind it doesn't know.
+    ("clangd", "unknown"),
+    // C++20 concept names are rare enough that the grammar's type color is
+    // good enough.
+    ("clangd", "concept"),
+];
+
+/// Modifiers no selector distinguishes, as `(server, modifier)`.
+const ALLOWED_MODIFIERS: &[(&str, &str)] = &[
+    // Where a symbol lives rather than what it is: trait members, things
+    // expanded from or used inside macros and attributes.
+    ("rust-analyzer", "associated"),
+    ("rust-analyzer", "attribute"),
+    ("rust-analyzer", "macro"),
+    ("rust-analyzer", "procMacro"),
+    // `const` items are their own token type, already styled.
+    ("rust-analyzer", "constant"),
+    // Block-scoped bindings are the norm in TypeScript; marking them would
+    // color most identifiers.
+    ("typescript-language-features", "local"),
+    // Builtins are `defaultLibrary` too, which is styled; type hints are
+    // ordinary classes and variables.
+    ("pylance", "builtin"),
+    ("pylance", "typeHint"),
+    ("pylance", "typeHintComment"),
+    // The underlying type of a Go value or type name; the token type says
+    // enough.
+    ("gopls", "array"),
+    ("gopls", "bool"),
+    ("gopls", "chan"),
+    ("gopls", "interface"),
+    ("gopls", "map"),
+    ("gopls", "number"),
+    ("gopls", "pointer"),
+    ("gopls", "signature"),
+    ("gopls", "slice"),
+    ("gopls", "string"),
+    ("gopls", "struct"),
+    // Format verbs inside strings, which the grammar already picks out.
+    ("gopls", "format"),
+    // Type deduction, dispatch and scope details that would make the same
+    // symbol change color from line to line.
+    ("clangd", "deduced"),
+    ("clangd", "virtual"),
+    ("clangd", "dependentName"),
+    ("clangd", "usedAsMutableReference"),
+    ("clangd", "usedAsMutablePointer"),
+    ("clangd", "constructorOrDestructor"),
+    ("clangd", "userDefined"),
+    ("clangd", "functionScope"),
+    ("clangd", "classScope"),
+    ("clangd", "fileScope"),
+    ("clangd", "globalScope"),
+];
+
+fn allowed(list: &[(&str, &str)], server: &str, name: &str) -> bool {
+    list.iter()
+        .any(|(s, n)| (*s == "*" || *s == server) && *n == name)
+}
+
+#[test]
+fn legends_are_styled_or_allowlisted() {
+    let project = Project::open(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")).unwrap();
+    let mut unstyled = Vec::new();
+    for entry in project.variants().unwrap() {
+        let label = entry.label.clone();
+        let theme = project.load_theme(entry).unwrap();
+        let resolver = Resolver::new(&theme);
+        for legend in LEGENDS {
+            for (token_type, _) in legend.types {
+                if !legend.type_styled(&theme, &resolver, token_type)
+                    && !allowed(ALLOWED_TYPES, legend.server, token_type)
+                {
+                    unstyled.push(format!("{}: {}: type `{token_type}`", label, legend.server));
+                }
+            }
+            for modifier in legend.modifiers {
+                if !legend.modifier_styled(&theme, &resolver, modifier)
+                    && !allowed(ALLOWED_MODIFIERS, legend.server, modifier)
+                {
+                    unstyled.push(format!(
+                        "{}: {}: modifier `{modifier}`",
+                        label, legend.server
+                    ));
+                }
+            }
+        }
+    }
+    assert!(
+        unstyled.is_empty(),
+        "semantic tokens that fall back to the default foreground; style them \
+         or allowlist them in {}:\n{}",
+        file!(),
+        unstyled.join("\n")
+    );
+}
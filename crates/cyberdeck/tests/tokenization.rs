@@ -0,0 +1,64 @@
+//! Golden tests for how the Rust demo tokenizes and what each token
+//! resolves to under the primary theme.
+//!
+//! A grammar bump or a theme edit that changes highlighting fails here until
+//! the snapshot is reviewed and accepted with `cargo insta review` (or by
+//! rerunning with `INSTA_UPDATE=always`).
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use cyberdeck::highlight::{Grammars, Styler};
+use cyberdeck::scope::Resolver;
+use cyberdeck::theme::Theme;
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+/// One line per token that isn't whitespace: its position, text, scope
+/// stack and resolved foreground and font style.
+fn triples(demo: &str, theme: &str) -> String {
+    let theme = Theme::load(&root().join(theme)).unwrap();
+    let document = Grammars::bundled().load(&root().join(demo)).unwrap();
+    let resolver = Resolver::new(&theme);
+    let mut styler = Styler::new(&resolver);
+    let mut out = String::new();
+    for (index, line) in document.lines.iter().enumerate() {
+        let mut column = 1;
+        for token in line {
+            let width = token.text.chars().count();
+            if !token.text.trim().is_empty() {
+                let style = styler.style(token);
+                let _ = writeln!(
+                    out,
+                    "{}:{column} {:?} [{}] {}{}",
+                    index + 1,
+                    token.text,
+                    token.scopes.join(" "),
+                    style
+                        .foreground
+                        .map_or_else(|| "default".to_owned(), |c| c.to_string()),
+                    style
+                        .font_style
+                        .as_deref()
+                        .filter(|s| !s.is_empty())
+                        .map_or_else(String::new, |s| format!(" {s}")),
+                );
+            }
+            column += width;
+        }
+    }
+    out
+}
+
+#[test]
+fn rust_demo_tokens() {
+    insta::assert_snapshot!(
+        "rust_demo_tokens",
+        triples(
+            "language_demos/rust_demo.rs",
+            "themes/Cyberdeck-2025-color-theme.json"
+        )
+    );
+}
@@ -0,0 +1,144 @@
+//! WebAssembly bindings for the theme playground: the same tokenizer, scope
+//! resolver and renderer the `cyberdeck` tool uses, driven from the page
+//! in `www/`.
+//!
+//! Built by `cyberdeck playground build`, which compiles this crate for
+//! `wasm32-unknown-unknown` and generates the JavaScript glue with
+//! `wasm-bindgen`.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use cyberdeck::color::{Color, Oklch};
+use cyberdeck::derive::derive_variant;
+use cyberdeck::export::theme_name;
+use cyberdeck::highlight::Grammars;
+use cyberdeck::render::gallery::spans_html;
+use cyberdeck::render::Highlighted;
+use cyberdeck::roles::resolve_roles;
+use cyberdeck::theme::Theme;
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+/// How far the sliders move the palette, applied to every role color.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct Adjustment {
+    /// Hue rotation in degrees.
+    pub hue: f64,
+    /// Chroma multiplier; 1 leaves colors as they are.
+    pub chroma: f64,
+    /// OKLab lightness offset.
+    pub lightness: f64,
+}
+
+#[wasm_bindgen]
+impl Adjustment {
+    #[wasm_bindgen(constructor)]
+    pub fn new(hue: f64, chroma: f64, lightness: f64) -> Adjustment {
+        Adjustment {
+            hue,
+            chroma,
+            lightness,
+        }
+    }
+
+    fn is_identity(self) -> bool {
+        self.hue == 0.0 && self.chroma == 1.0 && self.lightness == 0.0
+    }
+
+    fn apply(self, color: Color) -> Color {
+        let lch = color.to_oklch();
+        Color::from_oklch(Oklch {
+            l: (lch.l + self.lightness).clamp(0.0, 1.0),
+            c: (lch.c * self.chroma).max(0.0),
+            h: (lch.h + self.hue).rem_euclid(360.0),
+        })
+        .with_alpha(color.a)
+    }
+}
+
+#[wasm_bindgen]
+pub struct Playground {
+    grammars: Grammars,
+    themes: Vec<Theme>,
+}
+
+#[wasm_bindgen]
+impl Playground {
+    /// Takes the variants as a JSON array of VS Code color themes.
+    #[wasm_bindgen(constructor)]
+    pub fn new(themes: &str) -> Result<Playground, JsError> {
+        let values: Vec<Value> = serde_json::from_str(themes)?;
+        let themes = values
+            .iter()
+            .map(|value| Theme::parse(&value.to_string()))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|err| JsError::new(&format!("{err:#}")))?;
+        if themes.is_empty() {
+            return Err(JsError::new("the playground needs at least one theme"));
+        }
+        Ok(Playground {
+            grammars: Grammars::bundled(),
+            themes,
+        })
+    }
+
+    /// The variant names, in order.
+    pub fn variants(&self) -> Vec<String> {
+        self.themes
+            .iter()
+            .map(|t| theme_name(t).to_owned())
+            .collect()
+    }
+
+    /// Highlights `code` as the contents of `file_name` with a variant whose
+    /// palette is adjusted, as JSON: the `html` of the code, the editor
+    /// `background` and `foreground`, and the adjusted `roles`.
+    pub fn render(
+        &self,
+        variant: usize,
+        file_name: &str,
+        code: &str,
+        adjustment: &Adjustment,
+    ) -> Result<String, JsError> {
+        let base = self
+            .themes
+            .get(variant)
+            .ok_or_else(|| JsError::new("no such variant"))?;
+        let adjusted;
+        let theme = if adjustment.is_identity() {
+            base
+        } else {
+            let roles: IndexMap<&str, Color> = resolve_roles(base)
+                .into_iter()
+                .map(|(role, color)| (role, adjustment.apply(color)))
+                .collect();
+            adjusted = derive_variant(base, theme_name(base), &roles);
+            &adjusted
+        };
+
+        let document = self
+            .grammars
+            .parse(Path::new(file_name), code)
+            .map_err(|err| JsError::new(&format!("{err:#}")))?;
+        let highlighted = Highlighted::new(theme, &document);
+        let mut html = String::new();
+        for line in &highlighted.lines {
+            let _ = writeln!(html, "{}", spans_html(line, highlighted.foreground));
+        }
+        let roles: Vec<Value> = resolve_roles(theme)
+            .into_iter()
+            .map(|(role, color)| json!({ "role": role, "color": color.to_string() }))
+            .collect();
+        Ok(json!({
+            "html": html,
+            "language": document.language.as_deref().unwrap_or("Plain Text"),
+            "background": highlighted.background.to_string(),
+            "foreground": highlighted.foreground.to_string(),
+            "roles": roles,
+        })
+        .to_string())
+    }
+}
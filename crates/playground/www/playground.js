@@ -0,0 +1,94 @@
+// Drives the playground page: loads the WebAssembly renderer built from
+// crates/playground, then re-renders the code on every edit.
+
+import init, { Adjustment, Playground } from "./pkg/cyberdeck_playground.js";
+
+const $ = (id) => document.getElementById(id);
+
+async function main() {
+  await init();
+  const [themes, samples] = await Promise.all([
+    fetch("themes.json").then((r) => r.text()),
+    fetch("samples.json").then((r) => r.json()),
+  ]);
+  const playground = new Playground(themes);
+
+  playground.variants().forEach((name, index) => {
+    $("variant").add(new Option(name, index));
+  });
+  for (const name of Object.keys(samples)) {
+    $("sample").add(new Option(name, name));
+  }
+  const loadSample = () => {
+    const name = $("sample").value;
+    if (name) {
+      $("file-name").value = name;
+      $("code").value = samples[name];
+    }
+  };
+  loadSample();
+
+  const sliders = ["hue", "chroma", "lightness"];
+  let pending = false;
+  const render = () => {
+    pending = false;
+    for (const id of sliders) {
+      $(id).previousElementSibling.value = $(id).value;
+    }
+    const adjustment = new Adjustment(
+      Number($("hue").value),
+      Number($("chroma").value),
+      Number($("lightness").value),
+    );
+    try {
+      const result = JSON.parse(
+        playground.render(Number($("variant").value), $("file-name").value, $("code").value, adjustment),
+      );
+      const preview = $("preview");
+      preview.innerHTML = result.html;
+      preview.style.background = result.background;
+      preview.style.color = result.foreground;
+      $("language").textContent = result.language;
+      $("roles").replaceChildren(
+        ...result.roles.map(({ role, color }) => {
+          const item = document.createElement("li");
+          const swatch = document.createElement("i");
+          swatch.style.background = color;
+          item.append(swatch, `${role} ${color}`);
+          return item;
+        }),
+      );
+      $("error").textContent = "";
+    } catch (error) {
+      $("error").textContent = String(error);
+    } finally {
+      adjustment.free();
+    }
+  };
+  // Coalesce bursts of slider and key events into one render per frame.
+  const schedule = () => {
+    if (!pending) {
+      pending = true;
+      requestAnimationFrame(render);
+    }
+  };
+
+  $("sample").addEventListener("change", () => {
+    loadSample();
+    schedule();
+  });
+  for (const id of ["variant", "file-name", "code", ...sliders]) {
+    $(id).addEventListener("input", schedule);
+  }
+  $("reset").addEventListener("click", () => {
+    $("hue").value = 0;
+    $("chroma").value = 1;
+    $("lightness").value = 0;
+    schedule();
+  });
+  render();
+}
+
+main().catch((error) => {
+  $("error").textContent = `Failed to start: ${error}`;
+});
@@ -0,0 +1,22 @@
+async def fetch(url: str, delay: float) -> str:
+    await asyncio.sleep(delay)
+    return f"{url}: 200 OK"
+
+
+async def stream(limit: int):
+    for index in range(limit):
+        await asyncio.sleep(0)
+        yield index
+
+
+async def demonstrate_async() -> None:
+    print("-- Concurrency --")
+    results = await asyncio.gather(
+        fetch("https://night.city", 0.01),
+        fetch("https://chiba.city", 0.02),
+    )
+    async for value in stream(3):
+        print("streamed", value)
+    async with asyncio.timeout(1):
+        print(results)
+    print()
@@ -0,0 +1,60 @@
+class Color(Enum):
+    MAGENTA = auto()
+    CYAN = auto()
+    YELLOW = auto()
+
+
+class Shape:
+    """Base class for shapes."""
+
+    sides: int = 0
+
+    def __init__(self, name: str) -> None:
+        self.name = name
+        self._secret = "hidden"
+
+    def area(self) -> float:
+        raise NotImplementedError
+
+    def __repr__(self) -> str:
+        return f"{type(self).__name__}({self.name!r})"
+
+    @property
+    def description(self) -> str:
+        return f"{self.name} with {self.sides} sides"
+
+    @classmethod
+    def unit(cls) -> Shape:
+        return cls("unit")
+
+    @staticmethod
+    def validate(value: float) -> bool:
+        return value >= 0
+
+
+class Rectangle(Shape):
+    sides = 4
+
+    def __init__(self, width: float, height: float) -> None:
+        super().__init__("rectangle")
+        self.width = width
+        self.height = height
+
+    def area(self) -> float:
+        return self.width * self.height
+
+
+@dataclass(frozen=True)
+class Employee:
+    name: str
+    role: str = "netrunner"
+    skills: list[str] = field(default_factory=list)
+
+
+def demonstrate_classes() -> None:
+    print("-- Classes --")
+    rect = Rectangle(3, 4)
+    employee = Employee("Case", skills=["ice", "decks"])
+    print(rect, rect.area(), rect.description, Shape.validate(-1))
+    print(employee, Color.CYAN.name, Color["YELLOW"].value)
+    print()
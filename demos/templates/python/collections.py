@@ -0,0 +1,24 @@
+def demonstrate_collections() -> None:
+    print("-- Collections --")
+
+    numbers = [1, 2, 3, 4, 5]
+    coordinates = (10.5, -3.2)
+    unique = {1, 2, 3}
+    frozen = frozenset(unique)
+    ages = {"case": 27, "molly": 25}
+
+    squares = [n * n for n in numbers if n % 2 == 1]
+    lookup = {name: age for name, age in ages.items()}
+    evens = {n for n in range(10) if not n & 1}
+    lazy = (n ** 2 for n in numbers)
+
+    grouped: defaultdict[str, list[int]] = defaultdict(list)
+    for n in numbers:
+        grouped["even" if n % 2 == 0 else "odd"].append(n)
+
+    Point = namedtuple("Point", ["x", "y"])
+    origin = Point(0, 0)
+
+    print(numbers[1:-1], numbers[::-1], coordinates, frozen)
+    print(squares, lookup, evens, sum(lazy), dict(grouped), origin.x)
+    print()
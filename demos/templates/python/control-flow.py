@@ -0,0 +1,36 @@
+def demonstrate_control_flow(value: int) -> str:
+    print("-- Control Flow --")
+
+    if value < 0:
+        kind = "negative"
+    elif value == 0:
+        kind = "zero"
+    else:
+        kind = "positive"
+
+    for index, item in enumerate(["alpha", "beta", "gamma"]):
+        if index == 1:
+            continue
+        print(index, item)
+    else:
+        print("loop finished without break")
+
+    countdown = 3
+    while countdown > 0:
+        countdown -= 1
+        if countdown == 1:
+            break
+
+    match value:
+        case 0 | 1:
+            label = "small"
+        case int(n) if n > 100:
+            label = "huge"
+        case _:
+            label = "other"
+
+    result = "even" if value % 2 == 0 else "odd"
+    assert isinstance(result, str), "result must be a string"
+    print(kind, label, result)
+    print()
+    return kind
@@ -0,0 +1,43 @@
+def logged(func: Callable[..., T]) -> Callable[..., T]:
+    @functools.wraps(func)
+    def wrapper(*args, **kwargs):
+        print(f"calling {func.__name__}")
+        return func(*args, **kwargs)
+
+    return wrapper
+
+
+def repeat(times: int):
+    def decorator(func):
+        @functools.wraps(func)
+        def wrapper(*args, **kwargs):
+            return [func(*args, **kwargs) for _ in range(times)]
+
+        return wrapper
+
+    return decorator
+
+
+@logged
+@repeat(times=2)
+def greet(name: str) -> str:
+    return f"wake up, {name}"
+
+
+class Registry(type):
+    classes: dict[str, type] = {}
+
+    def __new__(mcs, name, bases, namespace):
+        cls = super().__new__(mcs, name, bases, namespace)
+        mcs.classes[name] = cls
+        return cls
+
+
+class Plugin(metaclass=Registry):
+    pass
+
+
+def demonstrate_decorators() -> None:
+    print("-- Decorators --")
+    print(greet("Case"), list(Registry.classes), getattr(Plugin, "__name__"))
+    print()
@@ -0,0 +1,41 @@
+class NetworkError(Exception):
+    """Raised when the matrix connection drops."""
+
+    def __init__(self, host: str, code: int) -> None:
+        super().__init__(f"{host} failed with {code}")
+        self.code = code
+
+
+def connect(host: str) -> str:
+    if not host:
+        raise ValueError("host must not be empty")
+    if host.endswith(".ice"):
+        raise NetworkError(host, 503)
+    return f"connected to {host}"
+
+
+def demonstrate_errors() -> None:
+    print("-- Error Handling --")
+    for host in ["", "corp.ice", "chiba.city"]:
+        try:
+            print(connect(host))
+        except ValueError as error:
+            print("invalid:", error)
+        except NetworkError as error:
+            print("network:", error.code)
+        else:
+            print("no errors")
+        finally:
+            print("attempted", repr(host))
+
+    try:
+        connect("")
+    except ValueError as error:
+        try:
+            raise RuntimeError("wrapped") from error
+        except RuntimeError as wrapped:
+            print(wrapped, "caused by", wrapped.__cause__)
+
+    with open(__file__, encoding="utf-8") as source:
+        print(len(source.readline()))
+    print()
@@ -0,0 +1,40 @@
+def add(a: int, b: int = 0) -> int:
+    """Return the sum of two integers."""
+    return a + b
+
+
+def variadic(*args: int, **kwargs: str) -> None:
+    print(args, kwargs)
+
+
+def keyword_only(value: int, /, *, scale: float = 1.0) -> float:
+    return value * scale
+
+
+def make_counter() -> Callable[[], int]:
+    count = 0
+
+    def increment() -> int:
+        nonlocal count
+        count += 1
+        return count
+
+    return increment
+
+
+def fibonacci(limit: int) -> Iterator[int]:
+    a, b = 0, 1
+    while a < limit:
+        yield a
+        a, b = b, a + b
+
+
+def demonstrate_functions() -> None:
+    print("-- Functions --")
+    square = lambda x: x * x
+    counter = make_counter()
+    counter()
+    variadic(1, 2, 3, mode="fast")
+    print(add(2, b=3), keyword_only(4, scale=0.5), square(7), counter())
+    print(list(fibonacci(50)), list(map(square, range(4))))
+    print()
@@ -0,0 +1,9 @@
+from __future__ import annotations
+
+import asyncio
+import functools
+import re
+from collections import defaultdict, namedtuple
+from dataclasses import dataclass, field
+from enum import Enum, auto
+from typing import Callable, Generic, Iterator, Protocol, TypeVar
@@ -0,0 +1,20 @@
+def main() -> None:
+    print("=== Python Language Demonstration ===")
+    print()
+
+    demonstrate_variables_and_types()
+    demonstrate_strings()
+    demonstrate_collections()
+    demonstrate_control_flow(7)
+    demonstrate_functions()
+    demonstrate_classes()
+    demonstrate_types()
+    demonstrate_errors()
+    demonstrate_decorators()
+    asyncio.run(demonstrate_async())
+
+    print("=== End of Python Demonstration ===")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,21 @@
+def demonstrate_strings() -> None:
+    print("-- Strings --")
+
+    name = "Cyberdeck"
+    single = 'single quotes'
+    escaped = "tab:\t newline:\n quote:\" unicode:\u2603"
+    raw = r"C:\path\to\file"
+    multiline = """A triple-quoted
+    string spanning lines"""
+    formatted = f"{name!r} has {len(name):>4} chars, pi is {3.14159:.2f}"
+    old_style = "%s is %d years old" % ("Case", 27)
+    byte_string = b"\x00\xff raw bytes"
+
+    pattern = re.compile(r"(?P<word>\w+)@(\d{2,})")
+    match = pattern.search("neon@2077")
+    if match:
+        print(match.group("word"), match.group(2))
+
+    print(single, escaped, raw, multiline, formatted, old_style, byte_string)
+    print(" | ".join(part.upper() for part in name.split("d")))
+    print()
@@ -0,0 +1,36 @@
+T = TypeVar("T")
+
+
+class Comparable(Protocol):
+    def __lt__(self, other: object) -> bool: ...
+
+
+class Stack(Generic[T]):
+    def __init__(self) -> None:
+        self._items: list[T] = []
+
+    def push(self, item: T) -> None:
+        self._items.append(item)
+
+    def pop(self) -> T | None:
+        return self._items.pop() if self._items else None
+
+    def __len__(self) -> int:
+        return len(self._items)
+
+
+def largest[K: Comparable](items: list[K]) -> K:
+    return max(items)
+
+
+type Matrix = list[list[float]]
+
+
+def demonstrate_types() -> None:
+    print("-- Types --")
+    stack: Stack[int] = Stack()
+    stack.push(1)
+    stack.push(2)
+    grid: Matrix = [[1.0, 0.0], [0.0, 1.0]]
+    print(stack.pop(), len(stack), largest([3, 9, 4]), grid)
+    print()
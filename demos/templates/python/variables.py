@@ -0,0 +1,28 @@
+def demonstrate_variables_and_types() -> None:
+    print("-- Variables and Types --")
+
+    message = "Hello, Python!"
+    count = 42
+    pi = 3.14159
+    big = 1_000_000
+    hexadecimal = 0xFF
+    binary = 0b1010
+    complex_number = 2 + 3j
+    is_true = True
+    nothing = None
+
+    # Multiple assignment and unpacking
+    x, y, *rest = 1, 2, 3, 4
+    first, second = "a", "b"
+    first, second = second, first
+
+    # Augmented assignment
+    count += 5
+    count //= 2
+
+    # Constants by convention
+    GRAVITY: float = 9.81
+
+    print(message, count, pi, big, hexadecimal, binary, complex_number)
+    print(is_true, nothing, x, y, rest, first, second, GRAVITY)
+    print()
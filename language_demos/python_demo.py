@@ -0,0 +1,440 @@
+# Python Demo
+# This file demonstrates various Python language features and idioms
+# Generated by `cyberdeck demos generate` from demos/matrix.toml;
+# edit demos/templates/python/ instead.
+
+from __future__ import annotations
+
+import asyncio
+import functools
+import re
+from collections import defaultdict, namedtuple
+from dataclasses import dataclass, field
+from enum import Enum, auto
+from typing import Callable, Generic, Iterator, Protocol, TypeVar
+
+# ============================================================================
+# VARIABLES AND BASIC TYPES
+# ============================================================================
+
+def demonstrate_variables_and_types() -> None:
+    print("-- Variables and Types --")
+
+    message = "Hello, Python!"
+    count = 42
+    pi = 3.14159
+    big = 1_000_000
+    hexadecimal = 0xFF
+    binary = 0b1010
+    complex_number = 2 + 3j
+    is_true = True
+    nothing = None
+
+    # Multiple assignment and unpacking
+    x, y, *rest = 1, 2, 3, 4
+    first, second = "a", "b"
+    first, second = second, first
+
+    # Augmented assignment
+    count += 5
+    count //= 2
+
+    # Constants by convention
+    GRAVITY: float = 9.81
+
+    print(message, count, pi, big, hexadecimal, binary, complex_number)
+    print(is_true, nothing, x, y, rest, first, second, GRAVITY)
+    print()
+
+# ============================================================================
+# STRINGS AND FORMATTING
+# ============================================================================
+
+def demonstrate_strings() -> None:
+    print("-- Strings --")
+
+    name = "Cyberdeck"
+    single = 'single quotes'
+    escaped = "tab:\t newline:\n quote:\" unicode:\u2603"
+    raw = r"C:\path\to\file"
+    multiline = """A triple-quoted
+    string spanning lines"""
+    formatted = f"{name!r} has {len(name):>4} chars, pi is {3.14159:.2f}"
+    old_style = "%s is %d years old" % ("Case", 27)
+    byte_string = b"\x00\xff raw bytes"
+
+    pattern = re.compile(r"(?P<word>\w+)@(\d{2,})")
+    match = pattern.search("neon@2077")
+    if match:
+        print(match.group("word"), match.group(2))
+
+    print(single, escaped, raw, multiline, formatted, old_style, byte_string)
+    print(" | ".join(part.upper() for part in name.split("d")))
+    print()
+
+# ============================================================================
+# COLLECTIONS
+# ============================================================================
+
+def demonstrate_collections() -> None:
+    print("-- Collections --")
+
+    numbers = [1, 2, 3, 4, 5]
+    coordinates = (10.5, -3.2)
+    unique = {1, 2, 3}
+    frozen = frozenset(unique)
+    ages = {"case": 27, "molly": 25}
+
+    squares = [n * n for n in numbers if n % 2 == 1]
+    lookup = {name: age for name, age in ages.items()}
+    evens = {n for n in range(10) if not n & 1}
+    lazy = (n ** 2 for n in numbers)
+
+    grouped: defaultdict[str, list[int]] = defaultdict(list)
+    for n in numbers:
+        grouped["even" if n % 2 == 0 else "odd"].append(n)
+
+    Point = namedtuple("Point", ["x", "y"])
+    origin = Point(0, 0)
+
+    print(numbers[1:-1], numbers[::-1], coordinates, frozen)
+    print(squares, lookup, evens, sum(lazy), dict(grouped), origin.x)
+    print()
+
+# ============================================================================
+# CONTROL FLOW
+# ============================================================================
+
+def demonstrate_control_flow(value: int) -> str:
+    print("-- Control Flow --")
+
+    if value < 0:
+        kind = "negative"
+    elif value == 0:
+        kind = "zero"
+    else:
+        kind = "positive"
+
+    for index, item in enumerate(["alpha", "beta", "gamma"]):
+        if index == 1:
+            continue
+        print(index, item)
+    else:
+        print("loop finished without break")
+
+    countdown = 3
+    while countdown > 0:
+        countdown -= 1
+        if countdown == 1:
+            break
+
+    match value:
+        case 0 | 1:
+            label = "small"
+        case int(n) if n > 100:
+            label = "huge"
+        case _:
+            label = "other"
+
+    result = "even" if value % 2 == 0 else "odd"
+    assert isinstance(result, str), "result must be a string"
+    print(kind, label, result)
+    print()
+    return kind
+
+# ============================================================================
+# FUNCTIONS
+# ============================================================================
+
+def add(a: int, b: int = 0) -> int:
+    """Return the sum of two integers."""
+    return a + b
+
+
+def variadic(*args: int, **kwargs: str) -> None:
+    print(args, kwargs)
+
+
+def keyword_only(value: int, /, *, scale: float = 1.0) -> float:
+    return value * scale
+
+
+def make_counter() -> Callable[[], int]:
+    count = 0
+
+    def increment() -> int:
+        nonlocal count
+        count += 1
+        return count
+
+    return increment
+
+
+def fibonacci(limit: int) -> Iterator[int]:
+    a, b = 0, 1
+    while a < limit:
+        yield a
+        a, b = b, a + b
+
+
+def demonstrate_functions() -> None:
+    print("-- Functions --")
+    square = lambda x: x * x
+    counter = make_counter()
+    counter()
+    variadic(1, 2, 3, mode="fast")
+    print(add(2, b=3), keyword_only(4, scale=0.5), square(7), counter())
+    print(list(fibonacci(50)), list(map(square, range(4))))
+    print()
+
+# ============================================================================
+# CLASSES AND INHERITANCE
+# ============================================================================
+
+class Color(Enum):
+    MAGENTA = auto()
+    CYAN = auto()
+    YELLOW = auto()
+
+
+class Shape:
+    """Base class for shapes."""
+
+    sides: int = 0
+
+    def __init__(self, name: str) -> None:
+        self.name = name
+        self._secret = "hidden"
+
+    def area(self) -> float:
+        raise NotImplementedError
+
+    def __repr__(self) -> str:
+        return f"{type(self).__name__}({self.name!r})"
+
+    @property
+    def description(self) -> str:
+        return f"{self.name} with {self.sides} sides"
+
+    @classmethod
+    def unit(cls) -> Shape:
+        return cls("unit")
+
+    @staticmethod
+    def validate(value: float) -> bool:
+        return value >= 0
+
+
+class Rectangle(Shape):
+    sides = 4
+
+    def __init__(self, width: float, height: float) -> None:
+        super().__init__("rectangle")
+        self.width = width
+        self.height = height
+
+    def area(self) -> float:
+        return self.width * self.height
+
+
+@dataclass(frozen=True)
+class Employee:
+    name: str
+    role: str = "netrunner"
+    skills: list[str] = field(default_factory=list)
+
+
+def demonstrate_classes() -> None:
+    print("-- Classes --")
+    rect = Rectangle(3, 4)
+    employee = Employee("Case", skills=["ice", "decks"])
+    print(rect, rect.area(), rect.description, Shape.validate(-1))
+    print(employee, Color.CYAN.name, Color["YELLOW"].value)
+    print()
+
+# ============================================================================
+# TYPE ANNOTATIONS AND GENERICS
+# ============================================================================
+
+T = TypeVar("T")
+
+
+class Comparable(Protocol):
+    def __lt__(self, other: object) -> bool: ...
+
+
+class Stack(Generic[T]):
+    def __init__(self) -> None:
+        self._items: list[T] = []
+
+    def push(self, item: T) -> None:
+        self._items.append(item)
+
+    def pop(self) -> T | None:
+        return self._items.pop() if self._items else None
+
+    def __len__(self) -> int:
+        return len(self._items)
+
+
+def largest[K: Comparable](items: list[K]) -> K:
+    return max(items)
+
+
+type Matrix = list[list[float]]
+
+
+def demonstrate_types() -> None:
+    print("-- Types --")
+    stack: Stack[int] = Stack()
+    stack.push(1)
+    stack.push(2)
+    grid: Matrix = [[1.0, 0.0], [0.0, 1.0]]
+    print(stack.pop(), len(stack), largest([3, 9, 4]), grid)
+    print()
+
+# ============================================================================
+# ERROR HANDLING
+# ============================================================================
+
+class NetworkError(Exception):
+    """Raised when the matrix connection drops."""
+
+    def __init__(self, host: str, code: int) -> None:
+        super().__init__(f"{host} failed with {code}")
+        self.code = code
+
+
+def connect(host: str) -> str:
+    if not host:
+        raise ValueError("host must not be empty")
+    if host.endswith(".ice"):
+        raise NetworkError(host, 503)
+    return f"connected to {host}"
+
+
+def demonstrate_errors() -> None:
+    print("-- Error Handling --")
+    for host in ["", "corp.ice", "chiba.city"]:
+        try:
+            print(connect(host))
+        except ValueError as error:
+            print("invalid:", error)
+        except NetworkError as error:
+            print("network:", error.code)
+        else:
+            print("no errors")
+        finally:
+            print("attempted", repr(host))
+
+    try:
+        connect("")
+    except ValueError as error:
+        try:
+            raise RuntimeError("wrapped") from error
+        except RuntimeError as wrapped:
+            print(wrapped, "caused by", wrapped.__cause__)
+
+    with open(__file__, encoding="utf-8") as source:
+        print(len(source.readline()))
+    print()
+
+# ============================================================================
+# DECORATORS AND METAPROGRAMMING
+# ============================================================================
+
+def logged(func: Callable[..., T]) -> Callable[..., T]:
+    @functools.wraps(func)
+    def wrapper(*args, **kwargs):
+        print(f"calling {func.__name__}")
+        return func(*args, **kwargs)
+
+    return wrapper
+
+
+def repeat(times: int):
+    def decorator(func):
+        @functools.wraps(func)
+        def wrapper(*args, **kwargs):
+            return [func(*args, **kwargs) for _ in range(times)]
+
+        return wrapper
+
+    return decorator
+
+
+@logged
+@repeat(times=2)
+def greet(name: str) -> str:
+    return f"wake up, {name}"
+
+
+class Registry(type):
+    classes: dict[str, type] = {}
+
+    def __new__(mcs, name, bases, namespace):
+        cls = super().__new__(mcs, name, bases, namespace)
+        mcs.classes[name] = cls
+        return cls
+
+
+class Plugin(metaclass=Registry):
+    pass
+
+
+def demonstrate_decorators() -> None:
+    print("-- Decorators --")
+    print(greet("Case"), list(Registry.classes), getattr(Plugin, "__name__"))
+    print()
+
+# ============================================================================
+# CONCURRENCY
+# ============================================================================
+
+async def fetch(url: str, delay: float) -> str:
+    await asyncio.sleep(delay)
+    return f"{url}: 200 OK"
+
+
+async def stream(limit: int):
+    for index in range(limit):
+        await asyncio.sleep(0)
+        yield index
+
+
+async def demonstrate_async() -> None:
+    print("-- Concurrency --")
+    results = await asyncio.gather(
+        fetch("https://night.city", 0.01),
+        fetch("https://chiba.city", 0.02),
+    )
+    async for value in stream(3):
+        print("streamed", value)
+    async with asyncio.timeout(1):
+        print(results)
+    print()
+
+# ============================================================================
+# MAIN FUNCTION
+# ============================================================================
+
+def main() -> None:
+    print("=== Python Language Demonstration ===")
+    print()
+
+    demonstrate_variables_and_types()
+    demonstrate_strings()
+    demonstrate_collections()
+    demonstrate_control_flow(7)
+    demonstrate_functions()
+    demonstrate_classes()
+    demonstrate_types()
+    demonstrate_errors()
+    demonstrate_decorators()
+    asyncio.run(demonstrate_async())
+
+    print("=== End of Python Demonstration ===")
+
+
+if __name__ == "__main__":
+    main()
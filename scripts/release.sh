@@ -59,43 +59,16 @@ fi
 
 VERSION_TYPE=$1
 
-if [[ "$VERSION_TYPE" =~ ^[0-9]+\.[0-9]+\.[0-9]+$ ]]; then
-    # Specific version provided
-    NEW_VERSION=$VERSION_TYPE
-    print_status "Setting version to: $NEW_VERSION"
-    npm version $NEW_VERSION --no-git-tag-version
-else
-    # Use npm version command
-    print_status "Bumping $VERSION_TYPE version..."
-    NEW_VERSION=$(npm version $VERSION_TYPE --no-git-tag-version | sed 's/^v//')
-fi
+# Bump every versioned file, derive the CHANGELOG.md section from the theme
+# diff since the last tag, then commit and tag
+print_status "Bumping $VERSION_TYPE version..."
+cargo run -q -p cyberdeck -- bump "$VERSION_TYPE"
+NEW_VERSION=$(node -p "require('./package.json').version")
 
 print_success "Version updated to: $NEW_VERSION"
 
-# Prompt for changelog update
-print_warning "Please update CHANGELOG.md with the changes for version $NEW_VERSION"
-print_status "Press Enter when you've updated the changelog, or Ctrl+C to cancel..."
-read -r
-
-# Verify changelog was updated
-if ! grep -q "\[$NEW_VERSION\]" CHANGELOG.md; then
-    print_warning "Version $NEW_VERSION not found in CHANGELOG.md"
-    print_status "Continue anyway? (y/N)"
-    read -r response
-    if [[ ! "$response" =~ ^[Yy]$ ]]; then
-        print_error "Release cancelled. Please update CHANGELOG.md"
-        exit 1
-    fi
-fi
-
-# Commit changes
-print_status "Committing version bump..."
-git add package.json CHANGELOG.md
-git commit -m "chore: bump version to $NEW_VERSION"
-
-# Create and push tag
-print_status "Creating and pushing tag: $NEW_VERSION"
-git tag $NEW_VERSION
+# Push commit and tag
+print_status "Pushing tag: $NEW_VERSION"
 git push origin main
 git push origin $NEW_VERSION
 